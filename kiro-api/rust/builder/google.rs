@@ -37,12 +37,12 @@ pub fn build_google_protos(
                 .unwrap(),
             proto_dir
                 .join("google/google/protobuf")
-                .join("descriptor.proto")
+                .join("empty.proto")
                 .to_str()
                 .unwrap(),
             proto_dir
                 .join("google/google/protobuf")
-                .join("empty.proto")
+                .join("field_mask.proto")
                 .to_str()
                 .unwrap(),
             proto_dir
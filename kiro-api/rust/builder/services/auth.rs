@@ -32,6 +32,26 @@ pub fn build_auth_service(
             r#"#[derive(utoipa::IntoParams)]
             #[into_params(parameter_in = Query)]"#,
         )
+        .type_attribute(
+            ".auth.v1.ReactivateAccountRequest",
+            r#"#[derive(utoipa::IntoParams)]
+            #[into_params(parameter_in = Query)]"#,
+        )
+        .type_attribute(
+            ".auth.v1.ConfirmEmailChangeRequest",
+            r#"#[derive(utoipa::IntoParams)]
+            #[into_params(parameter_in = Query)]"#,
+        )
+        .type_attribute(
+            ".auth.v1.CancelEmailChangeRequest",
+            r#"#[derive(utoipa::IntoParams)]
+            #[into_params(parameter_in = Query)]"#,
+        )
+        .type_attribute(
+            ".auth.v1.VerifyEmailRequest",
+            r#"#[derive(utoipa::IntoParams)]
+            #[into_params(parameter_in = Query)]"#,
+        )
         .extern_path(".google.protobuf", "crate::google::protobuf");
 
     builder.compile_protos(
@@ -27,6 +27,41 @@ pub fn build_client_service(
         .file_descriptor_set_path(out_dir.join("client").join("proto_descriptor_v1.bin"))
         .compile_well_known_types(config.compile_well_known_types)
         .type_attribute(".", "#[derive(utoipa::ToSchema)]")
+        .type_attribute(
+            ".client.v1.AcceptPolicyRequest",
+            r#"#[derive(utoipa::IntoParams)]
+            #[into_params(parameter_in = Query)]"#,
+        )
+        .type_attribute(
+            ".client.v1.AddEmailRequest",
+            r#"#[derive(utoipa::IntoParams)]
+            #[into_params(parameter_in = Query)]"#,
+        )
+        .type_attribute(
+            ".client.v1.RemoveEmailRequest",
+            r#"#[derive(utoipa::IntoParams)]
+            #[into_params(parameter_in = Query)]"#,
+        )
+        .type_attribute(
+            ".client.v1.SetPrimaryEmailRequest",
+            r#"#[derive(utoipa::IntoParams)]
+            #[into_params(parameter_in = Query)]"#,
+        )
+        .type_attribute(
+            ".client.v1.CreateInvitationRequest",
+            r#"#[derive(utoipa::IntoParams)]
+            #[into_params(parameter_in = Query)]"#,
+        )
+        .type_attribute(
+            ".client.v1.GetChangesSinceRequest",
+            r#"#[derive(utoipa::IntoParams)]
+            #[into_params(parameter_in = Query)]"#,
+        )
+        .type_attribute(
+            ".client.v1.ReactivateUserRequest",
+            r#"#[derive(utoipa::IntoParams)]
+            #[into_params(parameter_in = Query)]"#,
+        )
         .type_attribute(
             ".client.v1.UpdateEmailRequest",
             r#"#[derive(utoipa::IntoParams)]
@@ -52,11 +87,22 @@ pub fn build_client_service(
             r#"#[derive(utoipa::IntoParams)]
             #[into_params(parameter_in = Query)]"#,
         )
+        .type_attribute(
+            ".client.v1.UpdateProfileRequest",
+            r#"#[derive(utoipa::IntoParams)]
+            #[into_params(parameter_in = Query)]"#,
+        )
+        .type_attribute(
+            ".client.v1.UpdateSettingsRequest",
+            r#"#[derive(utoipa::IntoParams)]
+            #[into_params(parameter_in = Query)]"#,
+        )
         .type_attribute(
             ".client.v1.UpdateThemeRequest",
             r#"#[derive(utoipa::IntoParams)]
             #[into_params(parameter_in = Query)]"#,
         )
+        .extern_path(".common", "crate::common")
         .extern_path(".google.protobuf", "crate::google::protobuf");
 
     builder.compile_protos(
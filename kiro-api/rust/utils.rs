@@ -25,14 +25,14 @@ pub fn get_out_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
     Ok(PathBuf::from(std::env::var("OUT_DIR")?))
 }
 
-/// Gets the proto directory path by appending "proto" to CARGO_MANIFEST_DIR
+/// Gets the proto directory path, the "proto" directory next to CARGO_MANIFEST_DIR
 ///
 /// # Returns
 /// - `Ok(PathBuf)` - The proto directory path
 /// - `Err(Box<dyn Error>)` - If CARGO_MANIFEST_DIR is not set or invalid
 pub fn get_proto_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
     let proto_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR")?);
-    Ok(proto_dir.join("proto"))
+    Ok(proto_dir.join("../proto"))
 }
 
 /// Creates a set of output directories for proto generation
@@ -95,7 +95,7 @@ mod tests {
         env::set_var("CARGO_MANIFEST_DIR", "/tmp/project");
         let result = get_proto_dir();
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), PathBuf::from("/tmp/project/proto"));
+        assert_eq!(result.unwrap(), PathBuf::from("/tmp/project/../proto"));
     }
 
     #[test]
@@ -0,0 +1,894 @@
+// backends/ldap.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use kiro_database::{
+    db_bridge::{Database, DatabaseOperations},
+    get_env_or, DbId,
+};
+use ldap3::{ldap_escape, Ldap, LdapConnAsync, LdapConnSettings, Scope, SearchEntry};
+use tonic::async_trait;
+
+use super::AuthBackend;
+use crate::{
+    error::{AuthBackendError, ClientError},
+    utils::password::random_password,
+    CreateUserModel, SessionModel, UserModel,
+};
+
+/// LDAP result code returned for a wrong DN or password
+const INVALID_CREDENTIALS: u32 = 49;
+
+/// # Directory Entry
+///
+/// User entry found in the directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectoryEntry {
+    /// Immutable identifier of the entry, the local account is bound to it
+    pub id: String,
+    /// Distinguished name used to bind as the user
+    pub dn: String,
+    /// Email address of the user
+    pub email: String,
+    /// Distinguished names of the groups of the user
+    pub groups: Vec<String>,
+}
+
+/// # Directory
+///
+/// Operations the LDAP backend needs from the directory server.
+#[async_trait]
+pub trait Directory: Send + Sync {
+    /// Searches the entry of a user with the service account
+    async fn find_user(&self, email: &str) -> Result<Option<DirectoryEntry>, AuthBackendError>;
+
+    /// Binds as the user, returns false when the password is rejected
+    async fn bind(&self, dn: &str, password: &str) -> Result<bool, AuthBackendError>;
+}
+
+/// # LDAP Config
+///
+/// Connection settings of the directory, read from the environment.
+#[derive(Debug, Clone)]
+pub struct LdapConfig {
+    /// `ldap://` or `ldaps://` URL of the server (`LDAP_URL`)
+    pub url: String,
+    /// Upgrade `ldap://` connections with StartTLS (`LDAP_STARTTLS`)
+    pub starttls: bool,
+    /// DN of the service account used to search (`LDAP_BIND_DN`)
+    pub bind_dn: String,
+    /// Password of the service account (`LDAP_BIND_PASSWORD`)
+    pub bind_password: String,
+    /// Base DN of the user search (`LDAP_BASE_DN`)
+    pub base_dn: String,
+    /// Search filter, `{email}` is replaced by the escaped email (`LDAP_USER_FILTER`)
+    pub user_filter: String,
+    /// Attribute holding the email address (`LDAP_EMAIL_ATTRIBUTE`)
+    pub email_attribute: String,
+    /// Immutable attribute identifying the entry (`LDAP_ID_ATTRIBUTE`), `entryUUID`
+    /// on OpenLDAP and `objectGUID` on Active Directory
+    pub id_attribute: String,
+    /// Attribute listing the groups of the user (`LDAP_GROUP_ATTRIBUTE`)
+    pub group_attribute: String,
+    /// Connection timeout in seconds (`LDAP_TIMEOUT`)
+    pub timeout: u64,
+}
+
+impl LdapConfig {
+    /// Reads the configuration from the environment
+    pub fn from_env() -> Self {
+        Self {
+            url: get_env_or("LDAP_URL", "ldap://localhost:389"),
+            starttls: get_env_or("LDAP_STARTTLS", "false") == "true",
+            bind_dn: get_env_or("LDAP_BIND_DN", ""),
+            bind_password: get_env_or("LDAP_BIND_PASSWORD", ""),
+            base_dn: get_env_or("LDAP_BASE_DN", "dc=example,dc=com"),
+            user_filter: get_env_or("LDAP_USER_FILTER", "(mail={email})"),
+            email_attribute: get_env_or("LDAP_EMAIL_ATTRIBUTE", "mail"),
+            id_attribute: get_env_or("LDAP_ID_ATTRIBUTE", "entryUUID"),
+            group_attribute: get_env_or("LDAP_GROUP_ATTRIBUTE", "memberOf"),
+            timeout: get_env_or("LDAP_TIMEOUT", "5").parse().unwrap_or(5),
+        }
+    }
+}
+
+/// # LDAP Directory
+///
+/// `Directory` implementation talking to an LDAP / Active Directory server.
+pub struct LdapDirectory {
+    config: LdapConfig,
+}
+
+impl LdapDirectory {
+    /// Creates a directory client from its configuration
+    pub fn new(config: LdapConfig) -> Self {
+        Self { config }
+    }
+
+    /// Opens a new connection to the server
+    async fn connect(&self) -> Result<Ldap, AuthBackendError> {
+        let settings = LdapConnSettings::new()
+            .set_conn_timeout(Duration::from_secs(self.config.timeout))
+            .set_starttls(self.config.starttls);
+
+        let (conn, ldap) = LdapConnAsync::with_settings(settings, &self.config.url)
+            .await
+            .map_err(|e| AuthBackendError::Unavailable(e.to_string()))?;
+
+        tokio::spawn(async move {
+            if let Err(_e) = conn.drive().await {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("🔐 LDAP connection error: {}", _e);
+            }
+        });
+
+        Ok(ldap)
+    }
+}
+
+#[async_trait]
+impl Directory for LdapDirectory {
+    async fn find_user(&self, email: &str) -> Result<Option<DirectoryEntry>, AuthBackendError> {
+        let mut ldap = self.connect().await?;
+
+        if !self.config.bind_dn.is_empty() {
+            ldap.simple_bind(&self.config.bind_dn, &self.config.bind_password)
+                .await
+                .and_then(|result| result.success())
+                .map_err(|e| AuthBackendError::Unavailable(e.to_string()))?;
+        }
+
+        let filter = self
+            .config
+            .user_filter
+            .replace("{email}", &ldap_escape(email));
+
+        let (entries, _) = ldap
+            .search(
+                &self.config.base_dn,
+                Scope::Subtree,
+                &filter,
+                vec![
+                    self.config.id_attribute.as_str(),
+                    self.config.email_attribute.as_str(),
+                    self.config.group_attribute.as_str(),
+                ],
+            )
+            .await
+            .and_then(|result| result.success())
+            .map_err(|e| AuthBackendError::Unavailable(e.to_string()))?;
+
+        let _ = ldap.unbind().await;
+
+        let Some(entry) = entries.into_iter().next() else {
+            return Ok(None);
+        };
+        let mut entry = SearchEntry::construct(entry);
+
+        // Binary identifiers such as objectGUID are kept hex encoded
+        let id = entry
+            .attrs
+            .get(&self.config.id_attribute)
+            .and_then(|values| values.first().cloned())
+            .or_else(|| {
+                entry
+                    .bin_attrs
+                    .get(&self.config.id_attribute)
+                    .and_then(|values| values.first())
+                    .map(|value| value.iter().map(|byte| format!("{:02x}", byte)).collect())
+            })
+            .ok_or_else(|| {
+                AuthBackendError::Provisioning(format!(
+                    "{} has no {} attribute",
+                    entry.dn, self.config.id_attribute
+                ))
+            })?;
+
+        Ok(Some(DirectoryEntry {
+            id,
+            email: entry
+                .attrs
+                .get(&self.config.email_attribute)
+                .and_then(|values| values.first().cloned())
+                .unwrap_or_else(|| email.to_string()),
+            groups: entry
+                .attrs
+                .remove(&self.config.group_attribute)
+                .unwrap_or_default(),
+            dn: entry.dn,
+        }))
+    }
+
+    async fn bind(&self, dn: &str, password: &str) -> Result<bool, AuthBackendError> {
+        // An empty password is an unauthenticated bind, which servers accept
+        if password.is_empty() {
+            return Ok(false);
+        }
+
+        let mut ldap = self.connect().await?;
+
+        let result = ldap
+            .simple_bind(dn, password)
+            .await
+            .map_err(|e| AuthBackendError::Unavailable(e.to_string()))?;
+
+        let _ = ldap.unbind().await;
+
+        match result.rc {
+            0 => Ok(true),
+            INVALID_CREDENTIALS => Ok(false),
+            _ => Err(AuthBackendError::Unavailable(result.text)),
+        }
+    }
+}
+
+/// # Role Mapping
+///
+/// Maps directory groups to the admin flag and the groups of the `UserModel`.
+#[derive(Debug, Clone, Default)]
+pub struct RoleMapping {
+    /// Group DNs granting `is_admin` (`LDAP_ADMIN_GROUPS`, `;` separated)
+    pub admin_groups: Vec<String>,
+    /// Group DN to group id pairs (`LDAP_GROUP_MAPPING`, `dn=>id` separated by `;`)
+    pub groups: Vec<(String, String)>,
+}
+
+impl RoleMapping {
+    /// Reads the mapping from the environment
+    pub fn from_env() -> Self {
+        Self::parse(
+            &get_env_or("LDAP_ADMIN_GROUPS", ""),
+            &get_env_or("LDAP_GROUP_MAPPING", ""),
+        )
+    }
+
+    /// Parses the admin groups and group mapping settings
+    pub fn parse(admin_groups: &str, groups: &str) -> Self {
+        Self {
+            admin_groups: admin_groups
+                .split(';')
+                .map(str::trim)
+                .filter(|dn| !dn.is_empty())
+                .map(str::to_string)
+                .collect(),
+            groups: groups
+                .split(';')
+                .filter_map(|pair| pair.split_once("=>"))
+                .map(|(dn, id)| (dn.trim().to_string(), id.trim().to_string()))
+                .filter(|(dn, id)| !dn.is_empty() && !id.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Whether the directory groups grant admin privileges
+    ///
+    /// Returns `None` when no admin group is configured, the admin flag is then
+    /// managed locally and left untouched.
+    pub fn is_admin(&self, member_of: &[String]) -> Option<bool> {
+        if self.admin_groups.is_empty() {
+            return None;
+        }
+
+        Some(
+            self.admin_groups
+                .iter()
+                .any(|admin| member_of.iter().any(|dn| dn.eq_ignore_ascii_case(admin))),
+        )
+    }
+
+    /// Computes the groups of a user
+    ///
+    /// Groups that are not part of the mapping are kept untouched, mapped
+    /// groups follow the directory membership.
+    pub fn groups(&self, current: &[DbId], member_of: &[String]) -> Vec<DbId> {
+        let mapped: Vec<DbId> = self
+            .groups
+            .iter()
+            .map(|(_, id)| DbId::from(("groups", id.as_str())))
+            .collect();
+
+        let mut groups: Vec<DbId> = current
+            .iter()
+            .filter(|group| !mapped.contains(group))
+            .cloned()
+            .collect();
+
+        for (dn, id) in &self.groups {
+            let group = DbId::from(("groups", id.as_str()));
+
+            if member_of.iter().any(|m| m.eq_ignore_ascii_case(dn)) && !groups.contains(&group) {
+                groups.push(group);
+            }
+        }
+
+        groups
+    }
+}
+
+/// # LDAP Backend
+///
+/// Authenticates users with a search and bind against the directory. Users
+/// are provisioned on their first login and bound to the immutable id of their
+/// entry. Their mapped groups, and their admin flag when admin groups are
+/// configured, are synchronized on every login.
+pub struct LdapBackend<D: Directory = LdapDirectory> {
+    directory: D,
+    mapping: RoleMapping,
+}
+
+impl LdapBackend {
+    /// Creates the backend from the `LDAP_*` environment variables
+    pub fn from_env() -> Self {
+        Self::new(
+            LdapDirectory::new(LdapConfig::from_env()),
+            RoleMapping::from_env(),
+        )
+    }
+}
+
+impl<D: Directory> LdapBackend<D> {
+    /// Creates the backend from a directory and a role mapping
+    pub fn new(directory: D, mapping: RoleMapping) -> Self {
+        Self { directory, mapping }
+    }
+
+    /// Returns the local user bound to a directory entry, creating it if needed
+    ///
+    /// Existing accounts are never adopted through their email address: whoever
+    /// controls a directory entry would otherwise take over the local account
+    /// registered with the same address. Such accounts must be bound first, with
+    /// the SCIM `externalId`.
+    async fn provision(
+        &self, db: &Database, entry: &DirectoryEntry,
+    ) -> Result<UserModel, AuthBackendError> {
+        if let Some(user) = UserModel::get_user_by_directory_id(db, &entry.id)
+            .await
+            .map_err(|e| AuthBackendError::Provisioning(e.to_string()))?
+        {
+            return Ok(user);
+        }
+
+        match UserModel::get_user_by_email(db, entry.email.clone()).await {
+            Ok(_) => {
+                return Err(AuthBackendError::AccountConflict(format!(
+                    "{} is already used by an account that is not bound to the directory",
+                    entry.email
+                )))
+            }
+            Err(ClientError::DBOptionNone) => {}
+            Err(e) => return Err(AuthBackendError::Provisioning(e.to_string())),
+        }
+
+        // Directory users never log in with a local password
+        let password_hash = SessionModel::create_password_hash(random_password())
+            .await
+            .map_err(|e| AuthBackendError::Provisioning(e.to_string()))?;
+
+        #[cfg(feature = "tracing")]
+        tracing::info!("🔐 Provisioning directory user {}", entry.dn);
+
+        let mut user = db
+            .create::<CreateUserModel, UserModel>(
+                "users",
+                CreateUserModel {
+                    email: entry.email.clone(),
+                    password_hash,
+                },
+            )
+            .await
+            .map_err(|e| AuthBackendError::Provisioning(e.to_string()))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| AuthBackendError::Provisioning("Failed to create user".to_string()))?;
+
+        db.update_field(user.id.clone(), "directory_id", entry.id.clone())
+            .await
+            .map_err(|e| AuthBackendError::Provisioning(e.to_string()))?;
+        user.directory_id = Some(entry.id.clone());
+
+        Ok(user)
+    }
+
+    /// Applies the directory state to the local user
+    async fn synchronize(
+        &self, db: &Database, mut user: UserModel, entry: &DirectoryEntry,
+    ) -> Result<UserModel, AuthBackendError> {
+        let is_admin = self.mapping.is_admin(&entry.groups);
+        let groups = self.mapping.groups(&user.groups, &entry.groups);
+
+        if let Some(is_admin) = is_admin.filter(|is_admin| *is_admin != user.is_admin) {
+            db.update_field(user.id.clone(), "is_admin", is_admin)
+                .await
+                .map_err(|e| AuthBackendError::Provisioning(e.to_string()))?;
+            user.is_admin = is_admin;
+        }
+
+        if user.groups != groups {
+            db.update_field(user.id.clone(), "groups", groups.clone())
+                .await
+                .map_err(|e| AuthBackendError::Provisioning(e.to_string()))?;
+            user.groups = groups;
+        }
+
+        Ok(user)
+    }
+}
+
+#[async_trait]
+impl<D: Directory> AuthBackend for LdapBackend<D> {
+    fn name(&self) -> &'static str {
+        "ldap"
+    }
+
+    async fn authenticate(
+        &self, db: &Database, email: &str, password: &str,
+    ) -> Result<UserModel, AuthBackendError> {
+        let entry = self.directory.find_user(email).await?.ok_or_else(|| {
+            AuthBackendError::UserNotFound(format!("{} is not in the directory", email))
+        })?;
+
+        if !self.directory.bind(&entry.dn, password).await? {
+            return Err(AuthBackendError::InvalidCredentials);
+        }
+
+        let user = self.provision(db, &entry).await?;
+
+        self.synchronize(db, user, &entry).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UserEmailModel;
+    use kiro_database::db_bridge::MockDatabaseOperations;
+    use ldap3::asn1::{parse_tag, StructureTag, TagClass, PL};
+    use mockall::predicate::{always, eq};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+    };
+
+    const ADMIN_GROUP: &str = "cn=admins,ou=groups,dc=example,dc=com";
+    const ENGINEERING_GROUP: &str = "cn=engineering,ou=groups,dc=example,dc=com";
+    const SERVICE_DN: &str = "cn=service,dc=example,dc=com";
+    const SERVICE_PASSWORD: &str = "service-password";
+    const USER_DN: &str = "uid=jdoe,ou=people,dc=example,dc=com";
+    const USER_EMAIL: &str = "jdoe@example.com";
+    const USER_ID: &str = "5f0c2f3e-8d4b-4c6f-9a52-2b1d0f3c7e91";
+
+    /// Encodes a BER element
+    fn encode(tag: StructureTag, out: &mut Vec<u8>) {
+        let (constructed, content) = match tag.payload {
+            PL::P(content) => (0, content),
+            PL::C(tags) => {
+                let mut content = Vec::new();
+                for tag in tags {
+                    encode(tag, &mut content);
+                }
+                (0x20, content)
+            }
+        };
+
+        out.push(((tag.class as u8) << 6) | constructed | tag.id as u8);
+        if content.len() < 0x80 {
+            out.push(content.len() as u8);
+        } else {
+            let length: Vec<u8> = content
+                .len()
+                .to_be_bytes()
+                .into_iter()
+                .skip_while(|byte| *byte == 0)
+                .collect();
+            out.push(0x80 | length.len() as u8);
+            out.extend(length);
+        }
+        out.extend(content);
+    }
+
+    fn tag(class: TagClass, id: u64, tags: Vec<StructureTag>) -> StructureTag {
+        StructureTag {
+            class,
+            id,
+            payload: PL::C(tags),
+        }
+    }
+
+    fn octet_string(value: &str) -> StructureTag {
+        StructureTag {
+            class: TagClass::Universal,
+            id: 4,
+            payload: PL::P(value.as_bytes().to_vec()),
+        }
+    }
+
+    /// LDAPResult with an empty matched DN and diagnostic message
+    fn result(id: u64, code: u8) -> StructureTag {
+        let code = StructureTag {
+            class: TagClass::Universal,
+            id: 10,
+            payload: PL::P(vec![code]),
+        };
+
+        tag(
+            TagClass::Application,
+            id,
+            vec![code, octet_string(""), octet_string("")],
+        )
+    }
+
+    fn attribute(name: &str, values: &[&str]) -> StructureTag {
+        tag(
+            TagClass::Universal,
+            16,
+            vec![
+                octet_string(name),
+                tag(
+                    TagClass::Universal,
+                    17,
+                    values.iter().map(|value| octet_string(value)).collect(),
+                ),
+            ],
+        )
+    }
+
+    /// Collects the strings of an element, the last one of a filter is the email
+    fn strings(tag: StructureTag) -> Vec<String> {
+        match tag.payload {
+            PL::P(value) => vec![String::from_utf8_lossy(&value).to_string()],
+            PL::C(tags) => tags.into_iter().flat_map(strings).collect(),
+        }
+    }
+
+    /// Answers a LDAP message, `None` closes the connection
+    fn respond(message: StructureTag) -> Option<Vec<StructureTag>> {
+        let mut parts = message.expect_constructed()?.into_iter();
+        let message_id = parts.next()?;
+        let operation = parts.next()?;
+
+        let responses = match (operation.class, operation.id) {
+            // Bind request: version, name, simple password
+            (TagClass::Application, 0) => {
+                let mut fields = operation.expect_constructed()?.into_iter().skip(1);
+                let dn = strings(fields.next()?).concat();
+                let password = strings(fields.next()?).concat();
+
+                let valid = (dn == SERVICE_DN && password == SERVICE_PASSWORD)
+                    || (dn == USER_DN && password == "secret");
+
+                vec![result(1, if valid { 0 } else { INVALID_CREDENTIALS as u8 })]
+            }
+            // Search request, the filter is the seventh field
+            (TagClass::Application, 3) => {
+                let filter = operation.expect_constructed()?.into_iter().nth(6)?;
+                let email = strings(filter).pop()?;
+
+                let mut responses = Vec::new();
+                if email == USER_EMAIL {
+                    responses.push(tag(
+                        TagClass::Application,
+                        4,
+                        vec![
+                            octet_string(USER_DN),
+                            tag(
+                                TagClass::Universal,
+                                16,
+                                vec![
+                                    attribute("entryUUID", &[USER_ID]),
+                                    attribute("mail", &[USER_EMAIL]),
+                                    attribute(
+                                        "memberOf",
+                                        &[&ADMIN_GROUP.to_uppercase(), ENGINEERING_GROUP],
+                                    ),
+                                ],
+                            ),
+                        ],
+                    ));
+                }
+                responses.push(result(5, 0));
+                responses
+            }
+            // Unbind request
+            _ => return None,
+        };
+
+        Some(
+            responses
+                .into_iter()
+                .map(|response| tag(TagClass::Universal, 16, vec![message_id.clone(), response]))
+                .collect(),
+        )
+    }
+
+    async fn serve(mut stream: TcpStream) {
+        let mut buffer = Vec::new();
+        let mut chunk = [0; 4096];
+
+        loop {
+            while let Ok((rest, message)) = parse_tag(&buffer) {
+                let consumed = buffer.len() - rest.len();
+                buffer.drain(..consumed);
+
+                let Some(responses) = respond(message) else {
+                    return;
+                };
+
+                let mut out = Vec::new();
+                for response in responses {
+                    encode(response, &mut out);
+                }
+                if stream.write_all(&out).await.is_err() {
+                    return;
+                }
+            }
+
+            match stream.read(&mut chunk).await {
+                Ok(0) | Err(_) => return,
+                Ok(read) => buffer.extend_from_slice(&chunk[..read]),
+            }
+        }
+    }
+
+    /// Starts an in-process LDAP server holding the service account and a
+    /// single user with password `secret`, returns its directory client
+    async fn directory() -> LdapDirectory {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ldap://{}", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve(stream));
+            }
+        });
+
+        LdapDirectory::new(LdapConfig {
+            url,
+            starttls: false,
+            bind_dn: SERVICE_DN.to_string(),
+            bind_password: SERVICE_PASSWORD.to_string(),
+            base_dn: "ou=people,dc=example,dc=com".to_string(),
+            user_filter: "(mail={email})".to_string(),
+            email_attribute: "mail".to_string(),
+            id_attribute: "entryUUID".to_string(),
+            group_attribute: "memberOf".to_string(),
+            timeout: 5,
+        })
+    }
+
+    fn mapping() -> RoleMapping {
+        RoleMapping::parse(ADMIN_GROUP, &format!("{}=>engineering", ENGINEERING_GROUP))
+    }
+
+    /// Expects the lookup of the user bound to the directory entry
+    fn expect_bound_user(mock_db: &mut MockDatabaseOperations, user: Option<UserModel>) {
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .with(eq("users"), eq("directory_id"), eq(USER_ID), eq(None))
+            .times(1)
+            .returning(move |_, _, _, _| Ok(user.clone().into_iter().collect()));
+    }
+
+    /// Expects the lookup of the accounts using the email of the entry
+    fn expect_email_lookup(mock_db: &mut MockDatabaseOperations, user: Option<UserModel>) {
+        let found = user.is_some();
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .with(eq("users"), eq("email"), eq(USER_EMAIL), eq(None))
+            .times(1)
+            .returning(move |_, _, _, _| Ok(user.clone().into_iter().collect()));
+
+        if !found {
+            mock_db
+                .expect_read_by_field::<UserEmailModel>()
+                .with(eq("user_emails"), eq("email"), eq(USER_EMAIL), eq(None))
+                .times(1)
+                .returning(|_, _, _, _| Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn test_role_mapping() {
+        let mapping = mapping();
+        let current = vec![
+            DbId::from(("groups", "manual")),
+            DbId::from(("groups", "engineering")),
+        ];
+
+        assert_eq!(mapping.is_admin(&[ADMIN_GROUP.to_string()]), Some(true));
+        assert_eq!(
+            mapping.is_admin(&[ENGINEERING_GROUP.to_string()]),
+            Some(false)
+        );
+
+        // Without admin groups the admin flag is managed locally
+        assert_eq!(
+            RoleMapping::parse("", "").is_admin(&[ADMIN_GROUP.to_string()]),
+            None
+        );
+
+        // Mapped groups follow the directory, others are kept
+        assert_eq!(
+            mapping.groups(&current, &[]),
+            vec![DbId::from(("groups", "manual"))]
+        );
+        assert_eq!(
+            mapping.groups(&[], &[ENGINEERING_GROUP.to_string()]),
+            vec![DbId::from(("groups", "engineering"))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ldap_directory_search() {
+        let directory = directory().await;
+
+        let entry = directory.find_user(USER_EMAIL).await.unwrap().unwrap();
+        assert_eq!(entry.id, USER_ID);
+        assert_eq!(entry.dn, USER_DN);
+        assert_eq!(entry.email, USER_EMAIL);
+        assert_eq!(entry.groups.len(), 2);
+
+        assert!(directory
+            .find_user("unknown@example.com")
+            .await
+            .unwrap()
+            .is_none());
+
+        assert!(directory.bind(USER_DN, "secret").await.unwrap());
+        assert!(!directory.bind(USER_DN, "wrong").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_ldap_provisions_user() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        expect_bound_user(&mut mock_db, None);
+        expect_email_lookup(&mut mock_db, None);
+
+        mock_db
+            .expect_create::<CreateUserModel, UserModel>()
+            .with(eq("users"), always())
+            .times(1)
+            .returning(|_, data| {
+                Ok(vec![UserModel {
+                    email: data.email,
+                    groups: vec![],
+                    ..Default::default()
+                }])
+            });
+
+        mock_db
+            .expect_update_field::<String>()
+            .with(
+                eq(DbId::default()),
+                eq("directory_id"),
+                eq(USER_ID.to_string()),
+            )
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        mock_db
+            .expect_update_field::<bool>()
+            .with(eq(DbId::default()), eq("is_admin"), eq(true))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        mock_db
+            .expect_update_field::<Vec<DbId>>()
+            .with(
+                eq(DbId::default()),
+                eq("groups"),
+                eq(vec![DbId::from(("groups", "engineering"))]),
+            )
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let db = Database::Mock(mock_db);
+        let backend = LdapBackend::new(directory().await, mapping());
+
+        let user = backend
+            .authenticate(&db, USER_EMAIL, "secret")
+            .await
+            .unwrap();
+
+        assert_eq!(user.email, USER_EMAIL);
+        assert_eq!(user.directory_id, Some(USER_ID.to_string()));
+        assert!(user.is_admin);
+        assert_eq!(user.groups, vec![DbId::from(("groups", "engineering"))]);
+    }
+
+    #[tokio::test]
+    async fn test_ldap_does_not_adopt_unbound_account() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        // A local account registered the address before the directory user logged in
+        expect_bound_user(&mut mock_db, None);
+        expect_email_lookup(
+            &mut mock_db,
+            Some(UserModel {
+                email: USER_EMAIL.to_string(),
+                ..Default::default()
+            }),
+        );
+
+        let db = Database::Mock(mock_db);
+        let backend = LdapBackend::new(directory().await, mapping());
+
+        let error = backend
+            .authenticate(&db, USER_EMAIL, "secret")
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, AuthBackendError::AccountConflict(_)));
+        assert!(!error.is_fallthrough());
+    }
+
+    #[tokio::test]
+    async fn test_ldap_keeps_local_admin_flag() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        // Promoted locally, no admin group configured
+        expect_bound_user(
+            &mut mock_db,
+            Some(UserModel {
+                email: USER_EMAIL.to_string(),
+                is_admin: true,
+                groups: vec![],
+                directory_id: Some(USER_ID.to_string()),
+                ..Default::default()
+            }),
+        );
+
+        let db = Database::Mock(mock_db);
+        let backend = LdapBackend::new(directory().await, RoleMapping::parse("", ""));
+
+        let user = backend
+            .authenticate(&db, USER_EMAIL, "secret")
+            .await
+            .unwrap();
+
+        assert!(user.is_admin);
+    }
+
+    #[tokio::test]
+    async fn test_ldap_wrong_password() {
+        let db = Database::Mock(MockDatabaseOperations::new());
+        let backend = LdapBackend::new(directory().await, mapping());
+
+        let error = backend
+            .authenticate(&db, USER_EMAIL, "wrong")
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AuthBackendError::InvalidCredentials));
+
+        let error = backend.authenticate(&db, USER_EMAIL, "").await.unwrap_err();
+        assert!(matches!(error, AuthBackendError::InvalidCredentials));
+    }
+
+    #[tokio::test]
+    async fn test_ldap_unknown_user() {
+        let db = Database::Mock(MockDatabaseOperations::new());
+        let backend = LdapBackend::new(directory().await, mapping());
+
+        let error = backend
+            .authenticate(&db, "unknown@example.com", "secret")
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, AuthBackendError::UserNotFound(_)));
+    }
+}
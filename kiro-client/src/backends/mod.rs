@@ -0,0 +1,210 @@
+// backends/mod.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Authentication backends
+//!
+//! This module provides the backends checking the credentials sent to `login`:
+//! - Local password hashes stored on the `UserModel`
+//! - LDAP / Active Directory bind and search (feature `ldap`)
+//!
+//! The backends are tried in the order given by the `AUTH_BACKENDS` environment
+//! variable (comma separated, defaults to `password`).
+
+use std::sync::Arc;
+
+use kiro_database::{db_bridge::Database, get_env_or};
+use once_cell::sync::Lazy;
+use tonic::async_trait;
+
+#[cfg(test)]
+use mockall::automock;
+
+use crate::{error::AuthBackendError, UserModel};
+
+#[cfg(feature = "ldap")]
+pub mod ldap;
+pub mod password;
+
+/// # Auth Backend
+///
+/// Checks the credentials of a user and returns the matching local `UserModel`.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait AuthBackend: Send + Sync {
+    /// Name of the backend, as used in `AUTH_BACKENDS`
+    fn name(&self) -> &'static str;
+
+    /// Authenticates the credentials
+    ///
+    /// # Arguments
+    /// * `db` - Database connection
+    /// * `email` - Email sent by the user
+    /// * `password` - Password sent by the user
+    ///
+    /// # Returns
+    /// * `Ok(UserModel)` - Authenticated user
+    /// * `Err(AuthBackendError)` - Authentication error
+    async fn authenticate(
+        &self, db: &Database, email: &str, password: &str,
+    ) -> Result<UserModel, AuthBackendError>;
+}
+
+/// Backends configured through `AUTH_BACKENDS`
+static AUTH_BACKENDS: Lazy<Vec<Arc<dyn AuthBackend>>> = Lazy::new(|| {
+    let names = get_env_or("AUTH_BACKENDS", "password");
+
+    let backends: Vec<Arc<dyn AuthBackend>> = names
+        .split(',')
+        .map(str::trim)
+        .filter_map(|name| -> Option<Arc<dyn AuthBackend>> {
+            match name {
+                "password" => Some(Arc::new(password::PasswordBackend)),
+                #[cfg(feature = "ldap")]
+                "ldap" => Some(Arc::new(ldap::LdapBackend::from_env())),
+                _ => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!("🔐 Unknown authentication backend: {}", name);
+
+                    None
+                }
+            }
+        })
+        .collect();
+
+    if backends.is_empty() {
+        vec![Arc::new(password::PasswordBackend)]
+    } else {
+        backends
+    }
+});
+
+/// Authenticates a user with the configured backends
+///
+/// # Arguments
+/// * `db` - Database connection
+/// * `email` - Email sent by the user
+/// * `password` - Password sent by the user
+///
+/// # Returns
+/// * `Ok(UserModel)` - Authenticated user
+/// * `Err(AuthBackendError)` - Error of the last backend tried
+pub async fn authenticate(
+    db: &Database, email: &str, password: &str,
+) -> Result<UserModel, AuthBackendError> {
+    authenticate_with(&AUTH_BACKENDS, db, email, password).await
+}
+
+/// Authenticates a user with a chain of backends
+///
+/// Backends are tried in order. The next backend is only tried when the
+/// current one does not know the user or rejects the credentials, any other
+/// error stops the chain.
+///
+/// # Arguments
+/// * `backends` - Backends to try
+/// * `db` - Database connection
+/// * `email` - Email sent by the user
+/// * `password` - Password sent by the user
+///
+/// # Returns
+/// * `Ok(UserModel)` - Authenticated user
+/// * `Err(AuthBackendError)` - Error of the last backend tried
+pub async fn authenticate_with(
+    backends: &[Arc<dyn AuthBackend>], db: &Database, email: &str, password: &str,
+) -> Result<UserModel, AuthBackendError> {
+    let mut last_error = AuthBackendError::InvalidCredentials;
+
+    for backend in backends {
+        match backend.authenticate(db, email, password).await {
+            Ok(user) => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!("🔐 User authenticated by the {} backend", backend.name());
+
+                return Ok(user);
+            }
+            Err(e) if e.is_fallthrough() => last_error = e,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(last_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kiro_database::db_bridge::MockDatabaseOperations;
+
+    fn backend(result: fn() -> Result<UserModel, AuthBackendError>) -> Arc<dyn AuthBackend> {
+        let mut backend = MockAuthBackend::new();
+        backend.expect_name().return_const("mock");
+        backend
+            .expect_authenticate()
+            .times(1)
+            .returning(move |_, _, _| result());
+
+        Arc::new(backend)
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_falls_through() {
+        let db = Database::Mock(MockDatabaseOperations::new());
+        let backends = vec![
+            backend(|| Err(AuthBackendError::InvalidCredentials)),
+            backend(|| Ok(UserModel::default())),
+        ];
+
+        let user = authenticate_with(&backends, &db, "test@example.com", "Password123!")
+            .await
+            .unwrap();
+
+        assert_eq!(user.email, "test@example.com");
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_returns_last_error() {
+        let db = Database::Mock(MockDatabaseOperations::new());
+        let backends = vec![
+            backend(|| Err(AuthBackendError::InvalidCredentials)),
+            backend(|| Err(AuthBackendError::UserNotFound("unknown".to_string()))),
+        ];
+
+        let error = authenticate_with(&backends, &db, "test@example.com", "Password123!")
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, AuthBackendError::UserNotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_stops_on_unavailable() {
+        let db = Database::Mock(MockDatabaseOperations::new());
+
+        let mut unused = MockAuthBackend::new();
+        unused.expect_authenticate().never();
+
+        let backends: Vec<Arc<dyn AuthBackend>> = vec![
+            backend(|| Err(AuthBackendError::Unavailable("timeout".to_string()))),
+            Arc::new(unused),
+        ];
+
+        let error = authenticate_with(&backends, &db, "test@example.com", "Password123!")
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, AuthBackendError::Unavailable(_)));
+    }
+}
@@ -0,0 +1,212 @@
+// backends/password.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use kiro_database::db_bridge::Database;
+#[cfg(feature = "legacy_hashes")]
+use kiro_database::db_bridge::DatabaseOperations;
+use tonic::async_trait;
+
+use super::AuthBackend;
+#[cfg(feature = "legacy_hashes")]
+use crate::utils::legacy_hash::LegacyHash;
+use crate::{error::AuthBackendError, utils::password::valid_password, SessionModel, UserModel};
+
+/// # Password Backend
+///
+/// Authenticates users against the Argon2 password hash stored on the `UserModel`.
+/// With the `legacy_hashes` feature, imported bcrypt and PBKDF2 hashes are accepted
+/// and upgraded to Argon2 on the first successful login.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PasswordBackend;
+
+#[async_trait]
+impl AuthBackend for PasswordBackend {
+    fn name(&self) -> &'static str {
+        "password"
+    }
+
+    async fn authenticate(
+        &self, db: &Database, email: &str, password: &str,
+    ) -> Result<UserModel, AuthBackendError> {
+        // Get user by email
+        let user = UserModel::get_user_by_email(db, email.to_string())
+            .await
+            .map_err(|e| AuthBackendError::UserNotFound(e.to_string()))?;
+
+        // Imported passwords predate the password rules, only their hash is verified
+        #[cfg(feature = "legacy_hashes")]
+        let is_legacy = LegacyHash::detect(&user.password_hash).is_some();
+        #[cfg(not(feature = "legacy_hashes"))]
+        let is_legacy = false;
+
+        // Validate password format
+        if !is_legacy {
+            valid_password(password).map_err(|e| AuthBackendError::InvalidFormat(e.to_string()))?;
+        }
+
+        // Verify password
+        let verified =
+            SessionModel::verify_password(password.to_string(), user.password_hash.clone())
+                .await
+                .map_err(|e| AuthBackendError::Verification(e.to_string()))?;
+
+        if !verified {
+            return Err(AuthBackendError::InvalidCredentials);
+        }
+
+        // Imported hashes are replaced once the password is known
+        #[cfg(feature = "legacy_hashes")]
+        let user = upgrade_legacy_hash(db, user, password).await;
+
+        Ok(user)
+    }
+}
+
+/// Replaces an imported bcrypt or PBKDF2 hash by an Argon2 hash of the password
+///
+/// A failed upgrade does not fail the login, it is attempted again on the next one.
+#[cfg(feature = "legacy_hashes")]
+async fn upgrade_legacy_hash(db: &Database, mut user: UserModel, password: &str) -> UserModel {
+    if LegacyHash::detect(&user.password_hash).is_none() {
+        return user;
+    }
+
+    let result = match SessionModel::create_password_hash(password.to_string()).await {
+        Ok(hash) => db
+            .update_field(user.id.clone(), "password_hash", hash.clone())
+            .await
+            .map(|_| hash)
+            .map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+
+    match result {
+        Ok(hash) => user.password_hash = hash,
+        Err(_e) => {
+            #[cfg(feature = "tracing")]
+            tracing::warn!("🔐 Failed to upgrade a legacy password hash: {}", _e);
+        }
+    }
+
+    user
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kiro_database::db_bridge::MockDatabaseOperations;
+    use mockall::predicate::eq;
+
+    #[tokio::test]
+    async fn test_password_backend() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .with(eq("users"), eq("email"), eq("test@example.com"), eq(None))
+            .times(2)
+            .returning(|_, _, _, _| Ok(vec![UserModel::default()]));
+
+        let db = Database::Mock(mock_db);
+
+        let user = PasswordBackend
+            .authenticate(&db, "test@example.com", "Password123!")
+            .await
+            .unwrap();
+        assert_eq!(user.email, "test@example.com");
+
+        let error = PasswordBackend
+            .authenticate(&db, "test@example.com", "WrongPassword123!")
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AuthBackendError::InvalidCredentials));
+    }
+
+    #[cfg(feature = "legacy_hashes")]
+    #[tokio::test]
+    async fn test_password_backend_upgrades_legacy_hash() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(vec![UserModel {
+                    password_hash: bcrypt::hash("Password123!", 4).unwrap(),
+                    ..Default::default()
+                }])
+            });
+
+        mock_db
+            .expect_update_field::<String>()
+            .withf(|_, field, hash| field == "password_hash" && hash.starts_with("$argon2"))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let user = PasswordBackend
+            .authenticate(&Database::Mock(mock_db), "test@example.com", "Password123!")
+            .await
+            .unwrap();
+        assert!(user.password_hash.starts_with("$argon2"));
+    }
+
+    #[cfg(feature = "legacy_hashes")]
+    #[tokio::test]
+    async fn test_password_backend_legacy_hash_skips_format() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(vec![UserModel {
+                    password_hash: bcrypt::hash("short", 4).unwrap(),
+                    ..Default::default()
+                }])
+            });
+
+        mock_db
+            .expect_update_field::<String>()
+            .withf(|_, field, _| field == "password_hash")
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let user = PasswordBackend
+            .authenticate(&Database::Mock(mock_db), "test@example.com", "short")
+            .await
+            .unwrap();
+        assert!(user.password_hash.starts_with("$argon2"));
+    }
+
+    #[tokio::test]
+    async fn test_password_backend_invalid_format() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![UserModel::default()]));
+
+        let db = Database::Mock(mock_db);
+
+        let error = PasswordBackend
+            .authenticate(&db, "test@example.com", "short")
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, AuthBackendError::InvalidFormat(_)));
+    }
+}
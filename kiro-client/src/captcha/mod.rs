@@ -0,0 +1,485 @@
+// captcha/mod.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! CAPTCHA verification
+//!
+//! This module provides the CAPTCHA checks of `register` and `login`:
+//! - Registrations always require a valid CAPTCHA token
+//! - Logins require one once the account or the IP address crossed a number
+//!   of failed logins within a window
+//!
+//! The verifier is chosen by the `CAPTCHA_PROVIDER` environment variable
+//! (`hcaptcha`, `turnstile` or `recaptcha`, feature `captcha`) and configured
+//! by `CAPTCHA_SECRET`. Without a provider or secret, CAPTCHA checks are
+//! disabled. Clients send the token in the `x-captcha-token` metadata or header.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use kiro_database::{db_bridge::DatabaseOperations, get_env_or};
+use once_cell::sync::Lazy;
+use tonic::async_trait;
+
+use crate::{error::ClientError, LoginFailureModel};
+
+#[cfg(feature = "captcha")]
+pub mod siteverify;
+
+/// Name of the metadata key and header holding the CAPTCHA token
+pub const CAPTCHA_HEADER: &str = "x-captcha-token";
+
+/// # CaptchaError
+///
+/// The CaptchaError enum represents the errors of the CAPTCHA verification.
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum CaptchaError {
+    #[error("CAPTCHA verification required")]
+    Required,
+
+    #[error("CAPTCHA verification failed")]
+    Failed,
+
+    #[error("CAPTCHA verification unavailable: {0}")]
+    Unavailable(String),
+}
+
+impl CaptchaError {
+    /// Returns the stable key of the error, used to localize its message
+    pub fn key(&self) -> &'static str {
+        match self {
+            CaptchaError::Required => "captcha.required",
+            CaptchaError::Failed => "captcha.failed",
+            CaptchaError::Unavailable(_) => "captcha.unavailable",
+        }
+    }
+
+    /// Returns the HTTP status code of the error
+    pub fn status(&self) -> http::StatusCode {
+        match self {
+            CaptchaError::Required => http::StatusCode::PRECONDITION_REQUIRED,
+            CaptchaError::Failed => http::StatusCode::FORBIDDEN,
+            CaptchaError::Unavailable(_) => http::StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+}
+
+/// # Captcha Verifier
+///
+/// Checks a CAPTCHA token with the provider that issued it.
+#[async_trait]
+pub trait CaptchaVerifier: Send + Sync {
+    /// Name of the provider, as used in `CAPTCHA_PROVIDER`
+    fn name(&self) -> &'static str;
+
+    /// Verifies a token
+    ///
+    /// # Arguments
+    /// * `token` - Token sent by the client
+    /// * `remote_ip` - IP address of the client, if known
+    ///
+    /// # Returns
+    /// * `Ok(())` - The token is valid
+    /// * `Err(CaptchaError)` - The token is rejected, or the provider unreachable
+    async fn verify(&self, token: &str, remote_ip: Option<&str>) -> Result<(), CaptchaError>;
+}
+
+/// Verifier configured through `CAPTCHA_PROVIDER` and `CAPTCHA_SECRET`
+fn verifier_from_env() -> Option<Arc<dyn CaptchaVerifier>> {
+    let provider = get_env_or("CAPTCHA_PROVIDER", "none");
+
+    match provider.trim() {
+        "" | "none" => None,
+        #[cfg(feature = "captcha")]
+        name => siteverify::SiteVerifier::from_env(name)
+            .map(|verifier| -> Arc<dyn CaptchaVerifier> { Arc::new(verifier) }),
+        #[cfg(not(feature = "captcha"))]
+        _name => {
+            #[cfg(feature = "tracing")]
+            tracing::warn!("🤖 CAPTCHA provider {} requires the captcha feature", _name);
+
+            None
+        }
+    }
+}
+
+/// # Captcha Policy
+///
+/// The CAPTCHA policy decides when a CAPTCHA token is required, and verifies it.
+/// It is read from the environment:
+///
+/// - `CAPTCHA_LOGIN_ACCOUNT_THRESHOLD` - Failed logins of an account before a CAPTCHA is required
+/// - `CAPTCHA_LOGIN_IP_THRESHOLD` - Failed logins of an IP address before a CAPTCHA is required
+/// - `CAPTCHA_LOGIN_WINDOW_MINUTES` - Window in which the failed logins are counted
+pub struct CaptchaPolicy {
+    pub verifier: Option<Arc<dyn CaptchaVerifier>>,
+    pub account_threshold: usize,
+    pub ip_threshold: usize,
+    pub window: chrono::Duration,
+}
+
+/// CAPTCHA policy read from the environment
+static CAPTCHA_POLICY: Lazy<CaptchaPolicy> = Lazy::new(CaptchaPolicy::from_env);
+
+/// Returns the CAPTCHA policy read from the environment
+pub fn captcha_policy() -> &'static CaptchaPolicy {
+    &CAPTCHA_POLICY
+}
+
+impl CaptchaPolicy {
+    /// Reads the CAPTCHA policy from the environment
+    pub fn from_env() -> Self {
+        let minutes: i64 = get_env_or("CAPTCHA_LOGIN_WINDOW_MINUTES", "15")
+            .parse()
+            .unwrap_or(15);
+
+        Self {
+            verifier: verifier_from_env(),
+            account_threshold: get_env_or("CAPTCHA_LOGIN_ACCOUNT_THRESHOLD", "5")
+                .parse()
+                .unwrap_or(5),
+            ip_threshold: get_env_or("CAPTCHA_LOGIN_IP_THRESHOLD", "20")
+                .parse()
+                .unwrap_or(20),
+            window: chrono::Duration::minutes(minutes),
+        }
+    }
+
+    /// Whether a CAPTCHA verifier is configured
+    pub fn is_enabled(&self) -> bool {
+        self.verifier.is_some()
+    }
+
+    /// Verifies a CAPTCHA token
+    ///
+    /// Used by `register`, which always requires a token when a verifier is
+    /// configured.
+    ///
+    /// # Arguments
+    /// * `token` - Token sent by the client, if any
+    /// * `remote_ip` - IP address of the client, if known
+    ///
+    /// # Returns
+    /// * `Ok(())` - The token is valid, or no verifier is configured
+    /// * `Err(CaptchaError)` - Missing or rejected token, or provider unreachable
+    pub async fn verify(
+        &self, token: Option<&str>, remote_ip: Option<&str>,
+    ) -> Result<(), CaptchaError> {
+        let Some(verifier) = &self.verifier else {
+            return Ok(());
+        };
+
+        let token = token
+            .filter(|token| !token.is_empty())
+            .ok_or(CaptchaError::Required)?;
+
+        verifier.verify(token, remote_ip).await
+    }
+
+    /// Checks the CAPTCHA of a login
+    ///
+    /// A token is only required once the account or the IP address crossed
+    /// its threshold of failed logins within the window.
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `email` - Email sent with the login
+    /// * `remote_ip` - IP address of the client, if known
+    /// * `token` - Token sent by the client, if any
+    ///
+    /// # Returns
+    /// * `Ok(())` - The login may be attempted
+    /// * `Err(ClientError::Captcha)` - Missing or rejected token, or provider unreachable
+    /// * `Err(ClientError)` - Database error
+    pub async fn check_login<DB: DatabaseOperations + Send + Sync>(
+        &self, db: &DB, email: &str, remote_ip: Option<&str>, token: Option<&str>,
+    ) -> Result<(), ClientError> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+
+        let counts =
+            LoginFailureModel::count_recent(db, email, remote_ip, Utc::now() - self.window).await?;
+
+        if counts.account >= self.account_threshold
+            || (remote_ip.is_some() && counts.ip >= self.ip_threshold)
+        {
+            self.verify(token, remote_ip).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Records a failed login, when a verifier is configured
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `email` - Email sent with the login
+    /// * `remote_ip` - IP address of the client, if known
+    pub async fn record_failure<DB: DatabaseOperations + Send + Sync>(
+        &self, db: &DB, email: &str, remote_ip: Option<String>,
+    ) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        if let Err(_e) = LoginFailureModel::record(db, email, remote_ip).await {
+            #[cfg(feature = "tracing")]
+            tracing::warn!("🤖 Failed to record login failure: {}", _e);
+        }
+    }
+
+    /// Clears the failed logins of an account, when a verifier is configured
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `email` - Email of the account
+    pub async fn clear_failures<DB: DatabaseOperations + Send + Sync>(&self, db: &DB, email: &str) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        if let Err(_e) = LoginFailureModel::clear(db, email).await {
+            #[cfg(feature = "tracing")]
+            tracing::warn!("🤖 Failed to clear login failures: {}", _e);
+        }
+    }
+}
+
+/// Extracts the CAPTCHA token from gRPC metadata
+///
+/// # Arguments
+/// * `metadata` - The gRPC metadata
+///
+/// # Returns
+/// * `Some(String)` - The CAPTCHA token
+/// * `None` - No token was sent
+pub fn get_captcha_token_from_md(metadata: &tonic::metadata::MetadataMap) -> Option<String> {
+    metadata
+        .get(CAPTCHA_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().to_string())
+}
+
+/// Extracts the CAPTCHA token from HTTP headers
+///
+/// # Arguments
+/// * `headers` - The HTTP headers
+///
+/// # Returns
+/// * `Some(String)` - The CAPTCHA token
+/// * `None` - No token was sent
+pub fn get_captcha_token_from_headers(headers: &http::HeaderMap) -> Option<String> {
+    headers
+        .get(CAPTCHA_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::{db_bridge::MockDatabaseOperations, db_query::Condition};
+
+    /// Verifier accepting a single token
+    struct StaticVerifier;
+
+    #[async_trait]
+    impl CaptchaVerifier for StaticVerifier {
+        fn name(&self) -> &'static str {
+            "static"
+        }
+
+        async fn verify(&self, token: &str, _remote_ip: Option<&str>) -> Result<(), CaptchaError> {
+            if token == "valid_token" {
+                Ok(())
+            } else {
+                Err(CaptchaError::Failed)
+            }
+        }
+    }
+
+    fn policy() -> CaptchaPolicy {
+        CaptchaPolicy {
+            verifier: Some(Arc::new(StaticVerifier)),
+            account_threshold: 5,
+            ip_threshold: 20,
+            window: chrono::Duration::minutes(15),
+        }
+    }
+
+    fn counts(account: usize, ip: usize) -> MockDatabaseOperations {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_count_where()
+            .times(1..=2)
+            .returning(move |_, condition| match condition {
+                Condition::And(conditions)
+                    if conditions[0] == Condition::eq("email", "user@example.com") =>
+                {
+                    Ok(account as u64)
+                }
+                _ => Ok(ip as u64),
+            });
+
+        mock_db
+    }
+
+    #[tokio::test]
+    async fn test_verify_disabled() {
+        let policy = CaptchaPolicy {
+            verifier: None,
+            ..policy()
+        };
+
+        assert_eq!(policy.verify(None, None).await, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn test_verify_requires_token() {
+        assert_eq!(
+            policy().verify(None, None).await,
+            Err(CaptchaError::Required)
+        );
+        assert_eq!(
+            policy().verify(Some(""), None).await,
+            Err(CaptchaError::Required)
+        );
+        assert_eq!(
+            policy().verify(Some("invalid_token"), None).await,
+            Err(CaptchaError::Failed)
+        );
+        assert_eq!(policy().verify(Some("valid_token"), None).await, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn test_check_login_below_thresholds() {
+        let mock_db = counts(4, 19);
+
+        policy()
+            .check_login(&mock_db, "user@example.com", Some("203.0.113.195"), None)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_check_login_account_threshold() {
+        let mock_db = counts(5, 0);
+
+        let result = policy()
+            .check_login(&mock_db, "user@example.com", Some("203.0.113.195"), None)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ClientError::Captcha(CaptchaError::Required))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_check_login_ip_threshold() {
+        let mock_db = counts(0, 20);
+
+        policy()
+            .check_login(
+                &mock_db,
+                "user@example.com",
+                Some("203.0.113.195"),
+                Some("valid_token"),
+            )
+            .await
+            .unwrap();
+
+        let mock_db = counts(0, 20);
+
+        let result = policy()
+            .check_login(
+                &mock_db,
+                "user@example.com",
+                Some("203.0.113.195"),
+                Some("invalid_token"),
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ClientError::Captcha(CaptchaError::Failed))
+        ));
+    }
+
+    #[cfg(feature = "captcha")]
+    #[tokio::test]
+    async fn test_check_login_with_site_verifier() {
+        use siteverify::{CaptchaProvider, SiteVerifier};
+
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/siteverify")
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"success":true}"#)
+            .create_async()
+            .await;
+
+        let policy = CaptchaPolicy {
+            verifier: Some(Arc::new(SiteVerifier::new(
+                CaptchaProvider::HCaptcha,
+                "test_secret".to_string(),
+                format!("{}/siteverify", server.url()),
+                0.5,
+                std::time::Duration::from_secs(5),
+            ))),
+            ..policy()
+        };
+
+        let mock_db = counts(5, 0);
+
+        policy
+            .check_login(&mock_db, "user@example.com", None, Some("valid_token"))
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_record_failure_disabled() {
+        let policy = CaptchaPolicy {
+            verifier: None,
+            ..policy()
+        };
+
+        // No database call is expected
+        policy
+            .record_failure(&MockDatabaseOperations::new(), "user@example.com", None)
+            .await;
+    }
+
+    #[test]
+    fn test_get_captcha_token_from_headers() {
+        let mut headers = http::HeaderMap::new();
+        headers.insert(CAPTCHA_HEADER, "token".parse().unwrap());
+
+        assert_eq!(
+            get_captcha_token_from_headers(&headers),
+            Some("token".to_string())
+        );
+        assert_eq!(
+            get_captcha_token_from_headers(&http::HeaderMap::new()),
+            None
+        );
+    }
+}
@@ -0,0 +1,289 @@
+// captcha/siteverify.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use kiro_database::get_env_or;
+use serde::Deserialize;
+use tonic::async_trait;
+
+use super::{CaptchaError, CaptchaVerifier};
+
+/// # Captcha Provider
+///
+/// hCaptcha, Cloudflare Turnstile and Google reCAPTCHA share the `siteverify`
+/// protocol: the secret and the token are posted as a form, and the provider
+/// answers with a JSON object holding `success`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CaptchaProvider {
+    HCaptcha,
+    Turnstile,
+    ReCaptcha,
+}
+
+impl CaptchaProvider {
+    /// Returns the provider of a `CAPTCHA_PROVIDER` value
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "hcaptcha" => Some(CaptchaProvider::HCaptcha),
+            "turnstile" => Some(CaptchaProvider::Turnstile),
+            "recaptcha" => Some(CaptchaProvider::ReCaptcha),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the provider, as used in `CAPTCHA_PROVIDER`
+    pub fn name(&self) -> &'static str {
+        match self {
+            CaptchaProvider::HCaptcha => "hcaptcha",
+            CaptchaProvider::Turnstile => "turnstile",
+            CaptchaProvider::ReCaptcha => "recaptcha",
+        }
+    }
+
+    /// Returns the verification endpoint of the provider
+    pub fn verify_url(&self) -> &'static str {
+        match self {
+            CaptchaProvider::HCaptcha => "https://api.hcaptcha.com/siteverify",
+            CaptchaProvider::Turnstile => {
+                "https://challenges.cloudflare.com/turnstile/v0/siteverify"
+            }
+            CaptchaProvider::ReCaptcha => "https://www.google.com/recaptcha/api/siteverify",
+        }
+    }
+}
+
+/// Answer of a `siteverify` endpoint
+#[derive(Debug, Deserialize)]
+struct SiteVerifyResponse {
+    success: bool,
+    /// Score of reCAPTCHA v3 and hCaptcha Enterprise, from 0.0 (bot) to 1.0
+    score: Option<f64>,
+    #[serde(default, rename = "error-codes")]
+    error_codes: Vec<String>,
+}
+
+/// # Site Verifier
+///
+/// `CaptchaVerifier` implementation posting the tokens to the `siteverify`
+/// endpoint of a provider. It is configured by:
+///
+/// - `CAPTCHA_SECRET` - Secret key of the site
+/// - `CAPTCHA_VERIFY_URL` - Verification endpoint, defaults to the provider's
+/// - `CAPTCHA_MIN_SCORE` - Lowest score accepted, when the provider returns one
+/// - `CAPTCHA_TIMEOUT` - Timeout of the verification, in seconds
+pub struct SiteVerifier {
+    provider: CaptchaProvider,
+    secret: String,
+    url: String,
+    min_score: f64,
+    client: reqwest::Client,
+}
+
+impl SiteVerifier {
+    /// Creates a verifier
+    ///
+    /// # Arguments
+    /// * `provider` - Provider issuing the tokens
+    /// * `secret` - Secret key of the site
+    /// * `url` - Verification endpoint
+    /// * `min_score` - Lowest score accepted, when the provider returns one
+    /// * `timeout` - Timeout of the verification
+    pub fn new(
+        provider: CaptchaProvider, secret: String, url: String, min_score: f64, timeout: Duration,
+    ) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            provider,
+            secret,
+            url,
+            min_score,
+            client,
+        }
+    }
+
+    /// Creates the verifier of a provider from the environment
+    ///
+    /// # Returns
+    /// * `Some(SiteVerifier)` - Known provider with a secret
+    /// * `None` - Unknown provider or missing secret, CAPTCHA checks are disabled
+    pub fn from_env(name: &str) -> Option<Self> {
+        let Some(provider) = CaptchaProvider::from_name(name) else {
+            #[cfg(feature = "tracing")]
+            tracing::warn!("🤖 Unknown CAPTCHA provider: {}", name);
+
+            return None;
+        };
+
+        let secret = get_env_or("CAPTCHA_SECRET", "");
+        if secret.is_empty() {
+            #[cfg(feature = "tracing")]
+            tracing::warn!("🤖 CAPTCHA_SECRET is not set, CAPTCHA checks are disabled");
+
+            return None;
+        }
+
+        Some(Self::new(
+            provider,
+            secret,
+            get_env_or("CAPTCHA_VERIFY_URL", provider.verify_url()),
+            get_env_or("CAPTCHA_MIN_SCORE", "0.5")
+                .parse()
+                .unwrap_or(0.5),
+            Duration::from_secs(get_env_or("CAPTCHA_TIMEOUT", "5").parse().unwrap_or(5)),
+        ))
+    }
+}
+
+#[async_trait]
+impl CaptchaVerifier for SiteVerifier {
+    fn name(&self) -> &'static str {
+        self.provider.name()
+    }
+
+    async fn verify(&self, token: &str, remote_ip: Option<&str>) -> Result<(), CaptchaError> {
+        let mut form = vec![("secret", self.secret.as_str()), ("response", token)];
+        if let Some(remote_ip) = remote_ip {
+            form.push(("remoteip", remote_ip));
+        }
+
+        let response = self
+            .client
+            .post(&self.url)
+            .form(&form)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| CaptchaError::Unavailable(e.to_string()))?
+            .json::<SiteVerifyResponse>()
+            .await
+            .map_err(|e| CaptchaError::Unavailable(e.to_string()))?;
+
+        if !response.success {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                "🤖 {} rejected a CAPTCHA token: {:?}",
+                self.provider.name(),
+                response.error_codes
+            );
+
+            return Err(CaptchaError::Failed);
+        }
+
+        match response.score {
+            Some(score) if score < self.min_score => Err(CaptchaError::Failed),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mockito::{Matcher, Server};
+
+    fn verifier(server: &Server) -> SiteVerifier {
+        SiteVerifier::new(
+            CaptchaProvider::Turnstile,
+            "test_secret".to_string(),
+            format!("{}/siteverify", server.url()),
+            0.5,
+            Duration::from_secs(5),
+        )
+    }
+
+    #[test]
+    fn test_provider_from_name() {
+        assert_eq!(
+            CaptchaProvider::from_name("hcaptcha"),
+            Some(CaptchaProvider::HCaptcha)
+        );
+        assert_eq!(CaptchaProvider::from_name("unknown"), None);
+        assert_eq!(CaptchaProvider::ReCaptcha.name(), "recaptcha");
+    }
+
+    #[tokio::test]
+    async fn test_verify_success() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/siteverify")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("secret".to_string(), "test_secret".to_string()),
+                Matcher::UrlEncoded("response".to_string(), "valid_token".to_string()),
+                Matcher::UrlEncoded("remoteip".to_string(), "203.0.113.195".to_string()),
+            ]))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"success":true}"#)
+            .create_async()
+            .await;
+
+        let result = verifier(&server)
+            .verify("valid_token", Some("203.0.113.195"))
+            .await;
+
+        assert_eq!(result, Ok(()));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_verify_rejected_token() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("POST", "/siteverify")
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"success":false,"error-codes":["invalid-input-response"]}"#)
+            .create_async()
+            .await;
+
+        let result = verifier(&server).verify("invalid_token", None).await;
+
+        assert_eq!(result, Err(CaptchaError::Failed));
+    }
+
+    #[tokio::test]
+    async fn test_verify_low_score() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("POST", "/siteverify")
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"success":true,"score":0.2}"#)
+            .create_async()
+            .await;
+
+        let result = verifier(&server).verify("bot_token", None).await;
+
+        assert_eq!(result, Err(CaptchaError::Failed));
+    }
+
+    #[tokio::test]
+    async fn test_verify_provider_error() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("POST", "/siteverify")
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let result = verifier(&server).verify("valid_token", None).await;
+
+        assert!(matches!(result, Err(CaptchaError::Unavailable(_))));
+    }
+}
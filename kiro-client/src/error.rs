@@ -14,9 +14,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use kiro_database::DatabaseError;
+use kiro_database::{with_error_key, DatabaseError};
 use tonic::Status;
 
+#[cfg(feature = "import")]
+use crate::utils::user_import::ImportError;
+#[cfg(feature = "storage")]
+use crate::utils::{avatar::AvatarError, upload::UploadError};
+use crate::{
+    captcha::CaptchaError,
+    utils::{profile::ProfileError, registration::RegistrationError},
+};
+
 #[derive(thiserror::Error, Debug)]
 pub enum ClientError {
     #[error("Session key generation failed")]
@@ -64,10 +73,84 @@ pub enum ClientError {
     #[error("Decrypted data is invalid")]
     DecryptionError,
 
+    #[error(transparent)]
+    InvalidProfile(#[from] ProfileError),
+
+    #[error("Handle already in use")]
+    HandleTaken,
+
+    #[error("Account scheduled for deletion")]
+    PendingDeletion,
+
+    #[error("Account disabled")]
+    AccountDisabled,
+
+    #[error("Payment customer {0} must be deleted first")]
+    PaymentCustomer(String),
+
+    #[error("Email address already in use")]
+    EmailTaken,
+
+    #[error("No email change pending")]
+    NoPendingEmail,
+
+    #[error("Email address not found")]
+    EmailNotFound,
+
+    #[error("Email address not verified")]
+    EmailNotVerified,
+
+    #[error("Primary email address cannot be removed")]
+    PrimaryEmail,
+
+    #[error("{0}")]
+    InvalidSettings(String),
+
+    #[error("Settings were modified concurrently")]
+    StaleVersion,
+
+    #[error("Policy acceptance required")]
+    PolicyAcceptanceRequired,
+
+    #[error("Policy version is not current")]
+    PolicyNotCurrent,
+
+    #[error(transparent)]
+    RegistrationRejected(#[from] RegistrationError),
+
+    #[error("Group not found")]
+    GroupNotFound,
+
+    #[error("Invitation expiry must be in the future")]
+    InvalidInvitationExpiry,
+
+    #[error(transparent)]
+    Captcha(#[from] CaptchaError),
+
+    #[cfg(feature = "export")]
+    #[error("A data export was already requested recently")]
+    ExportThrottled,
+
+    #[cfg(feature = "export")]
+    #[error("Failed to build data export: {0}")]
+    ArchiveFailed(String),
+
     #[cfg(feature = "storage")]
     #[error(transparent)]
     StorageError(#[from] kiro_storage::StorageError),
 
+    #[cfg(feature = "storage")]
+    #[error(transparent)]
+    InvalidAvatar(#[from] AvatarError),
+
+    #[cfg(feature = "storage")]
+    #[error(transparent)]
+    InvalidUpload(#[from] UploadError),
+
+    #[cfg(feature = "import")]
+    #[error(transparent)]
+    Import(#[from] ImportError),
+
     #[cfg(feature = "mailer")]
     #[error(transparent)]
     MailerError(#[from] kiro_mailer::MailerError),
@@ -88,9 +171,82 @@ impl From<ClientError> for DatabaseError {
     }
 }
 
+impl ClientError {
+    /// Returns the stable key of the error, used to localize its message
+    pub fn key(&self) -> &'static str {
+        match self {
+            // Session errors
+            ClientError::KeyGenerationFailed => "session.key_generation_failed",
+            ClientError::NotCreated => "session.not_created",
+            ClientError::Expired => "session.expired",
+            ClientError::NotFound => "session.not_found",
+            ClientError::General => "session.error",
+            ClientError::RenewalFailed => "session.renewal_failed",
+            ClientError::DeletionFailed => "session.deletion_failed",
+            ClientError::DestroyAllFailed => "session.destroy_all_failed",
+            ClientError::IpMismatch => "session.ip_mismatch",
+            ClientError::PasswordHashingFailed => "password.hashing_failed",
+            ClientError::PasswordIncorrect => "password.incorrect",
+            // Mailer errors
+            ClientError::NewConnectionEmailFailed => "email.new_connection_failed",
+            ClientError::InvalidAddress(_) => "email.invalid_address",
+            ClientError::EncryptionError => "crypto.encryption_failed",
+            ClientError::DecryptionError => "crypto.decryption_failed",
+            // Profile errors
+            ClientError::InvalidProfile(e) => e.key(),
+            ClientError::HandleTaken => "profile.handle_taken",
+            // Account errors
+            ClientError::PendingDeletion => "account.pending_deletion",
+            ClientError::AccountDisabled => "account.disabled",
+            ClientError::PaymentCustomer(_) => "account.payment_customer",
+            ClientError::EmailTaken => "email.taken",
+            ClientError::NoPendingEmail => "email.no_pending_change",
+            ClientError::EmailNotFound => "email.not_found",
+            ClientError::EmailNotVerified => "email.not_verified",
+            ClientError::PrimaryEmail => "email.primary",
+            // Settings errors
+            ClientError::InvalidSettings(_) => "settings.invalid",
+            ClientError::StaleVersion => "settings.stale_version",
+            // Policy errors
+            ClientError::PolicyAcceptanceRequired => "policy.acceptance_required",
+            ClientError::PolicyNotCurrent => "policy.not_current",
+            // Registration errors
+            ClientError::RegistrationRejected(e) => e.key(),
+            // Invitation errors
+            ClientError::GroupNotFound => "group.not_found",
+            ClientError::InvalidInvitationExpiry => "invitation.invalid_expiry",
+            // CAPTCHA errors
+            ClientError::Captcha(e) => e.key(),
+            // Export errors
+            #[cfg(feature = "export")]
+            ClientError::ExportThrottled => "export.throttled",
+            #[cfg(feature = "export")]
+            ClientError::ArchiveFailed(_) => "export.archive_failed",
+            // Storage errors
+            #[cfg(feature = "storage")]
+            ClientError::StorageError(e) => e.key(),
+            #[cfg(feature = "storage")]
+            ClientError::InvalidAvatar(e) => e.key(),
+            #[cfg(feature = "storage")]
+            ClientError::InvalidUpload(e) => e.key(),
+            // Import errors
+            #[cfg(feature = "import")]
+            ClientError::Import(e) => e.key(),
+            // Mailer errors
+            #[cfg(feature = "mailer")]
+            ClientError::MailerError(e) => e.key(),
+            // Database errors
+            ClientError::DBOptionNone => "database.record_not_found",
+            ClientError::Database(e) => e.key(),
+        }
+    }
+}
+
 impl From<ClientError> for Status {
     fn from(error: ClientError) -> Self {
-        match error {
+        let key = error.key();
+
+        let status = match error {
             // Session errors
             ClientError::KeyGenerationFailed => Status::internal("Session key generation failed"),
             ClientError::NotCreated => Status::internal("Session not created"),
@@ -110,9 +266,81 @@ impl From<ClientError> for Status {
             ClientError::InvalidAddress(e) => Status::invalid_argument(e),
             ClientError::EncryptionError => Status::internal("Encrypted data is invalid"),
             ClientError::DecryptionError => Status::internal("Decrypted data is invalid"),
+            // Profile errors
+            ClientError::InvalidProfile(e) => Status::invalid_argument(e.to_string()),
+            ClientError::HandleTaken => Status::already_exists("Handle already in use"),
+            // Account errors
+            ClientError::PendingDeletion => {
+                Status::failed_precondition("Account scheduled for deletion")
+            }
+            ClientError::AccountDisabled => Status::permission_denied("Account disabled"),
+            ClientError::PaymentCustomer(_) => Status::failed_precondition(error.to_string()),
+            ClientError::EmailTaken => Status::already_exists("Email address already in use"),
+            ClientError::NoPendingEmail => Status::failed_precondition("No email change pending"),
+            ClientError::EmailNotFound => Status::not_found("Email address not found"),
+            ClientError::EmailNotVerified => {
+                Status::failed_precondition("Email address not verified")
+            }
+            ClientError::PrimaryEmail => {
+                Status::failed_precondition("Primary email address cannot be removed")
+            }
+            // Settings errors
+            ClientError::InvalidSettings(e) => Status::invalid_argument(e),
+            ClientError::StaleVersion => Status::aborted("Settings were modified concurrently"),
+            // Policy errors
+            ClientError::PolicyAcceptanceRequired => {
+                Status::failed_precondition("Policy acceptance required")
+            }
+            ClientError::PolicyNotCurrent => {
+                Status::invalid_argument("Policy version is not current")
+            }
+            // Registration errors
+            ClientError::RegistrationRejected(RegistrationError::InvalidEmail) => {
+                Status::invalid_argument("Invalid email address")
+            }
+            ClientError::RegistrationRejected(e) => Status::permission_denied(e.to_string()),
+            // Invitation errors
+            ClientError::GroupNotFound => Status::not_found("Group not found"),
+            ClientError::InvalidInvitationExpiry => {
+                Status::invalid_argument("Invitation expiry must be in the future")
+            }
+            // CAPTCHA errors
+            ClientError::Captcha(CaptchaError::Required) => {
+                Status::failed_precondition("CAPTCHA verification required")
+            }
+            ClientError::Captcha(CaptchaError::Failed) => {
+                Status::permission_denied("CAPTCHA verification failed")
+            }
+            ClientError::Captcha(e @ CaptchaError::Unavailable(_)) => {
+                Status::unavailable(e.to_string())
+            }
+            // Export errors
+            #[cfg(feature = "export")]
+            ClientError::ExportThrottled => {
+                Status::resource_exhausted("A data export was already requested recently")
+            }
+            #[cfg(feature = "export")]
+            ClientError::ArchiveFailed(e) => {
+                Status::internal(format!("Failed to build data export: {}", e))
+            }
             // Storage errors
             #[cfg(feature = "storage")]
             ClientError::StorageError(e) => e.into(),
+            #[cfg(feature = "storage")]
+            ClientError::InvalidAvatar(e) => Status::invalid_argument(e.to_string()),
+            #[cfg(feature = "storage")]
+            ClientError::InvalidUpload(UploadError::Interrupted) => {
+                Status::cancelled("Upload interrupted")
+            }
+            #[cfg(feature = "storage")]
+            ClientError::InvalidUpload(e) => Status::invalid_argument(e.to_string()),
+            // Import errors
+            #[cfg(feature = "import")]
+            ClientError::Import(ImportError::Interrupted) => {
+                Status::cancelled("Import interrupted")
+            }
+            #[cfg(feature = "import")]
+            ClientError::Import(e) => Status::invalid_argument(e.to_string()),
             // Mailer errors
             #[cfg(feature = "mailer")]
             ClientError::MailerError(e) => e.into(),
@@ -121,6 +349,291 @@ impl From<ClientError> for Status {
                 Status::not_found("Database Record that was just checked doesn't exist?")
             }
             ClientError::Database(e) => e.into(),
+        };
+
+        with_error_key(status, key)
+    }
+}
+
+/// # Auth Backend Error
+///
+/// The auth backend error enum represents the errors returned by the authentication
+/// backends consulted by `login`.
+#[derive(thiserror::Error, Debug)]
+pub enum AuthBackendError {
+    #[error("{0}")]
+    InvalidFormat(String),
+
+    #[error("{0}")]
+    UserNotFound(String),
+
+    #[error("Invalid password")]
+    InvalidCredentials,
+
+    #[error("Password verification error: {0}")]
+    Verification(String),
+
+    #[error("Directory unavailable: {0}")]
+    Unavailable(String),
+
+    #[error("User provisioning failed: {0}")]
+    Provisioning(String),
+
+    #[error("{0}")]
+    AccountConflict(String),
+}
+
+impl AuthBackendError {
+    /// Whether the next backend of the chain may still authenticate the user
+    pub fn is_fallthrough(&self) -> bool {
+        matches!(
+            self,
+            AuthBackendError::InvalidFormat(_)
+                | AuthBackendError::UserNotFound(_)
+                | AuthBackendError::InvalidCredentials
+        )
+    }
+
+    /// Returns the stable key of the error, used to localize its message
+    pub fn key(&self) -> &'static str {
+        match self {
+            AuthBackendError::InvalidFormat(_) => "auth.invalid_format",
+            AuthBackendError::UserNotFound(_) => "auth.user_not_found",
+            AuthBackendError::InvalidCredentials => "password.invalid",
+            AuthBackendError::Verification(_) => "auth.verification_failed",
+            AuthBackendError::Unavailable(_) => "auth.directory_unavailable",
+            AuthBackendError::Provisioning(_) => "auth.provisioning_failed",
+            AuthBackendError::AccountConflict(_) => "auth.account_conflict",
+        }
+    }
+
+    /// Returns the HTTP status code of the error
+    pub fn status(&self) -> http::StatusCode {
+        match self {
+            AuthBackendError::InvalidFormat(_) => http::StatusCode::BAD_REQUEST,
+            AuthBackendError::UserNotFound(_) => http::StatusCode::NOT_FOUND,
+            AuthBackendError::InvalidCredentials => http::StatusCode::UNAUTHORIZED,
+            AuthBackendError::Unavailable(_) => http::StatusCode::SERVICE_UNAVAILABLE,
+            AuthBackendError::AccountConflict(_) => http::StatusCode::CONFLICT,
+            AuthBackendError::Verification(_) | AuthBackendError::Provisioning(_) => {
+                http::StatusCode::INTERNAL_SERVER_ERROR
+            }
         }
     }
 }
+
+impl From<AuthBackendError> for Status {
+    fn from(error: AuthBackendError) -> Self {
+        let key = error.key();
+
+        let status = match error {
+            AuthBackendError::InvalidFormat(e) => Status::invalid_argument(e),
+            AuthBackendError::UserNotFound(e) => {
+                Status::not_found(format!("User not found: {}", e))
+            }
+            AuthBackendError::InvalidCredentials => Status::permission_denied("Invalid password"),
+            AuthBackendError::Unavailable(_) => Status::unavailable(error.to_string()),
+            AuthBackendError::AccountConflict(e) => Status::already_exists(e),
+            AuthBackendError::Verification(_) | AuthBackendError::Provisioning(_) => {
+                Status::internal(error.to_string())
+            }
+        };
+
+        with_error_key(status, key)
+    }
+}
+
+/// # OAuth Error
+///
+/// The OAuth error enum represents the errors returned by the OAuth2 / OpenID Connect
+/// endpoints, serialized as described in RFC 6749 section 5.2.
+#[cfg(feature = "oauth")]
+#[derive(thiserror::Error, Debug)]
+pub enum OAuthError {
+    #[error("{0}")]
+    InvalidRequest(String),
+
+    #[error("Client authentication failed")]
+    InvalidClient,
+
+    #[error("{0}")]
+    InvalidGrant(String),
+
+    #[error("Client is not allowed to use this grant type")]
+    UnauthorizedClient,
+
+    #[error("Unsupported grant type")]
+    UnsupportedGrantType,
+
+    #[error("Access denied")]
+    AccessDenied,
+
+    #[error("Unsupported response type")]
+    UnsupportedResponseType,
+
+    #[error("Requested scope is not allowed")]
+    InvalidScope,
+
+    #[error("Access token is invalid or expired")]
+    InvalidToken,
+
+    #[error("Token signing failed")]
+    SigningFailed,
+
+    #[error(transparent)]
+    Client(#[from] ClientError),
+
+    #[error(transparent)]
+    Database(#[from] DatabaseError),
+}
+
+#[cfg(feature = "oauth")]
+impl OAuthError {
+    /// Returns the RFC 6749 error code
+    pub fn code(&self) -> &'static str {
+        match self {
+            OAuthError::InvalidRequest(_) => "invalid_request",
+            OAuthError::InvalidClient => "invalid_client",
+            OAuthError::InvalidGrant(_) => "invalid_grant",
+            OAuthError::UnauthorizedClient => "unauthorized_client",
+            OAuthError::UnsupportedGrantType => "unsupported_grant_type",
+            OAuthError::AccessDenied => "access_denied",
+            OAuthError::UnsupportedResponseType => "unsupported_response_type",
+            OAuthError::InvalidScope => "invalid_scope",
+            OAuthError::InvalidToken => "invalid_token",
+            OAuthError::SigningFailed | OAuthError::Client(_) | OAuthError::Database(_) => {
+                "server_error"
+            }
+        }
+    }
+
+    /// Returns the HTTP status code of the error
+    pub fn status(&self) -> http::StatusCode {
+        match self {
+            OAuthError::InvalidClient | OAuthError::InvalidToken => http::StatusCode::UNAUTHORIZED,
+            OAuthError::SigningFailed | OAuthError::Client(_) | OAuthError::Database(_) => {
+                http::StatusCode::INTERNAL_SERVER_ERROR
+            }
+            _ => http::StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+#[cfg(feature = "oauth")]
+impl axum::response::IntoResponse for OAuthError {
+    fn into_response(self) -> axum::response::Response {
+        let mut response = (
+            self.status(),
+            axum::Json(serde_json::json!({
+                "error": self.code(),
+                "error_description": self.to_string(),
+            })),
+        )
+            .into_response();
+
+        if self.status() == http::StatusCode::UNAUTHORIZED {
+            response.headers_mut().insert(
+                http::header::WWW_AUTHENTICATE,
+                http::HeaderValue::from_static("Bearer"),
+            );
+        }
+
+        response
+    }
+}
+
+/// # SCIM Error
+///
+/// The SCIM error enum represents the errors returned by the SCIM 2.0 provisioning
+/// endpoints, serialized as described in RFC 7644 section 3.12.
+#[cfg(feature = "scim")]
+#[derive(thiserror::Error, Debug)]
+pub enum ScimError {
+    #[error("Provisioning token is missing or invalid")]
+    Unauthorized,
+
+    #[error("{0}")]
+    InvalidFilter(String),
+
+    #[error("{0}")]
+    InvalidSyntax(String),
+
+    #[error("{0}")]
+    InvalidPath(String),
+
+    #[error("{0}")]
+    InvalidValue(String),
+
+    #[error("{0}")]
+    Uniqueness(String),
+
+    #[error("{0} not found")]
+    NotFound(String),
+
+    #[error(transparent)]
+    Client(#[from] ClientError),
+
+    #[error(transparent)]
+    Database(#[from] DatabaseError),
+}
+
+#[cfg(feature = "scim")]
+impl ScimError {
+    /// Returns the RFC 7644 `scimType` of the error
+    pub fn scim_type(&self) -> Option<&'static str> {
+        match self {
+            ScimError::InvalidFilter(_) => Some("invalidFilter"),
+            ScimError::InvalidSyntax(_) => Some("invalidSyntax"),
+            ScimError::InvalidPath(_) => Some("invalidPath"),
+            ScimError::InvalidValue(_) => Some("invalidValue"),
+            ScimError::Uniqueness(_) => Some("uniqueness"),
+            _ => None,
+        }
+    }
+
+    /// Returns the HTTP status code of the error
+    pub fn status(&self) -> http::StatusCode {
+        match self {
+            ScimError::Unauthorized => http::StatusCode::UNAUTHORIZED,
+            ScimError::Uniqueness(_) | ScimError::Client(ClientError::PaymentCustomer(_)) => {
+                http::StatusCode::CONFLICT
+            }
+            ScimError::NotFound(_) => http::StatusCode::NOT_FOUND,
+            ScimError::Client(_) | ScimError::Database(_) => {
+                http::StatusCode::INTERNAL_SERVER_ERROR
+            }
+            _ => http::StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+#[cfg(feature = "scim")]
+impl axum::response::IntoResponse for ScimError {
+    fn into_response(self) -> axum::response::Response {
+        let mut body = serde_json::json!({
+            "schemas": ["urn:ietf:params:scim:api:messages:2.0:Error"],
+            "status": self.status().as_u16().to_string(),
+            "detail": self.to_string(),
+        });
+
+        if let Some(scim_type) = self.scim_type() {
+            body["scimType"] = serde_json::Value::from(scim_type);
+        }
+
+        let mut response = (self.status(), axum::Json(body)).into_response();
+
+        response.headers_mut().insert(
+            http::header::CONTENT_TYPE,
+            http::HeaderValue::from_static("application/scim+json"),
+        );
+
+        if self.status() == http::StatusCode::UNAUTHORIZED {
+            response.headers_mut().insert(
+                http::header::WWW_AUTHENTICATE,
+                http::HeaderValue::from_static("Bearer"),
+            );
+        }
+
+        response
+    }
+}
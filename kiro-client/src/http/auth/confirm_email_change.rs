@@ -0,0 +1,164 @@
+// http/auth/confirm_email_change.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use kiro_api::auth::v1::ConfirmEmailChangeRequest;
+use kiro_mailer::MailerError;
+
+use crate::{error::ClientError, utils::email_change::consume_email_confirmation_link};
+
+/// Email change confirmation route handler
+///
+/// # Description
+/// Swaps the email address of a user for the pending one, from the link emailed
+/// to the new address by `/user/update_email`. The availability of the address
+/// is checked again at this moment.
+///
+/// # Arguments
+/// * `service` - The authentication service instance
+/// * `request` - The request containing the token of the confirmation link
+///
+/// # Returns
+/// * HTTP response with either:
+///   * `200 OK` with empty JSON response
+///   * Error status code with message
+///
+/// # Errors
+/// * `400 BAD REQUEST` - The link is not a confirmation link
+/// * `404 NOT FOUND` - Unknown link
+/// * `409 CONFLICT` - The email was taken in the meantime
+/// * `410 GONE` - The link has expired or the change was cancelled
+/// * `500 INTERNAL SERVER ERROR` - Database or server error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{extract::State, Json};
+/// use kiro_api::auth::v1::ConfirmEmailChangeRequest;
+/// use kiro_client::{AuthService, confirm_email_change::confirm_email_change};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = AuthService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Confirmation request
+/// let request = Json(ConfirmEmailChangeRequest {
+///     temp_token: "temp_token".to_string(),
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     confirm_email_change(State(service), request).await;
+///
+///     println!("Email changed");
+/// });
+/// ```
+#[utoipa::path(
+    post,
+    path = "/auth/confirm_email_change",
+    tag = "auth",
+    params(
+        ConfirmEmailChangeRequest
+    ),
+    responses(
+        (status = 200, description = "Email changed", body = String),
+        (status = 400, description = "Invalid link type", body = String),
+        (status = 404, description = "Link not found", body = String),
+        (status = 409, description = "Email already in use", body = String),
+        (status = 410, description = "Link expired or change cancelled", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn confirm_email_change(
+    State(service): State<AuthService>, Json(request): Json<ConfirmEmailChangeRequest>,
+) -> impl IntoResponse {
+    match consume_email_confirmation_link(&service.db, &request.temp_token).await {
+        Ok(_) => (StatusCode::OK, Json(serde_json::json!({}))).into_response(),
+        Err(e) => {
+            let status = match e {
+                ClientError::MailerError(MailerError::NotFound) => StatusCode::NOT_FOUND,
+                ClientError::MailerError(MailerError::InvalidType) => StatusCode::BAD_REQUEST,
+                ClientError::MailerError(MailerError::Expired) => StatusCode::GONE,
+                ClientError::NoPendingEmail => StatusCode::GONE,
+                ClientError::EmailTaken => StatusCode::CONFLICT,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+
+            (
+                status,
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::{db_bridge::MockDatabaseOperations, DbDateTime, DbId};
+    use kiro_mailer::{LinkModel, LinkType};
+
+    use crate::UserModel;
+
+    #[tokio::test]
+    async fn test_confirm_email_change_cancelled() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<LinkModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(LinkModel {
+                    id: DbId::from(("links", "link")),
+                    user: DbId::from(("users", "user")),
+                    link_type: LinkType::EmailConfirmation,
+                    expiry: DbDateTime::from(chrono::Utc::now() + chrono::Duration::hours(1)),
+                }))
+            });
+
+        // No change pending anymore
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_delete_where::<LinkModel>()
+            .times(2)
+            .returning(|_, _| Ok(0));
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Json(ConfirmEmailChangeRequest {
+            temp_token: "link".to_string(),
+        });
+
+        let response = confirm_email_change(State(service), request)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::GONE);
+    }
+}
@@ -24,14 +24,19 @@ use kiro_api::{
 };
 
 use crate::{
-    utils::{ip::get_ip_from_headers, password::valid_password},
-    SessionModel, UserModel,
+    backends::authenticate,
+    captcha::{captcha_policy, get_captcha_token_from_headers},
+    error::ClientError,
+    utils::{cookie::session_cookie_headers, ip::get_ip_from_headers},
+    PrivacyPolicy, SessionModel,
 };
 
 /// Login service implementation
 ///
 /// # Description
-/// Authenticates a user and creates a new session
+/// Authenticates a user with the configured backends and creates a new session.
+/// Once the account or the IP address crossed its threshold of failed logins,
+/// a CAPTCHA token is required in the `x-captcha-token` header.
 ///
 /// # Arguments
 /// * `service` - The authentication service instance
@@ -40,15 +45,18 @@ use crate::{
 ///
 /// # Returns
 /// * HTTP response with either:
-///   * `200 OK` with Session containing token and expiry
+///   * `200 OK` with Session containing token and expiry, and the session and CSRF cookies set
 ///   * Error status code with message
 ///
 /// # Errors
 /// * `400 BAD REQUEST` - Invalid password format
 /// * `401 UNAUTHORIZED` - Invalid password
+/// * `403 FORBIDDEN` - Account disabled, or scheduled for deletion, see `/auth/restore_account`, or CAPTCHA failed
 /// * `404 NOT FOUND` - User not found
 /// * `409 CONFLICT` - User already exists
+/// * `428 PRECONDITION REQUIRED` - CAPTCHA token missing
 /// * `500 INTERNAL SERVER ERROR` - Database or server error
+/// * `503 SERVICE UNAVAILABLE` - Directory server or CAPTCHA provider unreachable
 ///
 /// # Example
 /// ```rust,no_run
@@ -69,7 +77,9 @@ use crate::{
 /// // Login request
 /// let request = Json(AuthRequest {
 ///     email: "user@example.com".to_string(),
-///     password: "password123!".to_string()
+///     password: "password123!".to_string(),
+///     invitation_code: None,
+///     accepted_policies: vec![],
 /// });
 ///
 /// // Empty headers
@@ -93,9 +103,12 @@ use crate::{
         (status = 200, description = "Session created", body = Session),
         (status = 400, description = "Invalid password format", body = String),
         (status = 401, description = "Invalid password", body = String),
+        (status = 403, description = "Account disabled or scheduled for deletion, or CAPTCHA failed", body = String),
         (status = 404, description = "User not found", body = String),
         (status = 409, description = "User already exists", body = String),
-        (status = 500, description = "Internal server error", body = String)
+        (status = 428, description = "CAPTCHA token missing", body = String),
+        (status = 500, description = "Internal server error", body = String),
+        (status = 503, description = "Directory server or CAPTCHA provider unreachable", body = String)
 
     )
 )]
@@ -103,44 +116,77 @@ pub async fn login(
     State(service): State<AuthService>, headers: HeaderMap, Json(request): Json<AuthRequest>,
 ) -> impl IntoResponse {
     // Extract IP address from request metadata
-    let ip_address = get_ip_from_headers(&headers).unwrap_or_else(|| "unknown".to_string());
-
-    if let Err(e) = valid_password(&request.password) {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({ "error": e.to_string() })),
+    let ip_address = get_ip_from_headers(&headers);
+
+    // Require a CAPTCHA after repeated failures
+    let captcha = captcha_policy();
+    match captcha
+        .check_login(
+            &service.db,
+            &request.email,
+            ip_address.as_deref(),
+            get_captcha_token_from_headers(&headers).as_deref(),
         )
-            .into_response();
-    }
-
-    // Check if the user exists
-    let user = match UserModel::get_user_by_email(&service.db, request.email).await {
-        Ok(user) => user,
-        Err(e) => {
+        .await
+    {
+        Ok(()) => {}
+        Err(ClientError::Captcha(e)) => {
             return (
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({ "error": e.to_string() })),
+                e.status(),
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
             )
                 .into_response()
         }
-    };
-
-    // Verify password
-    let verified = match SessionModel::verify_password(request.password, user.password_hash).await {
-        Ok(verified) => verified,
         Err(e) => {
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({ "error": e.to_string() })),
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
             )
                 .into_response()
         }
+    }
+
+    // Authenticate with the configured backends
+    let user = match authenticate(&service.db, &request.email, &request.password).await {
+        Ok(user) => user,
+        Err(e) => {
+            if e.is_fallthrough() {
+                captcha
+                    .record_failure(&service.db, &request.email, ip_address)
+                    .await;
+            }
+
+            return (
+                e.status(),
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+            )
+                .into_response();
+        }
     };
+    captcha.clear_failures(&service.db, &request.email).await;
+
+    // Disabled accounts must be reactivated first
+    if let Err(e) = user.check_active() {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({
+                "error": e.to_string(),
+                "code": e.key(),
+            })),
+        )
+            .into_response();
+    }
 
-    if !verified {
+    // Accounts scheduled for deletion must be restored first
+    if let Err(e) = user.check_not_pending_deletion() {
         return (
-            StatusCode::UNAUTHORIZED,
-            Json(serde_json::json!({ "error": "Invalid password" })),
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({
+                "error": e.to_string(),
+                "code": e.key(),
+                "deletion_scheduled_at": user.deletion_scheduled_at,
+                "restore": "/auth/restore_account",
+            })),
         )
             .into_response();
     }
@@ -149,7 +195,8 @@ pub async fn login(
     let session = match SessionModel::get_session_by_user_id(
         &service.db,
         user.id.clone(),
-        ip_address,
+        ip_address.unwrap_or_else(|| "unknown".to_string()),
+        &PrivacyPolicy::for_user(&user),
     )
     .await
     {
@@ -157,7 +204,7 @@ pub async fn login(
         Err(e) => {
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({ "error": e.to_string() })),
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
             )
                 .into_response()
         }
@@ -169,20 +216,25 @@ pub async fn login(
         Err(e) => {
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({ "error": e.to_string() })),
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
             )
                 .into_response()
         }
     };
 
+    let expires_at = chrono::Utc::now() + chrono::Duration::days(2);
     let expire_date: Option<Timestamp> = Some(Timestamp {
-        seconds: (chrono::Utc::now() + chrono::Duration::days(2)).timestamp(),
+        seconds: expires_at.timestamp(),
         nanos: 0,
     });
 
+    // Set browser session cookies
+    let cookies = session_cookie_headers(&refresh_token.1, expires_at);
+
     // Return session
     (
         StatusCode::OK,
+        cookies,
         Json(Session {
             token: refresh_token.1,
             expire_date,
@@ -195,7 +247,7 @@ pub async fn login(
 mod tests {
     use super::*;
 
-    use crate::{CreateSessionModel, SessionModel};
+    use crate::{CreateSessionModel, SessionModel, UserEmailModel, UserModel};
     use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError, DbId};
     use mockall::predicate::{always, eq};
 
@@ -230,12 +282,20 @@ mod tests {
         let request = Json(AuthRequest {
             email: "test@example.com".to_string(),
             password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
         });
 
         let headers = HeaderMap::new();
         let response = login(State(service), headers, request).await;
 
         let response = response.into_response();
+        let cookies: Vec<String> = response
+            .headers()
+            .get_all(http::header::SET_COOKIE)
+            .iter()
+            .map(|value| value.to_str().unwrap().to_string())
+            .collect();
         let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
             .await
             .unwrap();
@@ -243,6 +303,12 @@ mod tests {
 
         assert!(!session.token.is_empty());
         assert!(session.expire_date.is_some());
+        assert!(cookies
+            .iter()
+            .any(|cookie| cookie.starts_with(&format!("kiro_session={};", session.token))));
+        assert!(cookies
+            .iter()
+            .any(|cookie| cookie.starts_with("kiro_csrf=")));
     }
 
     #[tokio::test]
@@ -262,6 +328,8 @@ mod tests {
         let request = Json(AuthRequest {
             email: "test@example.com".to_string(),
             password: "WrongPassword123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
         });
 
         let headers = HeaderMap::new();
@@ -278,6 +346,88 @@ mod tests {
         assert_eq!(error["error"], "Invalid password");
     }
 
+    #[tokio::test]
+    async fn test_login_pending_deletion() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .with(eq("users"), eq("email"), eq("test@example.com"), eq(None))
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(vec![UserModel {
+                    deletion_scheduled_at: Some(kiro_database::DbDateTime::now()),
+                    ..Default::default()
+                }])
+            });
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Json(AuthRequest {
+            email: "test@example.com".to_string(),
+            password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
+        });
+
+        let headers = HeaderMap::new();
+        let response = login(State(service), headers, request).await;
+
+        let response = response.into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+
+        assert_eq!(error["error"], "Account scheduled for deletion");
+        assert_eq!(error["restore"], "/auth/restore_account");
+    }
+
+    #[tokio::test]
+    async fn test_login_disabled_account() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .with(eq("users"), eq("email"), eq("test@example.com"), eq(None))
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(vec![UserModel {
+                    activated: false,
+                    ..Default::default()
+                }])
+            });
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Json(AuthRequest {
+            email: "test@example.com".to_string(),
+            password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
+        });
+
+        let headers = HeaderMap::new();
+        let response = login(State(service), headers, request).await;
+
+        let response = response.into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+
+        assert_eq!(error["error"], "Account disabled");
+        assert_eq!(error["code"], "account.disabled");
+    }
+
     #[tokio::test]
     async fn test_login_user_not_found() {
         let mut mock_db = MockDatabaseOperations::new();
@@ -290,6 +440,17 @@ mod tests {
             .times(1)
             .returning(|_, _, _, _| Ok(vec![]));
 
+        mock_db
+            .expect_read_by_field::<UserEmailModel>()
+            .with(
+                eq("user_emails"),
+                eq("email"),
+                eq("nonexistent@example.com"),
+                eq(None),
+            )
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
         let service = AuthService {
             db: Database::Mock(mock_db),
         };
@@ -297,6 +458,8 @@ mod tests {
         let request = Json(AuthRequest {
             email: "nonexistent@example.com".to_string(),
             password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
         });
 
         let headers = HeaderMap::new();
@@ -335,6 +498,8 @@ mod tests {
         let request = Json(AuthRequest {
             email: "test@example.com".to_string(),
             password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
         });
 
         let headers = HeaderMap::new();
@@ -346,7 +511,14 @@ mod tests {
 
     #[tokio::test]
     async fn test_login_invalid_password_format() {
-        let mock_db = MockDatabaseOperations::new();
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .with(eq("users"), eq("email"), eq("test@example.com"), eq(None))
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![UserModel::default()]));
+
         let service = AuthService {
             db: Database::Mock(mock_db),
         };
@@ -354,6 +526,8 @@ mod tests {
         let request = Json(AuthRequest {
             email: "test@example.com".to_string(),
             password: "short".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
         });
 
         let headers = HeaderMap::new();
@@ -399,6 +573,8 @@ mod tests {
         let request = Json(AuthRequest {
             email: "test@example.com".to_string(),
             password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
         });
 
         let headers = HeaderMap::new();
@@ -18,12 +18,13 @@ use super::*;
 
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
 
-use crate::SessionModel;
+use crate::{utils::cookie::clear_cookie_headers, SessionModel};
 
 /// Logout route handler
 ///
 /// # Description
 /// Logs out the current user by deleting the session from the database
+/// and clearing the browser session cookies
 ///
 /// # Arguments
 /// * `service` - The authentication service instance
@@ -31,7 +32,7 @@ use crate::SessionModel;
 ///
 /// # Returns
 /// * HTTP response with either:
-///   * `200 OK` with empty JSON response
+///   * `200 OK` with empty JSON response and expired session cookies
 ///   * Error status code with message
 ///
 /// # Errors
@@ -78,10 +79,15 @@ pub async fn logout(
     State(service): State<AuthService>, Extension(session): Extension<SessionModel>,
 ) -> impl IntoResponse {
     match SessionModel::delete_session(&service.db, session.id).await {
-        Ok(_) => (StatusCode::OK, Json(serde_json::json!({}))).into_response(),
+        Ok(_) => (
+            StatusCode::OK,
+            clear_cookie_headers(),
+            Json(serde_json::json!({})),
+        )
+            .into_response(),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({ "error": e.to_string() })),
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
         )
             .into_response(),
     }
@@ -113,6 +119,14 @@ mod tests {
 
         let response = response.into_response();
         assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response
+                .headers()
+                .get_all(http::header::SET_COOKIE)
+                .iter()
+                .count(),
+            2
+        );
     }
 
     #[tokio::test]
@@ -20,9 +20,20 @@ use axum::{
 };
 use kiro_database::db_bridge::Database;
 
+#[cfg(feature = "mailer")]
+pub mod cancel_email_change;
+#[cfg(feature = "mailer")]
+pub mod confirm_email_change;
 pub mod login;
 pub mod logout;
+#[cfg(feature = "mailer")]
+pub mod reactivate_account;
 pub mod register;
+#[cfg(feature = "mailer")]
+pub mod request_reactivation;
+pub mod restore_account;
+#[cfg(feature = "mailer")]
+pub mod verify_email;
 
 use crate::AuthService;
 
@@ -36,6 +47,12 @@ use crate::AuthService;
 /// - POST /login - User login
 /// - GET /logout - User logout
 /// - POST /register - New user registration
+/// - POST /restore_account - Restore an account scheduled for deletion
+/// - POST /request_reactivation - Email a link to reactivate a disabled account
+/// - POST /reactivate_account - Reactivate a disabled account from its link
+/// - POST /confirm_email_change - Confirm a pending email change from its link
+/// - POST /cancel_email_change - Cancel a pending email change from its link
+/// - POST /verify_email - Verify a secondary email address from its link
 ///
 /// # Example
 /// ```rust,no_run
@@ -51,11 +68,35 @@ use crate::AuthService;
 pub fn auth_routes(db: Database) -> Router {
     let service = AuthService::new(db);
 
-    Router::new()
+    let mut router = Router::new()
         .route("/login", post(login::login))
         .route("/logout", get(logout::logout))
         .route("/register", post(register::register))
-        .with_state(service)
+        .route("/restore_account", post(restore_account::restore_account));
+
+    #[cfg(feature = "mailer")]
+    {
+        router = router
+            .route(
+                "/request_reactivation",
+                post(request_reactivation::request_reactivation),
+            )
+            .route(
+                "/reactivate_account",
+                post(reactivate_account::reactivate_account),
+            )
+            .route(
+                "/confirm_email_change",
+                post(confirm_email_change::confirm_email_change),
+            )
+            .route(
+                "/cancel_email_change",
+                post(cancel_email_change::cancel_email_change),
+            )
+            .route("/verify_email", post(verify_email::verify_email));
+    }
+
+    router.with_state(service)
 }
 
 #[cfg(test)]
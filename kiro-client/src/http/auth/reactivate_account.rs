@@ -0,0 +1,138 @@
+// http/auth/reactivate_account.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use kiro_api::auth::v1::ReactivateAccountRequest;
+use kiro_mailer::MailerError;
+
+use crate::{error::ClientError, utils::reactivation::consume_reactivation_link};
+
+/// Account reactivation route handler
+///
+/// # Description
+/// Reactivates a disabled account from the link emailed by `/auth/request_reactivation`
+///
+/// # Arguments
+/// * `service` - The authentication service instance
+/// * `request` - The request containing the token of the reactivation link
+///
+/// # Returns
+/// * HTTP response with either:
+///   * `200 OK` with empty JSON response
+///   * Error status code with message
+///
+/// # Errors
+/// * `400 BAD REQUEST` - The link is not a reactivation link
+/// * `404 NOT FOUND` - Unknown link
+/// * `410 GONE` - The link has expired
+/// * `500 INTERNAL SERVER ERROR` - Database or server error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{extract::State, Json};
+/// use kiro_api::auth::v1::ReactivateAccountRequest;
+/// use kiro_client::{AuthService, reactivate_account::reactivate_account};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = AuthService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Reactivation request
+/// let request = Json(ReactivateAccountRequest {
+///     temp_token: "temp_token".to_string(),
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     reactivate_account(State(service), request).await;
+///
+///     println!("Account reactivated");
+/// });
+/// ```
+#[utoipa::path(
+    post,
+    path = "/auth/reactivate_account",
+    tag = "auth",
+    params(
+        ReactivateAccountRequest
+    ),
+    responses(
+        (status = 200, description = "Account reactivated", body = String),
+        (status = 400, description = "Invalid link type", body = String),
+        (status = 404, description = "Link not found", body = String),
+        (status = 410, description = "Link expired", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn reactivate_account(
+    State(service): State<AuthService>, Json(request): Json<ReactivateAccountRequest>,
+) -> impl IntoResponse {
+    match consume_reactivation_link(&service.db, &request.temp_token).await {
+        Ok(_) => (StatusCode::OK, Json(serde_json::json!({}))).into_response(),
+        Err(e) => {
+            let status = match e {
+                ClientError::MailerError(MailerError::NotFound) => StatusCode::NOT_FOUND,
+                ClientError::MailerError(MailerError::InvalidType) => StatusCode::BAD_REQUEST,
+                ClientError::MailerError(MailerError::Expired) => StatusCode::GONE,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+
+            (
+                status,
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::db_bridge::MockDatabaseOperations;
+    use kiro_mailer::LinkModel;
+
+    #[tokio::test]
+    async fn test_reactivate_account_unknown_link() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<LinkModel>()
+            .times(1)
+            .returning(|_| Ok(None));
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Json(ReactivateAccountRequest {
+            temp_token: "unknown".to_string(),
+        });
+
+        let response = reactivate_account(State(service), request)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
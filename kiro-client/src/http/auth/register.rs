@@ -25,14 +25,26 @@ use kiro_api::{
 use kiro_database::db_bridge::DatabaseOperations;
 
 use crate::{
-    utils::{ip::get_ip_from_headers, password::valid_password},
-    CreateUserModel, SessionModel, UserModel,
+    captcha::{captcha_policy, get_captcha_token_from_headers},
+    error::ClientError,
+    utils::{
+        consent::check_accepted_policies,
+        cookie::session_cookie_headers,
+        ip::get_ip_from_headers,
+        password::valid_password,
+        registration::{check_registration, redeem_invitation, RegistrationError},
+    },
+    CreateUserModel, PolicyConsentModel, PrivacyPolicy, SessionModel, UserModel,
 };
 
 /// Register service implementation
 ///
 /// # Description
-/// Registers a new user with the system
+/// Registers a new user with the system, who accepts the current terms of
+/// service and privacy policy in the versions listed in the request. The
+/// registration is checked against the registration policy, and the invitation
+/// code, if any, is redeemed. A CAPTCHA token is required in the
+/// `x-captcha-token` header when CAPTCHA is enabled.
 ///
 /// # Arguments
 /// * `service` - The authentication service instance
@@ -41,15 +53,18 @@ use crate::{
 ///
 /// # Returns
 /// * HTTP response with either:
-///   * `200 OK` with Session containing token and expiry
+///   * `200 OK` with Session containing token and expiry, and the session and CSRF cookies set
 ///   * Error status code with message
 ///
 /// # Errors
-/// * `400 BAD REQUEST` - Invalid password or email already in use
+/// * `400 BAD REQUEST` - Invalid password, invalid email or email already in use
 /// * `401 UNAUTHORIZED` - Invalid password
+/// * `403 FORBIDDEN` - Registration rejected by the registration policy, or CAPTCHA failed
+/// * `428 PRECONDITION REQUIRED` - CAPTCHA token missing
 /// * `404 NOT FOUND` - User not found
 /// * `409 CONFLICT` - User already exists
 /// * `500 INTERNAL SERVER ERROR` - Database or server error
+/// * `503 SERVICE UNAVAILABLE` - CAPTCHA provider unreachable
 ///
 /// # Example
 /// ```rust,no_run
@@ -70,7 +85,9 @@ use crate::{
 /// // Registration request
 /// let request = Json(AuthRequest {
 ///     email: "user@example.com".to_string(),
-///     password: "password123!".to_string()
+///     password: "password123!".to_string(),
+///     invitation_code: None,
+///     accepted_policies: vec![],
 /// });
 ///
 /// // Empty headers
@@ -92,11 +109,15 @@ use crate::{
     ),
     responses(
         (status = 200, description = "Session created", body = Session),
-        (status = 400, description = "Invalid password format", body = String),
+        (status = 400, description = "Invalid password format, or policy version not current", body = String),
         (status = 401, description = "Invalid password", body = String),
+        (status = 403, description = "Registration rejected by the registration policy, or CAPTCHA failed", body = String),
         (status = 404, description = "User not found", body = String),
         (status = 409, description = "User already exists", body = String),
-        (status = 500, description = "Internal server error", body = String)
+        (status = 412, description = "Current policy versions not accepted", body = String),
+        (status = 428, description = "CAPTCHA token missing", body = String),
+        (status = 500, description = "Internal server error", body = String),
+        (status = 503, description = "CAPTCHA provider unreachable", body = String)
 
     )
 )]
@@ -104,7 +125,7 @@ pub async fn register(
     State(service): State<AuthService>, headers: HeaderMap, Json(request): Json<AuthRequest>,
 ) -> impl IntoResponse {
     // Extract IP address from request metadata
-    let ip_address = get_ip_from_headers(&headers).unwrap_or_else(|| "unknown".to_string());
+    let ip_address = get_ip_from_headers(&headers);
 
     if let Err(e) = valid_password(&request.password) {
         return (
@@ -114,6 +135,37 @@ pub async fn register(
             .into_response();
     }
 
+    // Verify the CAPTCHA
+    let captcha_token = get_captcha_token_from_headers(&headers);
+    if let Err(e) = captcha_policy()
+        .verify(captcha_token.as_deref(), ip_address.as_deref())
+        .await
+    {
+        return (
+            e.status(),
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        )
+            .into_response();
+    }
+
+    // Check the registration policy
+    let invitation = match check_registration(
+        &service.db,
+        &request.email,
+        request.invitation_code.as_deref(),
+    )
+    .await
+    {
+        Ok(invitation) => invitation,
+        Err(e) => return registration_error(e),
+    };
+
+    // Check the policy versions shown to the user
+    let policies = match check_accepted_policies(&service.db, &request.accepted_policies).await {
+        Ok(policies) => policies,
+        Err(e) => return registration_error(e),
+    };
+
     // Check if email is already in use
     match UserModel::check_email(&service.db, request.email.clone()).await {
         Ok(true) => {}
@@ -127,7 +179,7 @@ pub async fn register(
         Err(e) => {
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({ "error": e.to_string() })),
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
             )
                 .into_response()
         }
@@ -138,7 +190,7 @@ pub async fn register(
         Err(e) => {
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({ "error": e.to_string() })),
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
             )
                 .into_response()
         }
@@ -160,26 +212,50 @@ pub async fn register(
         Err(e) => {
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({ "error": e.to_string() })),
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
             )
                 .into_response()
         }
     };
 
-    // Create session
-    let session =
-        match SessionModel::create_session(&service.db, user.id.clone(), false, Some(ip_address))
+    // Grant the role and group of the invitation
+    if let Some(invitation) = &invitation {
+        if let Err(e) = redeem_invitation(&service.db, invitation, &user).await {
+            return registration_error(e);
+        }
+    }
+
+    // Record the acceptance of the policy documents shown to the user
+    if let Err(e) =
+        PolicyConsentModel::record_all(&service.db, user.id.clone(), &policies, ip_address.clone())
             .await
-        {
-            Ok(session) => session,
-            Err(e) => {
-                return (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(serde_json::json!({ "error": e.to_string() })),
-                )
-                    .into_response()
-            }
-        };
+    {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        )
+            .into_response();
+    }
+
+    // Create session
+    let session = match SessionModel::create_session(
+        &service.db,
+        user.id.clone(),
+        false,
+        Some(ip_address.unwrap_or_else(|| "unknown".to_string())),
+        &PrivacyPolicy::for_user(&user),
+    )
+    .await
+    {
+        Ok(session) => session,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+            )
+                .into_response()
+        }
+    };
 
     // Generate refresh token
     let refresh_token = match SessionModel::generate_refresh_token(session.user_id).await {
@@ -187,20 +263,25 @@ pub async fn register(
         Err(e) => {
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({ "error": e.to_string() })),
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
             )
                 .into_response()
         }
     };
 
+    let expires_at = chrono::Utc::now() + chrono::Duration::days(2);
     let expire_date: Option<Timestamp> = Some(Timestamp {
-        seconds: (chrono::Utc::now() + chrono::Duration::days(2)).timestamp(),
+        seconds: expires_at.timestamp(),
         nanos: 0,
     });
 
+    // Set browser session cookies
+    let cookies = session_cookie_headers(&refresh_token.1, expires_at);
+
     // Return session
     (
         StatusCode::OK,
+        cookies,
         Json(Session {
             token: refresh_token.1,
             expire_date,
@@ -209,11 +290,34 @@ pub async fn register(
         .into_response()
 }
 
+/// Converts an error of the registration checks into a response
+fn registration_error(error: ClientError) -> axum::response::Response {
+    let status = match &error {
+        ClientError::RegistrationRejected(RegistrationError::InvalidEmail) => {
+            StatusCode::BAD_REQUEST
+        }
+        ClientError::RegistrationRejected(_) => StatusCode::FORBIDDEN,
+        ClientError::PolicyNotCurrent => StatusCode::BAD_REQUEST,
+        ClientError::PolicyAcceptanceRequired => StatusCode::PRECONDITION_FAILED,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+
+    (
+        status,
+        Json(serde_json::json!({ "error": error.to_string(), "code": error.key() })),
+    )
+        .into_response()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use crate::{CreateSessionModel, SessionModel};
+    use crate::{
+        CreatePolicyConsentModel, CreateSessionModel, PolicyConsentModel, PolicyDocumentModel,
+        SessionModel, UserEmailModel,
+    };
+    use kiro_api::auth::v1::AcceptedPolicy;
     use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
     use mockall::predicate::{always, eq};
 
@@ -228,12 +332,34 @@ mod tests {
             .times(1)
             .returning(|_, _, _, _| Ok(vec![]));
 
+        mock_db
+            .expect_read_by_field::<UserEmailModel>()
+            .with(
+                eq("user_emails"),
+                eq("email"),
+                eq("test@example.com"),
+                eq(None),
+            )
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
         mock_db
             .expect_create::<CreateUserModel, UserModel>()
             .with(eq("users"), always())
             .times(1)
             .returning(move |_, _| Ok(vec![user.clone()]));
 
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![PolicyDocumentModel::default()]));
+
+        mock_db
+            .expect_create::<CreatePolicyConsentModel, PolicyConsentModel>()
+            .withf(|table, data| table == "policy_consents" && data.version == 1)
+            .times(1)
+            .returning(|_, _| Ok(vec![PolicyConsentModel::default()]));
+
         let session = SessionModel::default();
         mock_db
             .expect_create::<CreateSessionModel, SessionModel>()
@@ -248,6 +374,11 @@ mod tests {
         let request = Json(AuthRequest {
             email: "test@example.com".to_string(),
             password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![AcceptedPolicy {
+                kind: "Terms".to_string(),
+                version: 1,
+            }],
         });
 
         let headers = HeaderMap::new();
@@ -274,6 +405,8 @@ mod tests {
         let request = Json(AuthRequest {
             email: "test@example.com".to_string(),
             password: "invalid".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
         });
 
         let headers = HeaderMap::new();
@@ -292,6 +425,59 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_register_denied_domain() {
+        let mock_db = MockDatabaseOperations::new();
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Json(AuthRequest {
+            email: "test@yopmail.com".to_string(),
+            password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
+        });
+
+        let headers = HeaderMap::new();
+        let response = register(State(service), headers, request).await;
+
+        let response = response.into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+        assert_eq!(
+            error["error"],
+            "Disposable email addresses are not accepted"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_register_invalid_email() {
+        let mock_db = MockDatabaseOperations::new();
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Json(AuthRequest {
+            email: "not-an-email".to_string(),
+            password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
+        });
+
+        let headers = HeaderMap::new();
+        let response = register(State(service), headers, request).await;
+
+        let response = response.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
     #[tokio::test]
     async fn test_register_email_in_use() {
         let mut mock_db = MockDatabaseOperations::new();
@@ -303,6 +489,11 @@ mod tests {
             .times(1)
             .returning(move |_, _, _, _| Ok(vec![user.clone()]));
 
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
+
         let service = AuthService {
             db: Database::Mock(mock_db),
         };
@@ -310,6 +501,8 @@ mod tests {
         let request = Json(AuthRequest {
             email: "test@example.com".to_string(),
             password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
         });
 
         let headers = HeaderMap::new();
@@ -335,12 +528,28 @@ mod tests {
             .times(1)
             .returning(|_, _, _, _| Ok(vec![]));
 
+        mock_db
+            .expect_read_by_field::<UserEmailModel>()
+            .with(
+                eq("user_emails"),
+                eq("email"),
+                eq("test@example.com"),
+                eq(None),
+            )
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
         mock_db
             .expect_create::<CreateUserModel, UserModel>()
             .with(eq("users"), always())
             .times(1)
             .returning(|_, _| Err(DatabaseError::Internal("Failed to create user".to_string())));
 
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
+
         let service = AuthService {
             db: Database::Mock(mock_db),
         };
@@ -348,6 +557,8 @@ mod tests {
         let request = Json(AuthRequest {
             email: "test@example.com".to_string(),
             password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
         });
 
         let headers = HeaderMap::new();
@@ -374,12 +585,28 @@ mod tests {
             .times(1)
             .returning(|_, _, _, _| Ok(vec![]));
 
+        mock_db
+            .expect_read_by_field::<UserEmailModel>()
+            .with(
+                eq("user_emails"),
+                eq("email"),
+                eq("test@example.com"),
+                eq(None),
+            )
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
         mock_db
             .expect_create::<CreateUserModel, UserModel>()
             .with(eq("users"), always())
             .times(1)
             .returning(move |_, _| Ok(vec![user.clone()]));
 
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
+
         mock_db
             .expect_create::<CreateSessionModel, SessionModel>()
             .with(eq("sessions"), always())
@@ -397,6 +624,8 @@ mod tests {
         let request = Json(AuthRequest {
             email: "test@example.com".to_string(),
             password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
         });
 
         let headers = HeaderMap::new();
@@ -411,4 +640,82 @@ mod tests {
         let error: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
         assert_eq!(error["error"], "Internal error: Failed to create session");
     }
+
+    #[tokio::test]
+    async fn test_register_outdated_policy() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(vec![PolicyDocumentModel {
+                    version: 2,
+                    ..Default::default()
+                }])
+            });
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Json(AuthRequest {
+            email: "test@example.com".to_string(),
+            password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![AcceptedPolicy {
+                kind: "Terms".to_string(),
+                version: 1,
+            }],
+        });
+
+        let headers = HeaderMap::new();
+        let response = register(State(service), headers, request).await;
+
+        let response = response.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+        assert_eq!(error["code"], "policy.not_current");
+    }
+
+    #[tokio::test]
+    async fn test_register_policy_not_accepted() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![PolicyDocumentModel::default()]));
+
+        mock_db
+            .expect_create::<CreateUserModel, UserModel>()
+            .never();
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Json(AuthRequest {
+            email: "test@example.com".to_string(),
+            password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
+        });
+
+        let headers = HeaderMap::new();
+        let response = register(State(service), headers, request).await;
+
+        let response = response.into_response();
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+        assert_eq!(error["code"], "policy.acceptance_required");
+    }
 }
@@ -0,0 +1,162 @@
+// http/auth/request_reactivation.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use kiro_api::auth::v1::AuthRequest;
+
+use crate::{backends::authenticate, utils::reactivation::send_reactivation_link};
+
+/// Reactivation request route handler
+///
+/// # Description
+/// Authenticates the owner of a disabled account and emails them a link to
+/// reactivate it
+///
+/// # Arguments
+/// * `service` - The authentication service instance
+/// * `request` - The request containing email and password
+///
+/// # Returns
+/// * HTTP response with either:
+///   * `202 ACCEPTED` once the reactivation link is sent
+///   * Error status code with message
+///
+/// # Errors
+/// * `400 BAD REQUEST` - Invalid password format
+/// * `401 UNAUTHORIZED` - Invalid password
+/// * `404 NOT FOUND` - User not found
+/// * `409 CONFLICT` - Account is not disabled
+/// * `500 INTERNAL SERVER ERROR` - Database, mailer or server error
+/// * `503 SERVICE UNAVAILABLE` - Directory server unreachable
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{extract::State, Json};
+/// use kiro_api::auth::v1::AuthRequest;
+/// use kiro_client::{AuthService, request_reactivation::request_reactivation};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = AuthService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Reactivation request
+/// let request = Json(AuthRequest {
+///     email: "user@example.com".to_string(),
+///     password: "password123!".to_string(),
+///     invitation_code: None,
+///     accepted_policies: vec![],
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     request_reactivation(State(service), request).await;
+///
+///     println!("Reactivation link sent");
+/// });
+/// ```
+#[utoipa::path(
+    post,
+    path = "/auth/request_reactivation",
+    tag = "auth",
+    params(
+        AuthRequest
+    ),
+    responses(
+        (status = 202, description = "Reactivation link sent", body = String),
+        (status = 400, description = "Invalid password format", body = String),
+        (status = 401, description = "Invalid password", body = String),
+        (status = 404, description = "User not found", body = String),
+        (status = 409, description = "Account is not disabled", body = String),
+        (status = 500, description = "Internal server error", body = String),
+        (status = 503, description = "Directory server unreachable", body = String)
+    )
+)]
+pub async fn request_reactivation(
+    State(service): State<AuthService>, Json(request): Json<AuthRequest>,
+) -> impl IntoResponse {
+    // Authenticate with the configured backends
+    let user = match authenticate(&service.db, &request.email, &request.password).await {
+        Ok(user) => user,
+        Err(e) => {
+            return (
+                e.status(),
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+            )
+                .into_response()
+        }
+    };
+
+    if user.activated {
+        return (
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({
+                "error": "Account is not disabled",
+                "code": "account.not_disabled",
+            })),
+        )
+            .into_response();
+    }
+
+    match send_reactivation_link(&service.db, &user).await {
+        Ok(_) => (StatusCode::ACCEPTED, Json(serde_json::json!({}))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::UserModel;
+    use kiro_database::db_bridge::MockDatabaseOperations;
+
+    #[tokio::test]
+    async fn test_request_reactivation_active_account() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![UserModel::default()]));
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Json(AuthRequest {
+            email: "test@example.com".to_string(),
+            password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
+        });
+
+        let response = request_reactivation(State(service), request)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+}
@@ -0,0 +1,295 @@
+// http/auth/restore_account.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use http::HeaderMap;
+use kiro_api::{
+    auth::v1::{AuthRequest, Session},
+    google::protobuf::Timestamp,
+};
+
+use crate::{
+    backends::authenticate,
+    utils::{cookie::session_cookie_headers, ip::get_ip_from_headers},
+    PrivacyPolicy, SessionModel, UserModel,
+};
+
+/// Account restoration route handler
+///
+/// # Description
+/// Authenticates a user whose account is scheduled for deletion, cancels the
+/// deletion and creates a new session
+///
+/// # Arguments
+/// * `service` - The authentication service instance
+/// * `headers` - HTTP headers containing IP address and other metadata
+/// * `request` - The restore request containing email and password
+///
+/// # Returns
+/// * HTTP response with either:
+///   * `200 OK` with Session containing token and expiry, and the session and CSRF cookies set
+///   * Error status code with message
+///
+/// # Errors
+/// * `400 BAD REQUEST` - Invalid password format
+/// * `401 UNAUTHORIZED` - Invalid password
+/// * `403 FORBIDDEN` - Account disabled
+/// * `404 NOT FOUND` - User not found
+/// * `500 INTERNAL SERVER ERROR` - Database or server error
+/// * `503 SERVICE UNAVAILABLE` - Directory server unreachable
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{extract::State, Json};
+/// use http::HeaderMap;
+/// use kiro_api::auth::v1::AuthRequest;
+/// use kiro_client::{AuthService, restore_account::restore_account};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = AuthService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Restore request
+/// let request = Json(AuthRequest {
+///     email: "user@example.com".to_string(),
+///     password: "password123!".to_string(),
+///     invitation_code: None,
+///     accepted_policies: vec![],
+/// });
+///
+/// // Empty headers
+/// let headers = HeaderMap::new();
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     restore_account(State(service), headers, request).await;
+///
+///     println!("Account restored");
+/// });
+/// ```
+#[utoipa::path(
+    post,
+    path = "/auth/restore_account",
+    tag = "auth",
+    params(
+        AuthRequest
+    ),
+    responses(
+        (status = 200, description = "Account restored", body = Session),
+        (status = 400, description = "Invalid password format", body = String),
+        (status = 401, description = "Invalid password", body = String),
+        (status = 403, description = "Account disabled", body = String),
+        (status = 404, description = "User not found", body = String),
+        (status = 500, description = "Internal server error", body = String),
+        (status = 503, description = "Directory server unreachable", body = String)
+
+    )
+)]
+pub async fn restore_account(
+    State(service): State<AuthService>, headers: HeaderMap, Json(request): Json<AuthRequest>,
+) -> impl IntoResponse {
+    // Extract IP address from request metadata
+    let ip_address = get_ip_from_headers(&headers).unwrap_or_else(|| "unknown".to_string());
+
+    // Authenticate with the configured backends
+    let user = match authenticate(&service.db, &request.email, &request.password).await {
+        Ok(user) => user,
+        Err(e) => {
+            return (
+                e.status(),
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+            )
+                .into_response()
+        }
+    };
+
+    // Disabled accounts must be reactivated first
+    if let Err(e) = user.check_active() {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({
+                "error": e.to_string(),
+                "code": e.key(),
+            })),
+        )
+            .into_response();
+    }
+
+    // Cancel the scheduled deletion
+    if user.deletion_scheduled_at.is_some() {
+        if let Err(e) = UserModel::cancel_deletion(&service.db, user.id.clone()).await {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+            )
+                .into_response();
+        }
+    }
+
+    // Create or get existing session
+    let session = match SessionModel::get_session_by_user_id(
+        &service.db,
+        user.id.clone(),
+        ip_address,
+        &PrivacyPolicy::for_user(&user),
+    )
+    .await
+    {
+        Ok(session) => session,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+            )
+                .into_response()
+        }
+    };
+
+    // Generate refresh token
+    let refresh_token = match SessionModel::generate_refresh_token(session.user_id).await {
+        Ok(token) => token,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+            )
+                .into_response()
+        }
+    };
+
+    let expires_at = chrono::Utc::now() + chrono::Duration::days(2);
+    let expire_date: Option<Timestamp> = Some(Timestamp {
+        seconds: expires_at.timestamp(),
+        nanos: 0,
+    });
+
+    // Set browser session cookies
+    let cookies = session_cookie_headers(&refresh_token.1, expires_at);
+
+    // Return session
+    (
+        StatusCode::OK,
+        cookies,
+        Json(Session {
+            token: refresh_token.1,
+            expire_date,
+        }),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{CreateSessionModel, SessionModel, UserModel};
+    use kiro_database::{db_bridge::MockDatabaseOperations, DbDateTime, DbId};
+    use mockall::predicate::{always, eq};
+
+    #[tokio::test]
+    async fn test_restore_account_success() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let test_user = UserModel {
+            deletion_scheduled_at: Some(DbDateTime::now()),
+            ..Default::default()
+        };
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .with(eq("users"), eq("email"), eq("test@example.com"), eq(None))
+            .times(1)
+            .returning(move |_, _, _, _| Ok(vec![test_user.clone()]));
+
+        mock_db
+            .expect_update_field::<Option<DbDateTime>>()
+            .withf(|id, field, value| {
+                *id == DbId::default() && field == "deletion_scheduled_at" && value.is_none()
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        mock_db
+            .expect_read_by_field_thing::<SessionModel>()
+            .with(eq("sessions"), eq("user_id"), eq(DbId::default()), eq(None))
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        let session = SessionModel::default();
+        mock_db
+            .expect_create::<CreateSessionModel, SessionModel>()
+            .with(eq("sessions"), always())
+            .times(1)
+            .return_once(move |_, _| Ok(vec![session]));
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Json(AuthRequest {
+            email: "test@example.com".to_string(),
+            password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
+        });
+
+        let headers = HeaderMap::new();
+        let response = restore_account(State(service), headers, request).await;
+
+        let response = response.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_restore_account_invalid_password() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(vec![UserModel {
+                    deletion_scheduled_at: Some(DbDateTime::now()),
+                    ..Default::default()
+                }])
+            });
+
+        mock_db.expect_update_field::<Option<DbDateTime>>().never();
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Json(AuthRequest {
+            email: "test@example.com".to_string(),
+            password: "WrongPassword123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
+        });
+
+        let headers = HeaderMap::new();
+        let response = restore_account(State(service), headers, request).await;
+
+        let response = response.into_response();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,167 @@
+// http/auth/verify_email.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use kiro_api::auth::v1::VerifyEmailRequest;
+use kiro_mailer::MailerError;
+
+use crate::{error::ClientError, utils::secondary_email::consume_email_verification_link};
+
+/// Email verification route handler
+///
+/// # Description
+/// Verifies a secondary email address from the link emailed to it by
+/// `/user/add_email`. The availability of the address is checked again at this
+/// moment.
+///
+/// # Arguments
+/// * `service` - The authentication service instance
+/// * `request` - The request containing the token of the verification link
+///
+/// # Returns
+/// * HTTP response with either:
+///   * `200 OK` with empty JSON response
+///   * Error status code with message
+///
+/// # Errors
+/// * `400 BAD REQUEST` - The link is not a verification link
+/// * `404 NOT FOUND` - Unknown link, or the address was removed
+/// * `409 CONFLICT` - The email was verified by another account in the meantime
+/// * `410 GONE` - The link has expired
+/// * `500 INTERNAL SERVER ERROR` - Database or server error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{extract::State, Json};
+/// use kiro_api::auth::v1::VerifyEmailRequest;
+/// use kiro_client::{AuthService, verify_email::verify_email};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = AuthService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Verification request
+/// let request = Json(VerifyEmailRequest {
+///     temp_token: "temp_token".to_string(),
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     verify_email(State(service), request).await;
+///
+///     println!("Email verified");
+/// });
+/// ```
+#[utoipa::path(
+    post,
+    path = "/auth/verify_email",
+    tag = "auth",
+    params(
+        VerifyEmailRequest
+    ),
+    responses(
+        (status = 200, description = "Email verified", body = String),
+        (status = 400, description = "Invalid link type", body = String),
+        (status = 404, description = "Link or email not found", body = String),
+        (status = 409, description = "Email already in use", body = String),
+        (status = 410, description = "Link expired", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn verify_email(
+    State(service): State<AuthService>, Json(request): Json<VerifyEmailRequest>,
+) -> impl IntoResponse {
+    match consume_email_verification_link(&service.db, &request.temp_token).await {
+        Ok(_) => (StatusCode::OK, Json(serde_json::json!({}))).into_response(),
+        Err(e) => {
+            let status = match e {
+                ClientError::MailerError(MailerError::NotFound) => StatusCode::NOT_FOUND,
+                ClientError::MailerError(MailerError::InvalidType) => StatusCode::BAD_REQUEST,
+                ClientError::MailerError(MailerError::Expired) => StatusCode::GONE,
+                ClientError::EmailNotFound => StatusCode::NOT_FOUND,
+                ClientError::EmailTaken => StatusCode::CONFLICT,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+
+            (
+                status,
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::{db_bridge::MockDatabaseOperations, DbDateTime, DbId};
+    use kiro_mailer::{LinkModel, LinkType};
+
+    use crate::{UserEmailModel, UserModel};
+
+    #[tokio::test]
+    async fn test_verify_email_taken() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<LinkModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(LinkModel {
+                    id: DbId::from(("links", "link")),
+                    user: DbId::from(("users", "user")),
+                    link_type: LinkType::EmailVerification,
+                    expiry: DbDateTime::from(chrono::Utc::now() + chrono::Duration::hours(1)),
+                }))
+            });
+
+        mock_db
+            .expect_read_where::<UserEmailModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![UserEmailModel::default()]));
+
+        // Another account verified the address in the meantime
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![UserModel::default()]));
+
+        // Both the address and the link are deleted
+        mock_db.expect_delete().times(2).returning(|_| Ok(Some(())));
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = VerifyEmailRequest {
+            temp_token: "link".to_string(),
+        };
+
+        let response = verify_email(State(service), Json(request))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+}
@@ -15,29 +15,65 @@
 // limitations under the License.
 
 mod auth;
+#[cfg(feature = "oauth")]
+mod oauth;
+#[cfg(feature = "scim")]
+pub mod scim;
 mod user;
 
 /// # Auth HTTP1 Routes
 ///
 /// The auth module provides HTTP1 routes for the authentication service.
-pub use auth::{auth_routes, login, logout, register};
+pub use auth::{auth_routes, login, logout, register, restore_account};
+
+/// # Auth HTTP1 Routes (Mailer)
+///
+/// The auth module provides HTTP1 routes for the reactivation of disabled accounts
+/// and the confirmation of email changes.
+#[cfg(feature = "mailer")]
+pub use auth::{
+    cancel_email_change, confirm_email_change, reactivate_account, request_reactivation,
+    verify_email,
+};
 
 /// # User HTTP1 Routes
 ///
 /// The user module provides HTTP1 routes for the user service.
 pub use user::{
-    delete_user, disable_user, read_user, update_email, update_language, update_notifications,
-    update_password, update_privacy, update_security, update_theme, user_routes,
+    accept_policy, create_invitation, delete_user, disable_user, get_changes_since, list_consents,
+    list_emails, reactivate_user, read_user, remove_email, set_primary_email, update_email,
+    update_language, update_notifications, update_password, update_privacy, update_profile,
+    update_security, update_settings, update_theme, user_routes,
 };
 
 /// # User HTTP1 Routes (Mailer)
 ///
 /// The user module provides HTTP1 routes for the user service with mailer support.
 #[cfg(feature = "mailer")]
-pub use user::{send_email_to_change_email, send_email_to_change_password};
+pub use user::{add_email, send_email_to_change_email, send_email_to_change_password};
 
 /// # User HTTP1 Routes (Storage)
 ///
 /// The user module provides HTTP1 routes for the user service with storage support.
 #[cfg(feature = "storage")]
-pub use user::upload_avatar;
+pub use user::{upload_avatar, upload_file};
+
+/// # User HTTP1 Routes (Export)
+///
+/// The user module provides HTTP1 routes for the data exports of users.
+#[cfg(feature = "export")]
+pub use user::request_data_export;
+
+/// # OAuth HTTP1 Routes
+///
+/// The oauth module provides the OAuth2 / OpenID Connect identity provider routes.
+#[cfg(feature = "oauth")]
+pub use oauth::{
+    authorize, consent, discovery, jwks, oauth_routes, register_client, token, userinfo,
+};
+
+/// # SCIM HTTP1 Routes
+///
+/// The scim module provides the SCIM 2.0 provisioning routes.
+#[cfg(feature = "scim")]
+pub use scim::scim_routes;
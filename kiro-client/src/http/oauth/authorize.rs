@@ -0,0 +1,464 @@
+// http/oauth/authorize.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{
+    extract::{Query, RawQuery, State},
+    response::{IntoResponse, Response},
+};
+use http::HeaderMap;
+use kiro_database::get_env_or;
+use serde::Deserialize;
+
+use crate::{
+    error::OAuthError,
+    models::{AuthorizationCodeModel, ConsentModel, GrantType, OAuthClientModel},
+    utils::{
+        cookie::{get_cookie, SESSION_COOKIE},
+        jwt::issuer,
+    },
+    SessionModel, UserModel,
+};
+
+/// Authorization request parameters (RFC 6749 section 4.1.1 and RFC 7636)
+#[derive(Debug, Clone, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuthorizeParams {
+    pub response_type: String,
+    pub client_id: String,
+    pub redirect_uri: String,
+    pub scope: Option<String>,
+    pub state: Option<String>,
+    pub code_challenge: Option<String>,
+    pub code_challenge_method: Option<String>,
+    pub nonce: Option<String>,
+}
+
+/// Redirects an error back to the client as described in RFC 6749 section 4.1.2.1
+fn error_redirect(redirect_uri: &str, state: Option<&str>, error: OAuthError) -> Response {
+    let description = error.to_string();
+    let mut params = vec![
+        ("error", error.code()),
+        ("error_description", description.as_str()),
+    ];
+
+    if let Some(state) = state {
+        params.push(("state", state));
+    }
+
+    redirect_with(redirect_uri, &params)
+}
+
+/// Authorize route handler
+///
+/// # Description
+/// Authorization endpoint of the authorization code grant. PKCE (S256) is required.
+/// The user is identified by the existing Kiro browser session, giving single sign-on:
+/// - without session, the user is sent to the frontend login page
+/// - for a disabled account, the client receives an `access_denied` error
+/// - without consent for the requested scopes, the user is sent to the frontend consent page
+/// - otherwise a code is issued and the user is redirected to the client
+///
+/// # Arguments
+/// * `service` - The authentication service instance
+/// * `headers` - HTTP headers containing the session cookie
+/// * `params` - The authorization request parameters
+/// * `query` - The raw query, forwarded to the login and consent pages
+///
+/// # Returns
+/// * `303 SEE OTHER` - Redirection to the client, the login page or the consent page
+///
+/// # Errors
+/// * `400 BAD REQUEST` - Unregistered redirect URI
+/// * `401 UNAUTHORIZED` - Unknown client
+/// * Other errors are redirected to the client with an `error` parameter
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::extract::{Query, RawQuery, State};
+/// use http::HeaderMap;
+/// use kiro_client::{authorize::{authorize, AuthorizeParams}, AuthService};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock service
+/// let service = AuthService {
+///     db: Database::Mock(MockDatabaseOperations::new()),
+/// };
+///
+/// // Authorization request
+/// let params = AuthorizeParams {
+///     response_type: "code".to_string(),
+///     client_id: "client".to_string(),
+///     redirect_uri: "https://app.example.com/callback".to_string(),
+///     scope: Some("openid email".to_string()),
+///     state: Some("state".to_string()),
+///     code_challenge: Some("E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM".to_string()),
+///     code_challenge_method: Some("S256".to_string()),
+///     nonce: None,
+/// };
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     authorize(State(service), HeaderMap::new(), Query(params), RawQuery(None)).await;
+///
+///     println!("Authorization handled");
+/// });
+/// ```
+#[utoipa::path(
+    get,
+    path = "/oauth/authorize",
+    tag = "oauth",
+    params(
+        AuthorizeParams
+    ),
+    responses(
+        (status = 303, description = "Redirection to the client, the login or the consent page"),
+        (status = 400, description = "Unregistered redirect URI", body = String),
+        (status = 401, description = "Unknown client", body = String)
+    )
+)]
+pub async fn authorize(
+    State(service): State<AuthService>, headers: HeaderMap, Query(params): Query<AuthorizeParams>,
+    RawQuery(query): RawQuery,
+) -> impl IntoResponse {
+    // Unknown clients and redirect URIs are never redirected to
+    let client = match OAuthClientModel::get_client(&service.db, &params.client_id).await {
+        Ok(client) => client,
+        Err(e) => return e.into_response(),
+    };
+
+    if !client.allows_redirect(&params.redirect_uri) {
+        return OAuthError::InvalidRequest("Unregistered redirect_uri".to_string()).into_response();
+    }
+
+    let redirect_uri = params.redirect_uri.as_str();
+    let state = params.state.as_deref();
+
+    if params.response_type != "code" {
+        return error_redirect(redirect_uri, state, OAuthError::UnsupportedResponseType);
+    }
+
+    if !client.allows_grant(GrantType::AuthorizationCode) {
+        return error_redirect(redirect_uri, state, OAuthError::UnauthorizedClient);
+    }
+
+    let code_challenge = match (
+        &params.code_challenge,
+        params.code_challenge_method.as_deref(),
+    ) {
+        (Some(challenge), Some("S256")) if !challenge.is_empty() => challenge.clone(),
+        _ => {
+            return error_redirect(
+                redirect_uri,
+                state,
+                OAuthError::InvalidRequest("PKCE with S256 is required".to_string()),
+            )
+        }
+    };
+
+    let scopes = parse_scopes(params.scope.as_deref());
+    if !client.allows_scopes(&scopes) {
+        return error_redirect(redirect_uri, state, OAuthError::InvalidScope);
+    }
+
+    // Reuse the browser session of the user (single sign-on)
+    let session = match get_cookie(&headers, SESSION_COOKIE) {
+        Some(token) => SessionModel::get_session(&service.db, token)
+            .await
+            .ok()
+            .flatten(),
+        None => None,
+    };
+
+    let front_url = get_env_or("FRONT_URL", "http://localhost:5173");
+    let query = query.unwrap_or_default();
+
+    let session = match session {
+        Some(session) => session,
+        None => {
+            let authorize_url = format!("{}/oauth/authorize?{}", issuer(), query);
+            return redirect_with(
+                &format!("{}/login", front_url),
+                &[("redirect_to", authorize_url.as_str())],
+            );
+        }
+    };
+
+    // Disabled accounts cannot sign in to clients
+    match service
+        .db
+        .select::<UserModel>(session.user_id.clone())
+        .await
+    {
+        Ok(Some(user)) if user.check_active().is_ok() => {}
+        Ok(_) => return error_redirect(redirect_uri, state, OAuthError::AccessDenied),
+        Err(e) => return error_redirect(redirect_uri, state, e.into()),
+    }
+
+    // Ask the user for consent when the scopes were not granted yet
+    let consented =
+        match ConsentModel::get_consent(&service.db, session.user_id.clone(), client.id.clone())
+            .await
+        {
+            Ok(consent) => consent.is_some_and(|consent| consent.covers(&scopes)),
+            Err(e) => return error_redirect(redirect_uri, state, e.into()),
+        };
+
+    if !consented {
+        return axum::response::Redirect::to(&format!("{}/oauth/consent?{}", front_url, query))
+            .into_response();
+    }
+
+    match AuthorizationCodeModel::issue(
+        &service.db,
+        client.id,
+        session.user_id,
+        params.redirect_uri.clone(),
+        scopes,
+        code_challenge,
+        params.nonce.clone(),
+    )
+    .await
+    {
+        Ok(code) => {
+            let mut response_params = vec![("code", code.as_str())];
+            if let Some(state) = state {
+                response_params.push(("state", state));
+            }
+
+            redirect_with(redirect_uri, &response_params)
+        }
+        Err(e) => error_redirect(redirect_uri, state, e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use axum::http::StatusCode;
+    use kiro_database::{db_bridge::MockDatabaseOperations, DbDateTime, DbId};
+    use mockall::predicate::eq;
+
+    fn params() -> AuthorizeParams {
+        AuthorizeParams {
+            response_type: "code".to_string(),
+            client_id: "client".to_string(),
+            redirect_uri: "https://app.example.com/callback".to_string(),
+            scope: Some("openid email".to_string()),
+            state: Some("xyz".to_string()),
+            code_challenge: Some("E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM".to_string()),
+            code_challenge_method: Some("S256".to_string()),
+            nonce: None,
+        }
+    }
+
+    fn mock_client(mock_db: &mut MockDatabaseOperations) {
+        mock_db
+            .expect_select::<OAuthClientModel>()
+            .with(eq(DbId::from(("oauth_clients", "client"))))
+            .times(1)
+            .returning(|_| Ok(Some(OAuthClientModel::default())));
+    }
+
+    fn location(response: &Response) -> String {
+        response
+            .headers()
+            .get(http::header::LOCATION)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_authorize_unknown_client() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<OAuthClientModel>()
+            .times(1)
+            .returning(|_| Ok(None));
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let response = authorize(
+            State(service),
+            HeaderMap::new(),
+            Query(params()),
+            RawQuery(None),
+        )
+        .await
+        .into_response();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_authorize_unregistered_redirect_uri() {
+        let mut mock_db = MockDatabaseOperations::new();
+        mock_client(&mut mock_db);
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut params = params();
+        params.redirect_uri = "https://evil.example.com/callback".to_string();
+
+        let response = authorize(
+            State(service),
+            HeaderMap::new(),
+            Query(params),
+            RawQuery(None),
+        )
+        .await
+        .into_response();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_authorize_requires_pkce() {
+        let mut mock_db = MockDatabaseOperations::new();
+        mock_client(&mut mock_db);
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut params = params();
+        params.code_challenge_method = Some("plain".to_string());
+
+        let response = authorize(
+            State(service),
+            HeaderMap::new(),
+            Query(params),
+            RawQuery(None),
+        )
+        .await
+        .into_response();
+
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert!(location(&response)
+            .starts_with("https://app.example.com/callback?error=invalid_request"));
+        assert!(location(&response).ends_with("&state=xyz"));
+    }
+
+    #[tokio::test]
+    async fn test_authorize_invalid_scope() {
+        let mut mock_db = MockDatabaseOperations::new();
+        mock_client(&mut mock_db);
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut params = params();
+        params.scope = Some("openid admin".to_string());
+
+        let response = authorize(
+            State(service),
+            HeaderMap::new(),
+            Query(params),
+            RawQuery(None),
+        )
+        .await
+        .into_response();
+
+        assert!(
+            location(&response).starts_with("https://app.example.com/callback?error=invalid_scope")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_authorize_without_session_redirects_to_login() {
+        let mut mock_db = MockDatabaseOperations::new();
+        mock_client(&mut mock_db);
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let response = authorize(
+            State(service),
+            HeaderMap::new(),
+            Query(params()),
+            RawQuery(Some("client_id=client".to_string())),
+        )
+        .await
+        .into_response();
+
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert!(location(&response).contains("/login?redirect_to="));
+    }
+
+    #[tokio::test]
+    async fn test_authorize_disabled_user() {
+        let mut mock_db = MockDatabaseOperations::new();
+        mock_client(&mut mock_db);
+
+        let session = SessionModel::default();
+        let (_, token) = SessionModel::generate_refresh_token(session.user_id.clone())
+            .await
+            .unwrap();
+
+        mock_db
+            .expect_read_by_field_thing::<SessionModel>()
+            .times(1)
+            .returning(move |_, _, _, _| Ok(vec![session.clone()]));
+        mock_db
+            .expect_update_field::<DbDateTime>()
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(UserModel {
+                    activated: false,
+                    ..Default::default()
+                }))
+            });
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            http::header::COOKIE,
+            format!("{}={}", SESSION_COOKIE, token).parse().unwrap(),
+        );
+
+        let response = authorize(
+            State(service),
+            headers,
+            Query(params()),
+            RawQuery(Some("client_id=client".to_string())),
+        )
+        .await
+        .into_response();
+
+        assert!(
+            location(&response).starts_with("https://app.example.com/callback?error=access_denied")
+        );
+    }
+}
@@ -0,0 +1,263 @@
+// http/oauth/consent.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::OAuthError,
+    models::{ConsentModel, OAuthClientModel},
+    SessionModel,
+};
+
+/// Consent request sent by the frontend consent page
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ConsentRequest {
+    /// Client the consent is given to
+    pub client_id: String,
+    /// Space separated scopes granted by the user
+    pub scope: Option<String>,
+}
+
+/// Grant consent route handler
+///
+/// # Description
+/// Records the scopes the current user grants to a client. The frontend then
+/// resumes the authorization request.
+///
+/// # Arguments
+/// * `service` - The authentication service instance
+/// * `session` - The current user session
+/// * `request` - The client and the granted scopes
+///
+/// # Returns
+/// * HTTP response with either:
+///   * `200 OK` with empty JSON response
+///   * Error status code with message
+///
+/// # Errors
+/// * `400 BAD REQUEST` - Scope not allowed for the client
+/// * `401 UNAUTHORIZED` - Unknown client
+/// * `500 INTERNAL SERVER ERROR` - Database or server error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{extract::State, Extension, Json};
+/// use kiro_client::{consent::{grant_consent, ConsentRequest}, AuthService, SessionModel};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock service
+/// let service = AuthService {
+///     db: Database::Mock(MockDatabaseOperations::new()),
+/// };
+///
+/// // Consent request
+/// let request = Json(ConsentRequest {
+///     client_id: "client".to_string(),
+///     scope: Some("openid email".to_string()),
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     grant_consent(State(service), Extension(SessionModel::default()), request).await;
+///
+///     println!("Consent granted");
+/// });
+/// ```
+#[utoipa::path(
+    post,
+    path = "/oauth/consent",
+    tag = "oauth",
+    request_body = ConsentRequest,
+    responses(
+        (status = 200, description = "Consent granted", body = String),
+        (status = 400, description = "Scope not allowed", body = String),
+        (status = 401, description = "Unknown client", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn grant_consent(
+    State(service): State<AuthService>, Extension(session): Extension<SessionModel>,
+    Json(request): Json<ConsentRequest>,
+) -> impl IntoResponse {
+    let client = match OAuthClientModel::get_client(&service.db, &request.client_id).await {
+        Ok(client) => client,
+        Err(e) => return e.into_response(),
+    };
+
+    let scopes = parse_scopes(request.scope.as_deref());
+    if !client.allows_scopes(&scopes) {
+        return OAuthError::InvalidScope.into_response();
+    }
+
+    match ConsentModel::grant(&service.db, session.user_id, client.id, scopes).await {
+        Ok(_) => (StatusCode::OK, Json(serde_json::json!({}))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        )
+            .into_response(),
+    }
+}
+
+/// Revoke consent route handler
+///
+/// # Description
+/// Deletes the consent the current user gave to a client
+///
+/// # Arguments
+/// * `service` - The authentication service instance
+/// * `session` - The current user session
+/// * `request` - The client to revoke
+///
+/// # Returns
+/// * HTTP response with either:
+///   * `200 OK` with empty JSON response
+///   * Error status code with message
+///
+/// # Errors
+/// * `500 INTERNAL SERVER ERROR` - Database or server error
+#[utoipa::path(
+    delete,
+    path = "/oauth/consent",
+    tag = "oauth",
+    request_body = ConsentRequest,
+    responses(
+        (status = 200, description = "Consent revoked", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn revoke_consent(
+    State(service): State<AuthService>, Extension(session): Extension<SessionModel>,
+    Json(request): Json<ConsentRequest>,
+) -> impl IntoResponse {
+    let client = kiro_database::DbId::from(("oauth_clients", request.client_id.as_str()));
+
+    match ConsentModel::revoke(&service.db, session.user_id, client).await {
+        Ok(_) => (StatusCode::OK, Json(serde_json::json!({}))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::models::CreateConsentModel;
+    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError, DbDateTime, DbId};
+    use mockall::predicate::{always, eq};
+
+    fn mock_client(mock_db: &mut MockDatabaseOperations) {
+        mock_db
+            .expect_select::<OAuthClientModel>()
+            .with(eq(DbId::from(("oauth_clients", "client"))))
+            .times(1)
+            .returning(|_| Ok(Some(OAuthClientModel::default())));
+    }
+
+    #[tokio::test]
+    async fn test_grant_consent_success() {
+        let mut mock_db = MockDatabaseOperations::new();
+        mock_client(&mut mock_db);
+
+        mock_db
+            .expect_read_where::<ConsentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
+
+        mock_db
+            .expect_create::<CreateConsentModel, ConsentModel>()
+            .with(eq("oauth_consents"), always())
+            .times(1)
+            .returning(|_, data| {
+                Ok(vec![ConsentModel {
+                    id: DbId::from(("oauth_consents", "consent")),
+                    user: data.user,
+                    client: data.client,
+                    scopes: data.scopes,
+                    created_at: DbDateTime::now(),
+                    updated_at: DbDateTime::now(),
+                }])
+            });
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Json(ConsentRequest {
+            client_id: "client".to_string(),
+            scope: Some("openid email".to_string()),
+        });
+
+        let response = grant_consent(State(service), Extension(SessionModel::default()), request)
+            .await
+            .into_response();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_grant_consent_invalid_scope() {
+        let mut mock_db = MockDatabaseOperations::new();
+        mock_client(&mut mock_db);
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Json(ConsentRequest {
+            client_id: "client".to_string(),
+            scope: Some("admin".to_string()),
+        });
+
+        let response = grant_consent(State(service), Extension(SessionModel::default()), request)
+            .await
+            .into_response();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_revoke_consent_database_error() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<ConsentModel>()
+            .times(1)
+            .returning(|_, _, _| Err(DatabaseError::Internal("Database error".to_string())));
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Json(ConsentRequest {
+            client_id: "client".to_string(),
+            scope: None,
+        });
+
+        let response = revoke_consent(State(service), Extension(SessionModel::default()), request)
+            .await
+            .into_response();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
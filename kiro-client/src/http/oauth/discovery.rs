@@ -0,0 +1,93 @@
+// http/oauth/discovery.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{http::StatusCode, response::IntoResponse, Json};
+
+use crate::utils::jwt::issuer;
+
+/// Discovery route handler
+///
+/// # Description
+/// Returns the OpenID Connect discovery document of the provider
+///
+/// # Returns
+/// * HTTP response with `200 OK` and the provider metadata
+///
+/// # Example
+/// ```rust,no_run
+/// use kiro_client::discovery::discovery;
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     discovery().await;
+///
+///     println!("Discovery document returned");
+/// });
+/// ```
+#[utoipa::path(
+    get,
+    path = "/.well-known/openid-configuration",
+    tag = "oauth",
+    responses(
+        (status = 200, description = "OpenID Connect discovery document", body = serde_json::Value)
+    )
+)]
+pub async fn discovery() -> impl IntoResponse {
+    let issuer = issuer();
+
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "issuer": issuer,
+            "authorization_endpoint": format!("{}/oauth/authorize", issuer),
+            "token_endpoint": format!("{}/oauth/token", issuer),
+            "userinfo_endpoint": format!("{}/oauth/userinfo", issuer),
+            "jwks_uri": format!("{}/oauth/jwks", issuer),
+            "registration_endpoint": format!("{}/oauth/clients", issuer),
+            "scopes_supported": SUPPORTED_SCOPES,
+            "response_types_supported": ["code"],
+            "grant_types_supported": ["authorization_code", "client_credentials"],
+            "subject_types_supported": ["public"],
+            "id_token_signing_alg_values_supported": ["RS256"],
+            "token_endpoint_auth_methods_supported": ["client_secret_basic", "client_secret_post", "none"],
+            "code_challenge_methods_supported": ["S256"],
+            "claims_supported": ["iss", "sub", "aud", "iat", "exp", "nonce", "email", "picture", "locale"],
+        })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_discovery() {
+        let response = discovery().await.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let document: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+
+        assert_eq!(document["issuer"], issuer());
+        assert_eq!(document["jwks_uri"], format!("{}/oauth/jwks", issuer()));
+        assert_eq!(document["code_challenge_methods_supported"][0], "S256");
+    }
+}
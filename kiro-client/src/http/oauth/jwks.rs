@@ -0,0 +1,68 @@
+// http/oauth/jwks.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{http::StatusCode, response::IntoResponse, Json};
+
+/// JWKS route handler
+///
+/// # Description
+/// Returns the JSON Web Key Set used to verify the tokens signed by the provider
+///
+/// # Returns
+/// * HTTP response with `200 OK` and the key set
+///
+/// # Example
+/// ```rust,no_run
+/// use kiro_client::jwks::jwks;
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     jwks().await;
+///
+///     println!("Key set returned");
+/// });
+/// ```
+#[utoipa::path(
+    get,
+    path = "/oauth/jwks",
+    tag = "oauth",
+    responses(
+        (status = 200, description = "JSON Web Key Set", body = serde_json::Value)
+    )
+)]
+pub async fn jwks() -> impl IntoResponse {
+    (StatusCode::OK, Json(crate::utils::jwt::jwks())).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_jwks() {
+        let response = jwks().await.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let key_set: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+
+        assert_eq!(key_set["keys"].as_array().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,164 @@
+// http/oauth/mod.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::{
+    response::{IntoResponse, Redirect},
+    routing::{get, post},
+    Router,
+};
+use kiro_database::db_bridge::Database;
+
+pub mod authorize;
+pub mod consent;
+pub mod discovery;
+pub mod jwks;
+pub mod register_client;
+pub mod token;
+pub mod userinfo;
+
+use crate::AuthService;
+
+/// Scopes supported by the identity provider
+pub const SUPPORTED_SCOPES: [&str; 3] = ["openid", "email", "profile"];
+
+/// Splits a space separated scope parameter
+pub(crate) fn parse_scopes(scope: Option<&str>) -> Vec<String> {
+    scope
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(str::to_string)
+        .collect()
+}
+
+/// Redirects to a URI with additional query parameters
+pub(crate) fn redirect_with(uri: &str, params: &[(&str, &str)]) -> axum::response::Response {
+    let separator = if uri.contains('?') { '&' } else { '?' };
+    let query = serde_urlencoded::to_string(params).unwrap_or_default();
+
+    Redirect::to(&format!("{}{}{}", uri, separator, query)).into_response()
+}
+
+/// Creates and configures OAuth2 / OpenID Connect routes
+///
+/// # Arguments
+/// * `db` - Database connection pool
+///
+/// # Returns
+/// Router configured with identity provider endpoints:
+/// - GET /.well-known/openid-configuration - Discovery document
+/// - GET /oauth/jwks - JSON Web Key Set
+/// - GET /oauth/authorize - Authorization endpoint (code + PKCE)
+/// - POST /oauth/consent - Grant consent to a client
+/// - DELETE /oauth/consent - Revoke consent given to a client
+/// - POST /oauth/token - Token endpoint
+/// - GET /oauth/userinfo - UserInfo endpoint
+/// - POST /oauth/clients - Client registration (admin)
+///
+/// # Example
+/// ```rust,no_run
+/// use kiro_client::oauth_routes;
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// oauth_routes(Database::Mock(mock_db));
+///
+/// println!("OAuth routes created");
+/// ```
+pub fn oauth_routes(db: Database) -> Router {
+    let service = AuthService::new(db);
+
+    Router::new()
+        .route(
+            "/.well-known/openid-configuration",
+            get(discovery::discovery),
+        )
+        .route("/oauth/jwks", get(jwks::jwks))
+        .route("/oauth/authorize", get(authorize::authorize))
+        .route(
+            "/oauth/consent",
+            post(consent::grant_consent).delete(consent::revoke_consent),
+        )
+        .route("/oauth/token", post(token::token))
+        .route("/oauth/userinfo", get(userinfo::userinfo))
+        .route("/oauth/clients", post(register_client::register_client))
+        .with_state(service)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{Method, Request, StatusCode},
+    };
+    use kiro_database::db_bridge::MockDatabaseOperations;
+    use tower::ServiceExt;
+
+    #[test]
+    fn test_parse_scopes() {
+        assert_eq!(
+            parse_scopes(Some("openid  email profile")),
+            vec!["openid", "email", "profile"]
+        );
+        assert!(parse_scopes(None).is_empty());
+    }
+
+    #[test]
+    fn test_redirect_with() {
+        let response = redirect_with(
+            "https://app.example.com/callback?tenant=1",
+            &[("code", "abc"), ("state", "a b")],
+        );
+
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(
+            response.headers().get(http::header::LOCATION).unwrap(),
+            "https://app.example.com/callback?tenant=1&code=abc&state=a+b"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_oauth_routes() {
+        let mock_db = MockDatabaseOperations::new();
+        let app = oauth_routes(Database::Mock(mock_db));
+
+        let discovery_response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/.well-known/openid-configuration")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(discovery_response.status(), StatusCode::OK);
+
+        let token_response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/oauth/token")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(token_response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+}
@@ -0,0 +1,318 @@
+// http/oauth/register_client.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::OAuthError,
+    models::{CreateOAuthClientModel, GrantType, OAuthClientModel},
+    SessionModel,
+};
+
+/// Client registration request
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RegisterClientRequest {
+    /// Display name shown on the consent page
+    pub name: String,
+    /// Allowed redirect URIs (exact match)
+    pub redirect_uris: Vec<String>,
+    /// Scopes the client may request
+    pub scopes: Vec<String>,
+    /// Grants the client may use
+    pub grant_types: Vec<GrantType>,
+    /// Whether the client can keep a secret (server side application)
+    pub confidential: bool,
+}
+
+/// Client registration response, the secret is only returned once
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RegisterClientResponse {
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub name: String,
+    pub redirect_uris: Vec<String>,
+    pub scopes: Vec<String>,
+    pub grant_types: Vec<GrantType>,
+}
+
+/// Validates a client registration
+fn validate(request: &RegisterClientRequest) -> Result<(), OAuthError> {
+    if request.name.trim().is_empty() {
+        return Err(OAuthError::InvalidRequest(
+            "Client name is required".to_string(),
+        ));
+    }
+
+    if request.grant_types.is_empty() {
+        return Err(OAuthError::InvalidRequest(
+            "At least one grant type is required".to_string(),
+        ));
+    }
+
+    if request
+        .scopes
+        .iter()
+        .any(|scope| !SUPPORTED_SCOPES.contains(&scope.as_str()))
+    {
+        return Err(OAuthError::InvalidScope);
+    }
+
+    if request.grant_types.contains(&GrantType::AuthorizationCode)
+        && request.redirect_uris.is_empty()
+    {
+        return Err(OAuthError::InvalidRequest(
+            "Authorization code clients need a redirect URI".to_string(),
+        ));
+    }
+
+    if request.grant_types.contains(&GrantType::ClientCredentials) && !request.confidential {
+        return Err(OAuthError::InvalidRequest(
+            "Client credentials clients must be confidential".to_string(),
+        ));
+    }
+
+    let valid_uri = |uri: &String| {
+        (uri.starts_with("https://")
+            || uri.starts_with("http://localhost")
+            || uri.starts_with("http://127.0.0.1"))
+            && !uri.contains('#')
+    };
+
+    if !request.redirect_uris.iter().all(valid_uri) {
+        return Err(OAuthError::InvalidRequest(
+            "Redirect URIs must use https and have no fragment".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Register client route handler
+///
+/// # Description
+/// Registers an application allowed to use Kiro as identity provider. Admin only.
+///
+/// # Arguments
+/// * `service` - The authentication service instance
+/// * `session` - The current admin session
+/// * `request` - The client registration
+///
+/// # Returns
+/// * HTTP response with either:
+///   * `201 CREATED` with the client identifier and, for confidential clients, the secret
+///   * Error status code with message
+///
+/// # Errors
+/// * `400 BAD REQUEST` - Invalid registration
+/// * `403 FORBIDDEN` - Not an admin
+/// * `500 INTERNAL SERVER ERROR` - Database or server error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{extract::State, Extension, Json};
+/// use kiro_client::{
+///     register_client::{register_client, RegisterClientRequest},
+///     AuthService, GrantType, SessionModel,
+/// };
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock service
+/// let service = AuthService {
+///     db: Database::Mock(MockDatabaseOperations::new()),
+/// };
+///
+/// // Registration
+/// let request = Json(RegisterClientRequest {
+///     name: "Dashboard".to_string(),
+///     redirect_uris: vec!["https://dashboard.example.com/callback".to_string()],
+///     scopes: vec!["openid".to_string(), "email".to_string()],
+///     grant_types: vec![GrantType::AuthorizationCode],
+///     confidential: false,
+/// });
+///
+/// // Admin session
+/// let session = SessionModel {
+///     is_admin: true,
+///     ..Default::default()
+/// };
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     register_client(State(service), Extension(session), request).await;
+///
+///     println!("Client registered");
+/// });
+/// ```
+#[utoipa::path(
+    post,
+    path = "/oauth/clients",
+    tag = "oauth",
+    request_body = RegisterClientRequest,
+    responses(
+        (status = 201, description = "Client registered", body = RegisterClientResponse),
+        (status = 400, description = "Invalid registration", body = String),
+        (status = 403, description = "Admin privileges required", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn register_client(
+    State(service): State<AuthService>, Extension(session): Extension<SessionModel>,
+    Json(request): Json<RegisterClientRequest>,
+) -> impl IntoResponse {
+    if !session.is_admin {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({
+                "error": "Admin privileges required",
+                "code": "auth.admin_required",
+            })),
+        )
+            .into_response();
+    }
+
+    if let Err(e) = validate(&request) {
+        return e.into_response();
+    }
+
+    let client = CreateOAuthClientModel {
+        name: request.name,
+        secret_hash: None,
+        redirect_uris: request.redirect_uris,
+        scopes: request.scopes,
+        grant_types: request.grant_types,
+    };
+
+    match OAuthClientModel::register(&service.db, client, request.confidential).await {
+        Ok((client, client_secret)) => (
+            StatusCode::CREATED,
+            Json(RegisterClientResponse {
+                client_id: client.client_id(),
+                client_secret,
+                name: client.name,
+                redirect_uris: client.redirect_uris,
+                scopes: client.scopes,
+                grant_types: client.grant_types,
+            }),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::{db_bridge::MockDatabaseOperations, DbDateTime, DbId};
+    use mockall::predicate::{always, eq};
+
+    fn request(confidential: bool) -> RegisterClientRequest {
+        RegisterClientRequest {
+            name: "Dashboard".to_string(),
+            redirect_uris: vec!["https://dashboard.example.com/callback".to_string()],
+            scopes: vec!["openid".to_string(), "email".to_string()],
+            grant_types: vec![GrantType::AuthorizationCode],
+            confidential,
+        }
+    }
+
+    fn admin() -> SessionModel {
+        SessionModel {
+            is_admin: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate(&request(false)).is_ok());
+
+        let mut invalid = request(false);
+        invalid.redirect_uris = vec!["http://dashboard.example.com/callback".to_string()];
+        assert!(validate(&invalid).is_err());
+
+        let mut invalid = request(false);
+        invalid.scopes = vec!["admin".to_string()];
+        assert!(matches!(validate(&invalid), Err(OAuthError::InvalidScope)));
+
+        let mut invalid = request(false);
+        invalid.grant_types = vec![GrantType::ClientCredentials];
+        assert!(validate(&invalid).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_register_confidential_client() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_create::<CreateOAuthClientModel, OAuthClientModel>()
+            .with(eq("oauth_clients"), always())
+            .times(1)
+            .returning(|_, data| {
+                assert!(data.secret_hash.is_some());
+                Ok(vec![OAuthClientModel {
+                    id: DbId::from(("oauth_clients", "generated")),
+                    name: data.name,
+                    secret_hash: data.secret_hash,
+                    redirect_uris: data.redirect_uris,
+                    scopes: data.scopes,
+                    grant_types: data.grant_types,
+                    created_at: DbDateTime::now(),
+                }])
+            });
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let response = register_client(State(service), Extension(admin()), Json(request(true)))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let client: RegisterClientResponse = serde_json::from_slice(&body_bytes).unwrap();
+
+        assert_eq!(client.client_id, "generated");
+        assert!(client.client_secret.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_register_client_requires_admin() {
+        let service = AuthService {
+            db: Database::Mock(MockDatabaseOperations::new()),
+        };
+
+        let response = register_client(
+            State(service),
+            Extension(SessionModel::default()),
+            Json(request(false)),
+        )
+        .await
+        .into_response();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,558 @@
+// http/oauth/token.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Form, Json};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use http::{
+    header::{AUTHORIZATION, CACHE_CONTROL, PRAGMA},
+    HeaderMap,
+};
+use kiro_database::db_bridge::DatabaseOperations;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::OAuthError,
+    models::{AuthorizationCodeModel, GrantType, OAuthClientModel},
+    utils::jwt::{sign, AccessTokenClaims, IdTokenClaims, TOKEN_TTL},
+    UserModel,
+};
+
+/// Token request parameters (RFC 6749 sections 4.1.3 and 4.4.2)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TokenRequest {
+    pub grant_type: String,
+    pub code: Option<String>,
+    pub redirect_uri: Option<String>,
+    pub code_verifier: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    pub scope: Option<String>,
+}
+
+/// Successful token response (RFC 6749 section 5.1)
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TokenResponse {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: i64,
+    pub scope: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_token: Option<String>,
+}
+
+/// Extracts the client credentials from the `Authorization: Basic` header,
+/// falling back to the request body
+fn client_credentials(
+    headers: &HeaderMap, request: &TokenRequest,
+) -> Result<(String, Option<String>), OAuthError> {
+    let basic = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Basic "));
+
+    if let Some(basic) = basic {
+        let decoded = STANDARD
+            .decode(basic.trim())
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .ok_or(OAuthError::InvalidClient)?;
+
+        let (client_id, secret) = decoded.split_once(':').ok_or(OAuthError::InvalidClient)?;
+
+        return Ok((client_id.to_string(), Some(secret.to_string())));
+    }
+
+    request
+        .client_id
+        .clone()
+        .map(|client_id| (client_id, request.client_secret.clone()))
+        .ok_or(OAuthError::InvalidClient)
+}
+
+/// Exchanges an authorization code for tokens
+async fn authorization_code_grant(
+    service: &AuthService, client: OAuthClientModel, request: TokenRequest,
+) -> Result<TokenResponse, OAuthError> {
+    if !client.allows_grant(GrantType::AuthorizationCode) {
+        return Err(OAuthError::UnauthorizedClient);
+    }
+
+    let (code, redirect_uri, code_verifier) =
+        match (request.code, request.redirect_uri, request.code_verifier) {
+            (Some(code), Some(redirect_uri), Some(code_verifier)) => {
+                (code, redirect_uri, code_verifier)
+            }
+            _ => {
+                return Err(OAuthError::InvalidRequest(
+                    "code, redirect_uri and code_verifier are required".to_string(),
+                ))
+            }
+        };
+
+    let code =
+        AuthorizationCodeModel::consume(&service.db, &code, &client.id, &redirect_uri).await?;
+
+    if !code.verify_pkce(&code_verifier) {
+        return Err(OAuthError::InvalidGrant(
+            "Invalid code verifier".to_string(),
+        ));
+    }
+
+    let user = service
+        .db
+        .select::<UserModel>(code.user.clone())
+        .await?
+        .ok_or_else(|| OAuthError::InvalidGrant("User no longer exists".to_string()))?;
+
+    user.check_active()
+        .map_err(|_| OAuthError::InvalidGrant("User is disabled".to_string()))?;
+
+    let client_id = client.client_id();
+
+    let access_token = sign(&AccessTokenClaims::new(
+        user.id.to_string(),
+        client_id.clone(),
+        &code.scopes,
+    ))?;
+
+    let id_token = if code.scopes.iter().any(|scope| scope == "openid") {
+        Some(sign(&IdTokenClaims::from_user(
+            &user,
+            client_id,
+            &code.scopes,
+            code.nonce.clone(),
+        ))?)
+    } else {
+        None
+    };
+
+    Ok(TokenResponse {
+        access_token,
+        token_type: "Bearer".to_string(),
+        expires_in: TOKEN_TTL,
+        scope: code.scopes.join(" "),
+        id_token,
+    })
+}
+
+/// Issues a token to a confidential client acting on its own behalf
+fn client_credentials_grant(
+    client: OAuthClientModel, request: TokenRequest,
+) -> Result<TokenResponse, OAuthError> {
+    if !client.is_confidential() || !client.allows_grant(GrantType::ClientCredentials) {
+        return Err(OAuthError::UnauthorizedClient);
+    }
+
+    let scopes = parse_scopes(request.scope.as_deref());
+    if !client.allows_scopes(&scopes) {
+        return Err(OAuthError::InvalidScope);
+    }
+
+    let client_id = client.client_id();
+    let access_token = sign(&AccessTokenClaims::new(
+        client_id.clone(),
+        client_id,
+        &scopes,
+    ))?;
+
+    Ok(TokenResponse {
+        access_token,
+        token_type: "Bearer".to_string(),
+        expires_in: TOKEN_TTL,
+        scope: scopes.join(" "),
+        id_token: None,
+    })
+}
+
+/// Token route handler
+///
+/// # Description
+/// Token endpoint supporting the `authorization_code` (with PKCE) and
+/// `client_credentials` grants. Clients authenticate with HTTP Basic or
+/// with `client_id` / `client_secret` in the form body; public clients only
+/// send their `client_id`.
+///
+/// # Arguments
+/// * `service` - The authentication service instance
+/// * `headers` - HTTP headers containing the client credentials
+/// * `request` - The form encoded token request
+///
+/// # Returns
+/// * HTTP response with either:
+///   * `200 OK` with the access token and, for the `openid` scope, the ID token
+///   * Error status code with an RFC 6749 error
+///
+/// # Errors
+/// * `400 BAD REQUEST` - Invalid request, grant or scope
+/// * `401 UNAUTHORIZED` - Client authentication failed
+/// * `500 INTERNAL SERVER ERROR` - Database or signing error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{extract::State, Form};
+/// use http::HeaderMap;
+/// use kiro_client::{token::{token, TokenRequest}, AuthService};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock service
+/// let service = AuthService {
+///     db: Database::Mock(MockDatabaseOperations::new()),
+/// };
+///
+/// // Token request
+/// let request = Form(TokenRequest {
+///     grant_type: "client_credentials".to_string(),
+///     client_id: Some("client".to_string()),
+///     client_secret: Some("secret".to_string()),
+///     ..Default::default()
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     token(State(service), HeaderMap::new(), request).await;
+///
+///     println!("Token issued");
+/// });
+/// ```
+#[utoipa::path(
+    post,
+    path = "/oauth/token",
+    tag = "oauth",
+    request_body(content = TokenRequest, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 200, description = "Tokens issued", body = TokenResponse),
+        (status = 400, description = "Invalid request, grant or scope", body = String),
+        (status = 401, description = "Client authentication failed", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn token(
+    State(service): State<AuthService>, headers: HeaderMap, Form(request): Form<TokenRequest>,
+) -> impl IntoResponse {
+    let result = async {
+        let (client_id, secret) = client_credentials(&headers, &request)?;
+
+        let client = OAuthClientModel::get_client(&service.db, &client_id).await?;
+        client.authenticate(secret).await?;
+
+        match request.grant_type.as_str() {
+            "authorization_code" => authorization_code_grant(&service, client, request).await,
+            "client_credentials" => client_credentials_grant(client, request),
+            _ => Err(OAuthError::UnsupportedGrantType),
+        }
+    }
+    .await;
+
+    match result {
+        Ok(response) => (
+            StatusCode::OK,
+            [(CACHE_CONTROL, "no-store"), (PRAGMA, "no-cache")],
+            Json(response),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{utils::jwt::verify_access_token, SessionModel};
+    use chrono::Utc;
+    use kiro_database::{db_bridge::MockDatabaseOperations, db_query::Condition, DbDateTime, DbId};
+    use mockall::predicate::eq;
+
+    async fn confidential_client(secret: &str) -> OAuthClientModel {
+        let secret_hash = SessionModel::create_password_hash(secret.to_string())
+            .await
+            .unwrap();
+
+        OAuthClientModel {
+            secret_hash: Some(secret_hash),
+            grant_types: vec![GrantType::ClientCredentials],
+            ..Default::default()
+        }
+    }
+
+    fn authorization_code() -> AuthorizationCodeModel {
+        AuthorizationCodeModel {
+            id: DbId::from(("oauth_codes", "code")),
+            code: "code".to_string(),
+            client: DbId::from(("oauth_clients", "client")),
+            user: DbId::default(),
+            redirect_uri: "https://app.example.com/callback".to_string(),
+            scopes: vec!["openid".to_string(), "email".to_string()],
+            code_challenge: "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM".to_string(),
+            nonce: Some("nonce".to_string()),
+            expires_at: DbDateTime::from(Utc::now() + chrono::Duration::seconds(60)),
+        }
+    }
+
+    async fn body(response: axum::response::Response) -> serde_json::Value {
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body_bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_authorization_code_grant_success() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let code = authorization_code();
+
+        mock_db
+            .expect_select::<OAuthClientModel>()
+            .with(eq(DbId::from(("oauth_clients", "client"))))
+            .times(1)
+            .returning(|_| Ok(Some(OAuthClientModel::default())));
+
+        mock_db
+            .expect_read_by_field::<AuthorizationCodeModel>()
+            .with(eq("oauth_codes"), eq("code"), eq("code"), eq(None))
+            .times(1)
+            .returning(move |_, _, _, _| Ok(vec![code.clone()]));
+
+        mock_db
+            .expect_delete_where()
+            .with(eq("oauth_codes"), eq(Condition::eq("code", "code")))
+            .times(1)
+            .returning(|_, _| Ok(1));
+
+        mock_db
+            .expect_select::<UserModel>()
+            .with(eq(DbId::default()))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Form(TokenRequest {
+            grant_type: "authorization_code".to_string(),
+            code: Some("code".to_string()),
+            redirect_uri: Some("https://app.example.com/callback".to_string()),
+            code_verifier: Some("dBjftJeZ4CVP-mB92K1uGEbfxIArLpeUqeyIdKi8wF_s".to_string()),
+            client_id: Some("client".to_string()),
+            ..Default::default()
+        });
+
+        let response = token(State(service), HeaderMap::new(), request)
+            .await
+            .into_response();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers().get(CACHE_CONTROL).unwrap(), "no-store");
+
+        let response: TokenResponse = serde_json::from_value(body(response).await).unwrap();
+        assert_eq!(response.token_type, "Bearer");
+        assert!(response.id_token.is_some());
+
+        let claims = verify_access_token(&response.access_token).unwrap();
+        assert_eq!(claims.sub, DbId::default().to_string());
+        assert!(claims.has_scope("email"));
+    }
+
+    #[tokio::test]
+    async fn test_authorization_code_grant_invalid_verifier() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let code = authorization_code();
+
+        mock_db
+            .expect_select::<OAuthClientModel>()
+            .times(1)
+            .returning(|_| Ok(Some(OAuthClientModel::default())));
+
+        mock_db
+            .expect_read_by_field::<AuthorizationCodeModel>()
+            .times(1)
+            .returning(move |_, _, _, _| Ok(vec![code.clone()]));
+
+        mock_db
+            .expect_delete_where()
+            .times(1)
+            .returning(|_, _| Ok(1));
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Form(TokenRequest {
+            grant_type: "authorization_code".to_string(),
+            code: Some("code".to_string()),
+            redirect_uri: Some("https://app.example.com/callback".to_string()),
+            code_verifier: Some("wrong-verifier".to_string()),
+            client_id: Some("client".to_string()),
+            ..Default::default()
+        });
+
+        let response = token(State(service), HeaderMap::new(), request)
+            .await
+            .into_response();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(body(response).await["error"], "invalid_grant");
+    }
+
+    #[tokio::test]
+    async fn test_authorization_code_grant_disabled_user() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let code = authorization_code();
+
+        mock_db
+            .expect_select::<OAuthClientModel>()
+            .times(1)
+            .returning(|_| Ok(Some(OAuthClientModel::default())));
+
+        mock_db
+            .expect_read_by_field::<AuthorizationCodeModel>()
+            .times(1)
+            .returning(move |_, _, _, _| Ok(vec![code.clone()]));
+
+        mock_db
+            .expect_delete_where()
+            .times(1)
+            .returning(|_, _| Ok(1));
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(UserModel {
+                    activated: false,
+                    ..Default::default()
+                }))
+            });
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Form(TokenRequest {
+            grant_type: "authorization_code".to_string(),
+            code: Some("code".to_string()),
+            redirect_uri: Some("https://app.example.com/callback".to_string()),
+            code_verifier: Some("dBjftJeZ4CVP-mB92K1uGEbfxIArLpeUqeyIdKi8wF_s".to_string()),
+            client_id: Some("client".to_string()),
+            ..Default::default()
+        });
+
+        let response = token(State(service), HeaderMap::new(), request)
+            .await
+            .into_response();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(body(response).await["error"], "invalid_grant");
+    }
+
+    #[tokio::test]
+    async fn test_client_credentials_grant_with_basic_auth() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let client = confidential_client("secret").await;
+
+        mock_db
+            .expect_select::<OAuthClientModel>()
+            .times(1)
+            .returning(move |_| Ok(Some(client.clone())));
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            format!("Basic {}", STANDARD.encode("client:secret"))
+                .parse()
+                .unwrap(),
+        );
+
+        let request = Form(TokenRequest {
+            grant_type: "client_credentials".to_string(),
+            scope: Some("email".to_string()),
+            ..Default::default()
+        });
+
+        let response = token(State(service), headers, request)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response: TokenResponse = serde_json::from_value(body(response).await).unwrap();
+        assert!(response.id_token.is_none());
+
+        let claims = verify_access_token(&response.access_token).unwrap();
+        assert_eq!(claims.sub, "client");
+    }
+
+    #[tokio::test]
+    async fn test_client_credentials_grant_wrong_secret() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let client = confidential_client("secret").await;
+
+        mock_db
+            .expect_select::<OAuthClientModel>()
+            .times(1)
+            .returning(move |_| Ok(Some(client.clone())));
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Form(TokenRequest {
+            grant_type: "client_credentials".to_string(),
+            client_id: Some("client".to_string()),
+            client_secret: Some("wrong".to_string()),
+            ..Default::default()
+        });
+
+        let response = token(State(service), HeaderMap::new(), request)
+            .await
+            .into_response();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(body(response).await["error"], "invalid_client");
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_grant_type() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<OAuthClientModel>()
+            .times(1)
+            .returning(|_| Ok(Some(OAuthClientModel::default())));
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Form(TokenRequest {
+            grant_type: "password".to_string(),
+            client_id: Some("client".to_string()),
+            ..Default::default()
+        });
+
+        let response = token(State(service), HeaderMap::new(), request)
+            .await
+            .into_response();
+
+        assert_eq!(body(response).await["error"], "unsupported_grant_type");
+    }
+}
@@ -0,0 +1,237 @@
+// http/oauth/userinfo.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use http::{header::AUTHORIZATION, HeaderMap};
+use kiro_database::{db_bridge::DatabaseOperations, DbId};
+
+use crate::{error::OAuthError, utils::jwt::verify_access_token, UserModel};
+
+/// UserInfo route handler
+///
+/// # Description
+/// Returns the claims of the user an access token was issued for. The token
+/// must have been granted the `openid` scope; `email` and `profile` add the
+/// matching claims.
+///
+/// # Arguments
+/// * `service` - The authentication service instance
+/// * `headers` - HTTP headers containing the Bearer access token
+///
+/// # Returns
+/// * HTTP response with either:
+///   * `200 OK` with the user claims
+///   * Error status code with an RFC 6750 error
+///
+/// # Errors
+/// * `401 UNAUTHORIZED` - Missing, invalid or expired token
+/// * `500 INTERNAL SERVER ERROR` - Database or server error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::extract::State;
+/// use http::HeaderMap;
+/// use kiro_client::{userinfo::userinfo, AuthService};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock service
+/// let service = AuthService {
+///     db: Database::Mock(MockDatabaseOperations::new()),
+/// };
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     userinfo(State(service), HeaderMap::new()).await;
+///
+///     println!("User claims returned");
+/// });
+/// ```
+#[utoipa::path(
+    get,
+    path = "/oauth/userinfo",
+    tag = "oauth",
+    responses(
+        (status = 200, description = "User claims", body = serde_json::Value),
+        (status = 401, description = "Invalid token", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn userinfo(State(service): State<AuthService>, headers: HeaderMap) -> impl IntoResponse {
+    let result = async {
+        let token = headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or(OAuthError::InvalidToken)?;
+
+        let claims = verify_access_token(token.trim())?;
+        if !claims.has_scope("openid") {
+            return Err(OAuthError::InvalidToken);
+        }
+
+        // Client credentials tokens are not bound to a user
+        let user_id = DbId::try_from(claims.sub.as_str()).map_err(|_| OAuthError::InvalidToken)?;
+        if user_id.tb != "users" {
+            return Err(OAuthError::InvalidToken);
+        }
+
+        let user = service
+            .db
+            .select::<UserModel>(user_id)
+            .await?
+            .ok_or(OAuthError::InvalidToken)?;
+
+        user.check_active().map_err(|_| OAuthError::InvalidToken)?;
+
+        let mut info = serde_json::json!({ "sub": claims.sub });
+
+        if claims.has_scope("email") {
+            info["email"] = serde_json::json!(user.email);
+        }
+
+        if claims.has_scope("profile") {
+            if let Some(avatar) = &user.avatar {
+                info["picture"] = serde_json::json!(avatar);
+            }
+            if let Some(language) = user.settings.language {
+                info["locale"] = serde_json::json!(language.code());
+            }
+        }
+
+        Ok(info)
+    }
+    .await;
+
+    match result {
+        Ok(info) => (StatusCode::OK, Json(info)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::utils::jwt::{sign, AccessTokenClaims};
+    use kiro_database::db_bridge::MockDatabaseOperations;
+    use mockall::predicate::eq;
+
+    fn bearer(sub: &str, scopes: &[&str]) -> HeaderMap {
+        let scopes: Vec<String> = scopes.iter().map(|s| s.to_string()).collect();
+        let token = sign(&AccessTokenClaims::new(
+            sub.to_string(),
+            "client".to_string(),
+            &scopes,
+        ))
+        .unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, format!("Bearer {}", token).parse().unwrap());
+        headers
+    }
+
+    #[tokio::test]
+    async fn test_userinfo_success() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let user = UserModel {
+            id: DbId::from(("users", "test")),
+            ..Default::default()
+        };
+        let sub = user.id.to_string();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .with(eq(user.id.clone()))
+            .times(1)
+            .returning(move |_| Ok(Some(user.clone())));
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let response = userinfo(State(service), bearer(&sub, &["openid", "email"]))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let info: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+
+        assert_eq!(info["sub"], sub);
+        assert_eq!(info["email"], "test@example.com");
+        assert!(info.get("email_verified").is_none());
+        assert!(info.get("picture").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_userinfo_disabled_user() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let user = UserModel {
+            id: DbId::from(("users", "test")),
+            activated: false,
+            ..Default::default()
+        };
+        let sub = user.id.to_string();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(move |_| Ok(Some(user.clone())));
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let response = userinfo(State(service), bearer(&sub, &["openid", "email"]))
+            .await
+            .into_response();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_userinfo_missing_token() {
+        let service = AuthService {
+            db: Database::Mock(MockDatabaseOperations::new()),
+        };
+
+        let response = userinfo(State(service), HeaderMap::new())
+            .await
+            .into_response();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(response
+            .headers()
+            .contains_key(http::header::WWW_AUTHENTICATE));
+    }
+
+    #[tokio::test]
+    async fn test_userinfo_rejects_client_token() {
+        let service = AuthService {
+            db: Database::Mock(MockDatabaseOperations::new()),
+        };
+
+        let response = userinfo(State(service), bearer("client", &["openid"]))
+            .await
+            .into_response();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,330 @@
+// http/scim/filter.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::str::FromStr;
+
+use kiro_database::{
+    db_query::{Condition, TextMatch},
+    DbId,
+};
+use serde_json::Value;
+
+use crate::error::ScimError;
+
+/// Comparison operators supported in filters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operator {
+    Eq,
+    Ne,
+    Co,
+    Sw,
+    Ew,
+    Pr,
+}
+
+impl FromStr for Operator {
+    type Err = ScimError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "eq" => Ok(Operator::Eq),
+            "ne" => Ok(Operator::Ne),
+            "co" => Ok(Operator::Co),
+            "sw" => Ok(Operator::Sw),
+            "ew" => Ok(Operator::Ew),
+            "pr" => Ok(Operator::Pr),
+            _ => Err(ScimError::InvalidFilter(format!(
+                "Unsupported operator: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Single `attribute operator value` expression
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    /// Attribute path, lowercased as SCIM attributes are case insensitive
+    pub attribute: String,
+    pub operator: Operator,
+    /// Compared value, `Null` for the `pr` operator
+    pub value: Value,
+}
+
+/// Column a SCIM attribute is stored in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Column {
+    /// Case insensitive string column
+    Text(&'static str),
+    /// Boolean column
+    Bool(&'static str),
+    /// Record identifier of the given table
+    Id(&'static str),
+}
+
+/// # Filter
+///
+/// Conjunction (`and`) of comparisons, the subset of RFC 7644 section 3.4.2.2
+/// sent by identity providers when looking up users and groups.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Filter(pub Vec<Comparison>);
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Str(String),
+}
+
+/// Splits a filter into words and quoted strings
+fn tokenize(filter: &str) -> Result<Vec<Token>, ScimError> {
+    let mut tokens = Vec::new();
+    let mut chars = filter.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let mut value = String::new();
+
+            loop {
+                match chars.next() {
+                    Some('\\') if chars.peek().is_some() => value.extend(chars.next()),
+                    Some('"') => {
+                        tokens.push(Token::Str(value));
+                        break;
+                    }
+                    Some(c) if c != '\\' => value.push(c),
+                    _ => return Err(ScimError::InvalidFilter("Unterminated string".to_string())),
+                }
+            }
+        } else if c == '(' || c == ')' || c == '[' || c == ']' {
+            return Err(ScimError::InvalidFilter(
+                "Grouping and complex attribute filters are not supported".to_string(),
+            ));
+        } else {
+            let mut word = String::new();
+
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '"' {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+
+            tokens.push(Token::Word(word));
+        }
+    }
+
+    Ok(tokens)
+}
+
+impl FromStr for Filter {
+    type Err = ScimError;
+
+    fn from_str(filter: &str) -> Result<Self, Self::Err> {
+        let mut tokens = tokenize(filter)?.into_iter();
+        let mut comparisons = Vec::new();
+
+        loop {
+            let attribute = match tokens.next() {
+                Some(Token::Word(word)) => word.to_ascii_lowercase(),
+                _ => {
+                    return Err(ScimError::InvalidFilter(
+                        "Expected an attribute".to_string(),
+                    ))
+                }
+            };
+
+            let operator = match tokens.next() {
+                Some(Token::Word(word)) => word.parse::<Operator>()?,
+                _ => return Err(ScimError::InvalidFilter("Expected an operator".to_string())),
+            };
+
+            let value = match operator {
+                Operator::Pr => Value::Null,
+                _ => match tokens.next() {
+                    Some(Token::Str(value)) => Value::String(value),
+                    Some(Token::Word(word)) => match word.as_str() {
+                        "true" => Value::Bool(true),
+                        "false" => Value::Bool(false),
+                        "null" => Value::Null,
+                        _ => serde_json::from_str::<serde_json::Number>(&word)
+                            .map(Value::Number)
+                            .map_err(|_| {
+                                ScimError::InvalidFilter(format!("Invalid value: {}", word))
+                            })?,
+                    },
+                    None => return Err(ScimError::InvalidFilter("Expected a value".to_string())),
+                },
+            };
+
+            comparisons.push(Comparison {
+                attribute,
+                operator,
+                value,
+            });
+
+            match tokens.next() {
+                None => break,
+                Some(Token::Word(word)) if word.eq_ignore_ascii_case("and") => continue,
+                Some(Token::Word(word)) if word.eq_ignore_ascii_case("or") => {
+                    return Err(ScimError::InvalidFilter(
+                        "Only 'and' filters are supported".to_string(),
+                    ))
+                }
+                _ => return Err(ScimError::InvalidFilter("Expected 'and'".to_string())),
+            }
+        }
+
+        Ok(Filter(comparisons))
+    }
+}
+
+impl Filter {
+    /// Translates the filter into a database condition
+    ///
+    /// # Arguments
+    /// * `columns` - Maps a lowercased SCIM attribute to its column
+    ///
+    /// # Returns
+    /// * `Ok(Condition)` - Condition, matching every record for an empty filter
+    /// * `Err(ScimError::InvalidFilter)` - Unknown attribute or mismatching value
+    pub fn to_condition(
+        &self, columns: impl Fn(&str) -> Option<Column>,
+    ) -> Result<Condition, ScimError> {
+        let mut conditions = Vec::new();
+
+        for comparison in &self.0 {
+            let column = columns(&comparison.attribute).ok_or_else(|| {
+                ScimError::InvalidFilter(format!(
+                    "Filtering on {} is not supported",
+                    comparison.attribute
+                ))
+            })?;
+
+            let condition = match (column, comparison.operator, &comparison.value) {
+                (Column::Text(col) | Column::Bool(col), Operator::Pr, _) => Condition::is_set(col),
+                (Column::Text(col), operator, Value::String(value)) => match operator {
+                    Operator::Eq => Condition::text(col, TextMatch::Equals, value),
+                    Operator::Ne => Condition::not(Condition::text(col, TextMatch::Equals, value)),
+                    Operator::Co => Condition::text(col, TextMatch::Contains, value),
+                    Operator::Sw => Condition::text(col, TextMatch::StartsWith, value),
+                    Operator::Ew => Condition::text(col, TextMatch::EndsWith, value),
+                    Operator::Pr => unreachable!(),
+                },
+                (Column::Bool(col), Operator::Eq, Value::Bool(value)) => Condition::eq(col, *value),
+                (Column::Bool(col), Operator::Ne, Value::Bool(value)) => Condition::ne(col, *value),
+                (Column::Id(table), Operator::Eq, Value::String(value)) => {
+                    Condition::eq("id", DbId::from((table, value.as_str())))
+                }
+                (Column::Id(table), Operator::Ne, Value::String(value)) => {
+                    Condition::ne("id", DbId::from((table, value.as_str())))
+                }
+                _ => {
+                    return Err(ScimError::InvalidFilter(format!(
+                        "Invalid comparison on {}",
+                        comparison.attribute
+                    )))
+                }
+            };
+
+            conditions.push(condition);
+        }
+
+        Ok(Condition::And(conditions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns(attribute: &str) -> Option<Column> {
+        match attribute {
+            "username" => Some(Column::Text("email")),
+            "active" => Some(Column::Bool("activated")),
+            "id" => Some(Column::Id("users")),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_parse_filter() {
+        let filter: Filter = r#"userName eq "Jane \"J\" Doe" and active EQ true"#
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            filter.0,
+            vec![
+                Comparison {
+                    attribute: "username".to_string(),
+                    operator: Operator::Eq,
+                    value: Value::String("Jane \"J\" Doe".to_string()),
+                },
+                Comparison {
+                    attribute: "active".to_string(),
+                    operator: Operator::Eq,
+                    value: Value::Bool(true),
+                },
+            ]
+        );
+
+        let filter: Filter = "title pr".parse().unwrap();
+        assert_eq!(filter.0[0].operator, Operator::Pr);
+    }
+
+    #[test]
+    fn test_parse_invalid_filter() {
+        assert!(r#"userName eq "a" or userName eq "b""#.parse::<Filter>().is_err());
+        assert!(r#"emails[type eq "work"]"#.parse::<Filter>().is_err());
+        assert!(r#"userName gt "a""#.parse::<Filter>().is_err());
+        assert!(r#"userName eq "a"#.parse::<Filter>().is_err());
+        assert!("userName eq".parse::<Filter>().is_err());
+    }
+
+    #[test]
+    fn test_to_condition() {
+        let filter: Filter = r#"userName sw "Jane" and active eq false and id eq "abc""#
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            filter.to_condition(columns).unwrap(),
+            Condition::And(vec![
+                Condition::Text(
+                    "email".to_string(),
+                    TextMatch::StartsWith,
+                    "jane".to_string()
+                ),
+                Condition::eq("activated", false),
+                Condition::eq("id", DbId::from(("users", "abc"))),
+            ])
+        );
+        assert_eq!(
+            Filter::default().to_condition(columns).unwrap(),
+            Condition::And(vec![])
+        );
+
+        let filter: Filter = r#"title eq "CEO""#.parse().unwrap();
+        assert!(filter.to_condition(columns).is_err());
+
+        let filter: Filter = r#"active eq "yes""#.parse().unwrap();
+        assert!(filter.to_condition(columns).is_err());
+    }
+}
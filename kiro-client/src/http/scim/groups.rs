@@ -0,0 +1,1024 @@
+// http/scim/groups.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::extract::{Path, Query, State};
+use kiro_database::{
+    db_bridge::{DatabaseOperations, QueryOptions},
+    DbDateTime, DbId,
+};
+use serde_json::Value;
+
+use super::{
+    filter::{Column, Filter, Operator},
+    resources::{ListResponse, PatchRequest, ScimGroup, ScimGroupRequest, ScimMember},
+    users::find_user,
+};
+use crate::{error::ClientError, CreateGroupModel, GroupModel, UserModel};
+
+/// Maps group attributes to their column
+fn group_column(attribute: &str) -> Option<Column> {
+    match attribute {
+        "id" => Some(Column::Id("groups")),
+        "displayname" => Some(Column::Text("name")),
+        _ => None,
+    }
+}
+
+/// Membership change requested by a PATCH operation
+#[derive(Debug, PartialEq)]
+enum MembersChange {
+    Add(Vec<String>),
+    Replace(Vec<String>),
+    Remove(Vec<String>),
+    RemoveAll,
+}
+
+/// Changes requested by a PATCH request
+#[derive(Debug, Default, PartialEq)]
+struct GroupChanges {
+    display_name: Option<String>,
+    members: Vec<MembersChange>,
+}
+
+/// Reads the user identifiers of a `members` value
+fn parse_members(value: Value) -> Result<Vec<String>, ScimError> {
+    let members = match value {
+        Value::Array(members) => members,
+        member @ Value::Object(_) => vec![member],
+        _ => {
+            return Err(ScimError::InvalidValue(
+                "members must be a list of members".to_string(),
+            ))
+        }
+    };
+
+    members
+        .into_iter()
+        .map(|member| {
+            serde_json::from_value::<ScimMember>(member)
+                .map(|member| member.value)
+                .map_err(|e| ScimError::InvalidValue(e.to_string()))
+        })
+        .collect()
+}
+
+/// Reads the user identifier of a `members[value eq "id"]` path
+fn parse_member_path(path: &str) -> Result<String, ScimError> {
+    let filter = path
+        .get(.."members[".len())
+        .filter(|prefix| prefix.eq_ignore_ascii_case("members["))
+        .and_then(|_| path["members[".len()..].strip_suffix(']'))
+        .ok_or_else(|| ScimError::InvalidPath(format!("Unsupported path: {}", path)))?;
+
+    match filter.parse::<Filter>()?.0.as_slice() {
+        [comparison] if comparison.attribute == "value" && comparison.operator == Operator::Eq => {
+            comparison
+                .value
+                .as_str()
+                .map(str::to_string)
+                .ok_or_else(|| ScimError::InvalidPath(format!("Unsupported path: {}", path)))
+        }
+        _ => Err(ScimError::InvalidPath(format!(
+            "Unsupported path: {}",
+            path
+        ))),
+    }
+}
+
+/// Collects the changes of a PATCH request
+fn patch_changes(request: PatchRequest) -> Result<GroupChanges, ScimError> {
+    let mut changes = GroupChanges::default();
+
+    for operation in request.operations {
+        let op = operation.op.to_ascii_lowercase();
+
+        if !matches!(op.as_str(), "add" | "replace" | "remove") {
+            return Err(ScimError::InvalidSyntax(format!(
+                "Unsupported operation: {}",
+                operation.op
+            )));
+        }
+
+        let values: Vec<(String, Value)> = match operation.path {
+            Some(path) => vec![(path, operation.value)],
+            None => match operation.value {
+                Value::Object(values) => values.into_iter().collect(),
+                _ => {
+                    return Err(ScimError::InvalidSyntax(
+                        "Operations without path need an object value".to_string(),
+                    ))
+                }
+            },
+        };
+
+        for (path, value) in values {
+            let attribute = path.to_ascii_lowercase();
+
+            match (op.as_str(), attribute.as_str()) {
+                ("remove", "displayname") => {
+                    return Err(ScimError::InvalidValue(
+                        "displayName cannot be removed".to_string(),
+                    ))
+                }
+                (_, "displayname") => {
+                    changes.display_name =
+                        Some(value.as_str().map(str::to_string).ok_or_else(|| {
+                            ScimError::InvalidValue("displayName must be a string".to_string())
+                        })?)
+                }
+                ("add", "members") => changes
+                    .members
+                    .push(MembersChange::Add(parse_members(value)?)),
+                ("replace", "members") => changes
+                    .members
+                    .push(MembersChange::Replace(parse_members(value)?)),
+                ("remove", "members") if value.is_null() => {
+                    changes.members.push(MembersChange::RemoveAll)
+                }
+                ("remove", "members") => changes
+                    .members
+                    .push(MembersChange::Remove(parse_members(value)?)),
+                ("remove", _) if attribute.starts_with("members[") => changes
+                    .members
+                    .push(MembersChange::Remove(vec![parse_member_path(&path)?])),
+                _ => {
+                    return Err(ScimError::InvalidPath(format!(
+                        "Unsupported path: {}",
+                        path
+                    )))
+                }
+            }
+        }
+    }
+
+    Ok(changes)
+}
+
+/// Retrieves a group by its identifier
+async fn find_group(db: &Database, id: &str) -> Result<GroupModel, ScimError> {
+    GroupModel::get_group(db, id)
+        .await?
+        .ok_or_else(|| ScimError::NotFound(format!("Group {}", id)))
+}
+
+/// Retrieves the users of a membership change
+async fn find_members(db: &Database, ids: &[String]) -> Result<Vec<UserModel>, ScimError> {
+    let mut members = Vec::with_capacity(ids.len());
+
+    for id in ids {
+        match find_user(db, id).await {
+            Ok(user) => members.push(user),
+            Err(ScimError::NotFound(_)) => {
+                return Err(ScimError::InvalidValue(format!("Unknown member: {}", id)))
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(members)
+}
+
+/// Checks a group name is not used by another group
+async fn check_name(db: &Database, name: &str, group: Option<&DbId>) -> Result<(), ScimError> {
+    let groups = db
+        .read_by_field::<GroupModel>("groups", "name", name, None)
+        .await?;
+
+    if groups.iter().any(|existing| Some(&existing.id) != group) {
+        return Err(ScimError::Uniqueness(format!("{} is already in use", name)));
+    }
+
+    Ok(())
+}
+
+/// Applies membership changes and returns the resulting members
+async fn apply_members(
+    db: &Database, group: &GroupModel, mut members: Vec<UserModel>, changes: Vec<MembersChange>,
+) -> Result<Vec<UserModel>, ScimError> {
+    for change in changes {
+        let (added, removed): (Vec<UserModel>, Vec<UserModel>) = match change {
+            MembersChange::Add(ids) => (find_members(db, &ids).await?, vec![]),
+            MembersChange::Replace(ids) => {
+                let wanted = find_members(db, &ids).await?;
+                let removed = members
+                    .iter()
+                    .filter(|member| !wanted.iter().any(|user| user.id == member.id))
+                    .cloned()
+                    .collect();
+
+                (wanted, removed)
+            }
+            MembersChange::Remove(ids) => (
+                vec![],
+                members
+                    .iter()
+                    .filter(|member| ids.contains(&member.id.id.to_string()))
+                    .cloned()
+                    .collect(),
+            ),
+            MembersChange::RemoveAll => (vec![], members.clone()),
+        };
+
+        for user in &removed {
+            GroupModel::remove_member(db, user, group.id.clone()).await?;
+        }
+        members.retain(|member| !removed.iter().any(|user| user.id == member.id));
+
+        for mut user in added {
+            if members.iter().any(|member| member.id == user.id) {
+                continue;
+            }
+
+            GroupModel::add_member(db, &user, group.id.clone()).await?;
+            user.groups.push(group.id.clone());
+            members.push(user);
+        }
+    }
+
+    Ok(members)
+}
+
+/// Applies the changes of a PUT or PATCH request
+async fn apply_changes(
+    db: &Database, mut group: GroupModel, changes: GroupChanges,
+) -> Result<ScimGroup, ScimError> {
+    let mut changed = false;
+
+    if let Some(name) = changes.display_name.filter(|name| *name != group.name) {
+        check_name(db, &name, Some(&group.id)).await?;
+
+        db.update_field(group.id.clone(), "name", name.clone())
+            .await?;
+        group.name = name;
+        changed = true;
+    }
+
+    let mut members = GroupModel::get_members(db, group.id.clone()).await?;
+
+    if !changes.members.is_empty() {
+        members = apply_members(db, &group, members, changes.members).await?;
+        changed = true;
+    }
+
+    if changed {
+        let now = DbDateTime::now();
+
+        db.update_field(group.id.clone(), "updated_at", now.clone())
+            .await?;
+        group.updated_at = now;
+    }
+
+    Ok(ScimGroup::new(&group, &members))
+}
+
+async fn list(db: &Database, query: &ListQuery) -> Result<ListResponse<ScimGroup>, ScimError> {
+    let filter = match query.filter.as_deref().map(str::trim) {
+        Some(filter) if !filter.is_empty() => filter.parse::<Filter>()?,
+        _ => Filter::default(),
+    };
+
+    let condition = filter.to_condition(group_column)?;
+
+    let total = db.count_where("groups", condition.clone()).await? as usize;
+
+    if query.count() == 0 {
+        return Ok(ListResponse::new(vec![], total, query.start_index()));
+    }
+
+    let groups = db
+        .read_where::<GroupModel>(
+            "groups",
+            condition,
+            Some(QueryOptions {
+                order_by: Some("created_at".to_string()),
+                order_direction: None,
+                limit: Some(query.count()),
+                start: Some(query.start_index() - 1),
+            }),
+        )
+        .await?;
+
+    let mut resources = Vec::with_capacity(groups.len());
+
+    for group in &groups {
+        let members = if query.excludes("members") {
+            vec![]
+        } else {
+            GroupModel::get_members(db, group.id.clone()).await?
+        };
+
+        resources.push(ScimGroup::new(group, &members));
+    }
+
+    Ok(ListResponse::new(resources, total, query.start_index()))
+}
+
+async fn create(db: &Database, request: ScimGroupRequest) -> Result<ScimGroup, ScimError> {
+    check_name(db, &request.display_name, None).await?;
+
+    // Members are checked before the group is created
+    let ids: Vec<String> = request
+        .members
+        .into_iter()
+        .map(|member| member.value)
+        .collect();
+    let users = find_members(db, &ids).await?;
+
+    let group = db
+        .create::<CreateGroupModel, GroupModel>(
+            "groups",
+            CreateGroupModel {
+                name: request.display_name,
+            },
+        )
+        .await?
+        .into_iter()
+        .next()
+        .ok_or(ClientError::NotCreated)?;
+
+    let mut members = Vec::with_capacity(users.len());
+
+    for mut user in users {
+        if members
+            .iter()
+            .any(|member: &UserModel| member.id == user.id)
+        {
+            continue;
+        }
+
+        GroupModel::add_member(db, &user, group.id.clone()).await?;
+        user.groups.push(group.id.clone());
+        members.push(user);
+    }
+
+    Ok(ScimGroup::new(&group, &members))
+}
+
+/// List groups route handler
+///
+/// # Description
+/// Lists groups, optionally filtered (`displayName eq "Engineering"`) and paginated
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `query` - Filter and pagination parameters
+///
+/// # Returns
+/// * `200 OK` with a list response of groups
+///
+/// # Errors
+/// * `400 BAD REQUEST` - Invalid or unsupported filter
+/// * `401 UNAUTHORIZED` - Invalid provisioning token
+/// * `500 INTERNAL SERVER ERROR` - Database error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::extract::{Query, State};
+/// use kiro_client::{scim::{groups::list_groups, ListQuery}, ClientService};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(MockDatabaseOperations::new()),
+/// };
+///
+/// // Filter
+/// let query = Query(ListQuery {
+///     filter: Some(r#"displayName eq "Engineering""#.to_string()),
+///     ..Default::default()
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     list_groups(State(service), query).await;
+///
+///     println!("Groups listed");
+/// });
+/// ```
+#[utoipa::path(
+    get,
+    path = "/scim/v2/Groups",
+    tag = "scim",
+    params(ListQuery),
+    responses(
+        (status = 200, description = "Groups", body = ListResponse<ScimGroup>),
+        (status = 400, description = "Invalid filter", body = String),
+        (status = 401, description = "Invalid provisioning token", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn list_groups(
+    State(service): State<ClientService>, Query(query): Query<ListQuery>,
+) -> impl IntoResponse {
+    match list(&service.db, &query).await {
+        Ok(groups) => scim_response(StatusCode::OK, groups),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Get group route handler
+///
+/// # Description
+/// Reads a group and its members
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `id` - Identifier of the group
+///
+/// # Returns
+/// * `200 OK` with the group
+///
+/// # Errors
+/// * `401 UNAUTHORIZED` - Invalid provisioning token
+/// * `404 NOT FOUND` - Unknown group
+/// * `500 INTERNAL SERVER ERROR` - Database error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::extract::{Path, State};
+/// use kiro_client::{scim::groups::get_group, ClientService};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(MockDatabaseOperations::new()),
+/// };
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     get_group(State(service), Path("engineering".to_string())).await;
+///
+///     println!("Group read");
+/// });
+/// ```
+#[utoipa::path(
+    get,
+    path = "/scim/v2/Groups/{id}",
+    tag = "scim",
+    params(("id" = String, Path, description = "Group identifier")),
+    responses(
+        (status = 200, description = "Group", body = ScimGroup),
+        (status = 401, description = "Invalid provisioning token", body = String),
+        (status = 404, description = "Group not found", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn get_group(
+    State(service): State<ClientService>, Path(id): Path<String>,
+) -> impl IntoResponse {
+    let result = match find_group(&service.db, &id).await {
+        Ok(group) => GroupModel::get_members(&service.db, group.id.clone())
+            .await
+            .map(|members| ScimGroup::new(&group, &members))
+            .map_err(ScimError::from),
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(group) => scim_response(StatusCode::OK, group),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Create group route handler
+///
+/// # Description
+/// Creates a group and adds its members
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `request` - The group to create
+///
+/// # Returns
+/// * `201 CREATED` with the group and its location
+///
+/// # Errors
+/// * `400 BAD REQUEST` - Unknown member
+/// * `401 UNAUTHORIZED` - Invalid provisioning token
+/// * `409 CONFLICT` - Name already in use
+/// * `500 INTERNAL SERVER ERROR` - Database error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{extract::State, Json};
+/// use kiro_client::{
+///     scim::{groups::create_group, resources::ScimGroupRequest},
+///     ClientService,
+/// };
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(MockDatabaseOperations::new()),
+/// };
+///
+/// // Group
+/// let request = Json(ScimGroupRequest {
+///     schemas: vec![],
+///     display_name: "Engineering".to_string(),
+///     members: vec![],
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     create_group(State(service), request).await;
+///
+///     println!("Group created");
+/// });
+/// ```
+#[utoipa::path(
+    post,
+    path = "/scim/v2/Groups",
+    tag = "scim",
+    request_body = ScimGroupRequest,
+    responses(
+        (status = 201, description = "Group created", body = ScimGroup),
+        (status = 400, description = "Invalid group", body = String),
+        (status = 401, description = "Invalid provisioning token", body = String),
+        (status = 409, description = "Name already in use", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn create_group(
+    State(service): State<ClientService>, Json(request): Json<ScimGroupRequest>,
+) -> impl IntoResponse {
+    match create(&service.db, request).await {
+        Ok(group) => {
+            let location = HeaderValue::from_str(&group.meta.location).ok();
+
+            let mut response = scim_response(StatusCode::CREATED, group);
+            if let Some(location) = location {
+                response.headers_mut().insert(header::LOCATION, location);
+            }
+
+            response
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Replace group route handler
+///
+/// # Description
+/// Replaces the name and the members of a group
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `id` - Identifier of the group
+/// * `request` - The new state of the group
+///
+/// # Returns
+/// * `200 OK` with the updated group
+///
+/// # Errors
+/// * `400 BAD REQUEST` - Unknown member
+/// * `401 UNAUTHORIZED` - Invalid provisioning token
+/// * `404 NOT FOUND` - Unknown group
+/// * `409 CONFLICT` - Name already in use
+/// * `500 INTERNAL SERVER ERROR` - Database error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{extract::{Path, State}, Json};
+/// use kiro_client::{
+///     scim::{groups::replace_group, resources::ScimGroupRequest},
+///     ClientService,
+/// };
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(MockDatabaseOperations::new()),
+/// };
+///
+/// // Group
+/// let request = Json(ScimGroupRequest {
+///     schemas: vec![],
+///     display_name: "Engineering".to_string(),
+///     members: vec![],
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     replace_group(State(service), Path("engineering".to_string()), request).await;
+///
+///     println!("Group replaced");
+/// });
+/// ```
+#[utoipa::path(
+    put,
+    path = "/scim/v2/Groups/{id}",
+    tag = "scim",
+    params(("id" = String, Path, description = "Group identifier")),
+    request_body = ScimGroupRequest,
+    responses(
+        (status = 200, description = "Group replaced", body = ScimGroup),
+        (status = 400, description = "Invalid group", body = String),
+        (status = 401, description = "Invalid provisioning token", body = String),
+        (status = 404, description = "Group not found", body = String),
+        (status = 409, description = "Name already in use", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn replace_group(
+    State(service): State<ClientService>, Path(id): Path<String>,
+    Json(request): Json<ScimGroupRequest>,
+) -> impl IntoResponse {
+    let changes = GroupChanges {
+        display_name: Some(request.display_name),
+        members: vec![MembersChange::Replace(
+            request
+                .members
+                .into_iter()
+                .map(|member| member.value)
+                .collect(),
+        )],
+    };
+
+    let result = match find_group(&service.db, &id).await {
+        Ok(group) => apply_changes(&service.db, group, changes).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(group) => scim_response(StatusCode::OK, group),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Patch group route handler
+///
+/// # Description
+/// Renames a group or changes its members with SCIM PATCH operations
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `id` - Identifier of the group
+/// * `request` - The PATCH operations
+///
+/// # Returns
+/// * `200 OK` with the updated group
+///
+/// # Errors
+/// * `400 BAD REQUEST` - Invalid operation, path or member
+/// * `401 UNAUTHORIZED` - Invalid provisioning token
+/// * `404 NOT FOUND` - Unknown group
+/// * `409 CONFLICT` - Name already in use
+/// * `500 INTERNAL SERVER ERROR` - Database error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{extract::{Path, State}, Json};
+/// use kiro_client::{
+///     scim::{
+///         groups::patch_group,
+///         resources::{PatchOperation, PatchRequest},
+///     },
+///     ClientService,
+/// };
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(MockDatabaseOperations::new()),
+/// };
+///
+/// // Member removal
+/// let request = Json(PatchRequest {
+///     schemas: vec![],
+///     operations: vec![PatchOperation {
+///         op: "remove".to_string(),
+///         path: Some(r#"members[value eq "jdoe"]"#.to_string()),
+///         value: serde_json::Value::Null,
+///     }],
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     patch_group(State(service), Path("engineering".to_string()), request).await;
+///
+///     println!("Member removed");
+/// });
+/// ```
+#[utoipa::path(
+    patch,
+    path = "/scim/v2/Groups/{id}",
+    tag = "scim",
+    params(("id" = String, Path, description = "Group identifier")),
+    request_body = PatchRequest,
+    responses(
+        (status = 200, description = "Group updated", body = ScimGroup),
+        (status = 400, description = "Invalid operation", body = String),
+        (status = 401, description = "Invalid provisioning token", body = String),
+        (status = 404, description = "Group not found", body = String),
+        (status = 409, description = "Name already in use", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn patch_group(
+    State(service): State<ClientService>, Path(id): Path<String>, Json(request): Json<PatchRequest>,
+) -> impl IntoResponse {
+    let changes = match patch_changes(request) {
+        Ok(changes) => changes,
+        Err(e) => return e.into_response(),
+    };
+
+    let result = match find_group(&service.db, &id).await {
+        Ok(group) => apply_changes(&service.db, group, changes).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(group) => scim_response(StatusCode::OK, group),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Delete group route handler
+///
+/// # Description
+/// Removes the group from its members and deletes it
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `id` - Identifier of the group
+///
+/// # Returns
+/// * `204 NO CONTENT` once deleted
+///
+/// # Errors
+/// * `401 UNAUTHORIZED` - Invalid provisioning token
+/// * `404 NOT FOUND` - Unknown group
+/// * `500 INTERNAL SERVER ERROR` - Database error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::extract::{Path, State};
+/// use kiro_client::{scim::groups::delete_group, ClientService};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(MockDatabaseOperations::new()),
+/// };
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     delete_group(State(service), Path("engineering".to_string())).await;
+///
+///     println!("Group deleted");
+/// });
+/// ```
+#[utoipa::path(
+    delete,
+    path = "/scim/v2/Groups/{id}",
+    tag = "scim",
+    params(("id" = String, Path, description = "Group identifier")),
+    responses(
+        (status = 204, description = "Group deleted"),
+        (status = 401, description = "Invalid provisioning token", body = String),
+        (status = 404, description = "Group not found", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn delete_group(
+    State(service): State<ClientService>, Path(id): Path<String>,
+) -> impl IntoResponse {
+    let db = &service.db;
+
+    let result = async {
+        let group = find_group(db, &id).await?;
+
+        for user in GroupModel::get_members(db, group.id.clone()).await? {
+            GroupModel::remove_member(db, &user, group.id.clone()).await?;
+        }
+
+        db.delete(group.id).await?;
+
+        Ok::<(), ScimError>(())
+    }
+    .await;
+
+    match result {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::db_bridge::MockDatabaseOperations;
+    use mockall::predicate::{always, eq};
+
+    fn group() -> GroupModel {
+        GroupModel {
+            id: DbId::from(("groups", "engineering")),
+            name: "Engineering".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn member() -> UserModel {
+        UserModel {
+            id: DbId::from(("users", "jdoe")),
+            groups: vec![DbId::from(("groups", "engineering"))],
+            ..Default::default()
+        }
+    }
+
+    async fn body(response: Response) -> Value {
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body_bytes).unwrap()
+    }
+
+    #[test]
+    fn test_patch_changes() {
+        let request: PatchRequest = serde_json::from_value(serde_json::json!({
+            "Operations": [
+                { "op": "Replace", "value": { "displayName": "Platform" } },
+                { "op": "Add", "path": "members", "value": [{ "value": "jdoe" }] },
+                { "op": "Remove", "path": "members[value eq \"ASmith\"]" }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            patch_changes(request).unwrap(),
+            GroupChanges {
+                display_name: Some("Platform".to_string()),
+                members: vec![
+                    MembersChange::Add(vec!["jdoe".to_string()]),
+                    MembersChange::Remove(vec!["ASmith".to_string()]),
+                ],
+            }
+        );
+
+        let request: PatchRequest = serde_json::from_value(serde_json::json!({
+            "Operations": [{ "op": "remove", "path": "members[display eq \"Jane\"]" }]
+        }))
+        .unwrap();
+        assert!(patch_changes(request).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_group() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<GroupModel>()
+            .with(eq(DbId::from(("groups", "engineering"))))
+            .times(1)
+            .returning(|_| Ok(Some(group())));
+
+        mock_db
+            .expect_read_where::<UserModel>()
+            .withf(|table, _, _| table == "users")
+            .times(1)
+            .returning(|_, _, _| Ok(vec![member()]));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let response = get_group(State(service), Path("engineering".to_string()))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let group = body(response).await;
+        assert_eq!(group["displayName"], "Engineering");
+        assert_eq!(group["members"][0]["value"], "jdoe");
+    }
+
+    #[tokio::test]
+    async fn test_create_group_conflict() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field::<GroupModel>()
+            .with(eq("groups"), eq("name"), eq("Engineering"), eq(None))
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![group()]));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = ScimGroupRequest {
+            schemas: vec![],
+            display_name: "Engineering".to_string(),
+            members: vec![],
+        };
+
+        let response = create_group(State(service), Json(request))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_patch_group_removes_member() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<GroupModel>()
+            .times(1)
+            .returning(|_| Ok(Some(group())));
+
+        mock_db
+            .expect_read_where::<UserModel>()
+            .withf(|table, _, _| table == "users")
+            .times(1)
+            .returning(|_, _, _| Ok(vec![member()]));
+
+        mock_db
+            .expect_update_field::<Vec<DbId>>()
+            .with(eq(DbId::from(("users", "jdoe"))), eq("groups"), eq(vec![]))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        mock_db
+            .expect_update_field::<DbDateTime>()
+            .with(
+                eq(DbId::from(("groups", "engineering"))),
+                eq("updated_at"),
+                always(),
+            )
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request: PatchRequest = serde_json::from_value(serde_json::json!({
+            "Operations": [{ "op": "remove", "path": "members[value eq \"jdoe\"]" }]
+        }))
+        .unwrap();
+
+        let response = patch_group(
+            State(service),
+            Path("engineering".to_string()),
+            Json(request),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body(response).await["members"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_delete_group() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<GroupModel>()
+            .times(1)
+            .returning(|_| Ok(Some(group())));
+
+        mock_db
+            .expect_read_where::<UserModel>()
+            .withf(|table, _, _| table == "users")
+            .times(1)
+            .returning(|_, _, _| Ok(vec![member()]));
+
+        mock_db
+            .expect_update_field::<Vec<DbId>>()
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        mock_db
+            .expect_delete()
+            .with(eq(DbId::from(("groups", "engineering"))))
+            .times(1)
+            .returning(|_| Ok(Some(())));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let response = delete_group(State(service), Path("engineering".to_string()))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+}
@@ -0,0 +1,242 @@
+// http/scim/mod.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::{
+    extract::Request,
+    http::{header, HeaderValue, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use kiro_database::{db_bridge::Database, get_env_or};
+use serde::{Deserialize, Serialize};
+use subtle::ConstantTimeEq;
+
+pub mod filter;
+pub mod groups;
+pub mod resources;
+pub mod service_provider;
+pub mod users;
+
+use crate::{error::ScimError, ClientService};
+
+/// Page size used when the request has no `count`
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Largest page size a client may request
+const MAX_PAGE_SIZE: usize = 200;
+
+/// Query parameters of list endpoints
+#[derive(Debug, Clone, Default, Serialize, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct ListQuery {
+    /// Filter expression, e.g. `userName eq "jane@example.com"`
+    pub filter: Option<String>,
+    /// 1-based index of the first result
+    pub start_index: Option<usize>,
+    /// Maximum number of results
+    pub count: Option<usize>,
+    /// Comma separated attributes to leave out, e.g. `members`
+    pub excluded_attributes: Option<String>,
+}
+
+impl ListQuery {
+    /// 1-based index of the first result
+    pub fn start_index(&self) -> usize {
+        self.start_index.unwrap_or(1).max(1)
+    }
+
+    /// Number of results of the page
+    pub fn count(&self) -> usize {
+        self.count.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE)
+    }
+
+    /// Whether an attribute was excluded by the client
+    pub fn excludes(&self, attribute: &str) -> bool {
+        self.excluded_attributes
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .any(|excluded| excluded.trim().eq_ignore_ascii_case(attribute))
+    }
+}
+
+/// Serializes a resource with the `application/scim+json` content type
+pub(crate) fn scim_response<T: Serialize>(status: StatusCode, body: T) -> Response {
+    let mut response = (status, Json(body)).into_response();
+
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/scim+json"),
+    );
+
+    response
+}
+
+/// Checks the provisioning bearer token configured in `SCIM_TOKEN`
+///
+/// Provisioning is disabled when no token is configured.
+async fn require_token(request: Request, next: Next) -> Response {
+    let expected = get_env_or("SCIM_TOKEN", "");
+
+    let provided = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .unwrap_or_default();
+
+    let valid = !expected.is_empty() && bool::from(provided.as_bytes().ct_eq(expected.as_bytes()));
+
+    if !valid {
+        return ScimError::Unauthorized.into_response();
+    }
+
+    next.run(request).await
+}
+
+/// Creates and configures SCIM 2.0 provisioning routes
+///
+/// # Arguments
+/// * `db` - Database connection pool
+///
+/// # Returns
+/// Router configured with provisioning endpoints, authenticated by the `SCIM_TOKEN`
+/// bearer token:
+/// - GET /scim/v2/ServiceProviderConfig - Supported features
+/// - GET /scim/v2/Users - List and filter users
+/// - POST /scim/v2/Users - Create user
+/// - GET /scim/v2/Users/:id - Read user
+/// - PUT /scim/v2/Users/:id - Replace user
+/// - PATCH /scim/v2/Users/:id - Update user
+/// - DELETE /scim/v2/Users/:id - Delete user
+/// - GET /scim/v2/Groups - List and filter groups
+/// - POST /scim/v2/Groups - Create group
+/// - GET /scim/v2/Groups/:id - Read group
+/// - PUT /scim/v2/Groups/:id - Replace group
+/// - PATCH /scim/v2/Groups/:id - Update group
+/// - DELETE /scim/v2/Groups/:id - Delete group
+///
+/// # Example
+/// ```rust,no_run
+/// use kiro_client::scim_routes;
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// scim_routes(Database::Mock(mock_db));
+///
+/// println!("SCIM routes created");
+/// ```
+pub fn scim_routes(db: Database) -> Router {
+    let service = ClientService::new(db);
+
+    Router::new()
+        .route(
+            "/scim/v2/ServiceProviderConfig",
+            get(service_provider::service_provider_config),
+        )
+        .route(
+            "/scim/v2/Users",
+            get(users::list_users).post(users::create_user),
+        )
+        .route(
+            "/scim/v2/Users/:id",
+            get(users::get_user)
+                .put(users::replace_user)
+                .patch(users::patch_user)
+                .delete(users::delete_user),
+        )
+        .route(
+            "/scim/v2/Groups",
+            get(groups::list_groups).post(groups::create_group),
+        )
+        .route(
+            "/scim/v2/Groups/:id",
+            get(groups::get_group)
+                .put(groups::replace_group)
+                .patch(groups::patch_group)
+                .delete(groups::delete_group),
+        )
+        .route_layer(middleware::from_fn(require_token))
+        .with_state(service)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Method};
+    use kiro_database::db_bridge::MockDatabaseOperations;
+    use tower::ServiceExt;
+
+    #[test]
+    fn test_list_query() {
+        let query = ListQuery::default();
+        assert_eq!(query.start_index(), 1);
+        assert_eq!(query.count(), DEFAULT_PAGE_SIZE);
+
+        let query = ListQuery {
+            start_index: Some(0),
+            count: Some(1000),
+            excluded_attributes: Some("meta, Members".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(query.start_index(), 1);
+        assert_eq!(query.count(), MAX_PAGE_SIZE);
+        assert!(query.excludes("members"));
+        assert!(!query.excludes("emails"));
+    }
+
+    #[tokio::test]
+    async fn test_scim_routes_require_token() {
+        std::env::set_var("SCIM_TOKEN", "provisioning-token");
+
+        let app = scim_routes(Database::Mock(MockDatabaseOperations::new()));
+
+        let response = app
+            .clone()
+            .oneshot(
+                http::Request::builder()
+                    .method(Method::GET)
+                    .uri("/scim/v2/Users")
+                    .header(header::AUTHORIZATION, "Bearer wrong-token")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/scim+json"
+        );
+
+        let response = app
+            .oneshot(
+                http::Request::builder()
+                    .method(Method::GET)
+                    .uri("/scim/v2/ServiceProviderConfig")
+                    .header(header::AUTHORIZATION, "Bearer provisioning-token")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,314 @@
+// http/scim/resources.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{DateTime, Utc};
+use kiro_database::{get_env_or, DbDateTime};
+use serde::{Deserialize, Serialize};
+
+use crate::{GroupModel, UserModel};
+
+/// Schema of the user resource
+pub const USER_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:User";
+
+/// Schema of the group resource
+pub const GROUP_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:Group";
+
+/// Schema of list responses
+pub const LIST_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:ListResponse";
+
+/// Schema of PATCH requests
+pub const PATCH_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:PatchOp";
+
+/// Base URL used in resource locations
+fn base_url() -> String {
+    get_env_or("SCIM_BASE_URL", "https://localhost:3000/scim/v2")
+}
+
+/// Formats a database timestamp as RFC 3339
+fn timestamp(value: &DbDateTime) -> String {
+    DateTime::<Utc>::from(value.clone()).to_rfc3339()
+}
+
+/// Resource metadata
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimMeta {
+    pub resource_type: String,
+    pub created: String,
+    pub last_modified: String,
+    pub location: String,
+}
+
+/// Email address of a user
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ScimEmail {
+    pub value: String,
+    #[serde(default)]
+    pub primary: bool,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+}
+
+/// Reference to a group (on users) or a user (on groups)
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ScimMember {
+    pub value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<String>,
+    #[serde(rename = "$ref", default, skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+}
+
+/// # SCIM User
+///
+/// User resource, `userName` is the email address of the `UserModel` and
+/// `externalId` the directory entry the user is bound to.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimUser {
+    pub schemas: Vec<String>,
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    pub user_name: String,
+    pub active: bool,
+    pub emails: Vec<ScimEmail>,
+    pub groups: Vec<ScimMember>,
+    pub meta: ScimMeta,
+}
+
+impl From<&UserModel> for ScimUser {
+    fn from(user: &UserModel) -> Self {
+        let id = user.id.id.to_string();
+
+        Self {
+            schemas: vec![USER_SCHEMA.to_string()],
+            external_id: user.directory_id.clone(),
+            user_name: user.email.clone(),
+            active: user.activated,
+            emails: vec![ScimEmail {
+                value: user.email.clone(),
+                primary: true,
+                kind: Some("work".to_string()),
+            }],
+            groups: user
+                .groups
+                .iter()
+                .map(|group| {
+                    let group = group.id.to_string();
+
+                    ScimMember {
+                        reference: Some(format!("{}/Groups/{}", base_url(), group)),
+                        value: group,
+                        display: None,
+                    }
+                })
+                .collect(),
+            meta: ScimMeta {
+                resource_type: "User".to_string(),
+                created: timestamp(&user.created_at),
+                last_modified: timestamp(&user.updated_at),
+                location: format!("{}/Users/{}", base_url(), id),
+            },
+            id,
+        }
+    }
+}
+
+/// User creation and replacement request
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimUserRequest {
+    #[serde(default)]
+    pub schemas: Vec<String>,
+    #[serde(default)]
+    pub external_id: Option<String>,
+    pub user_name: String,
+    #[serde(default)]
+    pub active: Option<bool>,
+    #[serde(default)]
+    pub emails: Vec<ScimEmail>,
+    #[serde(default)]
+    pub password: Option<String>,
+}
+
+impl ScimUserRequest {
+    /// Email address of the user, the primary email or the user name
+    pub fn email(&self) -> String {
+        self.emails
+            .iter()
+            .find(|email| email.primary)
+            .map(|email| email.value.clone())
+            .unwrap_or_else(|| self.user_name.clone())
+    }
+}
+
+/// # SCIM Group
+///
+/// Group resource, members are the users having the group in `UserModel.groups`.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimGroup {
+    pub schemas: Vec<String>,
+    pub id: String,
+    pub display_name: String,
+    pub members: Vec<ScimMember>,
+    pub meta: ScimMeta,
+}
+
+impl ScimGroup {
+    /// Builds the group resource from the group and its members
+    pub fn new(group: &GroupModel, members: &[UserModel]) -> Self {
+        let id = group.id.id.to_string();
+
+        Self {
+            schemas: vec![GROUP_SCHEMA.to_string()],
+            display_name: group.name.clone(),
+            members: members
+                .iter()
+                .map(|user| {
+                    let user_id = user.id.id.to_string();
+
+                    ScimMember {
+                        reference: Some(format!("{}/Users/{}", base_url(), user_id)),
+                        value: user_id,
+                        display: Some(user.email.clone()),
+                    }
+                })
+                .collect(),
+            meta: ScimMeta {
+                resource_type: "Group".to_string(),
+                created: timestamp(&group.created_at),
+                last_modified: timestamp(&group.updated_at),
+                location: format!("{}/Groups/{}", base_url(), id),
+            },
+            id,
+        }
+    }
+}
+
+/// Group creation and replacement request
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimGroupRequest {
+    #[serde(default)]
+    pub schemas: Vec<String>,
+    pub display_name: String,
+    #[serde(default)]
+    pub members: Vec<ScimMember>,
+}
+
+/// Paginated list of resources
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListResponse<T> {
+    pub schemas: Vec<String>,
+    pub total_results: usize,
+    pub start_index: usize,
+    pub items_per_page: usize,
+    #[serde(rename = "Resources")]
+    pub resources: Vec<T>,
+}
+
+impl<T> ListResponse<T> {
+    /// Builds a list response for a page of resources
+    pub fn new(resources: Vec<T>, total_results: usize, start_index: usize) -> Self {
+        Self {
+            schemas: vec![LIST_SCHEMA.to_string()],
+            total_results,
+            start_index,
+            items_per_page: resources.len(),
+            resources,
+        }
+    }
+}
+
+/// Single PATCH operation
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PatchOperation {
+    /// `add`, `replace` or `remove` (case insensitive)
+    pub op: String,
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub value: serde_json::Value,
+}
+
+/// PATCH request
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PatchRequest {
+    #[serde(default)]
+    pub schemas: Vec<String>,
+    #[serde(rename = "Operations")]
+    pub operations: Vec<PatchOperation>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kiro_database::DbId;
+
+    #[test]
+    fn test_user_resource() {
+        let user = UserModel {
+            id: DbId::from(("users", "jdoe")),
+            groups: vec![DbId::from(("groups", "engineering"))],
+            ..Default::default()
+        };
+
+        let resource = serde_json::to_value(ScimUser::from(&user)).unwrap();
+
+        assert_eq!(resource["schemas"][0], USER_SCHEMA);
+        assert_eq!(resource["id"], "jdoe");
+        assert_eq!(resource["userName"], "test@example.com");
+        assert_eq!(resource["active"], true);
+        assert_eq!(resource["emails"][0]["primary"], true);
+        assert_eq!(resource["groups"][0]["value"], "engineering");
+        assert_eq!(resource["meta"]["resourceType"], "User");
+        assert!(resource["meta"]["location"]
+            .as_str()
+            .unwrap()
+            .ends_with("/Users/jdoe"));
+    }
+
+    #[test]
+    fn test_user_request_email() {
+        let request: ScimUserRequest = serde_json::from_value(serde_json::json!({
+            "schemas": [USER_SCHEMA],
+            "userName": "jdoe",
+            "name": { "givenName": "Jane" },
+            "emails": [
+                { "value": "jane@home.example.com", "type": "home" },
+                { "value": "jane@example.com", "type": "work", "primary": true }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(request.email(), "jane@example.com");
+    }
+
+    #[test]
+    fn test_list_response() {
+        let list = serde_json::to_value(ListResponse::new(vec![1, 2], 10, 3)).unwrap();
+
+        assert_eq!(list["schemas"][0], LIST_SCHEMA);
+        assert_eq!(list["totalResults"], 10);
+        assert_eq!(list["startIndex"], 3);
+        assert_eq!(list["itemsPerPage"], 2);
+        assert_eq!(list["Resources"][1], 2);
+    }
+}
@@ -0,0 +1,85 @@
+// http/scim/service_provider.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// Service provider configuration route handler
+///
+/// # Description
+/// Describes the SCIM features supported by Kiro (RFC 7643 section 5)
+///
+/// # Returns
+/// * `200 OK` with the service provider configuration
+///
+/// # Example
+/// ```rust,no_run
+/// use kiro_client::scim::service_provider::service_provider_config;
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     service_provider_config().await;
+///
+///     println!("Service provider configuration returned");
+/// });
+/// ```
+#[utoipa::path(
+    get,
+    path = "/scim/v2/ServiceProviderConfig",
+    tag = "scim",
+    responses(
+        (status = 200, description = "Service provider configuration", body = Object),
+        (status = 401, description = "Invalid provisioning token", body = String)
+    )
+)]
+pub async fn service_provider_config() -> impl IntoResponse {
+    scim_response(
+        StatusCode::OK,
+        serde_json::json!({
+            "schemas": ["urn:ietf:params:scim:schemas:core:2.0:ServiceProviderConfig"],
+            "patch": { "supported": true },
+            "bulk": { "supported": false, "maxOperations": 0, "maxPayloadSize": 0 },
+            "filter": { "supported": true, "maxResults": MAX_PAGE_SIZE },
+            "changePassword": { "supported": true },
+            "sort": { "supported": false },
+            "etag": { "supported": false },
+            "authenticationSchemes": [{
+                "type": "oauthbearertoken",
+                "name": "Provisioning token",
+                "description": "Bearer token configured in SCIM_TOKEN",
+                "primary": true
+            }]
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_service_provider_config() {
+        let response = service_provider_config().await.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let config: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+
+        assert_eq!(config["patch"]["supported"], true);
+        assert_eq!(config["filter"]["maxResults"], MAX_PAGE_SIZE);
+    }
+}
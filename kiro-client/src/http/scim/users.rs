@@ -0,0 +1,1201 @@
+// http/scim/users.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::extract::{Path, Query, State};
+use kiro_database::{
+    db_bridge::{DatabaseOperations, QueryOptions},
+    DbDateTime, DbId,
+};
+use serde_json::Value;
+
+use super::{
+    filter::{Column, Filter},
+    resources::{ListResponse, PatchRequest, ScimUser, ScimUserRequest},
+};
+use crate::{
+    error::ClientError,
+    utils::{
+        password::{random_password, valid_password},
+        purge::purge_user,
+    },
+    CreateUserModel, SessionModel, UserModel,
+};
+
+/// Maps user attributes to their column
+fn user_column(attribute: &str) -> Option<Column> {
+    match attribute {
+        "id" => Some(Column::Id("users")),
+        "username" | "emails" | "emails.value" => Some(Column::Text("email")),
+        "active" => Some(Column::Bool("activated")),
+        "externalid" => Some(Column::Text("directory_id")),
+        _ => None,
+    }
+}
+
+/// Changes requested by a PUT or PATCH request
+#[derive(Debug, Default, PartialEq)]
+struct UserChanges {
+    external_id: Option<Option<String>>,
+    user_name: Option<String>,
+    email: Option<String>,
+    active: Option<bool>,
+    password: Option<String>,
+}
+
+/// Reads a boolean, identity providers may send `"True"` / `"False"` strings
+fn parse_bool(value: &Value) -> Result<bool, ScimError> {
+    match value {
+        Value::Bool(value) => Ok(*value),
+        Value::String(value) if value.eq_ignore_ascii_case("true") => Ok(true),
+        Value::String(value) if value.eq_ignore_ascii_case("false") => Ok(false),
+        _ => Err(ScimError::InvalidValue(
+            "active must be a boolean".to_string(),
+        )),
+    }
+}
+
+/// Reads a string attribute
+fn parse_string(value: &Value, attribute: &str) -> Result<String, ScimError> {
+    value
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| ScimError::InvalidValue(format!("{} must be a string", attribute)))
+}
+
+/// Reads the primary address of an `emails` value
+fn parse_email(value: &Value) -> Result<String, ScimError> {
+    match value {
+        Value::String(email) => Ok(email.clone()),
+        Value::Array(emails) => emails
+            .iter()
+            .find(|email| email["primary"] == Value::Bool(true))
+            .or_else(|| emails.first())
+            .and_then(|email| email["value"].as_str())
+            .map(str::to_string)
+            .ok_or_else(|| ScimError::InvalidValue("emails has no value".to_string())),
+        _ => Err(ScimError::InvalidValue(
+            "emails must be a list of addresses".to_string(),
+        )),
+    }
+}
+
+/// Collects the changes of a PATCH request
+///
+/// Attributes Kiro does not store (name, title...) are ignored so identity
+/// providers can keep sending their full mapping.
+fn patch_changes(request: PatchRequest) -> Result<UserChanges, ScimError> {
+    let mut changes = UserChanges::default();
+
+    for operation in request.operations {
+        let op = operation.op.to_ascii_lowercase();
+
+        if !matches!(op.as_str(), "add" | "replace" | "remove") {
+            return Err(ScimError::InvalidSyntax(format!(
+                "Unsupported operation: {}",
+                operation.op
+            )));
+        }
+
+        let values: Vec<(String, Value)> = match operation.path {
+            Some(path) => vec![(path.to_ascii_lowercase(), operation.value)],
+            None => match operation.value {
+                Value::Object(values) => values
+                    .into_iter()
+                    .map(|(attribute, value)| (attribute.to_ascii_lowercase(), value))
+                    .collect(),
+                _ => {
+                    return Err(ScimError::InvalidSyntax(
+                        "Operations without path need an object value".to_string(),
+                    ))
+                }
+            },
+        };
+
+        for (path, value) in values {
+            let attribute = if path.starts_with("emails") {
+                "emails"
+            } else {
+                path.as_str()
+            };
+
+            match attribute {
+                "active" | "username" | "emails" | "password" if op == "remove" => {
+                    return Err(ScimError::InvalidValue(format!(
+                        "{} cannot be removed",
+                        path
+                    )))
+                }
+                "externalid" if op == "remove" => changes.external_id = Some(None),
+                "externalid" => changes.external_id = Some(Some(parse_string(&value, &path)?)),
+                "active" => changes.active = Some(parse_bool(&value)?),
+                "username" => changes.user_name = Some(parse_string(&value, &path)?),
+                "emails" => changes.email = Some(parse_email(&value)?),
+                "password" => changes.password = Some(parse_string(&value, &path)?),
+                _ => {}
+            }
+        }
+    }
+
+    Ok(changes)
+}
+
+/// Checks an email address sent by the identity provider
+fn validate_email(email: &str) -> Result<(), ScimError> {
+    match email.split_once('@') {
+        Some((local, domain)) if !local.is_empty() && domain.contains('.') => Ok(()),
+        _ => Err(ScimError::InvalidValue(format!(
+            "{} is not an email address",
+            email
+        ))),
+    }
+}
+
+/// Hashes the password sent by the identity provider, or a random one
+async fn password_hash(password: Option<String>) -> Result<String, ScimError> {
+    let password = match password {
+        Some(password) => {
+            valid_password(&password).map_err(|e| ScimError::InvalidValue(e.to_string()))?;
+            password
+        }
+        None => random_password(),
+    };
+
+    Ok(SessionModel::create_password_hash(password).await?)
+}
+
+/// Binds a user to a directory entry, or unbinds it
+///
+/// The LDAP backend only logs in accounts bound to the immutable id of their
+/// directory entry, this is how existing local accounts are linked.
+async fn bind_directory(
+    db: &Database, user: &mut UserModel, external_id: Option<String>,
+) -> Result<(), ScimError> {
+    if let Some(external_id) = &external_id {
+        if UserModel::get_user_by_directory_id(db, external_id)
+            .await?
+            .is_some_and(|bound| bound.id != user.id)
+        {
+            return Err(ScimError::Uniqueness(format!(
+                "externalId {} is already in use",
+                external_id
+            )));
+        }
+    }
+
+    db.update_field(user.id.clone(), "directory_id", external_id.clone())
+        .await?;
+    user.directory_id = external_id;
+
+    Ok(())
+}
+
+/// Retrieves a user by its identifier
+pub(crate) async fn find_user(db: &Database, id: &str) -> Result<UserModel, ScimError> {
+    db.select::<UserModel>(DbId::from(("users", id)))
+        .await?
+        .ok_or_else(|| ScimError::NotFound(format!("User {}", id)))
+}
+
+/// Activates a user, or deactivates it with a soft delete
+async fn set_active(db: &Database, user: &mut UserModel, active: bool) -> Result<(), ScimError> {
+    if active {
+        UserModel::reactivate(db, user.id.clone(), None).await?;
+    } else {
+        UserModel::disable(db, user.id.clone(), None).await?;
+    }
+
+    user.activated = active;
+
+    Ok(())
+}
+
+/// Applies the changes of a PUT or PATCH request
+async fn apply_changes(
+    db: &Database, mut user: UserModel, changes: UserChanges,
+) -> Result<UserModel, ScimError> {
+    let mut changed = false;
+
+    if let Some(email) = changes
+        .email
+        .or(changes.user_name)
+        .filter(|email| *email != user.email)
+    {
+        validate_email(&email)?;
+
+        if !UserModel::check_email(db, email.clone()).await? {
+            return Err(ScimError::Uniqueness(format!(
+                "{} is already in use",
+                email
+            )));
+        }
+
+        db.update_field(user.id.clone(), "email", email.clone())
+            .await?;
+        user.email = email;
+        changed = true;
+    }
+
+    if let Some(password) = changes.password {
+        let hash = password_hash(Some(password)).await?;
+
+        db.update_field(user.id.clone(), "password_hash", hash.clone())
+            .await?;
+        user.password_hash = hash;
+        changed = true;
+    }
+
+    if let Some(external_id) = changes
+        .external_id
+        .filter(|external_id| *external_id != user.directory_id)
+    {
+        bind_directory(db, &mut user, external_id).await?;
+        changed = true;
+    }
+
+    if let Some(active) = changes.active.filter(|active| *active != user.activated) {
+        set_active(db, &mut user, active).await?;
+        changed = true;
+    }
+
+    if changed {
+        let now = DbDateTime::now();
+
+        db.update_field(user.id.clone(), "updated_at", now.clone())
+            .await?;
+        user.updated_at = now;
+    }
+
+    Ok(user)
+}
+
+async fn list(db: &Database, query: &ListQuery) -> Result<ListResponse<ScimUser>, ScimError> {
+    let filter = match query.filter.as_deref().map(str::trim) {
+        Some(filter) if !filter.is_empty() => filter.parse::<Filter>()?,
+        _ => Filter::default(),
+    };
+
+    let condition = filter.to_condition(user_column)?;
+
+    let total = db.count_where("users", condition.clone()).await? as usize;
+
+    if query.count() == 0 {
+        return Ok(ListResponse::new(vec![], total, query.start_index()));
+    }
+
+    let users = db
+        .read_where::<UserModel>(
+            "users",
+            condition,
+            Some(QueryOptions {
+                order_by: Some("created_at".to_string()),
+                order_direction: None,
+                limit: Some(query.count()),
+                start: Some(query.start_index() - 1),
+            }),
+        )
+        .await?;
+
+    Ok(ListResponse::new(
+        users.iter().map(ScimUser::from).collect(),
+        total,
+        query.start_index(),
+    ))
+}
+
+async fn create(db: &Database, request: ScimUserRequest) -> Result<UserModel, ScimError> {
+    let email = request.email();
+    validate_email(&email)?;
+
+    if !UserModel::check_email(db, email.clone()).await? {
+        return Err(ScimError::Uniqueness(format!(
+            "{} is already in use",
+            email
+        )));
+    }
+
+    if let Some(external_id) = &request.external_id {
+        if UserModel::get_user_by_directory_id(db, external_id)
+            .await?
+            .is_some()
+        {
+            return Err(ScimError::Uniqueness(format!(
+                "externalId {} is already in use",
+                external_id
+            )));
+        }
+    }
+
+    let mut user = db
+        .create::<CreateUserModel, UserModel>(
+            "users",
+            CreateUserModel {
+                email,
+                password_hash: password_hash(request.password).await?,
+            },
+        )
+        .await?
+        .into_iter()
+        .next()
+        .ok_or(ClientError::NotCreated)?;
+
+    if let Some(external_id) = request.external_id {
+        bind_directory(db, &mut user, Some(external_id)).await?;
+    }
+
+    // Provisioned users are active unless stated otherwise
+    let active = request.active.unwrap_or(true);
+    if user.activated != active {
+        set_active(db, &mut user, active).await?;
+    }
+
+    Ok(user)
+}
+
+/// List users route handler
+///
+/// # Description
+/// Lists users, optionally filtered (`userName eq "jane@example.com"`) and paginated
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `query` - Filter and pagination parameters
+///
+/// # Returns
+/// * `200 OK` with a list response of users
+///
+/// # Errors
+/// * `400 BAD REQUEST` - Invalid or unsupported filter
+/// * `401 UNAUTHORIZED` - Invalid provisioning token
+/// * `500 INTERNAL SERVER ERROR` - Database error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::extract::{Query, State};
+/// use kiro_client::{scim::{users::list_users, ListQuery}, ClientService};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(MockDatabaseOperations::new()),
+/// };
+///
+/// // Filter
+/// let query = Query(ListQuery {
+///     filter: Some(r#"userName eq "jane@example.com""#.to_string()),
+///     ..Default::default()
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     list_users(State(service), query).await;
+///
+///     println!("Users listed");
+/// });
+/// ```
+#[utoipa::path(
+    get,
+    path = "/scim/v2/Users",
+    tag = "scim",
+    params(ListQuery),
+    responses(
+        (status = 200, description = "Users", body = ListResponse<ScimUser>),
+        (status = 400, description = "Invalid filter", body = String),
+        (status = 401, description = "Invalid provisioning token", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn list_users(
+    State(service): State<ClientService>, Query(query): Query<ListQuery>,
+) -> impl IntoResponse {
+    match list(&service.db, &query).await {
+        Ok(users) => scim_response(StatusCode::OK, users),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Get user route handler
+///
+/// # Description
+/// Reads a user
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `id` - Identifier of the user
+///
+/// # Returns
+/// * `200 OK` with the user
+///
+/// # Errors
+/// * `401 UNAUTHORIZED` - Invalid provisioning token
+/// * `404 NOT FOUND` - Unknown user
+/// * `500 INTERNAL SERVER ERROR` - Database error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::extract::{Path, State};
+/// use kiro_client::{scim::users::get_user, ClientService};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(MockDatabaseOperations::new()),
+/// };
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     get_user(State(service), Path("jdoe".to_string())).await;
+///
+///     println!("User read");
+/// });
+/// ```
+#[utoipa::path(
+    get,
+    path = "/scim/v2/Users/{id}",
+    tag = "scim",
+    params(("id" = String, Path, description = "User identifier")),
+    responses(
+        (status = 200, description = "User", body = ScimUser),
+        (status = 401, description = "Invalid provisioning token", body = String),
+        (status = 404, description = "User not found", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn get_user(
+    State(service): State<ClientService>, Path(id): Path<String>,
+) -> impl IntoResponse {
+    match find_user(&service.db, &id).await {
+        Ok(user) => scim_response(StatusCode::OK, ScimUser::from(&user)),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Create user route handler
+///
+/// # Description
+/// Provisions a user. Without password the account can only be used through
+/// single sign-on.
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `request` - The user to create
+///
+/// # Returns
+/// * `201 CREATED` with the user and its location
+///
+/// # Errors
+/// * `400 BAD REQUEST` - Invalid email or password
+/// * `401 UNAUTHORIZED` - Invalid provisioning token
+/// * `409 CONFLICT` - Email already in use
+/// * `500 INTERNAL SERVER ERROR` - Database error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{extract::State, Json};
+/// use kiro_client::{
+///     scim::{users::create_user, resources::ScimUserRequest},
+///     ClientService,
+/// };
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(MockDatabaseOperations::new()),
+/// };
+///
+/// // User
+/// let request = Json(ScimUserRequest {
+///     schemas: vec![],
+///     external_id: None,
+///     user_name: "jane@example.com".to_string(),
+///     active: Some(true),
+///     emails: vec![],
+///     password: None,
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     create_user(State(service), request).await;
+///
+///     println!("User created");
+/// });
+/// ```
+#[utoipa::path(
+    post,
+    path = "/scim/v2/Users",
+    tag = "scim",
+    request_body = ScimUserRequest,
+    responses(
+        (status = 201, description = "User created", body = ScimUser),
+        (status = 400, description = "Invalid user", body = String),
+        (status = 401, description = "Invalid provisioning token", body = String),
+        (status = 409, description = "Email already in use", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn create_user(
+    State(service): State<ClientService>, Json(request): Json<ScimUserRequest>,
+) -> impl IntoResponse {
+    match create(&service.db, request).await {
+        Ok(user) => {
+            let user = ScimUser::from(&user);
+            let location = HeaderValue::from_str(&user.meta.location).ok();
+
+            let mut response = scim_response(StatusCode::CREATED, user);
+            if let Some(location) = location {
+                response.headers_mut().insert(header::LOCATION, location);
+            }
+
+            response
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Replace user route handler
+///
+/// # Description
+/// Replaces the email, password and active state of a user
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `id` - Identifier of the user
+/// * `request` - The new state of the user
+///
+/// # Returns
+/// * `200 OK` with the updated user
+///
+/// # Errors
+/// * `400 BAD REQUEST` - Invalid email or password
+/// * `401 UNAUTHORIZED` - Invalid provisioning token
+/// * `404 NOT FOUND` - Unknown user
+/// * `409 CONFLICT` - Email already in use
+/// * `500 INTERNAL SERVER ERROR` - Database error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{extract::{Path, State}, Json};
+/// use kiro_client::{
+///     scim::{users::replace_user, resources::ScimUserRequest},
+///     ClientService,
+/// };
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(MockDatabaseOperations::new()),
+/// };
+///
+/// // User
+/// let request = Json(ScimUserRequest {
+///     schemas: vec![],
+///     external_id: None,
+///     user_name: "jane@example.com".to_string(),
+///     active: Some(false),
+///     emails: vec![],
+///     password: None,
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     replace_user(State(service), Path("jdoe".to_string()), request).await;
+///
+///     println!("User replaced");
+/// });
+/// ```
+#[utoipa::path(
+    put,
+    path = "/scim/v2/Users/{id}",
+    tag = "scim",
+    params(("id" = String, Path, description = "User identifier")),
+    request_body = ScimUserRequest,
+    responses(
+        (status = 200, description = "User replaced", body = ScimUser),
+        (status = 400, description = "Invalid user", body = String),
+        (status = 401, description = "Invalid provisioning token", body = String),
+        (status = 404, description = "User not found", body = String),
+        (status = 409, description = "Email already in use", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn replace_user(
+    State(service): State<ClientService>, Path(id): Path<String>,
+    Json(request): Json<ScimUserRequest>,
+) -> impl IntoResponse {
+    let changes = UserChanges {
+        external_id: Some(request.external_id),
+        user_name: None,
+        email: Some(request.email()),
+        active: request.active,
+        password: request.password,
+    };
+
+    let result = match find_user(&service.db, &id).await {
+        Ok(user) => apply_changes(&service.db, user, changes).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(user) => scim_response(StatusCode::OK, ScimUser::from(&user)),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Patch user route handler
+///
+/// # Description
+/// Updates a user with SCIM PATCH operations. Setting `active` to false
+/// deactivates the account with a soft delete.
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `id` - Identifier of the user
+/// * `request` - The PATCH operations
+///
+/// # Returns
+/// * `200 OK` with the updated user
+///
+/// # Errors
+/// * `400 BAD REQUEST` - Invalid operation or value
+/// * `401 UNAUTHORIZED` - Invalid provisioning token
+/// * `404 NOT FOUND` - Unknown user
+/// * `409 CONFLICT` - Email already in use
+/// * `500 INTERNAL SERVER ERROR` - Database error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{extract::{Path, State}, Json};
+/// use kiro_client::{
+///     scim::{
+///         resources::{PatchOperation, PatchRequest},
+///         users::patch_user,
+///     },
+///     ClientService,
+/// };
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(MockDatabaseOperations::new()),
+/// };
+///
+/// // Deactivation
+/// let request = Json(PatchRequest {
+///     schemas: vec![],
+///     operations: vec![PatchOperation {
+///         op: "replace".to_string(),
+///         path: Some("active".to_string()),
+///         value: serde_json::json!(false),
+///     }],
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     patch_user(State(service), Path("jdoe".to_string()), request).await;
+///
+///     println!("User deactivated");
+/// });
+/// ```
+#[utoipa::path(
+    patch,
+    path = "/scim/v2/Users/{id}",
+    tag = "scim",
+    params(("id" = String, Path, description = "User identifier")),
+    request_body = PatchRequest,
+    responses(
+        (status = 200, description = "User updated", body = ScimUser),
+        (status = 400, description = "Invalid operation", body = String),
+        (status = 401, description = "Invalid provisioning token", body = String),
+        (status = 404, description = "User not found", body = String),
+        (status = 409, description = "Email already in use", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn patch_user(
+    State(service): State<ClientService>, Path(id): Path<String>, Json(request): Json<PatchRequest>,
+) -> impl IntoResponse {
+    let changes = match patch_changes(request) {
+        Ok(changes) => changes,
+        Err(e) => return e.into_response(),
+    };
+
+    let result = match find_user(&service.db, &id).await {
+        Ok(user) => apply_changes(&service.db, user, changes).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(user) => scim_response(StatusCode::OK, ScimUser::from(&user)),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Delete user route handler
+///
+/// # Description
+/// Permanently deletes a user
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `id` - Identifier of the user
+///
+/// # Returns
+/// * `204 NO CONTENT` once deleted
+///
+/// # Errors
+/// * `401 UNAUTHORIZED` - Invalid provisioning token
+/// * `404 NOT FOUND` - Unknown user
+/// * `409 CONFLICT` - The user still has a payment customer
+/// * `500 INTERNAL SERVER ERROR` - Database error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::extract::{Path, State};
+/// use kiro_client::{scim::users::delete_user, ClientService};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(MockDatabaseOperations::new()),
+/// };
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     delete_user(State(service), Path("jdoe".to_string())).await;
+///
+///     println!("User deleted");
+/// });
+/// ```
+#[utoipa::path(
+    delete,
+    path = "/scim/v2/Users/{id}",
+    tag = "scim",
+    params(("id" = String, Path, description = "User identifier")),
+    responses(
+        (status = 204, description = "User deleted"),
+        (status = 401, description = "Invalid provisioning token", body = String),
+        (status = 404, description = "User not found", body = String),
+        (status = 409, description = "User has a payment customer", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn delete_user(
+    State(service): State<ClientService>, Path(id): Path<String>,
+) -> impl IntoResponse {
+    let result = match find_user(&service.db, &id).await {
+        Ok(user) => purge_user(&service.db, &user)
+            .await
+            .map_err(ScimError::from),
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::{
+        db_bridge::MockDatabaseOperations,
+        db_query::{Condition, TextMatch},
+    };
+    use mockall::predicate::{always, eq};
+
+    use crate::{AccountEventModel, CreateAccountEventModel, UserEmailModel};
+
+    fn user() -> UserModel {
+        UserModel {
+            id: DbId::from(("users", "jdoe")),
+            email: "jane@example.com".to_string(),
+            ..Default::default()
+        }
+    }
+
+    async fn body(response: Response) -> Value {
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body_bytes).unwrap()
+    }
+
+    #[test]
+    fn test_patch_changes() {
+        // Azure AD style, string booleans and filtered paths
+        let request: PatchRequest = serde_json::from_value(serde_json::json!({
+            "schemas": [crate::http::scim::resources::PATCH_SCHEMA],
+            "Operations": [
+                { "op": "Replace", "path": "active", "value": "False" },
+                { "op": "Replace", "path": "emails[type eq \"work\"].value", "value": "jane@example.org" },
+                { "op": "Add", "path": "name.givenName", "value": "Jane" }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            patch_changes(request).unwrap(),
+            UserChanges {
+                email: Some("jane@example.org".to_string()),
+                active: Some(false),
+                ..Default::default()
+            }
+        );
+
+        // Okta style, no path
+        let request: PatchRequest = serde_json::from_value(serde_json::json!({
+            "Operations": [{ "op": "replace", "value": { "active": true, "userName": "jane@example.org" } }]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            patch_changes(request).unwrap(),
+            UserChanges {
+                user_name: Some("jane@example.org".to_string()),
+                active: Some(true),
+                ..Default::default()
+            }
+        );
+
+        let request: PatchRequest = serde_json::from_value(serde_json::json!({
+            "Operations": [{ "op": "remove", "path": "userName" }]
+        }))
+        .unwrap();
+        assert!(patch_changes(request).is_err());
+
+        let request: PatchRequest = serde_json::from_value(serde_json::json!({
+            "Operations": [{ "op": "replace", "path": "externalId", "value": "5f0c2f3e" }]
+        }))
+        .unwrap();
+        assert_eq!(
+            patch_changes(request).unwrap(),
+            UserChanges {
+                external_id: Some(Some("5f0c2f3e".to_string())),
+                ..Default::default()
+            }
+        );
+
+        let request: PatchRequest = serde_json::from_value(serde_json::json!({
+            "Operations": [{ "op": "remove", "path": "externalId" }]
+        }))
+        .unwrap();
+        assert_eq!(
+            patch_changes(request).unwrap(),
+            UserChanges {
+                external_id: Some(None),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_users_with_filter() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_count_where()
+            .withf(|table, condition| {
+                table == "users"
+                    && *condition
+                        == Condition::And(vec![Condition::text(
+                            "email",
+                            TextMatch::Equals,
+                            "jane@example.com",
+                        )])
+            })
+            .times(1)
+            .returning(|_, _| Ok(1));
+
+        mock_db
+            .expect_read_where::<UserModel>()
+            .withf(|table, _, options| {
+                let options = options.as_ref().unwrap();
+                table == "users" && options.start == Some(0) && options.limit == Some(10)
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(vec![user()]));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let query = ListQuery {
+            filter: Some(r#"userName eq "Jane@example.com""#.to_string()),
+            count: Some(10),
+            ..Default::default()
+        };
+
+        let response = list_users(State(service), Query(query))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let list = body(response).await;
+        assert_eq!(list["totalResults"], 1);
+        assert_eq!(list["Resources"][0]["id"], "jdoe");
+    }
+
+    #[tokio::test]
+    async fn test_list_users_invalid_filter() {
+        let service = ClientService {
+            db: Database::Mock(MockDatabaseOperations::new()),
+        };
+
+        let query = ListQuery {
+            filter: Some(r#"title eq "CEO""#.to_string()),
+            ..Default::default()
+        };
+
+        let response = list_users(State(service), Query(query))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(body(response).await["scimType"], "invalidFilter");
+    }
+
+    #[tokio::test]
+    async fn test_create_user() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .with(eq("users"), eq("email"), eq("jane@example.com"), eq(None))
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        mock_db
+            .expect_read_by_field::<UserEmailModel>()
+            .with(
+                eq("user_emails"),
+                eq("email"),
+                eq("jane@example.com"),
+                eq(None),
+            )
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        mock_db
+            .expect_create::<CreateUserModel, UserModel>()
+            .with(eq("users"), always())
+            .times(1)
+            .returning(|_, data| {
+                Ok(vec![UserModel {
+                    email: data.email,
+                    activated: false,
+                    ..user()
+                }])
+            });
+
+        mock_db
+            .expect_update_field::<bool>()
+            .with(eq(DbId::from(("users", "jdoe"))), eq("activated"), eq(true))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        mock_db
+            .expect_create::<CreateAccountEventModel, AccountEventModel>()
+            .with(eq("account_events"), always())
+            .times(1)
+            .returning(|_, _| Ok(vec![AccountEventModel::default()]));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = ScimUserRequest {
+            schemas: vec![],
+            external_id: None,
+            user_name: "jane@example.com".to_string(),
+            active: None,
+            emails: vec![],
+            password: None,
+        };
+
+        let response = create_user(State(service), Json(request))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert!(response.headers().contains_key(header::LOCATION));
+
+        let user = body(response).await;
+        assert_eq!(user["userName"], "jane@example.com");
+        assert_eq!(user["active"], true);
+    }
+
+    #[tokio::test]
+    async fn test_create_user_conflict() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![user()]));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = ScimUserRequest {
+            schemas: vec![],
+            external_id: None,
+            user_name: "jane@example.com".to_string(),
+            active: None,
+            emails: vec![],
+            password: None,
+        };
+
+        let response = create_user(State(service), Json(request))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(body(response).await["scimType"], "uniqueness");
+    }
+
+    #[tokio::test]
+    async fn test_patch_user_deactivates() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .with(eq(DbId::from(("users", "jdoe"))))
+            .times(1)
+            .returning(|_| Ok(Some(user())));
+
+        mock_db
+            .expect_delete_soft()
+            .with(eq(DbId::from(("users", "jdoe"))))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        mock_db
+            .expect_delete_where()
+            .withf(|table, _| table == "sessions")
+            .times(1)
+            .returning(|_, _| Ok(0));
+
+        mock_db
+            .expect_create::<CreateAccountEventModel, AccountEventModel>()
+            .with(eq("account_events"), always())
+            .times(1)
+            .returning(|_, _| Ok(vec![AccountEventModel::default()]));
+
+        mock_db
+            .expect_update_field::<DbDateTime>()
+            .with(
+                eq(DbId::from(("users", "jdoe"))),
+                eq("updated_at"),
+                always(),
+            )
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request: PatchRequest = serde_json::from_value(serde_json::json!({
+            "Operations": [{ "op": "replace", "path": "active", "value": false }]
+        }))
+        .unwrap();
+
+        let response = patch_user(State(service), Path("jdoe".to_string()), Json(request))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body(response).await["active"], false);
+    }
+
+    #[tokio::test]
+    async fn test_delete_user_not_found() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(None));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let response = delete_user(State(service), Path("unknown".to_string()))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[cfg(not(feature = "storage"))]
+    #[tokio::test]
+    async fn test_delete_user() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .with(eq(DbId::from(("users", "jdoe"))))
+            .times(1)
+            .returning(|_| Ok(Some(user())));
+
+        mock_db
+            .expect_delete_where()
+            .times(1..)
+            .returning(|_, _| Ok(0));
+
+        mock_db
+            .expect_delete()
+            .with(eq(DbId::from(("users", "jdoe"))))
+            .times(1)
+            .returning(|_| Ok(Some(())));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let response = delete_user(State(service), Path("jdoe".to_string()))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_delete_user_payment_customer() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(UserModel {
+                    customer_id: Some("cus_123".to_string()),
+                    ..user()
+                }))
+            });
+
+        mock_db.expect_delete_where().never();
+        mock_db.expect_delete().never();
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let response = delete_user(State(service), Path("jdoe".to_string()))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+}
@@ -0,0 +1,215 @@
+// http/user/accept_policy.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
+use http::HeaderMap;
+use kiro_api::client::v1::{AcceptPolicyRequest, Consent};
+
+use crate::{
+    error::ClientError,
+    utils::{consent, ip::get_ip_from_headers},
+    SessionModel,
+};
+
+/// Accept policy route handler
+///
+/// # Description
+/// Records the acceptance of the current version of the terms of service or of
+/// the privacy policy by the current user, with the IP address of the request.
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `session` - The current session model
+/// * `headers` - HTTP headers containing the IP address
+/// * `request` - The request containing the kind and version of the document
+///
+/// # Returns
+/// * HTTP response with either:
+///   * `200 OK` with the recorded consent
+///   * Error status code with message
+///
+/// # Errors
+/// * `400 BAD REQUEST` - The version is not the current one
+/// * `500 INTERNAL SERVER ERROR` - Database or server error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{Extension, extract::State, Json};
+/// use http::HeaderMap;
+/// use kiro_api::client::v1::AcceptPolicyRequest;
+/// use kiro_client::{ClientService, accept_policy::accept_policy, SessionModel};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Mock session
+/// let session = SessionModel::default();
+///
+/// // Mock request
+/// let request = AcceptPolicyRequest {
+///     kind: "Terms".to_string(),
+///     version: 2,
+/// };
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     accept_policy(State(service), Extension(session), HeaderMap::new(), Json(request)).await;
+///
+///     println!("Policy accepted");
+/// });
+/// ```
+#[utoipa::path(
+    post,
+    path = "/user/accept_policy",
+    tag = "user",
+    params(
+        AcceptPolicyRequest
+    ),
+    responses(
+        (status = 200, description = "Policy accepted", body = Consent),
+        (status = 400, description = "Policy version is not current", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn accept_policy(
+    State(service): State<ClientService>, Extension(session): Extension<SessionModel>,
+    headers: HeaderMap, Json(request): Json<AcceptPolicyRequest>,
+) -> impl IntoResponse {
+    let ip_address = get_ip_from_headers(&headers);
+
+    match consent::accept_policy(
+        &service.db,
+        session.user_id.clone(),
+        &request.kind,
+        request.version,
+        ip_address,
+    )
+    .await
+    {
+        Ok(consent) => (StatusCode::OK, Json(consent)).into_response(),
+        Err(e @ ClientError::PolicyNotCurrent) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::db_bridge::MockDatabaseOperations;
+
+    use crate::{CreatePolicyConsentModel, PolicyConsentModel, PolicyDocumentModel};
+
+    #[tokio::test]
+    async fn test_accept_policy_success() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let extension = Extension(SessionModel::default());
+
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![PolicyDocumentModel::default()]));
+
+        mock_db
+            .expect_read_by_field_thing::<PolicyConsentModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        mock_db
+            .expect_create::<CreatePolicyConsentModel, PolicyConsentModel>()
+            .withf(|table, data| {
+                table == "policy_consents" && data.ip_address == Some("10.0.0.1".to_string())
+            })
+            .times(1)
+            .returning(|_, data| {
+                Ok(vec![PolicyConsentModel {
+                    ip_address: data.ip_address,
+                    ..Default::default()
+                }])
+            });
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "10.0.0.1".parse().unwrap());
+
+        let response = accept_policy(
+            State(service),
+            extension,
+            headers,
+            Json(AcceptPolicyRequest {
+                kind: "Terms".to_string(),
+                version: 1,
+            }),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+        assert_eq!(body["kind"], "Terms");
+        assert_eq!(body["ip_address"], "10.0.0.1");
+    }
+
+    #[tokio::test]
+    async fn test_accept_policy_outdated_version() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let extension = Extension(SessionModel::default());
+
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let response = accept_policy(
+            State(service),
+            extension,
+            HeaderMap::new(),
+            Json(AcceptPolicyRequest {
+                kind: "Privacy".to_string(),
+                version: 1,
+            }),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
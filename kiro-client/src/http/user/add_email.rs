@@ -0,0 +1,201 @@
+// http/user/add_email.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
+use kiro_api::client::v1::{AddEmailRequest, UserEmail};
+use kiro_database::db_bridge::DatabaseOperations;
+
+use crate::{
+    error::ClientError, models::UserModel, utils::secondary_email::add_secondary_email,
+    SessionModel,
+};
+
+/// Add email route handler
+///
+/// # Description
+/// Attaches a secondary email address to the current user and emails it a
+/// verification link. Once verified through `/auth/verify_email`, the address can
+/// be used to log in and to recover the account.
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `session` - The current session model
+/// * `request` - The request containing the address
+///
+/// # Returns
+/// * HTTP response with either:
+///   * `200 OK` with the attached address
+///   * Error status code with message
+///
+/// # Errors
+/// * `404 NOT FOUND` - User not found
+/// * `409 CONFLICT` - The email is already attached or in use
+/// * `500 INTERNAL SERVER ERROR` - Database, mailer or server error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{Extension, extract::State, Json};
+/// use kiro_api::client::v1::AddEmailRequest;
+/// use kiro_client::{ClientService, add_email::add_email, SessionModel};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Mock session
+/// let session = SessionModel::default();
+///
+/// // Mock request
+/// let request = AddEmailRequest {
+///     email: "backup@example.com".to_string(),
+/// };
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     add_email(State(service), Extension(session), Json(request)).await;
+///
+///     println!("Email added");
+/// });
+/// ```
+#[utoipa::path(
+    post,
+    path = "/user/add_email",
+    tag = "user",
+    params(
+        AddEmailRequest
+    ),
+    responses(
+        (status = 200, description = "Email added", body = UserEmail),
+        (status = 404, description = "User not found", body = String),
+        (status = 409, description = "Email already in use", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn add_email(
+    State(service): State<ClientService>, Extension(session): Extension<SessionModel>,
+    Json(request): Json<AddEmailRequest>,
+) -> impl IntoResponse {
+    // Get user details
+    let user = match service
+        .db
+        .select::<UserModel>(session.user_id.clone())
+        .await
+    {
+        Ok(Some(user)) => user,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({ "error": "User not found", "code": "user.not_found" })),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({ "error": format!("Database error: {}", e) })),
+            )
+                .into_response()
+        }
+    };
+
+    match add_secondary_email(&service.db, &user, &request.email).await {
+        Ok(address) => (StatusCode::OK, Json(UserEmail::from(&address))).into_response(),
+        Err(e @ ClientError::EmailTaken) => (
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::db_bridge::MockDatabaseOperations;
+
+    use crate::UserEmailModel;
+
+    #[tokio::test]
+    async fn test_add_email_taken() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let extension = Extension(SessionModel::default());
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_read_by_field_thing::<UserEmailModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![UserModel::default()]));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = AddEmailRequest {
+            email: "taken@example.com".to_string(),
+        };
+
+        let response = add_email(State(service), extension, Json(request))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_add_email_user_not_found() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let extension = Extension(SessionModel::default());
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(None));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = AddEmailRequest {
+            email: "backup@example.com".to_string(),
+        };
+
+        let response = add_email(State(service), extension, Json(request))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,186 @@
+// http/user/create_invitation.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
+use kiro_api::client::v1::{CreateInvitationRequest, Invitation};
+
+use crate::{error::ClientError, utils::registration::issue_invitation, SessionModel};
+
+/// Create invitation route handler
+///
+/// # Description
+/// Creates an invitation code for the invite-only registration, restricted to admins
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `session` - The current session model
+/// * `request` - The request containing the restrictions and grants of the invitation
+///
+/// # Returns
+/// * HTTP response with either:
+///   * `200 OK` with the created invitation
+///   * Error status code with message
+///
+/// # Errors
+/// * `400 BAD REQUEST` - Expiry not in the future
+/// * `403 FORBIDDEN` - Admin privileges required
+/// * `404 NOT FOUND` - Group not found
+/// * `500 INTERNAL SERVER ERROR` - Database or server error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{Extension, extract::State, Json};
+/// use kiro_api::client::v1::CreateInvitationRequest;
+/// use kiro_client::{ClientService, create_invitation::create_invitation, SessionModel};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Mock admin session
+/// let session = SessionModel {
+///     is_admin: true,
+///     ..Default::default()
+/// };
+///
+/// // Mock request
+/// let request = CreateInvitationRequest {
+///     email: Some("user@example.com".to_string()),
+///     is_admin: false,
+///     group_id: None,
+///     expires_at: None,
+/// };
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     create_invitation(State(service), Extension(session), Json(request)).await;
+///
+///     println!("Invitation created");
+/// });
+/// ```
+#[utoipa::path(
+    post,
+    path = "/user/create_invitation",
+    tag = "user",
+    params(
+        CreateInvitationRequest
+    ),
+    responses(
+        (status = 200, description = "Invitation created", body = Invitation),
+        (status = 400, description = "Expiry not in the future", body = String),
+        (status = 403, description = "Admin privileges required", body = String),
+        (status = 404, description = "Group not found", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn create_invitation(
+    State(service): State<ClientService>, Extension(session): Extension<SessionModel>,
+    Json(request): Json<CreateInvitationRequest>,
+) -> impl IntoResponse {
+    if !session.is_admin {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({
+                "error": "Admin privileges required",
+                "code": "auth.admin_required",
+            })),
+        )
+            .into_response();
+    }
+
+    match issue_invitation(&service.db, request).await {
+        Ok(invitation) => (StatusCode::OK, Json(Invitation::from(&invitation))).into_response(),
+        Err(e) => {
+            let status = match e {
+                ClientError::InvalidInvitationExpiry => StatusCode::BAD_REQUEST,
+                ClientError::GroupNotFound => StatusCode::NOT_FOUND,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+
+            (
+                status,
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::db_bridge::MockDatabaseOperations;
+
+    use crate::GroupModel;
+
+    #[tokio::test]
+    async fn test_create_invitation_unknown_group() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<GroupModel>()
+            .times(1)
+            .returning(|_| Ok(None));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let session = SessionModel {
+            is_admin: true,
+            ..Default::default()
+        };
+
+        let request = Json(CreateInvitationRequest {
+            group_id: Some("unknown".to_string()),
+            ..Default::default()
+        });
+
+        let response = create_invitation(State(service), Extension(session), request)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+        assert_eq!(error["code"], "group.not_found");
+    }
+
+    #[tokio::test]
+    async fn test_create_invitation_requires_admin() {
+        let service = ClientService {
+            db: Database::Mock(MockDatabaseOperations::new()),
+        };
+
+        let request = Json(CreateInvitationRequest::default());
+
+        let response =
+            create_invitation(State(service), Extension(SessionModel::default()), request)
+                .await
+                .into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
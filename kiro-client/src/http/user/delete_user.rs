@@ -16,15 +16,14 @@
 
 use super::*;
 
+use crate::{SessionModel, UserModel};
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
-use kiro_database::db_bridge::DatabaseOperations;
-
-use crate::SessionModel;
 
 /// Delete user route handler
 ///
 /// # Description
-/// Deletes the current user account
+/// Schedules the deletion of the current user account. The account is purged
+/// once the grace period is over, logging in again before offers to restore it.
 ///
 /// # Arguments
 /// * `service` - The client service instance
@@ -32,7 +31,7 @@ use crate::SessionModel;
 ///
 /// # Returns
 /// * HTTP response with either:
-///   * `200 OK` with empty JSON response
+///   * `200 OK` with the date the account will be purged
 ///   * Error status code with message
 ///
 /// # Errors
@@ -71,18 +70,22 @@ use crate::SessionModel;
     path = "/user/delete_user",
     tag = "user",
     responses(
-        (status = 200, description = "User deleted", body = String),
+        (status = 200, description = "User deletion scheduled", body = String),
         (status = 500, description = "Internal server error", body = String)
     )
 )]
 pub async fn delete_user(
     State(service): State<ClientService>, Extension(session): Extension<SessionModel>,
 ) -> impl IntoResponse {
-    match service.db.delete(session.user_id.clone()).await {
-        Ok(_) => (StatusCode::OK, Json(serde_json::json!({}))).into_response(),
+    match UserModel::schedule_deletion(&service.db, session.user_id.clone()).await {
+        Ok(purge_at) => (
+            StatusCode::OK,
+            Json(serde_json::json!({ "deletion_scheduled_at": purge_at })),
+        )
+            .into_response(),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({ "error": e.to_string() })),
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
         )
             .into_response(),
     }
@@ -92,8 +95,7 @@ pub async fn delete_user(
 mod tests {
     use super::*;
 
-    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
-    use mockall::predicate::eq;
+    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError, DbDateTime};
 
     #[tokio::test]
     async fn test_delete_user_success() {
@@ -102,10 +104,18 @@ mod tests {
         let extension = Extension(session.clone());
 
         mock_db
-            .expect_delete()
-            .with(eq(session.user_id.clone()))
+            .expect_update_field::<Option<DbDateTime>>()
+            .withf(move |id, field, value| {
+                *id == session.user_id && field == "deletion_scheduled_at" && value.is_some()
+            })
             .times(1)
-            .returning(|_| Ok(Some(())));
+            .returning(|_, _, _| Ok(()));
+
+        // Every session of the user is revoked
+        mock_db
+            .expect_delete_where::<serde_json::Value>()
+            .times(1)
+            .returning(|_, _| Ok(0));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -124,10 +134,9 @@ mod tests {
         let extension = Extension(session.clone());
 
         mock_db
-            .expect_delete()
-            .with(eq(session.user_id.clone()))
+            .expect_update_field::<Option<DbDateTime>>()
             .times(1)
-            .returning(|_| Err(DatabaseError::Internal("Database error".to_string())));
+            .returning(|_, _, _| Err(DatabaseError::Internal("Database error".to_string())));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -147,10 +156,18 @@ mod tests {
         let extension = Extension(session.clone());
 
         mock_db
-            .expect_delete()
-            .with(eq(session.user_id.clone()))
+            .expect_update_field::<Option<DbDateTime>>()
+            .withf(move |id, field, value| {
+                *id == session.user_id && field == "deletion_scheduled_at" && value.is_some()
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        // Every session of the user is revoked
+        mock_db
+            .expect_delete_where::<serde_json::Value>()
             .times(1)
-            .returning(|_| Ok(Some(())));
+            .returning(|_, _| Ok(0));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -17,9 +17,8 @@
 use super::*;
 
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
-use kiro_database::db_bridge::DatabaseOperations;
 
-use crate::SessionModel;
+use crate::{SessionModel, UserModel};
 
 /// Disable user route handler
 ///
@@ -78,11 +77,17 @@ use crate::SessionModel;
 pub async fn disable_user(
     State(service): State<ClientService>, Extension(session): Extension<SessionModel>,
 ) -> impl IntoResponse {
-    match service.db.delete_soft(session.user_id.clone()).await {
+    match UserModel::disable(
+        &service.db,
+        session.user_id.clone(),
+        Some(session.user_id.clone()),
+    )
+    .await
+    {
         Ok(_) => (StatusCode::OK, Json(serde_json::json!({}))).into_response(),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({ "error": e.to_string() })),
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
         )
             .into_response(),
     }
@@ -95,6 +100,8 @@ mod tests {
     use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
     use mockall::predicate::eq;
 
+    use crate::{AccountEventKind, AccountEventModel, CreateAccountEventModel};
+
     #[tokio::test]
     async fn test_disable_user_success() {
         let session = SessionModel::default();
@@ -107,6 +114,20 @@ mod tests {
             .times(1)
             .returning(|_| Ok(()));
 
+        mock_db
+            .expect_delete_where()
+            .withf(|table, _| table == "sessions")
+            .times(1)
+            .returning(|_, _| Ok(0));
+
+        mock_db
+            .expect_create::<CreateAccountEventModel, AccountEventModel>()
+            .withf(|table, data| {
+                table == "account_events" && data.kind == AccountEventKind::Disabled
+            })
+            .times(1)
+            .returning(|_, _| Ok(vec![AccountEventModel::default()]));
+
         let service = ClientService {
             db: Database::Mock(mock_db),
         };
@@ -152,6 +173,20 @@ mod tests {
             .times(1)
             .returning(|_| Ok(()));
 
+        mock_db
+            .expect_delete_where()
+            .withf(|table, _| table == "sessions")
+            .times(1)
+            .returning(|_, _| Ok(0));
+
+        mock_db
+            .expect_create::<CreateAccountEventModel, AccountEventModel>()
+            .withf(|table, data| {
+                table == "account_events" && data.kind == AccountEventKind::Disabled
+            })
+            .times(1)
+            .returning(|_, _| Ok(vec![AccountEventModel::default()]));
+
         let service = ClientService {
             db: Database::Mock(mock_db),
         };
@@ -174,6 +209,20 @@ mod tests {
             .times(1)
             .returning(|_| Ok(()));
 
+        mock_db
+            .expect_delete_where()
+            .withf(|table, _| table == "sessions")
+            .times(1)
+            .returning(|_, _| Ok(0));
+
+        mock_db
+            .expect_create::<CreateAccountEventModel, AccountEventModel>()
+            .withf(|table, data| {
+                table == "account_events" && data.kind == AccountEventKind::Disabled
+            })
+            .times(1)
+            .returning(|_, _| Ok(vec![AccountEventModel::default()]));
+
         let service = ClientService {
             db: Database::Mock(mock_db),
         };
@@ -0,0 +1,188 @@
+// http/user/get_changes_since.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
+use kiro_api::client::v1::{GetChangesSinceRequest, GetChangesSinceResponse};
+
+use crate::{error::ClientError, utils::sync, SessionModel};
+
+/// Delta sync route handler
+///
+/// # Description
+/// Returns the user, settings and group records of the current user changed
+/// since the cursor, with tombstones for the deleted ones, and the cursor to
+/// sync from next. The whole state is returned, with `reset` set, when the
+/// cursor is 0 or too old.
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `session` - The current session model
+/// * `request` - The request containing the cursor of the previous sync
+///
+/// # Returns
+/// * HTTP response with either:
+///   * `200 OK` with the changes and the new cursor
+///   * Error status code with message
+///
+/// # Errors
+/// * `404 NOT FOUND` - User not found
+/// * `500 INTERNAL SERVER ERROR` - Database or server error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{Extension, extract::State, Json};
+/// use kiro_api::client::v1::GetChangesSinceRequest;
+/// use kiro_client::{ClientService, get_changes_since::get_changes_since, SessionModel};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Mock session
+/// let session = SessionModel::default();
+///
+/// // Mock request, 0 for the first sync
+/// let request = GetChangesSinceRequest { cursor: 0 };
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     get_changes_since(State(service), Extension(session), Json(request)).await;
+///
+///     println!("Changes synced");
+/// });
+/// ```
+#[utoipa::path(
+    post,
+    path = "/user/get_changes_since",
+    tag = "user",
+    params(
+        GetChangesSinceRequest
+    ),
+    responses(
+        (status = 200, description = "Changes since the cursor", body = GetChangesSinceResponse),
+        (status = 404, description = "User not found", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn get_changes_since(
+    State(service): State<ClientService>, Extension(session): Extension<SessionModel>,
+    Json(request): Json<GetChangesSinceRequest>,
+) -> impl IntoResponse {
+    match sync::get_changes_since(&service.db, session.user_id.clone(), request.cursor).await {
+        Ok(response) => (StatusCode::OK, Json(response)).into_response(),
+        Err(ClientError::DBOptionNone) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": "User not found", "code": "user.not_found" })),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::db_bridge::MockDatabaseOperations;
+
+    use crate::{ChangeVersionsModel, UserModel};
+
+    #[tokio::test]
+    async fn test_get_changes_since_first_sync() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let extension = Extension(SessionModel::default());
+
+        mock_db
+            .expect_select::<ChangeVersionsModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(ChangeVersionsModel {
+                    version: 7,
+                    compacted: 0,
+                }))
+            });
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(UserModel {
+                    groups: vec![],
+                    ..Default::default()
+                }))
+            });
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let response = get_changes_since(
+            State(service),
+            extension,
+            Json(GetChangesSinceRequest { cursor: 0 }),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+        assert_eq!(body["cursor"], 7);
+        assert_eq!(body["reset"], true);
+    }
+
+    #[tokio::test]
+    async fn test_get_changes_since_user_not_found() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let extension = Extension(SessionModel::default());
+
+        mock_db
+            .expect_select::<ChangeVersionsModel>()
+            .times(1)
+            .returning(|_| Ok(None));
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(None));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let response = get_changes_since(
+            State(service),
+            extension,
+            Json(GetChangesSinceRequest { cursor: 0 }),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,148 @@
+// http/user/list_consents.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
+use kiro_api::client::v1::ListConsentsResponse;
+
+use crate::{utils::consent, SessionModel};
+
+/// List consents route handler
+///
+/// # Description
+/// Returns the policy documents accepted by the current user, most recent first,
+/// with the date and IP address of each acceptance, and the current documents
+/// the user has not accepted yet.
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `session` - The current session model
+///
+/// # Returns
+/// * HTTP response with either:
+///   * `200 OK` with the consent history and the pending documents
+///   * Error status code with message
+///
+/// # Errors
+/// * `500 INTERNAL SERVER ERROR` - Database or server error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{Extension, extract::State};
+/// use kiro_client::{ClientService, list_consents::list_consents, SessionModel};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Mock session
+/// let session = SessionModel::default();
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     list_consents(State(service), Extension(session)).await;
+///
+///     println!("Consents listed");
+/// });
+/// ```
+#[utoipa::path(
+    get,
+    path = "/user/list_consents",
+    tag = "user",
+    responses(
+        (status = 200, description = "Consent history", body = ListConsentsResponse),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn list_consents(
+    State(service): State<ClientService>, Extension(session): Extension<SessionModel>,
+) -> impl IntoResponse {
+    match consent::list_consents(&service.db, session.user_id.clone()).await {
+        Ok(response) => (StatusCode::OK, Json(response)).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
+
+    use crate::{PolicyConsentModel, PolicyDocumentModel};
+
+    #[tokio::test]
+    async fn test_list_consents_success() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let extension = Extension(SessionModel::default());
+
+        mock_db
+            .expect_read_by_field_thing::<PolicyConsentModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![PolicyDocumentModel::default()]));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let response = list_consents(State(service), extension)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+        assert_eq!(body["consents"].as_array().unwrap().len(), 0);
+        assert_eq!(body["pending"][0]["kind"], "Terms");
+    }
+
+    #[tokio::test]
+    async fn test_list_consents_db_error() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let extension = Extension(SessionModel::default());
+
+        mock_db
+            .expect_read_by_field_thing::<PolicyConsentModel>()
+            .times(1)
+            .returning(|_, _, _, _| Err(DatabaseError::Internal("Database error".to_string())));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let response = list_consents(State(service), extension)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,149 @@
+// http/user/list_emails.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
+use kiro_api::client::v1::ListEmailsResponse;
+use kiro_database::db_bridge::DatabaseOperations;
+
+use crate::{models::UserModel, SessionModel, UserEmailModel};
+
+/// List emails route handler
+///
+/// # Description
+/// Returns the email addresses of the current user, the primary address first,
+/// then the secondary addresses with their verification state.
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `session` - The current session model
+///
+/// # Returns
+/// * HTTP response with either:
+///   * `200 OK` with the addresses
+///   * Error status code with message
+///
+/// # Errors
+/// * `404 NOT FOUND` - User not found
+/// * `500 INTERNAL SERVER ERROR` - Database or server error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{Extension, extract::State};
+/// use kiro_client::{ClientService, list_emails::list_emails, SessionModel};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Mock session
+/// let session = SessionModel::default();
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     list_emails(State(service), Extension(session)).await;
+///
+///     println!("Emails listed");
+/// });
+/// ```
+#[utoipa::path(
+    get,
+    path = "/user/list_emails",
+    tag = "user",
+    responses(
+        (status = 200, description = "Email addresses", body = ListEmailsResponse),
+        (status = 404, description = "User not found", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn list_emails(
+    State(service): State<ClientService>, Extension(session): Extension<SessionModel>,
+) -> impl IntoResponse {
+    // Get user details
+    let user = match service
+        .db
+        .select::<UserModel>(session.user_id.clone())
+        .await
+    {
+        Ok(Some(user)) => user,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({ "error": "User not found", "code": "user.not_found" })),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({ "error": format!("Database error: {}", e) })),
+            )
+                .into_response()
+        }
+    };
+
+    match UserEmailModel::list(&service.db, &user).await {
+        Ok(emails) => (StatusCode::OK, Json(ListEmailsResponse { emails })).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::db_bridge::MockDatabaseOperations;
+
+    #[tokio::test]
+    async fn test_list_emails_success() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let extension = Extension(SessionModel::default());
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_read_by_field_thing::<UserEmailModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let response = list_emails(State(service), extension).await.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+        assert_eq!(body["emails"][0]["email"], "test@example.com");
+        assert_eq!(body["emails"][0]["primary"], true);
+    }
+}
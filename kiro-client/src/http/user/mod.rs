@@ -14,28 +14,46 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "storage")]
+use axum::extract::DefaultBodyLimit;
 use axum::{
     routing::{delete, get, post},
     Router,
 };
 use kiro_database::db_bridge::Database;
 
+pub mod accept_policy;
+#[cfg(feature = "mailer")]
+pub mod add_email;
+pub mod create_invitation;
 pub mod delete_user;
 pub mod disable_user;
+pub mod get_changes_since;
+pub mod list_consents;
+pub mod list_emails;
+pub mod reactivate_user;
 pub mod read_user;
+pub mod remove_email;
+#[cfg(feature = "export")]
+pub mod request_data_export;
 #[cfg(feature = "mailer")]
 pub mod send_email_to_change_email;
 #[cfg(feature = "mailer")]
 pub mod send_email_to_change_password;
+pub mod set_primary_email;
 pub mod update_email;
 pub mod update_language;
 pub mod update_notifications;
 pub mod update_password;
 pub mod update_privacy;
+pub mod update_profile;
 pub mod update_security;
+pub mod update_settings;
 pub mod update_theme;
 #[cfg(feature = "storage")]
 pub mod upload_avatar;
+#[cfg(feature = "storage")]
+pub mod upload_file;
 
 use crate::ClientService;
 
@@ -46,19 +64,32 @@ use crate::ClientService;
 ///
 /// # Returns
 /// Router configured with user endpoints:
+/// - POST /accept_policy - Accept the current version of a policy document
+/// - POST /add_email - Add a secondary email address
+/// - POST /create_invitation - Create an invitation code (admin)
 /// - DELETE /delete_user - Delete user
 /// - DELETE /disable_user - Disable user
+/// - POST /get_changes_since - Get the changes since a cursor
+/// - GET /list_consents - List the consents of the user
+/// - GET /list_emails - List the email addresses of the user
+/// - POST /reactivate_user - Reactivate user (admin)
 /// - GET /read_user - Read user
+/// - POST /remove_email - Remove a secondary email address
+/// - POST /set_primary_email - Promote a verified email address to primary
 /// - POST /update_email - Update email
 /// - POST /update_language - Update language
 /// - POST /update_notifications - Update notifications
 /// - POST /update_password - Update password
 /// - POST /update_privacy - Update privacy
+/// - POST /update_profile - Update profile
 /// - POST /update_security - Update security
+/// - POST /update_settings - Update several settings at once
 /// - POST /update_theme - Update theme
 /// - GET /send_email_to_change_email - Send email to change email
 /// - GET /send_email_to_change_password - Send email to change password
 /// - POST /upload_avatar - Upload avatar
+/// - POST /upload_file - Upload a file or an avatar, streamed
+/// - POST /request_data_export - Request data export
 ///
 /// # Example
 /// ```rust,no_run
@@ -77,9 +108,26 @@ pub fn user_routes(db: Database) -> Router {
     let mut router = Router::new();
 
     router = router
+        .route("/accept_policy", post(accept_policy::accept_policy))
+        .route(
+            "/create_invitation",
+            post(create_invitation::create_invitation),
+        )
         .route("/delete_user", delete(delete_user::delete_user))
         .route("/disable_user", delete(disable_user::disable_user))
+        .route(
+            "/get_changes_since",
+            post(get_changes_since::get_changes_since),
+        )
+        .route("/list_consents", get(list_consents::list_consents))
+        .route("/list_emails", get(list_emails::list_emails))
+        .route("/reactivate_user", post(reactivate_user::reactivate_user))
         .route("/read_user", get(read_user::read_user))
+        .route("/remove_email", post(remove_email::remove_email))
+        .route(
+            "/set_primary_email",
+            post(set_primary_email::set_primary_email),
+        )
         .route("/update_email", post(update_email::update_email))
         .route("/update_language", post(update_language::update_language))
         .route(
@@ -88,7 +136,9 @@ pub fn user_routes(db: Database) -> Router {
         )
         .route("/update_password", post(update_password::update_password))
         .route("/update_privacy", post(update_privacy::update_privacy))
+        .route("/update_profile", post(update_profile::update_profile))
         .route("/update_security", post(update_security::update_security))
+        .route("/update_settings", post(update_settings::update_settings))
         .route("/update_theme", post(update_theme::update_theme));
 
     #[cfg(feature = "mailer")]
@@ -101,12 +151,33 @@ pub fn user_routes(db: Database) -> Router {
             .route(
                 "/send_email_to_change_password",
                 get(send_email_to_change_password::send_email_to_change_password),
-            );
+            )
+            .route("/add_email", post(add_email::add_email));
     }
 
     #[cfg(feature = "storage")]
     {
-        router = router.route("/upload_avatar", post(upload_avatar::upload_avatar));
+        // Room for the multipart headers around the avatar
+        let body_limit = crate::utils::avatar::AVATAR_MAX_SIZE + 64 * 1024;
+
+        router = router.route(
+            "/upload_avatar",
+            post(upload_avatar::upload_avatar).layer(DefaultBodyLimit::max(body_limit)),
+        );
+
+        // The declared size is enforced while the file is streamed
+        router = router.route(
+            "/upload_file",
+            post(upload_file::upload_file).layer(DefaultBodyLimit::disable()),
+        );
+    }
+
+    #[cfg(feature = "export")]
+    {
+        router = router.route(
+            "/request_data_export",
+            post(request_data_export::request_data_export),
+        );
     }
 
     router.with_state(service)
@@ -0,0 +1,202 @@
+// http/user/reactivate_user.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
+use kiro_api::client::v1::ReactivateUserRequest;
+use kiro_database::{db_bridge::DatabaseOperations, DbId};
+
+use crate::{SessionModel, UserModel};
+
+/// Reactivate user route handler
+///
+/// # Description
+/// Reactivates a disabled user account, restricted to admins
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `session` - The current session model
+/// * `request` - The request containing the ID of the user to reactivate
+///
+/// # Returns
+/// * HTTP response with either:
+///   * `200 OK` with empty JSON response
+///   * Error status code with message
+///
+/// # Errors
+/// * `403 FORBIDDEN` - Admin privileges required
+/// * `404 NOT FOUND` - User not found
+/// * `500 INTERNAL SERVER ERROR` - Database or server error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{Extension, extract::State, Json};
+/// use kiro_api::client::v1::ReactivateUserRequest;
+/// use kiro_client::{ClientService, reactivate_user::reactivate_user, SessionModel};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Mock admin session
+/// let session = SessionModel {
+///     is_admin: true,
+///     ..Default::default()
+/// };
+///
+/// // Mock request
+/// let request = ReactivateUserRequest {
+///     user_id: "user".to_string(),
+/// };
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     reactivate_user(State(service), Extension(session), Json(request)).await;
+///
+///     println!("User reactivated");
+/// });
+/// ```
+#[utoipa::path(
+    post,
+    path = "/user/reactivate_user",
+    tag = "user",
+    params(
+        ReactivateUserRequest
+    ),
+    responses(
+        (status = 200, description = "User reactivated", body = String),
+        (status = 403, description = "Admin privileges required", body = String),
+        (status = 404, description = "User not found", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn reactivate_user(
+    State(service): State<ClientService>, Extension(session): Extension<SessionModel>,
+    Json(request): Json<ReactivateUserRequest>,
+) -> impl IntoResponse {
+    if !session.is_admin {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({
+                "error": "Admin privileges required",
+                "code": "auth.admin_required",
+            })),
+        )
+            .into_response();
+    }
+
+    let user_id = DbId::from(("users", request.user_id.as_str()));
+
+    // Check the user exists
+    match service.db.select::<UserModel>(user_id.clone()).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({ "error": "User not found", "code": "user.not_found" })),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+            )
+                .into_response()
+        }
+    }
+
+    match UserModel::reactivate(&service.db, user_id, Some(session.user_id.clone())).await {
+        Ok(_) => (StatusCode::OK, Json(serde_json::json!({}))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::db_bridge::MockDatabaseOperations;
+    use mockall::predicate::eq;
+
+    use crate::{AccountEventModel, CreateAccountEventModel};
+
+    #[tokio::test]
+    async fn test_reactivate_user_success() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let session = SessionModel {
+            is_admin: true,
+            ..Default::default()
+        };
+        let user_id = DbId::from(("users", "disabled"));
+
+        mock_db
+            .expect_select::<UserModel>()
+            .with(eq(user_id.clone()))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_field::<bool>()
+            .with(eq(user_id), eq("activated"), eq(true))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        mock_db
+            .expect_create::<CreateAccountEventModel, AccountEventModel>()
+            .times(1)
+            .returning(|_, _| Ok(vec![AccountEventModel::default()]));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Json(ReactivateUserRequest {
+            user_id: "disabled".to_string(),
+        });
+
+        let response = reactivate_user(State(service), Extension(session), request)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_reactivate_user_requires_admin() {
+        let service = ClientService {
+            db: Database::Mock(MockDatabaseOperations::new()),
+        };
+
+        let request = Json(ReactivateUserRequest {
+            user_id: "disabled".to_string(),
+        });
+
+        let response = reactivate_user(State(service), Extension(SessionModel::default()), request)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
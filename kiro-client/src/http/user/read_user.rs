@@ -23,7 +23,7 @@ use axum::{
     Extension, Json,
 };
 use futures::StreamExt;
-use kiro_api::client::v1::{Settings, User};
+use kiro_api::client::v1::User;
 use kiro_database::{db_bridge::DatabaseOperations, DbId};
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::ReceiverStream;
@@ -135,7 +135,7 @@ async fn stream_user(
         Ok(None) => {
             tx.send(Err((
                 StatusCode::NOT_FOUND,
-                Json(serde_json::json!({ "error": "User not found" })),
+                Json(serde_json::json!({ "error": "User not found", "code": "user.not_found" })),
             )))
             .await?;
             return Ok(());
@@ -143,29 +143,42 @@ async fn stream_user(
         Err(e) => {
             tx.send(Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({ "error": e.to_string() })),
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
             )))
             .await?;
             return Ok(());
         }
     }
 
-    // Live updates
-    let mut live_stream = db.live::<UserModel>("users").await?;
+    // Live updates, scoped to the user record
+    let mut live_stream = db.live_record::<UserModel>(user_id).await?;
+
+    loop {
+        let result = tokio::select! {
+            // Dropping the live stream once the client is gone kills the live query
+            _ = tx.closed() => break,
+            result = live_stream.next() => match result {
+                Some(result) => result,
+                None => break,
+            },
+        };
 
-    while let Some(result) = live_stream.next().await {
         match result {
-            Ok(users) => {
-                for user in users {
-                    if user.id == user_id {
-                        send_user_update(tx, user).await?;
-                    }
-                }
+            Ok(Some(user)) => send_user_update(tx, user).await?,
+            Ok(None) => {
+                tx.send(Err((
+                    StatusCode::NOT_FOUND,
+                    Json(
+                        serde_json::json!({ "error": "User not found", "code": "user.not_found" }),
+                    ),
+                )))
+                .await?;
+                break;
             }
             Err(e) => {
                 tx.send(Err((
                     StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(serde_json::json!({ "error": e.to_string() })),
+                    Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
                 )))
                 .await?;
                 break;
@@ -190,14 +203,7 @@ async fn stream_user(
 async fn send_user_update(
     tx: &mpsc::Sender<Result<User, (StatusCode, Json<serde_json::Value>)>>, user: UserModel,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let user = User {
-        email: user.email,
-        avatar: user.avatar,
-        settings: Some(Settings::from(&user.settings)),
-        is_admin: user.is_admin,
-    };
-
-    tx.send(Ok(user)).await?;
+    tx.send(Ok(User::from(&user))).await?;
 
     Ok(())
 }
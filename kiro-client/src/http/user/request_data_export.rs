@@ -0,0 +1,144 @@
+// http/user/request_data_export.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
+
+use crate::{error::ClientError, utils::export, SessionModel};
+
+/// Data export route handler
+///
+/// # Description
+/// Requests an export of everything held about the current user. The archive is
+/// built in the background and a time-limited download link is sent by email.
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `session` - The current session model
+///
+/// # Returns
+/// * HTTP response with either:
+///   * `202 ACCEPTED` with empty JSON response
+///   * Error status code with message
+///
+/// # Errors
+/// * `429 TOO MANY REQUESTS` - An export was already requested recently
+/// * `500 INTERNAL SERVER ERROR` - Database or server error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{Extension, extract::State};
+/// use kiro_client::{ClientService, request_data_export::request_data_export, SessionModel};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Mock session
+/// let session = SessionModel::default();
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     request_data_export(State(service), Extension(session)).await;
+///
+///     println!("Data export requested");
+/// });
+/// ```
+#[utoipa::path(
+    post,
+    path = "/user/request_data_export",
+    tag = "user",
+    responses(
+        (status = 202, description = "Data export requested", body = String),
+        (status = 429, description = "Data export already requested recently", body = String),
+        (status = 500, description = "Internal server error", body = String)
+
+    )
+)]
+pub async fn request_data_export(
+    State(service): State<ClientService>, Extension(session): Extension<SessionModel>,
+) -> impl IntoResponse {
+    match export::request_export(&service.db, session.user_id.clone()).await {
+        Ok(_) => (StatusCode::ACCEPTED, Json(serde_json::json!({}))),
+        Err(e @ ClientError::ExportThrottled) => (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
+
+    use crate::ExportModel;
+
+    #[tokio::test]
+    async fn test_request_data_export_throttled() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let session = SessionModel::default();
+        let extension = Extension(session.clone());
+
+        mock_db
+            .expect_read_by_field_thing::<ExportModel>()
+            .withf(move |table, field, user_id, _| {
+                table == "exports" && field == "user" && *user_id == session.user_id
+            })
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![ExportModel::default()]));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let response = request_data_export(State(service), extension).await;
+
+        let response = response.into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[tokio::test]
+    async fn test_request_data_export_db_error() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let extension = Extension(SessionModel::default());
+
+        mock_db
+            .expect_read_by_field_thing::<ExportModel>()
+            .times(1)
+            .returning(|_, _, _, _| Err(DatabaseError::Internal("Database error".to_string())));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let response = request_data_export(State(service), extension).await;
+
+        let response = response.into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -21,7 +21,9 @@ use chrono::{Days, Utc};
 use kiro_database::{db_bridge::DatabaseOperations, get_env_or};
 use kiro_mailer::{ContentType, LinkModel, LinkType, Mailer, MailerTrait};
 
-use crate::{models::UserModel, SessionModel};
+use crate::{
+    models::UserModel, utils::notification::send_notification, NotificationCategory, SessionModel,
+};
 
 /// Email change request handler
 ///
@@ -94,7 +96,7 @@ pub async fn send_email_to_change_email(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(serde_json::json!({ "error": "User not found" })),
+                Json(serde_json::json!({ "error": "User not found", "code": "user.not_found" })),
             )
                 .into_response()
         }
@@ -113,7 +115,10 @@ pub async fn send_email_to_change_email(
         None => {
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({ "error": "Failed to calculate expiry time" })),
+                Json(serde_json::json!({
+                    "error": "Failed to calculate expiry time",
+                    "code": "link.expiry_time_failed",
+                })),
             )
                 .into_response()
         }
@@ -173,7 +178,14 @@ pub async fn send_email_to_change_email(
         }
     };
 
-    match Mailer::new().send_mail(message).await {
+    match send_notification(
+        &Mailer::new(),
+        &user,
+        NotificationCategory::Security,
+        message,
+    )
+    .await
+    {
         Ok(_) => {}
         Err(e) => {
             return (
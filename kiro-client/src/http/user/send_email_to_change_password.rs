@@ -21,7 +21,9 @@ use chrono::{Days, Utc};
 use kiro_database::{db_bridge::DatabaseOperations, get_env_or};
 use kiro_mailer::{ContentType, LinkModel, LinkType, Mailer, MailerTrait};
 
-use crate::{models::UserModel, SessionModel};
+use crate::{
+    models::UserModel, utils::notification::send_notification, NotificationCategory, SessionModel,
+};
 
 /// Password change request handler
 ///
@@ -95,7 +97,7 @@ pub async fn send_email_to_change_password(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(serde_json::json!({ "error": "User not found" })),
+                Json(serde_json::json!({ "error": "User not found", "code": "user.not_found" })),
             )
                 .into_response()
         }
@@ -114,7 +116,10 @@ pub async fn send_email_to_change_password(
         None => {
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({ "error": "Failed to calculate expiry time" })),
+                Json(serde_json::json!({
+                    "error": "Failed to calculate expiry time",
+                    "code": "link.expiry_time_failed",
+                })),
             )
                 .into_response()
         }
@@ -174,7 +179,14 @@ pub async fn send_email_to_change_password(
         }
     };
 
-    match Mailer::new().send_mail(message).await {
+    match send_notification(
+        &Mailer::new(),
+        &user,
+        NotificationCategory::Security,
+        message,
+    )
+    .await
+    {
         Ok(_) => {}
         Err(e) => {
             return (
@@ -0,0 +1,222 @@
+// http/user/set_primary_email.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
+use kiro_api::client::v1::SetPrimaryEmailRequest;
+use kiro_database::{db_bridge::DatabaseOperations, DbId};
+
+use crate::{error::ClientError, models::UserModel, SessionModel, UserEmailModel};
+
+/// Set primary email route handler
+///
+/// # Description
+/// Promotes a verified secondary email address of the current user to primary.
+/// The previous primary address stays attached as a verified secondary address.
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `session` - The current session model
+/// * `request` - The request containing the identifier of the address
+///
+/// # Returns
+/// * HTTP response with either:
+///   * `200 OK` with empty JSON response
+///   * Error status code with message
+///
+/// # Errors
+/// * `404 NOT FOUND` - User or address not found
+/// * `409 CONFLICT` - The address is not verified
+/// * `500 INTERNAL SERVER ERROR` - Database or server error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{Extension, extract::State, Json};
+/// use kiro_api::client::v1::SetPrimaryEmailRequest;
+/// use kiro_client::{ClientService, set_primary_email::set_primary_email, SessionModel};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Mock session
+/// let session = SessionModel::default();
+///
+/// // Mock request
+/// let request = SetPrimaryEmailRequest {
+///     id: "address".to_string(),
+/// };
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     set_primary_email(State(service), Extension(session), Json(request)).await;
+///
+///     println!("Primary email changed");
+/// });
+/// ```
+#[utoipa::path(
+    post,
+    path = "/user/set_primary_email",
+    tag = "user",
+    params(
+        SetPrimaryEmailRequest
+    ),
+    responses(
+        (status = 200, description = "Primary email changed", body = String),
+        (status = 404, description = "Email not found", body = String),
+        (status = 409, description = "Email not verified", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+pub async fn set_primary_email(
+    State(service): State<ClientService>, Extension(session): Extension<SessionModel>,
+    Json(request): Json<SetPrimaryEmailRequest>,
+) -> impl IntoResponse {
+    // Get user details
+    let user = match service
+        .db
+        .select::<UserModel>(session.user_id.clone())
+        .await
+    {
+        Ok(Some(user)) => user,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({ "error": "User not found", "code": "user.not_found" })),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({ "error": format!("Database error: {}", e) })),
+            )
+                .into_response()
+        }
+    };
+
+    let id = DbId::from(("user_emails", request.id.as_str()));
+
+    match UserEmailModel::promote(&service.db, &user, id).await {
+        Ok(_) => (StatusCode::OK, Json(serde_json::json!({}))).into_response(),
+        Err(e) => {
+            let status = match e {
+                ClientError::EmailNotFound => StatusCode::NOT_FOUND,
+                ClientError::EmailNotVerified => StatusCode::CONFLICT,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+
+            (
+                status,
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::db_bridge::MockDatabaseOperations;
+
+    #[tokio::test]
+    async fn test_set_primary_email_success() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let extension = Extension(SessionModel::default());
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_select::<UserEmailModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(UserEmailModel {
+                    verified: true,
+                    ..Default::default()
+                }))
+            });
+
+        // The previous primary address already has a row
+        mock_db
+            .expect_read_by_field_thing::<UserEmailModel>()
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(vec![UserEmailModel {
+                    email: "test@example.com".to_string(),
+                    verified: true,
+                    ..Default::default()
+                }])
+            });
+
+        mock_db
+            .expect_update_chain::<UserModel>()
+            .times(1)
+            .returning(|_, _| Ok(vec![UserModel::default()]));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = SetPrimaryEmailRequest {
+            id: "address".to_string(),
+        };
+
+        let response = set_primary_email(State(service), extension, Json(request))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_set_primary_email_unverified() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let extension = Extension(SessionModel::default());
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_select::<UserEmailModel>()
+            .times(1)
+            .returning(|_| Ok(Some(UserEmailModel::default())));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = SetPrimaryEmailRequest {
+            id: "address".to_string(),
+        };
+
+        let response = set_primary_email(State(service), extension, Json(request))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+}
@@ -17,22 +17,25 @@
 use super::*;
 
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
-#[cfg(feature = "mailer")]
-use chrono::{Days, Utc};
 use kiro_api::client::v1::UpdateEmailRequest;
-use kiro_database::db_bridge::DatabaseOperations;
 #[cfg(feature = "mailer")]
-use kiro_database::{get_env_or, DbId};
+use kiro_database::db_bridge::DatabaseOperations;
 
 #[cfg(feature = "mailer")]
-use kiro_mailer::{ContentType, LinkModel, LinkType, Mailer, MailerTrait};
+use kiro_mailer::{LinkModel, LinkType, MailerError};
 
-use crate::{models::UserModel, SessionModel};
+#[cfg(feature = "mailer")]
+use crate::utils::email_change::start_email_change;
+use crate::{error::ClientError, models::UserModel, SessionModel};
 
 /// User email update route handler
 ///
 /// # Description
-/// Updates the current user's email address
+/// Requests the change of the current user's email address. The new address is
+/// stored as pending and only replaces the current one once confirmed from the
+/// link emailed to it, through `/auth/confirm_email_change`. A cancel link is
+/// emailed to the current address. Without the mailer the new address cannot be
+/// verified and the change applies at once.
 ///
 /// # Arguments
 /// * `service` - The client service instance
@@ -45,8 +48,8 @@ use crate::{models::UserModel, SessionModel};
 ///   * Error status code with message
 ///
 /// # Errors
-/// * `400 BAD REQUEST` - Invalid email address
 /// * `404 NOT FOUND` - Link not found
+/// * `409 CONFLICT` - Email already in use
 /// * `500 INTERNAL SERVER ERROR` - Database or server error
 ///
 /// # Example
@@ -81,7 +84,7 @@ use crate::{models::UserModel, SessionModel};
 /// tokio::runtime::Runtime::new().unwrap().block_on(async {
 ///     update_email(State(service), Extension(session), Json(request)).await;
 ///
-///     println!("Email updated");
+///     println!("Email change pending");
 /// });
 /// ```
 #[utoipa::path(
@@ -92,9 +95,9 @@ use crate::{models::UserModel, SessionModel};
         UpdateEmailRequest
     ),
     responses(
-        (status = 200, description = "Email updated", body = String),
-        (status = 400, description = "Invalid email address", body = String),
+        (status = 200, description = "Email change pending", body = String),
         (status = 404, description = "Link not found", body = String),
+        (status = 409, description = "Email already in use", body = String),
         (status = 500, description = "Internal server error", body = String)
 
     )
@@ -104,180 +107,159 @@ pub async fn update_email(
     Json(request): Json<UpdateEmailRequest>,
 ) -> impl IntoResponse {
     #[cfg(feature = "mailer")]
-    {
+    let result = {
         // Validate temporary change key
-        let link_id = DbId::from(("links".to_string(), request.temp_token.clone()));
-        match service.db.select::<LinkModel>(link_id).await {
-            Ok(Some(link)) => link,
-            Ok(None) => {
+        let link = match LinkModel::get_valid_link(
+            &service.db,
+            &request.temp_token,
+            LinkType::EmailChange,
+        )
+        .await
+        {
+            Ok(link) if link.user == session.user_id => link,
+            Ok(_)
+            | Err(MailerError::NotFound | MailerError::InvalidType | MailerError::Expired) => {
                 return (
                     StatusCode::NOT_FOUND,
-                    Json(serde_json::json!({ "error": "Invalid or expired change key" })),
+                    Json(serde_json::json!({
+                        "error": "Invalid or expired change key",
+                        "code": "link.invalid_change_key",
+                    })),
                 )
                     .into_response()
             }
             Err(e) => {
                 return (
                     StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(serde_json::json!({ "error": format!("Database error: {}", e) })),
+                    Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
                 )
                     .into_response()
             }
         };
-    }
 
-    // Check if email is already used
-    match UserModel::check_email(&service.db, request.email.clone()).await {
-        Ok(true) => {}
-        Ok(false) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({ "error": "Email already in use" })),
-            )
-                .into_response()
-        }
-        Err(e) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({ "error": e.to_string() })),
-            )
-                .into_response()
-        }
-    };
+        // Get user details
+        let user =
+            match service
+                .db
+                .select::<UserModel>(session.user_id.clone())
+                .await
+            {
+                Ok(Some(user)) => user,
+                Ok(None) => return (
+                    StatusCode::NOT_FOUND,
+                    Json(
+                        serde_json::json!({ "error": "User not found", "code": "user.not_found" }),
+                    ),
+                )
+                    .into_response(),
+                Err(e) => {
+                    return (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(serde_json::json!({ "error": format!("Database error: {}", e) })),
+                    )
+                        .into_response()
+                }
+            };
 
-    // Update email
-    match service
-        .db
-        .update_field(session.user_id.clone(), "email", request.email.clone())
-        .await
-    {
-        Ok(_) => {}
-        Err(e) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({ "error": format!("Failed to update email: {}", e) })),
-            )
-                .into_response()
+        // Store the pending email and send the confirmation and cancel links,
+        // then cleanup the change link
+        match start_email_change(&service.db, &user, &request.email).await {
+            Ok(()) => service
+                .db
+                .delete(link.id)
+                .await
+                .map(|_| ())
+                .map_err(ClientError::Database),
+            Err(e) => Err(e),
         }
     };
 
-    #[cfg(feature = "mailer")]
+    // Without the mailer the new address cannot be verified
+    #[cfg(not(feature = "mailer"))]
+    let result = match UserModel::request_email_change(
+        &service.db,
+        session.user_id.clone(),
+        request.email.clone(),
+    )
+    .await
     {
-        // Get user details
-        let user = match service
-            .db
-            .select::<UserModel>(session.user_id.clone())
+        Ok(()) => UserModel::confirm_email_change(&service.db, session.user_id.clone())
             .await
-        {
-            Ok(Some(user)) => user,
-            Ok(None) => {
-                return (
-                    StatusCode::NOT_FOUND,
-                    Json(serde_json::json!({ "error": "User not found" })),
-                )
-                    .into_response()
-            }
-            Err(e) => {
-                return (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(serde_json::json!({ "error": format!("Database error: {}", e) })),
-                )
-                    .into_response()
-            }
-        };
+            .map(|_| ()),
+        Err(e) => Err(e),
+    };
 
-        // Cleanup old change links
-        match LinkModel::delete_link_by_user_and_type(
-            &service.db,
-            session.user_id.clone(),
-            LinkType::EmailChange,
+    match result {
+        Ok(()) => (StatusCode::OK, Json(serde_json::json!({}))).into_response(),
+        Err(e @ ClientError::EmailTaken) => (
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
         )
-        .await
-        {
-            Ok(_) => {}
-            Err(e) => return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({ "error": format!("Failed to delete old links: {}", e) })),
-            )
-                .into_response(),
-        };
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        )
+            .into_response(),
+    }
+}
 
-        // Generate reset link for safety
-        let expiry = match Utc::now().checked_add_days(Days::new(2)) {
-            Some(expiry) => expiry,
-            None => {
-                return (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(serde_json::json!({ "error": "Failed to calculate expiry" })),
-                )
-                    .into_response()
-            }
-        };
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        let reset_link = match LinkModel::create_from_user(
-            &service.db,
-            session.user_id.clone(),
-            expiry,
-            LinkType::EmailReset,
-        )
-        .await
-        .map(|link| link.construct_link())
+    use kiro_database::{db_bridge::MockDatabaseOperations, DbId};
+
+    #[tokio::test]
+    async fn test_update_email_taken() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        #[cfg(feature = "mailer")]
         {
-            Ok(link) => link,
-            Err(e) => return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({ "error": format!("Failed to create reset link: {}", e) })),
-            )
-                .into_response(),
-        };
+            mock_db
+                .expect_select::<LinkModel>()
+                .times(1)
+                .returning(|_| {
+                    Ok(Some(LinkModel {
+                        id: DbId::from(("links", "link")),
+                        user: DbId::from(("users", "user")),
+                        link_type: LinkType::EmailChange,
+                        expiry: kiro_database::DbDateTime::from(
+                            chrono::Utc::now() + chrono::Duration::hours(1),
+                        ),
+                    }))
+                });
 
-        // Send confirmation email
-        let template = match Mailer::load_template("email_changed.html").await {
-            Ok(template) => template,
-            Err(e) => return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(
-                    serde_json::json!({ "error": format!("Failed to load email template: {}", e) }),
-                ),
-            )
-                .into_response(),
+            mock_db
+                .expect_select::<UserModel>()
+                .times(1)
+                .returning(|_| Ok(Some(UserModel::default())));
         }
-        .replace("${{USER_NAME}}", &user.email)
-        .replace("${{RESET_URL}}", &reset_link)
-        .replace("${{NEW_MAIL}}", &request.email);
 
-        let from = get_env_or("SMTP_USER", "test@example.com");
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![UserModel::default()]));
 
-        let message = match Mailer::build_mail(
-            &from,
-            &user.email,
-            "Email Changed",
-            ContentType::TEXT_HTML,
-            template,
-        ) {
-            Ok(message) => message,
-            Err(e) => {
-                return (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(serde_json::json!({ "error": format!("Failed to build email: {}", e) })),
-                )
-                    .into_response()
-            }
+        mock_db.expect_update_field::<Option<String>>().never();
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
         };
 
-        match Mailer::new().send_mail(message).await {
-            Ok(_) => {}
-            Err(e) => {
-                return (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(serde_json::json!({ "error": format!("Failed to send email: {}", e) })),
-                )
-                    .into_response()
-            }
+        let session = SessionModel {
+            user_id: DbId::from(("users", "user")),
+            ..Default::default()
         };
-    }
 
-    (StatusCode::OK, Json(serde_json::json!({}))).into_response()
-}
+        let request = UpdateEmailRequest {
+            email: "taken@example.com".to_string(),
+            temp_token: "link".to_string(),
+        };
 
-// TODO: Fix mailer tests Trait, before adding tests
+        let response = update_email(State(service), Extension(session), Json(request))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+}
@@ -17,10 +17,10 @@
 use super::*;
 
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
-use kiro_api::client::v1::UpdateLanguageRequest;
-use kiro_database::db_bridge::DatabaseOperations;
+use kiro_api::client::v1::{Settings, UpdateLanguageRequest};
 
-use crate::SessionModel;
+use super::update_settings::settings_error;
+use crate::{SessionModel, UserModel};
 
 /// Language update route handler
 ///
@@ -97,22 +97,23 @@ pub async fn update_language(
     if !matches!(request.language, 0..=9) {
         return (
             StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({ "error": "Invalid language value" })),
+            Json(serde_json::json!({
+                "error": "Invalid language value",
+                "code": "settings.invalid_language",
+            })),
         )
             .into_response();
     }
 
-    match service
-        .db
-        .update_field(session.user_id.clone(), "language", request.language)
-        .await
+    let patch = Settings {
+        language: Some(request.language),
+        ..Default::default()
+    };
+
+    match UserModel::update_setting(&service.db, session.user_id.clone(), &patch, "language").await
     {
         Ok(_) => (StatusCode::OK, Json(serde_json::json!({}))).into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({ "error": e.to_string() })),
-        )
-            .into_response(),
+        Err(e) => settings_error(e),
     }
 }
 
@@ -120,7 +121,11 @@ pub async fn update_language(
 mod tests {
     use super::*;
 
-    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
+    use kiro_database::{
+        db_bridge::MockDatabaseOperations,
+        db_query::{DbValue, FieldUpdate},
+        DatabaseError,
+    };
     use mockall::predicate::eq;
 
     #[tokio::test]
@@ -131,10 +136,19 @@ mod tests {
         let user_id = session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(eq(user_id), eq("language"), eq(0))
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => settings["language"] == "English",
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -155,10 +169,10 @@ mod tests {
         let extension = Extension(session.clone());
 
         mock_db
-            .expect_update_field()
-            .with(eq(session.user_id), eq("language"), eq(0))
+            .expect_select::<UserModel>()
+            .with(eq(session.user_id))
             .times(1)
-            .returning(|_, _, _| Err(DatabaseError::Internal("Database error".to_string())));
+            .returning(|_| Err(DatabaseError::Internal("Database error".to_string())));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -187,10 +201,19 @@ mod tests {
         let user_id = session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(eq(user_id), eq("language"), eq(0))
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => settings["language"] == "English",
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -17,10 +17,10 @@
 use super::*;
 
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
-use kiro_api::client::v1::UpdateNotificationsRequest;
-use kiro_database::db_bridge::DatabaseOperations;
+use kiro_api::client::v1::{Notifications, Settings, UpdateNotificationsRequest};
 
-use crate::SessionModel;
+use super::update_settings::settings_error;
+use crate::{SessionModel, UserModel};
 
 /// Update notifications route handler
 ///
@@ -95,29 +95,35 @@ pub async fn update_notifications(
     Json(request): Json<UpdateNotificationsRequest>,
 ) -> impl IntoResponse {
     // Validate the notification field name
-    if !["email", "push", "sms"].contains(&request.field.clone().as_str()) {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({ "error": format!("Invalid notification field: {}", request.field.clone()) })),
-        )
-            .into_response();
+    let mut notifications = Notifications::default();
+    match request.field.as_str() {
+        "email" => notifications.email = request.value,
+        "push" => notifications.push = request.value,
+        "sms" => notifications.sms = request.value,
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": format!("Invalid notification field: {}", request.field) })),
+            )
+                .into_response()
+        }
     }
 
-    match service
-        .db
-        .update_field(
-            session.user_id.clone(),
-            &format!("settings/notifications/{}", request.field),
-            request.value,
-        )
-        .await
+    let patch = Settings {
+        notifications: Some(notifications),
+        ..Default::default()
+    };
+
+    match UserModel::update_setting(
+        &service.db,
+        session.user_id.clone(),
+        &patch,
+        &format!("notifications.{}", request.field),
+    )
+    .await
     {
         Ok(_) => (StatusCode::OK, Json(serde_json::json!({}))).into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({ "error": e.to_string() })),
-        )
-            .into_response(),
+        Err(e) => settings_error(e),
     }
 }
 
@@ -125,7 +131,11 @@ pub async fn update_notifications(
 mod tests {
     use super::*;
 
-    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
+    use kiro_database::{
+        db_bridge::MockDatabaseOperations,
+        db_query::{DbValue, FieldUpdate},
+        DatabaseError,
+    };
     use mockall::predicate::eq;
 
     #[tokio::test]
@@ -135,14 +145,21 @@ mod tests {
         let extension = Extension(session.clone());
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(session.user_id.clone()),
-                eq("settings/notifications/email"),
-                eq(true),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(session.user_id.clone()))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["notifications"]["email"] == true
+                }
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -166,14 +183,21 @@ mod tests {
         let extension = Extension(session.clone());
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(session.user_id.clone()),
-                eq("settings/notifications/push"),
-                eq(false),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(session.user_id.clone()))
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["notifications"]["push"] == false
+                }
+                _ => false,
+            })
+            .times(1)
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -197,14 +221,21 @@ mod tests {
         let extension = Extension(session.clone());
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(session.user_id.clone()),
-                eq("settings/notifications/sms"),
-                eq(true),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(session.user_id.clone()))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["notifications"]["sms"] == true
+                }
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -255,14 +286,10 @@ mod tests {
         let extension = Extension(session.clone());
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(session.user_id.clone()),
-                eq("settings/notifications/email"),
-                eq(true),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(session.user_id.clone()))
             .times(1)
-            .returning(|_, _, _| Err(DatabaseError::Internal("Database error".to_string())));
+            .returning(|_| Err(DatabaseError::Internal("Database error".to_string())));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -293,10 +320,21 @@ mod tests {
         let user_id = admin_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(eq(user_id), eq("settings/notifications/email"), eq(true))
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["notifications"]["email"] == true
+                }
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
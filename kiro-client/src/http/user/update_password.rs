@@ -28,6 +28,8 @@ use kiro_database::{get_env_or, DbId};
 use kiro_mailer::{ContentType, LinkModel, LinkType, Mailer, MailerTrait};
 
 use crate::{models::UserModel, SessionModel};
+#[cfg(feature = "mailer")]
+use crate::{utils::notification::send_notification, NotificationCategory};
 
 /// User password update route handler
 ///
@@ -113,7 +115,10 @@ pub async fn update_password(
             Ok(None) => {
                 return (
                     StatusCode::NOT_FOUND,
-                    Json(serde_json::json!({ "error": "Invalid or expired change key" })),
+                    Json(serde_json::json!({
+                        "error": "Invalid or expired change key",
+                        "code": "link.invalid_change_key",
+                    })),
                 )
                     .into_response()
             }
@@ -137,7 +142,7 @@ pub async fn update_password(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(serde_json::json!({ "error": "User not found" })),
+                Json(serde_json::json!({ "error": "User not found", "code": "user.not_found" })),
             )
                 .into_response()
         }
@@ -156,7 +161,7 @@ pub async fn update_password(
         Err(e) => {
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({ "error": e.to_string() })),
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
             )
                 .into_response()
         }
@@ -168,7 +173,7 @@ pub async fn update_password(
         Err(e) => {
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({ "error": e.to_string() })),
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
             )
                 .into_response()
         }
@@ -184,7 +189,7 @@ pub async fn update_password(
         Err(e) => {
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({ "error": e.to_string() })),
+                Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
             )
                 .into_response();
         }
@@ -271,7 +276,14 @@ pub async fn update_password(
             }
         };
 
-        match Mailer::new().send_mail(message).await {
+        match send_notification(
+            &Mailer::new(),
+            &user,
+            NotificationCategory::Security,
+            message,
+        )
+        .await
+        {
             Ok(_) => {}
             Err(e) => {
                 return (
@@ -17,15 +17,16 @@
 use super::*;
 
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
-use kiro_api::client::v1::UpdatePrivacyRequest;
-use kiro_database::db_bridge::DatabaseOperations;
+use kiro_api::client::v1::{Privacy, Settings, UpdatePrivacyRequest};
 
-use crate::SessionModel;
+use super::update_settings::settings_error;
+use crate::{SessionModel, UserModel};
 
 /// Privacy update route handler
 ///
 /// # Description
-/// Updates the current user's privacy settings
+/// Updates the current user's privacy settings. Withdrawing a consent applies
+/// the privacy policy of the user to the IP addresses of its existing sessions.
 ///
 /// # Arguments
 /// * `service` - The client service instance
@@ -95,29 +96,35 @@ pub async fn update_privacy(
     Json(request): Json<UpdatePrivacyRequest>,
 ) -> impl IntoResponse {
     // Validate the privacy field name
-    if !["data_collection", "location"].contains(&request.field.clone().as_str()) {
-        return (
+    let mut privacy = Privacy::default();
+    match request.field.as_str() {
+        "data_collection" => privacy.data_collection = request.value,
+        "location" => privacy.location = request.value,
+        _ => return (
             StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({ "error": format!("Invalid privacy field: {}", request.field.clone()) })),
+            Json(
+                serde_json::json!({ "error": format!("Invalid privacy field: {}", request.field) }),
+            ),
         )
-            .into_response();
+            .into_response(),
     }
 
-    match service
-        .db
-        .update_field(
-            session.user_id.clone(),
-            &format!("settings/privacy/{}", request.field),
-            request.value,
-        )
-        .await
+    let patch = Settings {
+        privacy: Some(privacy),
+        ..Default::default()
+    };
+
+    // Withdrawn consents also apply to the existing sessions
+    match UserModel::update_setting(
+        &service.db,
+        session.user_id.clone(),
+        &patch,
+        &format!("privacy.{}", request.field),
+    )
+    .await
     {
         Ok(_) => (StatusCode::OK, Json(serde_json::json!({}))).into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({ "error": e.to_string() })),
-        )
-            .into_response(),
+        Err(e) => settings_error(e),
     }
 }
 
@@ -125,9 +132,15 @@ pub async fn update_privacy(
 mod tests {
     use super::*;
 
-    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
+    use kiro_database::{
+        db_bridge::MockDatabaseOperations,
+        db_query::{DbValue, FieldUpdate},
+        DatabaseError,
+    };
     use mockall::predicate::eq;
 
+    use crate::UserModel;
+
     #[tokio::test]
     async fn test_update_privacy_data_collection_success() {
         let session = SessionModel::default();
@@ -135,14 +148,21 @@ mod tests {
         let extension = Extension(session.clone());
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(session.user_id.clone()),
-                eq("settings/privacy/data_collection"),
-                eq(true),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(session.user_id.clone()))
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["privacy"]["data_collection"] == true
+                }
+                _ => false,
+            })
+            .times(1)
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -165,14 +185,21 @@ mod tests {
         let extension = Extension(session.clone());
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(session.user_id.clone()),
-                eq("settings/privacy/location"),
-                eq(true),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(session.user_id.clone()))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["privacy"]["location"] == true
+                }
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -188,6 +215,58 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn test_update_privacy_withdraw_consent() {
+        let session = SessionModel::default();
+        let mut mock_db = MockDatabaseOperations::new();
+        let extension = Extension(session.clone());
+
+        mock_db
+            .expect_select::<UserModel>()
+            .with(eq(session.user_id.clone()))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["privacy"]["data_collection"] == false
+                }
+                _ => false,
+            })
+            .times(1)
+            .returning(|_| {
+                let mut user = UserModel::default();
+                user.settings.privacy.data_collection = false;
+                Ok(vec![user])
+            });
+
+        // Withdrawing a consent anonymizes the existing sessions
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_read_by_field_thing::<SessionModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = UpdatePrivacyRequest {
+            field: "data_collection".to_string(),
+            value: false,
+        };
+        let response = update_privacy(State(service), extension, Json(request)).await;
+
+        let response = response.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
     #[tokio::test]
     async fn test_update_privacy_invalid_field() {
         let session = SessionModel::default();
@@ -220,14 +299,10 @@ mod tests {
         let extension = Extension(session.clone());
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(session.user_id.clone()),
-                eq("settings/privacy/data_collection"),
-                eq(true),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(session.user_id.clone()))
             .times(1)
-            .returning(|_, _, _| Err(DatabaseError::Internal("Database error".to_string())));
+            .returning(|_| Err(DatabaseError::Internal("Database error".to_string())));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -257,14 +332,21 @@ mod tests {
         let extension = Extension(session.clone());
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(session.user_id.clone()),
-                eq("settings/privacy/data_collection"),
-                eq(true),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(session.user_id.clone()))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["privacy"]["data_collection"] == true
+                }
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -312,14 +394,21 @@ mod tests {
         let extension = Extension(session.clone());
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(session.user_id.clone()),
-                eq("settings/privacy/data_collection"),
-                eq(true),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(session.user_id.clone()))
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["privacy"]["data_collection"] == true
+                }
+                _ => false,
+            })
+            .times(1)
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -339,14 +428,21 @@ mod tests {
         let extension = Extension(session.clone());
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(session.user_id.clone()),
-                eq("settings/privacy/location"),
-                eq(false),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(session.user_id.clone()))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["privacy"]["location"] == false
+                }
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
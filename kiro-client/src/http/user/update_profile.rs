@@ -0,0 +1,264 @@
+// http/user/update_profile.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
+use kiro_api::client::v1::UpdateProfileRequest;
+
+use crate::{error::ClientError, SessionModel, UserModel};
+
+/// Profile update route handler
+///
+/// # Description
+/// Updates the current user's public profile. Only the fields present in the
+/// request are updated, an empty string clears the field.
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `session` - The current session model
+/// * `request` - The profile update request
+///
+/// # Returns
+/// * HTTP response with either:
+///   * `200 OK` with empty JSON response
+///   * Error status code with message
+///
+/// # Errors
+/// * `400 BAD REQUEST` - Invalid display name, handle, bio, timezone or locale
+/// * `409 CONFLICT` - Handle already in use
+/// * `500 INTERNAL SERVER ERROR` - Database or server error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{Extension, extract::State, Json};
+/// use kiro_api::client::v1::UpdateProfileRequest;
+/// use kiro_client::{ClientService, update_profile::update_profile, SessionModel};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Mock session
+/// let session = SessionModel::default();
+///
+/// // Mock request
+/// let request = UpdateProfileRequest {
+///     display_name: Some("Jane Doe".to_string()),
+///     handle: Some("jane.doe".to_string()),
+///     bio: None,
+///     timezone: Some("Europe/Paris".to_string()),
+///     locale: Some("fr-FR".to_string()),
+/// };
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     update_profile(State(service), Extension(session), Json(request)).await;
+///
+///     println!("Profile updated");
+/// });
+/// ```
+#[utoipa::path(
+    post,
+    path = "/user/update_profile",
+    tag = "user",
+    params(
+        UpdateProfileRequest
+    ),
+    responses(
+        (status = 200, description = "Profile updated", body = String),
+        (status = 400, description = "Invalid profile field", body = String),
+        (status = 409, description = "Handle already in use", body = String),
+        (status = 500, description = "Internal server error", body = String)
+
+    )
+)]
+pub async fn update_profile(
+    State(service): State<ClientService>, Extension(session): Extension<SessionModel>,
+    Json(request): Json<UpdateProfileRequest>,
+) -> impl IntoResponse {
+    match UserModel::update_profile(&service.db, session.user_id.clone(), &request).await {
+        Ok(_) => (StatusCode::OK, Json(serde_json::json!({}))).into_response(),
+        Err(e @ ClientError::InvalidProfile(_)) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        )
+            .into_response(),
+        Err(e @ ClientError::HandleTaken) => (
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError, DbId};
+    use mockall::predicate::eq;
+
+    #[tokio::test]
+    async fn test_update_profile_success() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let session = SessionModel::default();
+        let extension = Extension(session.clone());
+        let user_id = session.user_id.clone();
+
+        // The user keeps its own handle
+        let owner_id = user_id.clone();
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .with(eq("users"), eq("handle"), eq("jane.doe"), eq(None))
+            .times(1)
+            .returning(move |_, _, _, _| {
+                Ok(vec![UserModel {
+                    id: owner_id.clone(),
+                    ..Default::default()
+                }])
+            });
+
+        mock_db
+            .expect_update_field::<Option<String>>()
+            .with(
+                eq(user_id.clone()),
+                eq("handle"),
+                eq(Some("jane.doe".to_string())),
+            )
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        mock_db
+            .expect_update_field::<Option<String>>()
+            .with(
+                eq(user_id),
+                eq("timezone"),
+                eq(Some("Europe/Paris".to_string())),
+            )
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Json(UpdateProfileRequest {
+            handle: Some("jane.doe".to_string()),
+            timezone: Some("Europe/Paris".to_string()),
+            ..Default::default()
+        });
+
+        let response = update_profile(State(service), extension, request).await;
+
+        let response = response.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_update_profile_handle_taken() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let session = SessionModel::default();
+        let extension = Extension(session.clone());
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(vec![UserModel {
+                    id: DbId::from(("users", "other")),
+                    ..Default::default()
+                }])
+            });
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Json(UpdateProfileRequest {
+            handle: Some("jane.doe".to_string()),
+            ..Default::default()
+        });
+
+        let response = update_profile(State(service), extension, request).await;
+
+        let response = response.into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+        assert_eq!(error["error"], "Handle already in use");
+    }
+
+    #[tokio::test]
+    async fn test_update_profile_invalid_handle() {
+        let mock_db = MockDatabaseOperations::new();
+        let session = SessionModel::default();
+        let extension = Extension(session.clone());
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Json(UpdateProfileRequest {
+            handle: Some("jane doe".to_string()),
+            ..Default::default()
+        });
+
+        let response = update_profile(State(service), extension, request).await;
+
+        let response = response.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_update_profile_db_error() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let session = SessionModel::default();
+        let extension = Extension(session.clone());
+
+        mock_db
+            .expect_update_field::<Option<String>>()
+            .times(1)
+            .returning(|_, _, _| Err(DatabaseError::Internal("Database error".to_string())));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Json(UpdateProfileRequest {
+            display_name: Some("Jane Doe".to_string()),
+            ..Default::default()
+        });
+
+        let response = update_profile(State(service), extension, request).await;
+
+        let response = response.into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -17,10 +17,10 @@
 use super::*;
 
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
-use kiro_api::client::v1::UpdateSecurityRequest;
-use kiro_database::db_bridge::DatabaseOperations;
+use kiro_api::client::v1::{Security, Settings, UpdateSecurityRequest};
 
-use crate::SessionModel;
+use super::update_settings::settings_error;
+use crate::{SessionModel, UserModel};
 
 /// Security update route handler
 ///
@@ -96,39 +96,47 @@ pub async fn update_security(
     if request.field.as_str() == "qr_code" {
         return (
             StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({ "error": "QR code field is immutable" })),
+            Json(serde_json::json!({
+                "error": "QR code field is immutable",
+                "code": "settings.qr_code_immutable",
+            })),
         )
             .into_response();
     }
 
-    let value = match request.field.as_str() {
-        "two_factor" | "magic_link" => serde_json::Value::Bool(request.value.is_some()),
-        "qr_code" => serde_json::Value::String(request.value.is_some().to_string()),
-        _ => unreachable!(),
+    let value = request.value.is_some();
+
+    let mut security = Security::default();
+    match request.field.as_str() {
+        "two_factor" => security.two_factor = value,
+        "magic_link" => security.magic_link = value,
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({
+                    "error": "Invalid security field",
+                    "code": "settings.invalid_security_field",
+                })),
+            )
+                .into_response()
+        }
+    }
+
+    let patch = Settings {
+        security: Some(security),
+        ..Default::default()
     };
 
-    if !["two_factor", "qr_code", "magic_link"].contains(&request.field.as_str()) {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({ "error": "Invalid security field" })),
-        )
-            .into_response();
-    }
-    match service
-        .db
-        .update_field(
-            session.user_id.clone(),
-            &format!("settings/security/{}", request.field),
-            value,
-        )
-        .await
+    match UserModel::update_setting(
+        &service.db,
+        session.user_id.clone(),
+        &patch,
+        &format!("security.{}", request.field),
+    )
+    .await
     {
         Ok(_) => (StatusCode::OK, Json(serde_json::json!({}))).into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({ "error": e.to_string() })),
-        )
-            .into_response(),
+        Err(e) => settings_error(e),
     }
 }
 
@@ -137,7 +145,11 @@ mod tests {
     use super::*;
 
     use kiro_api::client::v1::update_security_request::Value as JsonValue;
-    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
+    use kiro_database::{
+        db_bridge::MockDatabaseOperations,
+        db_query::{DbValue, FieldUpdate},
+        DatabaseError,
+    };
     use mockall::predicate::eq;
 
     #[tokio::test]
@@ -148,14 +160,21 @@ mod tests {
         let user_id = session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(user_id),
-                eq("settings/security/two_factor"),
-                eq(serde_json::Value::Bool(true)),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["security"]["two_factor"] == true
+                }
+                _ => false,
+            })
+            .times(1)
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -180,14 +199,21 @@ mod tests {
         let user_id = session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(user_id),
-                eq("settings/security/two_factor"),
-                eq(serde_json::Value::Bool(false)),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["security"]["two_factor"] == false
+                }
+                _ => false,
+            })
+            .times(1)
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -212,14 +238,21 @@ mod tests {
         let user_id = session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(user_id),
-                eq("settings/security/magic_link"),
-                eq(serde_json::Value::Bool(true)),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["security"]["magic_link"] == true
+                }
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -243,14 +276,10 @@ mod tests {
         let extension = Extension(session.clone());
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(session.user_id),
-                eq("settings/security/two_factor"),
-                eq(serde_json::Value::Bool(true)),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(session.user_id))
             .times(1)
-            .returning(|_, _, _| Err(DatabaseError::Internal("Database error".to_string())));
+            .returning(|_| Err(DatabaseError::Internal("Database error".to_string())));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -282,14 +311,21 @@ mod tests {
         let user_id = session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(user_id),
-                eq("settings/security/two_factor"),
-                eq(serde_json::Value::Bool(true)),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["security"]["two_factor"] == true
+                }
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -314,14 +350,21 @@ mod tests {
         let user_id = session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(user_id),
-                eq("settings/security/two_factor"),
-                eq(serde_json::Value::Bool(true)),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["security"]["two_factor"] == true
+                }
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -343,14 +386,21 @@ mod tests {
         let user_id = session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(user_id),
-                eq("settings/security/magic_link"),
-                eq(serde_json::Value::Bool(true)),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["security"]["magic_link"] == true
+                }
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -0,0 +1,259 @@
+// http/user/update_settings.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use kiro_api::client::v1::UpdateSettingsRequest;
+
+use crate::{error::ClientError, SessionModel, UserModel};
+
+/// Settings update route handler
+///
+/// # Description
+/// Updates the fields of the current user's settings listed in the update
+/// mask, all at once. The update is rejected if the settings changed since the
+/// given version was read.
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `session` - The current session model
+/// * `request` - The settings update request
+///
+/// # Returns
+/// * HTTP response with either:
+///   * `200 OK` with the new settings version
+///   * Error status code with message
+///
+/// # Errors
+/// * `400 BAD REQUEST` - Empty mask, unknown path or invalid value
+/// * `409 CONFLICT` - Settings modified since the given version
+/// * `500 INTERNAL SERVER ERROR` - Database or server error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{Extension, extract::State, Json};
+/// use kiro_api::{
+///     client::v1::{Settings, UpdateSettingsRequest},
+///     google::protobuf::FieldMask,
+/// };
+/// use kiro_client::{ClientService, update_settings::update_settings, SessionModel};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Mock session
+/// let session = SessionModel::default();
+///
+/// // Mock request
+/// let request = UpdateSettingsRequest {
+///     settings: Some(Settings {
+///         theme: Some(1), // Dark
+///         ..Default::default()
+///     }),
+///     update_mask: Some(FieldMask {
+///         paths: vec!["theme".to_string()],
+///     }),
+///     version: 0,
+/// };
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     update_settings(State(service), Extension(session), Json(request)).await;
+///
+///     println!("Settings updated");
+/// });
+/// ```
+#[utoipa::path(
+    post,
+    path = "/user/update_settings",
+    tag = "user",
+    params(
+        UpdateSettingsRequest
+    ),
+    responses(
+        (status = 200, description = "Settings updated", body = String),
+        (status = 400, description = "Invalid update mask or setting", body = String),
+        (status = 409, description = "Settings modified concurrently", body = String),
+        (status = 500, description = "Internal server error", body = String)
+
+    )
+)]
+pub async fn update_settings(
+    State(service): State<ClientService>, Extension(session): Extension<SessionModel>,
+    Json(request): Json<UpdateSettingsRequest>,
+) -> impl IntoResponse {
+    let patch = request.settings.unwrap_or_default();
+    let paths = request
+        .update_mask
+        .map(|mask| mask.paths)
+        .unwrap_or_default();
+
+    match UserModel::update_settings(
+        &service.db,
+        session.user_id.clone(),
+        &patch,
+        &paths,
+        request.version,
+    )
+    .await
+    {
+        Ok(user) => (
+            StatusCode::OK,
+            Json(serde_json::json!({ "version": user.settings_version })),
+        )
+            .into_response(),
+        Err(e) => settings_error(e),
+    }
+}
+
+/// Converts a settings update error to a response
+pub(crate) fn settings_error(error: ClientError) -> Response {
+    let status = match error {
+        ClientError::InvalidSettings(_) => StatusCode::BAD_REQUEST,
+        ClientError::StaleVersion => StatusCode::CONFLICT,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+
+    (
+        status,
+        Json(serde_json::json!({ "error": error.to_string(), "code": error.key() })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_api::{client::v1::Settings, google::protobuf::FieldMask};
+    use kiro_database::db_bridge::MockDatabaseOperations;
+
+    fn request(version: u64) -> Json<UpdateSettingsRequest> {
+        Json(UpdateSettingsRequest {
+            settings: Some(Settings {
+                theme: Some(1),
+                ..Default::default()
+            }),
+            update_mask: Some(FieldMask {
+                paths: vec!["theme".to_string()],
+            }),
+            version,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_update_settings_success() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let extension = Extension(SessionModel::default());
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(UserModel {
+                    settings_version: 3,
+                    ..Default::default()
+                }))
+            });
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(vec![UserModel {
+                    settings_version: 4,
+                    ..Default::default()
+                }])
+            });
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let response = update_settings(State(service), extension, request(3)).await;
+
+        let response = response.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+        assert_eq!(body["version"], 4);
+    }
+
+    #[tokio::test]
+    async fn test_update_settings_stale_version() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let extension = Extension(SessionModel::default());
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(UserModel {
+                    settings_version: 4,
+                    ..Default::default()
+                }))
+            });
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let response = update_settings(State(service), extension, request(3)).await;
+
+        let response = response.into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_update_settings_empty_mask() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let extension = Extension(SessionModel::default());
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let response = update_settings(
+            State(service),
+            extension,
+            Json(UpdateSettingsRequest::default()),
+        )
+        .await;
+
+        let response = response.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -17,10 +17,10 @@
 use super::*;
 
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
-use kiro_api::client::v1::UpdateThemeRequest;
-use kiro_database::db_bridge::DatabaseOperations;
+use kiro_api::client::v1::{Settings, UpdateThemeRequest};
 
-use crate::SessionModel;
+use super::update_settings::settings_error;
+use crate::{SessionModel, UserModel};
 
 /// User theme update route handler
 ///
@@ -92,25 +92,26 @@ pub async fn update_theme(
     State(service): State<ClientService>, Extension(session): Extension<SessionModel>,
     Json(request): Json<UpdateThemeRequest>,
 ) -> impl IntoResponse {
+    let patch = Settings {
+        theme: Some(request.theme),
+        ..Default::default()
+    };
+
     // Update the user's theme
-    match service
-        .db
-        .update_field(session.user_id.clone(), "theme", request.theme)
-        .await
-    {
+    match UserModel::update_setting(&service.db, session.user_id.clone(), &patch, "theme").await {
         Ok(_) => (StatusCode::OK, Json(serde_json::json!({}))).into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({ "error": e.to_string() })),
-        )
-            .into_response(),
+        Err(e) => settings_error(e),
     }
 }
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
+    use kiro_database::{
+        db_bridge::MockDatabaseOperations,
+        db_query::{DbValue, FieldUpdate},
+        DatabaseError,
+    };
     use mockall::predicate::eq;
 
     #[tokio::test]
@@ -121,10 +122,19 @@ mod tests {
         let user_id = test_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(eq(user_id), eq("theme"), eq(0)) // Assuming 0 represents light theme
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => settings["theme"] == "Light",
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -146,10 +156,19 @@ mod tests {
         let user_id = test_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(eq(user_id), eq("theme"), eq(1)) // Assuming 1 represents dark theme
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => settings["theme"] == "Dark",
+                _ => false,
+            })
+            .times(1)
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -171,10 +190,10 @@ mod tests {
         let user_id = test_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(eq(user_id), eq("theme"), eq(0)) // Assuming 0 represents light theme
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
             .times(1)
-            .returning(|_, _, _| Err(DatabaseError::Internal("Database error".to_string())));
+            .returning(|_| Err(DatabaseError::Internal("Database error".to_string())));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -204,10 +223,19 @@ mod tests {
         let user_id = test_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(eq(user_id), eq("theme"), eq(0)) // Assuming 0 represents light theme
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => settings["theme"] == "Light",
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -230,10 +258,19 @@ mod tests {
 
         // First update: light theme
         mock_db
-            .expect_update_field()
-            .with(eq(user_id.clone()), eq("theme"), eq(0))
+            .expect_select::<UserModel>()
+            .with(eq(user_id.clone()))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => settings["theme"] == "Light",
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -253,10 +290,19 @@ mod tests {
 
         // Second update: dark theme
         mock_db
-            .expect_update_field()
-            .with(eq(user_id.clone()), eq("theme"), eq(1))
+            .expect_select::<UserModel>()
+            .with(eq(user_id.clone()))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => settings["theme"] == "Dark",
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
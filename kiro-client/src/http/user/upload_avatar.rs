@@ -22,15 +22,20 @@ use axum::{
     response::IntoResponse,
     Extension, Json,
 };
-use kiro_database::db_bridge::DatabaseOperations;
-use kiro_storage::{BucketS3, ByteStream};
+use kiro_storage::BucketS3;
 
-use crate::SessionModel;
+use crate::{
+    error::ClientError,
+    utils::avatar::{store_avatar, AvatarError},
+    SessionModel,
+};
 
 /// Avatar upload route handler webhook
 ///
 /// # Description
-/// Uploads a new avatar image for the current user
+/// Uploads a new avatar image for the current user. The image must be a PNG,
+/// JPEG or WebP image, it is center cropped to a square and resized to each of
+/// the avatar sizes, without its metadata.
 ///
 /// # Arguments
 /// * `service` - The client service instance
@@ -39,11 +44,12 @@ use crate::SessionModel;
 ///
 /// # Returns
 /// * HTTP response with either:
-///   * `200 OK` with the URL of the uploaded avatar image
+///   * `200 OK` with the URL of the largest variant and the URLs of all variants by size
 ///   * Error status code with message
 ///
 /// # Errors
-/// * `400 BAD REQUEST` - No avatar file provided
+/// * `400 BAD REQUEST` - No avatar file provided, or not a valid image
+/// * `413 PAYLOAD TOO LARGE` - Avatar file too large
 /// * `500 INTERNAL SERVER ERROR` - S3 storage or database error
 ///
 /// # Example
@@ -86,7 +92,8 @@ use crate::SessionModel;
     ),
     responses(
         (status = 200, description = "Avatar uploaded", body = String),
-        (status = 400, description = "No avatar file provided", body = String),
+        (status = 400, description = "No avatar file provided or invalid image", body = String),
+        (status = 413, description = "Avatar file too large", body = String),
         (status = 500, description = "Internal server error", body = String)
 
     )
@@ -113,39 +120,36 @@ pub async fn upload_avatar(
         );
     }
 
-    // Upload file to S3
-    let avatar_stream = ByteStream::from(file_data);
-    let avatar_link = match BucketS3::new()
-        .await
-        .put_object(
-            avatar_stream,
-            session.user_id.clone(),
-            "images",
-            &format!("image-{}", session.user_id.clone()),
-        )
-        .await
-    {
-        Ok(link) => link,
-        Err(e) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({ "error": e.to_string() })),
-            )
-        }
-    };
-
-    match service
-        .db
-        .update_field(session.user_id.clone(), "/avatar", avatar_link.clone())
-        .await
+    // Validate the image, then upload its variants
+    match store_avatar(
+        &service.db,
+        &BucketS3::new().await,
+        session.user_id.clone(),
+        file_data,
+    )
+    .await
     {
-        Ok(_) => (
+        Ok(variants) => (
             StatusCode::OK,
-            Json(serde_json::json!({ "url": avatar_link })),
+            Json(serde_json::json!({
+                "url": variants.last().map(|variant| variant.url.clone()),
+                "urls": variants
+                    .iter()
+                    .map(|variant| (variant.size.to_string(), variant.url.clone()))
+                    .collect::<serde_json::Map<_, _>>(),
+            })),
+        ),
+        Err(e @ ClientError::InvalidAvatar(AvatarError::TooLarge)) => (
+            StatusCode::PAYLOAD_TOO_LARGE,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        ),
+        Err(e @ ClientError::InvalidAvatar(_)) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
         ),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({ "error": e.to_string() })),
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
         ),
     }
 }
@@ -0,0 +1,258 @@
+// http/user/upload_file.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::{
+    extract::{Multipart, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use futures::StreamExt;
+use kiro_api::client::v1::{FileMetadata, UploadPurpose};
+use kiro_storage::BucketS3;
+
+use crate::{
+    error::ClientError,
+    utils::upload::{receive_avatar, receive_file, UploadError},
+    SessionModel,
+};
+
+/// Chunked file upload route handler
+///
+/// # Description
+/// Uploads a file for the current user, streamed as it is received. The
+/// multipart form carries the metadata fields first: `name`, `content_type`,
+/// `size`, `checksum` (hex encoded SHA-256) and optionally `purpose` (`file` or
+/// `avatar`), then the `file` field. The upload is stopped as soon as more
+/// bytes than declared are received, and is rejected if the checksum does not
+/// match.
+///
+/// # Arguments
+/// * `service` - The client service instance
+/// * `session` - The current session model
+/// * `multipart` - The multipart form data containing the metadata and the file
+///
+/// # Returns
+/// * HTTP response with either:
+///   * `200 OK` with the URL of the file, or of the largest avatar variant along with the URLs of all variants by size
+///   * Error status code with message
+///
+/// # Errors
+/// * `400 BAD REQUEST` - Missing or invalid metadata, or content not matching it
+/// * `413 PAYLOAD TOO LARGE` - File too large
+/// * `500 INTERNAL SERVER ERROR` - S3 storage or database error
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{Extension, extract::State, Json};
+/// use http::HeaderMap;
+/// use kiro_client::{ClientService, upload_file::upload_file, SessionModel};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Empty headers
+/// let headers = HeaderMap::new();
+///
+/// // Mock session
+/// let session = SessionModel::default();
+///
+/// // Mock multipart form data
+/// let multipart = Multipart::new(headers, "boundary".to_string(), Vec::new());
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     let response = upload_file(State(service), Extension(session), multipart).await;
+///
+///     println!("File uploaded");
+/// });
+/// ```
+#[utoipa::path(
+    post,
+    path = "/user/upload_file",
+    tag = "user",
+    responses(
+        (status = 200, description = "File uploaded", body = String),
+        (status = 400, description = "Invalid metadata or content", body = String),
+        (status = 413, description = "File too large", body = String),
+        (status = 500, description = "Internal server error", body = String)
+
+    )
+)]
+pub async fn upload_file(
+    State(service): State<ClientService>, Extension(session): Extension<SessionModel>,
+    mut multipart: Multipart,
+) -> impl IntoResponse {
+    let mut metadata = FileMetadata::default();
+    let mut result = Err(ClientError::from(UploadError::MissingMetadata));
+
+    while let Ok(Some(field)) = multipart.next_field().await {
+        let name = field.name().unwrap_or("").to_string();
+
+        // The metadata is read before the file, which is streamed
+        if name == "file" {
+            let chunks = field
+                .map(|chunk| {
+                    chunk
+                        .map(|bytes| bytes.to_vec())
+                        .map_err(|_| ClientError::from(UploadError::Interrupted))
+                })
+                .boxed();
+
+            let bucket = BucketS3::new().await;
+            let user_id = session.user_id.clone();
+
+            result = match metadata.purpose() {
+                UploadPurpose::Avatar => {
+                    receive_avatar(&service.db, &bucket, user_id, metadata, chunks)
+                        .await
+                        .map(|variants| {
+                            serde_json::json!({
+                                "url": variants.last().map(|variant| variant.url.clone()),
+                                "urls": variants
+                                    .iter()
+                                    .map(|variant| (variant.size.to_string(), variant.url.clone()))
+                                    .collect::<serde_json::Map<_, _>>(),
+                            })
+                        })
+                }
+                UploadPurpose::File => {
+                    receive_file(&service.db, &bucket, user_id, metadata, chunks)
+                        .await
+                        .map(|file| serde_json::json!({ "url": file.url }))
+                }
+            };
+            break;
+        }
+
+        let Ok(value) = field.text().await else {
+            result = Err(UploadError::Interrupted.into());
+            break;
+        };
+
+        match name.as_str() {
+            "name" => metadata.name = value,
+            "content_type" => metadata.content_type = value,
+            "size" => metadata.size = value.parse().unwrap_or_default(),
+            "checksum" => metadata.checksum = value,
+            "purpose" if value == "avatar" => metadata.set_purpose(UploadPurpose::Avatar),
+            _ => {}
+        }
+    }
+
+    match result {
+        Ok(body) => (StatusCode::OK, Json(body)),
+        Err(
+            e @ ClientError::InvalidUpload(UploadError::TooLarge(_) | UploadError::SizeExceeded(_)),
+        ) => (
+            StatusCode::PAYLOAD_TOO_LARGE,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        ),
+        Err(e @ (ClientError::InvalidUpload(_) | ClientError::InvalidAvatar(_))) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": e.to_string(), "code": e.key() })),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use axum::{body::Body, extract::FromRequest, http::Request};
+    use kiro_database::db_bridge::MockDatabaseOperations;
+
+    async fn multipart(body: &'static str) -> Multipart {
+        let request = Request::builder()
+            .header("content-type", "multipart/form-data; boundary=X")
+            .body(Body::from(body.replace('\n', "\r\n")))
+            .unwrap();
+
+        Multipart::from_request(request, &()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_upload_file_missing_metadata() {
+        let service = ClientService {
+            db: Database::Mock(MockDatabaseOperations::new()),
+        };
+
+        let form = multipart(
+            "--X
+Content-Disposition: form-data; name=\"file\"; filename=\"hello.txt\"
+
+hello world
+--X--
+",
+        )
+        .await;
+
+        let response = upload_file(State(service), Extension(SessionModel::default()), form)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_upload_file_too_large() {
+        let service = ClientService {
+            db: Database::Mock(MockDatabaseOperations::new()),
+        };
+
+        let form = multipart(
+            "--X
+Content-Disposition: form-data; name=\"name\"
+
+hello.txt
+--X
+Content-Disposition: form-data; name=\"size\"
+
+5242881
+--X
+Content-Disposition: form-data; name=\"checksum\"
+
+b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9
+--X
+Content-Disposition: form-data; name=\"purpose\"
+
+avatar
+--X
+Content-Disposition: form-data; name=\"file\"; filename=\"hello.txt\"
+
+hello world
+--X--
+",
+        )
+        .await;
+
+        let response = upload_file(State(service), Extension(SessionModel::default()), form)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
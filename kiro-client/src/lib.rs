@@ -14,6 +14,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod backends;
+mod captcha;
 mod error;
 mod http;
 mod models;
@@ -29,10 +31,64 @@ pub use models::{CreateSessionModel, SessionModel};
 ///
 /// The user module provides models for users.
 pub use models::{
-    CreateUserModel, Language, NotificationSettings, PrivacySettings, SecuritySettings, Theme,
-    UserModel, UserSettings,
+    AvatarVariant, ChannelSettings, CreateUserModel, Language, NotificationCategory,
+    NotificationChannel, NotificationSettings, PrivacySettings, QuietHoursSettings,
+    SecuritySettings, Theme, UserModel, UserSettings,
 };
 
+/// # Group Models
+///
+/// The group module provides models for groups of users.
+pub use models::{CreateGroupModel, GroupModel};
+
+/// # Account Event Models
+///
+/// The account event module provides models for the audit log of account state changes.
+pub use models::{AccountEventKind, AccountEventModel, CreateAccountEventModel};
+
+/// # Change Models
+///
+/// The change module provides models for the change log of the delta sync.
+pub use models::{ChangeKind, ChangeModel, ChangeVersionsModel};
+
+/// # Policy Models
+///
+/// The policy module provides models for the policy documents and their acceptance by users.
+pub use models::{CreatePolicyConsentModel, PolicyConsentModel, PolicyDocumentModel, PolicyKind};
+
+/// # Invitation Models
+///
+/// The invitation module provides models for the invitation codes of the registration.
+pub use models::{CreateInvitationModel, InvitationModel};
+
+/// # Login Failure Models
+///
+/// The login failure module provides models for the failed logins counted by the CAPTCHA policy.
+pub use models::{CreateLoginFailureModel, LoginFailureCounts, LoginFailureModel};
+
+/// # User Email Models
+///
+/// The user email module provides models for the secondary email addresses of users.
+pub use models::{CreateUserEmailModel, UserEmailModel};
+
+/// # File Models
+///
+/// The file module provides models for the files uploaded by users.
+#[cfg(feature = "storage")]
+pub use models::{CreateFileModel, FileModel};
+
+/// # Export Models
+///
+/// The export module provides models for the data exports of users.
+#[cfg(feature = "export")]
+pub use models::{CreateExportModel, ExportModel, ExportStatus};
+
+/// # User Export Models
+///
+/// The user export module provides models for the audit log of bulk user exports.
+#[cfg(feature = "import")]
+pub use models::{CreateUserExportModel, UserExportModel};
+
 /// # Auth Services
 ///
 /// The auth module provides services for authentication.
@@ -46,21 +102,160 @@ pub use services::ClientService;
 /// # Auth HTTP1 Routes
 ///
 /// The auth module provides HTTP1 routes for the authentication service.
-pub use http::{auth_routes, login, logout, register};
+pub use http::{auth_routes, login, logout, register, restore_account};
+
+#[cfg(feature = "mailer")]
+pub use http::{
+    cancel_email_change, confirm_email_change, reactivate_account, request_reactivation,
+    verify_email,
+};
 
 /// # User HTTP1 Routes
 ///
 /// The user module provides HTTP1 routes for the user service.
 pub use http::{
-    delete_user, disable_user, read_user, update_email, update_language, update_notifications,
-    update_password, update_privacy, update_security, update_theme, user_routes,
+    accept_policy, create_invitation, delete_user, disable_user, get_changes_since, list_consents,
+    list_emails, reactivate_user, read_user, remove_email, set_primary_email, update_email,
+    update_language, update_notifications, update_password, update_privacy, update_profile,
+    update_security, update_settings, update_theme, user_routes,
 };
 
 #[cfg(feature = "mailer")]
-pub use http::{send_email_to_change_email, send_email_to_change_password};
+pub use http::{add_email, send_email_to_change_email, send_email_to_change_password};
 
 #[cfg(feature = "storage")]
-pub use http::upload_avatar;
+pub use http::{upload_avatar, upload_file};
+
+#[cfg(feature = "export")]
+pub use http::request_data_export;
+
+/// # OAuth Models
+///
+/// The oauth module provides models for the OAuth2 / OpenID Connect identity provider.
+#[cfg(feature = "oauth")]
+pub use models::{
+    AuthorizationCodeModel, ConsentModel, CreateAuthorizationCodeModel, CreateConsentModel,
+    CreateOAuthClientModel, GrantType, OAuthClientModel,
+};
+
+/// # OAuth HTTP1 Routes
+///
+/// The oauth module provides the OAuth2 / OpenID Connect identity provider routes.
+#[cfg(feature = "oauth")]
+pub use http::{
+    authorize, consent, discovery, jwks, oauth_routes, register_client, token, userinfo,
+};
+
+/// # OAuth Errors
+///
+/// The oauth error is returned by the identity provider routes.
+#[cfg(feature = "oauth")]
+pub use error::OAuthError;
+
+/// # OAuth Signing Keys
+///
+/// The signing keys are loaded at startup, a misconfigured key aborts the server.
+#[cfg(feature = "oauth")]
+pub use utils::jwt::init_signing_keys;
+
+/// # SCIM HTTP1 Routes
+///
+/// The scim module provides the SCIM 2.0 provisioning routes and resources.
+#[cfg(feature = "scim")]
+pub use http::{scim, scim_routes};
+
+/// # SCIM Errors
+///
+/// The scim error is returned by the provisioning routes.
+#[cfg(feature = "scim")]
+pub use error::ScimError;
+
+/// # Authentication Backends
+///
+/// The backends module provides the pluggable credential checks used by `login`.
+pub use backends::{authenticate, authenticate_with, password::PasswordBackend, AuthBackend};
+
+/// # LDAP Backend
+///
+/// The ldap module authenticates users against an LDAP / Active Directory server.
+#[cfg(feature = "ldap")]
+pub use backends::ldap::{
+    Directory, DirectoryEntry, LdapBackend, LdapConfig, LdapDirectory, RoleMapping,
+};
+
+/// # Auth Backend Errors
+///
+/// The auth backend error is returned by the authentication backends.
+pub use error::AuthBackendError;
+
+/// # CAPTCHA Verification
+///
+/// The captcha module verifies the CAPTCHA tokens required by `register` and `login`.
+pub use captcha::{
+    captcha_policy, get_captcha_token_from_headers, get_captcha_token_from_md, CaptchaError,
+    CaptchaPolicy, CaptchaVerifier, CAPTCHA_HEADER,
+};
+
+/// # Site Verifier
+///
+/// The siteverify module verifies the tokens of hCaptcha, Turnstile and reCAPTCHA.
+#[cfg(feature = "captcha")]
+pub use captcha::siteverify::{CaptchaProvider, SiteVerifier};
+
+/// # Cookie Utilities
+///
+/// The cookie module provides helpers for cookie-based browser sessions.
+pub use utils::cookie::{
+    clear_cookie_headers, get_cookie, session_cookie_headers, verify_csrf, CSRF_COOKIE,
+    CSRF_HEADER, SESSION_COOKIE,
+};
+
+/// # Privacy Policy
+///
+/// The privacy policy enforces the privacy settings of users on sessions, traces and logs.
+pub use utils::privacy::PrivacyPolicy;
+
+/// # Consent
+///
+/// The consent module checks that users accepted the current policy documents.
+pub use utils::consent::{check_consent, PolicyCache};
+
+/// # Registration Policy
+///
+/// The registration policy decides who may register, from the mode and the domain lists.
+pub use utils::registration::{RegistrationError, RegistrationMode, RegistrationPolicy};
+
+/// # Localization
+///
+/// The i18n module localizes error messages in the language of the user or of the request.
+pub use utils::i18n::{
+    keyed_status, localize_message, localize_status, negotiate_language, resolve_language,
+};
+
+/// # Account Purge Job
+///
+/// The purge job deletes the accounts whose deletion grace period is over.
+pub use utils::purge::run_purge_job;
+
+/// # Change Log Compaction Job
+///
+/// The compaction job removes the superseded and expired changes of the delta sync.
+pub use utils::sync::run_change_compaction_job;
+
+/// # Data Retention Job
+///
+/// The retention job deletes the expired links and sessions, and hashes the old IP addresses.
+pub use utils::retention::run_retention_job;
+
+/// # Bulk User Import
+///
+/// The user import module imports and exports users as CSV or JSON Lines, the export
+/// including the password hashes on request.
+#[cfg(feature = "import")]
+pub use utils::user_import::{
+    import_batch_size, ImportError, ImportReport, ImportRowReport, TransferFormat, UserExporter,
+    UserImporter, UserRecord,
+};
 
 /// # Auth Server Builder
 ///
@@ -0,0 +1,195 @@
+// models/account_event_model.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use kiro_database::{
+    db_bridge::{DatabaseOperations, HasId, OrderDirection, QueryOptions},
+    DbDateTime, DbId,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::error::ClientError;
+
+/// # Account Event Kind
+///
+/// The account event kind represents a change of state of a user account.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum AccountEventKind {
+    Disabled,
+    Reactivated,
+}
+
+/// # Account Event Model
+///
+/// The account event model records a change of state of a user account, along
+/// with the user who made it. The actor is empty when the change comes from a
+/// provisioning system.
+///
+/// ## Model
+///
+/// ```rust,no_run
+/// use kiro_client::{AccountEventKind, AccountEventModel};
+/// use kiro_database::{DbDateTime, DbId};
+///
+/// let event = AccountEventModel {
+///     id: DbId::from(("account_events", "event")),
+///     user: DbId::from(("users", "user")),
+///     kind: AccountEventKind::Disabled,
+///     actor: Some(DbId::from(("users", "user"))),
+///     created_at: DbDateTime::now(),
+/// };
+///
+/// println!("📝 Account event: {:?}", event);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountEventModel {
+    pub id: DbId,
+    pub user: DbId,
+    pub kind: AccountEventKind,
+    pub actor: Option<DbId>,
+    pub created_at: DbDateTime,
+}
+
+impl HasId for AccountEventModel {
+    type Id = DbId;
+    fn id(&self) -> &Self::Id {
+        &self.id
+    }
+}
+
+// WARNING: This is a default implementation for testing purposes only
+impl Default for AccountEventModel {
+    fn default() -> Self {
+        Self {
+            id: DbId::from(("account_events", "event")),
+            user: DbId::from(("users", "user")),
+            kind: AccountEventKind::Disabled,
+            actor: None,
+            created_at: DbDateTime::now(),
+        }
+    }
+}
+
+/// # Create Account Event Model
+///
+/// The create account event model is used to record a new account event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateAccountEventModel {
+    pub user: DbId,
+    pub kind: AccountEventKind,
+    pub actor: Option<DbId>,
+}
+
+impl AccountEventModel {
+    /// Record event
+    ///
+    /// Records a change of state of a user account
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user_id` - User identifier
+    /// * `kind` - Kind of change
+    /// * `actor` - User who made the change, if any
+    ///
+    /// # Returns
+    /// * `Ok(AccountEventModel)` - Recorded event
+    /// * `Err(ClientError)` - Database error
+    pub async fn record<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user_id: DbId, kind: AccountEventKind, actor: Option<DbId>,
+    ) -> Result<Self, ClientError> {
+        db.create::<CreateAccountEventModel, Self>(
+            "account_events",
+            CreateAccountEventModel {
+                user: user_id,
+                kind,
+                actor,
+            },
+        )
+        .await
+        .map_err(ClientError::Database)?
+        .pop()
+        .ok_or(ClientError::DBOptionNone)
+    }
+
+    /// Get events by user
+    ///
+    /// Retrieves the account events of a user, most recent first
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user_id` - User identifier
+    ///
+    /// # Returns
+    /// * `Ok(Vec<AccountEventModel>)` - Account events of the user
+    /// * `Err(ClientError)` - Database error
+    pub async fn get_events_by_user<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user_id: DbId,
+    ) -> Result<Vec<Self>, ClientError> {
+        db.read_by_field_thing::<Self>(
+            "account_events",
+            "user",
+            user_id,
+            Some(QueryOptions {
+                order_by: Some("created_at".to_string()),
+                order_direction: Some(OrderDirection::DESC),
+                limit: None,
+                start: None,
+            }),
+        )
+        .await
+        .map_err(ClientError::Database)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kiro_database::db_bridge::MockDatabaseOperations;
+
+    #[tokio::test]
+    async fn test_record_event() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let user_id = DbId::from(("users", "user"));
+
+        mock_db
+            .expect_create::<CreateAccountEventModel, AccountEventModel>()
+            .withf(|table, data| {
+                table == "account_events"
+                    && data.kind == AccountEventKind::Reactivated
+                    && data.actor.is_none()
+            })
+            .times(1)
+            .returning(|_, data| {
+                Ok(vec![AccountEventModel {
+                    user: data.user,
+                    kind: data.kind,
+                    actor: data.actor,
+                    ..Default::default()
+                }])
+            });
+
+        let event = AccountEventModel::record(
+            &mock_db,
+            user_id.clone(),
+            AccountEventKind::Reactivated,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(event.user, user_id);
+        assert_eq!(event.kind, AccountEventKind::Reactivated);
+    }
+}
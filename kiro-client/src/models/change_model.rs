@@ -0,0 +1,479 @@
+// models/change_model.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use kiro_database::{
+    db_bridge::{DatabaseOperations, HasId, OrderDirection, QueryOptions},
+    db_query::{Condition, DbValue, FieldUpdate, Update},
+    DbDateTime, DbId,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{error::ClientError, UserModel};
+
+/// # Change Kind
+///
+/// The change kind is the part of the user state a change applies to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+    User,
+    Settings,
+    Group,
+}
+
+/// # Change Model
+///
+/// The change model is an entry of the change log used by the delta sync. It is
+/// written by the `change_log` events of the `users` and `groups` tables on
+/// every mutation, with a version incremented in the same transaction. Deleted
+/// records leave a tombstone.
+///
+/// ## Model
+///
+/// ```rust,no_run
+/// use kiro_client::{ChangeKind, ChangeModel};
+/// use kiro_database::{DbDateTime, DbId};
+///
+/// let change = ChangeModel {
+///     id: DbId::from(("changes", "change")),
+///     version: 42,
+///     kind: ChangeKind::Settings,
+///     record: DbId::from(("users", "user")),
+///     user: Some(DbId::from(("users", "user"))),
+///     deleted: false,
+///     created_at: DbDateTime::now(),
+/// };
+///
+/// println!("🔄 Change: {:?}", change);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeModel {
+    pub id: DbId,
+    pub version: u64,
+    pub kind: ChangeKind,
+    pub record: DbId,
+    pub user: Option<DbId>,
+    pub deleted: bool,
+    pub created_at: DbDateTime,
+}
+
+impl HasId for ChangeModel {
+    type Id = DbId;
+    fn id(&self) -> &Self::Id {
+        &self.id
+    }
+}
+
+// WARNING: This is a default implementation for testing purposes only
+impl Default for ChangeModel {
+    fn default() -> Self {
+        Self {
+            id: DbId::from(("changes", "change")),
+            version: 1,
+            kind: ChangeKind::User,
+            record: DbId::default(),
+            user: Some(DbId::default()),
+            deleted: false,
+            created_at: DbDateTime::now(),
+        }
+    }
+}
+
+/// Number of changes read per page by the compaction
+const COMPACTION_PAGE_SIZE: usize = 500;
+
+/// # Change Versions Model
+///
+/// The change versions model holds the last version written to the change log,
+/// and the last version removed from it by the compaction. A cursor older than
+/// the compacted version can no longer be synced incrementally.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChangeVersionsModel {
+    pub version: u64,
+    pub compacted: u64,
+}
+
+impl ChangeModel {
+    /// Get versions
+    ///
+    /// Retrieves the last written and compacted versions of the change log
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    ///
+    /// # Returns
+    /// * `Ok(ChangeVersionsModel)` - Versions, zero while the log is empty
+    /// * `Err(ClientError)` - Database error
+    pub async fn get_versions<DB: DatabaseOperations + Send + Sync>(
+        db: &DB,
+    ) -> Result<ChangeVersionsModel, ClientError> {
+        Ok(db
+            .select::<ChangeVersionsModel>(DbId::from(("change_versions", "current")))
+            .await
+            .map_err(ClientError::Database)?
+            .unwrap_or_default())
+    }
+
+    /// Get changes since
+    ///
+    /// Retrieves the changes visible to a user after a version, oldest first:
+    /// the changes of the user record and of the groups the user belongs to
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user_id` - User identifier
+    /// * `cursor` - Last version known by the client
+    /// * `limit` - Maximum number of changes
+    ///
+    /// # Returns
+    /// * `Ok(Vec<ChangeModel>)` - Changes after the cursor
+    /// * `Err(ClientError)` - Database error
+    pub async fn get_changes_since<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user_id: DbId, cursor: u64, limit: usize,
+    ) -> Result<Vec<Self>, ClientError> {
+        let groups = db
+            .select::<UserModel>(user_id.clone())
+            .await
+            .map_err(ClientError::Database)?
+            .map(|user| user.groups)
+            .unwrap_or_default();
+
+        db.read_where::<Self>(
+            "changes",
+            Condition::And(vec![
+                Condition::gt("version", cursor),
+                Condition::Or(vec![
+                    Condition::eq("user", user_id),
+                    Condition::inside("record", groups),
+                ]),
+            ]),
+            Some(QueryOptions {
+                order_by: Some("version".to_string()),
+                order_direction: Some(OrderDirection::ASC),
+                limit: Some(limit),
+                start: None,
+            }),
+        )
+        .await
+        .map_err(ClientError::Database)
+    }
+
+    /// Compact superseded
+    ///
+    /// Removes the changes followed by a newer change of the same record and
+    /// kind. The delta sync returns the current state of the changed records, so
+    /// these changes carry nothing a client still needs. The log is read page by
+    /// page, newest first, to find the latest change of every record.
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of removed changes
+    /// * `Err(ClientError)` - Database error
+    pub async fn compact_superseded<DB: DatabaseOperations + Send + Sync>(
+        db: &DB,
+    ) -> Result<usize, ClientError> {
+        // Newest first, the first change read of a record and kind is its latest
+        let mut latest: HashMap<(DbId, ChangeKind), (u64, bool)> = HashMap::new();
+        let mut start = 0;
+
+        loop {
+            let page = db
+                .read_where::<Self>(
+                    "changes",
+                    Condition::is_set("version"),
+                    Some(QueryOptions {
+                        order_by: Some("version".to_string()),
+                        order_direction: Some(OrderDirection::DESC),
+                        limit: Some(COMPACTION_PAGE_SIZE),
+                        start: Some(start),
+                    }),
+                )
+                .await
+                .map_err(ClientError::Database)?;
+
+            let count = page.len();
+            for change in page {
+                latest
+                    .entry((change.record, change.kind))
+                    .and_modify(|(_, superseded)| *superseded = true)
+                    .or_insert((change.version, false));
+            }
+
+            if count < COMPACTION_PAGE_SIZE {
+                break;
+            }
+            start += count;
+        }
+
+        let mut removed = 0;
+
+        for ((record, kind), (version, _)) in latest
+            .into_iter()
+            .filter(|(_, (_, superseded))| *superseded)
+        {
+            let condition = Condition::And(vec![
+                Condition::eq("record", record),
+                Condition::eq("kind", DbValue::json(&kind).map_err(ClientError::Database)?),
+                Condition::lt("version", version),
+            ]);
+
+            removed += db
+                .delete_where("changes", condition)
+                .await
+                .map_err(ClientError::Database)?;
+        }
+
+        Ok(removed)
+    }
+
+    /// Compact expired
+    ///
+    /// Removes the changes written before a date. The compacted version is
+    /// raised first, so clients behind it are told to sync from scratch instead
+    /// of missing the removed changes.
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `before` - Date before which changes are removed
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of removed changes
+    /// * `Err(ClientError)` - Database error
+    pub async fn compact_expired<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, before: DateTime<Utc>,
+    ) -> Result<usize, ClientError> {
+        let Some(version) = db
+            .read_where::<Self>(
+                "changes",
+                Condition::lt("created_at", before),
+                Some(QueryOptions {
+                    order_by: Some("version".to_string()),
+                    order_direction: Some(OrderDirection::DESC),
+                    limit: Some(1),
+                    start: None,
+                }),
+            )
+            .await
+            .map_err(ClientError::Database)?
+            .pop()
+            .map(|change| change.version)
+        else {
+            return Ok(0);
+        };
+
+        db.update_where::<ChangeVersionsModel>(
+            Update::record(
+                DbId::from(("change_versions", "current")),
+                vec![FieldUpdate::set("compacted", version)],
+            )
+            .when(Condition::lt("compacted", version)),
+        )
+        .await
+        .map_err(ClientError::Database)?;
+
+        db.delete_where("changes", Condition::le("version", version))
+            .await
+            .map_err(ClientError::Database)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
+
+    #[tokio::test]
+    async fn test_get_versions_empty_log() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<ChangeVersionsModel>()
+            .withf(|id| *id == DbId::from(("change_versions", "current")))
+            .times(1)
+            .returning(|_| Ok(None));
+
+        let versions = ChangeModel::get_versions(&mock_db).await.unwrap();
+
+        assert_eq!(versions.version, 0);
+        assert_eq!(versions.compacted, 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_changes_since() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(UserModel {
+                    groups: vec![DbId::from(("groups", "group"))],
+                    ..Default::default()
+                }))
+            });
+
+        mock_db
+            .expect_read_where::<ChangeModel>()
+            .withf(|table, condition, options| {
+                table == "changes"
+                    && *condition
+                        == Condition::And(vec![
+                            Condition::gt("version", 7_u64),
+                            Condition::Or(vec![
+                                Condition::eq("user", DbId::default()),
+                                Condition::inside("record", vec![DbId::from(("groups", "group"))]),
+                            ]),
+                        ])
+                    && options.as_ref().unwrap().limit == Some(100)
+            })
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(vec![ChangeModel {
+                    version: 8,
+                    ..Default::default()
+                }])
+            });
+
+        let changes = ChangeModel::get_changes_since(&mock_db, DbId::default(), 7, 100)
+            .await
+            .unwrap();
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].version, 8);
+    }
+
+    #[tokio::test]
+    async fn test_compact_superseded() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<ChangeModel>()
+            .withf(|table, _, options| {
+                table == "changes" && options.as_ref().and_then(|options| options.start) == Some(0)
+            })
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(vec![
+                    ChangeModel {
+                        version: 9,
+                        ..Default::default()
+                    },
+                    ChangeModel {
+                        version: 8,
+                        kind: ChangeKind::Settings,
+                        ..Default::default()
+                    },
+                    ChangeModel {
+                        version: 5,
+                        ..Default::default()
+                    },
+                    ChangeModel {
+                        version: 2,
+                        ..Default::default()
+                    },
+                ])
+            });
+
+        // Only the user changes older than the latest one are removed
+        mock_db
+            .expect_delete_where()
+            .withf(|table, condition| {
+                table == "changes"
+                    && *condition
+                        == Condition::And(vec![
+                            Condition::eq("record", DbId::default()),
+                            Condition::eq("kind", DbValue::json(&ChangeKind::User).unwrap()),
+                            Condition::lt("version", 9u64),
+                        ])
+            })
+            .times(1)
+            .returning(|_, _| Ok(2));
+
+        assert_eq!(ChangeModel::compact_superseded(&mock_db).await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_compact_expired_raises_compacted_version() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<ChangeModel>()
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(vec![ChangeModel {
+                    version: 20,
+                    ..Default::default()
+                }])
+            });
+
+        mock_db
+            .expect_update_where::<ChangeVersionsModel>()
+            .withf(|update| {
+                update.table == "change_versions"
+                    && update.changes == vec![FieldUpdate::set("compacted", 20_u64)]
+            })
+            .times(1)
+            .returning(|_| Ok(vec![]));
+
+        mock_db
+            .expect_delete_where()
+            .withf(|table, condition| {
+                table == "changes" && *condition == Condition::le("version", 20_u64)
+            })
+            .times(1)
+            .returning(|_, _| Ok(1));
+
+        let removed = ChangeModel::compact_expired(&mock_db, Utc::now())
+            .await
+            .unwrap();
+
+        assert_eq!(removed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_compact_expired_nothing_to_remove() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<ChangeModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
+
+        let removed = ChangeModel::compact_expired(&mock_db, Utc::now())
+            .await
+            .unwrap();
+
+        assert_eq!(removed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_compact_expired_db_error() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<ChangeModel>()
+            .times(1)
+            .returning(|_, _, _| Err(DatabaseError::Internal("Database error".to_string())));
+
+        assert!(ChangeModel::compact_expired(&mock_db, Utc::now())
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,259 @@
+// models/export_model.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{DateTime, Duration, Utc};
+use kiro_database::{
+    db_bridge::{DatabaseOperations, HasId, OrderDirection, QueryOptions},
+    get_env_or, DbDateTime, DbId,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::error::ClientError;
+
+/// # Export Status
+///
+/// The export status represents the progress of a data export job.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ExportStatus {
+    Pending,
+    Completed,
+    Failed,
+}
+
+/// # Export Model
+///
+/// The export model records a data export requested by a user, it is used to
+/// throttle the requests.
+///
+/// ## Model
+///
+/// ```rust,no_run
+/// use kiro_client::{ExportModel, ExportStatus};
+/// use kiro_database::{DbDateTime, DbId};
+///
+/// let export = ExportModel {
+///     id: DbId::from(("exports", "export")),
+///     user: DbId::from(("users", "user")),
+///     status: ExportStatus::Pending,
+///     created_at: DbDateTime::now(),
+///     completed_at: None,
+/// };
+///
+/// println!("📦 Export: {:?}", export);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportModel {
+    pub id: DbId,
+    pub user: DbId,
+    pub status: ExportStatus,
+    pub created_at: DbDateTime,
+    pub completed_at: Option<DbDateTime>,
+}
+
+impl HasId for ExportModel {
+    type Id = DbId;
+    fn id(&self) -> &Self::Id {
+        &self.id
+    }
+}
+
+// WARNING: This is a default implementation for testing purposes only
+impl Default for ExportModel {
+    fn default() -> Self {
+        Self {
+            id: DbId::from(("exports", "export")),
+            user: DbId::from(("users", "user")),
+            status: ExportStatus::Pending,
+            created_at: DbDateTime::now(),
+            completed_at: None,
+        }
+    }
+}
+
+/// # Create Export Model
+///
+/// The create export model is used to record a new data export.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateExportModel {
+    pub user: DbId,
+    pub status: ExportStatus,
+}
+
+impl ExportModel {
+    /// Export interval
+    ///
+    /// Minimum delay between two exports of a user, configured in hours by
+    /// `DATA_EXPORT_INTERVAL_HOURS` (24 hours by default)
+    pub fn interval() -> Duration {
+        Duration::hours(
+            get_env_or("DATA_EXPORT_INTERVAL_HOURS", "24")
+                .parse()
+                .unwrap_or(24),
+        )
+    }
+
+    /// Get last export
+    ///
+    /// Retrieves the most recent export requested by a user
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user_id` - User identifier
+    ///
+    /// # Returns
+    /// * `Ok(Some(ExportModel))` - Most recent export
+    /// * `Ok(None)` - The user never requested an export
+    /// * `Err(ClientError)` - Database error
+    pub async fn get_last_export<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user_id: DbId,
+    ) -> Result<Option<Self>, ClientError> {
+        let exports = db
+            .read_by_field_thing::<Self>(
+                "exports",
+                "user",
+                user_id,
+                Some(QueryOptions {
+                    order_by: Some("created_at".to_string()),
+                    order_direction: Some(OrderDirection::DESC),
+                    limit: Some(1),
+                    start: None,
+                }),
+            )
+            .await
+            .map_err(ClientError::Database)?;
+
+        Ok(exports.into_iter().next())
+    }
+
+    /// Request export
+    ///
+    /// Records a new export for a user, unless the user already requested one
+    /// during the export interval
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user_id` - User identifier
+    ///
+    /// # Returns
+    /// * `Ok(ExportModel)` - Pending export
+    /// * `Err(ClientError::ExportThrottled)` - An export was requested recently
+    /// * `Err(ClientError)` - Database error
+    pub async fn request<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user_id: DbId,
+    ) -> Result<Self, ClientError> {
+        if let Some(last) = Self::get_last_export(db, user_id.clone()).await? {
+            let requested_at = DateTime::<Utc>::from(last.created_at);
+
+            if Utc::now() < requested_at + Self::interval() {
+                return Err(ClientError::ExportThrottled);
+            }
+        }
+
+        db.create::<CreateExportModel, Self>(
+            "exports",
+            CreateExportModel {
+                user: user_id,
+                status: ExportStatus::Pending,
+            },
+        )
+        .await
+        .map_err(ClientError::Database)?
+        .pop()
+        .ok_or(ClientError::DBOptionNone)
+    }
+
+    /// Set status
+    ///
+    /// Records the outcome of the export job
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `status` - New status of the export
+    pub async fn set_status<DB: DatabaseOperations + Send + Sync>(
+        &self, db: &DB, status: ExportStatus,
+    ) -> Result<(), ClientError> {
+        db.update_field(self.id.clone(), "status", status)
+            .await
+            .map_err(ClientError::Database)?;
+
+        db.update_field(self.id.clone(), "completed_at", Some(DbDateTime::now()))
+            .await
+            .map_err(ClientError::Database)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kiro_database::db_bridge::MockDatabaseOperations;
+
+    #[tokio::test]
+    async fn test_request_export() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let user_id = DbId::from(("users", "user"));
+
+        mock_db
+            .expect_read_by_field_thing::<ExportModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        mock_db
+            .expect_create::<CreateExportModel, ExportModel>()
+            .times(1)
+            .returning(|_, _| Ok(vec![ExportModel::default()]));
+
+        let export = ExportModel::request(&mock_db, user_id).await.unwrap();
+        assert_eq!(export.status, ExportStatus::Pending);
+    }
+
+    #[tokio::test]
+    async fn test_request_export_throttled() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let user_id = DbId::from(("users", "user"));
+
+        mock_db
+            .expect_read_by_field_thing::<ExportModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![ExportModel::default()]));
+
+        let result = ExportModel::request(&mock_db, user_id).await;
+        assert!(matches!(result, Err(ClientError::ExportThrottled)));
+    }
+
+    #[tokio::test]
+    async fn test_request_export_after_interval() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let user_id = DbId::from(("users", "user"));
+
+        let last = ExportModel {
+            created_at: DbDateTime::from(Utc::now() - ExportModel::interval() - Duration::hours(1)),
+            status: ExportStatus::Completed,
+            ..Default::default()
+        };
+
+        mock_db
+            .expect_read_by_field_thing::<ExportModel>()
+            .times(1)
+            .returning(move |_, _, _, _| Ok(vec![last.clone()]));
+
+        mock_db
+            .expect_create::<CreateExportModel, ExportModel>()
+            .times(1)
+            .returning(|_, _| Ok(vec![ExportModel::default()]));
+
+        assert!(ExportModel::request(&mock_db, user_id).await.is_ok());
+    }
+}
@@ -0,0 +1,119 @@
+// models/file_model.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use kiro_database::{
+    db_bridge::{DatabaseOperations, HasId},
+    DbDateTime, DbId,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::error::ClientError;
+
+/// # File Model
+///
+/// The file model represents a file uploaded by a user, stored in the bucket.
+/// The checksum is the hex encoded SHA-256 digest of the content, verified
+/// during the upload.
+///
+/// ## Model
+///
+/// ```rust,no_run
+/// use kiro_client::FileModel;
+/// use kiro_database::{DbDateTime, DbId};
+///
+/// let file = FileModel {
+///     id: DbId::from(("files", "file")),
+///     user: DbId::from(("users", "user")),
+///     name: "report.pdf".to_string(),
+///     content_type: "application/pdf".to_string(),
+///     size: 1024,
+///     checksum: "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08".to_string(),
+///     url: "https://bucket.s3.eu-west-3.amazonaws.com/user/files_report.pdf".to_string(),
+///     created_at: DbDateTime::now(),
+/// };
+///
+/// println!("📄 File: {:?}", file);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileModel {
+    pub id: DbId,
+    pub user: DbId,
+    pub name: String,
+    pub content_type: String,
+    pub size: u64,
+    pub checksum: String,
+    pub url: String,
+    pub created_at: DbDateTime,
+}
+
+impl HasId for FileModel {
+    type Id = DbId;
+    fn id(&self) -> &Self::Id {
+        &self.id
+    }
+}
+
+// WARNING: This is a default implementation for testing purposes only
+impl Default for FileModel {
+    fn default() -> Self {
+        Self {
+            id: DbId::from(("files", "file")),
+            user: DbId::from(("users", "user")),
+            name: "file.txt".to_string(),
+            content_type: "text/plain".to_string(),
+            size: 0,
+            checksum: String::new(),
+            url: String::new(),
+            created_at: DbDateTime::now(),
+        }
+    }
+}
+
+/// # Create File Model
+///
+/// The create file model is used to record a new uploaded file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateFileModel {
+    pub user: DbId,
+    pub name: String,
+    pub content_type: String,
+    pub size: u64,
+    pub checksum: String,
+    pub url: String,
+}
+
+impl FileModel {
+    /// Create file
+    ///
+    /// Records a file once its upload is complete
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `file` - Uploaded file
+    ///
+    /// # Returns
+    /// * `Ok(FileModel)` - Recorded file
+    /// * `Err(ClientError)` - Database error
+    pub async fn create<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, file: CreateFileModel,
+    ) -> Result<Self, ClientError> {
+        db.create::<CreateFileModel, Self>("files", file)
+            .await
+            .map_err(ClientError::Database)?
+            .pop()
+            .ok_or(ClientError::DBOptionNone)
+    }
+}
@@ -0,0 +1,229 @@
+// models/group_model.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use kiro_api::client::v1::Group;
+use kiro_database::{
+    db_bridge::{DatabaseOperations, HasId},
+    db_query::Condition,
+    DbDateTime, DbId,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::error::ClientError;
+
+use super::UserModel;
+
+/// # Group Model
+///
+/// The group model represents a group of users. Membership is stored on the
+/// `UserModel.groups` field of each member.
+///
+/// ## Model
+///
+/// ```rust,no_run
+/// use kiro_client::GroupModel;
+/// use kiro_database::{DbDateTime, DbId};
+///
+/// let group = GroupModel {
+///     id: DbId::from(("groups", "engineering")),
+///     name: "Engineering".to_string(),
+///     created_at: DbDateTime::now(),
+///     updated_at: DbDateTime::now(),
+/// };
+///
+/// println!("👥 Group: {:?}", group);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupModel {
+    pub id: DbId,
+    pub name: String,
+    pub created_at: DbDateTime,
+    pub updated_at: DbDateTime,
+}
+
+impl HasId for GroupModel {
+    type Id = DbId;
+    fn id(&self) -> &Self::Id {
+        &self.id
+    }
+}
+
+// WARNING: This is a default implementation for testing purposes only
+impl Default for GroupModel {
+    fn default() -> Self {
+        Self {
+            id: DbId::from(("groups", "group")),
+            name: "Test group".to_string(),
+            created_at: DbDateTime::now(),
+            updated_at: DbDateTime::now(),
+        }
+    }
+}
+
+impl From<&GroupModel> for Group {
+    fn from(row: &GroupModel) -> Self {
+        Self {
+            id: row.id.to_string(),
+            name: row.name.clone(),
+        }
+    }
+}
+
+/// # Create Group Model
+///
+/// The create group model is used to create a new group.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateGroupModel {
+    pub name: String,
+}
+
+impl GroupModel {
+    /// Get group
+    ///
+    /// Retrieves a group by its identifier
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `id` - Identifier of the group, without the table name
+    ///
+    /// # Returns
+    /// * `Ok(Some(GroupModel))` - Group if found
+    /// * `Ok(None)` - Unknown group
+    /// * `Err(ClientError)` - Database error
+    pub async fn get_group<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, id: &str,
+    ) -> Result<Option<Self>, ClientError> {
+        db.select::<Self>(DbId::from(("groups", id)))
+            .await
+            .map_err(ClientError::Database)
+    }
+
+    /// Get members
+    ///
+    /// Retrieves the users member of a group
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `group` - Group identifier
+    ///
+    /// # Returns
+    /// * `Ok(Vec<UserModel>)` - Members of the group
+    /// * `Err(ClientError)` - Database error
+    pub async fn get_members<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, group: DbId,
+    ) -> Result<Vec<UserModel>, ClientError> {
+        db.read_where::<UserModel>("users", Condition::contains("groups", group), None)
+            .await
+            .map_err(ClientError::Database)
+    }
+
+    /// Add member
+    ///
+    /// Adds the group to the groups of a user
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user` - User to add
+    /// * `group` - Group identifier
+    pub async fn add_member<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user: &UserModel, group: DbId,
+    ) -> Result<(), ClientError> {
+        if user.groups.contains(&group) {
+            return Ok(());
+        }
+
+        let mut groups = user.groups.clone();
+        groups.push(group);
+
+        db.update_field(user.id.clone(), "groups", groups)
+            .await
+            .map_err(ClientError::Database)
+    }
+
+    /// Remove member
+    ///
+    /// Removes the group from the groups of a user
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user` - User to remove
+    /// * `group` - Group identifier
+    pub async fn remove_member<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user: &UserModel, group: DbId,
+    ) -> Result<(), ClientError> {
+        if !user.groups.contains(&group) {
+            return Ok(());
+        }
+
+        let groups: Vec<DbId> = user
+            .groups
+            .iter()
+            .filter(|g| **g != group)
+            .cloned()
+            .collect();
+
+        db.update_field(user.id.clone(), "groups", groups)
+            .await
+            .map_err(ClientError::Database)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::db_bridge::MockDatabaseOperations;
+    use mockall::predicate::eq;
+
+    #[tokio::test]
+    async fn test_add_and_remove_member() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let group = DbId::from(("groups", "engineering"));
+        let user = UserModel::default();
+
+        let mut added = user.groups.clone();
+        added.push(group.clone());
+
+        mock_db
+            .expect_update_field::<Vec<DbId>>()
+            .with(eq(user.id.clone()), eq("groups"), eq(added.clone()))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        mock_db
+            .expect_update_field::<Vec<DbId>>()
+            .with(eq(user.id.clone()), eq("groups"), eq(user.groups.clone()))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        GroupModel::add_member(&mock_db, &user, group.clone())
+            .await
+            .unwrap();
+
+        let member = UserModel {
+            groups: added,
+            ..user.clone()
+        };
+        GroupModel::remove_member(&mock_db, &member, group.clone())
+            .await
+            .unwrap();
+
+        // Already a member, nothing to update
+        GroupModel::add_member(&mock_db, &member, group)
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,299 @@
+// models/invitation_model.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::Utc;
+use kiro_api::{client::v1::Invitation, google::protobuf::Timestamp};
+use kiro_database::{
+    db_bridge::{DatabaseOperations, HasId, QueryOptions},
+    db_query::{Condition, FieldUpdate, Update},
+    DbDateTime, DbId,
+};
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::error::ClientError;
+
+/// Length of generated invitation codes
+const CODE_LENGTH: usize = 24;
+
+/// # Invitation Model
+///
+/// The invitation model is an invitation code of the invite-only registration.
+/// A code is used once, and may be restricted to an email address. The user
+/// registering with it is granted the admin role or added to a group when the
+/// invitation carries them.
+///
+/// ## Model
+///
+/// ```rust,no_run
+/// use kiro_client::InvitationModel;
+/// use kiro_database::{DbDateTime, DbId};
+///
+/// let invitation = InvitationModel {
+///     id: DbId::from(("invitations", "invitation")),
+///     code: "invitation_code".to_string(),
+///     email: Some("user@example.com".to_string()),
+///     is_admin: false,
+///     group: Some(DbId::from(("groups", "engineering"))),
+///     expires_at: None,
+///     used_by: None,
+///     used_at: None,
+///     created_at: DbDateTime::now(),
+/// };
+///
+/// println!("✉️ Invitation: {:?}", invitation);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvitationModel {
+    pub id: DbId,
+    pub code: String,
+    pub email: Option<String>,
+    pub is_admin: bool,
+    pub group: Option<DbId>,
+    pub expires_at: Option<DbDateTime>,
+    pub used_by: Option<DbId>,
+    pub used_at: Option<DbDateTime>,
+    pub created_at: DbDateTime,
+}
+
+impl HasId for InvitationModel {
+    type Id = DbId;
+    fn id(&self) -> &Self::Id {
+        &self.id
+    }
+}
+
+// WARNING: This is a default implementation for testing purposes only
+impl Default for InvitationModel {
+    fn default() -> Self {
+        Self {
+            id: DbId::from(("invitations", "invitation")),
+            code: "invitation_code".to_string(),
+            email: None,
+            is_admin: false,
+            group: None,
+            expires_at: None,
+            used_by: None,
+            used_at: None,
+            created_at: DbDateTime::now(),
+        }
+    }
+}
+
+/// Converts a database date to a protobuf timestamp
+fn to_timestamp(date: &DbDateTime) -> Timestamp {
+    Timestamp {
+        seconds: date.timestamp(),
+        nanos: 0,
+    }
+}
+
+impl From<&InvitationModel> for Invitation {
+    fn from(row: &InvitationModel) -> Self {
+        Self {
+            code: row.code.clone(),
+            email: row.email.clone(),
+            is_admin: row.is_admin,
+            group_id: row.group.as_ref().map(|group| group.id.to_string()),
+            expires_at: row.expires_at.as_ref().map(to_timestamp),
+            created_at: Some(to_timestamp(&row.created_at)),
+        }
+    }
+}
+
+/// # Create Invitation Model
+///
+/// The create invitation model is used to create a new invitation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateInvitationModel {
+    pub code: String,
+    pub email: Option<String>,
+    pub is_admin: bool,
+    pub group: Option<DbId>,
+    pub expires_at: Option<DbDateTime>,
+}
+
+impl InvitationModel {
+    /// Create invitation
+    ///
+    /// Creates an invitation with a random code
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `email` - Only email address allowed to use the code, if any
+    /// * `is_admin` - Whether the invited user is granted the admin role
+    /// * `group` - Group the invited user is added to, if any
+    /// * `expires_at` - Expiry of the code, if any
+    ///
+    /// # Returns
+    /// * `Ok(InvitationModel)` - Created invitation
+    /// * `Err(ClientError)` - Database error
+    pub async fn create<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, email: Option<String>, is_admin: bool, group: Option<DbId>,
+        expires_at: Option<DbDateTime>,
+    ) -> Result<Self, ClientError> {
+        let code = thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(CODE_LENGTH)
+            .map(char::from)
+            .collect();
+
+        db.create::<CreateInvitationModel, Self>(
+            "invitations",
+            CreateInvitationModel {
+                code,
+                email: email.map(|email| email.to_lowercase()),
+                is_admin,
+                group,
+                expires_at,
+            },
+        )
+        .await
+        .map_err(ClientError::Database)?
+        .pop()
+        .ok_or(ClientError::DBOptionNone)
+    }
+
+    /// Get valid invitation
+    ///
+    /// Retrieves an invitation by its code, if it is neither used nor expired
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `code` - Invitation code
+    ///
+    /// # Returns
+    /// * `Ok(Some(InvitationModel))` - Usable invitation
+    /// * `Ok(None)` - Unknown, used or expired code
+    /// * `Err(ClientError)` - Database error
+    pub async fn get_valid<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, code: &str,
+    ) -> Result<Option<Self>, ClientError> {
+        Ok(db
+            .read_where::<Self>(
+                "invitations",
+                Condition::And(vec![
+                    Condition::eq("code", code),
+                    Condition::is_none("used_by"),
+                    Condition::Or(vec![
+                        Condition::is_none("expires_at"),
+                        Condition::gt("expires_at", Utc::now()),
+                    ]),
+                ]),
+                Some(QueryOptions {
+                    order_by: None,
+                    order_direction: None,
+                    limit: Some(1),
+                    start: None,
+                }),
+            )
+            .await
+            .map_err(ClientError::Database)?
+            .pop())
+    }
+
+    /// Claim invitation
+    ///
+    /// Marks the invitation as used by a user, unless another registration
+    /// used it in the meantime
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user_id` - Identifier of the registered user
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The invitation is now used by the user
+    /// * `Ok(false)` - The invitation was already used
+    /// * `Err(ClientError)` - Database error
+    pub async fn claim<DB: DatabaseOperations + Send + Sync>(
+        &self, db: &DB, user_id: DbId,
+    ) -> Result<bool, ClientError> {
+        Ok(!db
+            .update_where::<serde_json::Value>(
+                Update::record(
+                    self.id.clone(),
+                    vec![
+                        FieldUpdate::set("used_by", user_id),
+                        FieldUpdate::now("used_at"),
+                    ],
+                )
+                .when(Condition::is_none("used_by")),
+            )
+            .await
+            .map_err(ClientError::Database)?
+            .is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kiro_database::db_bridge::MockDatabaseOperations;
+
+    #[tokio::test]
+    async fn test_create_invitation() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_create::<CreateInvitationModel, InvitationModel>()
+            .withf(|table, data| {
+                table == "invitations"
+                    && data.code.len() == CODE_LENGTH
+                    && data.email == Some("user@example.com".to_string())
+                    && data.is_admin
+            })
+            .times(1)
+            .returning(|_, data| {
+                Ok(vec![InvitationModel {
+                    code: data.code,
+                    email: data.email,
+                    is_admin: data.is_admin,
+                    ..Default::default()
+                }])
+            });
+
+        let invitation = InvitationModel::create(
+            &mock_db,
+            Some("User@Example.com".to_string()),
+            true,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(invitation.code.len(), CODE_LENGTH);
+        assert!(invitation.is_admin);
+    }
+
+    #[tokio::test]
+    async fn test_claim_already_used() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_update_where::<serde_json::Value>()
+            .withf(|update| update.table == "invitations" && update.changes.len() == 2)
+            .times(1)
+            .returning(|_| Ok(vec![]));
+
+        let claimed = InvitationModel::default()
+            .claim(&mock_db, DbId::default())
+            .await
+            .unwrap();
+
+        assert!(!claimed);
+    }
+}
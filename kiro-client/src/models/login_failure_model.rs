@@ -0,0 +1,256 @@
+// models/login_failure_model.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{DateTime, Utc};
+use kiro_database::{
+    db_bridge::{DatabaseOperations, HasId},
+    db_query::Condition,
+    DbDateTime, DbId,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::error::ClientError;
+
+/// # Login Failure Model
+///
+/// The login failure model is a failed login, counted per account and per IP
+/// address to decide when a CAPTCHA is required.
+///
+/// ## Model
+///
+/// ```rust,no_run
+/// use kiro_client::LoginFailureModel;
+/// use kiro_database::{DbDateTime, DbId};
+///
+/// let failure = LoginFailureModel {
+///     id: DbId::from(("login_failures", "failure")),
+///     email: "user@example.com".to_string(),
+///     ip_address: Some("203.0.113.195".to_string()),
+///     created_at: DbDateTime::now(),
+/// };
+///
+/// println!("🔐 Login failure: {:?}", failure);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoginFailureModel {
+    pub id: DbId,
+    pub email: String,
+    pub ip_address: Option<String>,
+    pub created_at: DbDateTime,
+}
+
+impl HasId for LoginFailureModel {
+    type Id = DbId;
+    fn id(&self) -> &Self::Id {
+        &self.id
+    }
+}
+
+// WARNING: This is a default implementation for testing purposes only
+impl Default for LoginFailureModel {
+    fn default() -> Self {
+        Self {
+            id: DbId::from(("login_failures", "failure")),
+            email: "test@example.com".to_string(),
+            ip_address: Some("203.0.113.195".to_string()),
+            created_at: DbDateTime::now(),
+        }
+    }
+}
+
+/// # Create Login Failure Model
+///
+/// The create login failure model is used to record a failed login.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateLoginFailureModel {
+    pub email: String,
+    pub ip_address: Option<String>,
+}
+
+/// # Login Failure Counts
+///
+/// Number of recent failed logins of an account and of an IP address.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LoginFailureCounts {
+    pub account: usize,
+    pub ip: usize,
+}
+
+impl LoginFailureModel {
+    /// Record login failure
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `email` - Email sent with the failed login
+    /// * `ip_address` - IP address of the request, if known
+    ///
+    /// # Returns
+    /// * `Ok(())` - Failure recorded
+    /// * `Err(ClientError)` - Database error
+    pub async fn record<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, email: &str, ip_address: Option<String>,
+    ) -> Result<(), ClientError> {
+        db.create::<CreateLoginFailureModel, Self>(
+            "login_failures",
+            CreateLoginFailureModel {
+                email: email.to_lowercase(),
+                ip_address,
+            },
+        )
+        .await
+        .map_err(ClientError::Database)?;
+
+        Ok(())
+    }
+
+    /// Count recent login failures
+    ///
+    /// Counts the failures of the account and of the IP address since a date
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `email` - Email of the account
+    /// * `ip_address` - IP address of the request, if known
+    /// * `since` - Start of the counting window
+    ///
+    /// # Returns
+    /// * `Ok(LoginFailureCounts)` - Failures of the account and of the IP address
+    /// * `Err(ClientError)` - Database error
+    pub async fn count_recent<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, email: &str, ip_address: Option<&str>, since: DateTime<Utc>,
+    ) -> Result<LoginFailureCounts, ClientError> {
+        let account = db
+            .count_where(
+                "login_failures",
+                Condition::And(vec![
+                    Condition::eq("email", email.to_lowercase()),
+                    Condition::gt("created_at", since),
+                ]),
+            )
+            .await
+            .map_err(ClientError::Database)?;
+
+        let ip = match ip_address {
+            Some(ip_address) => db
+                .count_where(
+                    "login_failures",
+                    Condition::And(vec![
+                        Condition::eq("ip_address", ip_address),
+                        Condition::gt("created_at", since),
+                    ]),
+                )
+                .await
+                .map_err(ClientError::Database)?,
+            None => 0,
+        };
+
+        Ok(LoginFailureCounts {
+            account: account as usize,
+            ip: ip as usize,
+        })
+    }
+
+    /// Clear login failures
+    ///
+    /// Removes the failures of an account after a successful login
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `email` - Email of the account
+    ///
+    /// # Returns
+    /// * `Ok(())` - Failures removed
+    /// * `Err(ClientError)` - Database error
+    pub async fn clear<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, email: &str,
+    ) -> Result<(), ClientError> {
+        db.delete_where(
+            "login_failures",
+            Condition::eq("email", email.to_lowercase()),
+        )
+        .await
+        .map_err(ClientError::Database)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::db_bridge::MockDatabaseOperations;
+    use mockall::predicate::eq;
+
+    #[tokio::test]
+    async fn test_record_lowercases_email() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_create::<CreateLoginFailureModel, LoginFailureModel>()
+            .withf(|table, data| {
+                table == "login_failures"
+                    && data.email == "user@example.com"
+                    && data.ip_address.as_deref() == Some("203.0.113.195")
+            })
+            .times(1)
+            .returning(|_, _| Ok(vec![LoginFailureModel::default()]));
+
+        LoginFailureModel::record(
+            &mock_db,
+            "User@Example.com",
+            Some("203.0.113.195".to_string()),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_count_recent_without_failures() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_count_where()
+            .withf(|table, _| table == "login_failures")
+            .times(1)
+            .returning(|_, _| Ok(0));
+
+        let counts =
+            LoginFailureModel::count_recent(&mock_db, "user@example.com", None, Utc::now())
+                .await
+                .unwrap();
+
+        assert_eq!(counts, LoginFailureCounts::default());
+    }
+
+    #[tokio::test]
+    async fn test_clear() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_delete_where()
+            .with(
+                eq("login_failures"),
+                eq(Condition::eq("email", "user@example.com")),
+            )
+            .times(1)
+            .returning(|_, _| Ok(0));
+
+        LoginFailureModel::clear(&mock_db, "user@example.com")
+            .await
+            .unwrap();
+    }
+}
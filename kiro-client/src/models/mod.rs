@@ -14,7 +14,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod account_event_model;
+mod change_model;
+#[cfg(feature = "export")]
+mod export_model;
+#[cfg(feature = "storage")]
+mod file_model;
+mod group_model;
+mod invitation_model;
+mod login_failure_model;
+#[cfg(feature = "oauth")]
+mod oauth_model;
+mod policy_model;
 mod session_model;
+mod user_email_model;
+#[cfg(feature = "import")]
+mod user_export_model;
 mod user_model;
 
 /// # Session Models
@@ -26,6 +41,71 @@ pub use session_model::{CreateSessionModel, SessionModel};
 ///
 /// The user model provides models for users.
 pub use user_model::{
-    CreateUserModel, Language, NotificationSettings, PrivacySettings, SecuritySettings, Theme,
-    UserModel, UserSettings,
+    AvatarVariant, ChannelSettings, CreateUserModel, Language, NotificationCategory,
+    NotificationChannel, NotificationSettings, PrivacySettings, QuietHoursSettings,
+    SecuritySettings, Theme, UserModel, UserSettings,
+};
+
+/// # Group Models
+///
+/// The group model provides models for groups of users.
+pub use group_model::{CreateGroupModel, GroupModel};
+
+/// # Account Event Models
+///
+/// The account event model provides models for the audit log of account state changes.
+pub use account_event_model::{AccountEventKind, AccountEventModel, CreateAccountEventModel};
+
+/// # Change Models
+///
+/// The change model provides models for the change log of the delta sync.
+pub use change_model::{ChangeKind, ChangeModel, ChangeVersionsModel};
+
+/// # Policy Models
+///
+/// The policy model provides models for the policy documents and their acceptance by users.
+pub use policy_model::{
+    CreatePolicyConsentModel, PolicyConsentModel, PolicyDocumentModel, PolicyKind,
+};
+
+/// # Invitation Models
+///
+/// The invitation model provides models for the invitation codes of the registration.
+pub use invitation_model::{CreateInvitationModel, InvitationModel};
+
+/// # Login Failure Models
+///
+/// The login failure model provides models for the failed logins counted by the CAPTCHA policy.
+pub use login_failure_model::{CreateLoginFailureModel, LoginFailureCounts, LoginFailureModel};
+
+/// # User Email Models
+///
+/// The user email model provides models for the secondary email addresses of users.
+pub use user_email_model::{CreateUserEmailModel, UserEmailModel};
+
+/// # File Models
+///
+/// The file model provides models for the files uploaded by users.
+#[cfg(feature = "storage")]
+pub use file_model::{CreateFileModel, FileModel};
+
+/// # Export Models
+///
+/// The export model provides models for the data exports of users.
+#[cfg(feature = "export")]
+pub use export_model::{CreateExportModel, ExportModel, ExportStatus};
+
+/// # User Export Models
+///
+/// The user export model provides models for the audit log of bulk user exports.
+#[cfg(feature = "import")]
+pub use user_export_model::{CreateUserExportModel, UserExportModel};
+
+/// # OAuth Models
+///
+/// The oauth model provides models for the OAuth2 / OpenID Connect identity provider.
+#[cfg(feature = "oauth")]
+pub use oauth_model::{
+    AuthorizationCodeModel, ConsentModel, CreateAuthorizationCodeModel, CreateConsentModel,
+    CreateOAuthClientModel, GrantType, OAuthClientModel,
 };
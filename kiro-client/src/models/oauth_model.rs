@@ -0,0 +1,689 @@
+// models/oauth_model.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{Duration, Utc};
+use kiro_database::{
+    db_bridge::{DatabaseOperations, HasId},
+    db_query::Condition,
+    DbDateTime, DbId,
+};
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::{ClientError, OAuthError};
+
+use super::SessionModel;
+
+/// Lifetime of an authorization code
+const AUTHORIZATION_CODE_TTL: i64 = 60;
+
+/// Length of generated client secrets and authorization codes
+const SECRET_LENGTH: usize = 48;
+
+/// Generates a random alphanumeric secret
+fn generate_secret() -> String {
+    thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(SECRET_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+/// # Grant Type
+///
+/// The grant types an OAuth client can be registered for.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GrantType {
+    AuthorizationCode,
+    ClientCredentials,
+}
+
+/// # OAuth Client Model
+///
+/// The OAuth client model represents an application allowed to use Kiro as identity provider.
+/// Public clients (single page or mobile apps) have no secret and must use PKCE.
+///
+/// ## Model
+///
+/// ```rust,no_run
+/// use kiro_client::{GrantType, OAuthClientModel};
+/// use kiro_database::{DbDateTime, DbId};
+///
+/// let client = OAuthClientModel {
+///     id: DbId::from(("oauth_clients", "dashboard")),
+///     name: "Dashboard".to_string(),
+///     secret_hash: None,
+///     redirect_uris: vec!["https://dashboard.example.com/callback".to_string()],
+///     scopes: vec!["openid".to_string(), "email".to_string()],
+///     grant_types: vec![GrantType::AuthorizationCode],
+///     created_at: DbDateTime::now(),
+/// };
+///
+/// println!("🔑 Client: {:?}", client);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthClientModel {
+    pub id: DbId,
+    pub name: String,
+    pub secret_hash: Option<String>,
+    pub redirect_uris: Vec<String>,
+    pub scopes: Vec<String>,
+    pub grant_types: Vec<GrantType>,
+    pub created_at: DbDateTime,
+}
+
+impl HasId for OAuthClientModel {
+    type Id = DbId;
+    fn id(&self) -> &Self::Id {
+        &self.id
+    }
+}
+
+// WARNING: This is a default implementation for testing purposes only
+impl Default for OAuthClientModel {
+    fn default() -> Self {
+        Self {
+            id: DbId::from(("oauth_clients", "client")),
+            name: "Test client".to_string(),
+            secret_hash: None,
+            redirect_uris: vec!["https://app.example.com/callback".to_string()],
+            scopes: vec![
+                "openid".to_string(),
+                "email".to_string(),
+                "profile".to_string(),
+            ],
+            grant_types: vec![GrantType::AuthorizationCode],
+            created_at: DbDateTime::now(),
+        }
+    }
+}
+
+/// # Create OAuth Client Model
+///
+/// The create OAuth client model is used to register a new client.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateOAuthClientModel {
+    pub name: String,
+    pub secret_hash: Option<String>,
+    pub redirect_uris: Vec<String>,
+    pub scopes: Vec<String>,
+    pub grant_types: Vec<GrantType>,
+}
+
+impl OAuthClientModel {
+    /// Client identifier exposed to relying parties
+    pub fn client_id(&self) -> String {
+        self.id.id.to_string()
+    }
+
+    /// Whether the client holds a secret
+    pub fn is_confidential(&self) -> bool {
+        self.secret_hash.is_some()
+    }
+
+    /// Register client
+    ///
+    /// Creates a new client and returns it along with the plain secret, which is
+    /// only shown once. Public clients get no secret.
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `client` - Client registration without secret hash
+    /// * `confidential` - Whether a secret must be generated
+    ///
+    /// # Returns
+    /// * `Ok((OAuthClientModel, Option<String>))` - Created client and plain secret
+    /// * `Err(ClientError)` - Database or hashing error
+    pub async fn register<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, mut client: CreateOAuthClientModel, confidential: bool,
+    ) -> Result<(Self, Option<String>), ClientError> {
+        let secret = confidential.then(generate_secret);
+
+        client.secret_hash = match &secret {
+            Some(secret) => Some(SessionModel::create_password_hash(secret.clone()).await?),
+            None => None,
+        };
+
+        db.create::<CreateOAuthClientModel, Self>("oauth_clients", client)
+            .await
+            .map_err(ClientError::Database)
+            .and_then(|res| res.into_iter().next().ok_or(ClientError::NotCreated))
+            .map(|client| (client, secret))
+    }
+
+    /// Get client
+    ///
+    /// Retrieves a registered client by its public identifier
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `client_id` - Public client identifier
+    ///
+    /// # Returns
+    /// * `Ok(OAuthClientModel)` - Client if registered
+    /// * `Err(OAuthError::InvalidClient)` - Unknown client
+    pub async fn get_client<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, client_id: &str,
+    ) -> Result<Self, OAuthError> {
+        db.select::<Self>(DbId::from(("oauth_clients", client_id)))
+            .await?
+            .ok_or(OAuthError::InvalidClient)
+    }
+
+    /// Authenticate client
+    ///
+    /// Checks the secret of a confidential client. Public clients authenticate
+    /// with PKCE instead and always pass when no secret is given.
+    ///
+    /// # Arguments
+    /// * `secret` - Secret sent by the client, if any
+    ///
+    /// # Returns
+    /// * `Ok(())` - Client authenticated
+    /// * `Err(OAuthError::InvalidClient)` - Missing or wrong secret
+    pub async fn authenticate(&self, secret: Option<String>) -> Result<(), OAuthError> {
+        match (&self.secret_hash, secret) {
+            (None, None) => Ok(()),
+            (Some(hash), Some(secret)) => {
+                if SessionModel::verify_password(secret, hash.clone()).await? {
+                    Ok(())
+                } else {
+                    Err(OAuthError::InvalidClient)
+                }
+            }
+            _ => Err(OAuthError::InvalidClient),
+        }
+    }
+
+    /// Whether the redirect URI is registered for the client (exact match)
+    pub fn allows_redirect(&self, redirect_uri: &str) -> bool {
+        self.redirect_uris.iter().any(|uri| uri == redirect_uri)
+    }
+
+    /// Whether every requested scope is allowed for the client
+    pub fn allows_scopes(&self, scopes: &[String]) -> bool {
+        scopes.iter().all(|scope| self.scopes.contains(scope))
+    }
+
+    /// Whether the client is registered for the grant type
+    pub fn allows_grant(&self, grant_type: GrantType) -> bool {
+        self.grant_types.contains(&grant_type)
+    }
+}
+
+/// # Authorization Code Model
+///
+/// The authorization code model is a short-lived, single use code bound to a
+/// client, a redirect URI and a PKCE challenge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorizationCodeModel {
+    pub id: DbId,
+    pub code: String,
+    pub client: DbId,
+    pub user: DbId,
+    pub redirect_uri: String,
+    pub scopes: Vec<String>,
+    pub code_challenge: String,
+    pub nonce: Option<String>,
+    pub expires_at: DbDateTime,
+}
+
+impl HasId for AuthorizationCodeModel {
+    type Id = DbId;
+    fn id(&self) -> &Self::Id {
+        &self.id
+    }
+}
+
+/// # Create Authorization Code Model
+///
+/// The create authorization code model is used to issue a new code.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateAuthorizationCodeModel {
+    pub code: String,
+    pub client: DbId,
+    pub user: DbId,
+    pub redirect_uri: String,
+    pub scopes: Vec<String>,
+    pub code_challenge: String,
+    pub nonce: Option<String>,
+    pub expires_at: DbDateTime,
+}
+
+impl AuthorizationCodeModel {
+    /// Issue code
+    ///
+    /// Creates a new authorization code valid for one minute
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `client` - Client the code is issued to
+    /// * `user` - Authenticated user
+    /// * `redirect_uri` - Redirect URI the code is bound to
+    /// * `scopes` - Granted scopes
+    /// * `code_challenge` - S256 PKCE challenge
+    /// * `nonce` - OpenID Connect nonce to copy in the ID token
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The code to send back to the client
+    /// * `Err(ClientError)` - Database error
+    pub async fn issue<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, client: DbId, user: DbId, redirect_uri: String, scopes: Vec<String>,
+        code_challenge: String, nonce: Option<String>,
+    ) -> Result<String, ClientError> {
+        let code = generate_secret();
+
+        db.create::<CreateAuthorizationCodeModel, Self>(
+            "oauth_codes",
+            CreateAuthorizationCodeModel {
+                code: code.clone(),
+                client,
+                user,
+                redirect_uri,
+                scopes,
+                code_challenge,
+                nonce,
+                expires_at: DbDateTime::from(
+                    Utc::now() + Duration::seconds(AUTHORIZATION_CODE_TTL),
+                ),
+            },
+        )
+        .await
+        .map_err(ClientError::Database)
+        .and_then(|res| res.into_iter().next().ok_or(ClientError::NotCreated))
+        .map(|_| code)
+    }
+
+    /// Consume code
+    ///
+    /// Looks up and deletes the code so it can only be used once, a request that
+    /// did not delete the code itself is rejected. The code must not be expired
+    /// and must belong to the client and redirect URI.
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `code` - Code sent by the client
+    /// * `client` - Authenticated client
+    /// * `redirect_uri` - Redirect URI sent by the client
+    ///
+    /// # Returns
+    /// * `Ok(AuthorizationCodeModel)` - Valid code
+    /// * `Err(OAuthError::InvalidGrant)` - Unknown, used, expired or mismatched code
+    pub async fn consume<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, code: &str, client: &DbId, redirect_uri: &str,
+    ) -> Result<Self, OAuthError> {
+        let code = db
+            .read_by_field::<Self>("oauth_codes", "code", code, None)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| OAuthError::InvalidGrant("Unknown authorization code".to_string()))?;
+
+        // Only the request deleting the code may redeem it
+        let deleted = db
+            .delete_where("oauth_codes", Condition::eq("code", code.code.clone()))
+            .await?;
+
+        if deleted != 1 {
+            return Err(OAuthError::InvalidGrant(
+                "Authorization code already used".to_string(),
+            ));
+        }
+
+        if code.expires_at < Utc::now() {
+            return Err(OAuthError::InvalidGrant(
+                "Authorization code expired".to_string(),
+            ));
+        }
+
+        if &code.client != client || code.redirect_uri != redirect_uri {
+            return Err(OAuthError::InvalidGrant(
+                "Authorization code was not issued to this client".to_string(),
+            ));
+        }
+
+        Ok(code)
+    }
+
+    /// Verify PKCE
+    ///
+    /// Checks the S256 code verifier against the stored challenge (RFC 7636)
+    pub fn verify_pkce(&self, code_verifier: &str) -> bool {
+        let digest = Sha256::digest(code_verifier.as_bytes());
+        URL_SAFE_NO_PAD.encode(digest) == self.code_challenge
+    }
+}
+
+/// # Consent Model
+///
+/// The consent model records the scopes a user granted to a client.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsentModel {
+    pub id: DbId,
+    pub user: DbId,
+    pub client: DbId,
+    pub scopes: Vec<String>,
+    pub created_at: DbDateTime,
+    pub updated_at: DbDateTime,
+}
+
+impl HasId for ConsentModel {
+    type Id = DbId;
+    fn id(&self) -> &Self::Id {
+        &self.id
+    }
+}
+
+/// # Create Consent Model
+///
+/// The create consent model is used to record a new consent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateConsentModel {
+    pub user: DbId,
+    pub client: DbId,
+    pub scopes: Vec<String>,
+}
+
+impl ConsentModel {
+    /// Get consent
+    ///
+    /// Retrieves the consent a user gave to a client
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user` - User identifier
+    /// * `client` - Client identifier
+    ///
+    /// # Returns
+    /// * `Ok(Some(ConsentModel))` - Consent if recorded
+    /// * `Ok(None)` - No consent recorded
+    /// * `Err(ClientError)` - Database error
+    pub async fn get_consent<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user: DbId, client: DbId,
+    ) -> Result<Option<Self>, ClientError> {
+        db.read_where::<Self>(
+            "oauth_consents",
+            Condition::And(vec![
+                Condition::eq("user", user),
+                Condition::eq("client", client),
+            ]),
+            None,
+        )
+        .await
+        .map_err(ClientError::Database)
+        .map(|consents| consents.into_iter().next())
+    }
+
+    /// Whether the consent covers every requested scope
+    pub fn covers(&self, scopes: &[String]) -> bool {
+        scopes.iter().all(|scope| self.scopes.contains(scope))
+    }
+
+    /// Grant consent
+    ///
+    /// Records the scopes granted by a user, merging them with an existing consent
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user` - User identifier
+    /// * `client` - Client identifier
+    /// * `scopes` - Granted scopes
+    ///
+    /// # Returns
+    /// * `Ok(())` - Consent recorded
+    /// * `Err(ClientError)` - Database error
+    pub async fn grant<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user: DbId, client: DbId, scopes: Vec<String>,
+    ) -> Result<(), ClientError> {
+        match Self::get_consent(db, user.clone(), client.clone()).await? {
+            Some(consent) => {
+                let mut granted = consent.scopes.clone();
+                for scope in scopes {
+                    if !granted.contains(&scope) {
+                        granted.push(scope);
+                    }
+                }
+
+                db.update_field(consent.id.clone(), "scopes", granted)
+                    .await
+                    .map_err(ClientError::Database)?;
+                db.update_field(consent.id, "updated_at", DbDateTime::now())
+                    .await
+                    .map_err(ClientError::Database)
+            }
+            None => db
+                .create::<CreateConsentModel, Self>(
+                    "oauth_consents",
+                    CreateConsentModel {
+                        user,
+                        client,
+                        scopes,
+                    },
+                )
+                .await
+                .map_err(ClientError::Database)
+                .and_then(|res| res.into_iter().next().ok_or(ClientError::NotCreated))
+                .map(|_| ()),
+        }
+    }
+
+    /// Revoke consent
+    ///
+    /// Deletes the consent a user gave to a client
+    pub async fn revoke<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user: DbId, client: DbId,
+    ) -> Result<(), ClientError> {
+        if let Some(consent) = Self::get_consent(db, user, client).await? {
+            db.delete(consent.id).await.map_err(ClientError::Database)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::db_bridge::MockDatabaseOperations;
+    use mockall::predicate::{always, eq};
+
+    fn code_model(challenge: &str) -> AuthorizationCodeModel {
+        AuthorizationCodeModel {
+            id: DbId::from(("oauth_codes", "code")),
+            code: "code".to_string(),
+            client: DbId::from(("oauth_clients", "client")),
+            user: DbId::default(),
+            redirect_uri: "https://app.example.com/callback".to_string(),
+            scopes: vec!["openid".to_string()],
+            code_challenge: challenge.to_string(),
+            nonce: None,
+            expires_at: DbDateTime::from(Utc::now() + Duration::seconds(60)),
+        }
+    }
+
+    #[test]
+    fn test_verify_pkce() {
+        // Example from RFC 7636 appendix B
+        let code = code_model("E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM");
+
+        assert!(code.verify_pkce("dBjftJeZ4CVP-mB92K1uGEbfxIArLpeUqeyIdKi8wF_s"));
+        assert!(!code.verify_pkce("wrong-verifier"));
+    }
+
+    #[test]
+    fn test_client_checks() {
+        let client = OAuthClientModel::default();
+
+        assert_eq!(client.client_id(), "client");
+        assert!(!client.is_confidential());
+        assert!(client.allows_redirect("https://app.example.com/callback"));
+        assert!(!client.allows_redirect("https://evil.example.com/callback"));
+        assert!(client.allows_scopes(&["openid".to_string(), "email".to_string()]));
+        assert!(!client.allows_scopes(&["admin".to_string()]));
+        assert!(client.allows_grant(GrantType::AuthorizationCode));
+        assert!(!client.allows_grant(GrantType::ClientCredentials));
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_client() {
+        let mut client = OAuthClientModel::default();
+
+        // Public client without secret
+        assert!(client.authenticate(None).await.is_ok());
+        assert!(client
+            .authenticate(Some("secret".to_string()))
+            .await
+            .is_err());
+
+        // Confidential client
+        client.secret_hash = Some(
+            SessionModel::create_password_hash("secret".to_string())
+                .await
+                .unwrap(),
+        );
+        assert!(client
+            .authenticate(Some("secret".to_string()))
+            .await
+            .is_ok());
+        assert!(client
+            .authenticate(Some("wrong".to_string()))
+            .await
+            .is_err());
+        assert!(client.authenticate(None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_consume_code_is_single_use() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let code = code_model("challenge");
+
+        mock_db
+            .expect_read_by_field::<AuthorizationCodeModel>()
+            .with(eq("oauth_codes"), eq("code"), eq("code"), eq(None))
+            .times(1)
+            .returning(move |_, _, _, _| Ok(vec![code.clone()]));
+
+        mock_db
+            .expect_delete_where()
+            .with(eq("oauth_codes"), eq(Condition::eq("code", "code")))
+            .times(1)
+            .returning(|_, _| Ok(1));
+
+        let result = AuthorizationCodeModel::consume(
+            &mock_db,
+            "code",
+            &DbId::from(("oauth_clients", "client")),
+            "https://app.example.com/callback",
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_consume_code_wrong_client() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let code = code_model("challenge");
+
+        mock_db
+            .expect_read_by_field::<AuthorizationCodeModel>()
+            .times(1)
+            .returning(move |_, _, _, _| Ok(vec![code.clone()]));
+
+        mock_db
+            .expect_delete_where()
+            .times(1)
+            .returning(|_, _| Ok(1));
+
+        let result = AuthorizationCodeModel::consume(
+            &mock_db,
+            "code",
+            &DbId::from(("oauth_clients", "other")),
+            "https://app.example.com/callback",
+        )
+        .await;
+
+        assert!(matches!(result, Err(OAuthError::InvalidGrant(_))));
+    }
+
+    #[tokio::test]
+    async fn test_consume_code_already_used() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let code = code_model("challenge");
+
+        mock_db
+            .expect_read_by_field::<AuthorizationCodeModel>()
+            .times(1)
+            .returning(move |_, _, _, _| Ok(vec![code.clone()]));
+
+        // A concurrent request deleted the code first
+        mock_db
+            .expect_delete_where()
+            .with(eq("oauth_codes"), eq(Condition::eq("code", "code")))
+            .times(1)
+            .returning(|_, _| Ok(0));
+
+        let result = AuthorizationCodeModel::consume(
+            &mock_db,
+            "code",
+            &DbId::from(("oauth_clients", "client")),
+            "https://app.example.com/callback",
+        )
+        .await;
+
+        assert!(matches!(result, Err(OAuthError::InvalidGrant(_))));
+    }
+
+    #[tokio::test]
+    async fn test_grant_consent_creates_record() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<ConsentModel>()
+            .with(eq("oauth_consents"), always(), always())
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
+
+        mock_db
+            .expect_create::<CreateConsentModel, ConsentModel>()
+            .with(eq("oauth_consents"), always())
+            .times(1)
+            .returning(|_, data| {
+                Ok(vec![ConsentModel {
+                    id: DbId::from(("oauth_consents", "consent")),
+                    user: data.user,
+                    client: data.client,
+                    scopes: data.scopes,
+                    created_at: DbDateTime::now(),
+                    updated_at: DbDateTime::now(),
+                }])
+            });
+
+        let result = ConsentModel::grant(
+            &mock_db,
+            DbId::default(),
+            DbId::from(("oauth_clients", "client")),
+            vec!["openid".to_string()],
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+}
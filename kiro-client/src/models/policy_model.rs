@@ -0,0 +1,514 @@
+// models/policy_model.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::Utc;
+use kiro_api::{
+    client::v1::{Consent, PolicyDocument},
+    google::protobuf::Timestamp,
+};
+use kiro_database::{
+    db_bridge::{DatabaseOperations, HasId, OrderDirection, QueryOptions},
+    db_query::Condition,
+    DbDateTime, DbId,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::error::ClientError;
+
+/// # Policy Kind
+///
+/// The policy kind is the legal document a version belongs to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum PolicyKind {
+    Terms,
+    Privacy,
+}
+
+impl PolicyKind {
+    /// Name of the kind, as stored and sent to clients
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PolicyKind::Terms => "Terms",
+            PolicyKind::Privacy => "Privacy",
+        }
+    }
+
+    /// Parses the name of a kind
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Terms" => Some(PolicyKind::Terms),
+            "Privacy" => Some(PolicyKind::Privacy),
+            _ => None,
+        }
+    }
+}
+
+/// Converts a database date to a protobuf timestamp
+fn to_timestamp(date: &DbDateTime) -> Option<Timestamp> {
+    Some(Timestamp {
+        seconds: date.timestamp(),
+        nanos: 0,
+    })
+}
+
+/// # Policy Document Model
+///
+/// The policy document model is a published version of the terms of service or
+/// of the privacy policy. Versions are published by inserting a new document,
+/// the current version of a kind is the highest one already published.
+///
+/// ## Model
+///
+/// ```rust,no_run
+/// use kiro_client::{PolicyDocumentModel, PolicyKind};
+/// use kiro_database::{DbDateTime, DbId};
+///
+/// let document = PolicyDocumentModel {
+///     id: DbId::from(("policy_documents", "terms_2")),
+///     kind: PolicyKind::Terms,
+///     version: 2,
+///     url: "https://example.com/terms/2".to_string(),
+///     published_at: DbDateTime::now(),
+/// };
+///
+/// println!("📜 Policy document: {:?}", document);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyDocumentModel {
+    pub id: DbId,
+    pub kind: PolicyKind,
+    pub version: u32,
+    pub url: String,
+    pub published_at: DbDateTime,
+}
+
+impl HasId for PolicyDocumentModel {
+    type Id = DbId;
+    fn id(&self) -> &Self::Id {
+        &self.id
+    }
+}
+
+// WARNING: This is a default implementation for testing purposes only
+impl Default for PolicyDocumentModel {
+    fn default() -> Self {
+        Self {
+            id: DbId::from(("policy_documents", "terms_1")),
+            kind: PolicyKind::Terms,
+            version: 1,
+            url: "https://example.com/terms/1".to_string(),
+            published_at: DbDateTime::now(),
+        }
+    }
+}
+
+impl From<&PolicyDocumentModel> for PolicyDocument {
+    fn from(row: &PolicyDocumentModel) -> Self {
+        Self {
+            kind: row.kind.as_str().to_string(),
+            version: row.version,
+            url: row.url.clone(),
+            published_at: to_timestamp(&row.published_at),
+        }
+    }
+}
+
+/// # Policy Consent Model
+///
+/// The policy consent model records the acceptance of a policy document by a
+/// user, with the IP address the acceptance came from. The kind and version are
+/// copied from the document so the history reads without joining it.
+///
+/// ## Model
+///
+/// ```rust,no_run
+/// use kiro_client::{PolicyConsentModel, PolicyKind};
+/// use kiro_database::{DbDateTime, DbId};
+///
+/// let consent = PolicyConsentModel {
+///     id: DbId::from(("policy_consents", "consent")),
+///     user: DbId::from(("users", "user")),
+///     document: DbId::from(("policy_documents", "terms_2")),
+///     kind: PolicyKind::Terms,
+///     version: 2,
+///     ip_address: Some("127.0.0.1".to_string()),
+///     accepted_at: DbDateTime::now(),
+/// };
+///
+/// println!("✅ Policy consent: {:?}", consent);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyConsentModel {
+    pub id: DbId,
+    pub user: DbId,
+    pub document: DbId,
+    pub kind: PolicyKind,
+    pub version: u32,
+    pub ip_address: Option<String>,
+    pub accepted_at: DbDateTime,
+}
+
+impl HasId for PolicyConsentModel {
+    type Id = DbId;
+    fn id(&self) -> &Self::Id {
+        &self.id
+    }
+}
+
+// WARNING: This is a default implementation for testing purposes only
+impl Default for PolicyConsentModel {
+    fn default() -> Self {
+        Self {
+            id: DbId::from(("policy_consents", "consent")),
+            user: DbId::from(("users", "user")),
+            document: DbId::from(("policy_documents", "terms_1")),
+            kind: PolicyKind::Terms,
+            version: 1,
+            ip_address: Some("127.0.0.1".to_string()),
+            accepted_at: DbDateTime::now(),
+        }
+    }
+}
+
+impl From<&PolicyConsentModel> for Consent {
+    fn from(row: &PolicyConsentModel) -> Self {
+        Self {
+            kind: row.kind.as_str().to_string(),
+            version: row.version,
+            ip_address: row.ip_address.clone().unwrap_or_default(),
+            accepted_at: to_timestamp(&row.accepted_at),
+        }
+    }
+}
+
+/// # Create Policy Consent Model
+///
+/// The create policy consent model is used to record a new acceptance.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatePolicyConsentModel {
+    pub user: DbId,
+    pub document: DbId,
+    pub kind: PolicyKind,
+    pub version: u32,
+    pub ip_address: Option<String>,
+}
+
+impl PolicyDocumentModel {
+    /// Get current
+    ///
+    /// Retrieves the current version of each kind of document, the highest
+    /// version already published
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    ///
+    /// # Returns
+    /// * `Ok(Vec<PolicyDocumentModel>)` - Current documents, empty while none is published
+    /// * `Err(ClientError)` - Database error
+    pub async fn get_current<DB: DatabaseOperations + Send + Sync>(
+        db: &DB,
+    ) -> Result<Vec<Self>, ClientError> {
+        let documents = db
+            .read_where::<Self>(
+                "policy_documents",
+                Condition::le("published_at", Utc::now()),
+                Some(QueryOptions {
+                    order_by: Some("version".to_string()),
+                    order_direction: Some(OrderDirection::DESC),
+                    limit: None,
+                    start: None,
+                }),
+            )
+            .await
+            .map_err(ClientError::Database)?;
+
+        // Keep the first, highest, version of each kind
+        let mut current: Vec<Self> = Vec::new();
+        for document in documents {
+            if !current.iter().any(|known| known.kind == document.kind) {
+                current.push(document);
+            }
+        }
+
+        Ok(current)
+    }
+
+    /// Get pending
+    ///
+    /// Retrieves the current documents a user has not accepted yet
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user_id` - User identifier
+    ///
+    /// # Returns
+    /// * `Ok(Vec<PolicyDocumentModel>)` - Documents the user must accept
+    /// * `Err(ClientError)` - Database error
+    pub async fn get_pending<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user_id: DbId,
+    ) -> Result<Vec<Self>, ClientError> {
+        let current = Self::get_current(db).await?;
+        if current.is_empty() {
+            return Ok(current);
+        }
+
+        let consents = PolicyConsentModel::get_consents_by_user(db, user_id).await?;
+
+        Ok(current
+            .into_iter()
+            .filter(|document| {
+                !consents
+                    .iter()
+                    .any(|consent| consent.document == document.id)
+            })
+            .collect())
+    }
+}
+
+impl PolicyConsentModel {
+    /// Record consent
+    ///
+    /// Records the acceptance of a document by a user
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user_id` - User identifier
+    /// * `document` - Accepted document
+    /// * `ip_address` - IP address the acceptance came from
+    ///
+    /// # Returns
+    /// * `Ok(PolicyConsentModel)` - Recorded consent
+    /// * `Err(ClientError)` - Database error
+    pub async fn record<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user_id: DbId, document: &PolicyDocumentModel, ip_address: Option<String>,
+    ) -> Result<Self, ClientError> {
+        db.create::<CreatePolicyConsentModel, Self>(
+            "policy_consents",
+            CreatePolicyConsentModel {
+                user: user_id,
+                document: document.id.clone(),
+                kind: document.kind,
+                version: document.version,
+                ip_address,
+            },
+        )
+        .await
+        .map_err(ClientError::Database)?
+        .pop()
+        .ok_or(ClientError::DBOptionNone)
+    }
+
+    /// Record all
+    ///
+    /// Records the acceptance of several documents by a user, as done on
+    /// registration with the versions shown to the user
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user_id` - User identifier
+    /// * `documents` - Accepted documents
+    /// * `ip_address` - IP address the acceptance came from
+    ///
+    /// # Returns
+    /// * `Ok(Vec<PolicyConsentModel>)` - Recorded consents
+    /// * `Err(ClientError)` - Database error
+    pub async fn record_all<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user_id: DbId, documents: &[PolicyDocumentModel], ip_address: Option<String>,
+    ) -> Result<Vec<Self>, ClientError> {
+        let mut consents = Vec::new();
+
+        for document in documents {
+            consents.push(Self::record(db, user_id.clone(), document, ip_address.clone()).await?);
+        }
+
+        Ok(consents)
+    }
+
+    /// Get consents by user
+    ///
+    /// Retrieves the consent history of a user, most recent first
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user_id` - User identifier
+    ///
+    /// # Returns
+    /// * `Ok(Vec<PolicyConsentModel>)` - Consents of the user
+    /// * `Err(ClientError)` - Database error
+    pub async fn get_consents_by_user<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user_id: DbId,
+    ) -> Result<Vec<Self>, ClientError> {
+        db.read_by_field_thing::<Self>(
+            "policy_consents",
+            "user",
+            user_id,
+            Some(QueryOptions {
+                order_by: Some("accepted_at".to_string()),
+                order_direction: Some(OrderDirection::DESC),
+                limit: None,
+                start: None,
+            }),
+        )
+        .await
+        .map_err(ClientError::Database)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kiro_database::db_bridge::MockDatabaseOperations;
+
+    fn document(kind: PolicyKind, version: u32) -> PolicyDocumentModel {
+        PolicyDocumentModel {
+            id: DbId::from((
+                "policy_documents",
+                format!("{}_{}", kind.as_str(), version).as_str(),
+            )),
+            kind,
+            version,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_current_keeps_latest_version_of_each_kind() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(vec![
+                    document(PolicyKind::Terms, 3),
+                    document(PolicyKind::Privacy, 2),
+                    document(PolicyKind::Terms, 2),
+                    document(PolicyKind::Privacy, 1),
+                ])
+            });
+
+        let current = PolicyDocumentModel::get_current(&mock_db).await.unwrap();
+
+        assert_eq!(current.len(), 2);
+        assert_eq!(current[0].kind, PolicyKind::Terms);
+        assert_eq!(current[0].version, 3);
+        assert_eq!(current[1].kind, PolicyKind::Privacy);
+        assert_eq!(current[1].version, 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_pending_skips_accepted_documents() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(vec![
+                    document(PolicyKind::Terms, 2),
+                    document(PolicyKind::Privacy, 1),
+                ])
+            });
+
+        mock_db
+            .expect_read_by_field_thing::<PolicyConsentModel>()
+            .withf(|table, field, _, _| table == "policy_consents" && field == "user")
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(vec![
+                    PolicyConsentModel {
+                        document: document(PolicyKind::Privacy, 1).id,
+                        ..Default::default()
+                    },
+                    PolicyConsentModel {
+                        document: document(PolicyKind::Terms, 1).id,
+                        ..Default::default()
+                    },
+                ])
+            });
+
+        let pending = PolicyDocumentModel::get_pending(&mock_db, DbId::default())
+            .await
+            .unwrap();
+
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].kind, PolicyKind::Terms);
+        assert_eq!(pending[0].version, 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_pending_without_documents() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
+
+        let pending = PolicyDocumentModel::get_pending(&mock_db, DbId::default())
+            .await
+            .unwrap();
+
+        assert!(pending.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_record_all() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_create::<CreatePolicyConsentModel, PolicyConsentModel>()
+            .withf(|table, data| {
+                table == "policy_consents" && data.ip_address == Some("10.0.0.1".to_string())
+            })
+            .times(2)
+            .returning(|_, data| {
+                Ok(vec![PolicyConsentModel {
+                    user: data.user,
+                    document: data.document,
+                    kind: data.kind,
+                    version: data.version,
+                    ip_address: data.ip_address,
+                    ..Default::default()
+                }])
+            });
+
+        let consents = PolicyConsentModel::record_all(
+            &mock_db,
+            DbId::default(),
+            &[
+                document(PolicyKind::Terms, 2),
+                document(PolicyKind::Privacy, 1),
+            ],
+            Some("10.0.0.1".to_string()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(consents.len(), 2);
+        assert_eq!(consents[0].kind, PolicyKind::Terms);
+        assert_eq!(consents[0].version, 2);
+        assert_eq!(consents[1].kind, PolicyKind::Privacy);
+    }
+
+    #[test]
+    fn test_policy_kind_names() {
+        assert_eq!(PolicyKind::from_name("Terms"), Some(PolicyKind::Terms));
+        assert_eq!(PolicyKind::from_name("Privacy"), Some(PolicyKind::Privacy));
+        assert_eq!(PolicyKind::from_name("Cookies"), None);
+        assert_eq!(PolicyKind::Privacy.as_str(), "Privacy");
+    }
+}
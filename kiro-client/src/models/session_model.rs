@@ -23,6 +23,7 @@ use base64::{engine::general_purpose::URL_SAFE, Engine};
 use chrono::Utc;
 use kiro_database::{
     db_bridge::{DatabaseOperations, HasId},
+    db_query::Condition,
     DbDateTime, DbId,
 };
 #[cfg(feature = "mailer")]
@@ -36,10 +37,14 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "mailer")]
 use kiro_mailer::{ContentType, Mailer, MailerTrait};
 
-use crate::error::ClientError;
+#[cfg(feature = "legacy_hashes")]
+use crate::utils::legacy_hash::LegacyHash;
+use crate::{error::ClientError, utils::privacy::PrivacyPolicy};
 
 #[cfg(feature = "mailer")]
-use super::UserModel;
+use super::{NotificationCategory, UserModel};
+#[cfg(feature = "mailer")]
+use crate::utils::notification::send_notification;
 
 static ENCRYPTION_KEY: Lazy<[u8; 32]> = Lazy::new(|| {
     let mut key = [0u8; 32];
@@ -202,12 +207,13 @@ impl SessionModel {
 
     /// # Create session
     ///
-    /// The `create_session` method creates a session.
+    /// The `create_session` method creates a session, its IP address goes through
+    /// the privacy policy of the user.
     ///
     /// ## Example
     ///
     /// ```rust,no_run
-    /// use kiro_client::{SessionModel, CreateSessionModel};
+    /// use kiro_client::{PrivacyPolicy, SessionModel, CreateSessionModel, UserModel};
     /// use kiro_database::{DbId, db_bridge::{Database, MockDatabaseOperations}};
     ///
     /// // Mock database
@@ -222,15 +228,20 @@ impl SessionModel {
     /// // IP address
     /// let ip_address = "127.0.0.1".to_string();
     ///
+    /// // Privacy policy of the user
+    /// let policy = PrivacyPolicy::for_user(&UserModel::default());
+    ///
     /// // Async block to allow `await`
     /// tokio::runtime::Runtime::new().unwrap().block_on(async {
-    ///     let session = SessionModel::create_session(&db, user_id, is_admin, Some(ip_address)).await;
+    ///     let session =
+    ///         SessionModel::create_session(&db, user_id, is_admin, Some(ip_address), &policy)
+    ///             .await;
     ///
     ///     println!("🗝️ Session: {:?}", session);
     /// });
     /// ```
     pub async fn create_session<DB: DatabaseOperations + Send + Sync>(
-        db: &DB, user_id: DbId, is_admin: bool, ip_address: Option<String>,
+        db: &DB, user_id: DbId, is_admin: bool, ip_address: Option<String>, policy: &PrivacyPolicy,
     ) -> Result<SessionModel, ClientError> {
         let (session_key, _encrypted_user_id) =
             Self::generate_refresh_token(user_id.clone()).await?;
@@ -241,7 +252,7 @@ impl SessionModel {
                 session_key: session_key.clone(),
                 user_id,
                 is_admin,
-                ip_address,
+                ip_address: ip_address.map(|ip| policy.session_ip(&ip)),
             },
         )
         .await
@@ -298,12 +309,13 @@ impl SessionModel {
 
     /// # Get session by user id
     ///
-    /// The `get_token_by_user_id` method gets a token by user id.
+    /// The `get_token_by_user_id` method gets a token by user id. The IP address
+    /// goes through the privacy policy of the user before being compared or stored.
     ///
     /// ## Example
     ///
     /// ```rust,no_run
-    /// use kiro_client::SessionModel;
+    /// use kiro_client::{PrivacyPolicy, SessionModel, UserModel};
     /// use kiro_database::{DbId, db_bridge::{Database, MockDatabaseOperations}};
     ///
     /// // Mock database
@@ -315,16 +327,21 @@ impl SessionModel {
     /// // IP address
     /// let ip_address = "127.0.0.1".to_string();
     ///
+    /// // Privacy policy of the user
+    /// let policy = PrivacyPolicy::for_user(&UserModel::default());
+    ///
     /// // Async block to allow `await`
     /// tokio::runtime::Runtime::new().unwrap().block_on(async {
-    ///     let token = SessionModel::get_session_by_user_id(&db, user_id, ip_address).await;
+    ///     let token = SessionModel::get_session_by_user_id(&db, user_id, ip_address, &policy).await;
     ///
     ///     println!("🗝️ Token: {:?}", token);
     /// });
     /// ```
     pub async fn get_session_by_user_id<DB: DatabaseOperations + Send + Sync>(
-        db: &DB, user_id: DbId, ip_address: String,
+        db: &DB, user_id: DbId, ip_address: String, policy: &PrivacyPolicy,
     ) -> Result<SessionModel, ClientError> {
+        let ip_address = policy.session_ip(&ip_address);
+
         let res = db
             .read_by_field_thing::<SessionModel>("sessions", "user_id", user_id.clone(), None)
             .await
@@ -346,7 +363,14 @@ impl SessionModel {
                     .map_err(|e| DatabaseError::Internal(e.to_string()))?
                     .replace("${{CONNECTION_TYPE}}", "login")
                     .replace("${{CONNECTION_DATE}}", &chrono::Local::now().to_string())
-                    .replace("${{CONNECTION_IP}}", &ip_address);
+                    .replace(
+                        "${{CONNECTION_IP}}",
+                        if policy.allows_location() {
+                            &ip_address
+                        } else {
+                            "Hidden by your privacy settings"
+                        },
+                    );
 
                 let from = get_env_or("SMTP_USER", "contact@test.com");
                 let to = user.email.clone();
@@ -359,7 +383,13 @@ impl SessionModel {
                     template,
                 )?;
 
-                Mailer::new().send_mail(message).await.map(|_| ())?;
+                send_notification(
+                    &Mailer::new(),
+                    &user,
+                    NotificationCategory::Security,
+                    message,
+                )
+                .await?;
             }
 
             if Self::is_expired(&existing_session.expires_at) {
@@ -375,6 +405,7 @@ impl SessionModel {
                     user_id,
                     existing_session.is_admin,
                     Some(ip_address),
+                    policy,
                 )
                 .await;
             }
@@ -384,7 +415,7 @@ impl SessionModel {
             Ok(existing_session.clone())
         } else {
             // Create new session with IP address
-            Self::create_session(db, user_id, false, Some(ip_address)).await
+            Self::create_session(db, user_id, false, Some(ip_address), policy).await
         }
     }
 
@@ -531,7 +562,8 @@ impl SessionModel {
     ///
     /// The `verify_password` method verifies a password.
     ///
-    /// This method use Argon2 to verify the password.
+    /// This method use Argon2 to verify the password. With the `legacy_hashes`
+    /// feature, bcrypt and PBKDF2 hashes imported from other systems are verified too.
     ///
     /// ## Example
     ///
@@ -554,6 +586,11 @@ impl SessionModel {
     pub async fn verify_password(
         password: String, password_hash: String,
     ) -> Result<bool, ClientError> {
+        #[cfg(feature = "legacy_hashes")]
+        if let Some(scheme) = LegacyHash::detect(&password_hash) {
+            return scheme.verify(&password, &password_hash);
+        }
+
         let argon2 = Argon2::default();
 
         let hash = PasswordHash::new(&password_hash).map_err(|_| ClientError::PasswordIncorrect)?;
@@ -585,7 +622,7 @@ impl SessionModel {
     pub async fn destroy_all_sessions<DB: DatabaseOperations + Send + Sync>(
         db: &DB,
     ) -> Result<(), ClientError> {
-        db.query::<SessionModel>("DELETE sessions;", None)
+        db.delete_where("sessions", Condition::All)
             .await
             .map_err(ClientError::Database)
             .map(|_| ())
@@ -607,6 +644,14 @@ mod tests {
         Language, NotificationSettings, PrivacySettings, SecuritySettings, Theme, UserSettings,
     };
 
+    /// Policy of a user consenting to everything, keeping IP addresses as is
+    fn full_consent() -> PrivacyPolicy {
+        PrivacyPolicy::new(&crate::PrivacySettings {
+            data_collection: true,
+            location: true,
+        })
+    }
+
     #[tokio::test]
     async fn test_create_session_success() {
         let mut mock_db = MockDatabaseOperations::new();
@@ -625,6 +670,7 @@ mod tests {
             test_session.user_id.clone(),
             false,
             Some("127.0.0.1".to_string()),
+            &full_consent(),
         )
         .await;
 
@@ -799,10 +845,10 @@ mod tests {
         let mut mock_db = MockDatabaseOperations::new();
 
         mock_db
-            .expect_query::<SessionModel>()
-            .withf(|query: &str, _| query == "DELETE sessions;")
+            .expect_delete_where()
+            .withf(|table, condition| table == "sessions" && *condition == Condition::All)
             .times(1)
-            .returning(|_, _| Ok(vec![]));
+            .returning(|_, _| Ok(3));
 
         let result = SessionModel::destroy_all_sessions(&mock_db).await;
         assert!(result.is_ok());
@@ -837,6 +883,7 @@ mod tests {
             &mock_db,
             test_user_id.clone(),
             "127.0.0.1".to_string(),
+            &full_consent(),
         )
         .await;
 
@@ -928,9 +975,13 @@ mod tests {
         test_session.ip_address = Some("192.168.1.1".to_string());
         let test_user_id = test_session.user_id.clone();
 
-        let result =
-            SessionModel::get_session_by_user_id(&mock_db, test_user_id, "127.0.0.1".to_string())
-                .await;
+        let result = SessionModel::get_session_by_user_id(
+            &mock_db,
+            test_user_id,
+            "127.0.0.1".to_string(),
+            &full_consent(),
+        )
+        .await;
 
         assert!(result.is_ok());
     }
@@ -956,9 +1007,13 @@ mod tests {
             .times(1)
             .returning(|_| Ok(Some(())));
 
-        let result =
-            SessionModel::get_session_by_user_id(&mock_db, test_user_id, "127.0.0.1".to_string())
-                .await;
+        let result = SessionModel::get_session_by_user_id(
+            &mock_db,
+            test_user_id,
+            "127.0.0.1".to_string(),
+            &full_consent(),
+        )
+        .await;
 
         assert!(matches!(result, Err(ClientError::Expired)));
     }
@@ -989,9 +1044,13 @@ mod tests {
 
         let test_user_id = DbId::default();
 
-        let result =
-            SessionModel::get_session_by_user_id(&mock_db, test_user_id, "127.0.0.1".to_string())
-                .await;
+        let result = SessionModel::get_session_by_user_id(
+            &mock_db,
+            test_user_id,
+            "127.0.0.1".to_string(),
+            &full_consent(),
+        )
+        .await;
 
         assert!(result.is_ok());
     }
@@ -0,0 +1,749 @@
+// models/user_email_model.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use kiro_api::client::v1::UserEmail;
+use kiro_database::{
+    db_bridge::{DatabaseOperations, HasId, OrderDirection, QueryOptions},
+    db_query::{Condition, FieldUpdate, Update},
+    DbDateTime, DbId,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{error::ClientError, UserModel};
+
+/// # User Email Model
+///
+/// The user email model is an email address attached to an account besides its
+/// primary one. Once verified, the address can be used to log in and to recover
+/// the account, and can be promoted to primary. The primary address stays on
+/// the `UserModel`, the row of the address promoted last is flagged `is_primary`.
+///
+/// ## Model
+///
+/// ```rust,no_run
+/// use kiro_client::UserEmailModel;
+/// use kiro_database::{DbDateTime, DbId};
+///
+/// let address = UserEmailModel {
+///     id: DbId::from(("user_emails", "address")),
+///     user: DbId::from(("users", "user")),
+///     email: "backup@example.com".to_string(),
+///     verified: true,
+///     is_primary: false,
+///     link: None,
+///     verified_at: Some(DbDateTime::now()),
+///     created_at: DbDateTime::now(),
+/// };
+///
+/// println!("📧 Address: {:?}", address);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserEmailModel {
+    pub id: DbId,
+    pub user: DbId,
+    pub email: String,
+    pub verified: bool,
+    pub is_primary: bool,
+    /// Verification link sent to the address, until it is verified
+    pub link: Option<DbId>,
+    pub verified_at: Option<DbDateTime>,
+    pub created_at: DbDateTime,
+}
+
+impl HasId for UserEmailModel {
+    type Id = DbId;
+    fn id(&self) -> &Self::Id {
+        &self.id
+    }
+}
+
+// WARNING: This is a default implementation for testing purposes only
+impl Default for UserEmailModel {
+    fn default() -> Self {
+        Self {
+            id: DbId::from(("user_emails", "address")),
+            user: DbId::default(),
+            email: "backup@example.com".to_string(),
+            verified: false,
+            is_primary: false,
+            link: None,
+            verified_at: None,
+            created_at: DbDateTime::now(),
+        }
+    }
+}
+
+impl From<&UserEmailModel> for UserEmail {
+    fn from(row: &UserEmailModel) -> Self {
+        Self {
+            id: row.id.id.to_string(),
+            email: row.email.clone(),
+            verified: row.verified,
+            primary: row.is_primary,
+        }
+    }
+}
+
+/// # Create User Email Model
+///
+/// The create user email model is used to attach an address to an account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateUserEmailModel {
+    pub user: DbId,
+    pub email: String,
+    pub verified: bool,
+    pub is_primary: bool,
+}
+
+impl UserEmailModel {
+    /// Get addresses by user
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user_id` - Identifier of the user
+    ///
+    /// # Returns
+    /// * `Ok(Vec<UserEmailModel>)` - Addresses of the user, oldest first
+    /// * `Err(ClientError)` - Database error
+    pub async fn get_by_user<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user_id: DbId,
+    ) -> Result<Vec<Self>, ClientError> {
+        db.read_by_field_thing::<Self>(
+            "user_emails",
+            "user",
+            user_id,
+            Some(QueryOptions {
+                order_by: Some("created_at".to_string()),
+                order_direction: Some(OrderDirection::ASC),
+                limit: None,
+                start: None,
+            }),
+        )
+        .await
+        .map_err(ClientError::Database)
+    }
+
+    /// List addresses of a user
+    ///
+    /// The primary address comes first. Accounts whose primary address was never
+    /// promoted from a secondary one have no row for it, the entry is synthesized.
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user` - Owner of the addresses
+    ///
+    /// # Returns
+    /// * `Ok(Vec<UserEmail>)` - Addresses of the user
+    /// * `Err(ClientError)` - Database error
+    pub async fn list<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user: &UserModel,
+    ) -> Result<Vec<UserEmail>, ClientError> {
+        let rows = Self::get_by_user(db, user.id.clone()).await?;
+
+        let primary = rows
+            .iter()
+            .find(|row| row.email == user.email)
+            .map(|row| UserEmail {
+                primary: true,
+                ..UserEmail::from(row)
+            })
+            .unwrap_or_else(|| UserEmail {
+                id: String::new(),
+                email: user.email.clone(),
+                verified: true,
+                primary: true,
+            });
+
+        Ok(std::iter::once(primary)
+            .chain(
+                rows.iter()
+                    .filter(|row| row.email != user.email)
+                    .map(|row| UserEmail {
+                        primary: false,
+                        ..UserEmail::from(row)
+                    }),
+            )
+            .collect())
+    }
+
+    /// Get address of a user
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user_id` - Identifier of the user
+    /// * `id` - Identifier of the address
+    ///
+    /// # Returns
+    /// * `Ok(UserEmailModel)` - Address of the user
+    /// * `Err(ClientError::EmailNotFound)` - Unknown address, or address of another user
+    /// * `Err(ClientError)` - Database error
+    pub async fn get_for_user<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user_id: &DbId, id: DbId,
+    ) -> Result<Self, ClientError> {
+        db.select::<Self>(id)
+            .await
+            .map_err(ClientError::Database)?
+            .filter(|row| row.user == *user_id)
+            .ok_or(ClientError::EmailNotFound)
+    }
+
+    /// Get verified address
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `email` - Email address to search for
+    ///
+    /// # Returns
+    /// * `Ok(Some(UserEmailModel))` - Verified address
+    /// * `Ok(None)` - No account verified the address
+    /// * `Err(ClientError)` - Database error
+    pub async fn get_verified_by_email<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, email: &str,
+    ) -> Result<Option<Self>, ClientError> {
+        Ok(db
+            .read_by_field::<Self>("user_emails", "email", email, None)
+            .await
+            .map_err(ClientError::Database)?
+            .into_iter()
+            .find(|row| row.verified))
+    }
+
+    /// Add address
+    ///
+    /// Attaches an unverified address to an account
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user` - Owner of the address
+    /// * `email` - Address to attach
+    ///
+    /// # Returns
+    /// * `Ok(UserEmailModel)` - Attached address
+    /// * `Err(ClientError::EmailTaken)` - The address is already attached or in use
+    /// * `Err(ClientError)` - Database error
+    pub async fn add<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user: &UserModel, email: &str,
+    ) -> Result<Self, ClientError> {
+        if user.email.eq_ignore_ascii_case(email)
+            || Self::get_by_user(db, user.id.clone())
+                .await?
+                .iter()
+                .any(|row| row.email.eq_ignore_ascii_case(email))
+        {
+            return Err(ClientError::EmailTaken);
+        }
+
+        if !UserModel::check_email(db, email.to_string()).await? {
+            return Err(ClientError::EmailTaken);
+        }
+
+        db.create::<CreateUserEmailModel, Self>(
+            "user_emails",
+            CreateUserEmailModel {
+                user: user.id.clone(),
+                email: email.to_string(),
+                verified: false,
+                is_primary: false,
+            },
+        )
+        .await
+        .map_err(ClientError::Database)?
+        .pop()
+        .ok_or(ClientError::DBOptionNone)
+    }
+
+    /// Set verification link
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `link` - Verification link sent to the address
+    pub async fn set_link<DB: DatabaseOperations + Send + Sync>(
+        &self, db: &DB, link: DbId,
+    ) -> Result<(), ClientError> {
+        db.update_field(self.id.clone(), "link", Some(link))
+            .await
+            .map_err(ClientError::Database)
+    }
+
+    /// Verify address
+    ///
+    /// Marks the address a verification link was sent to as verified. The
+    /// availability of the address is checked again, a taken address is detached.
+    /// Verified addresses are unique in the schema, an address verified by
+    /// another account in the meantime fails the update and is detached too.
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `link_id` - Verification link
+    ///
+    /// # Returns
+    /// * `Ok(UserEmailModel)` - Verified address
+    /// * `Err(ClientError::EmailNotFound)` - The address was removed
+    /// * `Err(ClientError::EmailTaken)` - The address was verified by another account
+    /// * `Err(ClientError)` - Database error
+    pub async fn verify<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, link_id: DbId,
+    ) -> Result<Self, ClientError> {
+        let row = db
+            .read_where::<Self>(
+                "user_emails",
+                Condition::eq("link", link_id),
+                Some(QueryOptions {
+                    order_by: None,
+                    order_direction: None,
+                    limit: Some(1),
+                    start: None,
+                }),
+            )
+            .await
+            .map_err(ClientError::Database)?
+            .pop()
+            .ok_or(ClientError::EmailNotFound)?;
+
+        if !UserModel::check_email(db, row.email.clone()).await? {
+            return Err(Self::detach_taken(db, row.id).await);
+        }
+
+        let verified = db
+            .update_where::<Self>(Update::record(
+                row.id.clone(),
+                vec![
+                    FieldUpdate::set("verified", true),
+                    FieldUpdate::now("verified_at"),
+                    FieldUpdate::unset("link"),
+                ],
+            ))
+            .await;
+
+        match verified {
+            Ok(rows) => rows.into_iter().next().ok_or(ClientError::EmailNotFound),
+            // Verified addresses are unique, the address was verified by another
+            // account since the check
+            Err(e) => match UserModel::check_email(db, row.email).await {
+                Ok(false) => Err(Self::detach_taken(db, row.id).await),
+                _ => Err(ClientError::Database(e)),
+            },
+        }
+    }
+
+    /// Detaches an address verified by another account
+    async fn detach_taken<DB: DatabaseOperations + Send + Sync>(db: &DB, id: DbId) -> ClientError {
+        match db.delete(id).await {
+            Ok(_) => ClientError::EmailTaken,
+            Err(e) => ClientError::Database(e),
+        }
+    }
+
+    /// Remove address
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user` - Owner of the address
+    /// * `id` - Identifier of the address
+    ///
+    /// # Returns
+    /// * `Ok(())` - Address detached
+    /// * `Err(ClientError::EmailNotFound)` - Unknown address, or address of another user
+    /// * `Err(ClientError::PrimaryEmail)` - The address is the primary one
+    /// * `Err(ClientError)` - Database error
+    pub async fn remove<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user: &UserModel, id: DbId,
+    ) -> Result<(), ClientError> {
+        let row = Self::get_for_user(db, &user.id, id).await?;
+
+        if row.is_primary || row.email == user.email {
+            return Err(ClientError::PrimaryEmail);
+        }
+
+        db.delete(row.id).await.map_err(ClientError::Database)?;
+
+        Ok(())
+    }
+
+    /// Promote address
+    ///
+    /// Makes a verified address the primary one. The previous primary address
+    /// stays attached to the account as a verified address.
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user` - Owner of the address
+    /// * `id` - Identifier of the address
+    ///
+    /// # Returns
+    /// * `Ok(UserModel)` - User with its new primary address
+    /// * `Err(ClientError::EmailNotFound)` - Unknown address, or address of another user
+    /// * `Err(ClientError::EmailNotVerified)` - The address is not verified
+    /// * `Err(ClientError)` - Database error
+    pub async fn promote<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user: &UserModel, id: DbId,
+    ) -> Result<UserModel, ClientError> {
+        let row = Self::get_for_user(db, &user.id, id).await?;
+
+        if !row.verified {
+            return Err(ClientError::EmailNotVerified);
+        }
+
+        if row.email == user.email {
+            return Ok(user.clone());
+        }
+
+        // Keep the current primary address as a verified one
+        let rows = Self::get_by_user(db, user.id.clone()).await?;
+        if !rows.iter().any(|other| other.email == user.email) {
+            db.create::<CreateUserEmailModel, Self>(
+                "user_emails",
+                CreateUserEmailModel {
+                    user: user.id.clone(),
+                    email: user.email.clone(),
+                    verified: true,
+                    is_primary: false,
+                },
+            )
+            .await
+            .map_err(ClientError::Database)?;
+        }
+
+        let promote = Update::record(
+            user.id.clone(),
+            vec![
+                FieldUpdate::set("email", row.email),
+                FieldUpdate::now("updated_at"),
+            ],
+        );
+        let demote_others = Update::new(
+            "user_emails",
+            Condition::And(vec![
+                Condition::eq("user", user.id.clone()),
+                Condition::eq("is_primary", true),
+                Condition::ne("id", row.id.clone()),
+            ]),
+            vec![FieldUpdate::set("is_primary", false)],
+        );
+        let promote_address = Update::record(row.id, vec![FieldUpdate::set("is_primary", true)]);
+
+        db.update_chain::<UserModel>(promote, vec![demote_others, promote_address])
+            .await
+            .map_err(ClientError::Database)?
+            .pop()
+            .ok_or(ClientError::DBOptionNone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
+    use mockall::predicate::{always, eq};
+
+    fn address(verified: bool) -> UserEmailModel {
+        UserEmailModel {
+            verified,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_into_proto() {
+        let row = UserEmailModel {
+            verified: true,
+            ..Default::default()
+        };
+
+        let email = UserEmail::from(&row);
+        assert_eq!(email.id, "address");
+        assert_eq!(email.email, "backup@example.com");
+        assert!(email.verified);
+        assert!(!email.primary);
+    }
+
+    #[tokio::test]
+    async fn test_list_synthesizes_primary() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field_thing::<UserEmailModel>()
+            .with(eq("user_emails"), eq("user"), eq(DbId::default()), always())
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![address(true)]));
+
+        let emails = UserEmailModel::list(&mock_db, &UserModel::default())
+            .await
+            .unwrap();
+        assert_eq!(emails.len(), 2);
+        assert_eq!(emails[0].email, "test@example.com");
+        assert!(emails[0].primary && emails[0].id.is_empty());
+        assert_eq!(emails[1].email, "backup@example.com");
+        assert!(!emails[1].primary);
+    }
+
+    #[tokio::test]
+    async fn test_add_taken_by_another_account() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field_thing::<UserEmailModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .with(eq("users"), eq("email"), eq("taken@example.com"), eq(None))
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![UserModel::default()]));
+
+        mock_db
+            .expect_create::<CreateUserEmailModel, UserEmailModel>()
+            .never();
+
+        let result =
+            UserEmailModel::add(&mock_db, &UserModel::default(), "taken@example.com").await;
+        assert!(matches!(result, Err(ClientError::EmailTaken)));
+    }
+
+    #[tokio::test]
+    async fn test_add_own_primary() {
+        let mock_db = MockDatabaseOperations::new();
+
+        let result = UserEmailModel::add(&mock_db, &UserModel::default(), "Test@Example.com").await;
+        assert!(matches!(result, Err(ClientError::EmailTaken)));
+    }
+
+    #[tokio::test]
+    async fn test_get_for_user_of_another_user() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserEmailModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(UserEmailModel {
+                    user: DbId::from(("users", "other")),
+                    ..Default::default()
+                }))
+            });
+
+        let result = UserEmailModel::get_for_user(
+            &mock_db,
+            &DbId::default(),
+            DbId::from(("user_emails", "address")),
+        )
+        .await;
+        assert!(matches!(result, Err(ClientError::EmailNotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_remove_primary() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserEmailModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(UserEmailModel {
+                    is_primary: true,
+                    ..Default::default()
+                }))
+            });
+
+        mock_db.expect_delete().never();
+
+        let result = UserEmailModel::remove(
+            &mock_db,
+            &UserModel::default(),
+            DbId::from(("user_emails", "address")),
+        )
+        .await;
+        assert!(matches!(result, Err(ClientError::PrimaryEmail)));
+    }
+
+    #[tokio::test]
+    async fn test_promote_unverified() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserEmailModel>()
+            .times(1)
+            .returning(|_| Ok(Some(address(false))));
+
+        let result = UserEmailModel::promote(
+            &mock_db,
+            &UserModel::default(),
+            DbId::from(("user_emails", "address")),
+        )
+        .await;
+        assert!(matches!(result, Err(ClientError::EmailNotVerified)));
+    }
+
+    #[tokio::test]
+    async fn test_promote_keeps_previous_primary() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserEmailModel>()
+            .times(1)
+            .returning(|_| Ok(Some(address(true))));
+
+        mock_db
+            .expect_read_by_field_thing::<UserEmailModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![address(true)]));
+
+        mock_db
+            .expect_create::<CreateUserEmailModel, UserEmailModel>()
+            .withf(|table, data| {
+                table == "user_emails"
+                    && data.email == "test@example.com"
+                    && data.verified
+                    && !data.is_primary
+            })
+            .times(1)
+            .returning(|_, _| Ok(vec![UserEmailModel::default()]));
+
+        mock_db
+            .expect_update_chain::<UserModel>()
+            .withf(|promote, then| {
+                promote.changes[0] == FieldUpdate::set("email", "backup@example.com")
+                    && then.len() == 2
+                    && then[1].condition
+                        == Condition::eq("id", DbId::from(("user_emails", "address")))
+            })
+            .times(1)
+            .returning(|_, _| {
+                Ok(vec![UserModel {
+                    email: "backup@example.com".to_string(),
+                    ..Default::default()
+                }])
+            });
+
+        let user = UserEmailModel::promote(
+            &mock_db,
+            &UserModel::default(),
+            DbId::from(("user_emails", "address")),
+        )
+        .await
+        .unwrap();
+        assert_eq!(user.email, "backup@example.com");
+    }
+
+    #[tokio::test]
+    async fn test_verify_taken_in_the_meantime() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<UserEmailModel>()
+            .withf(|table, condition, _| {
+                table == "user_emails"
+                    && *condition == Condition::eq("link", DbId::from(("links", "link")))
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(vec![address(false)]));
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![UserModel::default()]));
+
+        mock_db
+            .expect_delete()
+            .with(eq(DbId::from(("user_emails", "address"))))
+            .times(1)
+            .returning(|_| Ok(Some(())));
+
+        let result = UserEmailModel::verify(&mock_db, DbId::from(("links", "link"))).await;
+        assert!(matches!(result, Err(ClientError::EmailTaken)));
+    }
+
+    #[tokio::test]
+    async fn test_verify() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<UserEmailModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![address(false)]));
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        mock_db
+            .expect_read_by_field::<UserEmailModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        mock_db
+            .expect_update_where::<UserEmailModel>()
+            .withf(|update| {
+                update.condition == Condition::eq("id", DbId::from(("user_emails", "address")))
+                    && update.changes[0] == FieldUpdate::set("verified", true)
+            })
+            .times(1)
+            .returning(|_| Ok(vec![address(true)]));
+
+        let row = UserEmailModel::verify(&mock_db, DbId::from(("links", "link")))
+            .await
+            .unwrap();
+        assert!(row.verified);
+    }
+
+    #[tokio::test]
+    async fn test_verify_conflicting_update() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<UserEmailModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![address(false)]));
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .times(2)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        // Free during the check, verified by another account before the update
+        let mut verified = vec![vec![], vec![address(true)]].into_iter();
+        mock_db
+            .expect_read_by_field::<UserEmailModel>()
+            .times(2)
+            .returning(move |_, _, _, _| Ok(verified.next().unwrap()));
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserEmailModel>()
+            .times(1)
+            .returning(|_| {
+                Err(DatabaseError::Internal(
+                    "Duplicate verified_email".to_string(),
+                ))
+            });
+
+        mock_db
+            .expect_delete()
+            .with(eq(DbId::from(("user_emails", "address"))))
+            .times(1)
+            .returning(|_| Ok(Some(())));
+
+        let result = UserEmailModel::verify(&mock_db, DbId::from(("links", "link"))).await;
+        assert!(matches!(result, Err(ClientError::EmailTaken)));
+    }
+}
@@ -0,0 +1,151 @@
+// models/user_export_model.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use kiro_database::{
+    db_bridge::{DatabaseOperations, HasId},
+    DbDateTime, DbId,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{error::ClientError, utils::user_import::TransferFormat};
+
+/// # User Export Model
+///
+/// The user export model records a bulk export of the users, along with the
+/// user who requested it. The actor is empty when the export comes from the
+/// command line.
+///
+/// ## Model
+///
+/// ```rust,no_run
+/// use kiro_client::UserExportModel;
+/// use kiro_database::{DbDateTime, DbId};
+///
+/// let export = UserExportModel {
+///     id: DbId::from(("user_exports", "export")),
+///     actor: Some(DbId::from(("users", "user"))),
+///     format: "csv".to_string(),
+///     password_hashes: false,
+///     created_at: DbDateTime::now(),
+/// };
+///
+/// println!("📝 User export: {:?}", export);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserExportModel {
+    pub id: DbId,
+    pub actor: Option<DbId>,
+    pub format: String,
+    pub password_hashes: bool,
+    pub created_at: DbDateTime,
+}
+
+impl HasId for UserExportModel {
+    type Id = DbId;
+    fn id(&self) -> &Self::Id {
+        &self.id
+    }
+}
+
+// WARNING: This is a default implementation for testing purposes only
+impl Default for UserExportModel {
+    fn default() -> Self {
+        Self {
+            id: DbId::from(("user_exports", "export")),
+            actor: None,
+            format: "csv".to_string(),
+            password_hashes: false,
+            created_at: DbDateTime::now(),
+        }
+    }
+}
+
+/// # Create User Export Model
+///
+/// The create user export model is used to record a new user export.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateUserExportModel {
+    pub actor: Option<DbId>,
+    pub format: String,
+    pub password_hashes: bool,
+}
+
+impl UserExportModel {
+    /// Record export
+    ///
+    /// Records a bulk export of the users, before any user is read
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `actor` - User who requested the export, if any
+    /// * `format` - Format of the export
+    /// * `password_hashes` - Whether the password hashes are exported
+    ///
+    /// # Returns
+    /// * `Ok(UserExportModel)` - Recorded export
+    /// * `Err(ClientError)` - Database error
+    pub async fn record<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, actor: Option<DbId>, format: TransferFormat, password_hashes: bool,
+    ) -> Result<Self, ClientError> {
+        db.create::<CreateUserExportModel, Self>(
+            "user_exports",
+            CreateUserExportModel {
+                actor,
+                format: format.as_str().to_string(),
+                password_hashes,
+            },
+        )
+        .await
+        .map_err(ClientError::Database)?
+        .pop()
+        .ok_or(ClientError::DBOptionNone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kiro_database::db_bridge::MockDatabaseOperations;
+
+    #[tokio::test]
+    async fn test_record_export() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let actor = DbId::from(("users", "admin"));
+
+        mock_db
+            .expect_create::<CreateUserExportModel, UserExportModel>()
+            .withf(|table, data| {
+                table == "user_exports" && data.format == "jsonl" && data.password_hashes
+            })
+            .times(1)
+            .returning(|_, data| {
+                Ok(vec![UserExportModel {
+                    actor: data.actor,
+                    format: data.format,
+                    password_hashes: data.password_hashes,
+                    ..Default::default()
+                }])
+            });
+
+        let export =
+            UserExportModel::record(&mock_db, Some(actor.clone()), TransferFormat::Jsonl, true)
+                .await
+                .unwrap();
+
+        assert_eq!(export.actor, Some(actor));
+        assert!(export.password_hashes);
+    }
+}
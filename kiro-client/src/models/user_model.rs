@@ -15,16 +15,31 @@
 // limitations under the License.
 
 use argon2::{password_hash::SaltString, Argon2, PasswordHasher};
-use chrono::Utc;
-use kiro_api::client::v1::{Notifications, Privacy, Security, Settings, User};
+use chrono::{DateTime, Duration, Timelike, Utc};
+use chrono_tz::Tz;
+use kiro_api::client::v1::{
+    NotificationChannels, Notifications, Privacy, QuietHours, Security, Settings,
+    UpdateProfileRequest, User,
+};
 use kiro_database::{
     db_bridge::{DatabaseOperations, HasId},
-    DbDateTime, DbId,
+    db_query::{Condition, DbValue, FieldUpdate, Update},
+    get_env_or, DbDateTime, DbId,
 };
 use rand_core::OsRng;
 use serde::{Deserialize, Serialize};
 
-use crate::error::ClientError;
+use crate::{
+    error::ClientError,
+    models::{AccountEventKind, AccountEventModel, UserEmailModel},
+    utils::{
+        privacy::{enforce_session_privacy, PrivacyPolicy},
+        profile::{
+            normalize_handle, valid_bio, valid_display_name, valid_handle, valid_locale,
+            valid_timezone,
+        },
+    },
+};
 
 /// Represents available language options for user interface
 ///
@@ -44,6 +59,64 @@ pub enum Language {
     Arabic = 9,
 }
 
+impl Language {
+    /// Returns the ISO 639-1 code of the language
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+            Language::French => "fr",
+            Language::German => "de",
+            Language::Italian => "it",
+            Language::Japanese => "ja",
+            Language::Korean => "ko",
+            Language::Chinese => "zh",
+            Language::Russian => "ru",
+            Language::Arabic => "ar",
+        }
+    }
+
+    /// Returns the language of an ISO 639-1 code, if supported
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.to_ascii_lowercase().as_str() {
+            "en" => Some(Language::English),
+            "es" => Some(Language::Spanish),
+            "fr" => Some(Language::French),
+            "de" => Some(Language::German),
+            "it" => Some(Language::Italian),
+            "ja" => Some(Language::Japanese),
+            "ko" => Some(Language::Korean),
+            "zh" => Some(Language::Chinese),
+            "ru" => Some(Language::Russian),
+            "ar" => Some(Language::Arabic),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<i32> for Language {
+    type Error = ClientError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Language::English),
+            1 => Ok(Language::Spanish),
+            2 => Ok(Language::French),
+            3 => Ok(Language::German),
+            4 => Ok(Language::Italian),
+            5 => Ok(Language::Japanese),
+            6 => Ok(Language::Korean),
+            7 => Ok(Language::Chinese),
+            8 => Ok(Language::Russian),
+            9 => Ok(Language::Arabic),
+            _ => Err(ClientError::InvalidSettings(format!(
+                "Invalid language value: {}",
+                value
+            ))),
+        }
+    }
+}
+
 /// Represents the user's theme preference for the application interface
 ///
 /// Provides options for light mode, dark mode, or system-based theme selection
@@ -54,20 +127,195 @@ pub enum Theme {
     System = 2,
 }
 
+impl TryFrom<i32> for Theme {
+    type Error = ClientError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Theme::Light),
+            1 => Ok(Theme::Dark),
+            2 => Ok(Theme::System),
+            _ => Err(ClientError::InvalidSettings(format!(
+                "Invalid theme value: {}",
+                value
+            ))),
+        }
+    }
+}
+
+/// # Notification category
+///
+/// The kind of information a notification carries. Users choose the channels of
+/// each category, except for the mandatory ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationCategory {
+    /// Sign-ins, password and email changes, account recovery
+    Security,
+    /// Account lifecycle, e.g. deletion
+    Account,
+    /// Invoices and payments
+    Billing,
+    /// Product updates
+    Product,
+    /// Offers and newsletters
+    Marketing,
+}
+
+impl NotificationCategory {
+    /// Returns true for the categories sent whatever the preferences of the user
+    pub fn is_mandatory(self) -> bool {
+        matches!(self, NotificationCategory::Security)
+    }
+}
+
+/// # Notification channel
+///
+/// The ways a notification can be delivered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationChannel {
+    Email,
+    Push,
+    Sms,
+}
+
+/// # Represents the channels enabled for a notification category
+///
+/// # Model
+///
+/// ```rust,no_run
+/// use kiro_client::ChannelSettings;
+///
+/// let channels = ChannelSettings {
+///     email: true,
+///     push: false,
+///     sms: false,
+/// };
+///
+/// println!("{:?}", channels);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChannelSettings {
+    pub email: bool,
+    pub push: bool,
+    pub sms: bool,
+}
+
+impl ChannelSettings {
+    /// Every channel enabled
+    pub fn all() -> Self {
+        Self {
+            email: true,
+            push: true,
+            sms: true,
+        }
+    }
+
+    /// Only emails enabled
+    pub fn email_only() -> Self {
+        Self {
+            email: true,
+            ..Default::default()
+        }
+    }
+
+    /// Returns true if the channel is enabled
+    pub fn allows(&self, channel: NotificationChannel) -> bool {
+        match channel {
+            NotificationChannel::Email => self.email,
+            NotificationChannel::Push => self.push,
+            NotificationChannel::Sms => self.sms,
+        }
+    }
+}
+
+impl From<&ChannelSettings> for NotificationChannels {
+    fn from(row: &ChannelSettings) -> Self {
+        Self {
+            email: row.email,
+            push: row.push,
+            sms: row.sms,
+        }
+    }
+}
+
+impl From<&NotificationChannels> for ChannelSettings {
+    fn from(channels: &NotificationChannels) -> Self {
+        Self {
+            email: channels.email,
+            push: channels.push,
+            sms: channels.sms,
+        }
+    }
+}
+
+/// # Represents the quiet hours of a user
+///
+/// Minutes since midnight, in the timezone of the user. The range wraps around
+/// midnight when `end_minute` is before `start_minute`, and is empty when both
+/// are equal.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct QuietHoursSettings {
+    pub start_minute: u32,
+    pub end_minute: u32,
+}
+
+impl QuietHoursSettings {
+    /// Returns true if the minute of the day is within the quiet hours
+    pub fn contains(&self, minute: u32) -> bool {
+        if self.start_minute <= self.end_minute {
+            self.start_minute <= minute && minute < self.end_minute
+        } else {
+            minute >= self.start_minute || minute < self.end_minute
+        }
+    }
+}
+
+impl From<&QuietHoursSettings> for QuietHours {
+    fn from(row: &QuietHoursSettings) -> Self {
+        Self {
+            start_minute: row.start_minute,
+            end_minute: row.end_minute,
+        }
+    }
+}
+
+impl TryFrom<&QuietHours> for QuietHoursSettings {
+    type Error = ClientError;
+
+    fn try_from(quiet_hours: &QuietHours) -> Result<Self, Self::Error> {
+        const MINUTES_PER_DAY: u32 = 24 * 60;
+
+        if quiet_hours.start_minute >= MINUTES_PER_DAY || quiet_hours.end_minute >= MINUTES_PER_DAY
+        {
+            return Err(ClientError::InvalidSettings(
+                "Quiet hours must be minutes of the day".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            start_minute: quiet_hours.start_minute,
+            end_minute: quiet_hours.end_minute,
+        })
+    }
+}
+
 /// # Represents user settings related to notifications
 ///
-/// Controls different notification channels including email, push notifications,
-/// and SMS messages
+/// The `email`, `push` and `sms` switches turn a channel on or off for every
+/// category, each category then has its own channels. Quiet hours hold back
+/// every notification that is not mandatory.
 ///
 /// # Model
 ///
 /// ```rust,no_run
-/// use kiro_client::NotificationSettings;
+/// use kiro_client::{ChannelSettings, NotificationSettings};
 ///
 /// let settings = NotificationSettings {
 ///     email: true,
 ///     push: true,
 ///     sms: false,
+///     marketing: ChannelSettings::email_only(),
+///     ..Default::default()
 /// };
 ///
 /// println!("{:?}", settings);
@@ -80,6 +328,24 @@ pub struct NotificationSettings {
     pub push: bool,
     /// Enable/disable SMS notifications
     pub sms: bool,
+    /// Channels of the security notifications, emails are always sent
+    #[serde(default = "ChannelSettings::all")]
+    pub security: ChannelSettings,
+    /// Channels of the account notifications
+    #[serde(default = "ChannelSettings::email_only")]
+    pub account: ChannelSettings,
+    /// Channels of the billing notifications
+    #[serde(default = "ChannelSettings::email_only")]
+    pub billing: ChannelSettings,
+    /// Channels of the product notifications
+    #[serde(default = "ChannelSettings::email_only")]
+    pub product: ChannelSettings,
+    /// Channels of the marketing notifications, opt-in
+    #[serde(default)]
+    pub marketing: ChannelSettings,
+    /// Hours during which notifications are held back
+    #[serde(default)]
+    pub quiet_hours: Option<QuietHoursSettings>,
 }
 
 // WARNING: This is a default implementation for testing purposes only
@@ -89,7 +355,68 @@ impl Default for NotificationSettings {
             email: true,
             push: true,
             sms: false,
+            security: ChannelSettings::all(),
+            account: ChannelSettings::email_only(),
+            billing: ChannelSettings::email_only(),
+            product: ChannelSettings::email_only(),
+            marketing: ChannelSettings::default(),
+            quiet_hours: None,
+        }
+    }
+}
+
+impl NotificationSettings {
+    /// Returns the channels of a category
+    pub fn channels(&self, category: NotificationCategory) -> &ChannelSettings {
+        match category {
+            NotificationCategory::Security => &self.security,
+            NotificationCategory::Account => &self.account,
+            NotificationCategory::Billing => &self.billing,
+            NotificationCategory::Product => &self.product,
+            NotificationCategory::Marketing => &self.marketing,
+        }
+    }
+
+    /// Allows
+    ///
+    /// Returns true if a notification of the category may be sent on the channel.
+    /// Mandatory categories are always sent, others need both the channel and
+    /// the category channel enabled, outside of the quiet hours.
+    ///
+    /// # Arguments
+    /// * `category` - Category of the notification
+    /// * `channel` - Channel of the notification
+    /// * `timezone` - IANA timezone of the user, UTC if missing or unknown
+    /// * `now` - Current time
+    pub fn allows(
+        &self, category: NotificationCategory, channel: NotificationChannel,
+        timezone: Option<&str>, now: DateTime<Utc>,
+    ) -> bool {
+        if category.is_mandatory() {
+            return true;
         }
+
+        let switch = match channel {
+            NotificationChannel::Email => self.email,
+            NotificationChannel::Push => self.push,
+            NotificationChannel::Sms => self.sms,
+        };
+
+        switch && self.channels(category).allows(channel) && !self.is_quiet(timezone, now)
+    }
+
+    /// Returns true during the quiet hours of the user
+    fn is_quiet(&self, timezone: Option<&str>, now: DateTime<Utc>) -> bool {
+        let Some(quiet_hours) = self.quiet_hours else {
+            return false;
+        };
+
+        let timezone = timezone
+            .and_then(|timezone| timezone.parse::<Tz>().ok())
+            .unwrap_or(Tz::UTC);
+        let local = now.with_timezone(&timezone);
+
+        quiet_hours.contains(local.hour() * 60 + local.minute())
     }
 }
 
@@ -99,10 +426,45 @@ impl From<&NotificationSettings> for Notifications {
             email: row.email,
             push: row.push,
             sms: row.sms,
+            security: Some(NotificationChannels::from(&row.security)),
+            account: Some(NotificationChannels::from(&row.account)),
+            billing: Some(NotificationChannels::from(&row.billing)),
+            product: Some(NotificationChannels::from(&row.product)),
+            marketing: Some(NotificationChannels::from(&row.marketing)),
+            quiet_hours: row.quiet_hours.as_ref().map(QuietHours::from),
         }
     }
 }
 
+impl TryFrom<&Notifications> for NotificationSettings {
+    type Error = ClientError;
+
+    fn try_from(notifications: &Notifications) -> Result<Self, Self::Error> {
+        let channels = |channels: &Option<NotificationChannels>| {
+            channels
+                .as_ref()
+                .map(ChannelSettings::from)
+                .unwrap_or_default()
+        };
+
+        Ok(Self {
+            email: notifications.email,
+            push: notifications.push,
+            sms: notifications.sms,
+            security: channels(&notifications.security),
+            account: channels(&notifications.account),
+            billing: channels(&notifications.billing),
+            product: channels(&notifications.product),
+            marketing: channels(&notifications.marketing),
+            quiet_hours: notifications
+                .quiet_hours
+                .as_ref()
+                .map(QuietHoursSettings::try_from)
+                .transpose()?,
+        })
+    }
+}
+
 /// # Represents user privacy settings
 ///
 /// Controls data collection and location tracking preferences
@@ -211,7 +573,7 @@ impl From<&SecuritySettings> for Security {
 /// let settings = UserSettings {
 ///     language: Some(Language::English),
 ///     theme: Some(Theme::Dark),
-///     notifications: NotificationSettings { email: true, push: true, sms: false },
+///     notifications: NotificationSettings { email: true, push: true, sms: false, ..Default::default() },
 ///     privacy: PrivacySettings { data_collection: true, location: false },
 ///     security: SecuritySettings {
 ///         two_factor: true,
@@ -244,6 +606,75 @@ impl Default for UserSettings {
     }
 }
 
+impl UserSettings {
+    /// Apply field mask
+    ///
+    /// Copies the fields listed in `paths` from a partial `Settings` message.
+    /// A listed field missing from the message is reset to its default value,
+    /// the QR code of the security settings is never written.
+    ///
+    /// # Arguments
+    /// * `patch` - Partial settings
+    /// * `paths` - Field mask paths, e.g. `theme`, `notifications.email` or `notifications.marketing`
+    ///
+    /// # Returns
+    /// * `Ok(())` - Settings updated
+    /// * `Err(ClientError::InvalidSettings)` - Empty mask, unknown path or invalid value
+    pub fn apply_mask(&mut self, patch: &Settings, paths: &[String]) -> Result<(), ClientError> {
+        if paths.is_empty() {
+            return Err(ClientError::InvalidSettings(
+                "The update mask is empty".to_string(),
+            ));
+        }
+
+        let notifications = patch.notifications.clone().unwrap_or_default();
+        let replacement = NotificationSettings::try_from(&notifications)?;
+        let privacy = patch.privacy.clone().unwrap_or_default();
+        let security = patch.security.clone().unwrap_or_default();
+
+        for path in paths {
+            match path.as_str() {
+                "language" => self.language = patch.language.map(Language::try_from).transpose()?,
+                "theme" => self.theme = patch.theme.map(Theme::try_from).transpose()?,
+                "notifications" => self.notifications = replacement.clone(),
+                "notifications.email" => self.notifications.email = notifications.email,
+                "notifications.push" => self.notifications.push = notifications.push,
+                "notifications.sms" => self.notifications.sms = notifications.sms,
+                "notifications.security" => self.notifications.security = replacement.security,
+                "notifications.account" => self.notifications.account = replacement.account,
+                "notifications.billing" => self.notifications.billing = replacement.billing,
+                "notifications.product" => self.notifications.product = replacement.product,
+                "notifications.marketing" => self.notifications.marketing = replacement.marketing,
+                "notifications.quiet_hours" => {
+                    self.notifications.quiet_hours = replacement.quiet_hours
+                }
+                "privacy" => {
+                    self.privacy = PrivacySettings {
+                        data_collection: privacy.data_collection,
+                        location: privacy.location,
+                    }
+                }
+                "privacy.data_collection" => self.privacy.data_collection = privacy.data_collection,
+                "privacy.location" => self.privacy.location = privacy.location,
+                "security" => {
+                    self.security.two_factor = security.two_factor;
+                    self.security.magic_link = security.magic_link;
+                }
+                "security.two_factor" => self.security.two_factor = security.two_factor,
+                "security.magic_link" => self.security.magic_link = security.magic_link,
+                _ => {
+                    return Err(ClientError::InvalidSettings(format!(
+                        "Invalid update mask path: {}",
+                        path
+                    )))
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl From<&UserSettings> for Settings {
     fn from(row: &UserSettings) -> Self {
         Self {
@@ -256,6 +687,20 @@ impl From<&UserSettings> for Settings {
     }
 }
 
+/// Avatar Variant
+///
+/// A resized, square copy of the user's avatar
+///
+/// # Fields
+///
+/// - `size`: Width and height of the image, in pixels
+/// - `url`: URL of the image
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AvatarVariant {
+    pub size: u32,
+    pub url: String,
+}
+
 /// User Model
 ///
 /// Core user model containing all user data and settings
@@ -266,13 +711,22 @@ impl From<&UserSettings> for Settings {
 /// - `customer_id`: Optional external customer reference
 /// - `email`: User's email address (unique)
 /// - `password_hash`: Hashed user password
-/// - `avatar`: Optional URL to user's profile picture
+/// - `avatar`: Optional URL to user's profile picture, the largest variant
+/// - `avatar_variants`: Resized copies of the profile picture
+/// - `display_name`: Optional name shown instead of the email
+/// - `handle`: Optional unique, lowercase handle (e.g. `jane.doe`)
+/// - `bio`: Optional short biography
+/// - `timezone`: Optional IANA timezone (e.g. `Europe/Paris`)
+/// - `locale`: Optional BCP 47 locale (e.g. `fr-FR`)
 /// - `settings`: User preferences and settings
 /// - `groups`: List of group memberships
 /// - `created_at`: Account creation timestamp
 /// - `updated_at`: Last update timestamp
 /// - `activated`: Account activation status
 /// - `is_admin`: Administrative privileges flag
+/// - `deletion_scheduled_at`: Date after which the account is purged, if deletion was requested
+/// - `settings_version`: Version of the settings, incremented on each update
+/// - `directory_id`: Identifier of the directory entry the account is bound to, if any
 ///
 /// # Example
 ///
@@ -286,12 +740,19 @@ impl From<&UserSettings> for Settings {
 ///     id: DbId::default(),
 ///     customer_id: Some("cust_123".to_string()),
 ///     email: "user@example.com".to_string(),
+///     pending_email: None,
 ///     password_hash: "hashed_password".to_string(),
 ///     avatar: Some("avatar.jpg".to_string()),
+///     avatar_variants: vec![],
+///     display_name: Some("Jane Doe".to_string()),
+///     handle: Some("jane.doe".to_string()),
+///     bio: None,
+///     timezone: Some("Europe/Paris".to_string()),
+///     locale: Some("fr-FR".to_string()),
 ///     settings: UserSettings {
 ///         language: Some(Language::English),
 ///         theme: Some(Theme::Dark),
-///         notifications: NotificationSettings { email: true, push: true, sms: false },
+///         notifications: NotificationSettings { email: true, push: true, sms: false, ..Default::default() },
 ///         privacy: PrivacySettings { data_collection: true, location: false },
 ///         security: SecuritySettings {
 ///             two_factor: true,
@@ -304,6 +765,9 @@ impl From<&UserSettings> for Settings {
 ///     updated_at: DbDateTime::from(Utc::now()),
 ///     activated: true,
 ///     is_admin: false,
+///     deletion_scheduled_at: None,
+///     settings_version: 0,
+///     directory_id: None,
 /// };
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -311,14 +775,29 @@ pub struct UserModel {
     pub id: DbId,
     pub customer_id: Option<String>,
     pub email: String,
+    #[serde(default)]
+    pub pending_email: Option<String>,
     pub password_hash: String,
     pub avatar: Option<String>,
+    #[serde(default)]
+    pub avatar_variants: Vec<AvatarVariant>,
+    pub display_name: Option<String>,
+    pub handle: Option<String>,
+    pub bio: Option<String>,
+    pub timezone: Option<String>,
+    pub locale: Option<String>,
     pub settings: UserSettings,
     pub groups: Vec<DbId>,
     pub created_at: DbDateTime,
     pub updated_at: DbDateTime,
     pub activated: bool,
     pub is_admin: bool,
+    #[serde(default)]
+    pub deletion_scheduled_at: Option<DbDateTime>,
+    #[serde(default)]
+    pub settings_version: u64,
+    #[serde(default)]
+    pub directory_id: Option<String>,
 }
 
 impl HasId for UserModel {
@@ -342,14 +821,24 @@ impl Default for UserModel {
             id: DbId::default(),
             customer_id: Some("cust_123".to_string()),
             email: "test@example.com".to_string(),
+            pending_email: None,
             password_hash,
             avatar: Some("avatar.jpg".to_string()),
+            avatar_variants: vec![],
+            display_name: Some("Test User".to_string()),
+            handle: Some("test_user".to_string()),
+            bio: None,
+            timezone: Some("UTC".to_string()),
+            locale: Some("en".to_string()),
             settings: UserSettings::default(),
             groups: vec![DbId::default()],
             created_at: DbDateTime::from(Utc::now()),
             updated_at: DbDateTime::from(Utc::now()),
             activated: true,
             is_admin: false,
+            deletion_scheduled_at: None,
+            settings_version: 0,
+            directory_id: None,
         }
     }
 }
@@ -358,9 +847,21 @@ impl From<&UserModel> for User {
     fn from(row: &UserModel) -> Self {
         Self {
             email: row.email.clone(),
+            pending_email: row.pending_email.clone(),
             avatar: row.avatar.clone(),
+            avatar_urls: row
+                .avatar_variants
+                .iter()
+                .map(|variant| (variant.size, variant.url.clone()))
+                .collect(),
             settings: Some(Settings::from(&row.settings)),
             is_admin: row.is_admin,
+            display_name: row.display_name.clone(),
+            handle: row.handle.clone(),
+            bio: row.bio.clone(),
+            timezone: row.timezone.clone(),
+            locale: row.locale.clone(),
+            settings_version: row.settings_version,
         }
     }
 }
@@ -406,7 +907,8 @@ impl Default for CreateUserModel {
 impl UserModel {
     /// Get user by email
     ///
-    /// Retrieves a user record from the database using their email address
+    /// Retrieves a user record from the database using their primary email
+    /// address, or one of their verified secondary addresses
     ///
     /// # Arguments
     /// * `db` - Database connection implementing DatabaseOperations trait
@@ -438,10 +940,66 @@ impl UserModel {
     pub async fn get_user_by_email<DB: DatabaseOperations + Send + Sync>(
         db: &DB, email: String,
     ) -> Result<Self, ClientError> {
-        db.read_by_field::<Self>("users", "email", &email, None)
+        if let Some(user) = db
+            .read_by_field::<Self>("users", "email", &email, None)
             .await
-            .map_err(ClientError::Database)
-            .and_then(|res| res.into_iter().next().ok_or(ClientError::DBOptionNone))
+            .map_err(ClientError::Database)?
+            .into_iter()
+            .next()
+        {
+            return Ok(user);
+        }
+
+        // Fall back to the verified secondary addresses
+        let address = UserEmailModel::get_verified_by_email(db, &email)
+            .await?
+            .ok_or(ClientError::DBOptionNone)?;
+
+        db.select::<Self>(address.user)
+            .await
+            .map_err(ClientError::Database)?
+            .ok_or(ClientError::DBOptionNone)
+    }
+
+    /// Get user by directory id
+    ///
+    /// Retrieves the user bound to a directory entry. Directory users are only
+    /// ever matched through this binding, never through their email address.
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `directory_id` - Immutable identifier of the directory entry
+    ///
+    /// # Returns
+    /// * `Ok(Some(UserModel))` - User bound to the entry
+    /// * `Ok(None)` - No user is bound to the entry
+    /// * `Err(ClientError)` - Database error
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use kiro_client::UserModel;
+    /// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+    ///
+    /// // Mock database
+    /// let db = Database::Mock(MockDatabaseOperations::new());
+    ///
+    /// // Async block to allow `await`
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async {
+    ///    let user = UserModel::get_user_by_directory_id(&db, "5f0c2f3e").await;
+    ///
+    ///    println!("User: {:?}", user);
+    /// });
+    /// ```
+    pub async fn get_user_by_directory_id<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, directory_id: &str,
+    ) -> Result<Option<Self>, ClientError> {
+        Ok(db
+            .read_by_field::<Self>("users", "directory_id", directory_id, None)
+            .await
+            .map_err(ClientError::Database)?
+            .into_iter()
+            .next())
     }
 
     /// Check email availability
@@ -485,53 +1043,642 @@ impl UserModel {
             Err(e) => Err(e),
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Get user by handle
+    ///
+    /// Retrieves a user record from the database using their handle
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `handle` - Handle to search for, normalized before the lookup
+    ///
+    /// # Returns
+    /// * `Ok(UserModel)` - User record if found
+    /// * `Err(ClientError)` - Database error or user not found
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use kiro_client::UserModel;
+    /// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+    ///
+    /// // Mock database
+    /// let db = Database::Mock(MockDatabaseOperations::new());
+    ///
+    /// // Async block to allow `await`
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async {
+    ///    let user = UserModel::get_user_by_handle(&db, "@jane.doe").await;
+    ///
+    ///    println!("{:?}", user);
+    /// });
+    /// ```
+    pub async fn get_user_by_handle<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, handle: &str,
+    ) -> Result<Self, ClientError> {
+        db.read_by_field::<Self>("users", "handle", &normalize_handle(handle), None)
+            .await
+            .map_err(ClientError::Database)
+            .and_then(|res| res.into_iter().next().ok_or(ClientError::DBOptionNone))
+    }
 
-    use kiro_database::db_bridge::MockDatabaseOperations;
-    use mockall::predicate::*;
+    /// Update profile
+    ///
+    /// Validates and stores the profile fields present in the request. Absent
+    /// fields are left untouched and empty strings clear the field.
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user_id` - Identifier of the user to update
+    /// * `profile` - Profile fields to update
+    ///
+    /// # Returns
+    /// * `Ok(())` - Profile updated
+    /// * `Err(ClientError::InvalidProfile)` - Invalid field
+    /// * `Err(ClientError::HandleTaken)` - Handle used by another user
+    /// * `Err(ClientError)` - Database error
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use kiro_api::client::v1::UpdateProfileRequest;
+    /// use kiro_client::UserModel;
+    /// use kiro_database::{DbId, db_bridge::{Database, MockDatabaseOperations}};
+    ///
+    /// // Mock database
+    /// let db = Database::Mock(MockDatabaseOperations::new());
+    ///
+    /// // Only the display name and the handle change
+    /// let profile = UpdateProfileRequest {
+    ///     display_name: Some("Jane Doe".to_string()),
+    ///     handle: Some("jane.doe".to_string()),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// // Async block to allow `await`
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async {
+    ///    let result = UserModel::update_profile(&db, DbId::default(), &profile).await;
+    ///
+    ///    println!("{:?}", result);
+    /// });
+    /// ```
+    pub async fn update_profile<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user_id: DbId, profile: &UpdateProfileRequest,
+    ) -> Result<(), ClientError> {
+        let mut fields: Vec<(&str, Option<String>)> = Vec::new();
 
-    #[test]
-    fn test_model_conversions() {
-        let user_model = UserModel::default();
+        if let Some(display_name) = profile.display_name.as_deref().map(str::trim) {
+            valid_display_name(display_name)?;
+            fields.push(("display_name", non_empty(display_name)));
+        }
 
-        // Test SecuritySettings conversion
-        let security: Security = (&user_model.settings.security).into();
-        assert_eq!(security.two_factor, true);
-        assert_eq!(security.qr_code, "qr_code");
-        assert_eq!(security.magic_link, true);
+        if let Some(handle) = profile.handle.as_deref().map(normalize_handle) {
+            if !handle.is_empty() {
+                valid_handle(&handle)?;
 
-        // Test PrivacySettings conversion
-        let privacy: Privacy = (&user_model.settings.privacy).into();
-        assert_eq!(privacy.data_collection, true);
-        assert_eq!(privacy.location, false);
+                match Self::get_user_by_handle(db, &handle).await {
+                    Ok(owner) if owner.id != user_id => return Err(ClientError::HandleTaken),
+                    Ok(_) | Err(ClientError::DBOptionNone) => {}
+                    Err(e) => return Err(e),
+                }
+            }
+            fields.push(("handle", non_empty(&handle)));
+        }
 
-        // Test NotificationSettings conversion
-        let notifications: Notifications = (&user_model.settings.notifications).into();
-        assert_eq!(notifications.email, true);
-        assert_eq!(notifications.push, true);
-        assert_eq!(notifications.sms, false);
+        if let Some(bio) = profile.bio.as_deref().map(str::trim) {
+            valid_bio(bio)?;
+            fields.push(("bio", non_empty(bio)));
+        }
 
-        // Test UserSettings conversion
-        let settings: Settings = (&user_model.settings).into();
-        assert_eq!(settings.language, Some(Language::English as i32));
-        assert_eq!(settings.theme, Some(Theme::Dark as i32));
-        assert!(settings.notifications.is_some());
-        assert!(settings.privacy.is_some());
-        assert!(settings.security.is_some());
+        if let Some(timezone) = profile.timezone.as_deref().map(str::trim) {
+            if !timezone.is_empty() {
+                valid_timezone(timezone)?;
+            }
+            fields.push(("timezone", non_empty(timezone)));
+        }
 
-        // Test UserModel conversion to User
-        let user: User = (&user_model).into();
-        assert_eq!(user.email, "test@example.com");
-        assert_eq!(user.avatar, Some("avatar.jpg".to_string()));
-        assert_eq!(user.is_admin, false);
-        assert!(user.settings.is_some());
+        if let Some(locale) = profile.locale.as_deref().map(str::trim) {
+            if !locale.is_empty() {
+                valid_locale(locale)?;
+            }
+            fields.push(("locale", non_empty(locale)));
+        }
+
+        // Every field is validated before the first write
+        for (field, value) in fields {
+            db.update_field(user_id.clone(), field, value)
+                .await
+                .map_err(ClientError::Database)?;
+        }
+
+        Ok(())
     }
 
-    #[tokio::test]
+    /// Deletion grace period
+    ///
+    /// Delay between a deletion request and the purge of the account, configured
+    /// in days by `ACCOUNT_DELETION_GRACE_DAYS` (30 days by default)
+    pub fn deletion_grace_period() -> Duration {
+        Duration::days(
+            get_env_or("ACCOUNT_DELETION_GRACE_DAYS", "30")
+                .parse()
+                .unwrap_or(30),
+        )
+    }
+
+    /// Schedule deletion
+    ///
+    /// Schedules the purge of an account after the grace period and logs the
+    /// user out of every session. Logging in again during the grace period offers
+    /// to restore the account.
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user_id` - ID of the user to delete
+    ///
+    /// # Returns
+    /// * `Ok(DbDateTime)` - Date after which the account is purged
+    /// * `Err(ClientError)` - Database error
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use kiro_client::UserModel;
+    /// use kiro_database::{db_bridge::{Database, MockDatabaseOperations}, DbId};
+    ///
+    /// // Mock database
+    /// let db = Database::Mock(MockDatabaseOperations::new());
+    ///
+    /// // Async block to allow `await`
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async {
+    ///    let purge_at = UserModel::schedule_deletion(&db, DbId::default()).await;
+    ///
+    ///    println!("{:?}", purge_at);
+    /// });
+    /// ```
+    pub async fn schedule_deletion<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user_id: DbId,
+    ) -> Result<DbDateTime, ClientError> {
+        let purge_at = DbDateTime::from(Utc::now() + Self::deletion_grace_period());
+
+        db.update_field(
+            user_id.clone(),
+            "deletion_scheduled_at",
+            Some(purge_at.clone()),
+        )
+        .await
+        .map_err(ClientError::Database)?;
+
+        db.delete_where("sessions", Condition::eq("user_id", user_id))
+            .await
+            .map_err(ClientError::Database)?;
+
+        Ok(purge_at)
+    }
+
+    /// Cancel deletion
+    ///
+    /// Restores an account scheduled for deletion
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user_id` - ID of the user to restore
+    pub async fn cancel_deletion<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user_id: DbId,
+    ) -> Result<(), ClientError> {
+        db.update_field(user_id, "deletion_scheduled_at", None::<DbDateTime>)
+            .await
+            .map_err(ClientError::Database)
+    }
+
+    /// Checks the account is not scheduled for deletion
+    ///
+    /// # Returns
+    /// * `Ok(())` - The account is active
+    /// * `Err(ClientError::PendingDeletion)` - The account must be restored first
+    pub fn check_not_pending_deletion(&self) -> Result<(), ClientError> {
+        match self.deletion_scheduled_at {
+            Some(_) => Err(ClientError::PendingDeletion),
+            None => Ok(()),
+        }
+    }
+
+    /// Disable
+    ///
+    /// Soft deletes a user account, signs it out everywhere and records the
+    /// change
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user_id` - ID of the user to disable
+    /// * `actor` - ID of the user making the change, if any
+    pub async fn disable<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user_id: DbId, actor: Option<DbId>,
+    ) -> Result<(), ClientError> {
+        db.delete_soft(user_id.clone())
+            .await
+            .map_err(ClientError::Database)?;
+
+        db.delete_where("sessions", Condition::eq("user_id", user_id.clone()))
+            .await
+            .map_err(ClientError::Database)?;
+
+        AccountEventModel::record(db, user_id, AccountEventKind::Disabled, actor).await?;
+
+        Ok(())
+    }
+
+    /// Reactivate
+    ///
+    /// Reactivates a disabled user account and records the change
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user_id` - ID of the user to reactivate
+    /// * `actor` - ID of the user making the change, if any
+    pub async fn reactivate<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user_id: DbId, actor: Option<DbId>,
+    ) -> Result<(), ClientError> {
+        db.update_field(user_id.clone(), "activated", true)
+            .await
+            .map_err(ClientError::Database)?;
+
+        AccountEventModel::record(db, user_id, AccountEventKind::Reactivated, actor).await?;
+
+        Ok(())
+    }
+
+    /// Checks the account is not disabled
+    ///
+    /// # Returns
+    /// * `Ok(())` - The account is active
+    /// * `Err(ClientError::AccountDisabled)` - The account must be reactivated first
+    pub fn check_active(&self) -> Result<(), ClientError> {
+        match self.activated {
+            true => Ok(()),
+            false => Err(ClientError::AccountDisabled),
+        }
+    }
+
+    /// Update settings
+    ///
+    /// Applies a partial settings update in a single statement, if the
+    /// settings were not modified since `version` was read. The version is
+    /// incremented and `updated_at` refreshed.
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user_id` - ID of the user to update
+    /// * `patch` - Partial settings
+    /// * `paths` - Field mask paths of the fields to update
+    /// * `version` - Settings version the update is based on
+    ///
+    /// # Returns
+    /// * `Ok(UserModel)` - Updated user
+    /// * `Err(ClientError::InvalidSettings)` - Invalid mask or value
+    /// * `Err(ClientError::StaleVersion)` - The settings were modified concurrently
+    /// * `Err(ClientError)` - Database error
+    pub async fn update_settings<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user_id: DbId, patch: &Settings, paths: &[String], version: u64,
+    ) -> Result<Self, ClientError> {
+        let user = db
+            .select::<Self>(user_id.clone())
+            .await
+            .map_err(ClientError::Database)?
+            .ok_or(ClientError::DBOptionNone)?;
+
+        if user.settings_version != version {
+            return Err(ClientError::StaleVersion);
+        }
+
+        Self::save_settings(db, user_id, user, patch, paths).await
+    }
+
+    /// Update setting
+    ///
+    /// Applies a partial settings update over the current settings, for the
+    /// single setting updates that do not send the version they are based on.
+    /// The version is still incremented and `updated_at` refreshed.
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user_id` - ID of the user to update
+    /// * `patch` - Partial settings
+    /// * `path` - Field mask path of the field to update
+    ///
+    /// # Returns
+    /// * `Ok(UserModel)` - Updated user
+    /// * `Err(ClientError::InvalidSettings)` - Invalid path or value
+    /// * `Err(ClientError::StaleVersion)` - The settings were modified concurrently
+    /// * `Err(ClientError)` - Database error
+    pub async fn update_setting<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user_id: DbId, patch: &Settings, path: &str,
+    ) -> Result<Self, ClientError> {
+        let user = db
+            .select::<Self>(user_id.clone())
+            .await
+            .map_err(ClientError::Database)?
+            .ok_or(ClientError::DBOptionNone)?;
+
+        Self::save_settings(db, user_id, user, patch, &[path.to_string()]).await
+    }
+
+    /// Writes the settings of a user with a partial update applied
+    async fn save_settings<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user_id: DbId, user: Self, patch: &Settings, paths: &[String],
+    ) -> Result<Self, ClientError> {
+        let version = user.settings_version;
+        let previous = PrivacyPolicy::new(&user.settings.privacy);
+
+        let mut settings = user.settings;
+        settings.apply_mask(patch, paths)?;
+
+        let update = Update::record(
+            user_id.clone(),
+            vec![
+                FieldUpdate::set(
+                    "settings",
+                    DbValue::json(&settings).map_err(ClientError::Database)?,
+                ),
+                FieldUpdate::increment("settings_version", 1),
+                FieldUpdate::now("updated_at"),
+            ],
+        );
+
+        // The version condition makes the update fail if another one won the race
+        let updated = db
+            .update_where::<Self>(update.when(Condition::eq("settings_version", version)))
+            .await
+            .map_err(ClientError::Database)?
+            .pop()
+            .ok_or(ClientError::StaleVersion)?;
+
+        // Changed consents also apply to the existing sessions
+        if PrivacyPolicy::for_user(&updated) != previous {
+            enforce_session_privacy(db, user_id).await?;
+        }
+
+        Ok(updated)
+    }
+
+    /// Update avatar
+    ///
+    /// Replaces the avatar variants of a user, the largest one also becomes the
+    /// avatar.
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user_id` - Identifier of the user to update
+    /// * `variants` - Uploaded variants of the avatar
+    ///
+    /// # Returns
+    /// * `Ok(())` - Avatar updated
+    /// * `Err(ClientError)` - Database error
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use kiro_client::{AvatarVariant, UserModel};
+    /// use kiro_database::{DbId, db_bridge::{Database, MockDatabaseOperations}};
+    ///
+    /// // Mock database
+    /// let db = Database::Mock(MockDatabaseOperations::new());
+    ///
+    /// let variants = vec![AvatarVariant {
+    ///     size: 64,
+    ///     url: "https://bucket/avatar-64".to_string(),
+    /// }];
+    ///
+    /// // Async block to allow `await`
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async {
+    ///    let result = UserModel::update_avatar(&db, DbId::default(), variants).await;
+    ///
+    ///    println!("{:?}", result);
+    /// });
+    /// ```
+    pub async fn update_avatar<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user_id: DbId, variants: Vec<AvatarVariant>,
+    ) -> Result<(), ClientError> {
+        let avatar = variants
+            .iter()
+            .max_by_key(|variant| variant.size)
+            .map(|variant| variant.url.clone());
+
+        let avatar = match avatar {
+            Some(url) => FieldUpdate::set("avatar", url),
+            None => FieldUpdate::unset("avatar"),
+        };
+        let variants = DbValue::json(&variants).map_err(ClientError::Database)?;
+
+        db.update_where::<Self>(Update::record(
+            user_id,
+            vec![
+                avatar,
+                FieldUpdate::set("avatar_variants", variants),
+                FieldUpdate::now("updated_at"),
+            ],
+        ))
+        .await
+        .map_err(ClientError::Database)?;
+
+        Ok(())
+    }
+
+    /// Request email change
+    ///
+    /// Stores a new email address as pending, the current one stays in use until
+    /// the change is confirmed.
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user_id` - Identifier of the user
+    /// * `email` - New email address
+    ///
+    /// # Returns
+    /// * `Ok(())` - Change pending
+    /// * `Err(ClientError::EmailTaken)` - The address is already in use
+    /// * `Err(ClientError)` - Database error
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use kiro_client::UserModel;
+    /// use kiro_database::{DbId, db_bridge::{Database, MockDatabaseOperations}};
+    ///
+    /// // Mock database
+    /// let db = Database::Mock(MockDatabaseOperations::new());
+    ///
+    /// // New address
+    /// let email = "new@example.com".to_string();
+    ///
+    /// // Async block to allow `await`
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async {
+    ///    let result = UserModel::request_email_change(&db, DbId::default(), email).await;
+    ///
+    ///    println!("{:?}", result);
+    /// });
+    /// ```
+    pub async fn request_email_change<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user_id: DbId, email: String,
+    ) -> Result<(), ClientError> {
+        if !Self::check_email(db, email.clone()).await? {
+            return Err(ClientError::EmailTaken);
+        }
+
+        db.update_field(user_id, "pending_email", Some(email))
+            .await
+            .map_err(ClientError::Database)
+    }
+
+    /// Confirm email change
+    ///
+    /// Swaps the email address of a user for its pending one. The availability of
+    /// the address is checked again, a taken address discards the change.
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user_id` - Identifier of the user
+    ///
+    /// # Returns
+    /// * `Ok(UserModel)` - Updated user
+    /// * `Err(ClientError::NoPendingEmail)` - No change is pending
+    /// * `Err(ClientError::EmailTaken)` - The address was taken in the meantime
+    /// * `Err(ClientError)` - Database error
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use kiro_client::UserModel;
+    /// use kiro_database::{DbId, db_bridge::{Database, MockDatabaseOperations}};
+    ///
+    /// // Mock database
+    /// let db = Database::Mock(MockDatabaseOperations::new());
+    ///
+    /// // Async block to allow `await`
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async {
+    ///    let user = UserModel::confirm_email_change(&db, DbId::default()).await;
+    ///
+    ///    println!("{:?}", user);
+    /// });
+    /// ```
+    pub async fn confirm_email_change<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user_id: DbId,
+    ) -> Result<Self, ClientError> {
+        let email = db
+            .select::<Self>(user_id.clone())
+            .await
+            .map_err(ClientError::Database)?
+            .ok_or(ClientError::DBOptionNone)?
+            .pending_email
+            .ok_or(ClientError::NoPendingEmail)?;
+
+        if !Self::check_email(db, email.clone()).await? {
+            Self::cancel_email_change(db, user_id).await?;
+            return Err(ClientError::EmailTaken);
+        }
+
+        let confirm = Update::record(
+            user_id.clone(),
+            vec![
+                FieldUpdate::set("email", email.clone()),
+                FieldUpdate::unset("pending_email"),
+                FieldUpdate::now("updated_at"),
+            ],
+        )
+        .when(Condition::eq("pending_email", email.clone()));
+        let demote = Update::new(
+            "user_emails",
+            Condition::And(vec![
+                Condition::eq("user", user_id),
+                Condition::eq("is_primary", true),
+                Condition::ne("email", email),
+            ]),
+            vec![FieldUpdate::set("is_primary", false)],
+        );
+
+        // The condition makes the update fail if the change was cancelled meanwhile,
+        // the secondary address promoted last is only demoted once the email changed
+        db.update_chain::<Self>(confirm, vec![demote])
+            .await
+            .map_err(ClientError::Database)?
+            .pop()
+            .ok_or(ClientError::NoPendingEmail)
+    }
+
+    /// Cancel email change
+    ///
+    /// Discards the pending email address of a user.
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    /// * `user_id` - Identifier of the user
+    ///
+    /// # Returns
+    /// * `Ok(())` - Change discarded
+    /// * `Err(ClientError)` - Database error
+    pub async fn cancel_email_change<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, user_id: DbId,
+    ) -> Result<(), ClientError> {
+        db.update_field(user_id, "pending_email", None::<String>)
+            .await
+            .map_err(ClientError::Database)
+    }
+}
+
+/// Maps an empty profile value to `None`, clearing the field
+fn non_empty(value: &str) -> Option<String> {
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::db_bridge::MockDatabaseOperations;
+    use mockall::predicate::*;
+
+    use crate::models::CreateAccountEventModel;
+
+    #[test]
+    fn test_model_conversions() {
+        let user_model = UserModel::default();
+
+        // Test SecuritySettings conversion
+        let security: Security = (&user_model.settings.security).into();
+        assert_eq!(security.two_factor, true);
+        assert_eq!(security.qr_code, "qr_code");
+        assert_eq!(security.magic_link, true);
+
+        // Test PrivacySettings conversion
+        let privacy: Privacy = (&user_model.settings.privacy).into();
+        assert_eq!(privacy.data_collection, true);
+        assert_eq!(privacy.location, false);
+
+        // Test NotificationSettings conversion
+        let notifications: Notifications = (&user_model.settings.notifications).into();
+        assert_eq!(notifications.email, true);
+        assert_eq!(notifications.push, true);
+        assert_eq!(notifications.sms, false);
+
+        // Test UserSettings conversion
+        let settings: Settings = (&user_model.settings).into();
+        assert_eq!(settings.language, Some(Language::English as i32));
+        assert_eq!(settings.theme, Some(Theme::Dark as i32));
+        assert!(settings.notifications.is_some());
+        assert!(settings.privacy.is_some());
+        assert!(settings.security.is_some());
+
+        // Test UserModel conversion to User
+        let user: User = (&user_model).into();
+        assert_eq!(user.email, "test@example.com");
+        assert_eq!(user.avatar, Some("avatar.jpg".to_string()));
+        assert_eq!(user.is_admin, false);
+        assert!(user.settings.is_some());
+        assert_eq!(user.display_name, Some("Test User".to_string()));
+        assert_eq!(user.handle, Some("test_user".to_string()));
+        assert_eq!(user.timezone, Some("UTC".to_string()));
+        assert_eq!(user.locale, Some("en".to_string()));
+    }
+
+    #[tokio::test]
     async fn test_get_user_by_email_success() {
         let mut mock_db = MockDatabaseOperations::new();
         let test_user = UserModel::default();
@@ -568,6 +1715,17 @@ mod tests {
             .times(1)
             .returning(|_, _, _, _| Ok(vec![]));
 
+        mock_db
+            .expect_read_by_field::<UserEmailModel>()
+            .with(
+                eq("user_emails"),
+                eq("email"),
+                eq("nonexistent@example.com"),
+                eq(None),
+            )
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
         let test_email = "nonexistent@example.com".to_string();
 
         let result = UserModel::get_user_by_email(&mock_db, test_email).await;
@@ -575,6 +1733,47 @@ mod tests {
         assert!(matches!(result.unwrap_err(), ClientError::DBOptionNone));
     }
 
+    #[tokio::test]
+    async fn test_get_user_by_email_secondary() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        mock_db
+            .expect_read_by_field::<UserEmailModel>()
+            .with(
+                eq("user_emails"),
+                eq("email"),
+                eq("backup@example.com"),
+                eq(None),
+            )
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(vec![
+                    UserEmailModel::default(),
+                    UserEmailModel {
+                        user: DbId::from(("users", "owner")),
+                        verified: true,
+                        ..Default::default()
+                    },
+                ])
+            });
+
+        mock_db
+            .expect_select::<UserModel>()
+            .with(eq(DbId::from(("users", "owner"))))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        let user = UserModel::get_user_by_email(&mock_db, "backup@example.com".to_string())
+            .await
+            .unwrap();
+        assert_eq!(user.email, "test@example.com");
+    }
+
     #[tokio::test]
     async fn test_check_email_exists() {
         let mut mock_db = MockDatabaseOperations::new();
@@ -610,10 +1809,572 @@ mod tests {
             .times(1)
             .returning(|_, _, _, _| Ok(vec![]));
 
+        mock_db
+            .expect_read_by_field::<UserEmailModel>()
+            .with(
+                eq("user_emails"),
+                eq("email"),
+                eq("nonexistent@example.com"),
+                eq(None),
+            )
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
         let test_email = "nonexistent@example.com".to_string();
 
         let result = UserModel::check_email(&mock_db, test_email).await;
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), true);
     }
+
+    #[tokio::test]
+    async fn test_update_profile() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let user_id = DbId::default();
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .withf(|table: &str, field: &str, value: &str, _| {
+                table == "users" && field == "handle" && value == "jane.doe"
+            })
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        mock_db
+            .expect_update_field::<Option<String>>()
+            .with(
+                eq(user_id.clone()),
+                eq("handle"),
+                eq(Some("jane.doe".to_string())),
+            )
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        mock_db
+            .expect_update_field::<Option<String>>()
+            .with(eq(user_id.clone()), eq("bio"), eq(None))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let profile = UpdateProfileRequest {
+            handle: Some("@Jane.Doe".to_string()),
+            bio: Some("".to_string()),
+            ..Default::default()
+        };
+
+        let result = UserModel::update_profile(&mock_db, user_id, &profile).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_update_profile_handle_taken() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(vec![UserModel {
+                    id: DbId::from(("users", "other")),
+                    ..Default::default()
+                }])
+            });
+
+        let profile = UpdateProfileRequest {
+            display_name: Some("Jane".to_string()),
+            handle: Some("jane.doe".to_string()),
+            ..Default::default()
+        };
+
+        // Nothing is written when a field is rejected
+        let result = UserModel::update_profile(&mock_db, DbId::default(), &profile).await;
+        assert!(matches!(result.unwrap_err(), ClientError::HandleTaken));
+    }
+
+    #[tokio::test]
+    async fn test_update_profile_invalid_timezone() {
+        let mock_db = MockDatabaseOperations::new();
+
+        let profile = UpdateProfileRequest {
+            timezone: Some("Mars/Olympus".to_string()),
+            ..Default::default()
+        };
+
+        let result = UserModel::update_profile(&mock_db, DbId::default(), &profile).await;
+        assert!(matches!(
+            result.unwrap_err(),
+            ClientError::InvalidProfile(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_schedule_deletion() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_update_field::<Option<DbDateTime>>()
+            .withf(|id, field, value| {
+                *id == DbId::default() && field == "deletion_scheduled_at" && value.is_some()
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        mock_db
+            .expect_delete_where()
+            .withf(|table, condition| {
+                table == "sessions" && *condition == Condition::eq("user_id", DbId::default())
+            })
+            .times(1)
+            .returning(|_, _| Ok(2));
+
+        let purge_at = UserModel::schedule_deletion(&mock_db, DbId::default())
+            .await
+            .unwrap();
+        assert!(chrono::DateTime::<Utc>::from(purge_at) > Utc::now());
+
+        let user = UserModel {
+            deletion_scheduled_at: Some(DbDateTime::now()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            user.check_not_pending_deletion(),
+            Err(ClientError::PendingDeletion)
+        ));
+        assert!(UserModel::default().check_not_pending_deletion().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_disable_and_reactivate() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let admin_id = DbId::from(("users", "admin"));
+
+        mock_db
+            .expect_delete_soft()
+            .with(eq(DbId::default()))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        // Disabling signs the user out everywhere
+        mock_db
+            .expect_delete_where()
+            .with(
+                eq("sessions"),
+                eq(Condition::eq("user_id", DbId::default())),
+            )
+            .times(1)
+            .returning(|_, _| Ok(2));
+
+        mock_db
+            .expect_update_field::<bool>()
+            .with(eq(DbId::default()), eq("activated"), eq(true))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        mock_db
+            .expect_create::<CreateAccountEventModel, AccountEventModel>()
+            .withf(|table, data| table == "account_events" && data.user == DbId::default())
+            .times(2)
+            .returning(|_, _| Ok(vec![AccountEventModel::default()]));
+
+        UserModel::disable(&mock_db, DbId::default(), None)
+            .await
+            .unwrap();
+        UserModel::reactivate(&mock_db, DbId::default(), Some(admin_id))
+            .await
+            .unwrap();
+
+        let user = UserModel {
+            activated: false,
+            ..Default::default()
+        };
+        assert!(matches!(
+            user.check_active(),
+            Err(ClientError::AccountDisabled)
+        ));
+        assert!(UserModel::default().check_active().is_ok());
+    }
+
+    #[test]
+    fn test_apply_mask() {
+        let mut settings = UserSettings::default();
+        let patch = Settings {
+            theme: Some(Theme::Light as i32),
+            notifications: Some(Notifications {
+                email: false,
+                push: false,
+                sms: true,
+                marketing: Some(NotificationChannels {
+                    email: true,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            security: Some(Security {
+                two_factor: false,
+                qr_code: "forged".to_string(),
+                magic_link: false,
+            }),
+            ..Default::default()
+        };
+
+        settings
+            .apply_mask(
+                &patch,
+                &[
+                    "theme".to_string(),
+                    "notifications.sms".to_string(),
+                    "notifications.marketing".to_string(),
+                    "security".to_string(),
+                ],
+            )
+            .unwrap();
+
+        assert!(matches!(settings.theme, Some(Theme::Light)));
+        assert!(matches!(settings.language, Some(Language::English)));
+        // Only the masked notification channel changes
+        assert!(settings.notifications.email);
+        assert!(settings.notifications.sms);
+        assert_eq!(
+            settings.notifications.marketing,
+            ChannelSettings::email_only()
+        );
+        assert_eq!(
+            settings.notifications.billing,
+            ChannelSettings::email_only()
+        );
+        assert!(!settings.security.two_factor);
+        assert_eq!(settings.security.qr_code, "qr_code");
+
+        assert!(matches!(
+            settings.apply_mask(&patch, &[]),
+            Err(ClientError::InvalidSettings(_))
+        ));
+        assert!(matches!(
+            settings.apply_mask(&patch, &["security.qr_code".to_string()]),
+            Err(ClientError::InvalidSettings(_))
+        ));
+        assert!(matches!(
+            settings.apply_mask(
+                &Settings {
+                    language: Some(42),
+                    ..Default::default()
+                },
+                &["language".to_string()]
+            ),
+            Err(ClientError::InvalidSettings(_))
+        ));
+    }
+
+    #[test]
+    fn test_notification_matrix() {
+        let now = Utc::now();
+        let mut settings = NotificationSettings::default();
+
+        assert!(settings.allows(
+            NotificationCategory::Account,
+            NotificationChannel::Email,
+            None,
+            now
+        ));
+        assert!(!settings.allows(
+            NotificationCategory::Marketing,
+            NotificationChannel::Email,
+            None,
+            now
+        ));
+        assert!(!settings.allows(
+            NotificationCategory::Billing,
+            NotificationChannel::Push,
+            None,
+            now
+        ));
+
+        // The channel switch applies to every category but the mandatory ones
+        settings.email = false;
+        assert!(!settings.allows(
+            NotificationCategory::Account,
+            NotificationChannel::Email,
+            None,
+            now
+        ));
+        settings.security = ChannelSettings::default();
+        assert!(settings.allows(
+            NotificationCategory::Security,
+            NotificationChannel::Email,
+            None,
+            now
+        ));
+    }
+
+    #[test]
+    fn test_quiet_hours() {
+        // 22:00 to 07:00, wrapping around midnight
+        let quiet_hours = QuietHoursSettings {
+            start_minute: 22 * 60,
+            end_minute: 7 * 60,
+        };
+        assert!(quiet_hours.contains(23 * 60));
+        assert!(quiet_hours.contains(6 * 60 + 59));
+        assert!(!quiet_hours.contains(7 * 60));
+        assert!(!quiet_hours.contains(12 * 60));
+
+        let settings = NotificationSettings {
+            quiet_hours: Some(quiet_hours),
+            ..Default::default()
+        };
+
+        // 21:30 UTC is 23:30 in Paris during summer time
+        let now = DateTime::parse_from_rfc3339("2024-07-01T21:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let allows = |category, timezone| {
+            settings.allows(category, NotificationChannel::Email, timezone, now)
+        };
+
+        assert!(allows(NotificationCategory::Account, None));
+        assert!(allows(NotificationCategory::Account, Some("Not/AZone")));
+        assert!(!allows(NotificationCategory::Account, Some("Europe/Paris")));
+        assert!(allows(NotificationCategory::Security, Some("Europe/Paris")));
+
+        assert!(matches!(
+            QuietHoursSettings::try_from(&QuietHours {
+                start_minute: 24 * 60,
+                end_minute: 0,
+            }),
+            Err(ClientError::InvalidSettings(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_update_settings() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .with(eq(DbId::default()))
+            .times(1)
+            .returning(|_| {
+                Ok(Some(UserModel {
+                    settings_version: 3,
+                    ..Default::default()
+                }))
+            });
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| {
+                let theme = match &update.changes[0] {
+                    FieldUpdate::Set(field, DbValue::Json(settings)) if field == "settings" => {
+                        settings["theme"].clone()
+                    }
+                    _ => serde_json::Value::Null,
+                };
+
+                update.condition
+                    == Condition::And(vec![
+                        Condition::eq("id", DbId::default()),
+                        Condition::eq("settings_version", 3_u64),
+                    ])
+                    && theme == "System"
+            })
+            .times(1)
+            .returning(|_| {
+                Ok(vec![UserModel {
+                    settings_version: 4,
+                    ..Default::default()
+                }])
+            });
+
+        let patch = Settings {
+            theme: Some(Theme::System as i32),
+            ..Default::default()
+        };
+
+        let user = UserModel::update_settings(
+            &mock_db,
+            DbId::default(),
+            &patch,
+            &["theme".to_string()],
+            3,
+        )
+        .await
+        .unwrap();
+        assert_eq!(user.settings_version, 4);
+    }
+
+    #[tokio::test]
+    async fn test_update_avatar() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        // The largest variant becomes the avatar
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| {
+                let variants = match &update.changes[1] {
+                    FieldUpdate::Set(_, DbValue::Json(variants)) => {
+                        variants.as_array().map(Vec::len)
+                    }
+                    _ => None,
+                };
+
+                update.changes[0] == FieldUpdate::set("avatar", "https://bucket/avatar-512")
+                    && variants == Some(2)
+            })
+            .times(1)
+            .returning(|_| Ok(vec![]));
+
+        let variants = vec![
+            AvatarVariant {
+                size: 64,
+                url: "https://bucket/avatar-64".to_string(),
+            },
+            AvatarVariant {
+                size: 512,
+                url: "https://bucket/avatar-512".to_string(),
+            },
+        ];
+
+        let result = UserModel::update_avatar(&mock_db, DbId::default(), variants).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_request_email_change_taken() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![UserModel::default()]));
+
+        mock_db.expect_update_field::<Option<String>>().never();
+
+        let result = UserModel::request_email_change(
+            &mock_db,
+            DbId::default(),
+            "taken@example.com".to_string(),
+        )
+        .await;
+        assert!(matches!(result, Err(ClientError::EmailTaken)));
+    }
+
+    #[tokio::test]
+    async fn test_confirm_email_change() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(UserModel {
+                    pending_email: Some("new@example.com".to_string()),
+                    ..Default::default()
+                }))
+            });
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .withf(|table, field, value, _| {
+                table == "users" && field == "email" && value == "new@example.com"
+            })
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        mock_db
+            .expect_read_by_field::<UserEmailModel>()
+            .with(
+                eq("user_emails"),
+                eq("email"),
+                eq("new@example.com"),
+                eq(None),
+            )
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        // The secondary addresses are only demoted once the email changed
+        mock_db
+            .expect_update_chain::<UserModel>()
+            .withf(|confirm, then| {
+                confirm.condition
+                    == Condition::And(vec![
+                        Condition::eq("id", DbId::default()),
+                        Condition::eq("pending_email", "new@example.com"),
+                    ])
+                    && then.len() == 1
+                    && then[0].table == "user_emails"
+                    && then[0].changes == vec![FieldUpdate::set("is_primary", false)]
+            })
+            .times(1)
+            .returning(|_, _| {
+                Ok(vec![UserModel {
+                    email: "new@example.com".to_string(),
+                    ..Default::default()
+                }])
+            });
+
+        let user = UserModel::confirm_email_change(&mock_db, DbId::default())
+            .await
+            .unwrap();
+        assert_eq!(user.email, "new@example.com");
+        assert_eq!(user.pending_email, None);
+    }
+
+    #[tokio::test]
+    async fn test_confirm_email_change_taken() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(UserModel {
+                    pending_email: Some("new@example.com".to_string()),
+                    ..Default::default()
+                }))
+            });
+
+        // The address was registered since the change was requested
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![UserModel::default()]));
+
+        mock_db
+            .expect_update_field::<Option<String>>()
+            .withf(|_, field, value| field == "pending_email" && value.is_none())
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        mock_db.expect_update_chain::<UserModel>().never();
+
+        let result = UserModel::confirm_email_change(&mock_db, DbId::default()).await;
+        assert!(matches!(result, Err(ClientError::EmailTaken)));
+    }
+
+    #[tokio::test]
+    async fn test_update_settings_stale_version() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(UserModel {
+                    settings_version: 4,
+                    ..Default::default()
+                }))
+            });
+
+        mock_db.expect_update_where::<UserModel>().never();
+
+        let result = UserModel::update_settings(
+            &mock_db,
+            DbId::default(),
+            &Settings::default(),
+            &["theme".to_string()],
+            3,
+        )
+        .await;
+        assert!(matches!(result, Err(ClientError::StaleVersion)));
+    }
 }
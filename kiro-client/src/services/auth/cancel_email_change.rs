@@ -0,0 +1,114 @@
+// services/auth/cancel_email_change.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use kiro_api::auth::v1::CancelEmailChangeRequest;
+use tonic::{Request, Response, Status};
+
+use crate::utils::email_change::consume_email_cancel_link;
+
+/// Email change cancellation service implementation
+///
+/// # Description
+/// Discards the pending email address of a user, from the link emailed to the
+/// current address by `update_email`
+///
+/// # Arguments
+/// * `service` - The auth service instance
+/// * `request` - Request containing the token of the cancel link
+///
+/// # Returns
+/// * `Ok(Response)` - Empty response once the change is cancelled
+/// * `Err(Status)` - Error status with description
+///
+/// # Errors
+/// * `Status::not_found` - Unknown link
+/// * `Status::invalid_argument` - The link is not a cancel link
+/// * `Status::failed_precondition` - The link has expired
+/// * `Status::internal` - Database or internal error
+///
+/// # Example
+/// ```rust,no_run
+/// use tonic::{Request, Response, Status};
+/// use kiro_api::auth::v1::{auth_service_server::AuthService, CancelEmailChangeRequest};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = kiro_client::AuthService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Cancellation request
+/// let request = Request::new(CancelEmailChangeRequest {
+///     temp_token: "temp_token".to_string(),
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     AuthService::cancel_email_change(&service, request).await;
+///
+///     println!("Email change cancelled");
+/// });
+/// ```
+pub async fn cancel_email_change(
+    service: &AuthService, request: Request<CancelEmailChangeRequest>,
+) -> Result<Response<Empty>, Status> {
+    consume_email_cancel_link(&service.db, &request.get_ref().temp_token).await?;
+
+    Ok(Response::new(Empty {}))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::{db_bridge::MockDatabaseOperations, DbDateTime, DbId};
+    use kiro_mailer::{LinkModel, LinkType};
+
+    #[tokio::test]
+    async fn test_cancel_email_change_expired_link() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<LinkModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(LinkModel {
+                    id: DbId::from(("links", "link")),
+                    user: DbId::from(("users", "user")),
+                    link_type: LinkType::EmailChangeCancel,
+                    expiry: DbDateTime::from(chrono::Utc::now() - chrono::Duration::hours(1)),
+                }))
+            });
+
+        mock_db.expect_update_field::<Option<String>>().never();
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Request::new(CancelEmailChangeRequest {
+            temp_token: "link".to_string(),
+        });
+
+        let error = cancel_email_change(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::FailedPrecondition);
+    }
+}
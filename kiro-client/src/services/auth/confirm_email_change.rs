@@ -0,0 +1,163 @@
+// services/auth/confirm_email_change.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use kiro_api::auth::v1::ConfirmEmailChangeRequest;
+use tonic::{Request, Response, Status};
+
+use crate::utils::email_change::consume_email_confirmation_link;
+
+/// Email change confirmation service implementation
+///
+/// # Description
+/// Swaps the email address of a user for the pending one, from the link emailed
+/// to the new address by `update_email`. The availability of the address is
+/// checked again at this moment.
+///
+/// # Arguments
+/// * `service` - The auth service instance
+/// * `request` - Request containing the token of the confirmation link
+///
+/// # Returns
+/// * `Ok(Response)` - Empty response once the email is changed
+/// * `Err(Status)` - Error status with description
+///
+/// # Errors
+/// * `Status::not_found` - Unknown link
+/// * `Status::invalid_argument` - The link is not a confirmation link
+/// * `Status::failed_precondition` - The link has expired or the change was cancelled
+/// * `Status::already_exists` - The email was taken in the meantime
+/// * `Status::internal` - Database or internal error
+///
+/// # Example
+/// ```rust,no_run
+/// use tonic::{Request, Response, Status};
+/// use kiro_api::auth::v1::{auth_service_server::AuthService, ConfirmEmailChangeRequest};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = kiro_client::AuthService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Confirmation request
+/// let request = Request::new(ConfirmEmailChangeRequest {
+///     temp_token: "temp_token".to_string(),
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     AuthService::confirm_email_change(&service, request).await;
+///
+///     println!("Email changed");
+/// });
+/// ```
+pub async fn confirm_email_change(
+    service: &AuthService, request: Request<ConfirmEmailChangeRequest>,
+) -> Result<Response<Empty>, Status> {
+    consume_email_confirmation_link(&service.db, &request.get_ref().temp_token).await?;
+
+    Ok(Response::new(Empty {}))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::{db_bridge::MockDatabaseOperations, DbDateTime, DbId};
+    use kiro_mailer::{LinkModel, LinkType};
+
+    use crate::UserModel;
+
+    #[tokio::test]
+    async fn test_confirm_email_change_taken() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<LinkModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(LinkModel {
+                    id: DbId::from(("links", "link")),
+                    user: DbId::from(("users", "user")),
+                    link_type: LinkType::EmailConfirmation,
+                    expiry: DbDateTime::from(chrono::Utc::now() + chrono::Duration::hours(1)),
+                }))
+            });
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(UserModel {
+                    pending_email: Some("new@example.com".to_string()),
+                    ..Default::default()
+                }))
+            });
+
+        // The address was registered since the change was requested
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![UserModel::default()]));
+
+        mock_db
+            .expect_update_field::<Option<String>>()
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        mock_db
+            .expect_delete_where::<LinkModel>()
+            .times(2)
+            .returning(|_, _| Ok(0));
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Request::new(ConfirmEmailChangeRequest {
+            temp_token: "link".to_string(),
+        });
+
+        let error = confirm_email_change(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::AlreadyExists);
+    }
+
+    #[tokio::test]
+    async fn test_confirm_email_change_unknown_link() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<LinkModel>()
+            .times(1)
+            .returning(|_| Ok(None));
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Request::new(ConfirmEmailChangeRequest {
+            temp_token: "unknown".to_string(),
+        });
+
+        let error = confirm_email_change(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::NotFound);
+    }
+}
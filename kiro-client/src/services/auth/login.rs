@@ -20,15 +20,18 @@ use kiro_api::google::protobuf::Timestamp;
 use tonic::{Request, Response, Status};
 
 use crate::{
-    models::UserModel,
-    utils::{ip::get_ip_from_md, password::valid_password},
-    SessionModel,
+    backends::authenticate,
+    captcha::{captcha_policy, get_captcha_token_from_md},
+    utils::ip::get_ip_from_md,
+    PrivacyPolicy, SessionModel,
 };
 
 /// Login service implementation
 ///
 /// # Description
-/// Authenticates a user and creates a new session
+/// Authenticates a user with the configured backends and creates a new session.
+/// Once the account or the IP address crossed its threshold of failed logins,
+/// a CAPTCHA token is required in the `x-captcha-token` metadata.
 ///
 /// # Arguments
 /// * `service` - The auth service instance
@@ -41,7 +44,9 @@ use crate::{
 /// # Errors
 /// * `Status::invalid_argument` - Invalid password format
 /// * `Status::not_found` - User not found
-/// * `Status::permission_denied` - Invalid password
+/// * `Status::permission_denied` - Invalid password or account disabled
+/// * `Status::failed_precondition` - Account scheduled for deletion, see `restore_account`, or CAPTCHA token missing
+/// * `Status::unavailable` - Directory server or CAPTCHA provider unreachable
 /// * `Status::internal` - Database or internal error
 ///
 /// # Example
@@ -61,7 +66,9 @@ use crate::{
 /// // Login request
 /// let request = Request::new(AuthRequest {
 ///     email: "user@example.com".to_string(),
-///     password: "password123!".to_string()
+///     password: "password123!".to_string(),
+///     invitation_code: None,
+///     accepted_policies: vec![],
 /// });
 ///
 /// // Async block to allow `await`
@@ -75,33 +82,52 @@ pub async fn login(
     service: &AuthService, request: Request<AuthRequest>,
 ) -> Result<Response<Session>, Status> {
     // Extract IP address from request metadata
-    let ip_address = get_ip_from_md(request.metadata()).unwrap_or_else(|| "unknown".to_string());
+    let ip_address = get_ip_from_md(request.metadata());
+    let captcha_token = get_captcha_token_from_md(request.metadata());
 
     let request = request.into_inner();
 
-    // Validate password format
-    if let Err(e) = valid_password(&request.password) {
-        return Err(Status::invalid_argument(e.to_string()));
-    }
-
-    // Get user by email
-    let user = UserModel::get_user_by_email(&service.db, request.email.clone())
-        .await
-        .map_err(|e| Status::not_found(format!("User not found: {}", e)))?;
-
-    // Verify password
-    let verified = SessionModel::verify_password(request.password, user.password_hash)
-        .await
-        .map_err(|e| Status::internal(format!("Password verification error: {}", e)))?;
-
-    if !verified {
-        return Err(Status::permission_denied("Invalid password"));
-    }
+    // Require a CAPTCHA after repeated failures
+    let captcha = captcha_policy();
+    captcha
+        .check_login(
+            &service.db,
+            &request.email,
+            ip_address.as_deref(),
+            captcha_token.as_deref(),
+        )
+        .await?;
+
+    // Authenticate with the configured backends
+    let user = match authenticate(&service.db, &request.email, &request.password).await {
+        Ok(user) => user,
+        Err(e) => {
+            if e.is_fallthrough() {
+                captcha
+                    .record_failure(&service.db, &request.email, ip_address)
+                    .await;
+            }
+
+            return Err(e.into());
+        }
+    };
+    captcha.clear_failures(&service.db, &request.email).await;
+
+    // Disabled accounts must be reactivated first
+    user.check_active()?;
+
+    // Accounts scheduled for deletion must be restored first
+    user.check_not_pending_deletion()?;
 
     // Create or get existing session
-    let session = SessionModel::get_session_by_user_id(&service.db, user.id.clone(), ip_address)
-        .await
-        .map_err(|e| Status::internal(format!("Session creation failed: {}", e)))?;
+    let session = SessionModel::get_session_by_user_id(
+        &service.db,
+        user.id.clone(),
+        ip_address.unwrap_or_else(|| "unknown".to_string()),
+        &PrivacyPolicy::for_user(&user),
+    )
+    .await
+    .map_err(|e| Status::internal(format!("Session creation failed: {}", e)))?;
 
     // Generate refresh token
     let refresh_token = SessionModel::generate_refresh_token(session.user_id)
@@ -123,7 +149,7 @@ pub async fn login(
 mod tests {
     use super::*;
 
-    use crate::{CreateSessionModel, SessionModel};
+    use crate::{CreateSessionModel, SessionModel, UserEmailModel, UserModel};
     use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError, DbId};
     use mockall::predicate::{always, eq};
 
@@ -158,6 +184,8 @@ mod tests {
         let request = Request::new(AuthRequest {
             email: "test@example.com".to_string(),
             password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
         });
 
         let response = login(&service, request).await.unwrap().into_inner();
@@ -183,6 +211,8 @@ mod tests {
         let request = Request::new(AuthRequest {
             email: "test@example.com".to_string(),
             password: "WrongPassword123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
         });
 
         let error = login(&service, request).await.unwrap_err();
@@ -190,6 +220,67 @@ mod tests {
         assert_eq!(error.message(), "Invalid password");
     }
 
+    #[tokio::test]
+    async fn test_login_pending_deletion() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .with(eq("users"), eq("email"), eq("test@example.com"), eq(None))
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(vec![UserModel {
+                    deletion_scheduled_at: Some(kiro_database::DbDateTime::now()),
+                    ..Default::default()
+                }])
+            });
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Request::new(AuthRequest {
+            email: "test@example.com".to_string(),
+            password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
+        });
+
+        let error = login(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::FailedPrecondition);
+    }
+
+    #[tokio::test]
+    async fn test_login_disabled_account() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .with(eq("users"), eq("email"), eq("test@example.com"), eq(None))
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(vec![UserModel {
+                    activated: false,
+                    ..Default::default()
+                }])
+            });
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Request::new(AuthRequest {
+            email: "test@example.com".to_string(),
+            password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
+        });
+
+        let error = login(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::PermissionDenied);
+        assert_eq!(error.message(), "Account disabled");
+    }
+
     #[tokio::test]
     async fn test_login_user_not_found() {
         let mut mock_db = MockDatabaseOperations::new();
@@ -202,6 +293,17 @@ mod tests {
             .times(1)
             .returning(|_, _, _, _| Ok(vec![]));
 
+        mock_db
+            .expect_read_by_field::<UserEmailModel>()
+            .with(
+                eq("user_emails"),
+                eq("email"),
+                eq("nonexistent@example.com"),
+                eq(None),
+            )
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
         let service = AuthService {
             db: Database::Mock(mock_db),
         };
@@ -209,6 +311,8 @@ mod tests {
         let request = Request::new(AuthRequest {
             email: "nonexistent@example.com".to_string(),
             password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
         });
 
         let error = login(&service, request).await.unwrap_err();
@@ -235,6 +339,8 @@ mod tests {
         let request = Request::new(AuthRequest {
             email: "test@example.com".to_string(),
             password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
         });
 
         let error = login(&service, request).await.unwrap_err();
@@ -243,7 +349,14 @@ mod tests {
 
     #[tokio::test]
     async fn test_login_invalid_password_format() {
-        let mock_db = MockDatabaseOperations::new();
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .with(eq("users"), eq("email"), eq("test@example.com"), eq(None))
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![UserModel::default()]));
+
         let service = AuthService {
             db: Database::Mock(mock_db),
         };
@@ -251,6 +364,8 @@ mod tests {
         let request = Request::new(AuthRequest {
             email: "test@example.com".to_string(),
             password: "short".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
         });
 
         let error = login(&service, request).await.unwrap_err();
@@ -285,6 +400,8 @@ mod tests {
         let request = Request::new(AuthRequest {
             email: "test@example.com".to_string(),
             password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
         });
 
         let error = login(&service, request).await.unwrap_err();
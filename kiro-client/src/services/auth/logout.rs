@@ -16,9 +16,9 @@
 
 use super::*;
 
-use tonic::{Request, Response, Status};
+use tonic::{Code, Request, Response, Status};
 
-use crate::SessionModel;
+use crate::{keyed_status, SessionModel};
 
 /// Logout service implementation
 ///
@@ -67,7 +67,7 @@ pub async fn logout(
     let session = request
         .extensions()
         .get::<SessionModel>()
-        .ok_or_else(|| Status::not_found("Session not found in request"))?;
+        .ok_or_else(|| keyed_status(Code::NotFound, "session.not_in_request"))?;
 
     match SessionModel::delete_session(&service.db, session.id.clone()).await {
         Ok(_) => Ok(Response::new(Empty {})),
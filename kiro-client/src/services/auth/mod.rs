@@ -20,6 +20,10 @@
 //! - User registration
 //! - Login/logout flows
 //! - Session management
+//! - Restoration of accounts scheduled for deletion
+//! - Self-service reactivation of disabled accounts
+//! - Confirmation and cancellation of pending email changes
+//! - Verification of secondary email addresses
 //!
 //! The service is implemented as a gRPC service using the tonic framework.
 
@@ -28,15 +32,27 @@ use tonic::{async_trait, Request, Response, Status};
 use kiro_api::{
     auth::v1::{
         auth_service_server::{self, AuthServiceServer},
-        AuthRequest, Session,
+        AuthRequest, CancelEmailChangeRequest, ConfirmEmailChangeRequest, ReactivateAccountRequest,
+        Session, VerifyEmailRequest,
     },
     google::protobuf::Empty,
 };
 use kiro_database::db_bridge::Database;
 
+#[cfg(feature = "mailer")]
+mod cancel_email_change;
+#[cfg(feature = "mailer")]
+mod confirm_email_change;
 mod login;
 mod logout;
+#[cfg(feature = "mailer")]
+mod reactivate_account;
 mod register;
+#[cfg(feature = "mailer")]
+mod request_reactivation;
+mod restore_account;
+#[cfg(feature = "mailer")]
+mod verify_email;
 
 /// The main authentication service implementation
 #[derive(Clone)]
@@ -100,6 +116,104 @@ impl auth_service_server::AuthService for AuthService {
     async fn register(&self, request: Request<AuthRequest>) -> Result<Response<Session>, Status> {
         register::register(self, request).await
     }
+
+    /// Handles the restoration of accounts scheduled for deletion
+    ///
+    /// # Arguments
+    /// * `request` - Restore request containing credentials
+    ///
+    /// # Returns
+    /// A new session if the account is restored
+    async fn restore_account(
+        &self, request: Request<AuthRequest>,
+    ) -> Result<Response<Session>, Status> {
+        restore_account::restore_account(self, request).await
+    }
+
+    /// Handles the reactivation requests of disabled accounts
+    ///
+    /// # Arguments
+    /// * `request` - Request containing credentials
+    ///
+    /// # Returns
+    /// Empty response once the reactivation link is sent
+    async fn request_reactivation(
+        &self, #[cfg(feature = "mailer")] request: Request<AuthRequest>,
+        #[cfg(not(feature = "mailer"))] _request: Request<AuthRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        #[cfg(not(feature = "mailer"))]
+        unimplemented!("Email functionality is disabled");
+        #[cfg(feature = "mailer")]
+        request_reactivation::request_reactivation(self, request).await
+    }
+
+    /// Handles the reactivation of disabled accounts
+    ///
+    /// # Arguments
+    /// * `request` - Request containing the reactivation link token
+    ///
+    /// # Returns
+    /// Empty response once the account is reactivated
+    async fn reactivate_account(
+        &self, #[cfg(feature = "mailer")] request: Request<ReactivateAccountRequest>,
+        #[cfg(not(feature = "mailer"))] _request: Request<ReactivateAccountRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        #[cfg(not(feature = "mailer"))]
+        unimplemented!("Email functionality is disabled");
+        #[cfg(feature = "mailer")]
+        reactivate_account::reactivate_account(self, request).await
+    }
+
+    /// Handles the confirmation of pending email changes
+    ///
+    /// # Arguments
+    /// * `request` - Request containing the confirmation link token
+    ///
+    /// # Returns
+    /// Empty response once the email is changed
+    async fn confirm_email_change(
+        &self, #[cfg(feature = "mailer")] request: Request<ConfirmEmailChangeRequest>,
+        #[cfg(not(feature = "mailer"))] _request: Request<ConfirmEmailChangeRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        #[cfg(not(feature = "mailer"))]
+        unimplemented!("Email functionality is disabled");
+        #[cfg(feature = "mailer")]
+        confirm_email_change::confirm_email_change(self, request).await
+    }
+
+    /// Handles the cancellation of pending email changes
+    ///
+    /// # Arguments
+    /// * `request` - Request containing the cancel link token
+    ///
+    /// # Returns
+    /// Empty response once the change is cancelled
+    async fn cancel_email_change(
+        &self, #[cfg(feature = "mailer")] request: Request<CancelEmailChangeRequest>,
+        #[cfg(not(feature = "mailer"))] _request: Request<CancelEmailChangeRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        #[cfg(not(feature = "mailer"))]
+        unimplemented!("Email functionality is disabled");
+        #[cfg(feature = "mailer")]
+        cancel_email_change::cancel_email_change(self, request).await
+    }
+
+    /// Handles the verification of secondary email addresses
+    ///
+    /// # Arguments
+    /// * `request` - Request containing the verification link token
+    ///
+    /// # Returns
+    /// Empty response once the address is verified
+    async fn verify_email(
+        &self, #[cfg(feature = "mailer")] request: Request<VerifyEmailRequest>,
+        #[cfg(not(feature = "mailer"))] _request: Request<VerifyEmailRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        #[cfg(not(feature = "mailer"))]
+        unimplemented!("Email functionality is disabled");
+        #[cfg(feature = "mailer")]
+        verify_email::verify_email(self, request).await
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,134 @@
+// services/auth/reactivate_account.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use kiro_api::auth::v1::ReactivateAccountRequest;
+use tonic::{Request, Response, Status};
+
+use crate::utils::reactivation::consume_reactivation_link;
+
+/// Account reactivation service implementation
+///
+/// # Description
+/// Reactivates a disabled account from the link emailed by `request_reactivation`
+///
+/// # Arguments
+/// * `service` - The auth service instance
+/// * `request` - Request containing the token of the reactivation link
+///
+/// # Returns
+/// * `Ok(Response)` - Empty response once the account is reactivated
+/// * `Err(Status)` - Error status with description
+///
+/// # Errors
+/// * `Status::not_found` - Unknown link
+/// * `Status::invalid_argument` - The link is not a reactivation link
+/// * `Status::failed_precondition` - The link has expired
+/// * `Status::internal` - Database or internal error
+///
+/// # Example
+/// ```rust,no_run
+/// use tonic::{Request, Response, Status};
+/// use kiro_api::auth::v1::{auth_service_server::AuthService, ReactivateAccountRequest};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = kiro_client::AuthService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Reactivation request
+/// let request = Request::new(ReactivateAccountRequest {
+///     temp_token: "temp_token".to_string(),
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     AuthService::reactivate_account(&service, request).await;
+///
+///     println!("Account reactivated");
+/// });
+/// ```
+pub async fn reactivate_account(
+    service: &AuthService, request: Request<ReactivateAccountRequest>,
+) -> Result<Response<Empty>, Status> {
+    consume_reactivation_link(&service.db, &request.get_ref().temp_token).await?;
+
+    Ok(Response::new(Empty {}))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::{db_bridge::MockDatabaseOperations, DbDateTime, DbId};
+    use kiro_mailer::{LinkModel, LinkType};
+
+    #[tokio::test]
+    async fn test_reactivate_account_expired_link() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<LinkModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(LinkModel {
+                    id: DbId::from(("links", "link")),
+                    user: DbId::from(("users", "user")),
+                    link_type: LinkType::AccountReactivation,
+                    expiry: DbDateTime::from(chrono::Utc::now() - chrono::Duration::hours(1)),
+                }))
+            });
+
+        mock_db.expect_update_field::<bool>().never();
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Request::new(ReactivateAccountRequest {
+            temp_token: "link".to_string(),
+        });
+
+        let error = reactivate_account(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::FailedPrecondition);
+    }
+
+    #[tokio::test]
+    async fn test_reactivate_account_unknown_link() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<LinkModel>()
+            .times(1)
+            .returning(|_| Ok(None));
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Request::new(ReactivateAccountRequest {
+            temp_token: "unknown".to_string(),
+        });
+
+        let error = reactivate_account(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::NotFound);
+    }
+}
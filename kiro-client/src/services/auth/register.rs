@@ -18,17 +18,29 @@ use super::*;
 
 use kiro_api::google::protobuf::Timestamp;
 use kiro_database::db_bridge::DatabaseOperations;
-use tonic::{Request, Response, Status};
+use tonic::{Code, Request, Response, Status};
 
 use crate::{
-    utils::{ip::get_ip_from_md, password::valid_password},
-    CreateUserModel, SessionModel, UserModel,
+    captcha::{captcha_policy, get_captcha_token_from_md},
+    error::ClientError,
+    keyed_status,
+    utils::{
+        consent::check_accepted_policies,
+        ip::get_ip_from_md,
+        password::valid_password,
+        registration::{check_registration, redeem_invitation},
+    },
+    CreateUserModel, PolicyConsentModel, PrivacyPolicy, SessionModel, UserModel,
 };
 
 /// Register service implementation
 ///
 /// # Description
-/// Registers a new user with the system
+/// Registers a new user with the system, who accepts the current terms of
+/// service and privacy policy in the versions listed in the request. The
+/// registration is checked against the registration policy, and the invitation
+/// code, if any, is redeemed. A CAPTCHA token is required in the
+/// `x-captcha-token` metadata when CAPTCHA is enabled.
 ///
 /// # Arguments
 /// * `service` - The authentication service instance
@@ -39,7 +51,10 @@ use crate::{
 /// * `Err(Status)` - Appropriate error status on failure
 ///
 /// # Errors
-/// * `Status::InvalidArgument` - Invalid password format
+/// * `Status::InvalidArgument` - Invalid password or email format
+/// * `Status::PermissionDenied` - Registration rejected by the registration policy, or CAPTCHA failed
+/// * `Status::FailedPrecondition` - CAPTCHA token missing
+/// * `Status::Unavailable` - CAPTCHA provider unreachable
 /// * `Status::Internal` - Database error
 ///
 /// # Example
@@ -59,7 +74,9 @@ use crate::{
 /// // Register request
 /// let request = Request::new(AuthRequest {
 ///     email: "user@example.com".to_string(),
-///     password: "password123!".to_string()
+///     password: "password123!".to_string(),
+///     invitation_code: None,
+///     accepted_policies: vec![],
 /// });
 ///
 /// // Async block to allow `await`
@@ -73,7 +90,8 @@ pub async fn register(
     service: &AuthService, request: Request<AuthRequest>,
 ) -> Result<Response<Session>, Status> {
     // Extract IP address from request metadata
-    let ip_address = get_ip_from_md(request.metadata()).unwrap_or_else(|| "unknown".to_string());
+    let ip_address = get_ip_from_md(request.metadata());
+    let captcha_token = get_captcha_token_from_md(request.metadata());
 
     let request = request.into_inner();
 
@@ -82,6 +100,23 @@ pub async fn register(
         return Err(Status::invalid_argument(e.to_string()));
     }
 
+    // Verify the CAPTCHA
+    captcha_policy()
+        .verify(captcha_token.as_deref(), ip_address.as_deref())
+        .await
+        .map_err(ClientError::from)?;
+
+    // Check the registration policy
+    let invitation = check_registration(
+        &service.db,
+        &request.email,
+        request.invitation_code.as_deref(),
+    )
+    .await?;
+
+    // Check the policy versions shown to the user
+    let policies = check_accepted_policies(&service.db, &request.accepted_policies).await?;
+
     // Check if email is already in use
     match UserModel::check_email(&service.db, request.email.clone()).await {
         Ok(true) => {}
@@ -104,13 +139,27 @@ pub async fn register(
         .await?
         .into_iter()
         .next()
-        .ok_or_else(|| Status::internal("Failed to create user"))?;
+        .ok_or_else(|| keyed_status(Code::Internal, "user.creation_failed"))?;
+
+    // Grant the role and group of the invitation
+    if let Some(invitation) = &invitation {
+        redeem_invitation(&service.db, invitation, &user).await?;
+    }
+
+    // Record the acceptance of the policy documents shown to the user
+    PolicyConsentModel::record_all(&service.db, user.id.clone(), &policies, ip_address.clone())
+        .await?;
 
     // Create session
-    let session =
-        SessionModel::create_session(&service.db, user.id.clone(), false, Some(ip_address))
-            .await
-            .map_err(|e| Status::internal(e.to_string()))?;
+    let session = SessionModel::create_session(
+        &service.db,
+        user.id.clone(),
+        false,
+        Some(ip_address.unwrap_or_else(|| "unknown".to_string())),
+        &PrivacyPolicy::for_user(&user),
+    )
+    .await
+    .map_err(|e| Status::internal(e.to_string()))?;
 
     // Generate refresh token
     let refresh_token = SessionModel::generate_refresh_token(session.user_id)
@@ -132,7 +181,11 @@ pub async fn register(
 mod tests {
     use super::*;
 
-    use crate::{CreateSessionModel, SessionModel};
+    use crate::{
+        CreatePolicyConsentModel, CreateSessionModel, InvitationModel, PolicyConsentModel,
+        PolicyDocumentModel, SessionModel, UserEmailModel,
+    };
+    use kiro_api::auth::v1::AcceptedPolicy;
     use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
     use mockall::predicate::{always, eq};
 
@@ -147,12 +200,34 @@ mod tests {
             .times(1)
             .returning(|_, _, _, _| Ok(vec![]));
 
+        mock_db
+            .expect_read_by_field::<UserEmailModel>()
+            .with(
+                eq("user_emails"),
+                eq("email"),
+                eq("test@example.com"),
+                eq(None),
+            )
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
         mock_db
             .expect_create::<CreateUserModel, UserModel>()
             .with(eq("users"), always())
             .times(1)
             .returning(move |_, _| Ok(vec![user.clone()]));
 
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![PolicyDocumentModel::default()]));
+
+        mock_db
+            .expect_create::<CreatePolicyConsentModel, PolicyConsentModel>()
+            .withf(|table, data| table == "policy_consents" && data.version == 1)
+            .times(1)
+            .returning(|_, _| Ok(vec![PolicyConsentModel::default()]));
+
         let session = SessionModel::default();
         mock_db
             .expect_create::<CreateSessionModel, SessionModel>()
@@ -167,6 +242,11 @@ mod tests {
         let request = Request::new(AuthRequest {
             email: "test@example.com".to_string(),
             password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![AcceptedPolicy {
+                kind: "Terms".to_string(),
+                version: 1,
+            }],
         });
 
         let response = register(&service, request).await.unwrap().into_inner();
@@ -186,6 +266,8 @@ mod tests {
         let request = Request::new(AuthRequest {
             email: "test@example.com".to_string(),
             password: "invalid".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
         });
 
         let error = register(&service, request).await.unwrap_err();
@@ -197,6 +279,54 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_register_disposable_email() {
+        let mock_db = MockDatabaseOperations::new();
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Request::new(AuthRequest {
+            email: "test@mailinator.com".to_string(),
+            password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
+        });
+
+        let error = register(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::PermissionDenied);
+        assert_eq!(
+            error.message(),
+            "Disposable email addresses are not accepted"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_register_invalid_invitation() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<InvitationModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Request::new(AuthRequest {
+            email: "test@example.com".to_string(),
+            password: "Password123!".to_string(),
+            invitation_code: Some("unknown".to_string()),
+            accepted_policies: vec![],
+        });
+
+        let error = register(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::PermissionDenied);
+        assert_eq!(error.message(), "Invitation code is invalid or expired");
+    }
+
     #[tokio::test]
     async fn test_register_email_in_use() {
         let mut mock_db = MockDatabaseOperations::new();
@@ -208,6 +338,11 @@ mod tests {
             .times(1)
             .returning(move |_, _, _, _| Ok(vec![user.clone()]));
 
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
+
         let service = AuthService {
             db: Database::Mock(mock_db),
         };
@@ -215,6 +350,8 @@ mod tests {
         let request = Request::new(AuthRequest {
             email: "test@example.com".to_string(),
             password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
         });
 
         let error = register(&service, request).await.unwrap_err();
@@ -232,12 +369,28 @@ mod tests {
             .times(1)
             .returning(|_, _, _, _| Ok(vec![]));
 
+        mock_db
+            .expect_read_by_field::<UserEmailModel>()
+            .with(
+                eq("user_emails"),
+                eq("email"),
+                eq("test@example.com"),
+                eq(None),
+            )
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
         mock_db
             .expect_create::<CreateUserModel, UserModel>()
             .with(eq("users"), always())
             .times(1)
             .returning(|_, _| Err(DatabaseError::Internal("Failed to create user".to_string())));
 
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
+
         let service = AuthService {
             db: Database::Mock(mock_db),
         };
@@ -245,6 +398,8 @@ mod tests {
         let request = Request::new(AuthRequest {
             email: "test@example.com".to_string(),
             password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
         });
 
         let error = register(&service, request).await.unwrap_err();
@@ -263,12 +418,28 @@ mod tests {
             .times(1)
             .returning(|_, _, _, _| Ok(vec![]));
 
+        mock_db
+            .expect_read_by_field::<UserEmailModel>()
+            .with(
+                eq("user_emails"),
+                eq("email"),
+                eq("test@example.com"),
+                eq(None),
+            )
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
         mock_db
             .expect_create::<CreateUserModel, UserModel>()
             .with(eq("users"), always())
             .times(1)
             .returning(move |_, _| Ok(vec![user.clone()]));
 
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
+
         mock_db
             .expect_create::<CreateSessionModel, SessionModel>()
             .with(eq("sessions"), always())
@@ -286,10 +457,74 @@ mod tests {
         let request = Request::new(AuthRequest {
             email: "test@example.com".to_string(),
             password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
         });
 
         let error = register(&service, request).await.unwrap_err();
         assert_eq!(error.code(), tonic::Code::Internal);
         assert_eq!(error.message(), "Internal error: Failed to create session");
     }
+
+    #[tokio::test]
+    async fn test_register_outdated_policy() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(vec![PolicyDocumentModel {
+                    version: 2,
+                    ..Default::default()
+                }])
+            });
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Request::new(AuthRequest {
+            email: "test@example.com".to_string(),
+            password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![AcceptedPolicy {
+                kind: "Terms".to_string(),
+                version: 1,
+            }],
+        });
+
+        let error = register(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+        assert_eq!(error.message(), "Policy version is not current");
+    }
+
+    #[tokio::test]
+    async fn test_register_policy_not_accepted() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![PolicyDocumentModel::default()]));
+
+        mock_db
+            .expect_create::<CreateUserModel, UserModel>()
+            .never();
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Request::new(AuthRequest {
+            email: "test@example.com".to_string(),
+            password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
+        });
+
+        let error = register(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::FailedPrecondition);
+        assert_eq!(error.message(), "Policy acceptance required");
+    }
 }
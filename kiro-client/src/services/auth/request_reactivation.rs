@@ -0,0 +1,158 @@
+// services/auth/request_reactivation.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use tonic::{Code, Request, Response, Status};
+
+use crate::{backends::authenticate, keyed_status, utils::reactivation::send_reactivation_link};
+
+/// Reactivation request service implementation
+///
+/// # Description
+/// Authenticates the owner of a disabled account and emails them a link to
+/// reactivate it
+///
+/// # Arguments
+/// * `service` - The auth service instance
+/// * `request` - Request containing email and password
+///
+/// # Returns
+/// * `Ok(Response)` - Empty response once the link is sent
+/// * `Err(Status)` - Error status with description
+///
+/// # Errors
+/// * `Status::invalid_argument` - Invalid password format
+/// * `Status::not_found` - User not found
+/// * `Status::permission_denied` - Invalid password
+/// * `Status::failed_precondition` - Account is not disabled
+/// * `Status::unavailable` - Directory server unreachable
+/// * `Status::internal` - Database, mailer or internal error
+///
+/// # Example
+/// ```rust,no_run
+/// use tonic::{Request, Response, Status};
+/// use kiro_api::auth::v1::{auth_service_server::AuthService, AuthRequest};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = kiro_client::AuthService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Reactivation request
+/// let request = Request::new(AuthRequest {
+///     email: "user@example.com".to_string(),
+///     password: "password123!".to_string(),
+///     invitation_code: None,
+///     accepted_policies: vec![],
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     AuthService::request_reactivation(&service, request).await;
+///
+///     println!("Reactivation link sent");
+/// });
+/// ```
+pub async fn request_reactivation(
+    service: &AuthService, request: Request<AuthRequest>,
+) -> Result<Response<Empty>, Status> {
+    let request = request.into_inner();
+
+    // Authenticate with the configured backends
+    let user = authenticate(&service.db, &request.email, &request.password).await?;
+
+    if user.activated {
+        return Err(keyed_status(
+            Code::FailedPrecondition,
+            "account.not_disabled",
+        ));
+    }
+
+    send_reactivation_link(&service.db, &user).await?;
+
+    Ok(Response::new(Empty {}))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::UserModel;
+    use kiro_database::db_bridge::MockDatabaseOperations;
+    use kiro_mailer::{CreateLinkModel, LinkModel};
+
+    #[tokio::test]
+    async fn test_request_reactivation_active_account() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![UserModel::default()]));
+
+        mock_db
+            .expect_create::<CreateLinkModel, LinkModel>()
+            .never();
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Request::new(AuthRequest {
+            email: "test@example.com".to_string(),
+            password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
+        });
+
+        let error = request_reactivation(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::FailedPrecondition);
+    }
+
+    #[tokio::test]
+    async fn test_request_reactivation_invalid_password() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(vec![UserModel {
+                    activated: false,
+                    ..Default::default()
+                }])
+            });
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Request::new(AuthRequest {
+            email: "test@example.com".to_string(),
+            password: "WrongPassword123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
+        });
+
+        let error = request_reactivation(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::PermissionDenied);
+    }
+}
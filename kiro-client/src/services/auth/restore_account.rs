@@ -0,0 +1,213 @@
+// services/auth/restore_account.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use kiro_api::google::protobuf::Timestamp;
+use tonic::{Request, Response, Status};
+
+use crate::{
+    backends::authenticate, utils::ip::get_ip_from_md, PrivacyPolicy, SessionModel, UserModel,
+};
+
+/// Account restoration service implementation
+///
+/// # Description
+/// Authenticates a user whose account is scheduled for deletion, cancels the
+/// deletion and creates a new session
+///
+/// # Arguments
+/// * `service` - The auth service instance
+/// * `request` - Restore request containing email and password
+///
+/// # Returns
+/// * `Ok(Response)` - Response containing session token and expiry
+/// * `Err(Status)` - Error status with description
+///
+/// # Errors
+/// * `Status::invalid_argument` - Invalid password format
+/// * `Status::not_found` - User not found
+/// * `Status::permission_denied` - Invalid password or account disabled
+/// * `Status::unavailable` - Directory server unreachable
+/// * `Status::internal` - Database or internal error
+///
+/// # Example
+/// ```rust,no_run
+/// use tonic::{Request, Response, Status};
+/// use kiro_api::auth::v1::{auth_service_server::AuthService, AuthRequest};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = kiro_client::AuthService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Restore request
+/// let request = Request::new(AuthRequest {
+///     email: "user@example.com".to_string(),
+///     password: "password123!".to_string(),
+///     invitation_code: None,
+///     accepted_policies: vec![],
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     AuthService::restore_account(&service, request).await;
+///
+///     println!("Account restored");
+/// });
+/// ```
+pub async fn restore_account(
+    service: &AuthService, request: Request<AuthRequest>,
+) -> Result<Response<Session>, Status> {
+    // Extract IP address from request metadata
+    let ip_address = get_ip_from_md(request.metadata()).unwrap_or_else(|| "unknown".to_string());
+
+    let request = request.into_inner();
+
+    // Authenticate with the configured backends
+    let user = authenticate(&service.db, &request.email, &request.password).await?;
+
+    // Disabled accounts must be reactivated first
+    user.check_active()?;
+
+    // Cancel the scheduled deletion
+    if user.deletion_scheduled_at.is_some() {
+        UserModel::cancel_deletion(&service.db, user.id.clone()).await?;
+    }
+
+    // Create or get existing session
+    let session = SessionModel::get_session_by_user_id(
+        &service.db,
+        user.id.clone(),
+        ip_address,
+        &PrivacyPolicy::for_user(&user),
+    )
+    .await
+    .map_err(|e| Status::internal(format!("Session creation failed: {}", e)))?;
+
+    // Generate refresh token
+    let refresh_token = SessionModel::generate_refresh_token(session.user_id)
+        .await
+        .map_err(|e| Status::internal(format!("Refresh token generation failed: {}", e)))?;
+
+    let expire_date: Option<Timestamp> = Some(Timestamp {
+        seconds: (chrono::Utc::now() + chrono::Duration::days(2)).timestamp(),
+        nanos: 0,
+    });
+
+    Ok(Response::new(Session {
+        token: refresh_token.1,
+        expire_date,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{CreateSessionModel, SessionModel, UserModel};
+    use kiro_database::{db_bridge::MockDatabaseOperations, DbDateTime, DbId};
+    use mockall::predicate::{always, eq};
+
+    #[tokio::test]
+    async fn test_restore_account_success() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let test_user = UserModel {
+            deletion_scheduled_at: Some(DbDateTime::now()),
+            ..Default::default()
+        };
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .with(eq("users"), eq("email"), eq("test@example.com"), eq(None))
+            .times(1)
+            .returning(move |_, _, _, _| Ok(vec![test_user.clone()]));
+
+        mock_db
+            .expect_update_field::<Option<DbDateTime>>()
+            .withf(|id, field, value| {
+                *id == DbId::default() && field == "deletion_scheduled_at" && value.is_none()
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        mock_db
+            .expect_read_by_field_thing::<SessionModel>()
+            .with(eq("sessions"), eq("user_id"), eq(DbId::default()), eq(None))
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        let session = SessionModel::default();
+        mock_db
+            .expect_create::<CreateSessionModel, SessionModel>()
+            .with(eq("sessions"), always())
+            .times(1)
+            .return_once(move |_, _| Ok(vec![session]));
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Request::new(AuthRequest {
+            email: "test@example.com".to_string(),
+            password: "Password123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
+        });
+
+        let response = restore_account(&service, request)
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert!(!response.token.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_restore_account_invalid_password() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(vec![UserModel {
+                    deletion_scheduled_at: Some(DbDateTime::now()),
+                    ..Default::default()
+                }])
+            });
+
+        mock_db.expect_update_field::<Option<DbDateTime>>().never();
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Request::new(AuthRequest {
+            email: "test@example.com".to_string(),
+            password: "WrongPassword123!".to_string(),
+            invitation_code: None,
+            accepted_policies: vec![],
+        });
+
+        let error = restore_account(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::PermissionDenied);
+    }
+}
@@ -0,0 +1,123 @@
+// services/auth/verify_email.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use kiro_api::auth::v1::VerifyEmailRequest;
+use tonic::{Request, Response, Status};
+
+use crate::utils::secondary_email::consume_email_verification_link;
+
+/// Email verification service implementation
+///
+/// # Description
+/// Verifies a secondary email address from the link emailed to it by
+/// `add_email`. The availability of the address is checked again at this moment.
+///
+/// # Arguments
+/// * `service` - The auth service instance
+/// * `request` - Request containing the token of the verification link
+///
+/// # Returns
+/// * `Ok(Response)` - Empty response once the address is verified
+/// * `Err(Status)` - Error status with description
+///
+/// # Errors
+/// * `Status::not_found` - Unknown link, or the address was removed
+/// * `Status::invalid_argument` - The link is not a verification link
+/// * `Status::failed_precondition` - The link has expired
+/// * `Status::already_exists` - The email was verified by another account in the meantime
+/// * `Status::internal` - Database or internal error
+///
+/// # Example
+/// ```rust,no_run
+/// use tonic::{Request, Response, Status};
+/// use kiro_api::auth::v1::{auth_service_server::AuthService, VerifyEmailRequest};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = kiro_client::AuthService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Verification request
+/// let request = Request::new(VerifyEmailRequest {
+///     temp_token: "temp_token".to_string(),
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     AuthService::verify_email(&service, request).await;
+///
+///     println!("Email verified");
+/// });
+/// ```
+pub async fn verify_email(
+    service: &AuthService, request: Request<VerifyEmailRequest>,
+) -> Result<Response<Empty>, Status> {
+    consume_email_verification_link(&service.db, &request.get_ref().temp_token).await?;
+
+    Ok(Response::new(Empty {}))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::{db_bridge::MockDatabaseOperations, DbDateTime, DbId};
+    use kiro_mailer::{LinkModel, LinkType};
+
+    use crate::UserEmailModel;
+
+    #[tokio::test]
+    async fn test_verify_email_removed() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<LinkModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(LinkModel {
+                    id: DbId::from(("links", "link")),
+                    user: DbId::from(("users", "user")),
+                    link_type: LinkType::EmailVerification,
+                    expiry: DbDateTime::from(chrono::Utc::now() + chrono::Duration::hours(1)),
+                }))
+            });
+
+        // The address was removed before the link was followed
+        mock_db
+            .expect_read_where::<UserEmailModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
+
+        mock_db.expect_delete().times(1).returning(|_| Ok(Some(())));
+
+        let service = AuthService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Request::new(VerifyEmailRequest {
+            temp_token: "link".to_string(),
+        });
+
+        let error = verify_email(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::NotFound);
+    }
+}
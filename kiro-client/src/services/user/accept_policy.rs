@@ -0,0 +1,149 @@
+// services/user/accept_policy.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use tonic::{Code, Request, Response, Status};
+
+use crate::{
+    keyed_status,
+    utils::{consent, ip::get_ip_from_md},
+    SessionModel,
+};
+
+/// Accepts the current version of a policy document
+///
+/// Records the acceptance with the IP address of the request. Sessions are
+/// rejected with `FAILED_PRECONDITION` until the current versions are accepted.
+///
+/// # Arguments
+///
+/// * `service` - The UserService instance
+/// * `request` - The request containing the kind and version of the document
+///
+/// # Returns
+///
+/// Returns the recorded consent on success
+///
+/// # Errors
+///
+/// Returns Status::unauthenticated if no valid session is found
+/// Returns Status::invalid_argument if the version is not the current one
+/// Returns Status::internal for database errors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tonic::{Request, Response, Status};
+/// use kiro_api::client::v1::{client_service_server::ClientService, AcceptPolicyRequest};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = kiro_client::ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Accept policy request
+/// let request = Request::new(AcceptPolicyRequest {
+///     kind: "Terms".to_string(),
+///     version: 2,
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     ClientService::accept_policy(&service, request).await;
+///
+///     println!("Policy accepted");
+/// });
+/// ```
+pub async fn accept_policy(
+    service: &ClientService, request: Request<AcceptPolicyRequest>,
+) -> Result<Response<Consent>, Status> {
+    // Get authenticated session from request extensions
+    let session = request
+        .extensions()
+        .get::<SessionModel>()
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
+
+    let ip_address = get_ip_from_md(request.metadata());
+
+    let consent = consent::accept_policy(
+        &service.db,
+        session.user_id.clone(),
+        &request.get_ref().kind,
+        request.get_ref().version,
+        ip_address,
+    )
+    .await?;
+
+    Ok(Response::new(consent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::db_bridge::MockDatabaseOperations;
+
+    use crate::PolicyDocumentModel;
+
+    #[tokio::test]
+    async fn test_accept_policy_no_session() {
+        let service = ClientService {
+            db: Database::Mock(MockDatabaseOperations::new()),
+        };
+
+        let request = Request::new(AcceptPolicyRequest {
+            kind: "Terms".to_string(),
+            version: 1,
+        });
+
+        let error = accept_policy(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::Unauthenticated);
+    }
+
+    #[tokio::test]
+    async fn test_accept_policy_outdated_version() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(vec![PolicyDocumentModel {
+                    version: 2,
+                    ..Default::default()
+                }])
+            });
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut request = Request::new(AcceptPolicyRequest {
+            kind: "Terms".to_string(),
+            version: 1,
+        });
+        request.extensions_mut().insert(SessionModel::default());
+
+        let error = accept_policy(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+        assert_eq!(error.message(), "Policy version is not current");
+    }
+}
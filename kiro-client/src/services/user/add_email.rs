@@ -0,0 +1,161 @@
+// services/user/add_email.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use kiro_database::db_bridge::DatabaseOperations;
+use tonic::{Code, Request, Response, Status};
+
+use crate::{
+    keyed_status, models::UserModel, utils::secondary_email::add_secondary_email, SessionModel,
+};
+
+/// Attaches a secondary email address to the user
+///
+/// The address is attached unverified and a verification link is emailed to it.
+/// Once verified through `AuthService::verify_email`, the address can be used to
+/// log in and to recover the account.
+///
+/// # Arguments
+///
+/// * `service` - The UserService instance providing database access
+/// * `request` - The gRPC request containing the address
+///
+/// # Returns
+///
+/// * `Ok(Response)` - The attached address
+/// * `Err(Status)` - Various error conditions with appropriate status codes
+///
+/// # Errors
+///
+/// Returns Status::unauthenticated if no valid session is found
+/// Returns Status::already_exists if the email is already attached or in use
+/// Returns Status::internal for database and mailer errors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tonic::{Request, Response, Status};
+/// use kiro_api::client::v1::{client_service_server::ClientService, AddEmailRequest};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = kiro_client::ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Add email request
+/// let request = Request::new(AddEmailRequest {
+///     email: "backup@example.com".to_string(),
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     ClientService::add_email(&service, request).await;
+///
+///     println!("Email added");
+/// });
+/// ```
+pub async fn add_email(
+    service: &ClientService, request: Request<AddEmailRequest>,
+) -> Result<Response<UserEmail>, Status> {
+    // Get authenticated session
+    let session = request
+        .extensions()
+        .get::<SessionModel>()
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
+
+    // Get user details
+    let user = match service
+        .db
+        .select::<UserModel>(session.user_id.clone())
+        .await
+    {
+        Ok(Some(user)) => user,
+        Ok(None) => return Err(keyed_status(Code::NotFound, "user.not_found")),
+        Err(e) => return Err(Status::internal(format!("Database error: {}", e))),
+    };
+
+    let address = add_secondary_email(&service.db, &user, &request.get_ref().email).await?;
+
+    Ok(Response::new(UserEmail::from(&address)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::{db_bridge::MockDatabaseOperations, DbId};
+
+    use crate::UserEmailModel;
+
+    fn request(email: &str) -> Request<AddEmailRequest> {
+        let mut request = Request::new(AddEmailRequest {
+            email: email.to_string(),
+        });
+        request.extensions_mut().insert(SessionModel::default());
+        request
+    }
+
+    #[tokio::test]
+    async fn test_add_email_no_session() {
+        let service = ClientService {
+            db: Database::Mock(MockDatabaseOperations::new()),
+        };
+
+        let error = add_email(
+            &service,
+            Request::new(AddEmailRequest {
+                email: "backup@example.com".to_string(),
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error.code(), tonic::Code::Unauthenticated);
+    }
+
+    #[tokio::test]
+    async fn test_add_email_already_attached() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_read_by_field_thing::<UserEmailModel>()
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(vec![UserEmailModel {
+                    user: DbId::default(),
+                    ..Default::default()
+                }])
+            });
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let error = add_email(&service, request("backup@example.com"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), tonic::Code::AlreadyExists);
+    }
+}
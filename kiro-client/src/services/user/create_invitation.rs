@@ -0,0 +1,161 @@
+// services/user/create_invitation.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use kiro_api::client::v1::{CreateInvitationRequest, Invitation};
+use tonic::{Code, Request, Response, Status};
+
+use crate::{keyed_status, utils::registration::issue_invitation, SessionModel};
+
+/// Creates an invitation code
+///
+/// The code lets one user register while the registration is invite-only, and
+/// may be restricted to an email address, expire, grant the admin role or add
+/// the user to a group.
+///
+/// # Arguments
+///
+/// * `service` - The UserService instance
+/// * `request` - The request containing the restrictions and grants of the invitation
+///
+/// # Returns
+///
+/// Returns the created invitation, holding the code to send to the invited user
+///
+/// # Errors
+///
+/// Returns Status::unauthenticated if no valid session is found
+/// Returns Status::permission_denied if the session is not an admin session
+/// Returns Status::not_found if the group does not exist
+/// Returns Status::invalid_argument if the expiry is not in the future
+/// Returns Status::internal for database errors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tonic::{Request, Response, Status};
+/// use kiro_api::client::v1::{client_service_server::ClientService, CreateInvitationRequest};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = kiro_client::ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Create invitation request
+/// let request = Request::new(CreateInvitationRequest {
+///     email: Some("user@example.com".to_string()),
+///     is_admin: false,
+///     group_id: None,
+///     expires_at: None,
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     ClientService::create_invitation(&service, request).await;
+///
+///     println!("Invitation created");
+/// });
+/// ```
+pub async fn create_invitation(
+    service: &ClientService, request: Request<CreateInvitationRequest>,
+) -> Result<Response<Invitation>, Status> {
+    // Get authenticated session from request extensions
+    let session = request
+        .extensions()
+        .get::<SessionModel>()
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
+
+    if !session.is_admin {
+        return Err(keyed_status(Code::PermissionDenied, "auth.admin_required"));
+    }
+
+    let invitation = issue_invitation(&service.db, request.into_inner()).await?;
+
+    Ok(Response::new(Invitation::from(&invitation)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::db_bridge::MockDatabaseOperations;
+
+    use crate::{CreateInvitationModel, InvitationModel};
+
+    #[tokio::test]
+    async fn test_create_invitation_success() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_create::<CreateInvitationModel, InvitationModel>()
+            .withf(|table, data| {
+                table == "invitations"
+                    && data.email == Some("user@example.com".to_string())
+                    && data.is_admin
+            })
+            .times(1)
+            .returning(|_, data| {
+                Ok(vec![InvitationModel {
+                    code: data.code,
+                    email: data.email,
+                    is_admin: data.is_admin,
+                    ..Default::default()
+                }])
+            });
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut request = Request::new(CreateInvitationRequest {
+            email: Some("user@example.com".to_string()),
+            is_admin: true,
+            group_id: None,
+            expires_at: None,
+        });
+        request.extensions_mut().insert(SessionModel {
+            is_admin: true,
+            ..Default::default()
+        });
+
+        let invitation = create_invitation(&service, request)
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert!(!invitation.code.is_empty());
+        assert_eq!(invitation.email, Some("user@example.com".to_string()));
+        assert!(invitation.is_admin);
+    }
+
+    #[tokio::test]
+    async fn test_create_invitation_requires_admin() {
+        let service = ClientService {
+            db: Database::Mock(MockDatabaseOperations::new()),
+        };
+
+        let mut request = Request::new(CreateInvitationRequest::default());
+        request.extensions_mut().insert(SessionModel::default());
+
+        let error = create_invitation(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::PermissionDenied);
+    }
+}
@@ -16,13 +16,15 @@
 
 use super::*;
 
-use kiro_database::db_bridge::DatabaseOperations;
-use tonic::{Request, Response, Status};
+use tonic::{Code, Request, Response, Status};
 
-use crate::SessionModel;
+use crate::{keyed_status, SessionModel, UserModel};
 
 /// Deletes a user account
 ///
+/// The account is purged once the grace period is over, logging in again
+/// before offers to restore it. Every session of the user is revoked.
+///
 /// # Arguments
 ///
 /// * `service` - The UserService instance
@@ -70,14 +72,12 @@ pub async fn delete_user(
     let session = request
         .extensions()
         .get::<SessionModel>()
-        .ok_or_else(|| Status::unauthenticated("No valid session found"))?;
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
 
-    // Delete the user account
-    service
-        .db
-        .delete(session.user_id.clone())
+    // Schedule the deletion of the user account
+    UserModel::schedule_deletion(&service.db, session.user_id.clone())
         .await
-        .map_err(|_| Status::internal("Failed to delete user account"))?;
+        .map_err(|_| keyed_status(Code::Internal, "user.deletion_failed"))?;
 
     Ok(Response::new(Empty {}))
 }
@@ -86,8 +86,7 @@ pub async fn delete_user(
 mod tests {
     use super::*;
 
-    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
-    use mockall::predicate::eq;
+    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError, DbDateTime};
 
     #[tokio::test]
     async fn test_delete_user_success() {
@@ -97,10 +96,18 @@ mod tests {
 
         // Expect delete operation to be called with the user's ID
         mock_db
-            .expect_delete()
-            .with(eq(user_id))
+            .expect_update_field::<Option<DbDateTime>>()
+            .withf(move |id, field, value| {
+                *id == user_id && field == "deletion_scheduled_at" && value.is_some()
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        // Every session of the user is revoked
+        mock_db
+            .expect_delete_where::<serde_json::Value>()
             .times(1)
-            .returning(|_| Ok(Some(())));
+            .returning(|_, _| Ok(0));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -136,10 +143,9 @@ mod tests {
 
         // Simulate database error
         mock_db
-            .expect_delete()
-            .with(eq(user_id))
+            .expect_update_field::<Option<DbDateTime>>()
             .times(1)
-            .returning(|_| Err(DatabaseError::Internal("Database error".to_string())));
+            .returning(|_, _, _| Err(DatabaseError::Internal("Database error".to_string())));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -161,10 +167,18 @@ mod tests {
         let user_id = admin_session.user_id.clone();
 
         mock_db
-            .expect_delete()
-            .with(eq(user_id))
+            .expect_update_field::<Option<DbDateTime>>()
+            .withf(move |id, field, value| {
+                *id == user_id && field == "deletion_scheduled_at" && value.is_some()
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        // Every session of the user is revoked
+        mock_db
+            .expect_delete_where::<serde_json::Value>()
             .times(1)
-            .returning(|_| Ok(Some(())));
+            .returning(|_, _| Ok(0));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -16,10 +16,9 @@
 
 use super::*;
 
-use kiro_database::db_bridge::DatabaseOperations;
-use tonic::{Request, Response, Status};
+use tonic::{Code, Request, Response, Status};
 
-use crate::SessionModel;
+use crate::{keyed_status, SessionModel, UserModel};
 
 /// Disables a user account
 ///
@@ -70,10 +69,15 @@ pub async fn disable_user(
     let session = request
         .extensions()
         .get::<SessionModel>()
-        .ok_or_else(|| Status::unauthenticated("No valid session found"))?;
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
 
     // Disable the user account
-    service.db.delete_soft(session.user_id.clone()).await?;
+    UserModel::disable(
+        &service.db,
+        session.user_id.clone(),
+        Some(session.user_id.clone()),
+    )
+    .await?;
 
     Ok(Response::new(Empty {}))
 }
@@ -85,6 +89,8 @@ mod tests {
     use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
     use mockall::predicate::eq;
 
+    use crate::{AccountEventKind, AccountEventModel, CreateAccountEventModel};
+
     #[tokio::test]
     async fn test_disable_user_success() {
         let mut mock_db = MockDatabaseOperations::new();
@@ -98,6 +104,20 @@ mod tests {
             .times(1)
             .returning(|_| Ok(()));
 
+        mock_db
+            .expect_delete_where()
+            .withf(|table, _| table == "sessions")
+            .times(1)
+            .returning(|_, _| Ok(0));
+
+        mock_db
+            .expect_create::<CreateAccountEventModel, AccountEventModel>()
+            .withf(|table, data| {
+                table == "account_events" && data.kind == AccountEventKind::Disabled
+            })
+            .times(1)
+            .returning(|_, _| Ok(vec![AccountEventModel::default()]));
+
         let service = ClientService {
             db: Database::Mock(mock_db),
         };
@@ -161,6 +181,20 @@ mod tests {
             .times(1)
             .returning(|_| Ok(()));
 
+        mock_db
+            .expect_delete_where()
+            .withf(|table, _| table == "sessions")
+            .times(1)
+            .returning(|_, _| Ok(0));
+
+        mock_db
+            .expect_create::<CreateAccountEventModel, AccountEventModel>()
+            .withf(|table, data| {
+                table == "account_events" && data.kind == AccountEventKind::Disabled
+            })
+            .times(1)
+            .returning(|_, _| Ok(vec![AccountEventModel::default()]));
+
         let service = ClientService {
             db: Database::Mock(mock_db),
         };
@@ -185,6 +219,20 @@ mod tests {
             .times(1)
             .returning(|_| Ok(())); // No rows affected indicates already disabled
 
+        mock_db
+            .expect_delete_where()
+            .withf(|table, _| table == "sessions")
+            .times(1)
+            .returning(|_, _| Ok(0));
+
+        mock_db
+            .expect_create::<CreateAccountEventModel, AccountEventModel>()
+            .withf(|table, data| {
+                table == "account_events" && data.kind == AccountEventKind::Disabled
+            })
+            .times(1)
+            .returning(|_, _| Ok(vec![AccountEventModel::default()]));
+
         let service = ClientService {
             db: Database::Mock(mock_db),
         };
@@ -0,0 +1,291 @@
+// services/user/export_users.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use std::pin::Pin;
+
+use futures::Stream;
+use kiro_api::client::v1::{ExportUsersRequest, ExportUsersResponse};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Code, Request, Response, Status};
+
+use crate::{
+    error::ClientError,
+    keyed_status,
+    utils::user_import::{TransferFormat, UserExporter},
+    SessionModel, UserExportModel,
+};
+
+pub type ExportUsersStream =
+    Pin<Box<dyn Stream<Item = Result<ExportUsersResponse, Status>> + Send + Sync>>;
+
+/// Exports all users in bulk
+///
+/// The users are streamed page by page, as a CSV file with a header line or as
+/// a JSON Lines file, in the format accepted by `ImportUsers`. Password hashes
+/// are only exported when `include_password_hashes` is set, the stream must
+/// then be handled as a secret. Every export is recorded in the audit log
+/// before any user is read.
+///
+/// # Arguments
+///
+/// * `service` - The UserService instance
+/// * `request` - The request containing the format of the export and whether to include the password hashes
+///
+/// # Returns
+///
+/// Returns a stream of file chunks
+///
+/// # Errors
+///
+/// Returns Status::unauthenticated if no valid session is found
+/// Returns Status::permission_denied if the session is not an admin session
+/// Returns Status::invalid_argument if the format is unknown
+/// Returns Status::internal if the export could not be recorded
+/// Returns Status::internal for database errors, in the stream
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tonic::{Request, Response, Status};
+/// use kiro_api::client::v1::{client_service_server::ClientService, ExportUsersRequest};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = kiro_client::ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Export users request
+/// let request = Request::new(ExportUsersRequest {
+///     format: "jsonl".to_string(),
+///     include_password_hashes: false,
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     ClientService::export_users(&service, request).await;
+///
+///     println!("Users exported");
+/// });
+/// ```
+pub async fn export_users(
+    service: &ClientService, request: Request<ExportUsersRequest>,
+) -> Result<Response<ExportUsersStream>, Status> {
+    // Get authenticated session from request extensions
+    let session = request
+        .extensions()
+        .get::<SessionModel>()
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
+
+    if !session.is_admin {
+        return Err(keyed_status(Code::PermissionDenied, "auth.admin_required"));
+    }
+
+    let format = request
+        .get_ref()
+        .format
+        .parse::<TransferFormat>()
+        .map_err(ClientError::from)?;
+    let include_password_hashes = request.get_ref().include_password_hashes;
+
+    // Refuse the export when it cannot be audited
+    UserExportModel::record(
+        &service.db,
+        Some(session.user_id.clone()),
+        format,
+        include_password_hashes,
+    )
+    .await?;
+
+    let (tx, rx) = mpsc::channel(4);
+    let db = service.db.clone();
+
+    tokio::spawn(async move {
+        let mut exporter = UserExporter::new(&db, format, include_password_hashes);
+
+        loop {
+            let frame = match exporter.next_chunk().await {
+                Ok(Some(chunk)) => Ok(ExportUsersResponse { chunk }),
+                Ok(None) => break,
+                Err(e) => Err(Status::from(e)),
+            };
+
+            let failed = frame.is_err();
+
+            // Stop reading the users once the client is gone
+            if tx.send(frame).await.is_err() || failed {
+                break;
+            }
+        }
+    });
+
+    Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::StreamExt;
+    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
+
+    use crate::{CreateUserExportModel, UserModel};
+
+    fn admin_session() -> SessionModel {
+        SessionModel {
+            is_admin: true,
+            ..Default::default()
+        }
+    }
+
+    fn expect_audit(mock_db: &mut MockDatabaseOperations, password_hashes: bool) {
+        mock_db
+            .expect_create::<CreateUserExportModel, UserExportModel>()
+            .withf(move |table, data| {
+                table == "user_exports"
+                    && data.actor == Some(admin_session().user_id)
+                    && data.password_hashes == password_hashes
+            })
+            .times(1)
+            .returning(|_, _| Ok(vec![UserExportModel::default()]));
+    }
+
+    #[tokio::test]
+    async fn test_export_users_success() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        expect_audit(&mut mock_db, false);
+        mock_db
+            .expect_read_all::<UserModel>()
+            .times(1)
+            .returning(|_, _| Ok(vec![UserModel::default(), UserModel::default()]));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut request = Request::new(ExportUsersRequest {
+            format: "jsonl".to_string(),
+            include_password_hashes: false,
+        });
+        request.extensions_mut().insert(admin_session());
+
+        let frames: Vec<_> = export_users(&service, request)
+            .await
+            .unwrap()
+            .into_inner()
+            .collect()
+            .await;
+
+        assert_eq!(frames.len(), 1);
+        let chunk = &frames[0].as_ref().unwrap().chunk;
+        assert_eq!(chunk.split(|byte| *byte == b'\n').count(), 3);
+        assert!(!String::from_utf8_lossy(chunk).contains("$argon2"));
+    }
+
+    #[tokio::test]
+    async fn test_export_users_with_password_hashes() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        expect_audit(&mut mock_db, true);
+        mock_db
+            .expect_read_all::<UserModel>()
+            .times(1)
+            .returning(|_, _| Ok(vec![UserModel::default()]));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut request = Request::new(ExportUsersRequest {
+            format: "csv".to_string(),
+            include_password_hashes: true,
+        });
+        request.extensions_mut().insert(admin_session());
+
+        let frames: Vec<_> = export_users(&service, request)
+            .await
+            .unwrap()
+            .into_inner()
+            .collect()
+            .await;
+
+        let chunk = &frames[0].as_ref().unwrap().chunk;
+        assert!(String::from_utf8_lossy(chunk).contains("$argon2"));
+    }
+
+    #[tokio::test]
+    async fn test_export_users_audit_failure() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        // No user is read when the export cannot be recorded
+        mock_db
+            .expect_create::<CreateUserExportModel, UserExportModel>()
+            .times(1)
+            .returning(|_, _| Err(DatabaseError::Internal("Database error".to_string())));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut request = Request::new(ExportUsersRequest {
+            format: "csv".to_string(),
+            include_password_hashes: true,
+        });
+        request.extensions_mut().insert(admin_session());
+
+        let error = export_users(&service, request).await.err().unwrap();
+        assert_eq!(error.code(), tonic::Code::Internal);
+    }
+
+    #[tokio::test]
+    async fn test_export_users_requires_admin() {
+        let service = ClientService {
+            db: Database::Mock(MockDatabaseOperations::new()),
+        };
+
+        let mut request = Request::new(ExportUsersRequest {
+            format: "csv".to_string(),
+            ..Default::default()
+        });
+        request.extensions_mut().insert(SessionModel::default());
+
+        let error = export_users(&service, request).await.err().unwrap();
+        assert_eq!(error.code(), tonic::Code::PermissionDenied);
+    }
+
+    #[tokio::test]
+    async fn test_export_users_unknown_format() {
+        let service = ClientService {
+            db: Database::Mock(MockDatabaseOperations::new()),
+        };
+
+        let mut request = Request::new(ExportUsersRequest {
+            format: "xml".to_string(),
+            ..Default::default()
+        });
+        request.extensions_mut().insert(admin_session());
+
+        let error = export_users(&service, request).await.err().unwrap();
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+    }
+}
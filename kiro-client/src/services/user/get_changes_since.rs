@@ -0,0 +1,152 @@
+// services/user/get_changes_since.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use tonic::{Code, Request, Response, Status};
+
+use crate::{keyed_status, utils::sync, SessionModel};
+
+/// Gets the changes of the user since a cursor
+///
+/// Returns the user, settings and group records changed since the cursor, with
+/// tombstones for the deleted ones, and the cursor to sync from next. The whole
+/// state is returned, with `reset` set, when the cursor is 0 or too old.
+///
+/// # Arguments
+///
+/// * `service` - The UserService instance
+/// * `request` - The request containing the cursor of the previous sync
+///
+/// # Returns
+///
+/// Returns the changes and the new cursor on success
+///
+/// # Errors
+///
+/// Returns Status::unauthenticated if no valid session is found
+/// Returns Status::not_found if the user doesn't exist
+/// Returns Status::internal for database errors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tonic::{Request, Response, Status};
+/// use kiro_api::client::v1::{client_service_server::ClientService, GetChangesSinceRequest};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = kiro_client::ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Get changes request, 0 for the first sync
+/// let request = Request::new(GetChangesSinceRequest { cursor: 0 });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     ClientService::get_changes_since(&service, request).await;
+///
+///     println!("Changes synced");
+/// });
+/// ```
+pub async fn get_changes_since(
+    service: &ClientService, request: Request<GetChangesSinceRequest>,
+) -> Result<Response<GetChangesSinceResponse>, Status> {
+    // Get authenticated session from request extensions
+    let session = request
+        .extensions()
+        .get::<SessionModel>()
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
+
+    let response = sync::get_changes_since(
+        &service.db,
+        session.user_id.clone(),
+        request.get_ref().cursor,
+    )
+    .await?;
+
+    Ok(Response::new(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
+
+    use crate::{ChangeVersionsModel, UserModel};
+
+    #[tokio::test]
+    async fn test_get_changes_since_no_session() {
+        let service = ClientService {
+            db: Database::Mock(MockDatabaseOperations::new()),
+        };
+
+        let request = Request::new(GetChangesSinceRequest { cursor: 0 });
+
+        let error = get_changes_since(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::Unauthenticated);
+    }
+
+    #[tokio::test]
+    async fn test_get_changes_since_user_not_found() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<ChangeVersionsModel>()
+            .times(1)
+            .returning(|_| Ok(None));
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(None));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut request = Request::new(GetChangesSinceRequest { cursor: 0 });
+        request.extensions_mut().insert(SessionModel::default());
+
+        let error = get_changes_since(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_get_changes_since_db_error() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<ChangeVersionsModel>()
+            .times(1)
+            .returning(|_| Err(DatabaseError::Internal("Database error".to_string())));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut request = Request::new(GetChangesSinceRequest { cursor: 3 });
+        request.extensions_mut().insert(SessionModel::default());
+
+        let error = get_changes_since(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::Internal);
+    }
+}
@@ -0,0 +1,114 @@
+// services/user/import_users.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use kiro_api::client::v1::{ImportUsersRequest, ImportUsersResponse};
+use tonic::{Code, Request, Response, Status, Streaming};
+
+use crate::{
+    error::ClientError,
+    keyed_status,
+    utils::user_import::{import_batch_size, ImportError, TransferFormat, UserImporter},
+    SessionModel,
+};
+
+/// Imports users in bulk, from a file sent in chunks
+///
+/// The file is a CSV file with a header line, or a JSON Lines file. Each user
+/// has an email and optionally a password hash, a display name, a locale and
+/// the `activated` and `is_admin` flags. Argon2, bcrypt and PBKDF2 hashes are
+/// kept as is, the others are replaced by an Argon2 hash on the first login.
+///
+/// The format is read from the first frame. Users are inserted in batches of
+/// `IMPORT_BATCH_SIZE`, invalid rows and emails already in use are skipped and
+/// reported with their line number.
+///
+/// # Arguments
+///
+/// * `service` - The UserService instance
+/// * `request` - The stream of frames holding the file
+///
+/// # Returns
+///
+/// Returns the number of imported users and the rejected rows
+///
+/// # Errors
+///
+/// Returns Status::unauthenticated if no valid session is found
+/// Returns Status::permission_denied if the session is not an admin session
+/// Returns Status::invalid_argument if the format is unknown or the CSV header has no email column
+/// Returns Status::cancelled if the client stream fails
+/// Returns Status::internal for database errors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use kiro_api::client::v1::{client_service_client::ClientServiceClient, ImportUsersRequest};
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     let mut client = ClientServiceClient::connect("http://[::1]:50051").await.unwrap();
+///
+///     let frames = vec![ImportUsersRequest {
+///         format: "csv".to_string(),
+///         chunk: b"email,display_name\njane@example.com,Jane\n".to_vec(),
+///     }];
+///
+///     let response = client.import_users(tokio_stream::iter(frames)).await;
+///
+///     println!("Users imported: {:?}", response);
+/// });
+/// ```
+pub async fn import_users(
+    service: &ClientService, request: Request<Streaming<ImportUsersRequest>>,
+) -> Result<Response<ImportUsersResponse>, Status> {
+    // Get authenticated session from request extensions
+    let session = request
+        .extensions()
+        .get::<SessionModel>()
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
+
+    if !session.is_admin {
+        return Err(keyed_status(Code::PermissionDenied, "auth.admin_required"));
+    }
+
+    let mut frames = request.into_inner();
+
+    let Some(first) = frames.message().await? else {
+        return Ok(Response::new(ImportUsersResponse::default()));
+    };
+
+    let format = first
+        .format
+        .parse::<TransferFormat>()
+        .map_err(ClientError::from)?;
+
+    let mut importer = UserImporter::new(&service.db, format, import_batch_size());
+    importer.feed(&first.chunk).await?;
+
+    loop {
+        match frames.message().await {
+            Ok(Some(frame)) => importer.feed(&frame.chunk).await?,
+            Ok(None) => break,
+            Err(_) => return Err(ClientError::from(ImportError::Interrupted).into()),
+        }
+    }
+
+    let report = importer.finish().await?;
+
+    Ok(Response::new(report.into()))
+}
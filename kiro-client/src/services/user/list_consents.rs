@@ -0,0 +1,145 @@
+// services/user/list_consents.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use tonic::{Code, Request, Response, Status};
+
+use crate::{keyed_status, utils::consent, SessionModel};
+
+/// Lists the consents of the user
+///
+/// Returns the policy documents accepted by the user, most recent first, with
+/// the date and IP address of each acceptance, and the current documents the
+/// user has not accepted yet.
+///
+/// # Arguments
+///
+/// * `service` - The UserService instance
+/// * `request` - Empty request
+///
+/// # Returns
+///
+/// Returns the consent history and the pending documents on success
+///
+/// # Errors
+///
+/// Returns Status::unauthenticated if no valid session is found
+/// Returns Status::internal for database errors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tonic::{Request, Response, Status};
+/// use kiro_api::{client::v1::client_service_server::ClientService, google::protobuf::Empty};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = kiro_client::ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     ClientService::list_consents(&service, Request::new(Empty {})).await;
+///
+///     println!("Consents listed");
+/// });
+/// ```
+pub async fn list_consents(
+    service: &ClientService, request: Request<Empty>,
+) -> Result<Response<ListConsentsResponse>, Status> {
+    // Get authenticated session from request extensions
+    let session = request
+        .extensions()
+        .get::<SessionModel>()
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
+
+    let response = consent::list_consents(&service.db, session.user_id.clone()).await?;
+
+    Ok(Response::new(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
+
+    use crate::{PolicyConsentModel, PolicyDocumentModel};
+
+    #[tokio::test]
+    async fn test_list_consents_no_session() {
+        let service = ClientService {
+            db: Database::Mock(MockDatabaseOperations::new()),
+        };
+
+        let error = list_consents(&service, Request::new(Empty {}))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), tonic::Code::Unauthenticated);
+    }
+
+    #[tokio::test]
+    async fn test_list_consents_success() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field_thing::<PolicyConsentModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![PolicyConsentModel::default()]));
+
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![PolicyDocumentModel::default()]));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut request = Request::new(Empty {});
+        request.extensions_mut().insert(SessionModel::default());
+
+        let response = list_consents(&service, request).await.unwrap().into_inner();
+        assert_eq!(response.consents.len(), 1);
+        assert_eq!(response.consents[0].ip_address, "127.0.0.1");
+        assert!(response.pending.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_list_consents_db_error() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field_thing::<PolicyConsentModel>()
+            .times(1)
+            .returning(|_, _, _, _| Err(DatabaseError::Internal("Database error".to_string())));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut request = Request::new(Empty {});
+        request.extensions_mut().insert(SessionModel::default());
+
+        let error = list_consents(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::Internal);
+    }
+}
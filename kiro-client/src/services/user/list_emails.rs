@@ -0,0 +1,154 @@
+// services/user/list_emails.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use kiro_database::db_bridge::DatabaseOperations;
+use tonic::{Code, Request, Response, Status};
+
+use crate::{keyed_status, models::UserModel, SessionModel, UserEmailModel};
+
+/// Lists the email addresses of the user
+///
+/// Returns the primary address first, then the secondary addresses with their
+/// verification state.
+///
+/// # Arguments
+///
+/// * `service` - The UserService instance
+/// * `request` - Empty request
+///
+/// # Returns
+///
+/// Returns the addresses of the user on success
+///
+/// # Errors
+///
+/// Returns Status::unauthenticated if no valid session is found
+/// Returns Status::internal for database errors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tonic::{Request, Response, Status};
+/// use kiro_api::{client::v1::client_service_server::ClientService, google::protobuf::Empty};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = kiro_client::ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     ClientService::list_emails(&service, Request::new(Empty {})).await;
+///
+///     println!("Emails listed");
+/// });
+/// ```
+pub async fn list_emails(
+    service: &ClientService, request: Request<Empty>,
+) -> Result<Response<ListEmailsResponse>, Status> {
+    // Get authenticated session from request extensions
+    let session = request
+        .extensions()
+        .get::<SessionModel>()
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
+
+    // Get user details
+    let user = match service
+        .db
+        .select::<UserModel>(session.user_id.clone())
+        .await
+    {
+        Ok(Some(user)) => user,
+        Ok(None) => return Err(keyed_status(Code::NotFound, "user.not_found")),
+        Err(e) => return Err(Status::internal(format!("Database error: {}", e))),
+    };
+
+    let emails = UserEmailModel::list(&service.db, &user).await?;
+
+    Ok(Response::new(ListEmailsResponse { emails }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
+
+    #[tokio::test]
+    async fn test_list_emails_no_session() {
+        let service = ClientService {
+            db: Database::Mock(MockDatabaseOperations::new()),
+        };
+
+        let error = list_emails(&service, Request::new(Empty {}))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), tonic::Code::Unauthenticated);
+    }
+
+    #[tokio::test]
+    async fn test_list_emails_success() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_read_by_field_thing::<UserEmailModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![UserEmailModel::default()]));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut request = Request::new(Empty {});
+        request.extensions_mut().insert(SessionModel::default());
+
+        let response = list_emails(&service, request).await.unwrap().into_inner();
+        assert_eq!(response.emails.len(), 2);
+        assert!(response.emails[0].primary);
+        assert!(!response.emails[1].verified);
+    }
+
+    #[tokio::test]
+    async fn test_list_emails_db_error() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Err(DatabaseError::Internal("Database error".to_string())));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut request = Request::new(Empty {});
+        request.extensions_mut().insert(SessionModel::default());
+
+        let error = list_emails(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::Internal);
+    }
+}
@@ -20,42 +20,84 @@
 //! - Reading user data
 //! - Updating user data
 //! - Deleting user data
-//! - Disabling user accounts
+//! - Disabling user accounts, and reactivating them as an admin
 //! - Sending emails to change email or password
-//! - Uploading avatars
+//! - Uploading avatars, and files or avatars sent in chunks
 //! - Changing language, theme, notifications, privacy, and security settings
+//! - Updating several settings at once with a field mask
+//! - Updating the public profile
+//! - Requesting an export of the user data
+//! - Getting the changes since a cursor, for offline-capable clients
+//! - Accepting the terms of service and privacy policy, and listing the consents
+//! - Managing secondary email addresses and promoting one to primary
+//! - Importing and exporting users in bulk, as an admin
+//! - Creating invitation codes, as an admin
 //!
 //! The service is implemented as a gRPC service using the tonic framework.
 
-use tonic::{async_trait, Request, Response, Status};
+use tonic::{async_trait, Request, Response, Status, Streaming};
 
 use kiro_api::{
     client::v1::{
         client_service_server::{self, ClientServiceServer},
-        UpdateEmailRequest, UpdateLanguageRequest, UpdateNotificationsRequest,
-        UpdatePasswordRequest, UpdatePrivacyRequest, UpdateSecurityRequest, UpdateThemeRequest,
-        UploadAvatarRequest, UploadAvatarResponse,
+        AcceptPolicyRequest, AddEmailRequest, Consent, CreateInvitationRequest, ExportUsersRequest,
+        GetChangesSinceRequest, GetChangesSinceResponse, ImportUsersRequest, ImportUsersResponse,
+        Invitation, ListConsentsResponse, ListEmailsResponse, ReactivateUserRequest,
+        RemoveEmailRequest, SetPrimaryEmailRequest, UpdateEmailRequest, UpdateLanguageRequest,
+        UpdateNotificationsRequest, UpdatePasswordRequest, UpdatePrivacyRequest,
+        UpdateProfileRequest, UpdateSecurityRequest, UpdateSettingsRequest, UpdateSettingsResponse,
+        UpdateThemeRequest, UploadAvatarRequest, UploadAvatarResponse, UploadFileRequest,
+        UploadFileResponse, UserEmail,
     },
     google::protobuf::Empty,
 };
 use kiro_database::db_bridge::Database;
 
+mod accept_policy;
+#[cfg(feature = "mailer")]
+mod add_email;
+mod create_invitation;
 mod delete_user;
 mod disable_user;
+#[cfg(feature = "import")]
+mod export_users;
+mod get_changes_since;
+#[cfg(feature = "import")]
+mod import_users;
+mod list_consents;
+mod list_emails;
+mod reactivate_user;
 mod read_user;
+mod remove_email;
+#[cfg(feature = "export")]
+mod request_data_export;
 #[cfg(feature = "mailer")]
 mod send_email_to_change_email;
 #[cfg(feature = "mailer")]
 mod send_email_to_change_password;
+mod set_primary_email;
 mod update_email;
 mod update_language;
 mod update_notifications;
 mod update_password;
 mod update_privacy;
+mod update_profile;
 mod update_security;
+mod update_settings;
 mod update_theme;
 #[cfg(feature = "storage")]
 mod upload_avatar;
+#[cfg(feature = "storage")]
+mod upload_file;
+
+#[cfg(not(feature = "import"))]
+type ExportUsersStream = std::pin::Pin<
+    Box<
+        dyn futures::Stream<Item = Result<kiro_api::client::v1::ExportUsersResponse, Status>>
+            + Send
+            + Sync,
+    >,
+>;
 
 /// The main authentication service implementation
 #[derive(Clone)]
@@ -88,6 +130,10 @@ impl ClientService {
 #[async_trait]
 impl client_service_server::ClientService for ClientService {
     type ReadUserStream = read_user::ReadUserStream;
+    #[cfg(feature = "import")]
+    type ExportUsersStream = export_users::ExportUsersStream;
+    #[cfg(not(feature = "import"))]
+    type ExportUsersStream = ExportUsersStream;
 
     async fn read_user(
         &self, request: Request<Empty>,
@@ -103,6 +149,12 @@ impl client_service_server::ClientService for ClientService {
         disable_user::disable_user(self, request).await
     }
 
+    async fn reactivate_user(
+        &self, request: Request<ReactivateUserRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        reactivate_user::reactivate_user(self, request).await
+    }
+
     async fn send_email_to_change_email(
         &self, #[cfg(feature = "mailer")] request: Request<Empty>,
         #[cfg(not(feature = "mailer"))] _request: Request<Empty>,
@@ -145,6 +197,16 @@ impl client_service_server::ClientService for ClientService {
         upload_avatar::upload_avatar(self, request).await
     }
 
+    async fn upload_file(
+        &self, #[cfg(feature = "storage")] request: Request<Streaming<UploadFileRequest>>,
+        #[cfg(not(feature = "storage"))] _request: Request<Streaming<UploadFileRequest>>,
+    ) -> Result<Response<UploadFileResponse>, Status> {
+        #[cfg(not(feature = "storage"))]
+        unimplemented!("Storage functionality is disabled");
+        #[cfg(feature = "storage")]
+        upload_file::upload_file(self, request).await
+    }
+
     async fn update_language(
         &self, request: Request<UpdateLanguageRequest>,
     ) -> Result<Response<Empty>, Status> {
@@ -174,6 +236,100 @@ impl client_service_server::ClientService for ClientService {
     ) -> Result<Response<Empty>, Status> {
         update_security::update_security(self, request).await
     }
+
+    async fn update_profile(
+        &self, request: Request<UpdateProfileRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        update_profile::update_profile(self, request).await
+    }
+
+    async fn update_settings(
+        &self, request: Request<UpdateSettingsRequest>,
+    ) -> Result<Response<UpdateSettingsResponse>, Status> {
+        update_settings::update_settings(self, request).await
+    }
+
+    async fn request_data_export(
+        &self, #[cfg(feature = "export")] request: Request<Empty>,
+        #[cfg(not(feature = "export"))] _request: Request<Empty>,
+    ) -> Result<Response<Empty>, Status> {
+        #[cfg(not(feature = "export"))]
+        unimplemented!("Export functionality is disabled");
+        #[cfg(feature = "export")]
+        request_data_export::request_data_export(self, request).await
+    }
+
+    async fn get_changes_since(
+        &self, request: Request<GetChangesSinceRequest>,
+    ) -> Result<Response<GetChangesSinceResponse>, Status> {
+        get_changes_since::get_changes_since(self, request).await
+    }
+
+    async fn accept_policy(
+        &self, request: Request<AcceptPolicyRequest>,
+    ) -> Result<Response<Consent>, Status> {
+        accept_policy::accept_policy(self, request).await
+    }
+
+    async fn list_consents(
+        &self, request: Request<Empty>,
+    ) -> Result<Response<ListConsentsResponse>, Status> {
+        list_consents::list_consents(self, request).await
+    }
+
+    async fn add_email(
+        &self, #[cfg(feature = "mailer")] request: Request<AddEmailRequest>,
+        #[cfg(not(feature = "mailer"))] _request: Request<AddEmailRequest>,
+    ) -> Result<Response<UserEmail>, Status> {
+        #[cfg(not(feature = "mailer"))]
+        unimplemented!("Email functionality is disabled");
+        #[cfg(feature = "mailer")]
+        add_email::add_email(self, request).await
+    }
+
+    async fn list_emails(
+        &self, request: Request<Empty>,
+    ) -> Result<Response<ListEmailsResponse>, Status> {
+        list_emails::list_emails(self, request).await
+    }
+
+    async fn remove_email(
+        &self, request: Request<RemoveEmailRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        remove_email::remove_email(self, request).await
+    }
+
+    async fn set_primary_email(
+        &self, request: Request<SetPrimaryEmailRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        set_primary_email::set_primary_email(self, request).await
+    }
+
+    async fn import_users(
+        &self, #[cfg(feature = "import")] request: Request<Streaming<ImportUsersRequest>>,
+        #[cfg(not(feature = "import"))] _request: Request<Streaming<ImportUsersRequest>>,
+    ) -> Result<Response<ImportUsersResponse>, Status> {
+        #[cfg(not(feature = "import"))]
+        unimplemented!("Import functionality is disabled");
+        #[cfg(feature = "import")]
+        import_users::import_users(self, request).await
+    }
+
+    async fn export_users(
+        &self, #[cfg(feature = "import")] request: Request<ExportUsersRequest>,
+        #[cfg(not(feature = "import"))] _request: Request<ExportUsersRequest>,
+    ) -> Result<Response<Self::ExportUsersStream>, Status> {
+        #[cfg(not(feature = "import"))]
+        unimplemented!("Import functionality is disabled");
+        #[cfg(feature = "import")]
+        export_users::export_users(self, request).await
+    }
+
+    async fn create_invitation(
+        &self, request: Request<CreateInvitationRequest>,
+    ) -> Result<Response<Invitation>, Status> {
+        create_invitation::create_invitation(self, request).await
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,197 @@
+// services/user/reactivate_user.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use kiro_api::client::v1::ReactivateUserRequest;
+use kiro_database::{db_bridge::DatabaseOperations, DbId};
+use tonic::{Code, Request, Response, Status};
+
+use crate::{keyed_status, SessionModel, UserModel};
+
+/// Reactivates a disabled user account
+///
+/// # Arguments
+///
+/// * `service` - The UserService instance
+/// * `request` - The request containing the ID of the user to reactivate
+///
+/// # Returns
+///
+/// Returns an empty response on success
+///
+/// # Errors
+///
+/// Returns Status::unauthenticated if no valid session is found
+/// Returns Status::permission_denied if the session is not an admin session
+/// Returns Status::not_found if the user does not exist
+/// Returns Status::internal for database errors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tonic::{Request, Response, Status};
+/// use kiro_api::client::v1::{client_service_server::ClientService, ReactivateUserRequest};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = kiro_client::ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Reactivate user request
+/// let request = Request::new(ReactivateUserRequest {
+///     user_id: "user".to_string(),
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     ClientService::reactivate_user(&service, request).await;
+///
+///     println!("User account reactivated");
+/// });
+/// ```
+pub async fn reactivate_user(
+    service: &ClientService, request: Request<ReactivateUserRequest>,
+) -> Result<Response<Empty>, Status> {
+    // Get authenticated session from request extensions
+    let session = request
+        .extensions()
+        .get::<SessionModel>()
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
+
+    if !session.is_admin {
+        return Err(keyed_status(Code::PermissionDenied, "auth.admin_required"));
+    }
+
+    let user_id = DbId::from(("users", request.get_ref().user_id.as_str()));
+
+    // Check the user exists
+    service
+        .db
+        .select::<UserModel>(user_id.clone())
+        .await
+        .map_err(|e| Status::internal(format!("Database error: {}", e)))?
+        .ok_or_else(|| keyed_status(Code::NotFound, "user.not_found"))?;
+
+    // Reactivate the user account
+    UserModel::reactivate(&service.db, user_id, Some(session.user_id.clone())).await?;
+
+    Ok(Response::new(Empty {}))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::db_bridge::MockDatabaseOperations;
+    use mockall::predicate::eq;
+
+    use crate::{AccountEventKind, AccountEventModel, CreateAccountEventModel};
+
+    fn admin_session() -> SessionModel {
+        SessionModel {
+            is_admin: true,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reactivate_user_success() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let session = admin_session();
+        let admin_id = session.user_id.clone();
+        let user_id = DbId::from(("users", "disabled"));
+
+        mock_db
+            .expect_select::<UserModel>()
+            .with(eq(user_id.clone()))
+            .times(1)
+            .returning(|_| {
+                Ok(Some(UserModel {
+                    activated: false,
+                    ..Default::default()
+                }))
+            });
+
+        mock_db
+            .expect_update_field::<bool>()
+            .with(eq(user_id.clone()), eq("activated"), eq(true))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        mock_db
+            .expect_create::<CreateAccountEventModel, AccountEventModel>()
+            .withf(move |table, data| {
+                table == "account_events"
+                    && data.kind == AccountEventKind::Reactivated
+                    && data.actor == Some(admin_id.clone())
+            })
+            .times(1)
+            .returning(|_, _| Ok(vec![AccountEventModel::default()]));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut request = Request::new(ReactivateUserRequest {
+            user_id: "disabled".to_string(),
+        });
+        request.extensions_mut().insert(session);
+
+        assert!(reactivate_user(&service, request).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_reactivate_user_requires_admin() {
+        let service = ClientService {
+            db: Database::Mock(MockDatabaseOperations::new()),
+        };
+
+        let mut request = Request::new(ReactivateUserRequest {
+            user_id: "disabled".to_string(),
+        });
+        request.extensions_mut().insert(SessionModel::default());
+
+        let error = reactivate_user(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::PermissionDenied);
+    }
+
+    #[tokio::test]
+    async fn test_reactivate_user_not_found() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(None));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut request = Request::new(ReactivateUserRequest {
+            user_id: "unknown".to_string(),
+        });
+        request.extensions_mut().insert(admin_session());
+
+        let error = reactivate_user(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::NotFound);
+    }
+}
@@ -19,13 +19,16 @@ use super::*;
 use std::pin::Pin;
 
 use futures::{future, Stream, StreamExt};
-use kiro_api::client::v1::{Settings, User};
+use kiro_api::client::v1::User;
 use kiro_database::{db_bridge::DatabaseOperations, DbId};
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::ReceiverStream;
-use tonic::{Request, Response, Status};
+use tonic::{Code, Request, Response, Status};
 
-use crate::models::{SessionModel, UserModel};
+use crate::{
+    keyed_status,
+    models::{SessionModel, UserModel},
+};
 
 pub type ReadUserStream = Pin<Box<dyn Stream<Item = Result<User, Status>> + Send + Sync>>;
 
@@ -76,7 +79,7 @@ pub async fn read_user(
     let session = request
         .extensions()
         .get::<SessionModel>()
-        .ok_or_else(|| Status::unauthenticated("Missing session information"))?;
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing_information"))?;
 
     let db = service.db.clone();
     let user_id = session.user_id.clone();
@@ -110,7 +113,8 @@ async fn stream_user(
     match db.select::<UserModel>(user_id.clone()).await {
         Ok(Some(user)) => send_user_update(tx, user).await?,
         Ok(None) => {
-            tx.send(Err(Status::not_found("User not found"))).await?;
+            tx.send(Err(keyed_status(Code::NotFound, "user.not_found")))
+                .await?;
             return Ok(());
         }
         Err(e) => {
@@ -119,17 +123,25 @@ async fn stream_user(
         }
     }
 
-    // Live updates
-    let mut live_stream = db.live::<UserModel>("users").await?;
+    // Live updates, scoped to the user record
+    let mut live_stream = db.live_record::<UserModel>(user_id).await?;
+
+    loop {
+        let result = tokio::select! {
+            // Dropping the live stream once the client is gone kills the live query
+            _ = tx.closed() => break,
+            result = live_stream.next() => match result {
+                Some(result) => result,
+                None => break,
+            },
+        };
 
-    while let Some(result) = live_stream.next().await {
         match result {
-            Ok(users) => {
-                for user in users {
-                    if user.id == user_id {
-                        send_user_update(tx, user).await?;
-                    }
-                }
+            Ok(Some(user)) => send_user_update(tx, user).await?,
+            Ok(None) => {
+                tx.send(Err(keyed_status(Code::NotFound, "user.not_found")))
+                    .await?;
+                break;
             }
             Err(e) => {
                 tx.send(Err(Status::internal(e.to_string()))).await?;
@@ -155,14 +167,7 @@ async fn stream_user(
 async fn send_user_update(
     tx: &mpsc::Sender<Result<User, Status>>, user: UserModel,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let user = User {
-        email: user.email,
-        avatar: user.avatar,
-        settings: Some(Settings::from(&user.settings)),
-        is_admin: user.is_admin,
-    };
-
-    tx.send(Ok(user)).await?;
+    tx.send(Ok(User::from(&user))).await?;
 
     Ok(())
 }
@@ -172,7 +177,10 @@ mod tests {
     use super::*;
 
     use futures::StreamExt;
-    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError, DbId};
+    use kiro_database::{
+        db_bridge::{DatabaseStream, MockDatabaseOperations},
+        DatabaseError, DbId,
+    };
     use mockall::predicate::eq;
     use std::pin::Pin;
     use tokio_stream::wrappers::ReceiverStream;
@@ -191,17 +199,21 @@ mod tests {
             .times(1)
             .returning(move |_| Ok(Some(test_user.clone())));
 
-        // Mock live stream
+        // Mock live record stream
         mock_db
-            .expect_live()
-            .with(eq("users"))
+            .expect_live_record::<UserModel>()
+            .with(eq(DbId::default()))
             .times(1)
             .returning(move |_| {
                 let (_tx, rx) = mpsc::channel(32);
                 let rx = ReceiverStream::new(rx);
                 Ok(Box::pin(rx)
                     as Pin<
-                        Box<dyn Stream<Item = Result<Vec<UserModel>, DatabaseError>> + Send + Sync>,
+                        Box<
+                            dyn Stream<Item = Result<Option<UserModel>, DatabaseError>>
+                                + Send
+                                + Sync,
+                        >,
                     >)
             });
 
@@ -220,6 +232,7 @@ mod tests {
             let user = result.unwrap();
             assert_eq!(user.email, "test@example.com");
             assert_eq!(user.avatar.unwrap(), "avatar.jpg");
+            assert_eq!(user.handle.unwrap(), "test_user");
             assert!(!user.is_admin);
         } else {
             panic!("No initial user data received");
@@ -254,17 +267,21 @@ mod tests {
             .times(1)
             .returning(|_| Ok(None));
 
-        // Mock live stream
+        // Mock live record stream
         mock_db
-            .expect_live()
-            .with(eq("users"))
+            .expect_live_record::<UserModel>()
+            .with(eq(DbId::default()))
             .times(1)
             .returning(|_| {
                 let (_tx, rx) = mpsc::channel(32);
                 let rx = ReceiverStream::new(rx);
                 Ok(Box::pin(rx)
                     as Pin<
-                        Box<dyn Stream<Item = Result<Vec<UserModel>, DatabaseError>> + Send + Sync>,
+                        Box<
+                            dyn Stream<Item = Result<Option<UserModel>, DatabaseError>>
+                                + Send
+                                + Sync,
+                        >,
                     >)
             });
 
@@ -296,17 +313,21 @@ mod tests {
             .times(1)
             .returning(|_| Err(DatabaseError::Internal("Database error".to_string())));
 
-        // Mock live stream
+        // Mock live record stream
         mock_db
-            .expect_live()
-            .with(eq("users"))
+            .expect_live_record::<UserModel>()
+            .with(eq(DbId::default()))
             .times(1)
             .returning(|_| {
                 let (_tx, rx) = mpsc::channel(32);
                 let rx = ReceiverStream::new(rx);
                 Ok(Box::pin(rx)
                     as Pin<
-                        Box<dyn Stream<Item = Result<Vec<UserModel>, DatabaseError>> + Send + Sync>,
+                        Box<
+                            dyn Stream<Item = Result<Option<UserModel>, DatabaseError>>
+                                + Send
+                                + Sync,
+                        >,
                     >)
             });
 
@@ -324,4 +345,45 @@ mod tests {
         assert_eq!(error.code(), tonic::Code::Internal);
         assert!(error.message().contains("Database error"));
     }
+
+    #[tokio::test]
+    async fn test_read_user_live_record_updates() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let test_session = SessionModel::default();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .with(eq(DbId::default()))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        // The record is updated, then deleted
+        mock_db
+            .expect_live_record::<UserModel>()
+            .with(eq(DbId::default()))
+            .times(1)
+            .returning(|_| {
+                let updated = UserModel {
+                    email: "updated@example.com".to_string(),
+                    ..Default::default()
+                };
+                let stream: DatabaseStream<Option<UserModel>> =
+                    Box::pin(futures::stream::iter(vec![Ok(Some(updated)), Ok(None)]));
+                Ok(stream)
+            });
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut request = Request::new(Empty {});
+        request.extensions_mut().insert(test_session);
+
+        let response = read_user(&service, request).await.unwrap();
+        let users: Vec<_> = response.into_inner().collect().await;
+
+        // The deletion ends the stream
+        assert_eq!(users.len(), 2);
+        assert_eq!(users[1].as_ref().unwrap().email, "updated@example.com");
+    }
 }
@@ -0,0 +1,168 @@
+// services/user/remove_email.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use kiro_database::{db_bridge::DatabaseOperations, DbId};
+use tonic::{Code, Request, Response, Status};
+
+use crate::{keyed_status, models::UserModel, SessionModel, UserEmailModel};
+
+/// Removes a secondary email address of the user
+///
+/// # Arguments
+///
+/// * `service` - The UserService instance providing database access
+/// * `request` - The gRPC request containing the identifier of the address
+///
+/// # Returns
+///
+/// * `Ok(Response)` - Address removed
+/// * `Err(Status)` - Various error conditions with appropriate status codes
+///
+/// # Errors
+///
+/// Returns Status::unauthenticated if no valid session is found
+/// Returns Status::not_found if the address is unknown or belongs to another user
+/// Returns Status::failed_precondition if the address is the primary one
+/// Returns Status::internal for database errors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tonic::{Request, Response, Status};
+/// use kiro_api::client::v1::{client_service_server::ClientService, RemoveEmailRequest};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = kiro_client::ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Remove email request
+/// let request = Request::new(RemoveEmailRequest {
+///     id: "address".to_string(),
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     ClientService::remove_email(&service, request).await;
+///
+///     println!("Email removed");
+/// });
+/// ```
+pub async fn remove_email(
+    service: &ClientService, request: Request<RemoveEmailRequest>,
+) -> Result<Response<Empty>, Status> {
+    // Get authenticated session
+    let session = request
+        .extensions()
+        .get::<SessionModel>()
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
+
+    // Get user details
+    let user = match service
+        .db
+        .select::<UserModel>(session.user_id.clone())
+        .await
+    {
+        Ok(Some(user)) => user,
+        Ok(None) => return Err(keyed_status(Code::NotFound, "user.not_found")),
+        Err(e) => return Err(Status::internal(format!("Database error: {}", e))),
+    };
+
+    let id = DbId::from(("user_emails", request.get_ref().id.as_str()));
+
+    UserEmailModel::remove(&service.db, &user, id).await?;
+
+    Ok(Response::new(Empty {}))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::db_bridge::MockDatabaseOperations;
+    use mockall::predicate::eq;
+
+    fn request(id: &str) -> Request<RemoveEmailRequest> {
+        let mut request = Request::new(RemoveEmailRequest { id: id.to_string() });
+        request.extensions_mut().insert(SessionModel::default());
+        request
+    }
+
+    #[tokio::test]
+    async fn test_remove_email_success() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_select::<UserEmailModel>()
+            .with(eq(DbId::from(("user_emails", "address"))))
+            .times(1)
+            .returning(|_| Ok(Some(UserEmailModel::default())));
+
+        mock_db
+            .expect_delete()
+            .with(eq(DbId::from(("user_emails", "address"))))
+            .times(1)
+            .returning(|_| Ok(Some(())));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        assert!(remove_email(&service, request("address")).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_remove_email_of_another_user() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_select::<UserEmailModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(UserEmailModel {
+                    user: DbId::from(("users", "other")),
+                    ..Default::default()
+                }))
+            });
+
+        mock_db.expect_delete().never();
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let error = remove_email(&service, request("address"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), tonic::Code::NotFound);
+    }
+}
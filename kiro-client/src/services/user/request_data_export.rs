@@ -0,0 +1,146 @@
+// services/user/request_data_export.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use tonic::{Code, Request, Response, Status};
+
+use crate::{keyed_status, utils::export, SessionModel};
+
+/// Requests an export of everything held about a user
+///
+/// The archive is built in a background job, the user receives a time-limited
+/// download link by email once it is ready.
+///
+/// # Arguments
+///
+/// * `service` - The UserService instance
+/// * `request` - The empty request of the authenticated user
+///
+/// # Returns
+///
+/// Returns an empty response once the export is scheduled
+///
+/// # Errors
+///
+/// Returns Status::unauthenticated if no valid session is found
+/// Returns Status::resource_exhausted if an export was requested recently
+/// Returns Status::internal for database errors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tonic::{Request, Response, Status};
+/// use kiro_api::{client::v1::client_service_server::ClientService, google::protobuf::Empty};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = kiro_client::ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Data export request
+/// let request = Request::new(Empty {});
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     ClientService::request_data_export(&service, request).await;
+///
+///     println!("Data export requested");
+/// });
+/// ```
+pub async fn request_data_export(
+    service: &ClientService, request: Request<Empty>,
+) -> Result<Response<Empty>, Status> {
+    // Get authenticated session from request extensions
+    let session = request
+        .extensions()
+        .get::<SessionModel>()
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
+
+    export::request_export(&service.db, session.user_id.clone()).await?;
+
+    Ok(Response::new(Empty {}))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
+
+    use crate::ExportModel;
+
+    #[tokio::test]
+    async fn test_request_data_export_no_session() {
+        let mock_db = MockDatabaseOperations::new();
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Request::new(Empty {});
+        // Don't insert session into extensions
+
+        let error = request_data_export(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::Unauthenticated);
+        assert_eq!(error.message(), "No valid session found");
+    }
+
+    #[tokio::test]
+    async fn test_request_data_export_throttled() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let test_session = SessionModel::default();
+
+        mock_db
+            .expect_read_by_field_thing::<ExportModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![ExportModel::default()]));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut request = Request::new(Empty {});
+        request.extensions_mut().insert(test_session);
+
+        let error = request_data_export(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::ResourceExhausted);
+    }
+
+    #[tokio::test]
+    async fn test_request_data_export_db_error() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let test_session = SessionModel::default();
+
+        mock_db
+            .expect_read_by_field_thing::<ExportModel>()
+            .times(1)
+            .returning(|_, _, _, _| Err(DatabaseError::Internal("Database error".to_string())));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut request = Request::new(Empty {});
+        request.extensions_mut().insert(test_session);
+
+        let error = request_data_export(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::Internal);
+    }
+}
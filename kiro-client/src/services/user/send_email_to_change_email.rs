@@ -19,9 +19,12 @@ use super::*;
 use chrono::{Days, Utc};
 use kiro_database::{db_bridge::DatabaseOperations, get_env_or};
 use kiro_mailer::{ContentType, LinkModel, LinkType, Mailer, MailerTrait};
-use tonic::{Request, Response, Status};
+use tonic::{Code, Request, Response, Status};
 
-use crate::{models::UserModel, SessionModel};
+use crate::{
+    keyed_status, models::UserModel, utils::notification::send_notification, NotificationCategory,
+    SessionModel,
+};
 
 /// Sends an email to the user to change their email address
 ///
@@ -79,7 +82,7 @@ pub async fn send_email_to_change_email(
     let session = request
         .extensions()
         .get::<SessionModel>()
-        .ok_or_else(|| Status::unauthenticated("No valid session found"))?;
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
 
     // Get user details
     let user = service
@@ -87,12 +90,12 @@ pub async fn send_email_to_change_email(
         .select::<UserModel>(session.user_id.clone())
         .await
         .map_err(|e| Status::internal(e.to_string()))?
-        .ok_or_else(|| Status::not_found("User not found"))?;
+        .ok_or_else(|| keyed_status(Code::NotFound, "user.not_found"))?;
 
     // Create change email link that expires in 24 hours
     let expiry_time = Utc::now()
         .checked_add_days(Days::new(1))
-        .ok_or_else(|| Status::internal("Failed to calculate expiry time"))?;
+        .ok_or_else(|| keyed_status(Code::Internal, "link.expiry_time_failed"))?;
 
     let link = LinkModel::create_from_user(
         &service.db,
@@ -123,10 +126,14 @@ pub async fn send_email_to_change_email(
     )
     .map_err(|e| Status::internal(format!("Failed to build email: {}", e)))?;
 
-    Mailer::new()
-        .send_mail(message)
-        .await
-        .map_err(|e| Status::internal(format!("Failed to send email: {}", e)))?;
+    send_notification(
+        &Mailer::new(),
+        &user,
+        NotificationCategory::Security,
+        message,
+    )
+    .await
+    .map_err(|e| Status::internal(format!("Failed to send email: {}", e)))?;
 
     Ok(Response::new(Empty {}))
 }
@@ -0,0 +1,134 @@
+// services/user/set_primary_email.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use kiro_database::{db_bridge::DatabaseOperations, DbId};
+use tonic::{Code, Request, Response, Status};
+
+use crate::{keyed_status, models::UserModel, SessionModel, UserEmailModel};
+
+/// Promotes a verified secondary email address of the user to primary
+///
+/// The previous primary address stays attached to the account as a verified
+/// secondary address.
+///
+/// # Arguments
+///
+/// * `service` - The UserService instance providing database access
+/// * `request` - The gRPC request containing the identifier of the address
+///
+/// # Returns
+///
+/// * `Ok(Response)` - Address promoted
+/// * `Err(Status)` - Various error conditions with appropriate status codes
+///
+/// # Errors
+///
+/// Returns Status::unauthenticated if no valid session is found
+/// Returns Status::not_found if the address is unknown or belongs to another user
+/// Returns Status::failed_precondition if the address is not verified
+/// Returns Status::internal for database errors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tonic::{Request, Response, Status};
+/// use kiro_api::client::v1::{client_service_server::ClientService, SetPrimaryEmailRequest};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = kiro_client::ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Set primary email request
+/// let request = Request::new(SetPrimaryEmailRequest {
+///     id: "address".to_string(),
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     ClientService::set_primary_email(&service, request).await;
+///
+///     println!("Primary email changed");
+/// });
+/// ```
+pub async fn set_primary_email(
+    service: &ClientService, request: Request<SetPrimaryEmailRequest>,
+) -> Result<Response<Empty>, Status> {
+    // Get authenticated session
+    let session = request
+        .extensions()
+        .get::<SessionModel>()
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
+
+    // Get user details
+    let user = match service
+        .db
+        .select::<UserModel>(session.user_id.clone())
+        .await
+    {
+        Ok(Some(user)) => user,
+        Ok(None) => return Err(keyed_status(Code::NotFound, "user.not_found")),
+        Err(e) => return Err(Status::internal(format!("Database error: {}", e))),
+    };
+
+    let id = DbId::from(("user_emails", request.get_ref().id.as_str()));
+
+    UserEmailModel::promote(&service.db, &user, id).await?;
+
+    Ok(Response::new(Empty {}))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::db_bridge::MockDatabaseOperations;
+
+    #[tokio::test]
+    async fn test_set_primary_email_unverified() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_select::<UserEmailModel>()
+            .times(1)
+            .returning(|_| Ok(Some(UserEmailModel::default())));
+
+        mock_db.expect_update_chain::<UserModel>().never();
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut request = Request::new(SetPrimaryEmailRequest {
+            id: "address".to_string(),
+        });
+        request.extensions_mut().insert(SessionModel::default());
+
+        let error = set_primary_email(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::FailedPrecondition);
+    }
+}
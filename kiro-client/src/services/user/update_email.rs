@@ -16,19 +16,18 @@
 
 use super::*;
 
+use tonic::{Code, Request, Response, Status};
+
 #[cfg(feature = "mailer")]
-use chrono::{Days, Utc};
 use kiro_database::db_bridge::DatabaseOperations;
 #[cfg(feature = "mailer")]
-use kiro_database::{get_env_or, DbId};
-use tonic::{Request, Response, Status};
+use kiro_mailer::{LinkModel, LinkType};
 
 #[cfg(feature = "mailer")]
-use kiro_mailer::{ContentType, LinkModel, LinkType, Mailer, MailerTrait};
-
-use crate::{models::UserModel, SessionModel};
+use crate::utils::email_change::start_email_change;
+use crate::{keyed_status, models::UserModel, SessionModel};
 
-/// Updates a user's email address with validation and notification
+/// Requests the change of a user's email address
 ///
 /// # Arguments
 ///
@@ -37,21 +36,26 @@ use crate::{models::UserModel, SessionModel};
 ///
 /// # Returns
 ///
-/// * `Ok(Response)` - Email update successful
+/// * `Ok(Response)` - Email change pending
 /// * `Err(Status)` - Various error conditions with appropriate status codes
 ///
 /// # Flow
 ///
 /// 1. Validates session and temporary change key
 /// 2. Checks if email is available
-/// 3. Updates user's email
-/// 4. Sends confirmation email with reset option
-/// 5. Cleans up temporary links
+/// 3. Stores the new email as pending
+/// 4. Sends a confirmation link to the new email and a cancel link to the current one
+/// 5. Cleans up the temporary link
+///
+/// The email is only swapped once the new address confirms the change, through
+/// `AuthService::confirm_email_change`. Without the mailer the new address cannot
+/// be verified and the change applies at once.
 ///
 /// # Errors
 ///
 /// Returns Status::unauthenticated if no valid session is found
-/// Returns Status::invalid_argument if no password is provided
+/// Returns Status::not_found if the change key is unknown or belongs to another user
+/// Returns Status::already_exists if the email is already in use
 /// Returns Status::internal for database errors
 ///
 /// # Example
@@ -79,7 +83,7 @@ use crate::{models::UserModel, SessionModel};
 /// tokio::runtime::Runtime::new().unwrap().block_on(async {
 ///     ClientService::update_email(&service, request).await;
 ///
-///     println!("Email updated");
+///     println!("Email change pending");
 /// });
 /// ```
 pub async fn update_email(
@@ -89,35 +93,21 @@ pub async fn update_email(
     let session = request
         .extensions()
         .get::<SessionModel>()
-        .ok_or_else(|| Status::unauthenticated("No valid session found"))?;
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
 
     let request = request.get_ref();
 
     #[cfg(feature = "mailer")]
     {
         // Validate temporary change key
-        let link_id = DbId::from(("links".to_string(), request.temp_token.clone()));
-        match service.db.select::<LinkModel>(link_id).await {
-            Ok(Some(link)) => link,
-            Ok(None) => return Err(Status::not_found("Invalid or expired change key")),
-            Err(e) => return Err(Status::internal(format!("Database error: {}", e))),
-        };
-    }
-
-    // Check if email is already used
-    if UserModel::check_email(&service.db, request.email.clone()).await? {
-        return Err(Status::already_exists("Email address already in use"));
-    }
+        let link =
+            LinkModel::get_valid_link(&service.db, &request.temp_token, LinkType::EmailChange)
+                .await?;
 
-    // Update email
-    service
-        .db
-        .update_field(session.user_id.clone(), "email", request.email.clone())
-        .await
-        .map_err(|e| Status::internal(format!("Failed to update email: {}", e)))?;
+        if link.user != session.user_id {
+            return Err(keyed_status(Code::NotFound, "link.invalid_change_key"));
+        }
 
-    #[cfg(feature = "mailer")]
-    {
         // Get user details
         let user = match service
             .db
@@ -125,59 +115,127 @@ pub async fn update_email(
             .await
         {
             Ok(Some(user)) => user,
-            Ok(None) => return Err(Status::not_found("User not found")),
+            Ok(None) => return Err(keyed_status(Code::NotFound, "user.not_found")),
             Err(e) => return Err(Status::internal(format!("Database error: {}", e))),
         };
 
-        // Cleanup old change links
-        LinkModel::delete_link_by_user_and_type(
+        // Store the pending email and send the confirmation and cancel links
+        start_email_change(&service.db, &user, &request.email).await?;
+
+        // Cleanup change link
+        service
+            .db
+            .delete(link.id)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to delete link: {}", e)))?;
+    }
+
+    // Without the mailer the new address cannot be verified
+    #[cfg(not(feature = "mailer"))]
+    {
+        UserModel::request_email_change(
             &service.db,
             session.user_id.clone(),
-            LinkType::EmailChange,
+            request.email.clone(),
         )
         .await?;
 
-        // Generate reset link for safety
-        let expiry = Utc::now()
-            .checked_add_days(Days::new(2))
-            .ok_or_else(|| Status::internal("Failed to calculate expiry"))?;
+        UserModel::confirm_email_change(&service.db, session.user_id.clone()).await?;
+    }
 
-        let reset_link = LinkModel::create_from_user(
-            &service.db,
-            session.user_id.clone(),
-            expiry,
-            LinkType::EmailReset,
-        )
-        .await
-        .map(|link| link.construct_link())
-        .map_err(|e| Status::internal(format!("Failed to create reset link: {}", e)))?;
+    Ok(Response::new(Empty {}))
+}
 
-        // Send confirmation email
-        let template = Mailer::load_template("email_changed.html")
-            .await
-            .map_err(|e| Status::internal(format!("Failed to load email template: {}", e)))?
-            .replace("${{USER_NAME}}", &user.email)
-            .replace("${{RESET_URL}}", &reset_link)
-            .replace("${{NEW_MAIL}}", &request.email);
-
-        let from = get_env_or("SMTP_USER", "test@example.com");
-
-        let message = Mailer::build_mail(
-            &from,
-            &user.email,
-            "Email Changed",
-            ContentType::TEXT_HTML,
-            template,
-        )
-        .map_err(|e| Status::internal(format!("Failed to build email: {}", e)))?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::{db_bridge::MockDatabaseOperations, DbId};
+
+    fn request(email: &str) -> Request<UpdateEmailRequest> {
+        let mut request = Request::new(UpdateEmailRequest {
+            email: email.to_string(),
+            temp_token: "link".to_string(),
+        });
+        request.extensions_mut().insert(SessionModel {
+            user_id: DbId::from(("users", "user")),
+            ..Default::default()
+        });
+        request
+    }
+
+    #[cfg(feature = "mailer")]
+    #[tokio::test]
+    async fn test_update_email_foreign_link() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        // The change key of another user
+        mock_db
+            .expect_select::<LinkModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(LinkModel {
+                    id: DbId::from(("links", "link")),
+                    user: DbId::from(("users", "other")),
+                    link_type: LinkType::EmailChange,
+                    expiry: kiro_database::DbDateTime::from(
+                        chrono::Utc::now() + chrono::Duration::hours(1),
+                    ),
+                }))
+            });
+
+        mock_db.expect_update_field::<Option<String>>().never();
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
 
-        Mailer::new()
-            .send_mail(message)
+        let error = update_email(&service, request("new@example.com"))
             .await
-            .map_err(|e| Status::internal(format!("Failed to send email: {}", e)))?;
+            .unwrap_err();
+        assert_eq!(error.code(), tonic::Code::NotFound);
     }
 
-    Ok(Response::new(Empty {}))
-}
+    #[tokio::test]
+    async fn test_update_email_taken() {
+        let mut mock_db = MockDatabaseOperations::new();
 
-// TODO: Fix mailer tests Trait, before adding tests
+        #[cfg(feature = "mailer")]
+        {
+            mock_db
+                .expect_select::<LinkModel>()
+                .times(1)
+                .returning(|_| {
+                    Ok(Some(LinkModel {
+                        id: DbId::from(("links", "link")),
+                        user: DbId::from(("users", "user")),
+                        link_type: LinkType::EmailChange,
+                        expiry: kiro_database::DbDateTime::from(
+                            chrono::Utc::now() + chrono::Duration::hours(1),
+                        ),
+                    }))
+                });
+
+            mock_db
+                .expect_select::<UserModel>()
+                .times(1)
+                .returning(|_| Ok(Some(UserModel::default())));
+        }
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![UserModel::default()]));
+
+        mock_db.expect_update_field::<Option<String>>().never();
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let error = update_email(&service, request("taken@example.com"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), tonic::Code::AlreadyExists);
+    }
+}
@@ -16,10 +16,10 @@
 
 use super::*;
 
-use kiro_database::db_bridge::DatabaseOperations;
-use tonic::{Request, Response, Status};
+use kiro_api::client::v1::Settings;
+use tonic::{Code, Request, Response, Status};
 
-use crate::SessionModel;
+use crate::{keyed_status, SessionModel, UserModel};
 
 /// Updates a user's language preference
 ///
@@ -73,22 +73,26 @@ pub async fn update_language(
     let session = request
         .extensions()
         .get::<SessionModel>()
-        .ok_or_else(|| Status::unauthenticated("No valid session found"))?;
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
 
     // Get the new language from the request
     let language = request.get_ref().language;
 
     // Check if the language is a valid Language enum value
     if !matches!(language, 0..=9) {
-        return Err(Status::invalid_argument("Invalid language value"));
+        return Err(keyed_status(
+            Code::InvalidArgument,
+            "settings.invalid_language",
+        ));
     }
 
-    // Update the user's language in the database
-    service
-        .db
-        .update_field(session.user_id.clone(), "language", language)
-        .await
-        .map_err(|e| Status::internal(e.to_string()))?;
+    let patch = Settings {
+        language: Some(language),
+        ..Default::default()
+    };
+
+    // Update the user's language in the settings
+    UserModel::update_setting(&service.db, session.user_id.clone(), &patch, "language").await?;
 
     Ok(Response::new(Empty {}))
 }
@@ -97,7 +101,11 @@ pub async fn update_language(
 mod tests {
     use super::*;
 
-    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
+    use kiro_database::{
+        db_bridge::MockDatabaseOperations,
+        db_query::{DbValue, FieldUpdate},
+        DatabaseError,
+    };
     use mockall::predicate::eq;
 
     #[tokio::test]
@@ -107,10 +115,19 @@ mod tests {
         let user_id = test_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(eq(user_id), eq("language"), eq(0))
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => settings["language"] == "English",
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -146,10 +163,10 @@ mod tests {
 
         // Simulate database error
         mock_db
-            .expect_update_field()
-            .with(eq(user_id), eq("language"), eq(0))
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
             .times(1)
-            .returning(|_, _, _| Err(DatabaseError::Internal("Database error".to_string())));
+            .returning(|_| Err(DatabaseError::Internal("Database error".to_string())));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -171,10 +188,19 @@ mod tests {
         let user_id = admin_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(eq(user_id), eq("language"), eq(0))
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => settings["language"] == "English",
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -16,10 +16,10 @@
 
 use super::*;
 
-use kiro_database::db_bridge::DatabaseOperations;
-use tonic::{Request, Response, Status};
+use kiro_api::client::v1::{Notifications, Settings};
+use tonic::{Code, Request, Response, Status};
 
-use crate::SessionModel;
+use crate::{keyed_status, SessionModel, UserModel};
 
 /// Updates a user's notification settings
 ///
@@ -73,30 +73,39 @@ pub async fn update_notifications(
     let session = request
         .extensions()
         .get::<SessionModel>()
-        .ok_or_else(|| Status::unauthenticated("No valid session found"))?;
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
 
     // Get the field and value from the request
     let field = request.get_ref().field.as_str();
     let value = request.get_ref().value;
 
     // Validate the notification field name
-    if !["email", "push", "sms"].contains(&field) {
-        return Err(Status::invalid_argument(format!(
-            "Invalid notification field: {}",
-            field
-        )));
+    let mut notifications = Notifications::default();
+    match field {
+        "email" => notifications.email = value,
+        "push" => notifications.push = value,
+        "sms" => notifications.sms = value,
+        _ => {
+            return Err(Status::invalid_argument(format!(
+                "Invalid notification field: {}",
+                field
+            )))
+        }
     }
 
-    // Update the user's notification settings in the database
-    service
-        .db
-        .update_field(
-            session.user_id.clone(),
-            &format!("settings/notifications/{}", field),
-            value,
-        )
-        .await
-        .map_err(|e| Status::internal(e.to_string()))?;
+    let patch = Settings {
+        notifications: Some(notifications),
+        ..Default::default()
+    };
+
+    // Update the user's notification settings
+    UserModel::update_setting(
+        &service.db,
+        session.user_id.clone(),
+        &patch,
+        &format!("notifications.{}", field),
+    )
+    .await?;
 
     Ok(Response::new(Empty {}))
 }
@@ -105,7 +114,11 @@ pub async fn update_notifications(
 mod tests {
     use super::*;
 
-    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
+    use kiro_database::{
+        db_bridge::MockDatabaseOperations,
+        db_query::{DbValue, FieldUpdate},
+        DatabaseError,
+    };
     use mockall::predicate::eq;
 
     #[tokio::test]
@@ -115,10 +128,21 @@ mod tests {
         let user_id = test_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(eq(user_id), eq("settings/notifications/email"), eq(true))
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["notifications"]["email"] == true
+                }
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -141,10 +165,21 @@ mod tests {
         let user_id = test_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(eq(user_id), eq("settings/notifications/push"), eq(false))
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["notifications"]["push"] == false
+                }
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -167,10 +202,21 @@ mod tests {
         let user_id = test_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(eq(user_id), eq("settings/notifications/sms"), eq(true))
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["notifications"]["sms"] == true
+                }
+                _ => false,
+            })
+            .times(1)
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -231,10 +277,10 @@ mod tests {
         let user_id = test_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(eq(user_id), eq("settings/notifications/email"), eq(true))
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
             .times(1)
-            .returning(|_, _, _| Err(DatabaseError::Internal("Database error".to_string())));
+            .returning(|_| Err(DatabaseError::Internal("Database error".to_string())));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -259,10 +305,21 @@ mod tests {
         let user_id = admin_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(eq(user_id), eq("settings/notifications/email"), eq(true))
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["notifications"]["email"] == true
+                }
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
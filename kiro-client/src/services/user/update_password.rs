@@ -21,12 +21,14 @@ use chrono::{Days, Utc};
 use kiro_database::db_bridge::DatabaseOperations;
 #[cfg(feature = "mailer")]
 use kiro_database::{get_env_or, DbId};
-use tonic::{Request, Response, Status};
+use tonic::{Code, Request, Response, Status};
 
 #[cfg(feature = "mailer")]
 use kiro_mailer::{ContentType, LinkModel, LinkType, Mailer, MailerTrait};
 
-use crate::{models::UserModel, SessionModel};
+use crate::{keyed_status, models::UserModel, SessionModel};
+#[cfg(feature = "mailer")]
+use crate::{utils::notification::send_notification, NotificationCategory};
 
 /// Updates a user's password and sends a confirmation email
 ///
@@ -92,7 +94,7 @@ pub async fn update_password(
     let session = request
         .extensions()
         .get::<SessionModel>()
-        .ok_or_else(|| Status::unauthenticated("No valid session found"))?;
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
 
     let request = request.get_ref();
 
@@ -103,7 +105,7 @@ pub async fn update_password(
 
         match service.db.select::<LinkModel>(link_id.clone()).await {
             Ok(Some(link)) => link,
-            Ok(None) => return Err(Status::not_found("Link not found")),
+            Ok(None) => return Err(keyed_status(Code::NotFound, "link.not_found")),
             Err(e) => return Err(Status::internal(e.to_string())),
         };
     }
@@ -115,13 +117,13 @@ pub async fn update_password(
         .await
     {
         Ok(Some(user)) => user,
-        Ok(None) => return Err(Status::not_found("User not found")),
+        Ok(None) => return Err(keyed_status(Code::NotFound, "user.not_found")),
         Err(e) => return Err(Status::internal(e.to_string())),
     };
 
     // Verify old password
     if !SessionModel::verify_password(request.old_password.clone(), user.password_hash).await? {
-        return Err(Status::invalid_argument("Invalid password"));
+        return Err(keyed_status(Code::InvalidArgument, "password.invalid"));
     }
 
     // Update password hash
@@ -146,7 +148,7 @@ pub async fn update_password(
         // Create reset link for confirmation email
         let expiry = Utc::now()
             .checked_add_days(Days::new(2))
-            .ok_or_else(|| Status::internal("Failed to calculate expiry date"))?;
+            .ok_or_else(|| keyed_status(Code::Internal, "link.expiry_date_failed"))?;
 
         let reset_link = LinkModel::create_from_user(
             &service.db,
@@ -176,10 +178,14 @@ pub async fn update_password(
         )
         .map_err(|e| Status::internal(e.to_string()))?;
 
-        Mailer::new()
-            .send_mail(message)
-            .await
-            .map_err(|e| Status::internal(e.to_string()))?;
+        send_notification(
+            &Mailer::new(),
+            &user,
+            NotificationCategory::Security,
+            message,
+        )
+        .await
+        .map_err(|e| Status::internal(e.to_string()))?;
     }
 
     Ok(Response::new(Empty {}))
@@ -16,10 +16,10 @@
 
 use super::*;
 
-use kiro_database::db_bridge::DatabaseOperations;
-use tonic::{Request, Response, Status};
+use kiro_api::client::v1::{Privacy, Settings};
+use tonic::{Code, Request, Response, Status};
 
-use crate::SessionModel;
+use crate::{keyed_status, SessionModel, UserModel};
 
 /// Updates a user's privacy settings
 ///
@@ -32,6 +32,9 @@ use crate::SessionModel;
 ///
 /// Returns an empty response on success
 ///
+/// Withdrawing a consent applies the privacy policy of the user to the IP
+/// addresses of its existing sessions.
+///
 /// # Errors
 ///
 /// Returns Status::unauthenticated if no valid session is found
@@ -73,27 +76,39 @@ pub async fn update_privacy(
     let session = request
         .extensions()
         .get::<SessionModel>()
-        .ok_or_else(|| Status::unauthenticated("No valid session found"))?;
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
 
     // Get the field and value from the request
     let field = request.get_ref().field.as_str();
     let value = request.get_ref().value;
 
     // Validate the privacy field name
-    if !["data_collection", "location"].contains(&field) {
-        return Err(Status::invalid_argument("Invalid privacy field"));
+    let mut privacy = Privacy::default();
+    match field {
+        "data_collection" => privacy.data_collection = value,
+        "location" => privacy.location = value,
+        _ => {
+            return Err(keyed_status(
+                Code::InvalidArgument,
+                "settings.invalid_privacy_field",
+            ))
+        }
     }
 
-    // Update the user's privacy settings in the database
-    service
-        .db
-        .update_field(
-            session.user_id.clone(),
-            &format!("settings/privacy/{}", field),
-            value,
-        )
-        .await
-        .map_err(|e| Status::internal(e.to_string()))?;
+    let patch = Settings {
+        privacy: Some(privacy),
+        ..Default::default()
+    };
+
+    // Update the user's privacy settings, withdrawn consents also apply to the
+    // existing sessions
+    UserModel::update_setting(
+        &service.db,
+        session.user_id.clone(),
+        &patch,
+        &format!("privacy.{}", field),
+    )
+    .await?;
 
     Ok(Response::new(Empty {}))
 }
@@ -102,9 +117,15 @@ pub async fn update_privacy(
 mod tests {
     use super::*;
 
-    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
+    use kiro_database::{
+        db_bridge::MockDatabaseOperations,
+        db_query::{DbValue, FieldUpdate},
+        DatabaseError,
+    };
     use mockall::predicate::eq;
 
+    use crate::UserModel;
+
     #[tokio::test]
     async fn test_update_privacy_data_collection_success() {
         let mut mock_db = MockDatabaseOperations::new();
@@ -112,14 +133,21 @@ mod tests {
         let user_id = test_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(user_id),
-                eq("settings/privacy/data_collection"),
-                eq(true),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["privacy"]["data_collection"] == true
+                }
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -142,10 +170,21 @@ mod tests {
         let user_id = test_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(eq(user_id), eq("settings/privacy/location"), eq(false))
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["privacy"]["location"] == false
+                }
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -161,6 +200,58 @@ mod tests {
         assert!(response.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_update_privacy_withdraw_consent() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let test_session = SessionModel::default();
+        let user_id = test_session.user_id.clone();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["privacy"]["data_collection"] == false
+                }
+                _ => false,
+            })
+            .times(1)
+            .returning(|_| {
+                let mut user = UserModel::default();
+                user.settings.privacy.data_collection = false;
+                Ok(vec![user])
+            });
+
+        // Withdrawing a consent anonymizes the existing sessions
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_read_by_field_thing::<SessionModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut request = Request::new(UpdatePrivacyRequest {
+            field: "data_collection".to_string(),
+            value: false,
+        });
+        request.extensions_mut().insert(test_session);
+
+        let response = update_privacy(&service, request).await;
+        assert!(response.is_ok());
+    }
+
     #[tokio::test]
     async fn test_update_privacy_no_session() {
         let mock_db = MockDatabaseOperations::new();
@@ -206,14 +297,10 @@ mod tests {
         let user_id = test_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(user_id),
-                eq("settings/privacy/data_collection"),
-                eq(true),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
             .times(1)
-            .returning(|_, _, _| Err(DatabaseError::Internal("Database error".to_string())));
+            .returning(|_| Err(DatabaseError::Internal("Database error".to_string())));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -238,14 +325,21 @@ mod tests {
         let user_id = admin_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(user_id),
-                eq("settings/privacy/data_collection"),
-                eq(true),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["privacy"]["data_collection"] == true
+                }
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -289,21 +383,39 @@ mod tests {
 
         // Test data_collection first
         mock_db
-            .expect_update_field()
-            .with(
-                eq(user_id.clone()),
-                eq("settings/privacy/data_collection"),
-                eq(true),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(user_id.clone()))
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["privacy"]["data_collection"] == true
+                }
+                _ => false,
+            })
+            .times(1)
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         // Then test location
         mock_db
-            .expect_update_field()
-            .with(eq(user_id), eq("settings/privacy/location"), eq(false))
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["privacy"]["location"] == false
+                }
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -0,0 +1,214 @@
+// services/user/update_profile.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use tonic::{Code, Request, Response, Status};
+
+use crate::{keyed_status, SessionModel, UserModel};
+
+/// Updates a user's public profile
+///
+/// Only the fields present in the request are updated, an empty string clears
+/// the field.
+///
+/// # Arguments
+///
+/// * `service` - The UserService instance
+/// * `request` - The request containing the profile fields to update
+///
+/// # Returns
+///
+/// Returns an empty response on success
+///
+/// # Errors
+///
+/// Returns Status::unauthenticated if no valid session is found
+/// Returns Status::invalid_argument if a field is invalid
+/// Returns Status::already_exists if the handle is used by another user
+/// Returns Status::internal for database errors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tonic::{Request, Response, Status};
+/// use kiro_api::client::v1::{client_service_server::ClientService, UpdateProfileRequest};
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = kiro_client::ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Update profile request
+/// let request = Request::new(UpdateProfileRequest {
+///     display_name: Some("Jane Doe".to_string()),
+///     handle: Some("jane.doe".to_string()),
+///     bio: None,
+///     timezone: Some("Europe/Paris".to_string()),
+///     locale: Some("fr-FR".to_string()),
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     ClientService::update_profile(&service, request).await;
+///
+///     println!("Profile updated successfully");
+/// });
+/// ```
+pub async fn update_profile(
+    service: &ClientService, request: Request<UpdateProfileRequest>,
+) -> Result<Response<Empty>, Status> {
+    // Get authenticated session from request extensions
+    let session = request
+        .extensions()
+        .get::<SessionModel>()
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
+
+    // Validate and store the profile fields
+    UserModel::update_profile(&service.db, session.user_id.clone(), request.get_ref()).await?;
+
+    Ok(Response::new(Empty {}))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError, DbId};
+    use mockall::predicate::eq;
+
+    #[tokio::test]
+    async fn test_update_profile_success() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let test_session = SessionModel::default();
+        let user_id = test_session.user_id.clone();
+
+        mock_db
+            .expect_update_field::<Option<String>>()
+            .with(
+                eq(user_id),
+                eq("display_name"),
+                eq(Some("Jane Doe".to_string())),
+            )
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut request = Request::new(UpdateProfileRequest {
+            display_name: Some(" Jane Doe ".to_string()),
+            ..Default::default()
+        });
+        request.extensions_mut().insert(test_session);
+
+        let response = update_profile(&service, request).await;
+        assert!(response.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_update_profile_no_session() {
+        let mock_db = MockDatabaseOperations::new();
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let request = Request::new(UpdateProfileRequest::default());
+        // Don't insert session into extensions
+
+        let error = update_profile(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::Unauthenticated);
+        assert_eq!(error.message(), "No valid session found");
+    }
+
+    #[tokio::test]
+    async fn test_update_profile_handle_taken() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let test_session = SessionModel::default();
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .with(eq("users"), eq("handle"), eq("jane.doe"), eq(None))
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(vec![UserModel {
+                    id: DbId::from(("users", "other")),
+                    ..Default::default()
+                }])
+            });
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut request = Request::new(UpdateProfileRequest {
+            handle: Some("jane.doe".to_string()),
+            ..Default::default()
+        });
+        request.extensions_mut().insert(test_session);
+
+        let error = update_profile(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::AlreadyExists);
+    }
+
+    #[tokio::test]
+    async fn test_update_profile_invalid_locale() {
+        let mock_db = MockDatabaseOperations::new();
+        let test_session = SessionModel::default();
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut request = Request::new(UpdateProfileRequest {
+            locale: Some("french".to_string()),
+            ..Default::default()
+        });
+        request.extensions_mut().insert(test_session);
+
+        let error = update_profile(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_update_profile_db_error() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let test_session = SessionModel::default();
+
+        mock_db
+            .expect_update_field::<Option<String>>()
+            .times(1)
+            .returning(|_, _, _| Err(DatabaseError::Internal("Database error".to_string())));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let mut request = Request::new(UpdateProfileRequest {
+            bio: Some("Hello".to_string()),
+            ..Default::default()
+        });
+        request.extensions_mut().insert(test_session);
+
+        let error = update_profile(&service, request).await.unwrap_err();
+        assert_eq!(error.code(), tonic::Code::Internal);
+    }
+}
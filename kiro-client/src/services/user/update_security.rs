@@ -16,10 +16,10 @@
 
 use super::*;
 
-use kiro_database::db_bridge::DatabaseOperations;
-use tonic::{Request, Response, Status};
+use kiro_api::client::v1::{Security, Settings};
+use tonic::{Code, Request, Response, Status};
 
-use crate::SessionModel;
+use crate::{keyed_status, SessionModel, UserModel};
 
 /// Updates a user's security settings
 ///
@@ -74,36 +74,45 @@ pub async fn update_security(
     let session = request
         .extensions()
         .get::<SessionModel>()
-        .ok_or_else(|| Status::unauthenticated("No valid session found"))?;
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
 
     // Get the field and value from the request
     let field = request.get_ref().field.as_str();
+    let value = request.get_ref().value.is_some();
 
     // QR code field is immutable
     if field == "qr_code" {
-        return Err(Status::invalid_argument("QR code field is immutable"));
+        return Err(keyed_status(
+            Code::InvalidArgument,
+            "settings.qr_code_immutable",
+        ));
     }
 
-    let value = match field {
-        "two_factor" | "magic_link" => serde_json::Value::Bool(request.get_ref().value.is_some()),
-        "qr_code" => serde_json::Value::String(request.get_ref().value.is_some().to_string()),
-        _ => unreachable!(),
-    };
-
-    if !["two_factor", "qr_code", "magic_link"].contains(&field) {
-        return Err(Status::invalid_argument("Invalid security field"));
+    let mut security = Security::default();
+    match field {
+        "two_factor" => security.two_factor = value,
+        "magic_link" => security.magic_link = value,
+        _ => {
+            return Err(keyed_status(
+                Code::InvalidArgument,
+                "settings.invalid_security_field",
+            ))
+        }
     }
 
-    // Update the security setting in the database
-    service
-        .db
-        .update_field(
-            session.user_id.clone(),
-            &format!("settings/security/{}", field),
-            value,
-        )
-        .await
-        .map_err(|e| Status::internal(e.to_string()))?;
+    let patch = Settings {
+        security: Some(security),
+        ..Default::default()
+    };
+
+    // Update the security setting
+    UserModel::update_setting(
+        &service.db,
+        session.user_id.clone(),
+        &patch,
+        &format!("security.{}", field),
+    )
+    .await?;
 
     Ok(Response::new(Empty {}))
 }
@@ -113,7 +122,11 @@ mod tests {
     use super::*;
 
     use kiro_api::client::v1::update_security_request::Value as JsonValue;
-    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
+    use kiro_database::{
+        db_bridge::MockDatabaseOperations,
+        db_query::{DbValue, FieldUpdate},
+        DatabaseError,
+    };
     use mockall::predicate::eq;
 
     #[tokio::test]
@@ -123,14 +136,21 @@ mod tests {
         let user_id = test_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(user_id),
-                eq("settings/security/two_factor"),
-                eq(serde_json::Value::Bool(true)),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["security"]["two_factor"] == true
+                }
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -153,14 +173,21 @@ mod tests {
         let user_id = test_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(user_id),
-                eq("settings/security/two_factor"),
-                eq(serde_json::Value::Bool(false)),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["security"]["two_factor"] == false
+                }
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -183,14 +210,21 @@ mod tests {
         let user_id = test_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(user_id),
-                eq("settings/security/magic_link"),
-                eq(serde_json::Value::Bool(true)),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["security"]["magic_link"] == true
+                }
+                _ => false,
+            })
+            .times(1)
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -231,14 +265,10 @@ mod tests {
         let user_id = test_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(user_id),
-                eq("settings/security/two_factor"),
-                eq(serde_json::Value::Bool(true)),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
             .times(1)
-            .returning(|_, _, _| Err(DatabaseError::Internal("Database error".to_string())));
+            .returning(|_| Err(DatabaseError::Internal("Database error".to_string())));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -263,14 +293,21 @@ mod tests {
         let user_id = admin_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(
-                eq(user_id),
-                eq("settings/security/two_factor"),
-                eq(serde_json::Value::Bool(true)),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["security"]["two_factor"] == true
+                }
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -294,25 +331,39 @@ mod tests {
 
         // First update: two_factor
         mock_db
-            .expect_update_field()
-            .with(
-                eq(user_id.clone()),
-                eq("settings/security/two_factor"),
-                eq(serde_json::Value::Bool(true)),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(user_id.clone()))
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["security"]["two_factor"] == true
+                }
+                _ => false,
+            })
+            .times(1)
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         // Second update: magic_link
         mock_db
-            .expect_update_field()
-            .with(
-                eq(user_id.clone()),
-                eq("settings/security/magic_link"),
-                eq(serde_json::Value::Bool(false)),
-            )
+            .expect_select::<UserModel>()
+            .with(eq(user_id.clone()))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => {
+                    settings["security"]["magic_link"] == false
+                }
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -0,0 +1,215 @@
+// services/user/update_settings.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use kiro_api::client::v1::UpdateSettingsResponse;
+use tonic::{Code, Request, Response, Status};
+
+use crate::{keyed_status, SessionModel, UserModel};
+
+/// Updates a user's settings
+///
+/// Only the fields listed in the update mask are updated, all of them at once.
+/// The update is rejected if the settings changed since the given version was
+/// read.
+///
+/// # Arguments
+///
+/// * `service` - The UserService instance
+/// * `request` - The request containing the partial settings, the update mask and the version
+///
+/// # Returns
+///
+/// Returns the new settings version on success
+///
+/// # Errors
+///
+/// Returns Status::unauthenticated if no valid session is found
+/// Returns Status::invalid_argument if the mask is empty or a path or value is invalid
+/// Returns Status::aborted if the version is stale, the settings must be read again
+/// Returns Status::internal for database errors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tonic::{Request, Response, Status};
+/// use kiro_api::{
+///     client::v1::{client_service_server::ClientService, Settings, UpdateSettingsRequest},
+///     google::protobuf::FieldMask,
+/// };
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// // Mock database
+/// let mock_db = MockDatabaseOperations::new();
+///
+/// // Mock service
+/// let service = kiro_client::ClientService {
+///     db: Database::Mock(mock_db),
+/// };
+///
+/// // Switch to the dark theme
+/// let request = Request::new(UpdateSettingsRequest {
+///     settings: Some(Settings {
+///         theme: Some(1),
+///         ..Default::default()
+///     }),
+///     update_mask: Some(FieldMask {
+///         paths: vec!["theme".to_string()],
+///     }),
+///     version: 0,
+/// });
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     ClientService::update_settings(&service, request).await;
+///
+///     println!("Settings updated");
+/// });
+/// ```
+pub async fn update_settings(
+    service: &ClientService, request: Request<UpdateSettingsRequest>,
+) -> Result<Response<UpdateSettingsResponse>, Status> {
+    // Get authenticated session from request extensions
+    let session = request
+        .extensions()
+        .get::<SessionModel>()
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
+
+    let request = request.get_ref();
+    let patch = request.settings.clone().unwrap_or_default();
+    let paths = request
+        .update_mask
+        .as_ref()
+        .map(|mask| mask.paths.clone())
+        .unwrap_or_default();
+
+    let user = UserModel::update_settings(
+        &service.db,
+        session.user_id.clone(),
+        &patch,
+        &paths,
+        request.version,
+    )
+    .await?;
+
+    Ok(Response::new(UpdateSettingsResponse {
+        version: user.settings_version,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_api::{client::v1::Settings, google::protobuf::FieldMask};
+    use kiro_database::{
+        db_bridge::MockDatabaseOperations,
+        db_query::{DbValue, FieldUpdate},
+    };
+
+    fn request(paths: Vec<&str>, version: u64) -> Request<UpdateSettingsRequest> {
+        let mut request = Request::new(UpdateSettingsRequest {
+            settings: Some(Settings {
+                language: Some(2),
+                ..Default::default()
+            }),
+            update_mask: Some(FieldMask {
+                paths: paths.into_iter().map(String::from).collect(),
+            }),
+            version,
+        });
+        request.extensions_mut().insert(SessionModel::default());
+        request
+    }
+
+    #[tokio::test]
+    async fn test_update_settings_success() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => settings["language"] == "French",
+                _ => false,
+            })
+            .times(1)
+            .returning(|_| {
+                Ok(vec![UserModel {
+                    settings_version: 1,
+                    ..Default::default()
+                }])
+            });
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let response = update_settings(&service, request(vec!["language"], 0))
+            .await
+            .unwrap();
+        assert_eq!(response.into_inner().version, 1);
+    }
+
+    #[tokio::test]
+    async fn test_update_settings_lost_race() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        // Another update bumped the version between the read and the write
+        mock_db
+            .expect_update_where::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(vec![]));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let error = update_settings(&service, request(vec!["language"], 0))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), tonic::Code::Aborted);
+    }
+
+    #[tokio::test]
+    async fn test_update_settings_invalid_path() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        let service = ClientService {
+            db: Database::Mock(mock_db),
+        };
+
+        let error = update_settings(&service, request(vec!["password_hash"], 0))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+    }
+}
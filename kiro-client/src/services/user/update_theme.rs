@@ -16,10 +16,10 @@
 
 use super::*;
 
-use kiro_database::db_bridge::DatabaseOperations;
-use tonic::{Request, Response, Status};
+use kiro_api::client::v1::Settings;
+use tonic::{Code, Request, Response, Status};
 
-use crate::SessionModel;
+use crate::{keyed_status, SessionModel, UserModel};
 
 /// Updates a user's theme preference
 ///
@@ -72,17 +72,15 @@ pub async fn update_theme(
     let session = request
         .extensions()
         .get::<SessionModel>()
-        .ok_or_else(|| Status::unauthenticated("No valid session found"))?;
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
 
-    // Get the new theme from the request
-    let theme = request.get_ref().theme;
+    let patch = Settings {
+        theme: Some(request.get_ref().theme),
+        ..Default::default()
+    };
 
-    // Update the user's theme in the database
-    service
-        .db
-        .update_field(session.user_id.clone(), "theme", theme)
-        .await
-        .map_err(|e| Status::internal(e.to_string()))?;
+    // Update the user's theme in the settings
+    UserModel::update_setting(&service.db, session.user_id.clone(), &patch, "theme").await?;
 
     Ok(Response::new(Empty {}))
 }
@@ -91,7 +89,11 @@ pub async fn update_theme(
 mod tests {
     use super::*;
 
-    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
+    use kiro_database::{
+        db_bridge::MockDatabaseOperations,
+        db_query::{DbValue, FieldUpdate},
+        DatabaseError,
+    };
     use mockall::predicate::eq;
 
     #[tokio::test]
@@ -101,10 +103,19 @@ mod tests {
         let user_id = test_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(eq(user_id), eq("theme"), eq(0)) // Assuming 0 represents light theme
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => settings["theme"] == "Light",
+                _ => false,
+            })
+            .times(1)
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -124,10 +135,19 @@ mod tests {
         let user_id = test_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(eq(user_id), eq("theme"), eq(1)) // Assuming 1 represents dark theme
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => settings["theme"] == "Dark",
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -162,10 +182,10 @@ mod tests {
         let user_id = test_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(eq(user_id), eq("theme"), eq(0))
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
             .times(1)
-            .returning(|_, _, _| Err(DatabaseError::Internal("Database error".to_string())));
+            .returning(|_| Err(DatabaseError::Internal("Database error".to_string())));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -187,10 +207,19 @@ mod tests {
         let user_id = admin_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(eq(user_id), eq("theme"), eq(0))
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => settings["theme"] == "Light",
+                _ => false,
+            })
+            .times(1)
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -211,17 +240,35 @@ mod tests {
 
         // First update: light theme
         mock_db
-            .expect_update_field()
-            .with(eq(user_id.clone()), eq("theme"), eq(0))
+            .expect_select::<UserModel>()
+            .with(eq(user_id.clone()))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => settings["theme"] == "Light",
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         // Second update: dark theme
         mock_db
-            .expect_update_field()
-            .with(eq(user_id), eq("theme"), eq(1))
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => settings["theme"] == "Dark",
+                _ => false,
+            })
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -247,10 +294,19 @@ mod tests {
         let user_id = test_session.user_id.clone();
 
         mock_db
-            .expect_update_field()
-            .with(eq(user_id), eq("theme"), eq(2)) // Assuming 2 represents system theme
+            .expect_select::<UserModel>()
+            .with(eq(user_id))
             .times(1)
-            .returning(|_, _, _| Ok(()));
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .withf(|update| match &update.changes[0] {
+                FieldUpdate::Set(_, DbValue::Json(settings)) => settings["theme"] == "System",
+                _ => false,
+            })
+            .times(1)
+            .returning(|_| Ok(vec![UserModel::default()]));
 
         let service = ClientService {
             db: Database::Mock(mock_db),
@@ -270,12 +326,21 @@ mod tests {
         let user_id = test_session.user_id.clone();
 
         // Multiple theme changes in sequence
-        for theme in [0, 1, 2, 0] {
+        for theme in ["Light", "Dark", "System", "Light"] {
+            mock_db
+                .expect_select::<UserModel>()
+                .with(eq(user_id.clone()))
+                .times(1)
+                .returning(|_| Ok(Some(UserModel::default())));
+
             mock_db
-                .expect_update_field()
-                .with(eq(user_id.clone()), eq("theme"), eq(theme))
+                .expect_update_where::<UserModel>()
+                .withf(move |update| match &update.changes[0] {
+                    FieldUpdate::Set(_, DbValue::Json(settings)) => settings["theme"] == theme,
+                    _ => false,
+                })
                 .times(1)
-                .returning(|_, _, _| Ok(()));
+                .returning(|_| Ok(vec![UserModel::default()]));
         }
 
         let service = ClientService {
@@ -16,14 +16,16 @@
 
 use super::*;
 
-use kiro_database::db_bridge::DatabaseOperations;
-use kiro_storage::{BucketS3, ByteStream};
-use tonic::{Request, Response, Status};
+use kiro_storage::BucketS3;
+use tonic::{Code, Request, Response, Status};
 
-use crate::SessionModel;
+use crate::{keyed_status, utils::avatar::store_avatar, SessionModel};
 
 /// Updates a user's avatar image
 ///
+/// The image must be a PNG, JPEG or WebP image. It is center cropped to a
+/// square and resized to each of the avatar sizes, without its metadata.
+///
 /// # Arguments
 ///
 /// * `service` - The UserService instance
@@ -31,12 +33,12 @@ use crate::SessionModel;
 ///
 /// # Returns
 ///
-/// Returns the URL of the uploaded avatar image on success
+/// Returns the URL of the largest variant and the URLs of all variants by size on success
 ///
 /// # Errors
 ///
 /// Returns Status::unauthenticated if no valid session is found
-/// Returns Status::invalid_argument if no avatar file is provided
+/// Returns Status::invalid_argument if no avatar file is provided, or if it is not a valid image
 /// Returns Status::internal for S3 storage or database errors
 ///
 /// # Example
@@ -77,34 +79,32 @@ pub async fn upload_avatar(
     let session = request
         .extensions()
         .get::<SessionModel>()
-        .ok_or_else(|| Status::unauthenticated("No valid session found"))?;
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
 
     // Extract avatar file from request
     let file = request
         .get_ref()
         .file
         .clone()
-        .ok_or_else(|| Status::invalid_argument("No avatar provided"))?;
-
-    // Upload file to S3
-    let avatar_stream = ByteStream::from(file.content);
-    let avatar_link = BucketS3::new()
-        .await
-        .put_object(
-            avatar_stream,
-            session.user_id.clone(),
-            "images",
-            &format!("image-{}", session.user_id.clone()),
-        )
-        .await
-        .map_err(|e| Status::internal(e.to_string()))?;
+        .ok_or_else(|| keyed_status(Code::InvalidArgument, "avatar.missing"))?;
 
-    // Update user's avatar field in database
-    service
-        .db
-        .update_field(session.user_id.clone(), "/avatar", avatar_link.clone())
-        .await
-        .map_err(|e| Status::internal(e.to_string()))?;
+    // Validate the image, then upload its variants
+    let variants = store_avatar(
+        &service.db,
+        &BucketS3::new().await,
+        session.user_id.clone(),
+        file.content,
+    )
+    .await?;
 
-    Ok(Response::new(UploadAvatarResponse { url: avatar_link }))
+    Ok(Response::new(UploadAvatarResponse {
+        url: variants
+            .last()
+            .map(|variant| variant.url.clone())
+            .unwrap_or_default(),
+        urls: variants
+            .into_iter()
+            .map(|variant| (variant.size, variant.url))
+            .collect(),
+    }))
 }
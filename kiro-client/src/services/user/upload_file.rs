@@ -0,0 +1,148 @@
+// services/user/upload_file.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use futures::StreamExt;
+use kiro_api::client::v1::{upload_file_request::Data, UploadPurpose};
+use kiro_storage::BucketS3;
+use tonic::{Code, Request, Response, Status, Streaming};
+
+use crate::{
+    error::ClientError,
+    keyed_status,
+    utils::upload::{receive_avatar, receive_file, UploadError},
+    SessionModel,
+};
+
+/// Uploads a file sent in chunks
+///
+/// The first frame carries the file metadata: name, content type, size,
+/// SHA-256 checksum and purpose. The following frames carry the content. The
+/// upload is stopped as soon as more bytes than declared are received, and is
+/// rejected if the checksum does not match.
+///
+/// Files are streamed to the bucket in parts. Avatars are processed like
+/// `UploadAvatar` ones once received.
+///
+/// # Arguments
+///
+/// * `service` - The UserService instance
+/// * `request` - The stream of frames, the metadata first
+///
+/// # Returns
+///
+/// Returns the URL of the file, or of the largest avatar variant along with
+/// the URLs of all variants by size
+///
+/// # Errors
+///
+/// Returns Status::unauthenticated if no valid session is found
+/// Returns Status::invalid_argument if the metadata is missing or invalid, or if the content does not match it
+/// Returns Status::cancelled if the client stream fails
+/// Returns Status::internal for S3 storage or database errors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use kiro_api::client::v1::{
+///     client_service_client::ClientServiceClient, upload_file_request::Data, FileMetadata,
+///     UploadFileRequest,
+/// };
+///
+/// // Async block to allow `await`
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     let mut client = ClientServiceClient::connect("http://[::1]:50051").await.unwrap();
+///
+///     let frames = vec![
+///         UploadFileRequest {
+///             data: Some(Data::Metadata(FileMetadata {
+///                 name: "hello.txt".to_string(),
+///                 content_type: "text/plain".to_string(),
+///                 size: 11,
+///                 checksum: "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+///                     .to_string(),
+///                 ..Default::default()
+///             })),
+///         },
+///         UploadFileRequest {
+///             data: Some(Data::Chunk(b"hello world".to_vec())),
+///         },
+///     ];
+///
+///     let response = client.upload_file(tokio_stream::iter(frames)).await;
+///
+///     println!("File uploaded: {:?}", response);
+/// });
+/// ```
+pub async fn upload_file(
+    service: &ClientService, request: Request<Streaming<UploadFileRequest>>,
+) -> Result<Response<UploadFileResponse>, Status> {
+    // Get authenticated session from request extensions
+    let session = request
+        .extensions()
+        .get::<SessionModel>()
+        .cloned()
+        .ok_or_else(|| keyed_status(Code::Unauthenticated, "session.missing"))?;
+
+    let mut frames = request.into_inner();
+
+    let metadata = match frames.message().await? {
+        Some(UploadFileRequest {
+            data: Some(Data::Metadata(metadata)),
+        }) => metadata,
+        _ => return Err(ClientError::from(UploadError::MissingMetadata).into()),
+    };
+
+    let chunks = frames.map(|frame| match frame {
+        Ok(UploadFileRequest {
+            data: Some(Data::Chunk(chunk)),
+        }) => Ok(chunk),
+        Ok(_) => Err(ClientError::from(UploadError::UnexpectedFrame)),
+        Err(_) => Err(ClientError::from(UploadError::Interrupted)),
+    });
+
+    let bucket = BucketS3::new().await;
+
+    let response = match metadata.purpose() {
+        UploadPurpose::Avatar => {
+            let variants =
+                receive_avatar(&service.db, &bucket, session.user_id, metadata, chunks).await?;
+
+            UploadFileResponse {
+                url: variants
+                    .last()
+                    .map(|variant| variant.url.clone())
+                    .unwrap_or_default(),
+                avatar_urls: variants
+                    .into_iter()
+                    .map(|variant| (variant.size, variant.url))
+                    .collect(),
+            }
+        }
+        UploadPurpose::File => {
+            let file =
+                receive_file(&service.db, &bucket, session.user_id, metadata, chunks).await?;
+
+            UploadFileResponse {
+                url: file.url,
+                ..Default::default()
+            }
+        }
+    };
+
+    Ok(Response::new(response))
+}
@@ -0,0 +1,308 @@
+// utils/avatar.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Cursor;
+
+use image::{
+    imageops::FilterType, DynamicImage, ImageDecoder, ImageError, ImageFormat, ImageReader, Limits,
+};
+use kiro_database::{db_bridge::DatabaseOperations, DbId};
+use kiro_storage::{BucketS3Trait, ByteStream};
+
+use crate::{error::ClientError, AvatarVariant, UserModel};
+
+/// # AvatarError
+///
+/// The AvatarError enum is an enum that represents the errors for avatar images.
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum AvatarError {
+    #[error("Avatar too large. Maximum size: {} bytes", AVATAR_MAX_SIZE)]
+    TooLarge,
+
+    #[error("Avatar must be a PNG, JPEG or WebP image")]
+    UnsupportedFormat,
+
+    #[error(
+        "Avatar dimensions too large. Maximum size: {}x{} pixels",
+        AVATAR_MAX_DIMENSION,
+        AVATAR_MAX_DIMENSION
+    )]
+    DimensionsTooLarge,
+
+    #[error("Avatar is not a valid image")]
+    InvalidImage,
+}
+
+impl AvatarError {
+    /// Returns the stable key of the error, used to localize its message
+    pub fn key(&self) -> &'static str {
+        match self {
+            AvatarError::TooLarge => "avatar.too_large",
+            AvatarError::UnsupportedFormat => "avatar.unsupported_format",
+            AvatarError::DimensionsTooLarge => "avatar.dimensions_too_large",
+            AvatarError::InvalidImage => "avatar.invalid_image",
+        }
+    }
+}
+
+/// Maximum size of an uploaded avatar, in bytes
+pub const AVATAR_MAX_SIZE: usize = 5 * 1024 * 1024;
+
+/// Maximum width and height of an uploaded avatar, in pixels
+const AVATAR_MAX_DIMENSION: u32 = 4096;
+
+/// Sizes of the generated variants, in pixels
+pub const AVATAR_SIZES: [u32; 3] = [64, 256, 512];
+
+/// # Avatar name
+///
+/// The `avatar_name` method returns the object name of an avatar variant in the bucket.
+pub fn avatar_name(size: u32) -> String {
+    format!("avatar-{}.png", size)
+}
+
+/// # Process avatar
+///
+/// The `process_avatar` method validates an uploaded avatar and returns its
+/// variants, as PNG images, for each of the [`AVATAR_SIZES`].
+///
+/// The image is decoded, so only actual PNG, JPEG and WebP images are accepted.
+/// Its EXIF orientation is applied, then it is center cropped to a square.
+/// Variants are encoded from the decoded pixels, so no metadata of the upload
+/// (EXIF GPS position included) is kept.
+pub fn process_avatar(bytes: &[u8]) -> Result<Vec<(u32, Vec<u8>)>, AvatarError> {
+    if bytes.len() > AVATAR_MAX_SIZE {
+        return Err(AvatarError::TooLarge);
+    }
+
+    let format = image::guess_format(bytes).map_err(|_| AvatarError::UnsupportedFormat)?;
+    if !matches!(
+        format,
+        ImageFormat::Png | ImageFormat::Jpeg | ImageFormat::WebP
+    ) {
+        return Err(AvatarError::UnsupportedFormat);
+    }
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(AVATAR_MAX_DIMENSION);
+    limits.max_image_height = Some(AVATAR_MAX_DIMENSION);
+
+    let mut reader = ImageReader::with_format(Cursor::new(bytes), format);
+    reader.limits(limits);
+
+    let mut decoder = reader.into_decoder().map_err(decode_error)?;
+    let orientation = decoder.orientation().map_err(decode_error)?;
+    let mut image = DynamicImage::from_decoder(decoder).map_err(decode_error)?;
+    image.apply_orientation(orientation);
+
+    // Keep the center square of the image
+    let side = image.width().min(image.height());
+    let square = image.crop_imm(
+        (image.width() - side) / 2,
+        (image.height() - side) / 2,
+        side,
+        side,
+    );
+
+    AVATAR_SIZES
+        .iter()
+        .map(|&size| {
+            let mut variant = Cursor::new(Vec::new());
+            square
+                .resize_exact(size, size, FilterType::Lanczos3)
+                .write_to(&mut variant, ImageFormat::Png)
+                .map_err(|_| AvatarError::InvalidImage)?;
+
+            Ok((size, variant.into_inner()))
+        })
+        .collect()
+}
+
+/// Maps a decoding error, images over the dimension limits are reported as such
+fn decode_error(error: ImageError) -> AvatarError {
+    match error {
+        ImageError::Limits(_) => AvatarError::DimensionsTooLarge,
+        _ => AvatarError::InvalidImage,
+    }
+}
+
+/// # Store avatar
+///
+/// The `store_avatar` method processes an uploaded avatar, uploads its variants
+/// to the bucket and stores their URLs on the user.
+pub async fn store_avatar<DB: DatabaseOperations + Send + Sync, B: BucketS3Trait>(
+    db: &DB, bucket: &B, user_id: DbId, bytes: Vec<u8>,
+) -> Result<Vec<AvatarVariant>, ClientError> {
+    // Decoding and resizing are CPU bound
+    let processed = tokio::task::spawn_blocking(move || process_avatar(&bytes))
+        .await
+        .map_err(|_| AvatarError::InvalidImage)??;
+
+    let mut variants = Vec::with_capacity(processed.len());
+    for (size, image) in processed {
+        let url = bucket
+            .put_object(
+                ByteStream::from(image),
+                user_id.clone(),
+                "images",
+                &avatar_name(size),
+            )
+            .await?;
+
+        variants.push(AvatarVariant { size, url });
+    }
+
+    UserModel::update_avatar(db, user_id, variants.clone()).await?;
+
+    Ok(variants)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use image::{codecs::jpeg::JpegEncoder, Rgb, RgbImage};
+    use kiro_database::db_bridge::MockDatabaseOperations;
+    use kiro_storage::MockBucketS3Trait;
+
+    fn encode(image: &RgbImage, format: ImageFormat) -> Vec<u8> {
+        let mut bytes = Cursor::new(Vec::new());
+        image.write_to(&mut bytes, format).unwrap();
+        bytes.into_inner()
+    }
+
+    #[test]
+    fn test_process_avatar_variants() {
+        // Wide image, the left and right sides are cropped
+        let image = RgbImage::from_fn(300, 200, |x, _| {
+            if (50..250).contains(&x) {
+                Rgb([255, 0, 0])
+            } else {
+                Rgb([0, 0, 255])
+            }
+        });
+
+        let variants = process_avatar(&encode(&image, ImageFormat::Png)).unwrap();
+        assert_eq!(
+            variants.iter().map(|(size, _)| *size).collect::<Vec<_>>(),
+            AVATAR_SIZES
+        );
+
+        for (size, bytes) in variants {
+            let variant = image::load_from_memory_with_format(&bytes, ImageFormat::Png)
+                .unwrap()
+                .to_rgb8();
+            assert_eq!(variant.dimensions(), (size, size));
+            assert_eq!(variant.get_pixel(0, size / 2), &Rgb([255, 0, 0]));
+        }
+    }
+
+    #[test]
+    fn test_process_avatar_strips_exif() {
+        let image = RgbImage::from_pixel(100, 100, Rgb([0, 128, 0]));
+        let jpeg = encode(&image, ImageFormat::Jpeg);
+
+        // APP1 segment with an empty big endian TIFF directory
+        let exif = b"Exif\0\0MM\0\x2a\0\0\0\x08\0\0\0\0\0\0";
+        let mut bytes = jpeg[..2].to_vec();
+        bytes.extend_from_slice(&[0xFF, 0xE1, 0, (exif.len() + 2) as u8]);
+        bytes.extend_from_slice(exif);
+        bytes.extend_from_slice(&jpeg[2..]);
+
+        for (_, variant) in process_avatar(&bytes).unwrap() {
+            assert!(!variant.windows(4).any(|window| window == b"Exif"));
+        }
+    }
+
+    #[test]
+    fn test_process_avatar_rejections() {
+        assert_eq!(
+            process_avatar(&vec![0; AVATAR_MAX_SIZE + 1]),
+            Err(AvatarError::TooLarge)
+        );
+        assert_eq!(
+            process_avatar(b"GIF89a\x01\0\x01\0\0\0\0;"),
+            Err(AvatarError::UnsupportedFormat)
+        );
+        assert_eq!(
+            process_avatar(b"not an image"),
+            Err(AvatarError::UnsupportedFormat)
+        );
+
+        // Valid signature, truncated data
+        let png = encode(&RgbImage::new(10, 10), ImageFormat::Png);
+        assert_eq!(
+            process_avatar(&png[..png.len() / 2]),
+            Err(AvatarError::InvalidImage)
+        );
+
+        // Only the header is read before the limits are checked
+        let mut jpeg = Vec::new();
+        JpegEncoder::new(&mut jpeg)
+            .encode(&vec![0; 3 * 4097], 4097, 1, image::ExtendedColorType::Rgb8)
+            .unwrap();
+        assert_eq!(process_avatar(&jpeg), Err(AvatarError::DimensionsTooLarge));
+    }
+
+    #[tokio::test]
+    async fn test_store_avatar() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let mut mock_bucket = MockBucketS3Trait::new();
+
+        mock_bucket
+            .expect_put_object()
+            .withf(|_, _, path, name| path == "images" && name.starts_with("avatar-"))
+            .times(AVATAR_SIZES.len())
+            .returning(|_, _, _, name| Ok(format!("https://bucket/{}", name)));
+
+        mock_db
+            .expect_update_where::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(vec![]));
+
+        let png = encode(&RgbImage::new(32, 32), ImageFormat::Png);
+        let variants = store_avatar(&mock_db, &mock_bucket, DbId::default(), png)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            variants.last(),
+            Some(&AvatarVariant {
+                size: 512,
+                url: "https://bucket/avatar-512.png".to_string(),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_store_avatar_invalid() {
+        let mock_db = MockDatabaseOperations::new();
+        let mock_bucket = MockBucketS3Trait::new();
+
+        let result = store_avatar(
+            &mock_db,
+            &mock_bucket,
+            DbId::default(),
+            b"not an image".to_vec(),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(ClientError::InvalidAvatar(AvatarError::UnsupportedFormat))
+        ));
+    }
+}
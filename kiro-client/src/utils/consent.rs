@@ -0,0 +1,502 @@
+// utils/consent.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use kiro_api::{
+    auth::v1::AcceptedPolicy,
+    client::v1::{Consent, ListConsentsResponse, PolicyDocument},
+};
+use kiro_database::{db_bridge::DatabaseOperations, DbId};
+use tokio::sync::RwLock;
+use tonic::Status;
+
+use crate::{error::ClientError, PolicyConsentModel, PolicyDocumentModel, PolicyKind};
+
+/// Time the current policy versions are cached for
+const POLICY_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// # Policy Cache
+///
+/// The policy cache keeps the current version of each policy document, looked up
+/// on every authenticated request. A newly published version is enforced once the
+/// cached versions expire.
+#[derive(Debug, Clone, Default)]
+pub struct PolicyCache {
+    current: Arc<RwLock<Option<(Instant, Vec<PolicyDocumentModel>)>>>,
+}
+
+impl PolicyCache {
+    /// Creates an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the current documents, from the database once the cache expired
+    ///
+    /// # Arguments
+    /// * `db` - Database connection implementing DatabaseOperations trait
+    pub async fn current<DB: DatabaseOperations + Send + Sync>(
+        &self, db: &DB,
+    ) -> Result<Vec<PolicyDocumentModel>, ClientError> {
+        if let Some((fetched_at, documents)) = &*self.current.read().await {
+            if fetched_at.elapsed() < POLICY_CACHE_TTL {
+                return Ok(documents.clone());
+            }
+        }
+
+        let documents = PolicyDocumentModel::get_current(db).await?;
+        *self.current.write().await = Some((Instant::now(), documents.clone()));
+
+        Ok(documents)
+    }
+}
+
+/// Checks the consent of a user
+///
+/// Rejects the users who have not accepted the current version of every policy
+/// document, as after a new version is published.
+///
+/// # Arguments
+/// * `db` - Database connection implementing DatabaseOperations trait
+/// * `policies` - Cache of the current policy versions
+/// * `user_id` - User identifier
+///
+/// # Returns
+/// * `Ok(())` - The user accepted the current documents
+/// * `Err(Status::FailedPrecondition)` - The user must accept the new versions
+/// * `Err(Status)` - Database error
+pub async fn check_consent<DB: DatabaseOperations + Send + Sync>(
+    db: &DB, policies: &PolicyCache, user_id: DbId,
+) -> Result<(), Status> {
+    let current = policies.current(db).await?;
+    if current.is_empty() {
+        return Ok(());
+    }
+
+    let consents = PolicyConsentModel::get_consents_by_user(db, user_id).await?;
+
+    if current.iter().all(|document| {
+        consents
+            .iter()
+            .any(|consent| consent.document == document.id)
+    }) {
+        Ok(())
+    } else {
+        Err(ClientError::PolicyAcceptanceRequired.into())
+    }
+}
+
+/// Checks the policy versions accepted on registration
+///
+/// The client sends the version of each document shown to the user. Every current
+/// document must be accepted, and in its current version, so a user registering
+/// from an outdated page is shown the new versions first.
+///
+/// # Arguments
+/// * `db` - Database connection implementing DatabaseOperations trait
+/// * `accepted` - Versions accepted by the user
+///
+/// # Returns
+/// * `Ok(Vec<PolicyDocumentModel>)` - Accepted documents, to record once the user exists
+/// * `Err(ClientError::PolicyNotCurrent)` - An accepted version is not the current one
+/// * `Err(ClientError::PolicyAcceptanceRequired)` - A current document was not accepted
+/// * `Err(ClientError)` - Database error
+pub(crate) async fn check_accepted_policies<DB: DatabaseOperations + Send + Sync>(
+    db: &DB, accepted: &[AcceptedPolicy],
+) -> Result<Vec<PolicyDocumentModel>, ClientError> {
+    let current = PolicyDocumentModel::get_current(db).await?;
+
+    for policy in accepted {
+        let is_current = current.iter().any(|document| {
+            PolicyKind::from_name(&policy.kind) == Some(document.kind)
+                && document.version == policy.version
+        });
+
+        if !is_current {
+            return Err(ClientError::PolicyNotCurrent);
+        }
+    }
+
+    for document in &current {
+        if !accepted
+            .iter()
+            .any(|policy| policy.kind == document.kind.as_str())
+        {
+            return Err(ClientError::PolicyAcceptanceRequired);
+        }
+    }
+
+    Ok(current)
+}
+
+/// Accepts a policy document
+///
+/// Records the acceptance of the current version of a kind of document. The
+/// acceptance of a version already accepted returns the recorded one, so a
+/// client may retry safely.
+///
+/// # Arguments
+/// * `db` - Database connection implementing DatabaseOperations trait
+/// * `user_id` - User identifier
+/// * `kind` - Name of the kind of document, `Terms` or `Privacy`
+/// * `version` - Version of the document shown to the user
+/// * `ip_address` - IP address the acceptance came from
+///
+/// # Returns
+/// * `Ok(Consent)` - Recorded consent
+/// * `Err(ClientError::PolicyNotCurrent)` - The version is not the current one
+/// * `Err(ClientError)` - Database error
+pub(crate) async fn accept_policy<DB: DatabaseOperations + Send + Sync>(
+    db: &DB, user_id: DbId, kind: &str, version: u32, ip_address: Option<String>,
+) -> Result<Consent, ClientError> {
+    let kind = PolicyKind::from_name(kind).ok_or(ClientError::PolicyNotCurrent)?;
+
+    let document = PolicyDocumentModel::get_current(db)
+        .await?
+        .into_iter()
+        .find(|document| document.kind == kind && document.version == version)
+        .ok_or(ClientError::PolicyNotCurrent)?;
+
+    let consents = PolicyConsentModel::get_consents_by_user(db, user_id.clone()).await?;
+    if let Some(consent) = consents
+        .iter()
+        .find(|consent| consent.document == document.id)
+    {
+        return Ok(Consent::from(consent));
+    }
+
+    let consent = PolicyConsentModel::record(db, user_id, &document, ip_address).await?;
+
+    Ok(Consent::from(&consent))
+}
+
+/// Lists the consents of a user
+///
+/// # Arguments
+/// * `db` - Database connection implementing DatabaseOperations trait
+/// * `user_id` - User identifier
+///
+/// # Returns
+/// * `Ok(ListConsentsResponse)` - Consent history, most recent first, and the
+///   current documents the user has not accepted yet
+/// * `Err(ClientError)` - Database error
+pub(crate) async fn list_consents<DB: DatabaseOperations + Send + Sync>(
+    db: &DB, user_id: DbId,
+) -> Result<ListConsentsResponse, ClientError> {
+    let consents = PolicyConsentModel::get_consents_by_user(db, user_id).await?;
+
+    let pending = PolicyDocumentModel::get_current(db)
+        .await?
+        .iter()
+        .filter(|document| {
+            !consents
+                .iter()
+                .any(|consent| consent.document == document.id)
+        })
+        .map(PolicyDocument::from)
+        .collect();
+
+    Ok(ListConsentsResponse {
+        consents: consents.iter().map(Consent::from).collect(),
+        pending,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::db_bridge::MockDatabaseOperations;
+
+    use crate::CreatePolicyConsentModel;
+
+    fn terms(version: u32) -> PolicyDocumentModel {
+        PolicyDocumentModel {
+            id: DbId::from(("policy_documents", format!("terms_{}", version).as_str())),
+            version,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_consent_requires_new_version() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![terms(2)]));
+
+        mock_db
+            .expect_read_by_field_thing::<PolicyConsentModel>()
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(vec![PolicyConsentModel {
+                    document: terms(1).id,
+                    ..Default::default()
+                }])
+            });
+
+        let status = check_consent(&mock_db, &PolicyCache::new(), DbId::default())
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+        assert_eq!(
+            kiro_database::error_key(&status).as_deref(),
+            Some("policy.acceptance_required")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_consent_accepted() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![terms(2)]));
+
+        mock_db
+            .expect_read_by_field_thing::<PolicyConsentModel>()
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(vec![PolicyConsentModel {
+                    document: terms(2).id,
+                    ..Default::default()
+                }])
+            });
+
+        assert!(
+            check_consent(&mock_db, &PolicyCache::new(), DbId::default())
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_policy_cache_reuses_current() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        // Only the first check reads the current documents
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![terms(2)]));
+
+        mock_db
+            .expect_read_by_field_thing::<PolicyConsentModel>()
+            .times(2)
+            .returning(|_, _, _, _| {
+                Ok(vec![PolicyConsentModel {
+                    document: terms(2).id,
+                    ..Default::default()
+                }])
+            });
+
+        let policies = PolicyCache::new();
+        assert!(check_consent(&mock_db, &policies, DbId::default())
+            .await
+            .is_ok());
+        assert!(check_consent(&mock_db, &policies, DbId::default())
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_check_accepted_policies() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![terms(2)]));
+
+        let accepted = [AcceptedPolicy {
+            kind: "Terms".to_string(),
+            version: 2,
+        }];
+
+        let documents = check_accepted_policies(&mock_db, &accepted).await.unwrap();
+        assert_eq!(documents.len(), 1);
+        assert_eq!(documents[0].version, 2);
+    }
+
+    #[tokio::test]
+    async fn test_check_accepted_policies_outdated_version() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![terms(2)]));
+
+        let accepted = [AcceptedPolicy {
+            kind: "Terms".to_string(),
+            version: 1,
+        }];
+
+        let result = check_accepted_policies(&mock_db, &accepted).await;
+        assert!(matches!(result, Err(ClientError::PolicyNotCurrent)));
+    }
+
+    #[tokio::test]
+    async fn test_check_accepted_policies_missing() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![terms(2)]));
+
+        let result = check_accepted_policies(&mock_db, &[]).await;
+        assert!(matches!(result, Err(ClientError::PolicyAcceptanceRequired)));
+    }
+
+    #[tokio::test]
+    async fn test_accept_policy_records_consent() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![terms(2)]));
+
+        mock_db
+            .expect_read_by_field_thing::<PolicyConsentModel>()
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(vec![PolicyConsentModel {
+                    document: terms(1).id,
+                    ..Default::default()
+                }])
+            });
+
+        mock_db
+            .expect_create::<CreatePolicyConsentModel, PolicyConsentModel>()
+            .withf(|table, data| {
+                table == "policy_consents"
+                    && data.document == terms(2).id
+                    && data.ip_address == Some("10.0.0.1".to_string())
+            })
+            .times(1)
+            .returning(|_, data| {
+                Ok(vec![PolicyConsentModel {
+                    document: data.document,
+                    version: data.version,
+                    ip_address: data.ip_address,
+                    ..Default::default()
+                }])
+            });
+
+        let consent = accept_policy(
+            &mock_db,
+            DbId::default(),
+            "Terms",
+            2,
+            Some("10.0.0.1".to_string()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(consent.kind, "Terms");
+        assert_eq!(consent.version, 2);
+        assert_eq!(consent.ip_address, "10.0.0.1");
+    }
+
+    #[tokio::test]
+    async fn test_accept_policy_already_accepted() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![terms(2)]));
+
+        mock_db
+            .expect_read_by_field_thing::<PolicyConsentModel>()
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(vec![PolicyConsentModel {
+                    document: terms(2).id,
+                    version: 2,
+                    ..Default::default()
+                }])
+            });
+
+        mock_db
+            .expect_create::<CreatePolicyConsentModel, PolicyConsentModel>()
+            .never();
+
+        let consent = accept_policy(&mock_db, DbId::default(), "Terms", 2, None)
+            .await
+            .unwrap();
+
+        assert_eq!(consent.version, 2);
+    }
+
+    #[tokio::test]
+    async fn test_accept_policy_outdated_version() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![terms(2)]));
+
+        let result = accept_policy(&mock_db, DbId::default(), "Terms", 1, None).await;
+        assert!(matches!(result, Err(ClientError::PolicyNotCurrent)));
+    }
+
+    #[tokio::test]
+    async fn test_accept_policy_unknown_kind() {
+        let mock_db = MockDatabaseOperations::new();
+
+        let result = accept_policy(&mock_db, DbId::default(), "Cookies", 1, None).await;
+        assert!(matches!(result, Err(ClientError::PolicyNotCurrent)));
+    }
+
+    #[tokio::test]
+    async fn test_list_consents() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_by_field_thing::<PolicyConsentModel>()
+            .withf(|table, field, _, _| table == "policy_consents" && field == "user")
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(vec![PolicyConsentModel {
+                    document: terms(1).id,
+                    ..Default::default()
+                }])
+            });
+
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![terms(2)]));
+
+        let response = list_consents(&mock_db, DbId::default()).await.unwrap();
+
+        assert_eq!(response.consents.len(), 1);
+        assert_eq!(response.consents[0].version, 1);
+        assert_eq!(response.pending.len(), 1);
+        assert_eq!(response.pending[0].version, 2);
+    }
+}
@@ -0,0 +1,239 @@
+// utils/cookie.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{DateTime, Utc};
+use http::{header::SET_COOKIE, HeaderMap, HeaderValue, Method};
+use kiro_database::get_env_or;
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use subtle::ConstantTimeEq;
+
+/// Name of the HttpOnly cookie holding the session token
+pub const SESSION_COOKIE: &str = "kiro_session";
+
+/// Name of the cookie holding the CSRF token (readable by the frontend)
+pub const CSRF_COOKIE: &str = "kiro_csrf";
+
+/// Name of the header the frontend must echo the CSRF token in
+pub const CSRF_HEADER: &str = "x-csrf-token";
+
+const CSRF_TOKEN_LENGTH: usize = 32;
+
+/// Builds the common cookie attributes
+///
+/// # Arguments
+/// * `max_age` - Lifetime of the cookie in seconds
+///
+/// # Returns
+/// * `String` - Attributes appended to every cookie set by the server
+fn cookie_attributes(max_age: i64) -> String {
+    let same_site = get_env_or("COOKIE_SAME_SITE", "Lax");
+    let domain = get_env_or("COOKIE_DOMAIN", "");
+
+    let mut attributes = format!(
+        "Path=/; Max-Age={}; Secure; SameSite={}",
+        max_age, same_site
+    );
+
+    if !domain.is_empty() {
+        attributes.push_str(&format!("; Domain={}", domain));
+    }
+
+    attributes
+}
+
+/// Generates a random CSRF token
+///
+/// # Returns
+/// * `String` - Alphanumeric token used for the double-submit check
+pub fn generate_csrf_token() -> String {
+    thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(CSRF_TOKEN_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+/// Builds the headers setting the session and CSRF cookies
+///
+/// # Arguments
+/// * `token` - Session token returned to the client
+/// * `expires_at` - Expiry date of the session
+///
+/// # Returns
+/// * `HeaderMap` - Headers containing both `Set-Cookie` values
+pub fn session_cookie_headers(token: &str, expires_at: DateTime<Utc>) -> HeaderMap {
+    let max_age = (expires_at - Utc::now()).num_seconds().max(0);
+    let attributes = cookie_attributes(max_age);
+
+    let mut headers = HeaderMap::new();
+
+    if let Ok(value) = HeaderValue::from_str(&format!(
+        "{}={}; HttpOnly; {}",
+        SESSION_COOKIE, token, attributes
+    )) {
+        headers.append(SET_COOKIE, value);
+    }
+
+    if let Ok(value) = HeaderValue::from_str(&format!(
+        "{}={}; {}",
+        CSRF_COOKIE,
+        generate_csrf_token(),
+        attributes
+    )) {
+        headers.append(SET_COOKIE, value);
+    }
+
+    headers
+}
+
+/// Builds the headers expiring the session and CSRF cookies
+///
+/// # Returns
+/// * `HeaderMap` - Headers containing both expired `Set-Cookie` values
+pub fn clear_cookie_headers() -> HeaderMap {
+    let attributes = cookie_attributes(0);
+
+    let mut headers = HeaderMap::new();
+
+    for (name, http_only) in [(SESSION_COOKIE, "HttpOnly; "), (CSRF_COOKIE, "")] {
+        if let Ok(value) = HeaderValue::from_str(&format!("{}=; {}{}", name, http_only, attributes))
+        {
+            headers.append(SET_COOKIE, value);
+        }
+    }
+
+    headers
+}
+
+/// Extracts a cookie value from request headers
+///
+/// # Arguments
+/// * `headers` - Request headers
+/// * `name` - Name of the cookie
+///
+/// # Returns
+/// * `Option<String>` - Cookie value if found and not empty, None otherwise
+pub fn get_cookie(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get_all(http::header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Checks the double-submit CSRF token of a cookie-authenticated request
+///
+/// Safe methods (GET, HEAD, OPTIONS) are always accepted. Other methods
+/// must send the `x-csrf-token` header with the value of the CSRF cookie.
+///
+/// # Arguments
+/// * `method` - Request method
+/// * `headers` - Request headers
+///
+/// # Returns
+/// * `bool` - True if the request passes the CSRF check
+pub fn verify_csrf(method: &Method, headers: &HeaderMap) -> bool {
+    if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+        return true;
+    }
+
+    let cookie = match get_cookie(headers, CSRF_COOKIE) {
+        Some(cookie) => cookie,
+        None => return false,
+    };
+
+    let header = match headers.get(CSRF_HEADER).and_then(|h| h.to_str().ok()) {
+        Some(header) => header,
+        None => return false,
+    };
+
+    cookie.as_bytes().ct_eq(header.as_bytes()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_cookie_headers() {
+        let headers = session_cookie_headers("token", Utc::now() + chrono::Duration::days(2));
+        let cookies: Vec<&str> = headers
+            .get_all(SET_COOKIE)
+            .iter()
+            .map(|value| value.to_str().unwrap())
+            .collect();
+
+        assert_eq!(cookies.len(), 2);
+        assert!(cookies[0].starts_with("kiro_session=token; HttpOnly;"));
+        assert!(cookies[0].contains("Secure"));
+        assert!(cookies[0].contains("SameSite="));
+        assert!(cookies[1].starts_with("kiro_csrf="));
+        assert!(!cookies[1].contains("HttpOnly"));
+    }
+
+    #[test]
+    fn test_clear_cookie_headers() {
+        let headers = clear_cookie_headers();
+        let cookies: Vec<&str> = headers
+            .get_all(SET_COOKIE)
+            .iter()
+            .map(|value| value.to_str().unwrap())
+            .collect();
+
+        assert_eq!(cookies.len(), 2);
+        assert!(cookies.iter().all(|cookie| cookie.contains("Max-Age=0")));
+    }
+
+    #[test]
+    fn test_get_cookie() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            http::header::COOKIE,
+            HeaderValue::from_static("theme=dark; kiro_session=abc; kiro_csrf="),
+        );
+
+        assert_eq!(get_cookie(&headers, SESSION_COOKIE).unwrap(), "abc");
+        assert!(get_cookie(&headers, CSRF_COOKIE).is_none());
+        assert!(get_cookie(&headers, "missing").is_none());
+    }
+
+    #[test]
+    fn test_verify_csrf() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            http::header::COOKIE,
+            HeaderValue::from_static("kiro_session=abc; kiro_csrf=csrf-token"),
+        );
+
+        // Safe methods do not need the header
+        assert!(verify_csrf(&Method::GET, &headers));
+
+        // Missing header
+        assert!(!verify_csrf(&Method::POST, &headers));
+
+        // Mismatching header
+        headers.insert(CSRF_HEADER, HeaderValue::from_static("other-token"));
+        assert!(!verify_csrf(&Method::POST, &headers));
+
+        // Matching header
+        headers.insert(CSRF_HEADER, HeaderValue::from_static("csrf-token"));
+        assert!(verify_csrf(&Method::POST, &headers));
+    }
+}
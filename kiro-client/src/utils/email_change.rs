@@ -0,0 +1,235 @@
+// utils/email_change.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{Duration, Utc};
+use kiro_database::{db_bridge::DatabaseOperations, get_env_or, DbId};
+use kiro_mailer::{ContentType, LinkModel, LinkType, Mailer, MailerError, MailerTrait};
+
+use crate::{
+    error::ClientError, utils::notification::send_notification, NotificationCategory, UserModel,
+};
+
+/// Validity of the confirmation and cancel links
+const LINK_VALIDITY_DAYS: i64 = 2;
+
+/// Starts the change of a user's email address
+///
+/// The new address is stored as pending, a confirmation link is emailed to it and
+/// a cancel link to the current address. The current address stays in use until
+/// the change is confirmed.
+///
+/// # Arguments
+/// * `db` - Database connection implementing DatabaseOperations trait
+/// * `user` - User changing its email address
+/// * `email` - New email address
+///
+/// # Returns
+/// * `Ok(())` - Links sent
+/// * `Err(ClientError::EmailTaken)` - The address is already in use
+/// * `Err(ClientError)` - Database or mailer error
+pub(crate) async fn start_email_change<DB: DatabaseOperations + Send + Sync>(
+    db: &DB, user: &UserModel, email: &str,
+) -> Result<(), ClientError> {
+    UserModel::request_email_change(db, user.id.clone(), email.to_string()).await?;
+
+    // Links of a previous request must not confirm this one
+    delete_email_change_links(db, user.id.clone()).await?;
+
+    let expiry = Utc::now() + Duration::days(LINK_VALIDITY_DAYS);
+
+    let confirm_url =
+        LinkModel::create_from_user(db, user.id.clone(), expiry, LinkType::EmailConfirmation)
+            .await?
+            .construct_link();
+
+    let cancel_url =
+        LinkModel::create_from_user(db, user.id.clone(), expiry, LinkType::EmailChangeCancel)
+            .await?
+            .construct_link();
+
+    let from = get_env_or("SMTP_USER", "test@example.com");
+    let mailer = Mailer::new();
+
+    // Confirmation goes to the new address, proving it belongs to the user
+    let template = load_template("email_change_confirm.html")
+        .await?
+        .replace("${{USER_NAME}}", &user.email)
+        .replace("${{NEW_MAIL}}", email)
+        .replace("${{CONFIRM_URL}}", &confirm_url);
+
+    let message = Mailer::build_mail(
+        &from,
+        email,
+        "Confirm your new email address",
+        ContentType::TEXT_HTML,
+        template,
+    )?;
+
+    send_notification(&mailer, user, NotificationCategory::Security, message).await?;
+
+    // Cancel link goes to the current address
+    let template = load_template("email_change_cancel.html")
+        .await?
+        .replace("${{USER_NAME}}", &user.email)
+        .replace("${{NEW_MAIL}}", email)
+        .replace("${{CANCEL_URL}}", &cancel_url);
+
+    let message = Mailer::build_mail(
+        &from,
+        &user.email,
+        "Email change requested",
+        ContentType::TEXT_HTML,
+        template,
+    )?;
+
+    send_notification(&mailer, user, NotificationCategory::Security, message).await?;
+
+    Ok(())
+}
+
+/// Confirms the pending email change a confirmation link was sent for
+///
+/// The links of the change are deleted whether the swap succeeds or the address
+/// was taken in the meantime.
+///
+/// # Arguments
+/// * `db` - Database connection implementing DatabaseOperations trait
+/// * `link_id` - Identifier of the confirmation link
+///
+/// # Returns
+/// * `Ok(UserModel)` - User with its new email address
+/// * `Err(ClientError::MailerError)` - The link is unknown, expired or of another type
+/// * `Err(ClientError::EmailTaken)` - The address was taken in the meantime
+/// * `Err(ClientError::NoPendingEmail)` - The change was cancelled
+pub(crate) async fn consume_email_confirmation_link<DB: DatabaseOperations + Send + Sync>(
+    db: &DB, link_id: &str,
+) -> Result<UserModel, ClientError> {
+    let link = LinkModel::get_valid_link(db, link_id, LinkType::EmailConfirmation).await?;
+
+    let result = UserModel::confirm_email_change(db, link.user.clone()).await;
+
+    delete_email_change_links(db, link.user).await?;
+
+    result
+}
+
+/// Cancels the pending email change a cancel link was sent for
+///
+/// # Arguments
+/// * `db` - Database connection implementing DatabaseOperations trait
+/// * `link_id` - Identifier of the cancel link
+///
+/// # Returns
+/// * `Ok(DbId)` - ID of the user
+/// * `Err(ClientError::MailerError)` - The link is unknown, expired or of another type
+pub(crate) async fn consume_email_cancel_link<DB: DatabaseOperations + Send + Sync>(
+    db: &DB, link_id: &str,
+) -> Result<DbId, ClientError> {
+    let link = LinkModel::get_valid_link(db, link_id, LinkType::EmailChangeCancel).await?;
+
+    UserModel::cancel_email_change(db, link.user.clone()).await?;
+
+    delete_email_change_links(db, link.user.clone()).await?;
+
+    Ok(link.user)
+}
+
+/// Deletes the confirmation and cancel links of a user
+async fn delete_email_change_links<DB: DatabaseOperations + Send + Sync>(
+    db: &DB, user_id: DbId,
+) -> Result<(), ClientError> {
+    for link_type in [LinkType::EmailConfirmation, LinkType::EmailChangeCancel] {
+        match LinkModel::delete_link_by_user_and_type(db, user_id.clone(), link_type).await {
+            Ok(()) | Err(MailerError::NotFound) => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Ok(())
+}
+
+/// Loads an email template
+async fn load_template(name: &str) -> Result<String, ClientError> {
+    Mailer::load_template(name)
+        .await
+        .map_err(|e| ClientError::Database(kiro_database::DatabaseError::Internal(e.to_string())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kiro_database::{db_bridge::MockDatabaseOperations, DbDateTime};
+    use mockall::predicate::eq;
+
+    fn link(link_type: LinkType) -> LinkModel {
+        LinkModel {
+            id: DbId::from(("links", "link")),
+            user: DbId::from(("users", "user")),
+            link_type,
+            expiry: DbDateTime::from(Utc::now() + Duration::hours(1)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_consume_email_cancel_link() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<LinkModel>()
+            .with(eq(DbId::from(("links", "link"))))
+            .times(1)
+            .returning(|_| Ok(Some(link(LinkType::EmailChangeCancel))));
+
+        mock_db
+            .expect_update_field::<Option<String>>()
+            .withf(|user, field, value| {
+                *user == DbId::from(("users", "user"))
+                    && field == "pending_email"
+                    && value.is_none()
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        // Both the confirmation and the cancel links are deleted
+        mock_db
+            .expect_delete_where()
+            .withf(|table, _| table == "links")
+            .times(2)
+            .returning(|_, _| Ok(0));
+
+        let user_id = consume_email_cancel_link(&mock_db, "link").await.unwrap();
+        assert_eq!(user_id, DbId::from(("users", "user")));
+    }
+
+    #[tokio::test]
+    async fn test_consume_email_confirmation_link_wrong_type() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        // A cancel link cannot confirm the change
+        mock_db
+            .expect_select::<LinkModel>()
+            .times(1)
+            .returning(|_| Ok(Some(link(LinkType::EmailChangeCancel))));
+
+        mock_db.expect_select::<UserModel>().never();
+
+        let result = consume_email_confirmation_link(&mock_db, "link").await;
+        assert!(matches!(
+            result,
+            Err(ClientError::MailerError(MailerError::InvalidType))
+        ));
+    }
+}
@@ -0,0 +1,350 @@
+// utils/export.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::{Cursor, Write};
+
+use kiro_database::{
+    db_bridge::{Database, DatabaseOperations},
+    get_env_or, DbId,
+};
+use kiro_mailer::{ContentType, LinkModel, Mailer, MailerTrait};
+use kiro_storage::{BucketS3, ByteStream};
+use serde::Serialize;
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+use crate::{
+    error::ClientError,
+    models::{AccountEventModel, ExportModel, ExportStatus, FileModel},
+    utils::{
+        avatar::{avatar_name, AVATAR_SIZES},
+        notification::send_notification,
+    },
+    NotificationCategory, SessionModel, UserModel,
+};
+
+/// Fields of the user record that are never exported
+const USER_SECRET_FIELDS: [&str; 1] = ["password_hash"];
+
+/// Fields of the sessions that are never exported
+const SESSION_SECRET_FIELDS: [&str; 1] = ["session_key"];
+
+/// Serializes a record as JSON without its secret fields
+fn to_json<T: Serialize>(record: &T, secrets: &[&str]) -> Result<serde_json::Value, ClientError> {
+    let mut value =
+        serde_json::to_value(record).map_err(|e| ClientError::ArchiveFailed(e.to_string()))?;
+
+    if let Some(object) = value.as_object_mut() {
+        for secret in secrets {
+            object.remove(*secret);
+        }
+    }
+
+    Ok(value)
+}
+
+/// Serializes a value as a pretty printed JSON file
+fn to_file(value: &serde_json::Value) -> Result<Vec<u8>, ClientError> {
+    serde_json::to_vec_pretty(value).map_err(|e| ClientError::ArchiveFailed(e.to_string()))
+}
+
+/// Collects the records held about a user
+///
+/// # Arguments
+/// * `db` - Database connection implementing DatabaseOperations trait
+/// * `user` - User to export
+///
+/// # Returns
+/// The files of the archive as `(name, content)` pairs:
+/// - `user.json` - User record and profile, without the password hash
+/// - `settings.json` - User settings
+/// - `sessions.json` - Sessions with their IP address, without the session keys
+/// - `links.json` - Email links sent to the user
+/// - `account_events.json` - Changes of state of the account
+/// - `files.json` - Files uploaded by the user, the files themselves stay in the bucket
+pub(crate) async fn collect<DB: DatabaseOperations + Send + Sync>(
+    db: &DB, user: &UserModel,
+) -> Result<Vec<(String, Vec<u8>)>, ClientError> {
+    let sessions = db
+        .read_by_field_thing::<SessionModel>("sessions", "user_id", user.id.clone(), None)
+        .await
+        .map_err(ClientError::Database)?
+        .iter()
+        .map(|session| to_json(session, &SESSION_SECRET_FIELDS))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let links = db
+        .read_by_field_thing::<LinkModel>("links", "user", user.id.clone(), None)
+        .await
+        .map_err(ClientError::Database)?;
+
+    let events = AccountEventModel::get_events_by_user(db, user.id.clone()).await?;
+
+    let uploads = db
+        .read_by_field_thing::<FileModel>("files", "user", user.id.clone(), None)
+        .await
+        .map_err(ClientError::Database)?;
+
+    Ok(vec![
+        (
+            "user.json".to_string(),
+            to_file(&to_json(user, &USER_SECRET_FIELDS)?)?,
+        ),
+        (
+            "settings.json".to_string(),
+            to_file(&to_json(&user.settings, &[])?)?,
+        ),
+        (
+            "sessions.json".to_string(),
+            to_file(&serde_json::Value::Array(sessions))?,
+        ),
+        ("links.json".to_string(), to_file(&to_json(&links, &[])?)?),
+        (
+            "account_events.json".to_string(),
+            to_file(&to_json(&events, &[])?)?,
+        ),
+        ("files.json".to_string(), to_file(&to_json(&uploads, &[])?)?),
+    ])
+}
+
+/// Builds a ZIP archive from a list of files
+///
+/// # Arguments
+/// * `files` - Files of the archive as `(name, content)` pairs
+///
+/// # Returns
+/// * `Ok(Vec<u8>)` - Content of the archive
+/// * `Err(ClientError::ArchiveFailed)` - The archive could not be written
+pub(crate) fn build_archive(files: &[(String, Vec<u8>)]) -> Result<Vec<u8>, ClientError> {
+    let mut archive = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for (name, content) in files {
+        archive
+            .start_file(name.as_str(), options)
+            .map_err(|e| ClientError::ArchiveFailed(e.to_string()))?;
+        archive
+            .write_all(content)
+            .map_err(|e| ClientError::ArchiveFailed(e.to_string()))?;
+    }
+
+    archive
+        .finish()
+        .map(Cursor::into_inner)
+        .map_err(|e| ClientError::ArchiveFailed(e.to_string()))
+}
+
+/// Downloads the largest avatar variant of a user from the bucket
+async fn fetch_avatar(bucket: &BucketS3, user_id: DbId) -> Result<Vec<u8>, ClientError> {
+    let avatar = bucket
+        .get_object(
+            user_id.clone(),
+            "images",
+            &avatar_name(AVATAR_SIZES[AVATAR_SIZES.len() - 1]),
+        )
+        .await?
+        .collect()
+        .await
+        .map_err(|e| ClientError::ArchiveFailed(e.to_string()))?;
+
+    Ok(avatar.into_bytes().to_vec())
+}
+
+/// Builds the archive of a user, uploads it and emails the download link
+async fn run_export(db: &Database, export: &ExportModel) -> Result<(), ClientError> {
+    let user = db
+        .select::<UserModel>(export.user.clone())
+        .await?
+        .ok_or(ClientError::DBOptionNone)?;
+
+    let bucket = BucketS3::new().await;
+    let mut files = collect(db, &user).await?;
+
+    if user.avatar.is_some() {
+        files.push((
+            "avatar".to_string(),
+            fetch_avatar(&bucket, user.id.clone()).await?,
+        ));
+    }
+
+    let archive = build_archive(&files)?;
+
+    // Download link expires after 48 hours by default
+    let expiry_hours: u64 = get_env_or("DATA_EXPORT_LINK_EXPIRY_HOURS", "48")
+        .parse()
+        .unwrap_or(48);
+
+    let download_url = bucket
+        .put_signed_object(
+            ByteStream::from(archive),
+            user.id.clone(),
+            "exports",
+            &format!("export-{}.zip", export.id.id),
+            expiry_hours * 3600,
+        )
+        .await?;
+
+    let template = Mailer::load_template("data_export.html")
+        .await
+        .map_err(|e| ClientError::ArchiveFailed(e.to_string()))?
+        .replace("${{USER_NAME}}", &user.email)
+        .replace("${{DOWNLOAD_URL}}", &download_url)
+        .replace("${{EXPIRY_HOURS}}", &expiry_hours.to_string());
+
+    let from = get_env_or("SMTP_USER", "test@example.com");
+
+    let message = Mailer::build_mail(
+        &from,
+        &user.email,
+        "Your data export",
+        ContentType::TEXT_HTML,
+        template,
+    )?;
+
+    // The link gives access to the personal data of the user, like a password reset link
+    send_notification(
+        &Mailer::new(),
+        &user,
+        NotificationCategory::Security,
+        message,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Requests a data export
+///
+/// Records the export then builds it in a background job, the user receives a
+/// time-limited download link by email once the archive is ready.
+///
+/// # Arguments
+/// * `db` - Database connection
+/// * `user_id` - User requesting the export
+///
+/// # Returns
+/// * `Ok(ExportModel)` - Pending export
+/// * `Err(ClientError::ExportThrottled)` - An export was requested recently
+/// * `Err(ClientError)` - Database error
+pub async fn request_export(db: &Database, user_id: DbId) -> Result<ExportModel, ClientError> {
+    let export = ExportModel::request(db, user_id).await?;
+
+    let db = db.clone();
+    let job = export.clone();
+
+    tokio::spawn(async move {
+        let status = match run_export(&db, &job).await {
+            Ok(()) => ExportStatus::Completed,
+            Err(_e) => {
+                #[cfg(feature = "tracing")]
+                tracing::error!("📦 Data export {} failed: {}", job.id, _e);
+
+                ExportStatus::Failed
+            }
+        };
+
+        if let Err(_e) = job.set_status(&db, status).await {
+            #[cfg(feature = "tracing")]
+            tracing::error!("📦 Failed to update data export {}: {}", job.id, _e);
+        }
+    });
+
+    Ok(export)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kiro_database::db_bridge::MockDatabaseOperations;
+    use mockall::predicate::eq;
+    use std::io::Read;
+
+    #[tokio::test]
+    async fn test_collect() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let user = UserModel::default();
+
+        mock_db
+            .expect_read_by_field_thing::<SessionModel>()
+            .with(eq("sessions"), eq("user_id"), eq(user.id.clone()), eq(None))
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(vec![SessionModel {
+                    ip_address: Some("127.0.0.1".to_string()),
+                    ..Default::default()
+                }])
+            });
+
+        mock_db
+            .expect_read_by_field_thing::<LinkModel>()
+            .with(eq("links"), eq("user"), eq(user.id.clone()), eq(None))
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        mock_db
+            .expect_read_by_field_thing::<AccountEventModel>()
+            .withf(|table, field, _, _| table == "account_events" && field == "user")
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![AccountEventModel::default()]));
+
+        mock_db
+            .expect_read_by_field_thing::<FileModel>()
+            .with(eq("files"), eq("user"), eq(user.id.clone()), eq(None))
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![FileModel::default()]));
+
+        let files = collect(&mock_db, &user).await.unwrap();
+        let names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "user.json",
+                "settings.json",
+                "sessions.json",
+                "links.json",
+                "account_events.json",
+                "files.json"
+            ]
+        );
+
+        let record: serde_json::Value = serde_json::from_slice(&files[0].1).unwrap();
+        assert_eq!(record["email"], user.email);
+        assert!(record.get("password_hash").is_none());
+
+        let sessions: serde_json::Value = serde_json::from_slice(&files[2].1).unwrap();
+        assert_eq!(sessions[0]["ip_address"], "127.0.0.1");
+        assert!(sessions[0].get("session_key").is_none());
+    }
+
+    #[test]
+    fn test_build_archive() {
+        let files = vec![
+            ("user.json".to_string(), b"{}".to_vec()),
+            ("avatar".to_string(), vec![0xFF, 0xD8, 0xFF]),
+        ];
+
+        let archive = build_archive(&files).unwrap();
+        let mut reader = zip::ZipArchive::new(Cursor::new(archive)).unwrap();
+        assert_eq!(reader.len(), 2);
+
+        let mut avatar = Vec::new();
+        reader
+            .by_name("avatar")
+            .unwrap()
+            .read_to_end(&mut avatar)
+            .unwrap();
+        assert_eq!(avatar, vec![0xFF, 0xD8, 0xFF]);
+    }
+}
@@ -0,0 +1,257 @@
+// utils/i18n.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use kiro_database::{db_bridge::DatabaseOperations, error_key, with_error_key};
+use once_cell::sync::Lazy;
+use tonic::{Code, Status};
+
+use crate::{Language, SessionModel, UserModel};
+
+/// Message catalogues, by language code
+///
+/// Each catalogue maps the stable key of an error to its message. Errors whose
+/// message embeds a value have a key but no entry, clients localize them from
+/// the key and the English message.
+static CATALOGUES: Lazy<HashMap<&'static str, HashMap<String, String>>> = Lazy::new(|| {
+    [
+        ("en", include_str!("../../locales/en.json")),
+        ("es", include_str!("../../locales/es.json")),
+        ("fr", include_str!("../../locales/fr.json")),
+        ("de", include_str!("../../locales/de.json")),
+        ("it", include_str!("../../locales/it.json")),
+        ("ja", include_str!("../../locales/ja.json")),
+        ("ko", include_str!("../../locales/ko.json")),
+        ("zh", include_str!("../../locales/zh.json")),
+        ("ru", include_str!("../../locales/ru.json")),
+        ("ar", include_str!("../../locales/ar.json")),
+    ]
+    .into_iter()
+    .map(|(code, catalogue)| {
+        (
+            code,
+            serde_json::from_str(catalogue).expect("Invalid message catalogue"),
+        )
+    })
+    .collect()
+});
+
+/// Translates the message of an error
+///
+/// # Arguments
+/// * `key` - Stable key of the error
+/// * `language` - Language of the message
+///
+/// # Returns
+/// * `Some(&str)` - Translated message
+/// * `None` - Unknown key, or message embedding a value
+pub fn translate(key: &str, language: Language) -> Option<&'static str> {
+    CATALOGUES
+        .get(language.code())
+        .and_then(|catalogue| catalogue.get(key))
+        .map(String::as_str)
+}
+
+/// Returns the preferred supported language of an `accept-language` header
+///
+/// # Arguments
+/// * `accept_language` - Value of the header, e.g. `fr-CH, fr;q=0.9, en;q=0.8`
+pub fn negotiate_language(accept_language: &str) -> Option<Language> {
+    let mut ranges: Vec<(Language, f32)> = accept_language
+        .split(',')
+        .filter_map(|range| {
+            let mut parts = range.split(';');
+            let tag = parts.next()?.trim();
+            let quality = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())?;
+
+            let language = Language::from_code(tag.split('-').next()?)?;
+
+            (quality > 0.0).then_some((language, quality))
+        })
+        .collect();
+
+    // Stable, so ranges of the same quality keep the order of the header
+    ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    ranges.first().map(|(language, _)| *language)
+}
+
+/// Resolves the language of the messages sent to a client
+///
+/// The language of the session user's settings comes first, then the
+/// `accept-language` header, then English.
+///
+/// # Arguments
+/// * `db` - Database connection implementing DatabaseOperations trait
+/// * `session` - Session of the request, if authenticated
+/// * `accept_language` - Value of the `accept-language` header, if any
+pub async fn resolve_language<DB: DatabaseOperations + Send + Sync>(
+    db: &DB, session: Option<&SessionModel>, accept_language: Option<&str>,
+) -> Language {
+    if let Some(session) = session {
+        if let Ok(Some(user)) = db.select::<UserModel>(session.user_id.clone()).await {
+            if let Some(language) = user.settings.language {
+                return language;
+            }
+        }
+    }
+
+    accept_language
+        .and_then(negotiate_language)
+        .unwrap_or(Language::English)
+}
+
+/// Localizes an error message
+///
+/// # Arguments
+/// * `key` - Stable key of the error
+/// * `language` - Language of the message
+///
+/// # Returns
+/// * `Some(&str)` - Translated message, the English one if not translated
+/// * `None` - Unknown key, or message embedding a value
+pub fn localize_message(key: &str, language: Language) -> Option<&'static str> {
+    translate(key, language).or_else(|| translate(key, Language::English))
+}
+
+/// Builds a status from the stable key of an error
+///
+/// The message is the English one of the catalogue, the key is attached to the
+/// details so the status can be localized.
+///
+/// # Arguments
+/// * `code` - Code of the status
+/// * `key` - Stable key of the error
+///
+/// # Example
+/// ```rust,no_run
+/// use kiro_client::keyed_status;
+/// use tonic::Code;
+///
+/// let status = keyed_status(Code::NotFound, "user.not_found");
+///
+/// assert_eq!(status.message(), "User not found");
+/// ```
+pub fn keyed_status(code: Code, key: &str) -> Status {
+    let message = translate(key, Language::English).unwrap_or(key);
+
+    with_error_key(Status::new(code, message), key)
+}
+
+/// Localizes a status
+///
+/// Translates the message of a status carrying a stable key in its details.
+/// Statuses without a key are returned unchanged, as are those whose message
+/// embeds a value.
+///
+/// # Arguments
+/// * `status` - Status to localize
+/// * `language` - Language of the message
+///
+/// # Example
+/// ```rust,no_run
+/// use kiro_client::{keyed_status, localize_status, Language};
+/// use tonic::Code;
+///
+/// let status = localize_status(keyed_status(Code::NotFound, "user.not_found"), Language::French);
+///
+/// assert_eq!(status.message(), "Utilisateur introuvable");
+/// ```
+pub fn localize_status(status: Status, language: Language) -> Status {
+    let Some(key) = error_key(&status) else {
+        return status;
+    };
+
+    let message = translate(&key, language).unwrap_or(status.message());
+
+    with_error_key(Status::new(status.code(), message), &key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::ClientError;
+
+    #[test]
+    fn test_catalogues_have_the_english_keys() {
+        for (code, catalogue) in CATALOGUES.iter() {
+            for key in CATALOGUES["en"].keys() {
+                assert!(catalogue.contains_key(key), "{} is missing {}", code, key);
+            }
+        }
+    }
+
+    #[test]
+    fn test_negotiate_language() {
+        assert_eq!(
+            negotiate_language("de-CH, fr;q=0.9, en;q=0.8"),
+            Some(Language::German)
+        );
+        assert_eq!(
+            negotiate_language("pt-BR, ja;q=0.5, ko;q=0.7"),
+            Some(Language::Korean)
+        );
+        assert_eq!(negotiate_language("fr;q=0, pt"), None);
+        assert_eq!(negotiate_language(""), None);
+    }
+
+    #[test]
+    fn test_localize_status_from_error() {
+        let status = localize_status(Status::from(ClientError::EmailTaken), Language::Spanish);
+
+        assert_eq!(status.code(), tonic::Code::AlreadyExists);
+        assert_eq!(status.message(), "La dirección de correo ya está en uso");
+        assert_eq!(error_key(&status).as_deref(), Some("email.taken"));
+    }
+
+    #[test]
+    fn test_keyed_status() {
+        let status = keyed_status(Code::Unauthenticated, "session.missing");
+
+        assert_eq!(status.code(), Code::Unauthenticated);
+        assert_eq!(status.message(), "No valid session found");
+        assert_eq!(error_key(&status).as_deref(), Some("session.missing"));
+
+        let status = localize_status(status, Language::German);
+        assert_eq!(status.message(), "Keine gültige Sitzung gefunden");
+        assert_eq!(error_key(&status).as_deref(), Some("session.missing"));
+    }
+
+    #[test]
+    fn test_localize_status_without_translation() {
+        let status = localize_status(
+            Status::from(ClientError::InvalidSettings("Unknown path".to_string())),
+            Language::Japanese,
+        );
+
+        // The message embeds a value, only the key is attached
+        assert_eq!(status.message(), "Unknown path");
+        assert_eq!(error_key(&status).as_deref(), Some("settings.invalid"));
+    }
+
+    #[test]
+    fn test_localize_status_without_key() {
+        // Statuses are localized by key only, never by their message
+        let status = localize_status(Status::not_found("User not found"), Language::French);
+
+        assert_eq!(status.message(), "User not found");
+        assert_eq!(error_key(&status), None);
+    }
+}
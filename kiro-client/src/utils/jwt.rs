@@ -0,0 +1,343 @@
+// utils/jwt.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::Utc;
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use kiro_database::get_env_or;
+use once_cell::sync::Lazy;
+use rand_core::OsRng;
+use rsa::{
+    pkcs1::DecodeRsaPrivateKey,
+    pkcs8::{DecodePrivateKey, EncodePrivateKey, LineEnding},
+    traits::PublicKeyParts,
+    RsaPrivateKey,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{error::OAuthError, UserModel};
+
+/// Lifetime of access and ID tokens in seconds
+pub const TOKEN_TTL: i64 = 3600;
+
+/// # Signing Keys
+///
+/// RSA key pair used to sign the tokens, published through the JWKS endpoint.
+struct SigningKeys {
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    kid: String,
+    n: String,
+    e: String,
+}
+
+/// Loads the signing key from `OAUTH_SIGNING_KEY_PATH` (PKCS#8 or PKCS#1 PEM),
+/// or generates an ephemeral one when no key is configured
+///
+/// Panics when a key is configured but cannot be loaded, tokens must not be
+/// signed with a key the relying parties do not know.
+static SIGNING_KEYS: Lazy<SigningKeys> = Lazy::new(|| {
+    let path = get_env_or("OAUTH_SIGNING_KEY_PATH", "");
+
+    let private_key = if path.is_empty() {
+        #[cfg(feature = "tracing")]
+        tracing::warn!("🔑 No OAuth signing key configured, generating an ephemeral one");
+
+        RsaPrivateKey::new(&mut OsRng, 2048).expect("Failed to generate OAuth signing key")
+    } else {
+        read_private_key(&path).unwrap_or_else(|error| {
+            panic!("Failed to load OAuth signing key from {}: {}", path, error)
+        })
+    };
+
+    let pem = private_key
+        .to_pkcs8_pem(LineEnding::LF)
+        .expect("Failed to encode OAuth signing key");
+
+    let n = URL_SAFE_NO_PAD.encode(private_key.n().to_bytes_be());
+    let e = URL_SAFE_NO_PAD.encode(private_key.e().to_bytes_be());
+    let kid = URL_SAFE_NO_PAD.encode(&Sha256::digest(n.as_bytes())[..12]);
+
+    SigningKeys {
+        encoding: EncodingKey::from_rsa_pem(pem.as_bytes())
+            .expect("Failed to load OAuth signing key"),
+        decoding: DecodingKey::from_rsa_components(&n, &e)
+            .expect("Failed to load OAuth verification key"),
+        kid,
+        n,
+        e,
+    }
+});
+
+/// Reads a RSA private key from a PKCS#8 or PKCS#1 PEM file
+///
+/// # Arguments
+/// * `path` - Path of the PEM file
+///
+/// # Returns
+/// * `Ok(RsaPrivateKey)` - Loaded key
+/// * `Err(String)` - Unreadable file or invalid key
+fn read_private_key(path: &str) -> Result<RsaPrivateKey, String> {
+    let pem = std::fs::read_to_string(path).map_err(|error| error.to_string())?;
+
+    RsaPrivateKey::from_pkcs8_pem(&pem)
+        .or_else(|_| RsaPrivateKey::from_pkcs1_pem(&pem))
+        .map_err(|error| error.to_string())
+}
+
+/// Loads the signing keys
+///
+/// Called at startup so that a misconfigured `OAUTH_SIGNING_KEY_PATH` aborts
+/// the server instead of the first token request.
+///
+/// # Panics
+/// When a key is configured but cannot be loaded
+pub fn init_signing_keys() {
+    Lazy::force(&SIGNING_KEYS);
+}
+
+/// Returns the issuer identifier of the provider
+pub fn issuer() -> String {
+    get_env_or("OAUTH_ISSUER", "https://localhost:3000")
+}
+
+/// # Access Token Claims
+///
+/// Claims of the JWT access tokens. `sub` is the user id for the authorization
+/// code grant and the client id for the client credentials grant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessTokenClaims {
+    pub iss: String,
+    pub sub: String,
+    pub aud: String,
+    pub client_id: String,
+    pub scope: String,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+impl AccessTokenClaims {
+    /// Creates the claims of a new access token
+    pub fn new(sub: String, client_id: String, scopes: &[String]) -> Self {
+        let now = Utc::now().timestamp();
+
+        Self {
+            iss: issuer(),
+            sub,
+            aud: client_id.clone(),
+            client_id,
+            scope: scopes.join(" "),
+            iat: now,
+            exp: now + TOKEN_TTL,
+        }
+    }
+
+    /// Whether the token was granted the scope
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scope.split_whitespace().any(|s| s == scope)
+    }
+}
+
+/// # ID Token Claims
+///
+/// Claims of the OpenID Connect ID tokens, built from the `UserModel`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdTokenClaims {
+    pub iss: String,
+    pub sub: String,
+    pub aud: String,
+    pub iat: i64,
+    pub exp: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub picture: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+}
+
+impl IdTokenClaims {
+    /// Builds the ID token claims of a user for the granted scopes
+    ///
+    /// # Arguments
+    /// * `user` - Authenticated user
+    /// * `client_id` - Audience of the token
+    /// * `scopes` - Granted scopes, `email` and `profile` add the matching claims
+    /// * `nonce` - Nonce sent in the authorization request
+    pub fn from_user(
+        user: &UserModel, client_id: String, scopes: &[String], nonce: Option<String>,
+    ) -> Self {
+        let now = Utc::now().timestamp();
+        let email = scopes.iter().any(|s| s == "email");
+        let profile = scopes.iter().any(|s| s == "profile");
+
+        Self {
+            iss: issuer(),
+            sub: user.id.to_string(),
+            aud: client_id,
+            iat: now,
+            exp: now + TOKEN_TTL,
+            nonce,
+            email: email.then(|| user.email.clone()),
+            picture: if profile { user.avatar.clone() } else { None },
+            locale: if profile {
+                user.settings
+                    .language
+                    .map(|language| language.code().to_string())
+            } else {
+                None
+            },
+        }
+    }
+}
+
+/// Signs claims into a RS256 JWT
+///
+/// # Arguments
+/// * `claims` - Claims to sign
+///
+/// # Returns
+/// * `Ok(String)` - Signed token
+/// * `Err(OAuthError::SigningFailed)` - Signing error
+pub fn sign<T: Serialize>(claims: &T) -> Result<String, OAuthError> {
+    let keys = &*SIGNING_KEYS;
+
+    let mut header = Header::new(Algorithm::RS256);
+    header.kid = Some(keys.kid.clone());
+
+    jsonwebtoken::encode(&header, claims, &keys.encoding).map_err(|_| OAuthError::SigningFailed)
+}
+
+/// Verifies an access token issued by this provider
+///
+/// # Arguments
+/// * `token` - Bearer token sent by the relying party
+///
+/// # Returns
+/// * `Ok(AccessTokenClaims)` - Claims of a valid token
+/// * `Err(OAuthError::InvalidToken)` - Invalid, expired or foreign token
+pub fn verify_access_token(token: &str) -> Result<AccessTokenClaims, OAuthError> {
+    let mut validation = Validation::new(Algorithm::RS256);
+    validation.set_issuer(&[issuer()]);
+    validation.validate_aud = false;
+
+    jsonwebtoken::decode::<AccessTokenClaims>(token, &SIGNING_KEYS.decoding, &validation)
+        .map(|data| data.claims)
+        .map_err(|_| OAuthError::InvalidToken)
+}
+
+/// Returns the JSON Web Key Set publishing the verification key
+pub fn jwks() -> serde_json::Value {
+    let keys = &*SIGNING_KEYS;
+
+    serde_json::json!({
+        "keys": [{
+            "kty": "RSA",
+            "use": "sig",
+            "alg": "RS256",
+            "kid": keys.kid,
+            "n": keys.n,
+            "e": keys.e,
+        }]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify_access_token() {
+        let claims = AccessTokenClaims::new(
+            "users:test".to_string(),
+            "client".to_string(),
+            &["openid".to_string(), "email".to_string()],
+        );
+
+        let token = sign(&claims).unwrap();
+        let verified = verify_access_token(&token).unwrap();
+
+        assert_eq!(verified.sub, "users:test");
+        assert_eq!(verified.client_id, "client");
+        assert!(verified.has_scope("email"));
+        assert!(!verified.has_scope("profile"));
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_token() {
+        let claims = AccessTokenClaims::new("users:test".to_string(), "client".to_string(), &[]);
+        let token = sign(&claims).unwrap();
+
+        assert!(verify_access_token(&format!("{}x", token)).is_err());
+        assert!(verify_access_token("not-a-token").is_err());
+    }
+
+    #[test]
+    fn test_id_token_claims_follow_scopes() {
+        let user = UserModel::default();
+
+        let claims = IdTokenClaims::from_user(&user, "client".to_string(), &[], None);
+        assert!(claims.email.is_none());
+        assert!(claims.picture.is_none());
+
+        let claims = IdTokenClaims::from_user(
+            &user,
+            "client".to_string(),
+            &["email".to_string(), "profile".to_string()],
+            Some("nonce".to_string()),
+        );
+        assert_eq!(claims.email, Some(user.email.clone()));
+        assert_eq!(claims.picture, user.avatar.clone());
+        assert_eq!(claims.locale, Some("en".to_string()));
+        assert_eq!(claims.nonce, Some("nonce".to_string()));
+    }
+
+    #[test]
+    fn test_read_private_key_rejects_missing_or_invalid_keys() {
+        assert!(read_private_key("/nonexistent/signing_key.pem").is_err());
+
+        let path = std::env::temp_dir().join("kiro_invalid_signing_key.pem");
+        std::fs::write(&path, "not a key").unwrap();
+        assert!(read_private_key(path.to_str().unwrap()).is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_read_private_key() {
+        let private_key = RsaPrivateKey::new(&mut OsRng, 2048).unwrap();
+        let pem = private_key.to_pkcs8_pem(LineEnding::LF).unwrap();
+
+        let path = std::env::temp_dir().join("kiro_signing_key.pem");
+        std::fs::write(&path, pem.as_bytes()).unwrap();
+        let loaded = read_private_key(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(loaded.n(), private_key.n());
+    }
+
+    #[test]
+    fn test_jwks() {
+        let jwks = jwks();
+        let key = &jwks["keys"][0];
+
+        assert_eq!(key["kty"], "RSA");
+        assert_eq!(key["alg"], "RS256");
+        assert!(!key["n"].as_str().unwrap().is_empty());
+    }
+}
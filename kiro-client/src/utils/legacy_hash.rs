@@ -0,0 +1,272 @@
+// utils/legacy_hash.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{ops::RangeInclusive, str::FromStr};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bcrypt::HashParts;
+use sha2::{Sha256, Sha512};
+use subtle::ConstantTimeEq;
+
+use crate::error::ClientError;
+
+/// Iteration counts accepted in PBKDF2 hashes, the key is derived on every login
+const PBKDF2_ITERATIONS: RangeInclusive<u32> = 1..=10_000_000;
+
+/// Key lengths accepted in PBKDF2 hashes, in bytes
+const PBKDF2_KEY_LENGTHS: RangeInclusive<usize> = 16..=64;
+
+/// # Legacy Hash Error
+///
+/// The LegacyHashError enum represents the reasons a legacy hash is rejected.
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum LegacyHashError {
+    #[error("Malformed {0} hash")]
+    Malformed(&'static str),
+
+    #[error(
+        "PBKDF2 iteration count must be between {} and {}",
+        PBKDF2_ITERATIONS.start(),
+        PBKDF2_ITERATIONS.end()
+    )]
+    Iterations,
+
+    #[error(
+        "PBKDF2 key must be {} to {} bytes long",
+        PBKDF2_KEY_LENGTHS.start(),
+        PBKDF2_KEY_LENGTHS.end()
+    )]
+    KeyLength,
+}
+
+/// # Legacy Hash
+///
+/// Password hashes imported from other systems. They are checked as is on login,
+/// then replaced by an Argon2 hash of the password.
+///
+/// - bcrypt: `$2a$`, `$2b$` and `$2y$` hashes
+/// - PBKDF2: `pbkdf2_sha256$<iterations>$<salt>$<base64 key>` and its SHA-512
+///   counterpart, as stored by Django and compatible systems
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LegacyHash {
+    Bcrypt,
+    Pbkdf2Sha256,
+    Pbkdf2Sha512,
+}
+
+impl LegacyHash {
+    /// Detects the scheme of a hash, `None` for Argon2 and unknown hashes
+    pub fn detect(hash: &str) -> Option<Self> {
+        if ["$2a$", "$2b$", "$2y$"]
+            .iter()
+            .any(|prefix| hash.starts_with(prefix))
+        {
+            Some(Self::Bcrypt)
+        } else if hash.starts_with("pbkdf2_sha256$") {
+            Some(Self::Pbkdf2Sha256)
+        } else if hash.starts_with("pbkdf2_sha512$") {
+            Some(Self::Pbkdf2Sha512)
+        } else {
+            None
+        }
+    }
+
+    /// Checks that a hash of this scheme is well formed and within bounds
+    ///
+    /// # Returns
+    /// * `Ok(())` - The hash can be verified on login
+    /// * `Err(LegacyHashError)` - The hash is malformed or out of bounds
+    pub fn validate(self, hash: &str) -> Result<(), LegacyHashError> {
+        match self {
+            Self::Bcrypt => HashParts::from_str(hash)
+                .map(|_| ())
+                .map_err(|_| LegacyHashError::Malformed("bcrypt")),
+            Self::Pbkdf2Sha256 | Self::Pbkdf2Sha512 => Pbkdf2Hash::parse(hash).map(|_| ()),
+        }
+    }
+
+    /// Verifies a password against a hash of this scheme
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Whether the password matches
+    /// * `Err(ClientError::PasswordIncorrect)` - The hash is malformed or out of bounds
+    pub fn verify(self, password: &str, hash: &str) -> Result<bool, ClientError> {
+        match self {
+            Self::Bcrypt => {
+                bcrypt::verify(password, hash).map_err(|_| ClientError::PasswordIncorrect)
+            }
+            Self::Pbkdf2Sha256 | Self::Pbkdf2Sha512 => Pbkdf2Hash::parse(hash)
+                .map(|parsed| parsed.verify(self, password))
+                .map_err(|_| ClientError::PasswordIncorrect),
+        }
+    }
+}
+
+/// Parts of a `<algorithm>$<iterations>$<salt>$<key>` hash
+struct Pbkdf2Hash<'a> {
+    iterations: u32,
+    salt: &'a str,
+    key: Vec<u8>,
+}
+
+impl<'a> Pbkdf2Hash<'a> {
+    /// Parses a hash, rejecting iteration counts and key lengths out of bounds
+    fn parse(hash: &'a str) -> Result<Self, LegacyHashError> {
+        let mut parts = hash.splitn(4, '$').skip(1);
+
+        let (iterations, salt, key) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(iterations), Some(salt), Some(key)) => (iterations, salt, key),
+            _ => return Err(LegacyHashError::Malformed("PBKDF2")),
+        };
+
+        let iterations = iterations
+            .parse::<u32>()
+            .map_err(|_| LegacyHashError::Malformed("PBKDF2"))?;
+        if !PBKDF2_ITERATIONS.contains(&iterations) {
+            return Err(LegacyHashError::Iterations);
+        }
+
+        let key = STANDARD
+            .decode(key)
+            .map_err(|_| LegacyHashError::Malformed("PBKDF2"))?;
+        if !PBKDF2_KEY_LENGTHS.contains(&key.len()) {
+            return Err(LegacyHashError::KeyLength);
+        }
+
+        Ok(Self {
+            iterations,
+            salt,
+            key,
+        })
+    }
+
+    /// Verifies a password against the key
+    fn verify(&self, scheme: LegacyHash, password: &str) -> bool {
+        let mut derived = vec![0u8; self.key.len()];
+        match scheme {
+            LegacyHash::Pbkdf2Sha512 => pbkdf2::pbkdf2_hmac::<Sha512>(
+                password.as_bytes(),
+                self.salt.as_bytes(),
+                self.iterations,
+                &mut derived,
+            ),
+            _ => pbkdf2::pbkdf2_hmac::<Sha256>(
+                password.as_bytes(),
+                self.salt.as_bytes(),
+                self.iterations,
+                &mut derived,
+            ),
+        }
+
+        derived.ct_eq(&self.key).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PBKDF2_SHA256: &str =
+        "pbkdf2_sha256$1000$seasalt$gsa5NFZxPLjumoeVIZmIYcaAzf9vYZB6QwpmWPzeaxc=";
+    const PBKDF2_SHA512: &str = "pbkdf2_sha512$1000$seasalt$kuApriZplsxq9TJGMn84OX1Wg0jWRvW9dOnDSudeeuLRAnqHiqcwUzbtlUR0bmi6iYaABeDdDGuEIlX4OpDdOw==";
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(
+            LegacyHash::detect("$2b$04$abcdefghijklmnopqrstuu"),
+            Some(LegacyHash::Bcrypt)
+        );
+        assert_eq!(
+            LegacyHash::detect(PBKDF2_SHA256),
+            Some(LegacyHash::Pbkdf2Sha256)
+        );
+        assert_eq!(
+            LegacyHash::detect(PBKDF2_SHA512),
+            Some(LegacyHash::Pbkdf2Sha512)
+        );
+        assert_eq!(
+            LegacyHash::detect("$argon2id$v=19$m=16,t=2,p=1$c2FsdA$aGFzaA"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_verify_bcrypt() {
+        let hash = bcrypt::hash("Password123!", 4).unwrap();
+
+        assert!(LegacyHash::Bcrypt.verify("Password123!", &hash).unwrap());
+        assert!(!LegacyHash::Bcrypt
+            .verify("WrongPassword123!", &hash)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_verify_pbkdf2() {
+        assert!(LegacyHash::Pbkdf2Sha256
+            .verify("Password123!", PBKDF2_SHA256)
+            .unwrap());
+        assert!(!LegacyHash::Pbkdf2Sha256
+            .verify("WrongPassword123!", PBKDF2_SHA256)
+            .unwrap());
+        assert!(LegacyHash::Pbkdf2Sha512
+            .verify("Password123!", PBKDF2_SHA512)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_validate() {
+        let hash = bcrypt::hash("Password123!", 4).unwrap();
+
+        assert!(LegacyHash::Bcrypt.validate(&hash).is_ok());
+        assert!(LegacyHash::Pbkdf2Sha256.validate(PBKDF2_SHA256).is_ok());
+        assert!(LegacyHash::Pbkdf2Sha512.validate(PBKDF2_SHA512).is_ok());
+        assert_eq!(
+            LegacyHash::Bcrypt.validate("$2b$nope"),
+            Err(LegacyHashError::Malformed("bcrypt"))
+        );
+        assert_eq!(
+            LegacyHash::Pbkdf2Sha256.validate("pbkdf2_sha256$1000$seasalt"),
+            Err(LegacyHashError::Malformed("PBKDF2"))
+        );
+        assert_eq!(
+            LegacyHash::Pbkdf2Sha256.validate(
+                "pbkdf2_sha256$4000000000$seasalt$gsa5NFZxPLjumoeVIZmIYcaAzf9vYZB6QwpmWPzeaxc="
+            ),
+            Err(LegacyHashError::Iterations)
+        );
+        assert_eq!(
+            LegacyHash::Pbkdf2Sha256
+                .validate("pbkdf2_sha256$0$seasalt$gsa5NFZxPLjumoeVIZmIYcaAzf9vYZB6QwpmWPzeaxc="),
+            Err(LegacyHashError::Iterations)
+        );
+        assert_eq!(
+            LegacyHash::Pbkdf2Sha256.validate("pbkdf2_sha256$1000$seasalt$a2V5"),
+            Err(LegacyHashError::KeyLength)
+        );
+    }
+
+    #[test]
+    fn test_verify_malformed() {
+        assert!(matches!(
+            LegacyHash::Pbkdf2Sha256.verify("Password123!", "pbkdf2_sha256$many$salt$key"),
+            Err(ClientError::PasswordIncorrect)
+        ));
+        assert!(matches!(
+            LegacyHash::Bcrypt.verify("Password123!", "$2b$nope"),
+            Err(ClientError::PasswordIncorrect)
+        ));
+    }
+}
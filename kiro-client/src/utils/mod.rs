@@ -14,12 +14,117 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+/// # Avatar
+///
+/// The `avatar` module validates uploaded avatars and generates their resized variants.
+#[cfg(feature = "storage")]
+pub mod avatar;
+
+/// # Consent
+///
+/// The `consent` module records the acceptance of the terms of service and privacy policy.
+pub mod consent;
+
+/// # Cookie
+///
+/// The `cookie` module provides utilities for browser session cookies and CSRF protection.
+pub mod cookie;
+
+/// # Email change
+///
+/// The `email_change` module verifies new email addresses before they replace the current ones.
+#[cfg(feature = "mailer")]
+pub mod email_change;
+
+/// # Export
+///
+/// The `export` module builds and delivers the data exports of users.
+#[cfg(feature = "export")]
+pub mod export;
+
+/// # I18n
+///
+/// The `i18n` module localizes error messages from a catalogue of stable error keys.
+pub mod i18n;
+
 /// # IP
 ///
 /// The `ip` module provides utilities for IP addresses.
 pub mod ip;
 
+/// # JWT
+///
+/// The `jwt` module provides signing and verification of the OAuth2 / OpenID Connect tokens.
+#[cfg(feature = "oauth")]
+pub mod jwt;
+
+/// # Legacy hash
+///
+/// The `legacy_hash` module verifies the bcrypt and PBKDF2 password hashes of imported users.
+#[cfg(feature = "legacy_hashes")]
+pub mod legacy_hash;
+
+/// # Notification
+///
+/// The `notification` module sends the emails of users according to their notification preferences.
+#[cfg(feature = "mailer")]
+pub mod notification;
+
 /// # Password
 ///
 /// The password module is a module that provides utilities for passwords.
 pub mod password;
+
+/// # Privacy
+///
+/// The privacy module enforces the privacy settings of users on the data kept or emitted about them.
+pub mod privacy;
+
+/// # Profile
+///
+/// The profile module provides validation of the public profile fields.
+pub mod profile;
+
+/// # Purge
+///
+/// The `purge` module deletes the accounts whose deletion grace period is over.
+pub mod purge;
+
+/// # Reactivation
+///
+/// The `reactivation` module provides the self-service reactivation of disabled accounts.
+#[cfg(feature = "mailer")]
+pub mod reactivation;
+
+/// # Registration
+///
+/// The `registration` module checks registrations against the registration policy and redeems invitations.
+pub mod registration;
+
+/// # Retention
+///
+/// The `retention` module deletes or anonymizes the data kept past its retention period.
+pub mod retention;
+
+/// # Secondary email
+///
+/// The `secondary_email` module adds and verifies the secondary email addresses of users.
+#[cfg(feature = "mailer")]
+pub mod secondary_email;
+
+/// # Sync
+///
+/// The `sync` module returns the changes of users since a cursor, and compacts the change log.
+pub mod sync;
+
+/// # Upload
+///
+/// The `upload` module receives files and avatars sent in chunks, with size and checksum verification.
+#[cfg(feature = "storage")]
+pub mod upload;
+
+/// # User import
+///
+/// The `user_import` module imports and exports users in bulk, as CSV or JSON Lines.
+#[cfg(feature = "import")]
+pub mod user_import;
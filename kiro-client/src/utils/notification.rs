@@ -0,0 +1,139 @@
+// utils/notification.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::Utc;
+use kiro_mailer::{MailerTrait, Message};
+
+#[cfg(feature = "tracing")]
+use crate::PrivacyPolicy;
+use crate::{error::ClientError, NotificationCategory, NotificationChannel, UserModel};
+
+/// # Delivery
+///
+/// The outcome of a notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delivery {
+    /// The notification was sent
+    Sent,
+    /// The preferences of the user held the notification back
+    Suppressed,
+}
+
+/// # Send notification
+///
+/// The `send_notification` method emails a notification to a user, if their
+/// notification preferences allow it for the category at the current time in
+/// their timezone. Every email sent to a user goes through this check,
+/// mandatory categories are always sent.
+pub async fn send_notification<M: MailerTrait>(
+    mailer: &M, user: &UserModel, category: NotificationCategory, message: Message,
+) -> Result<Delivery, ClientError> {
+    let allowed = user.settings.notifications.allows(
+        category,
+        NotificationChannel::Email,
+        user.timezone.as_deref(),
+        Utc::now(),
+    );
+
+    if !allowed {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            "📭 {:?} email to {} held back by its preferences",
+            category,
+            PrivacyPolicy::for_user(user).identifier(&user.id)
+        );
+
+        return Ok(Delivery::Suppressed);
+    }
+
+    mailer.send_mail(message).await?;
+
+    Ok(Delivery::Sent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_mailer::{
+        Category, Code, ContentType, Detail, Mailer, MockMailerTrait, Response, Severity,
+    };
+
+    use crate::NotificationSettings;
+
+    fn message() -> Message {
+        Mailer::build_mail(
+            "no-reply@example.com",
+            "test@example.com",
+            "Test",
+            ContentType::TEXT_HTML,
+            "<h1>Test</h1>".to_string(),
+        )
+        .unwrap()
+    }
+
+    fn user(notifications: NotificationSettings) -> UserModel {
+        let mut user = UserModel::default();
+        user.settings.notifications = notifications;
+        user
+    }
+
+    #[tokio::test]
+    async fn test_send_notification() {
+        let mut mock_mailer = MockMailerTrait::default();
+        mock_mailer.expect_send_mail().times(1).returning(|_| {
+            Ok(Response::new(
+                Code::new(
+                    Severity::PositiveCompletion,
+                    Category::MailSystem,
+                    Detail::Zero,
+                ),
+                Vec::new(),
+            ))
+        });
+
+        let delivery = send_notification(
+            &mock_mailer,
+            &user(NotificationSettings::default()),
+            NotificationCategory::Account,
+            message(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(delivery, Delivery::Sent);
+    }
+
+    #[tokio::test]
+    async fn test_send_notification_suppressed() {
+        let mut mock_mailer = MockMailerTrait::default();
+        mock_mailer.expect_send_mail().never();
+
+        let delivery = send_notification(
+            &mock_mailer,
+            &user(NotificationSettings {
+                email: false,
+                ..Default::default()
+            }),
+            NotificationCategory::Account,
+            message(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(delivery, Delivery::Suppressed);
+    }
+}
@@ -14,6 +14,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+
 /// # PasswordError
 ///
 /// The PasswordError enum is an enum that represents the errors for passwords.
@@ -51,6 +53,8 @@ const PASS_MIN_SYMBOLS: usize = 1;
 const PASS_MIN_DIGITS: usize = 1;
 const PASS_MIN_LETTERS: usize = 1;
 
+const RANDOM_PASSWORD_LENGTH: usize = 32;
+
 const DIGITS: &str = "1234567890";
 const LETTERS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
 const SPECIAL_SYMBOLS: &str = "-_/\\(){}[]|!@#$%^&*)+=\"\';:<>,.?";
@@ -90,6 +94,18 @@ pub fn valid_password(password: &str) -> Result<(), PasswordError> {
     Ok(())
 }
 
+/// # Random password
+///
+/// The `random_password` method generates an unusable password for accounts
+/// that never log in with a local password (directory or provisioned users).
+pub fn random_password() -> String {
+    thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(RANDOM_PASSWORD_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -152,6 +168,13 @@ mod tests {
         assert_eq!(result.unwrap_err(), PasswordError::NotEnoughDigits);
     }
 
+    #[test]
+    fn test_random_password() {
+        let password = random_password();
+        assert_eq!(password.len(), RANDOM_PASSWORD_LENGTH);
+        assert_ne!(password, random_password());
+    }
+
     #[test]
     fn test_valid_password_not_enough_letters() {
         let result = valid_password("123!@#123!@#");
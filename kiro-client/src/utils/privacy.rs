@@ -0,0 +1,336 @@
+// utils/privacy.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::net::IpAddr;
+
+use hmac::{Hmac, Mac};
+use http::{HeaderMap, HeaderValue};
+use kiro_database::{db_bridge::DatabaseOperations, get_env_unsafe, DbId};
+use sha2::Sha256;
+
+use crate::{error::ClientError, PrivacySettings, SessionModel, UserModel, CSRF_HEADER};
+
+/// Headers carrying credentials or client addresses
+const SENSITIVE_HEADERS: [&str; 9] = [
+    "authorization",
+    "cookie",
+    "set-cookie",
+    CSRF_HEADER,
+    "x-forwarded-for",
+    "x-real-ip",
+    "true-client-ip",
+    "cf-connecting-ip",
+    "x-peer-addr",
+];
+
+/// Value replacing redacted data
+const REDACTED: &str = "[redacted]";
+
+/// # Privacy Policy
+///
+/// The privacy policy enforces the privacy settings of a user on the data the
+/// server keeps or emits about them. Every session IP, trace or error report
+/// mentioning a user goes through it.
+///
+/// - Without location consent, IP addresses are truncated to their network
+///   and no location is derived from them.
+/// - Without data collection consent, IP addresses and identifiers are
+///   replaced by an HMAC-SHA256 keyed with `PRIVACY_HASH_KEY`.
+///
+/// ## Example
+///
+/// ```rust
+/// use kiro_client::{PrivacyPolicy, PrivacySettings};
+///
+/// let policy = PrivacyPolicy::new(&PrivacySettings {
+///     data_collection: true,
+///     location: false,
+/// });
+///
+/// assert_eq!(policy.session_ip("203.0.113.195"), "203.0.113.0");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrivacyPolicy {
+    data_collection: bool,
+    location: bool,
+}
+
+impl PrivacyPolicy {
+    /// Creates the policy of privacy settings
+    pub fn new(settings: &PrivacySettings) -> Self {
+        Self {
+            data_collection: settings.data_collection,
+            location: settings.location,
+        }
+    }
+
+    /// Creates the policy of a user
+    pub fn for_user(user: &UserModel) -> Self {
+        Self::new(&user.settings.privacy)
+    }
+
+    /// Whether the user consents to the collection of their data
+    pub fn allows_data_collection(&self) -> bool {
+        self.data_collection
+    }
+
+    /// Whether the user consents to being located from their connections
+    pub fn allows_location(&self) -> bool {
+        self.location
+    }
+
+    /// Returns the IP address to store in a session
+    ///
+    /// Values that are not IP addresses were already anonymized and are kept.
+    pub fn session_ip(&self, ip: &str) -> String {
+        let Ok(address) = ip.parse::<IpAddr>() else {
+            return ip.to_string();
+        };
+
+        if !self.data_collection {
+            pseudonymize(ip)
+        } else if !self.location {
+            truncate_ip(address).to_string()
+        } else {
+            ip.to_string()
+        }
+    }
+
+    /// Returns the identifier of the user as it may appear in traces and error reports
+    pub fn identifier(&self, id: &DbId) -> String {
+        if self.data_collection {
+            id.to_string()
+        } else {
+            pseudonymize(&id.to_string())
+        }
+    }
+
+    /// Returns a copy of request or response headers safe to log
+    ///
+    /// Headers are logged before the user is known, credentials and client
+    /// addresses are therefore always redacted.
+    pub fn redact_headers(headers: &HeaderMap) -> HeaderMap {
+        let mut redacted = headers.clone();
+
+        for name in SENSITIVE_HEADERS {
+            if let http::header::Entry::Occupied(mut entry) = redacted.entry(name) {
+                entry.insert(HeaderValue::from_static(REDACTED));
+            }
+        }
+
+        redacted
+    }
+}
+
+/// Applies the privacy policy of a user to the IP addresses of its sessions
+///
+/// Called when the user withdraws a consent, sessions created afterwards go
+/// through the policy on creation.
+///
+/// # Arguments
+/// * `db` - Database connection implementing DatabaseOperations trait
+/// * `user_id` - Identifier of the user
+pub(crate) async fn enforce_session_privacy<DB: DatabaseOperations + Send + Sync>(
+    db: &DB, user_id: DbId,
+) -> Result<(), ClientError> {
+    let user = db
+        .select::<UserModel>(user_id.clone())
+        .await?
+        .ok_or(ClientError::DBOptionNone)?;
+    let policy = PrivacyPolicy::for_user(&user);
+
+    let sessions = db
+        .read_by_field_thing::<SessionModel>("sessions", "user_id", user_id, None)
+        .await?;
+
+    for session in sessions {
+        let Some(ip) = session.ip_address else {
+            continue;
+        };
+
+        let anonymized = policy.session_ip(&ip);
+        if anonymized != ip {
+            db.update_field(session.id, "ip_address", anonymized)
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Truncates an IP address to its network, /24 for IPv4 and /48 for IPv6
+fn truncate_ip(address: IpAddr) -> IpAddr {
+    match address {
+        IpAddr::V4(v4) => {
+            let [a, b, c, _] = v4.octets();
+            IpAddr::from([a, b, c, 0])
+        }
+        IpAddr::V6(v6) => {
+            let [a, b, c, ..] = v6.segments();
+            IpAddr::from([a, b, c, 0, 0, 0, 0, 0])
+        }
+    }
+}
+
+/// Replaces a value by a keyed hash, stable for a deployment
+///
+/// # Panics
+///
+/// Panics if `PRIVACY_HASH_KEY` is not set, the server refuses to start without it.
+pub(crate) fn pseudonymize(value: &str) -> String {
+    let key = get_env_unsafe("PRIVACY_HASH_KEY");
+
+    // Safety: HMAC accepts keys of any length
+    let mut mac = Hmac::<Sha256>::new_from_slice(key.as_bytes()).unwrap();
+    mac.update(value.as_bytes());
+
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .take(16)
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::db_bridge::MockDatabaseOperations;
+
+    use crate::UserSettings;
+
+    fn policy(data_collection: bool, location: bool) -> PrivacyPolicy {
+        std::env::set_var("PRIVACY_HASH_KEY", "privacy_hash_key");
+
+        PrivacyPolicy::new(&PrivacySettings {
+            data_collection,
+            location,
+        })
+    }
+
+    #[test]
+    fn test_session_ip() {
+        // Full consent keeps the address
+        assert_eq!(
+            policy(true, true).session_ip("203.0.113.195"),
+            "203.0.113.195"
+        );
+
+        // No location consent truncates it
+        assert_eq!(
+            policy(true, false).session_ip("203.0.113.195"),
+            "203.0.113.0"
+        );
+        assert_eq!(
+            policy(true, false).session_ip("2001:db8:85a3:8d3:1319:8a2e:370:7348"),
+            "2001:db8:85a3::"
+        );
+
+        // No data collection consent hashes it
+        let hashed = policy(false, true).session_ip("203.0.113.195");
+        assert_eq!(hashed.len(), 32);
+        assert_ne!(hashed, "203.0.113.195");
+        assert_eq!(policy(false, false).session_ip("203.0.113.195"), hashed);
+
+        // Anonymized values are kept as is
+        assert_eq!(policy(false, false).session_ip(&hashed), hashed);
+        assert_eq!(policy(true, false).session_ip("unknown"), "unknown");
+    }
+
+    #[test]
+    fn test_identifier() {
+        let id = DbId::from(("users", "user"));
+
+        assert_eq!(policy(true, false).identifier(&id), "users:user");
+        assert_ne!(policy(false, false).identifier(&id), "users:user");
+        assert_eq!(
+            policy(false, false).identifier(&id),
+            policy(false, true).identifier(&id)
+        );
+    }
+
+    #[test]
+    fn test_pseudonymize() {
+        std::env::set_var("PRIVACY_HASH_KEY", "privacy_hash_key");
+
+        // HMAC-SHA256 of the value, truncated to 128 bits
+        assert_eq!(
+            pseudonymize("203.0.113.195"),
+            "a52163b1e91f97724dcb73349f3c6502"
+        );
+    }
+
+    #[test]
+    fn test_redact_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", HeaderValue::from_static("Bearer token"));
+        headers.insert("x-forwarded-for", HeaderValue::from_static("203.0.113.195"));
+        headers.insert("content-type", HeaderValue::from_static("application/grpc"));
+
+        let redacted = PrivacyPolicy::redact_headers(&headers);
+        assert_eq!(redacted["authorization"], REDACTED);
+        assert_eq!(redacted["x-forwarded-for"], REDACTED);
+        assert_eq!(redacted["content-type"], "application/grpc");
+    }
+
+    #[tokio::test]
+    async fn test_enforce_session_privacy() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| {
+                Ok(Some(UserModel {
+                    settings: UserSettings {
+                        privacy: PrivacySettings {
+                            data_collection: true,
+                            location: false,
+                        },
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }))
+            });
+
+        mock_db
+            .expect_read_by_field_thing::<SessionModel>()
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(vec![
+                    SessionModel {
+                        ip_address: Some("203.0.113.195".to_string()),
+                        ..Default::default()
+                    },
+                    SessionModel {
+                        ip_address: Some("198.51.100.0".to_string()),
+                        ..Default::default()
+                    },
+                ])
+            });
+
+        // Only the session still holding a full address is updated
+        mock_db
+            .expect_update_field::<String>()
+            .withf(|_, field, value| field == "ip_address" && value == "203.0.113.0")
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let result = enforce_session_privacy(&mock_db, DbId::default()).await;
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,222 @@
+// utils/profile.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono_tz::Tz;
+
+/// # ProfileError
+///
+/// The ProfileError enum is an enum that represents the errors for profile fields.
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum ProfileError {
+    #[error(
+        "Display name too long. Maximum size: {} characters",
+        DISPLAY_NAME_MAX_LENGTH
+    )]
+    DisplayNameTooLong,
+
+    #[error(
+        "Handle must be {} to {} characters long",
+        HANDLE_MIN_LENGTH,
+        HANDLE_MAX_LENGTH
+    )]
+    InvalidHandleLength,
+
+    #[error("Handle may only contain lowercase letters, digits, '_' and '.'")]
+    InvalidHandleCharacters,
+
+    #[error("Bio too long. Maximum size: {} characters", BIO_MAX_LENGTH)]
+    BioTooLong,
+
+    #[error("{0} is not an IANA timezone")]
+    InvalidTimezone(String),
+
+    #[error("{0} is not a locale")]
+    InvalidLocale(String),
+}
+
+impl ProfileError {
+    /// Returns the stable key of the error, used to localize its message
+    pub fn key(&self) -> &'static str {
+        match self {
+            ProfileError::DisplayNameTooLong => "profile.display_name_too_long",
+            ProfileError::InvalidHandleLength => "profile.invalid_handle_length",
+            ProfileError::InvalidHandleCharacters => "profile.invalid_handle_characters",
+            ProfileError::BioTooLong => "profile.bio_too_long",
+            ProfileError::InvalidTimezone(_) => "profile.invalid_timezone",
+            ProfileError::InvalidLocale(_) => "profile.invalid_locale",
+        }
+    }
+}
+
+const DISPLAY_NAME_MAX_LENGTH: usize = 64;
+
+const HANDLE_MIN_LENGTH: usize = 3;
+const HANDLE_MAX_LENGTH: usize = 30;
+
+const BIO_MAX_LENGTH: usize = 280;
+
+/// # Normalize handle
+///
+/// The `normalize_handle` method returns the stored form of a handle, handles are
+/// case insensitive and may be sent with a leading `@`.
+pub fn normalize_handle(handle: &str) -> String {
+    handle.trim().trim_start_matches('@').to_lowercase()
+}
+
+/// # Valid display name
+///
+/// The `valid_display_name` method returns a result if the display name is valid.
+pub fn valid_display_name(display_name: &str) -> Result<(), ProfileError> {
+    if display_name.chars().count() > DISPLAY_NAME_MAX_LENGTH {
+        return Err(ProfileError::DisplayNameTooLong);
+    }
+    Ok(())
+}
+
+/// # Valid handle
+///
+/// The `valid_handle` method returns a result if the normalized handle is valid.
+pub fn valid_handle(handle: &str) -> Result<(), ProfileError> {
+    if !(HANDLE_MIN_LENGTH..=HANDLE_MAX_LENGTH).contains(&handle.len()) {
+        return Err(ProfileError::InvalidHandleLength);
+    }
+    if !handle
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '.')
+    {
+        return Err(ProfileError::InvalidHandleCharacters);
+    }
+    Ok(())
+}
+
+/// # Valid bio
+///
+/// The `valid_bio` method returns a result if the bio is valid.
+pub fn valid_bio(bio: &str) -> Result<(), ProfileError> {
+    if bio.chars().count() > BIO_MAX_LENGTH {
+        return Err(ProfileError::BioTooLong);
+    }
+    Ok(())
+}
+
+/// # Valid timezone
+///
+/// The `valid_timezone` method returns a result if the timezone is a name of the
+/// IANA database (`UTC`, `Europe/Paris`, `America/Argentina/Buenos_Aires`).
+pub fn valid_timezone(timezone: &str) -> Result<(), ProfileError> {
+    if timezone.parse::<Tz>().is_err() {
+        return Err(ProfileError::InvalidTimezone(timezone.to_string()));
+    }
+    Ok(())
+}
+
+/// # Valid locale
+///
+/// The `valid_locale` method returns a result if the locale is a BCP 47 language
+/// tag made of a language, an optional script and an optional region (`fr`,
+/// `en-GB`, `zh-Hant-TW`).
+pub fn valid_locale(locale: &str) -> Result<(), ProfileError> {
+    let mut parts = locale.split('-');
+
+    let language = parts.next().unwrap_or_default();
+    let mut valid =
+        (2..=3).contains(&language.len()) && language.chars().all(|c| c.is_ascii_lowercase());
+
+    let mut rest: Vec<&str> = parts.collect();
+
+    // Optional script, e.g. `Hant`
+    if rest.first().is_some_and(|script| {
+        script.len() == 4
+            && script.starts_with(|c: char| c.is_ascii_uppercase())
+            && script[1..].chars().all(|c| c.is_ascii_lowercase())
+    }) {
+        rest.remove(0);
+    }
+
+    // Optional region, e.g. `GB` or `419`
+    if let Some(region) = rest.first() {
+        let is_region = (region.len() == 2 && region.chars().all(|c| c.is_ascii_uppercase()))
+            || (region.len() == 3 && region.chars().all(|c| c.is_ascii_digit()));
+        valid &= is_region && rest.len() == 1;
+    }
+
+    if !valid {
+        return Err(ProfileError::InvalidLocale(locale.to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_handle() {
+        assert_eq!(normalize_handle(" @Jane.Doe "), "jane.doe");
+    }
+
+    #[test]
+    fn test_valid_handle() {
+        assert!(valid_handle("jane_doe.42").is_ok());
+        assert_eq!(valid_handle("jd"), Err(ProfileError::InvalidHandleLength));
+        assert_eq!(
+            valid_handle("jane doe"),
+            Err(ProfileError::InvalidHandleCharacters)
+        );
+        assert_eq!(
+            valid_handle("Jane"),
+            Err(ProfileError::InvalidHandleCharacters)
+        );
+    }
+
+    #[test]
+    fn test_valid_display_name_and_bio() {
+        assert!(valid_display_name("Jane Doe").is_ok());
+        assert_eq!(
+            valid_display_name(&"a".repeat(DISPLAY_NAME_MAX_LENGTH + 1)),
+            Err(ProfileError::DisplayNameTooLong)
+        );
+        assert!(valid_bio(&"é".repeat(BIO_MAX_LENGTH)).is_ok());
+        assert_eq!(
+            valid_bio(&"a".repeat(BIO_MAX_LENGTH + 1)),
+            Err(ProfileError::BioTooLong)
+        );
+    }
+
+    #[test]
+    fn test_valid_timezone() {
+        assert!(valid_timezone("UTC").is_ok());
+        assert!(valid_timezone("Europe/Paris").is_ok());
+        assert!(valid_timezone("America/Argentina/Buenos_Aires").is_ok());
+        assert!(valid_timezone("Etc/GMT+2").is_ok());
+        assert!(valid_timezone("Paris").is_err());
+        assert!(valid_timezone("Mars/Olympus").is_err());
+        assert!(valid_timezone("Europe/").is_err());
+        assert!(valid_timezone("Europe/Atlantis").is_err());
+    }
+
+    #[test]
+    fn test_valid_locale() {
+        assert!(valid_locale("fr").is_ok());
+        assert!(valid_locale("en-GB").is_ok());
+        assert!(valid_locale("zh-Hant-TW").is_ok());
+        assert!(valid_locale("es-419").is_ok());
+        assert!(valid_locale("en_GB").is_err());
+        assert!(valid_locale("EN").is_err());
+        assert!(valid_locale("en-gb").is_err());
+        assert!(valid_locale("en-GB-x").is_err());
+    }
+}
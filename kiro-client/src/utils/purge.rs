@@ -0,0 +1,360 @@
+// utils/purge.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use kiro_database::{
+    db_bridge::{Database, DatabaseOperations},
+    db_query::Condition,
+    get_env_or, DbDateTime,
+};
+
+#[cfg(feature = "mailer")]
+use kiro_mailer::{ContentType, Mailer, MailerTrait};
+#[cfg(feature = "storage")]
+use kiro_storage::BucketS3;
+
+#[cfg(feature = "tracing")]
+use crate::PrivacyPolicy;
+use crate::{error::ClientError, UserModel};
+#[cfg(feature = "mailer")]
+use crate::{utils::notification::send_notification, NotificationCategory};
+
+/// Records referencing a user, as `(table, field)` pairs
+const USER_RECORDS: &[(&str, &str)] = &[
+    ("sessions", "user_id"),
+    ("links", "user"),
+    ("account_events", "user"),
+    ("changes", "user"),
+    ("policy_consents", "user"),
+    ("invitations", "used_by"),
+    ("user_emails", "user"),
+    #[cfg(feature = "storage")]
+    ("files", "user"),
+    #[cfg(feature = "export")]
+    ("exports", "user"),
+    #[cfg(feature = "oauth")]
+    ("oauth_codes", "user"),
+    #[cfg(feature = "oauth")]
+    ("oauth_consents", "user"),
+];
+
+/// Purges an account
+///
+/// Deletes the records referencing the user, the objects stored for the user
+/// and the user record, then confirms the deletion by email. Group memberships
+/// are stored on the user record and are removed with it.
+///
+/// Accounts with a payment customer are kept until kiro-payment can delete
+/// customers, the customer holds personal data too.
+///
+/// # Arguments
+/// * `db` - Database connection implementing DatabaseOperations trait
+/// * `user` - User to purge
+pub(crate) async fn purge_user<DB: DatabaseOperations + Send + Sync>(
+    db: &DB, user: &UserModel,
+) -> Result<(), ClientError> {
+    if let Some(customer_id) = user.customer_id.as_ref().filter(|id| !id.is_empty()) {
+        return Err(ClientError::PaymentCustomer(customer_id.clone()));
+    }
+
+    for (table, field) in USER_RECORDS {
+        db.delete_where(table, Condition::eq(field, user.id.clone()))
+            .await
+            .map_err(ClientError::Database)?;
+    }
+
+    // Avatar, data exports and any other object stored for the user
+    #[cfg(feature = "storage")]
+    BucketS3::new()
+        .await
+        .delete_objects(user.id.clone())
+        .await?;
+
+    db.delete(user.id.clone())
+        .await
+        .map_err(ClientError::Database)?;
+
+    #[cfg(feature = "mailer")]
+    if let Err(_e) = send_confirmation(user).await {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            "🗑️ Failed to confirm the deletion of {}: {}",
+            PrivacyPolicy::for_user(user).identifier(&user.id),
+            _e
+        );
+    }
+
+    Ok(())
+}
+
+/// Confirms the deletion of an account by email
+///
+/// Sent as a security notice, whatever the preferences of the user.
+#[cfg(feature = "mailer")]
+async fn send_confirmation(user: &UserModel) -> Result<(), ClientError> {
+    let template = Mailer::load_template("account_deleted.html")
+        .await
+        .map_err(|e| ClientError::Database(kiro_database::DatabaseError::Internal(e.to_string())))?
+        .replace("${{USER_NAME}}", &user.email);
+
+    let from = get_env_or("SMTP_USER", "test@example.com");
+
+    let message = Mailer::build_mail(
+        &from,
+        &user.email,
+        "Your account has been deleted",
+        ContentType::TEXT_HTML,
+        template,
+    )?;
+
+    send_notification(
+        &Mailer::new(),
+        user,
+        NotificationCategory::Security,
+        message,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Purges the accounts whose grace period is over
+///
+/// Accounts with a payment customer cannot be purged until kiro-payment can
+/// delete customers. Their purge is held, `purge_held_at` is set and the job
+/// skips them instead of retrying every run.
+///
+/// # Arguments
+/// * `db` - Database connection implementing DatabaseOperations trait
+///
+/// # Returns
+/// * `Ok(usize)` - Number of purged accounts
+/// * `Err(ClientError)` - Database error
+pub(crate) async fn purge_deleted_users<DB: DatabaseOperations + Send + Sync>(
+    db: &DB,
+) -> Result<usize, ClientError> {
+    let users = db
+        .read_where::<UserModel>(
+            "users",
+            Condition::And(vec![
+                Condition::is_set("deletion_scheduled_at"),
+                Condition::le("deletion_scheduled_at", Utc::now()),
+                Condition::is_none("purge_held_at"),
+            ]),
+            None,
+        )
+        .await
+        .map_err(ClientError::Database)?;
+
+    let mut purged = 0;
+
+    for user in &users {
+        match purge_user(db, user).await {
+            Ok(()) => purged += 1,
+            Err(ClientError::PaymentCustomer(_)) => {
+                let _held = db
+                    .update_field(user.id.clone(), "purge_held_at", DbDateTime::now())
+                    .await;
+
+                #[cfg(feature = "tracing")]
+                match _held {
+                    Ok(()) => tracing::warn!(
+                        "🗑️ Purge of {} held until its payment customer is deleted",
+                        PrivacyPolicy::for_user(user).identifier(&user.id)
+                    ),
+                    Err(e) => tracing::error!(
+                        "🗑️ Failed to hold the purge of {}: {}",
+                        PrivacyPolicy::for_user(user).identifier(&user.id),
+                        e
+                    ),
+                }
+            }
+            Err(_e) => {
+                #[cfg(feature = "tracing")]
+                tracing::error!(
+                    "🗑️ Failed to purge {}: {}",
+                    PrivacyPolicy::for_user(user).identifier(&user.id),
+                    _e
+                );
+            }
+        }
+    }
+
+    Ok(purged)
+}
+
+/// Runs the account purge job
+///
+/// Purges the accounts whose deletion grace period is over, every
+/// `ACCOUNT_PURGE_INTERVAL_MINUTES` (60 minutes by default).
+///
+/// # Arguments
+/// * `db` - Database connection
+///
+/// # Example
+/// ```rust,no_run
+/// use kiro_client::run_purge_job;
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// let db = Database::Mock(MockDatabaseOperations::new());
+///
+/// tokio::spawn(run_purge_job(db));
+/// ```
+pub async fn run_purge_job(db: Database) {
+    let minutes: u64 = get_env_or("ACCOUNT_PURGE_INTERVAL_MINUTES", "60")
+        .parse()
+        .unwrap_or(60)
+        // A zero period makes `interval` panic
+        .max(1);
+
+    let mut interval = tokio::time::interval(Duration::from_secs(minutes.saturating_mul(60)));
+
+    loop {
+        interval.tick().await;
+
+        match purge_deleted_users(&db).await {
+            Ok(_purged) =>
+            {
+                #[cfg(feature = "tracing")]
+                if _purged > 0 {
+                    tracing::info!("🗑️ Purged {} deleted accounts", _purged);
+                }
+            }
+            Err(_e) => {
+                #[cfg(feature = "tracing")]
+                tracing::error!("🗑️ Account purge failed: {}", _e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError, DbId};
+    use mockall::predicate::{always, eq};
+
+    #[cfg(not(feature = "storage"))]
+    #[tokio::test]
+    async fn test_purge_user() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let user = UserModel {
+            customer_id: None,
+            deletion_scheduled_at: Some(DbDateTime::now()),
+            ..Default::default()
+        };
+
+        mock_db
+            .expect_delete_where()
+            .withf(|table, _| USER_RECORDS.iter().any(|(record, _)| *record == table))
+            .times(USER_RECORDS.len())
+            .returning(|_, _| Ok(0));
+
+        mock_db
+            .expect_delete()
+            .with(eq(user.id.clone()))
+            .times(1)
+            .returning(|_| Ok(Some(())));
+
+        assert!(purge_user(&mock_db, &user).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_purge_user_db_error() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let user = UserModel {
+            customer_id: None,
+            ..Default::default()
+        };
+
+        mock_db
+            .expect_delete_where()
+            .times(1)
+            .returning(|_, _| Err(DatabaseError::Internal("Database error".to_string())));
+
+        mock_db.expect_delete().never();
+
+        assert!(purge_user(&mock_db, &user).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_purge_user_payment_customer() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let user = UserModel {
+            customer_id: Some("cus_123".to_string()),
+            ..Default::default()
+        };
+
+        mock_db.expect_delete_where().never();
+        mock_db.expect_delete().never();
+
+        assert!(matches!(
+            purge_user(&mock_db, &user).await,
+            Err(ClientError::PaymentCustomer(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_purge_deleted_users_none_due() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<UserModel>()
+            .with(eq("users"), always(), eq(None))
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
+
+        assert_eq!(purge_deleted_users(&mock_db).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_purge_deleted_users_holds_payment_customers() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<UserModel>()
+            .withf(|_, condition, _| match condition {
+                Condition::And(conditions) => {
+                    conditions.contains(&Condition::is_none("purge_held_at"))
+                }
+                _ => false,
+            })
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(vec![UserModel {
+                    id: DbId::from(("users", "customer")),
+                    customer_id: Some("cus_123".to_string()),
+                    deletion_scheduled_at: Some(DbDateTime::now()),
+                    ..Default::default()
+                }])
+            });
+
+        mock_db.expect_delete_where().never();
+        mock_db.expect_delete().never();
+
+        mock_db
+            .expect_update_field::<DbDateTime>()
+            .withf(|id, field, _| {
+                *id == DbId::from(("users", "customer")) && field == "purge_held_at"
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        assert_eq!(purge_deleted_users(&mock_db).await.unwrap(), 0);
+    }
+}
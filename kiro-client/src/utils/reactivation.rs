@@ -0,0 +1,160 @@
+// utils/reactivation.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{Duration, Utc};
+use kiro_database::{db_bridge::DatabaseOperations, get_env_or, DbId};
+use kiro_mailer::{ContentType, LinkModel, LinkType, Mailer, MailerTrait};
+
+use crate::{
+    error::ClientError, utils::notification::send_notification, NotificationCategory, UserModel,
+};
+
+/// Emails a reactivation link to the owner of a disabled account
+///
+/// # Arguments
+/// * `db` - Database connection implementing DatabaseOperations trait
+/// * `user` - Disabled user
+pub(crate) async fn send_reactivation_link<DB: DatabaseOperations + Send + Sync>(
+    db: &DB, user: &UserModel,
+) -> Result<(), ClientError> {
+    let expiry = Utc::now() + Duration::days(2);
+
+    let reactivate_url =
+        LinkModel::create_from_user(db, user.id.clone(), expiry, LinkType::AccountReactivation)
+            .await?
+            .construct_link();
+
+    let template = Mailer::load_template("account_reactivation.html")
+        .await
+        .map_err(|e| ClientError::Database(kiro_database::DatabaseError::Internal(e.to_string())))?
+        .replace("${{USER_NAME}}", &user.email)
+        .replace("${{REACTIVATE_URL}}", &reactivate_url);
+
+    let from = get_env_or("SMTP_USER", "test@example.com");
+
+    let message = Mailer::build_mail(
+        &from,
+        &user.email,
+        "Reactivate your account",
+        ContentType::TEXT_HTML,
+        template,
+    )?;
+
+    send_notification(
+        &Mailer::new(),
+        user,
+        NotificationCategory::Security,
+        message,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Reactivates the account a reactivation link was sent to
+///
+/// The link is deleted once used, the change is recorded with the owner of the
+/// account as actor.
+///
+/// # Arguments
+/// * `db` - Database connection implementing DatabaseOperations trait
+/// * `link_id` - Identifier of the reactivation link
+///
+/// # Returns
+/// * `Ok(DbId)` - ID of the reactivated user
+/// * `Err(ClientError::MailerError)` - The link is unknown, expired or of another type
+pub(crate) async fn consume_reactivation_link<DB: DatabaseOperations + Send + Sync>(
+    db: &DB, link_id: &str,
+) -> Result<DbId, ClientError> {
+    let link = LinkModel::get_valid_link(db, link_id, LinkType::AccountReactivation).await?;
+
+    UserModel::reactivate(db, link.user.clone(), Some(link.user.clone())).await?;
+
+    db.delete(link.id).await.map_err(ClientError::Database)?;
+
+    Ok(link.user)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kiro_database::{db_bridge::MockDatabaseOperations, DbDateTime};
+    use kiro_mailer::MailerError;
+    use mockall::predicate::eq;
+
+    use crate::models::{AccountEventKind, AccountEventModel, CreateAccountEventModel};
+
+    fn link(link_type: LinkType) -> LinkModel {
+        LinkModel {
+            id: DbId::from(("links", "link")),
+            user: DbId::from(("users", "user")),
+            link_type,
+            expiry: DbDateTime::from(Utc::now() + Duration::hours(1)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_consume_reactivation_link() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<LinkModel>()
+            .with(eq(DbId::from(("links", "link"))))
+            .times(1)
+            .returning(|_| Ok(Some(link(LinkType::AccountReactivation))));
+
+        mock_db
+            .expect_update_field::<bool>()
+            .with(eq(DbId::from(("users", "user"))), eq("activated"), eq(true))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        mock_db
+            .expect_create::<CreateAccountEventModel, AccountEventModel>()
+            .withf(|_, data| {
+                data.kind == AccountEventKind::Reactivated && data.actor == Some(data.user.clone())
+            })
+            .times(1)
+            .returning(|_, _| Ok(vec![AccountEventModel::default()]));
+
+        mock_db
+            .expect_delete()
+            .with(eq(DbId::from(("links", "link"))))
+            .times(1)
+            .returning(|_| Ok(Some(())));
+
+        let user_id = consume_reactivation_link(&mock_db, "link").await.unwrap();
+        assert_eq!(user_id, DbId::from(("users", "user")));
+    }
+
+    #[tokio::test]
+    async fn test_consume_reactivation_link_wrong_type() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<LinkModel>()
+            .times(1)
+            .returning(|_| Ok(Some(link(LinkType::PasswordReset))));
+
+        mock_db.expect_update_field::<bool>().never();
+
+        let result = consume_reactivation_link(&mock_db, "link").await;
+        assert!(matches!(
+            result,
+            Err(ClientError::MailerError(MailerError::InvalidType))
+        ));
+    }
+}
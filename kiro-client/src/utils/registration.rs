@@ -0,0 +1,569 @@
+// utils/registration.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use kiro_api::client::v1::CreateInvitationRequest;
+use kiro_database::{db_bridge::DatabaseOperations, get_env_or, DbDateTime, DbId};
+use once_cell::sync::Lazy;
+
+use crate::{error::ClientError, GroupModel, InvitationModel, UserModel};
+
+/// Disposable email domains, from the bundled seed list
+static DISPOSABLE_DOMAINS: Lazy<HashSet<&'static str>> = Lazy::new(|| {
+    include_str!("../../data/disposable_domains.txt")
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect()
+});
+
+/// # RegistrationError
+///
+/// The RegistrationError enum represents the reasons a registration is rejected
+/// by the registration policy.
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum RegistrationError {
+    #[error("Invalid email address")]
+    InvalidEmail,
+
+    #[error("Registration requires an invitation")]
+    InvitationRequired,
+
+    #[error("Invitation code is invalid or expired")]
+    InvalidInvitation,
+
+    #[error("Email domain is not allowed")]
+    DomainNotAllowed,
+
+    #[error("Email domain is denied")]
+    DomainDenied,
+
+    #[error("Disposable email addresses are not accepted")]
+    DisposableEmail,
+}
+
+impl RegistrationError {
+    /// Returns the stable key of the error, used to localize its message
+    pub fn key(&self) -> &'static str {
+        match self {
+            RegistrationError::InvalidEmail => "registration.invalid_email",
+            RegistrationError::InvitationRequired => "registration.invitation_required",
+            RegistrationError::InvalidInvitation => "registration.invalid_invitation",
+            RegistrationError::DomainNotAllowed => "registration.domain_not_allowed",
+            RegistrationError::DomainDenied => "registration.domain_denied",
+            RegistrationError::DisposableEmail => "registration.disposable_email",
+        }
+    }
+}
+
+/// # Registration Mode
+///
+/// The registration mode decides who may register.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RegistrationMode {
+    /// Anyone may register
+    Open,
+    /// Only the holders of an invitation code may register
+    InviteOnly,
+    /// Only the addresses of the allowed domains, or the holders of an
+    /// invitation code, may register
+    Allowlist,
+}
+
+/// # Registration Policy
+///
+/// The registration policy is read from the environment:
+///
+/// - `REGISTRATION_MODE` - `open`, `invite_only` or `allowlist`
+/// - `REGISTRATION_ALLOWED_DOMAINS` - Domains of the allowlist, comma separated
+/// - `REGISTRATION_DENIED_DOMAINS` - Domains always rejected, comma separated
+/// - `REGISTRATION_BLOCK_DISPOSABLE` - Whether disposable email domains are rejected
+///
+/// A domain matches its subdomains. The denylist and the disposable domains
+/// are checked in every mode, invitations included. The bundled disposable
+/// domains are a seed list of common providers, the denylist extends it.
+#[derive(Debug, Clone)]
+pub struct RegistrationPolicy {
+    pub mode: RegistrationMode,
+    pub allowed_domains: Vec<String>,
+    pub denied_domains: Vec<String>,
+    pub block_disposable: bool,
+}
+
+/// Parses a comma separated list of domains
+fn parse_domains(domains: &str) -> Vec<String> {
+    domains
+        .split(',')
+        .map(|domain| domain.trim().trim_start_matches('@').to_lowercase())
+        .filter(|domain| !domain.is_empty())
+        .collect()
+}
+
+/// Whether a domain is one of the domains of a list, or one of their subdomains
+fn matches_domain<'a>(domain: &str, mut domains: impl Iterator<Item = &'a str>) -> bool {
+    domains.any(|listed| {
+        domain == listed
+            || domain
+                .strip_suffix(listed)
+                .is_some_and(|prefix| prefix.ends_with('.'))
+    })
+}
+
+impl RegistrationPolicy {
+    /// Reads the registration policy from the environment
+    pub fn from_env() -> Self {
+        let mode = match get_env_or("REGISTRATION_MODE", "open").as_str() {
+            "invite_only" => RegistrationMode::InviteOnly,
+            "allowlist" => RegistrationMode::Allowlist,
+            "open" => RegistrationMode::Open,
+            _unknown => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("📝 Unknown registration mode: {}", _unknown);
+                RegistrationMode::Open
+            }
+        };
+
+        Self {
+            mode,
+            allowed_domains: parse_domains(&get_env_or("REGISTRATION_ALLOWED_DOMAINS", "")),
+            denied_domains: parse_domains(&get_env_or("REGISTRATION_DENIED_DOMAINS", "")),
+            block_disposable: get_env_or("REGISTRATION_BLOCK_DISPOSABLE", "true") == "true",
+        }
+    }
+
+    /// Checks an email address against the policy
+    ///
+    /// # Arguments
+    /// * `email` - Address of the registration
+    /// * `invited` - Whether the registration holds a valid invitation
+    ///
+    /// # Returns
+    /// * `Ok(())` - The address may register
+    /// * `Err(RegistrationError)` - Reason of the rejection
+    pub fn check_email(&self, email: &str, invited: bool) -> Result<(), RegistrationError> {
+        let domain = email
+            .rsplit_once('@')
+            .map(|(local, domain)| (local, domain.trim().to_lowercase()))
+            .filter(|(local, domain)| !local.is_empty() && domain.contains('.'))
+            .map(|(_, domain)| domain)
+            .ok_or(RegistrationError::InvalidEmail)?;
+
+        if matches_domain(&domain, self.denied_domains.iter().map(String::as_str)) {
+            return Err(RegistrationError::DomainDenied);
+        }
+
+        if self.block_disposable && matches_domain(&domain, DISPOSABLE_DOMAINS.iter().copied()) {
+            return Err(RegistrationError::DisposableEmail);
+        }
+
+        match self.mode {
+            RegistrationMode::Open => Ok(()),
+            RegistrationMode::InviteOnly if invited => Ok(()),
+            RegistrationMode::InviteOnly => Err(RegistrationError::InvitationRequired),
+            RegistrationMode::Allowlist
+                if invited
+                    || matches_domain(&domain, self.allowed_domains.iter().map(String::as_str)) =>
+            {
+                Ok(())
+            }
+            RegistrationMode::Allowlist => Err(RegistrationError::DomainNotAllowed),
+        }
+    }
+}
+
+/// Checks a registration against the registration policy
+///
+/// # Arguments
+/// * `db` - Database connection implementing DatabaseOperations trait
+/// * `email` - Address of the registration
+/// * `invitation_code` - Invitation code sent with the registration, if any
+///
+/// # Returns
+/// * `Ok(Some(InvitationModel))` - The registration is accepted with an invitation
+/// * `Ok(None)` - The registration is accepted without invitation
+/// * `Err(ClientError::RegistrationRejected)` - Reason of the rejection
+/// * `Err(ClientError)` - Database error
+pub(crate) async fn check_registration<DB: DatabaseOperations + Send + Sync>(
+    db: &DB, email: &str, invitation_code: Option<&str>,
+) -> Result<Option<InvitationModel>, ClientError> {
+    let invitation = match invitation_code.filter(|code| !code.is_empty()) {
+        Some(code) => Some(
+            InvitationModel::get_valid(db, code)
+                .await?
+                .filter(|invitation| {
+                    invitation
+                        .email
+                        .as_ref()
+                        .map_or(true, |invited| invited.eq_ignore_ascii_case(email))
+                })
+                .ok_or(RegistrationError::InvalidInvitation)?,
+        ),
+        None => None,
+    };
+
+    RegistrationPolicy::from_env().check_email(email, invitation.is_some())?;
+
+    Ok(invitation)
+}
+
+/// Redeems the invitation of a registered user
+///
+/// Marks the invitation as used, then grants the user the role and group the
+/// invitation carries. When another registration used the invitation in the
+/// meantime, the user is deleted and the registration rejected.
+///
+/// # Arguments
+/// * `db` - Database connection implementing DatabaseOperations trait
+/// * `invitation` - Invitation returned by `check_registration`
+/// * `user` - Registered user
+pub(crate) async fn redeem_invitation<DB: DatabaseOperations + Send + Sync>(
+    db: &DB, invitation: &InvitationModel, user: &UserModel,
+) -> Result<(), ClientError> {
+    if !invitation.claim(db, user.id.clone()).await? {
+        db.delete(user.id.clone())
+            .await
+            .map_err(ClientError::Database)?;
+
+        return Err(RegistrationError::InvalidInvitation.into());
+    }
+
+    if invitation.is_admin {
+        db.update_field(user.id.clone(), "is_admin", true)
+            .await
+            .map_err(ClientError::Database)?;
+    }
+
+    if let Some(group) = &invitation.group {
+        GroupModel::add_member(db, user, group.clone()).await?;
+    }
+
+    Ok(())
+}
+
+/// Issues an invitation on behalf of an admin
+///
+/// # Arguments
+/// * `db` - Database connection implementing DatabaseOperations trait
+/// * `request` - Restrictions and grants of the invitation
+///
+/// # Returns
+/// * `Ok(InvitationModel)` - Created invitation, holding the code to send
+/// * `Err(ClientError::GroupNotFound)` - The group does not exist
+/// * `Err(ClientError::InvalidInvitationExpiry)` - The expiry is not in the future
+/// * `Err(ClientError)` - Database error
+pub(crate) async fn issue_invitation<DB: DatabaseOperations + Send + Sync>(
+    db: &DB, request: CreateInvitationRequest,
+) -> Result<InvitationModel, ClientError> {
+    let group = match request.group_id.filter(|id| !id.is_empty()) {
+        Some(id) => {
+            let group_id = DbId::from(("groups", id.as_str()));
+
+            db.select::<GroupModel>(group_id.clone())
+                .await
+                .map_err(ClientError::Database)?
+                .ok_or(ClientError::GroupNotFound)?;
+
+            Some(group_id)
+        }
+        None => None,
+    };
+
+    let expires_at = match request.expires_at {
+        Some(expiry) => Some(
+            u32::try_from(expiry.nanos)
+                .ok()
+                .and_then(|nanos| DbDateTime::from_timestamp(expiry.seconds, nanos))
+                .filter(|expiry| **expiry > chrono::Utc::now())
+                .ok_or(ClientError::InvalidInvitationExpiry)?,
+        ),
+        None => None,
+    };
+
+    InvitationModel::create(
+        db,
+        request.email.filter(|email| !email.is_empty()),
+        request.is_admin,
+        group,
+        expires_at,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_api::google::protobuf::Timestamp;
+    use kiro_database::db_bridge::MockDatabaseOperations;
+    use mockall::predicate::eq;
+
+    use crate::CreateInvitationModel;
+
+    fn policy(mode: RegistrationMode) -> RegistrationPolicy {
+        RegistrationPolicy {
+            mode,
+            allowed_domains: parse_domains("example.com, @kiro.dev"),
+            denied_domains: parse_domains("spam.example.org"),
+            block_disposable: true,
+        }
+    }
+
+    #[test]
+    fn test_open_mode() {
+        let policy = policy(RegistrationMode::Open);
+
+        assert_eq!(policy.check_email("user@gmail.com", false), Ok(()));
+        assert_eq!(
+            policy.check_email("user@mailinator.com", false),
+            Err(RegistrationError::DisposableEmail)
+        );
+        assert_eq!(
+            policy.check_email("user@eu.mailinator.com", false),
+            Err(RegistrationError::DisposableEmail)
+        );
+        assert_eq!(
+            policy.check_email("user@spam.example.org", true),
+            Err(RegistrationError::DomainDenied)
+        );
+        assert_eq!(
+            policy.check_email("user@localhost", false),
+            Err(RegistrationError::InvalidEmail)
+        );
+    }
+
+    #[test]
+    fn test_invite_only_mode() {
+        let policy = policy(RegistrationMode::InviteOnly);
+
+        assert_eq!(
+            policy.check_email("user@example.com", false),
+            Err(RegistrationError::InvitationRequired)
+        );
+        assert_eq!(policy.check_email("user@example.com", true), Ok(()));
+    }
+
+    #[test]
+    fn test_allowlist_mode() {
+        let policy = policy(RegistrationMode::Allowlist);
+
+        assert_eq!(policy.check_email("user@Example.com", false), Ok(()));
+        assert_eq!(policy.check_email("user@team.kiro.dev", false), Ok(()));
+        assert_eq!(
+            policy.check_email("user@notexample.com", false),
+            Err(RegistrationError::DomainNotAllowed)
+        );
+        assert_eq!(policy.check_email("user@gmail.com", true), Ok(()));
+    }
+
+    #[test]
+    fn test_disposable_domains_loaded() {
+        assert!(DISPOSABLE_DOMAINS.contains("yopmail.com"));
+        assert!(!DISPOSABLE_DOMAINS
+            .iter()
+            .any(|domain| domain.starts_with('#')));
+    }
+
+    #[tokio::test]
+    async fn test_check_registration_invalid_code() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<InvitationModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
+
+        let result = check_registration(&mock_db, "user@example.com", Some("unknown")).await;
+        assert!(matches!(
+            result,
+            Err(ClientError::RegistrationRejected(
+                RegistrationError::InvalidInvitation
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_check_registration_invitation_for_another_email() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<InvitationModel>()
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(vec![InvitationModel {
+                    email: Some("invited@example.com".to_string()),
+                    ..Default::default()
+                }])
+            });
+
+        let result =
+            check_registration(&mock_db, "user@example.com", Some("invitation_code")).await;
+        assert!(matches!(
+            result,
+            Err(ClientError::RegistrationRejected(
+                RegistrationError::InvalidInvitation
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_redeem_invitation_grants_role_and_group() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let group = DbId::from(("groups", "engineering"));
+        let user = UserModel {
+            groups: vec![],
+            ..Default::default()
+        };
+
+        mock_db
+            .expect_update_where::<serde_json::Value>()
+            .times(1)
+            .returning(|_| Ok(vec![serde_json::json!({})]));
+
+        mock_db
+            .expect_update_field::<bool>()
+            .with(eq(user.id.clone()), eq("is_admin"), eq(true))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        mock_db
+            .expect_update_field::<Vec<DbId>>()
+            .with(eq(user.id.clone()), eq("groups"), eq(vec![group.clone()]))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let invitation = InvitationModel {
+            is_admin: true,
+            group: Some(group),
+            ..Default::default()
+        };
+
+        redeem_invitation(&mock_db, &invitation, &user)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_redeem_used_invitation_deletes_user() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let user = UserModel::default();
+
+        mock_db
+            .expect_update_where::<serde_json::Value>()
+            .times(1)
+            .returning(|_| Ok(vec![]));
+
+        mock_db
+            .expect_delete()
+            .with(eq(user.id.clone()))
+            .times(1)
+            .returning(|_| Ok(Some(())));
+
+        let result = redeem_invitation(&mock_db, &InvitationModel::default(), &user).await;
+        assert!(matches!(
+            result,
+            Err(ClientError::RegistrationRejected(
+                RegistrationError::InvalidInvitation
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_issue_invitation() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let group_id = DbId::from(("groups", "engineering"));
+
+        mock_db
+            .expect_select::<GroupModel>()
+            .with(eq(group_id.clone()))
+            .times(1)
+            .returning(|_| Ok(Some(GroupModel::default())));
+
+        mock_db
+            .expect_create::<CreateInvitationModel, InvitationModel>()
+            .withf(move |table, data| {
+                table == "invitations"
+                    && data.group == Some(group_id.clone())
+                    && data.expires_at.is_some()
+            })
+            .times(1)
+            .returning(|_, data| {
+                Ok(vec![InvitationModel {
+                    code: data.code,
+                    group: data.group,
+                    expires_at: data.expires_at,
+                    ..Default::default()
+                }])
+            });
+
+        let invitation = issue_invitation(
+            &mock_db,
+            CreateInvitationRequest {
+                email: None,
+                is_admin: false,
+                group_id: Some("engineering".to_string()),
+                expires_at: Some(Timestamp {
+                    seconds: (chrono::Utc::now() + chrono::Duration::days(7)).timestamp(),
+                    nanos: 0,
+                }),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert!(!invitation.code.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_issue_invitation_unknown_group() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<GroupModel>()
+            .times(1)
+            .returning(|_| Ok(None));
+
+        let result = issue_invitation(
+            &mock_db,
+            CreateInvitationRequest {
+                group_id: Some("unknown".to_string()),
+                ..Default::default()
+            },
+        )
+        .await;
+
+        assert!(matches!(result, Err(ClientError::GroupNotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_issue_invitation_past_expiry() {
+        let mock_db = MockDatabaseOperations::new();
+
+        let result = issue_invitation(
+            &mock_db,
+            CreateInvitationRequest {
+                expires_at: Some(Timestamp {
+                    seconds: (chrono::Utc::now() - chrono::Duration::days(1)).timestamp(),
+                    nanos: 0,
+                }),
+                ..Default::default()
+            },
+        )
+        .await;
+
+        assert!(matches!(result, Err(ClientError::InvalidInvitationExpiry)));
+    }
+}
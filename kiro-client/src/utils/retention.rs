@@ -0,0 +1,374 @@
+// utils/retention.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{net::IpAddr, time::Duration};
+
+use chrono::{DateTime, Utc};
+use kiro_database::{
+    db_bridge::{Database, DatabaseOperations},
+    db_query::Condition,
+    get_env_or, DbId,
+};
+use serde::Deserialize;
+
+use crate::{error::ClientError, utils::privacy::pseudonymize};
+
+/// Action applied to the records a retention rule expires
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RetentionAction {
+    /// Deletes the records
+    Delete,
+    /// Replaces the IP address of the records by a keyed hash
+    HashIp,
+}
+
+impl RetentionAction {
+    fn as_str(&self) -> &'static str {
+        match self {
+            RetentionAction::Delete => "delete",
+            RetentionAction::HashIp => "hash_ip",
+        }
+    }
+}
+
+/// Retention rule of a table
+#[derive(Debug)]
+struct RetentionRule {
+    /// Table the rule applies to
+    table: &'static str,
+    /// Date field the age of a record is measured from
+    field: &'static str,
+    /// Action applied to the records older than the retention
+    action: RetentionAction,
+    /// Environment variable holding the retention, in days
+    variable: &'static str,
+    /// Retention used when the variable is not set
+    default_days: i64,
+}
+
+/// Retention rules, applied in order
+const RETENTION_RULES: &[RetentionRule] = &[
+    RetentionRule {
+        table: "links",
+        field: "expiry",
+        action: RetentionAction::Delete,
+        variable: "LINK_RETENTION_DAYS",
+        default_days: 7,
+    },
+    RetentionRule {
+        table: "sessions",
+        field: "expires_at",
+        action: RetentionAction::Delete,
+        variable: "SESSION_RETENTION_DAYS",
+        default_days: 0,
+    },
+    RetentionRule {
+        table: "sessions",
+        field: "created_at",
+        action: RetentionAction::HashIp,
+        variable: "SESSION_IP_RETENTION_DAYS",
+        default_days: 3,
+    },
+    RetentionRule {
+        table: "policy_consents",
+        field: "accepted_at",
+        action: RetentionAction::HashIp,
+        variable: "CONSENT_IP_RETENTION_DAYS",
+        default_days: 365,
+    },
+    RetentionRule {
+        table: "login_failures",
+        field: "created_at",
+        action: RetentionAction::Delete,
+        variable: "LOGIN_FAILURE_RETENTION_DAYS",
+        default_days: 1,
+    },
+];
+
+/// Record holding an IP address
+#[derive(Debug, Clone, Deserialize)]
+struct AddressedRecord {
+    id: DbId,
+    ip_address: Option<String>,
+}
+
+impl RetentionRule {
+    /// Date before which the records are expired
+    fn cutoff(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let days: i64 = get_env_or(self.variable, &self.default_days.to_string())
+            .parse()
+            .unwrap_or(self.default_days);
+
+        now - chrono::Duration::days(days)
+    }
+
+    /// Applies the rule to the records older than the cutoff
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of deleted or anonymized records
+    /// * `Err(ClientError)` - Database error
+    async fn apply<DB: DatabaseOperations + Send + Sync>(
+        &self, db: &DB, before: DateTime<Utc>,
+    ) -> Result<usize, ClientError> {
+        let expired = Condition::lt(self.field, before);
+
+        match self.action {
+            RetentionAction::Delete => db
+                .delete_where(self.table, expired)
+                .await
+                .map_err(ClientError::Database),
+            RetentionAction::HashIp => {
+                let records = db
+                    .read_where::<AddressedRecord>(
+                        self.table,
+                        Condition::And(vec![expired, Condition::is_set("ip_address")]),
+                        None,
+                    )
+                    .await
+                    .map_err(ClientError::Database)?;
+
+                let mut hashed = 0;
+
+                // Values that are not IP addresses were already anonymized
+                for record in records {
+                    let Some(ip) = record.ip_address else {
+                        continue;
+                    };
+
+                    if ip.parse::<IpAddr>().is_ok() {
+                        db.update_field(record.id, "ip_address", pseudonymize(&ip))
+                            .await
+                            .map_err(ClientError::Database)?;
+                        hashed += 1;
+                    }
+                }
+
+                Ok(hashed)
+            }
+        }
+    }
+}
+
+/// Applies the retention rules
+///
+/// Deletes the links expired for more than `LINK_RETENTION_DAYS` and the
+/// sessions expired for more than `SESSION_RETENTION_DAYS`, and hashes the IP
+/// addresses of the sessions and consents older than
+/// `SESSION_IP_RETENTION_DAYS` and `CONSENT_IP_RETENTION_DAYS`. A failing rule
+/// doesn't prevent the next ones from being applied.
+///
+/// # Arguments
+/// * `db` - Database connection implementing DatabaseOperations trait
+///
+/// # Returns
+/// The number of records deleted or anonymized by each rule, as
+/// `(table, action, count)`, for the rules that were applied
+pub(crate) async fn apply_retention<DB: DatabaseOperations + Send + Sync>(
+    db: &DB,
+) -> Vec<(&'static str, &'static str, usize)> {
+    let now = Utc::now();
+    let mut report = Vec::new();
+
+    for rule in RETENTION_RULES {
+        match rule.apply(db, rule.cutoff(now)).await {
+            Ok(count) => report.push((rule.table, rule.action.as_str(), count)),
+            Err(_e) => {
+                #[cfg(feature = "tracing")]
+                tracing::error!(
+                    "🧹 Retention of {} ({}) failed: {}",
+                    rule.table,
+                    rule.action.as_str(),
+                    _e
+                );
+            }
+        }
+    }
+
+    report
+}
+
+/// Runs the data retention job
+///
+/// Applies the retention rules every `RETENTION_INTERVAL_MINUTES` (60 minutes
+/// by default), and reports the records deleted or anonymized by each rule as
+/// the `retention_records` counter.
+///
+/// # Arguments
+/// * `db` - Database connection
+///
+/// # Example
+/// ```rust,no_run
+/// use kiro_client::run_retention_job;
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// let db = Database::Mock(MockDatabaseOperations::new());
+///
+/// tokio::spawn(run_retention_job(db));
+/// ```
+pub async fn run_retention_job(db: Database) {
+    let minutes: u64 = get_env_or("RETENTION_INTERVAL_MINUTES", "60")
+        .parse()
+        .unwrap_or(60)
+        // A zero period makes `interval` panic
+        .max(1);
+
+    let mut interval = tokio::time::interval(Duration::from_secs(minutes.saturating_mul(60)));
+
+    loop {
+        interval.tick().await;
+
+        for (_table, _action, count) in apply_retention(&db).await {
+            #[cfg(feature = "tracing")]
+            if count > 0 {
+                tracing::info!(
+                    monotonic_counter.retention_records = count as u64,
+                    table = _table,
+                    action = _action,
+                    "🧹 Retention applied to {} records of {} ({})",
+                    count,
+                    _table,
+                    _action
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::{db_bridge::MockDatabaseOperations, DatabaseError};
+
+    fn rule(table: &str, action: RetentionAction) -> &'static RetentionRule {
+        RETENTION_RULES
+            .iter()
+            .find(|rule| rule.table == table && rule.action == action)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_cutoff_uses_default_days() {
+        let now = Utc::now();
+
+        assert_eq!(
+            rule("links", RetentionAction::Delete).cutoff(now),
+            now - chrono::Duration::days(7)
+        );
+        assert_eq!(rule("sessions", RetentionAction::Delete).cutoff(now), now);
+    }
+
+    #[tokio::test]
+    async fn test_delete_expired_links() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_delete_where()
+            .withf(|table, condition| {
+                table == "links"
+                    && matches!(condition, Condition::Lt(field, _) if field == "expiry")
+            })
+            .times(1)
+            .returning(|_, _| Ok(2));
+
+        let removed = rule("links", RetentionAction::Delete)
+            .apply(&mock_db, Utc::now())
+            .await
+            .unwrap();
+
+        assert_eq!(removed, 2);
+    }
+
+    #[tokio::test]
+    async fn test_hash_old_session_ips() {
+        std::env::set_var("PRIVACY_HASH_KEY", "privacy_hash_key");
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<AddressedRecord>()
+            .withf(|table, _, _| table == "sessions")
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(vec![
+                    AddressedRecord {
+                        id: DbId::from(("sessions", "clear")),
+                        ip_address: Some("203.0.113.195".to_string()),
+                    },
+                    AddressedRecord {
+                        id: DbId::from(("sessions", "hashed")),
+                        ip_address: Some(pseudonymize("198.51.100.7")),
+                    },
+                ])
+            });
+
+        mock_db
+            .expect_update_field::<String>()
+            .withf(|id, field, value| {
+                *id == DbId::from(("sessions", "clear"))
+                    && field == "ip_address"
+                    && *value == pseudonymize("203.0.113.195")
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let hashed = rule("sessions", RetentionAction::HashIp)
+            .apply(&mock_db, Utc::now())
+            .await
+            .unwrap();
+
+        assert_eq!(hashed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_apply_retention_continues_after_failure() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_delete_where()
+            .withf(|table, _| table == "links")
+            .times(1)
+            .returning(|_, _| Err(DatabaseError::Internal("Database error".to_string())));
+
+        mock_db
+            .expect_delete_where()
+            .withf(|table, _| table == "sessions")
+            .times(1)
+            .returning(|_, _| Ok(1));
+
+        mock_db
+            .expect_delete_where()
+            .withf(|table, _| table == "login_failures")
+            .times(1)
+            .returning(|_, _| Ok(0));
+
+        mock_db
+            .expect_read_where::<AddressedRecord>()
+            .times(2)
+            .returning(|_, _, _| Ok(vec![]));
+
+        let report = apply_retention(&mock_db).await;
+
+        assert_eq!(
+            report,
+            vec![
+                ("sessions", "delete", 1),
+                ("sessions", "hash_ip", 0),
+                ("policy_consents", "hash_ip", 0),
+                ("login_failures", "delete", 0),
+            ]
+        );
+    }
+}
@@ -0,0 +1,188 @@
+// utils/secondary_email.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{Duration, Utc};
+use kiro_database::{db_bridge::DatabaseOperations, get_env_or};
+use kiro_mailer::{ContentType, LinkModel, LinkType, Mailer, MailerTrait};
+
+use crate::{
+    error::ClientError, utils::notification::send_notification, NotificationCategory,
+    UserEmailModel, UserModel,
+};
+
+/// Validity of the verification links
+const LINK_VALIDITY_DAYS: i64 = 2;
+
+/// Attaches a secondary address to a user and emails it a verification link
+///
+/// The address cannot be used to log in until the link is followed.
+///
+/// # Arguments
+/// * `db` - Database connection implementing DatabaseOperations trait
+/// * `user` - Owner of the address
+/// * `email` - Address to attach
+///
+/// # Returns
+/// * `Ok(UserEmailModel)` - Attached address
+/// * `Err(ClientError::EmailTaken)` - The address is already attached or in use
+/// * `Err(ClientError)` - Database or mailer error
+pub(crate) async fn add_secondary_email<DB: DatabaseOperations + Send + Sync>(
+    db: &DB, user: &UserModel, email: &str,
+) -> Result<UserEmailModel, ClientError> {
+    let address = UserEmailModel::add(db, user, email).await?;
+
+    let expiry = Utc::now() + Duration::days(LINK_VALIDITY_DAYS);
+
+    let link =
+        LinkModel::create_from_user(db, user.id.clone(), expiry, LinkType::EmailVerification)
+            .await?;
+
+    address.set_link(db, link.id.clone()).await?;
+
+    let template = Mailer::load_template("email_verification.html")
+        .await
+        .map_err(|e| ClientError::Database(kiro_database::DatabaseError::Internal(e.to_string())))?
+        .replace("${{USER_NAME}}", &user.email)
+        .replace("${{NEW_MAIL}}", email)
+        .replace("${{CONFIRM_URL}}", &link.construct_link());
+
+    let message = Mailer::build_mail(
+        &get_env_or("SMTP_USER", "test@example.com"),
+        email,
+        "Verify your email address",
+        ContentType::TEXT_HTML,
+        template,
+    )?;
+
+    send_notification(
+        &Mailer::new(),
+        user,
+        NotificationCategory::Security,
+        message,
+    )
+    .await?;
+
+    Ok(address)
+}
+
+/// Verifies the secondary address a verification link was sent to
+///
+/// The link is deleted whether the address is verified or was taken in the meantime.
+///
+/// # Arguments
+/// * `db` - Database connection implementing DatabaseOperations trait
+/// * `link_id` - Identifier of the verification link
+///
+/// # Returns
+/// * `Ok(UserEmailModel)` - Verified address
+/// * `Err(ClientError::MailerError)` - The link is unknown, expired or of another type
+/// * `Err(ClientError::EmailNotFound)` - The address was removed
+/// * `Err(ClientError::EmailTaken)` - The address was verified by another account
+pub(crate) async fn consume_email_verification_link<DB: DatabaseOperations + Send + Sync>(
+    db: &DB, link_id: &str,
+) -> Result<UserEmailModel, ClientError> {
+    let link = LinkModel::get_valid_link(db, link_id, LinkType::EmailVerification).await?;
+
+    let result = UserEmailModel::verify(db, link.id.clone()).await;
+
+    db.delete(link.id).await.map_err(ClientError::Database)?;
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kiro_database::{db_bridge::MockDatabaseOperations, DbDateTime, DbId};
+    use kiro_mailer::MailerError;
+    use mockall::predicate::eq;
+
+    fn link(link_type: LinkType) -> LinkModel {
+        LinkModel {
+            id: DbId::from(("links", "link")),
+            user: DbId::from(("users", "user")),
+            link_type,
+            expiry: DbDateTime::from(Utc::now() + Duration::hours(1)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_consume_email_verification_link() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<LinkModel>()
+            .with(eq(DbId::from(("links", "link"))))
+            .times(1)
+            .returning(|_| Ok(Some(link(LinkType::EmailVerification))));
+
+        mock_db
+            .expect_read_where::<UserEmailModel>()
+            .withf(|table, _, _| table == "user_emails")
+            .times(1)
+            .returning(|_, _, _| Ok(vec![UserEmailModel::default()]));
+
+        mock_db
+            .expect_read_by_field::<UserModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        mock_db
+            .expect_read_by_field::<UserEmailModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        mock_db
+            .expect_update_where::<UserEmailModel>()
+            .withf(|update| update.table == "user_emails")
+            .times(1)
+            .returning(|_| {
+                Ok(vec![UserEmailModel {
+                    verified: true,
+                    ..Default::default()
+                }])
+            });
+
+        mock_db
+            .expect_delete()
+            .with(eq(DbId::from(("links", "link"))))
+            .times(1)
+            .returning(|_| Ok(Some(())));
+
+        let address = consume_email_verification_link(&mock_db, "link")
+            .await
+            .unwrap();
+        assert!(address.verified);
+    }
+
+    #[tokio::test]
+    async fn test_consume_email_verification_link_wrong_type() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<LinkModel>()
+            .times(1)
+            .returning(|_| Ok(Some(link(LinkType::EmailConfirmation))));
+
+        mock_db.expect_read_where::<UserEmailModel>().never();
+
+        let result = consume_email_verification_link(&mock_db, "link").await;
+        assert!(matches!(
+            result,
+            Err(ClientError::MailerError(MailerError::InvalidType))
+        ));
+    }
+}
@@ -0,0 +1,400 @@
+// utils/sync.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use kiro_api::client::v1::{
+    change::Record, Change, GetChangesSinceResponse, Group, Settings, User,
+};
+use kiro_database::{
+    db_bridge::{Database, DatabaseOperations},
+    get_env_or, DbId,
+};
+
+use crate::{error::ClientError, ChangeKind, ChangeModel, GroupModel, UserModel};
+
+/// Maximum number of changes returned at once
+const PAGE_SIZE: usize = 500;
+
+/// Gets the changes of a user since a cursor
+///
+/// Returns the current state of the user, settings and group records changed
+/// after the cursor, oldest first, and tombstones for the deleted ones. A
+/// record changed several times is returned once. When the cursor can't be
+/// synced incrementally, because it is the first sync or the changes after it
+/// were compacted, the whole state is returned and `reset` is set.
+///
+/// # Arguments
+/// * `db` - Database connection implementing DatabaseOperations trait
+/// * `user_id` - User identifier
+/// * `cursor` - Cursor returned by the previous sync, 0 for the first one
+///
+/// # Returns
+/// * `Ok(GetChangesSinceResponse)` - Changes and the cursor to sync from next
+/// * `Err(ClientError)` - Database error, or unknown user
+pub(crate) async fn get_changes_since<DB: DatabaseOperations + Send + Sync>(
+    db: &DB, user_id: DbId, cursor: u64,
+) -> Result<GetChangesSinceResponse, ClientError> {
+    // Read before the records, so a concurrent change is synced again rather than missed
+    let versions = ChangeModel::get_versions(db).await?;
+
+    let user = db
+        .select::<UserModel>(user_id.clone())
+        .await
+        .map_err(ClientError::Database)?
+        .ok_or(ClientError::DBOptionNone)?;
+
+    let group_ids = user.groups.iter().map(|group| group.to_string()).collect();
+
+    // First sync, compacted changes, or a cursor ahead of the log from another database
+    if cursor == 0 || cursor < versions.compacted || cursor > versions.version {
+        return Ok(GetChangesSinceResponse {
+            changes: snapshot(db, &user, versions.version).await?,
+            cursor: versions.version,
+            has_more: false,
+            reset: true,
+            group_ids,
+        });
+    }
+
+    let mut entries = ChangeModel::get_changes_since(db, user_id, cursor, PAGE_SIZE + 1).await?;
+    let has_more = entries.len() > PAGE_SIZE;
+    entries.truncate(PAGE_SIZE);
+
+    let next = entries.last().map_or(cursor, |entry| entry.version);
+
+    // Keep the latest change of each record
+    let mut latest: Vec<ChangeModel> = Vec::new();
+    for entry in entries.into_iter().rev() {
+        if !latest
+            .iter()
+            .any(|other| other.kind == entry.kind && other.record == entry.record)
+        {
+            latest.push(entry);
+        }
+    }
+    latest.reverse();
+
+    // Changed groups, and every group of the user when memberships may have changed
+    let user_changed = latest.iter().any(|entry| entry.kind == ChangeKind::User);
+    let mut group_ids_to_read: Vec<DbId> = latest
+        .iter()
+        .filter(|entry| entry.kind == ChangeKind::Group && !entry.deleted)
+        .map(|entry| entry.record.clone())
+        .collect();
+    if user_changed {
+        group_ids_to_read.extend(user.groups.iter().cloned());
+    }
+    let groups = read_groups(db, group_ids_to_read).await?;
+
+    let mut changes = Vec::with_capacity(latest.len());
+
+    for entry in &latest {
+        let record = if entry.deleted {
+            None
+        } else {
+            match entry.kind {
+                ChangeKind::User => Some(Record::User(User::from(&user))),
+                ChangeKind::Settings => Some(Record::Settings(Settings::from(&user.settings))),
+                ChangeKind::Group => groups
+                    .iter()
+                    .find(|group| group.id == entry.record)
+                    .map(|group| Record::Group(Group::from(group))),
+            }
+        };
+
+        changes.push(Change {
+            version: entry.version,
+            id: entry.record.to_string(),
+            // A group deleted after the change is a tombstone as well
+            deleted: record.is_none(),
+            record,
+        });
+
+        if entry.kind == ChangeKind::User && !entry.deleted {
+            changes.extend(
+                groups
+                    .iter()
+                    .filter(|group| user.groups.contains(&group.id))
+                    .filter(|group| {
+                        !latest.iter().any(|other| {
+                            other.kind == ChangeKind::Group && other.record == group.id
+                        })
+                    })
+                    .map(|group| group_change(entry.version, group)),
+            );
+        }
+    }
+
+    Ok(GetChangesSinceResponse {
+        changes,
+        cursor: next,
+        has_more,
+        reset: false,
+        group_ids,
+    })
+}
+
+/// Builds the whole state of a user, as changes at the given version
+async fn snapshot<DB: DatabaseOperations + Send + Sync>(
+    db: &DB, user: &UserModel, version: u64,
+) -> Result<Vec<Change>, ClientError> {
+    let mut changes = vec![
+        Change {
+            version,
+            id: user.id.to_string(),
+            deleted: false,
+            record: Some(Record::User(User::from(user))),
+        },
+        Change {
+            version,
+            id: user.id.to_string(),
+            deleted: false,
+            record: Some(Record::Settings(Settings::from(&user.settings))),
+        },
+    ];
+
+    changes.extend(
+        read_groups(db, user.groups.clone())
+            .await?
+            .iter()
+            .map(|group| group_change(version, group)),
+    );
+
+    Ok(changes)
+}
+
+/// Reads groups by identifier, skipping the deleted ones
+async fn read_groups<DB: DatabaseOperations + Send + Sync>(
+    db: &DB, ids: Vec<DbId>,
+) -> Result<Vec<GroupModel>, ClientError> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    db.read_by_ids::<GroupModel>("groups", ids, None)
+        .await
+        .map_err(ClientError::Database)
+}
+
+/// Builds the change of a group
+fn group_change(version: u64, group: &GroupModel) -> Change {
+    Change {
+        version,
+        id: group.id.to_string(),
+        deleted: false,
+        record: Some(Record::Group(Group::from(group))),
+    }
+}
+
+/// Compacts the change log
+///
+/// Removes the changes superseded by a newer change of the same record, then
+/// the changes older than `CHANGE_LOG_RETENTION_DAYS` (30 days by default).
+/// Clients that didn't sync since then get their whole state again.
+///
+/// # Arguments
+/// * `db` - Database connection implementing DatabaseOperations trait
+///
+/// # Returns
+/// * `Ok(usize)` - Number of removed changes
+/// * `Err(ClientError)` - Database error
+pub(crate) async fn compact_change_log<DB: DatabaseOperations + Send + Sync>(
+    db: &DB,
+) -> Result<usize, ClientError> {
+    let days: i64 = get_env_or("CHANGE_LOG_RETENTION_DAYS", "30")
+        .parse()
+        .unwrap_or(30);
+
+    let superseded = ChangeModel::compact_superseded(db).await?;
+    let expired =
+        ChangeModel::compact_expired(db, Utc::now() - chrono::Duration::days(days)).await?;
+
+    Ok(superseded + expired)
+}
+
+/// Runs the change log compaction job
+///
+/// Compacts the change log of the delta sync every
+/// `CHANGE_LOG_COMPACTION_INTERVAL_MINUTES` (60 minutes by default).
+///
+/// # Arguments
+/// * `db` - Database connection
+///
+/// # Example
+/// ```rust,no_run
+/// use kiro_client::run_change_compaction_job;
+/// use kiro_database::db_bridge::{Database, MockDatabaseOperations};
+///
+/// let db = Database::Mock(MockDatabaseOperations::new());
+///
+/// tokio::spawn(run_change_compaction_job(db));
+/// ```
+pub async fn run_change_compaction_job(db: Database) {
+    let minutes: u64 = get_env_or("CHANGE_LOG_COMPACTION_INTERVAL_MINUTES", "60")
+        .parse()
+        .unwrap_or(60);
+
+    let mut interval = tokio::time::interval(Duration::from_secs(minutes * 60));
+
+    loop {
+        interval.tick().await;
+
+        match compact_change_log(&db).await {
+            Ok(_removed) =>
+            {
+                #[cfg(feature = "tracing")]
+                if _removed > 0 {
+                    tracing::info!("🔄 Compacted {} changes", _removed);
+                }
+            }
+            Err(_e) => {
+                #[cfg(feature = "tracing")]
+                tracing::error!("🔄 Change log compaction failed: {}", _e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kiro_database::db_bridge::MockDatabaseOperations;
+
+    use crate::ChangeVersionsModel;
+
+    fn mock_versions(mock_db: &mut MockDatabaseOperations, version: u64, compacted: u64) {
+        mock_db
+            .expect_select::<ChangeVersionsModel>()
+            .times(1)
+            .returning(move |_| Ok(Some(ChangeVersionsModel { version, compacted })));
+    }
+
+    fn mock_user(mock_db: &mut MockDatabaseOperations, user: UserModel) {
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(move |_| Ok(Some(user.clone())));
+    }
+
+    #[tokio::test]
+    async fn test_first_sync_returns_whole_state() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let user = UserModel {
+            groups: vec![DbId::from(("groups", "engineering"))],
+            ..Default::default()
+        };
+
+        mock_versions(&mut mock_db, 42, 0);
+        mock_user(&mut mock_db, user.clone());
+
+        mock_db
+            .expect_read_by_ids::<GroupModel>()
+            .withf(|table, ids, _| table == "groups" && ids.len() == 1)
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(vec![GroupModel {
+                    id: DbId::from(("groups", "engineering")),
+                    ..Default::default()
+                }])
+            });
+
+        let response = get_changes_since(&mock_db, user.id.clone(), 0)
+            .await
+            .unwrap();
+
+        assert!(response.reset);
+        assert_eq!(response.cursor, 42);
+        assert_eq!(response.changes.len(), 3);
+        assert_eq!(response.group_ids, vec!["groups:engineering".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_compacted_cursor_resets() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let user = UserModel {
+            groups: vec![],
+            ..Default::default()
+        };
+
+        mock_versions(&mut mock_db, 42, 30);
+        mock_user(&mut mock_db, user.clone());
+
+        let response = get_changes_since(&mock_db, user.id.clone(), 12)
+            .await
+            .unwrap();
+
+        assert!(response.reset);
+        assert_eq!(response.cursor, 42);
+    }
+
+    #[tokio::test]
+    async fn test_incremental_sync() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let user = UserModel::default();
+        let user_id = user.id.clone();
+
+        mock_versions(&mut mock_db, 42, 10);
+        mock_user(&mut mock_db, user.clone());
+
+        mock_db
+            .expect_read_where::<ChangeModel>()
+            .times(1)
+            .returning(move |_, _, _| {
+                Ok(vec![
+                    ChangeModel {
+                        version: 21,
+                        kind: ChangeKind::Settings,
+                        record: user_id.clone(),
+                        ..Default::default()
+                    },
+                    ChangeModel {
+                        version: 22,
+                        kind: ChangeKind::Group,
+                        record: DbId::from(("groups", "engineering")),
+                        user: None,
+                        deleted: true,
+                        ..Default::default()
+                    },
+                    ChangeModel {
+                        version: 23,
+                        kind: ChangeKind::Settings,
+                        record: user_id.clone(),
+                        ..Default::default()
+                    },
+                ])
+            });
+
+        let response = get_changes_since(&mock_db, user.id.clone(), 20)
+            .await
+            .unwrap();
+
+        assert!(!response.reset);
+        assert!(!response.has_more);
+        assert_eq!(response.cursor, 23);
+
+        // The settings changed twice are returned once, after the tombstone
+        assert_eq!(response.changes.len(), 2);
+        assert!(response.changes[0].deleted);
+        assert_eq!(response.changes[0].id, "groups:engineering");
+        assert_eq!(response.changes[1].version, 23);
+        assert!(matches!(
+            response.changes[1].record,
+            Some(Record::Settings(_))
+        ));
+    }
+}
@@ -0,0 +1,535 @@
+// utils/upload.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use futures::{Stream, StreamExt};
+use kiro_api::client::v1::FileMetadata;
+use kiro_database::{db_bridge::DatabaseOperations, get_env_or, DbId};
+use kiro_storage::{BucketS3Trait, ByteStream, MultipartUpload};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    error::ClientError,
+    models::{CreateFileModel, FileModel},
+    utils::avatar::{store_avatar, AVATAR_MAX_SIZE},
+    AvatarVariant,
+};
+
+/// # UploadError
+///
+/// The UploadError enum is an enum that represents the errors for chunked uploads.
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum UploadError {
+    #[error("The first frame of an upload must be the file metadata")]
+    MissingMetadata,
+
+    #[error("Only file chunks may follow the file metadata")]
+    UnexpectedFrame,
+
+    #[error(
+        "File name must be 1 to {} characters, without path separators",
+        NAME_MAX_LENGTH
+    )]
+    InvalidName,
+
+    #[error("Checksum must be a hex encoded SHA-256 digest")]
+    InvalidChecksum,
+
+    #[error("File too large. Maximum size: {0} bytes")]
+    TooLarge(u64),
+
+    #[error("Received more than the declared {0} bytes")]
+    SizeExceeded(u64),
+
+    #[error("Received {received} bytes, {expected} were declared")]
+    SizeMismatch { expected: u64, received: u64 },
+
+    #[error("Checksum mismatch")]
+    ChecksumMismatch,
+
+    #[error("Upload interrupted")]
+    Interrupted,
+}
+
+impl UploadError {
+    /// Returns the stable key of the error, used to localize its message
+    pub fn key(&self) -> &'static str {
+        match self {
+            UploadError::MissingMetadata => "upload.missing_metadata",
+            UploadError::UnexpectedFrame => "upload.unexpected_frame",
+            UploadError::InvalidName => "upload.invalid_name",
+            UploadError::InvalidChecksum => "upload.invalid_checksum",
+            UploadError::TooLarge(_) => "upload.too_large",
+            UploadError::SizeExceeded(_) => "upload.size_exceeded",
+            UploadError::SizeMismatch { .. } => "upload.size_mismatch",
+            UploadError::ChecksumMismatch => "upload.checksum_mismatch",
+            UploadError::Interrupted => "upload.interrupted",
+        }
+    }
+}
+
+const NAME_MAX_LENGTH: usize = 255;
+
+/// Size of the parts sent to the bucket, S3 requires at least 5 MiB
+const PART_SIZE: usize = 8 * 1024 * 1024;
+
+/// Bucket path of the uploaded files
+const FILES_PATH: &str = "files";
+
+/// # Max file size
+///
+/// The `max_file_size` method returns the maximum size of an uploaded file, in bytes.
+/// Defaults to 100 MiB.
+pub fn max_file_size() -> u64 {
+    get_env_or("UPLOAD_MAX_SIZE", "104857600")
+        .parse()
+        .unwrap_or(104_857_600)
+}
+
+/// # Upload verifier
+///
+/// The UploadVerifier struct checks the chunks of an upload against its
+/// metadata. The declared size is enforced as the chunks arrive, so an upload
+/// is stopped as soon as it goes over it.
+#[derive(Debug)]
+pub struct UploadVerifier {
+    size: u64,
+    checksum: String,
+    received: u64,
+    hasher: Sha256,
+}
+
+impl UploadVerifier {
+    /// # New
+    ///
+    /// The `new` method validates the metadata of an upload, its size may not
+    /// go over `max_size`.
+    pub fn new(metadata: &FileMetadata, max_size: u64) -> Result<Self, UploadError> {
+        let name = metadata.name.as_str();
+        if name.is_empty()
+            || name.chars().count() > NAME_MAX_LENGTH
+            || name.contains(['/', '\\'])
+            || name == "."
+            || name == ".."
+        {
+            return Err(UploadError::InvalidName);
+        }
+
+        if metadata.checksum.len() != 64
+            || !metadata.checksum.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            return Err(UploadError::InvalidChecksum);
+        }
+
+        if metadata.size > max_size {
+            return Err(UploadError::TooLarge(max_size));
+        }
+
+        Ok(Self {
+            size: metadata.size,
+            checksum: metadata.checksum.to_lowercase(),
+            received: 0,
+            hasher: Sha256::new(),
+        })
+    }
+
+    /// # Push
+    ///
+    /// The `push` method accounts for a received chunk.
+    pub fn push(&mut self, chunk: &[u8]) -> Result<(), UploadError> {
+        self.received += chunk.len() as u64;
+        if self.received > self.size {
+            return Err(UploadError::SizeExceeded(self.size));
+        }
+
+        self.hasher.update(chunk);
+        Ok(())
+    }
+
+    /// # Finish
+    ///
+    /// The `finish` method checks that the whole file was received, unaltered.
+    pub fn finish(self) -> Result<(), UploadError> {
+        if self.received != self.size {
+            return Err(UploadError::SizeMismatch {
+                expected: self.size,
+                received: self.received,
+            });
+        }
+
+        if format!("{:x}", self.hasher.finalize()) != self.checksum {
+            return Err(UploadError::ChecksumMismatch);
+        }
+
+        Ok(())
+    }
+}
+
+/// # Receive file
+///
+/// The `receive_file` method streams an upload to the bucket, in parts, and
+/// records the file once its size and checksum are verified. The parts are
+/// discarded if the upload fails.
+pub async fn receive_file<DB, B, S>(
+    db: &DB, bucket: &B, user_id: DbId, metadata: FileMetadata, chunks: S,
+) -> Result<FileModel, ClientError>
+where
+    DB: DatabaseOperations + Send + Sync,
+    B: BucketS3Trait,
+    S: Stream<Item = Result<Vec<u8>, ClientError>> + Unpin + Send,
+{
+    let mut verifier = UploadVerifier::new(&metadata, max_file_size())?;
+
+    // The checksum prefix keeps files with the same name apart
+    let object_name = format!("{}-{}", &metadata.checksum[..16], metadata.name);
+    let upload = bucket
+        .create_multipart_upload(user_id.clone(), FILES_PATH, &object_name)
+        .await?;
+
+    let url = match write_parts(bucket, &upload, &mut verifier, chunks).await {
+        Ok(e_tags) => match verifier.finish() {
+            Ok(()) => bucket.complete_multipart_upload(&upload, e_tags).await,
+            Err(e) => Err(e.into()),
+        },
+        Err(e) => Err(e),
+    };
+
+    let url = match url {
+        Ok(url) => url,
+        Err(e) => {
+            // Best effort, unfinished uploads also expire with the bucket lifecycle rules
+            let _ = bucket.abort_multipart_upload(&upload).await;
+            return Err(e);
+        }
+    };
+
+    FileModel::create(
+        db,
+        CreateFileModel {
+            user: user_id,
+            name: metadata.name,
+            content_type: metadata.content_type,
+            size: metadata.size,
+            checksum: metadata.checksum.to_lowercase(),
+            url,
+        },
+    )
+    .await
+}
+
+/// Sends the chunks to the bucket in parts of [`PART_SIZE`], returns the ETags of the parts
+async fn write_parts<B, S>(
+    bucket: &B, upload: &MultipartUpload, verifier: &mut UploadVerifier, mut chunks: S,
+) -> Result<Vec<String>, ClientError>
+where
+    B: BucketS3Trait,
+    S: Stream<Item = Result<Vec<u8>, ClientError>> + Unpin + Send,
+{
+    let mut e_tags = Vec::new();
+    let mut part = Vec::with_capacity(PART_SIZE);
+
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk?;
+        verifier.push(&chunk)?;
+        part.extend_from_slice(&chunk);
+
+        if part.len() >= PART_SIZE {
+            let part_number = e_tags.len() as i32 + 1;
+            let e_tag = bucket
+                .upload_part(
+                    upload,
+                    part_number,
+                    ByteStream::from(std::mem::take(&mut part)),
+                )
+                .await?;
+            e_tags.push(e_tag);
+        }
+    }
+
+    // The last part may be smaller, an upload has at least one part
+    if !part.is_empty() || e_tags.is_empty() {
+        let part_number = e_tags.len() as i32 + 1;
+        e_tags.push(
+            bucket
+                .upload_part(upload, part_number, ByteStream::from(part))
+                .await?,
+        );
+    }
+
+    Ok(e_tags)
+}
+
+/// # Receive avatar
+///
+/// The `receive_avatar` method receives an avatar sent in chunks, verifies its
+/// size and checksum, then stores its variants.
+pub async fn receive_avatar<DB, B, S>(
+    db: &DB, bucket: &B, user_id: DbId, metadata: FileMetadata, mut chunks: S,
+) -> Result<Vec<AvatarVariant>, ClientError>
+where
+    DB: DatabaseOperations + Send + Sync,
+    B: BucketS3Trait,
+    S: Stream<Item = Result<Vec<u8>, ClientError>> + Unpin + Send,
+{
+    let mut verifier = UploadVerifier::new(&metadata, AVATAR_MAX_SIZE as u64)?;
+    let mut bytes = Vec::with_capacity(metadata.size as usize);
+
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk?;
+        verifier.push(&chunk)?;
+        bytes.extend_from_slice(&chunk);
+    }
+    verifier.finish()?;
+
+    store_avatar(db, bucket, user_id, bytes).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use kiro_database::db_bridge::MockDatabaseOperations;
+    use kiro_storage::MockBucketS3Trait;
+
+    fn metadata(content: &[u8]) -> FileMetadata {
+        FileMetadata {
+            name: "report.txt".to_string(),
+            content_type: "text/plain".to_string(),
+            size: content.len() as u64,
+            checksum: format!("{:x}", Sha256::digest(content)),
+            ..Default::default()
+        }
+    }
+
+    fn chunks(content: &[u8], size: usize) -> impl Stream<Item = Result<Vec<u8>, ClientError>> {
+        futures::stream::iter(
+            content
+                .chunks(size)
+                .map(|chunk| Ok(chunk.to_vec()))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn upload() -> MultipartUpload {
+        MultipartUpload {
+            key: "user/files_report.txt".to_string(),
+            upload_id: "upload".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_verifier() {
+        let content = b"hello world";
+
+        let mut verifier = UploadVerifier::new(&metadata(content), 1024).unwrap();
+        verifier.push(&content[..5]).unwrap();
+        verifier.push(&content[5..]).unwrap();
+        assert_eq!(verifier.finish(), Ok(()));
+
+        // Altered content
+        let mut verifier = UploadVerifier::new(&metadata(content), 1024).unwrap();
+        verifier.push(b"hello World").unwrap();
+        assert_eq!(verifier.finish(), Err(UploadError::ChecksumMismatch));
+
+        // Truncated content
+        let mut verifier = UploadVerifier::new(&metadata(content), 1024).unwrap();
+        verifier.push(&content[..5]).unwrap();
+        assert_eq!(
+            verifier.finish(),
+            Err(UploadError::SizeMismatch {
+                expected: 11,
+                received: 5
+            })
+        );
+
+        // More than declared, stopped mid-stream
+        let mut verifier = UploadVerifier::new(&metadata(content), 1024).unwrap();
+        verifier.push(content).unwrap();
+        assert_eq!(verifier.push(b"!"), Err(UploadError::SizeExceeded(11)));
+    }
+
+    #[test]
+    fn test_verifier_metadata() {
+        let content = b"hello world";
+
+        assert_eq!(
+            UploadVerifier::new(&metadata(content), 10).unwrap_err(),
+            UploadError::TooLarge(10)
+        );
+
+        let mut invalid = metadata(content);
+        invalid.name = "../report.txt".to_string();
+        assert_eq!(
+            UploadVerifier::new(&invalid, 1024).unwrap_err(),
+            UploadError::InvalidName
+        );
+
+        let mut invalid = metadata(content);
+        invalid.checksum = "abc".to_string();
+        assert_eq!(
+            UploadVerifier::new(&invalid, 1024).unwrap_err(),
+            UploadError::InvalidChecksum
+        );
+    }
+
+    #[tokio::test]
+    async fn test_receive_file() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let mut mock_bucket = MockBucketS3Trait::new();
+
+        // Two full parts and a smaller last one
+        let content = vec![7; 2 * PART_SIZE + 10];
+
+        mock_bucket
+            .expect_create_multipart_upload()
+            .withf(|_, path, name| path == "files" && name.ends_with("-report.txt"))
+            .times(1)
+            .returning(|_, _, _| Ok(upload()));
+
+        mock_bucket
+            .expect_upload_part()
+            .times(3)
+            .returning(|_, part_number, _| Ok(format!("etag-{}", part_number)));
+
+        mock_bucket
+            .expect_complete_multipart_upload()
+            .withf(|_, e_tags| e_tags == &["etag-1", "etag-2", "etag-3"])
+            .times(1)
+            .returning(|upload, _| Ok(format!("https://bucket/{}", upload.key)));
+
+        mock_db
+            .expect_create::<CreateFileModel, FileModel>()
+            .withf(|table, file| table == "files" && file.size == 2 * PART_SIZE as u64 + 10)
+            .times(1)
+            .returning(|_, file| {
+                Ok(vec![FileModel {
+                    url: file.url,
+                    ..Default::default()
+                }])
+            });
+
+        let file = receive_file(
+            &mock_db,
+            &mock_bucket,
+            DbId::default(),
+            metadata(&content),
+            chunks(&content, 1024 * 1024),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(file.url, "https://bucket/user/files_report.txt");
+    }
+
+    #[tokio::test]
+    async fn test_receive_file_checksum_mismatch() {
+        let mock_db = MockDatabaseOperations::new();
+        let mut mock_bucket = MockBucketS3Trait::new();
+
+        let content = b"hello world";
+        let mut declared = metadata(content);
+        declared.checksum = format!("{:x}", Sha256::digest(b"hello World"));
+
+        mock_bucket
+            .expect_create_multipart_upload()
+            .times(1)
+            .returning(|_, _, _| Ok(upload()));
+
+        mock_bucket
+            .expect_upload_part()
+            .times(1)
+            .returning(|_, _, _| Ok("etag-1".to_string()));
+
+        // The parts are discarded, nothing is recorded
+        mock_bucket
+            .expect_abort_multipart_upload()
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let result = receive_file(
+            &mock_db,
+            &mock_bucket,
+            DbId::default(),
+            declared,
+            chunks(content, 4),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(ClientError::InvalidUpload(UploadError::ChecksumMismatch))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_receive_file_interrupted() {
+        let mock_db = MockDatabaseOperations::new();
+        let mut mock_bucket = MockBucketS3Trait::new();
+
+        mock_bucket
+            .expect_create_multipart_upload()
+            .times(1)
+            .returning(|_, _, _| Ok(upload()));
+
+        mock_bucket
+            .expect_abort_multipart_upload()
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let content = b"hello world";
+        let stream = futures::stream::iter(vec![
+            Ok(b"hello".to_vec()),
+            Err(ClientError::InvalidUpload(UploadError::Interrupted)),
+        ]);
+
+        let result = receive_file(
+            &mock_db,
+            &mock_bucket,
+            DbId::default(),
+            metadata(content),
+            stream,
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(ClientError::InvalidUpload(UploadError::Interrupted))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_receive_avatar_too_large() {
+        let mock_db = MockDatabaseOperations::new();
+        let mock_bucket = MockBucketS3Trait::new();
+
+        let content = b"hello world";
+        let mut declared = metadata(content);
+        declared.size = AVATAR_MAX_SIZE as u64 + 1;
+
+        let result = receive_avatar(
+            &mock_db,
+            &mock_bucket,
+            DbId::default(),
+            declared,
+            chunks(content, 4),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(ClientError::InvalidUpload(UploadError::TooLarge(_)))
+        ));
+    }
+}
@@ -0,0 +1,812 @@
+// utils/user_import.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{collections::HashSet, str::FromStr};
+
+use argon2::PasswordHash;
+use kiro_api::client::v1::{ImportRowError, ImportUsersResponse};
+use kiro_database::{
+    db_bridge::{DatabaseOperations, QueryOptions},
+    db_query::Condition,
+    get_env_or, DatabaseError,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::ClientError,
+    models::{SessionModel, UserModel},
+    utils::{
+        legacy_hash::{LegacyHash, LegacyHashError},
+        password::random_password,
+        profile::{valid_display_name, valid_locale},
+    },
+};
+
+/// # ImportError
+///
+/// The ImportError enum is an enum that represents the errors for bulk user imports.
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum ImportError {
+    #[error("Unknown format {0}, expected csv or jsonl")]
+    UnknownFormat(String),
+
+    #[error("The CSV header must contain an email column")]
+    MissingHeader,
+
+    #[error("Invalid record: {0}")]
+    InvalidRecord(String),
+
+    #[error("{0} is not an email address")]
+    InvalidEmail(String),
+
+    #[error("Email address listed more than once")]
+    DuplicateEmail,
+
+    #[error("Password hash must be an Argon2, bcrypt or PBKDF2 hash")]
+    UnsupportedHash,
+
+    #[error("Invalid password hash: {0}")]
+    InvalidHash(LegacyHashError),
+
+    #[error("Import interrupted")]
+    Interrupted,
+}
+
+impl ImportError {
+    /// Returns the stable key of the error, used to localize its message
+    pub fn key(&self) -> &'static str {
+        match self {
+            ImportError::UnknownFormat(_) => "import.unknown_format",
+            ImportError::MissingHeader => "import.missing_header",
+            ImportError::InvalidRecord(_) => "import.invalid_record",
+            ImportError::InvalidEmail(_) => "import.invalid_email",
+            ImportError::DuplicateEmail => "import.duplicate_email",
+            ImportError::UnsupportedHash => "import.unsupported_hash",
+            ImportError::InvalidHash(_) => "import.invalid_hash",
+            ImportError::Interrupted => "import.interrupted",
+        }
+    }
+}
+
+/// Number of users exported per page
+const EXPORT_PAGE_SIZE: usize = 500;
+
+/// Columns of the CSV files, in the order of `UserRecord`
+const CSV_COLUMNS: [&str; 6] = [
+    "email",
+    "password_hash",
+    "display_name",
+    "locale",
+    "activated",
+    "is_admin",
+];
+
+/// # Import batch size
+///
+/// The `import_batch_size` method returns the number of users inserted at once.
+/// Defaults to 500.
+pub fn import_batch_size() -> usize {
+    get_env_or("IMPORT_BATCH_SIZE", "500")
+        .parse()
+        .ok()
+        .filter(|size| *size > 0)
+        .unwrap_or(500)
+}
+
+/// # Transfer format
+///
+/// The formats of the import and export files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferFormat {
+    /// Comma separated values, with a header line
+    Csv,
+    /// One JSON object per line
+    Jsonl,
+}
+
+impl TransferFormat {
+    /// Name of the format, as accepted by `from_str`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Jsonl => "jsonl",
+        }
+    }
+}
+
+impl FromStr for TransferFormat {
+    type Err = ImportError;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format.trim().to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "jsonl" | "ndjson" => Ok(Self::Jsonl),
+            _ => Err(ImportError::UnknownFormat(format.to_string())),
+        }
+    }
+}
+
+/// # User record
+///
+/// A user of an import or export file. Only the email is required on import,
+/// users without a password hash get a random password and have to reset it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UserRecord {
+    pub email: String,
+    #[serde(default)]
+    pub password_hash: Option<String>,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub locale: Option<String>,
+    #[serde(default)]
+    pub activated: Option<bool>,
+    #[serde(default)]
+    pub is_admin: Option<bool>,
+}
+
+impl From<UserModel> for UserRecord {
+    fn from(user: UserModel) -> Self {
+        Self {
+            email: user.email,
+            password_hash: Some(user.password_hash),
+            display_name: user.display_name,
+            locale: user.locale,
+            activated: Some(user.activated),
+            is_admin: Some(user.is_admin),
+        }
+    }
+}
+
+/// User inserted by an import
+#[derive(Debug, Clone, Serialize)]
+struct ImportUserModel {
+    email: String,
+    password_hash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    locale: Option<String>,
+    activated: bool,
+    is_admin: bool,
+}
+
+/// Email of an existing or inserted user
+#[derive(Debug, Clone, Deserialize)]
+struct EmailRow {
+    email: String,
+}
+
+/// # Import report
+///
+/// The number of imported users, and the rows that were rejected with their
+/// line number in the file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ImportReport {
+    pub imported: u64,
+    pub errors: Vec<ImportRowReport>,
+}
+
+/// # Import row report
+///
+/// A rejected row of an import file.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ImportRowReport {
+    pub line: u64,
+    pub email: Option<String>,
+    pub message: String,
+}
+
+impl From<ImportReport> for ImportUsersResponse {
+    fn from(report: ImportReport) -> Self {
+        Self {
+            imported: report.imported,
+            failed: report.errors.len() as u64,
+            errors: report
+                .errors
+                .into_iter()
+                .map(|error| ImportRowError {
+                    line: error.line,
+                    email: error.email.unwrap_or_default(),
+                    message: error.message,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// # User importer
+///
+/// The UserImporter struct reads an import file fed in chunks of any size, and
+/// inserts its users in batches. Rows that are invalid, or whose email is
+/// already in use, are reported and skipped; the other rows are imported.
+pub struct UserImporter<'a, DB> {
+    db: &'a DB,
+    format: TransferFormat,
+    batch_size: usize,
+    buffer: Vec<u8>,
+    line: u64,
+    header: Option<csv::StringRecord>,
+    batch: Vec<(u64, ImportUserModel)>,
+    seen: HashSet<String>,
+    report: ImportReport,
+}
+
+impl<'a, DB: DatabaseOperations + Send + Sync> UserImporter<'a, DB> {
+    /// # New
+    ///
+    /// The `new` method creates an importer inserting `batch_size` users at once.
+    pub fn new(db: &'a DB, format: TransferFormat, batch_size: usize) -> Self {
+        Self {
+            db,
+            format,
+            batch_size: batch_size.max(1),
+            buffer: Vec::new(),
+            line: 0,
+            header: None,
+            batch: Vec::new(),
+            seen: HashSet::new(),
+            report: ImportReport::default(),
+        }
+    }
+
+    /// # Feed
+    ///
+    /// The `feed` method reads the complete lines of a chunk, the last partial
+    /// line is kept for the next chunk.
+    pub async fn feed(&mut self, chunk: &[u8]) -> Result<(), ClientError> {
+        self.buffer.extend_from_slice(chunk);
+
+        while let Some(end) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            self.read_line(&line[..end]).await?;
+        }
+
+        Ok(())
+    }
+
+    /// # Finish
+    ///
+    /// The `finish` method reads the last line, inserts the last batch and
+    /// returns the report of the import.
+    pub async fn finish(mut self) -> Result<ImportReport, ClientError> {
+        let line = std::mem::take(&mut self.buffer);
+        self.read_line(&line).await?;
+        self.flush().await?;
+
+        Ok(self.report)
+    }
+
+    /// Reads a line of the file, blank lines are skipped
+    async fn read_line(&mut self, line: &[u8]) -> Result<(), ClientError> {
+        self.line += 1;
+
+        let line = String::from_utf8_lossy(line);
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() {
+            return Ok(());
+        }
+
+        if self.format == TransferFormat::Csv && self.header.is_none() {
+            let header = parse_csv_line(line)?;
+            if !header.iter().any(|column| column.trim() == "email") {
+                return Err(ImportError::MissingHeader.into());
+            }
+            self.header = Some(header.iter().map(str::trim).collect());
+            return Ok(());
+        }
+
+        let record = match self.format {
+            TransferFormat::Csv => parse_csv_line(line).and_then(|record| {
+                record
+                    .deserialize::<UserRecord>(self.header.as_ref())
+                    .map_err(|e| ImportError::InvalidRecord(e.to_string()))
+            }),
+            TransferFormat::Jsonl => serde_json::from_str::<UserRecord>(line)
+                .map_err(|e| ImportError::InvalidRecord(e.to_string())),
+        };
+
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                self.reject(self.line, None, e.to_string());
+                return Ok(());
+            }
+        };
+
+        let email = record.email.trim().to_string();
+        match self.validate(email.clone(), record).await {
+            Ok(user) => {
+                self.batch.push((self.line, user));
+                if self.batch.len() >= self.batch_size {
+                    self.flush().await?;
+                }
+            }
+            Err(message) => self.reject(self.line, Some(email), message),
+        }
+
+        Ok(())
+    }
+
+    /// Validates a record, users without a password hash get a random password
+    async fn validate(
+        &mut self, email: String, record: UserRecord,
+    ) -> Result<ImportUserModel, String> {
+        valid_email(&email).map_err(|e| e.to_string())?;
+
+        if let Some(display_name) = &record.display_name {
+            valid_display_name(display_name).map_err(|e| e.to_string())?;
+        }
+        if let Some(locale) = &record.locale {
+            valid_locale(locale).map_err(|e| e.to_string())?;
+        }
+
+        let password_hash = match record.password_hash.filter(|hash| !hash.is_empty()) {
+            Some(hash) => {
+                valid_hash(&hash).map_err(|e| e.to_string())?;
+                hash
+            }
+            None => SessionModel::create_password_hash(random_password())
+                .await
+                .map_err(|e| e.to_string())?,
+        };
+
+        if !self.seen.insert(email.to_lowercase()) {
+            return Err(ImportError::DuplicateEmail.to_string());
+        }
+
+        Ok(ImportUserModel {
+            email,
+            password_hash,
+            display_name: record.display_name,
+            locale: record.locale,
+            activated: record.activated.unwrap_or(true),
+            is_admin: record.is_admin.unwrap_or(false),
+        })
+    }
+
+    /// Inserts the batch, skipping the users whose email is already in use
+    async fn flush(&mut self) -> Result<(), ClientError> {
+        if self.batch.is_empty() {
+            return Ok(());
+        }
+
+        let batch = std::mem::take(&mut self.batch);
+        let emails: Vec<&str> = batch.iter().map(|(_, user)| user.email.as_str()).collect();
+
+        let mut taken = self
+            .db
+            .read_where::<EmailRow>("users", Condition::inside("email", emails.clone()), None)
+            .await?;
+        taken.extend(
+            self.db
+                .read_where::<EmailRow>(
+                    "user_emails",
+                    Condition::And(vec![
+                        Condition::eq("verified", true),
+                        Condition::inside("email", emails),
+                    ]),
+                    None,
+                )
+                .await?,
+        );
+        let taken: Vec<String> = taken.into_iter().map(|row| row.email).collect();
+
+        let (taken, batch): (Vec<_>, Vec<_>) = batch
+            .into_iter()
+            .partition(|(_, user)| taken.contains(&user.email));
+
+        for (line, user) in taken {
+            self.reject(line, Some(user.email), ClientError::EmailTaken.to_string());
+        }
+
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        let users: Vec<ImportUserModel> = batch.iter().map(|(_, user)| user.clone()).collect();
+        match self
+            .db
+            .insert::<ImportUserModel, EmailRow>("users", users)
+            .await
+        {
+            Ok(inserted) => self.report.imported += inserted.len() as u64,
+            Err(e) => {
+                // The batch is inserted in one statement, none of its users were created
+                for (line, user) in batch {
+                    self.reject(line, Some(user.email), e.to_string());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds a rejected row to the report
+    fn reject(&mut self, line: u64, email: Option<String>, message: String) {
+        self.report.errors.push(ImportRowReport {
+            line,
+            email,
+            message,
+        });
+    }
+}
+
+/// Parses a line of a CSV file
+fn parse_csv_line(line: &str) -> Result<csv::StringRecord, ImportError> {
+    csv::ReaderBuilder::new()
+        .has_headers(false)
+        .from_reader(line.as_bytes())
+        .records()
+        .next()
+        .unwrap_or_else(|| Ok(csv::StringRecord::new()))
+        .map_err(|e| ImportError::InvalidRecord(e.to_string()))
+}
+
+/// Checks the syntax of an email address
+fn valid_email(email: &str) -> Result<(), ImportError> {
+    match email.split_once('@') {
+        Some((local, domain)) if !local.is_empty() && domain.contains('.') => Ok(()),
+        _ => Err(ImportError::InvalidEmail(email.to_string())),
+    }
+}
+
+/// Checks that a password hash can be verified on login, legacy hashes are
+/// parsed so that malformed or unbounded ones are reported on their row
+fn valid_hash(hash: &str) -> Result<(), ImportError> {
+    if let Some(scheme) = LegacyHash::detect(hash) {
+        scheme.validate(hash).map_err(ImportError::InvalidHash)
+    } else if hash.starts_with("$argon2") && PasswordHash::new(hash).is_ok() {
+        Ok(())
+    } else {
+        Err(ImportError::UnsupportedHash)
+    }
+}
+
+/// # User exporter
+///
+/// The UserExporter struct reads the users page by page, ordered by creation
+/// date, and writes them in the export format. Password hashes are only
+/// exported when requested, so the users can keep their password when
+/// imported elsewhere; otherwise the column is left empty.
+pub struct UserExporter<'a, DB> {
+    db: &'a DB,
+    format: TransferFormat,
+    include_password_hashes: bool,
+    start: usize,
+    done: bool,
+}
+
+impl<'a, DB: DatabaseOperations + Send + Sync> UserExporter<'a, DB> {
+    /// # New
+    ///
+    /// The `new` method creates an exporter starting at the first user,
+    /// exporting the password hashes if `include_password_hashes` is set.
+    pub fn new(db: &'a DB, format: TransferFormat, include_password_hashes: bool) -> Self {
+        Self {
+            db,
+            format,
+            include_password_hashes,
+            start: 0,
+            done: false,
+        }
+    }
+
+    /// # Next chunk
+    ///
+    /// The `next_chunk` method returns the next page of users in the export
+    /// format, `None` once all users were exported. The CSV header is written
+    /// in the first chunk.
+    pub async fn next_chunk(&mut self) -> Result<Option<Vec<u8>>, ClientError> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let users = self
+            .db
+            .read_all::<UserModel>(
+                "users",
+                Some(QueryOptions {
+                    order_by: Some("created_at".to_string()),
+                    order_direction: None,
+                    limit: Some(EXPORT_PAGE_SIZE),
+                    start: Some(self.start),
+                }),
+            )
+            .await?;
+
+        let first = self.start == 0;
+        self.start += users.len();
+        self.done = users.len() < EXPORT_PAGE_SIZE;
+
+        // Only the CSV header is left to write when there are no users
+        if users.is_empty() && !(first && self.format == TransferFormat::Csv) {
+            return Ok(None);
+        }
+
+        let include_password_hashes = self.include_password_hashes;
+        let records = users
+            .into_iter()
+            .map(UserRecord::from)
+            .map(|record| UserRecord {
+                password_hash: record.password_hash.filter(|_| include_password_hashes),
+                ..record
+            });
+        let chunk = match self.format {
+            TransferFormat::Csv => write_csv(records, first)?,
+            TransferFormat::Jsonl => {
+                let mut chunk = Vec::new();
+                for record in records {
+                    serde_json::to_writer(&mut chunk, &record).map_err(internal)?;
+                    chunk.push(b'\n');
+                }
+                chunk
+            }
+        };
+
+        Ok(Some(chunk))
+    }
+}
+
+/// Writes records as CSV, with the header line if requested
+fn write_csv(
+    records: impl Iterator<Item = UserRecord>, header: bool,
+) -> Result<Vec<u8>, ClientError> {
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(Vec::new());
+
+    if header {
+        writer.write_record(CSV_COLUMNS).map_err(internal)?;
+    }
+    for record in records {
+        writer.serialize(record).map_err(internal)?;
+    }
+
+    writer.into_inner().map_err(internal)
+}
+
+/// Maps a serialization error of the export
+fn internal(error: impl std::fmt::Display) -> ClientError {
+    ClientError::Database(DatabaseError::Internal(error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kiro_database::db_bridge::MockDatabaseOperations;
+
+    const BCRYPT_HASH: &str = "$2b$04$hKAYyBzqwvMM/UosqRbqW.mmJqpw7TEvULNHVWmIWcZLNKEz.Npqm";
+
+    fn expect_taken(mock_db: &mut MockDatabaseOperations, taken: Vec<String>) {
+        mock_db
+            .expect_read_where::<EmailRow>()
+            .withf(|table, _, _| table == "users")
+            .returning(move |_, _, _| {
+                Ok(taken
+                    .iter()
+                    .map(|email| EmailRow {
+                        email: email.clone(),
+                    })
+                    .collect())
+            });
+        mock_db
+            .expect_read_where::<EmailRow>()
+            .withf(|table, _, _| table == "user_emails")
+            .returning(|_, _, _| Ok(vec![]));
+    }
+
+    fn expect_insert(mock_db: &mut MockDatabaseOperations, times: usize) {
+        mock_db
+            .expect_insert::<ImportUserModel, EmailRow>()
+            .withf(|table, _| table == "users")
+            .times(times)
+            .returning(|_, users| {
+                Ok(users
+                    .into_iter()
+                    .map(|user| EmailRow { email: user.email })
+                    .collect())
+            });
+    }
+
+    #[test]
+    fn test_transfer_format_from_str() {
+        assert_eq!("CSV".parse::<TransferFormat>(), Ok(TransferFormat::Csv));
+        assert_eq!(
+            "ndjson".parse::<TransferFormat>(),
+            Ok(TransferFormat::Jsonl)
+        );
+        assert_eq!(
+            "xml".parse::<TransferFormat>(),
+            Err(ImportError::UnknownFormat("xml".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_valid_hash() {
+        assert!(valid_hash(BCRYPT_HASH).is_ok());
+        assert!(valid_hash(&UserModel::default().password_hash).is_ok());
+        assert!(valid_hash(
+            "pbkdf2_sha256$1000$seasalt$gsa5NFZxPLjumoeVIZmIYcaAzf9vYZB6QwpmWPzeaxc="
+        )
+        .is_ok());
+        assert_eq!(
+            valid_hash("pbkdf2_sha256$1000$seasalt$key"),
+            Err(ImportError::InvalidHash(LegacyHashError::Malformed(
+                "PBKDF2"
+            )))
+        );
+        assert_eq!(
+            valid_hash(
+                "pbkdf2_sha256$999999999$seasalt$gsa5NFZxPLjumoeVIZmIYcaAzf9vYZB6QwpmWPzeaxc="
+            ),
+            Err(ImportError::InvalidHash(LegacyHashError::Iterations))
+        );
+        assert_eq!(
+            valid_hash("5f4dcc3b5aa765d61d8327deb882cf99"),
+            Err(ImportError::UnsupportedHash)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_csv_in_chunks() {
+        let mut mock_db = MockDatabaseOperations::new();
+        expect_taken(&mut mock_db, vec![]);
+        expect_insert(&mut mock_db, 2);
+
+        let file = format!(
+            "email,password_hash,display_name\r\njane@example.com,{},Jane\r\n\r\njohn@example.com,,\r\nmax@example.com,,Max",
+            BCRYPT_HASH
+        );
+
+        let mut importer = UserImporter::new(&mock_db, TransferFormat::Csv, 2);
+        for chunk in file.as_bytes().chunks(7) {
+            importer.feed(chunk).await.unwrap();
+        }
+        let report = importer.finish().await.unwrap();
+
+        assert_eq!(report.imported, 3);
+        assert!(report.errors.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_import_reports_invalid_rows() {
+        let mut mock_db = MockDatabaseOperations::new();
+        expect_taken(&mut mock_db, vec!["taken@example.com".to_string()]);
+        expect_insert(&mut mock_db, 1);
+
+        let file = [
+            r#"{"email": "jane@example.com", "locale": "fr-FR"}"#,
+            r#"{"email": "not-an-email"}"#,
+            r#"{"email": "jane@example.com"}"#,
+            r#"{"email": "md5@example.com", "password_hash": "5f4dcc3b5aa765d61d8327deb882cf99"}"#,
+            r#"{"email": "pbkdf2@example.com", "password_hash": "pbkdf2_sha256$999999999$salt$gsa5NFZxPLjumoeVIZmIYcaAzf9vYZB6QwpmWPzeaxc="}"#,
+            r#"{"display_name": "No email"}"#,
+            r#"{"email": "taken@example.com"}"#,
+        ]
+        .join("\n");
+
+        let mut importer = UserImporter::new(&mock_db, TransferFormat::Jsonl, 500);
+        importer.feed(file.as_bytes()).await.unwrap();
+        let report = importer.finish().await.unwrap();
+
+        assert_eq!(report.imported, 1);
+        assert_eq!(
+            report
+                .errors
+                .iter()
+                .map(|error| (error.line, error.email.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![
+                (2, Some("not-an-email")),
+                (3, Some("jane@example.com")),
+                (4, Some("md5@example.com")),
+                (5, Some("pbkdf2@example.com")),
+                (6, None),
+                (7, Some("taken@example.com")),
+            ]
+        );
+        assert_eq!(
+            report.errors[1].message,
+            ImportError::DuplicateEmail.to_string()
+        );
+        assert_eq!(
+            report.errors[3].message,
+            ImportError::InvalidHash(LegacyHashError::Iterations).to_string()
+        );
+        assert_eq!(
+            report.errors[5].message,
+            ClientError::EmailTaken.to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_csv_without_email_column() {
+        let mock_db = MockDatabaseOperations::new();
+
+        let mut importer = UserImporter::new(&mock_db, TransferFormat::Csv, 500);
+        let result = importer.feed(b"name,password\n").await;
+
+        assert!(matches!(
+            result,
+            Err(ClientError::Import(ImportError::MissingHeader))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_export_csv() {
+        let mut mock_db = MockDatabaseOperations::new();
+        mock_db
+            .expect_read_all::<UserModel>()
+            .withf(|table, options| table == "users" && options.as_ref().unwrap().start == Some(0))
+            .times(1)
+            .returning(|_, _| Ok(vec![UserModel::default()]));
+
+        let mut exporter = UserExporter::new(&mock_db, TransferFormat::Csv, true);
+        let chunk = String::from_utf8(exporter.next_chunk().await.unwrap().unwrap()).unwrap();
+        let mut lines = chunk.lines();
+
+        assert_eq!(
+            lines.next(),
+            Some("email,password_hash,display_name,locale,activated,is_admin")
+        );
+        assert!(lines
+            .next()
+            .unwrap()
+            .starts_with("test@example.com,$argon2"));
+        assert_eq!(exporter.next_chunk().await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_export_jsonl_round_trip() {
+        let mut mock_db = MockDatabaseOperations::new();
+        mock_db
+            .expect_read_all::<UserModel>()
+            .times(1)
+            .returning(|_, _| Ok(vec![UserModel::default()]));
+
+        let mut exporter = UserExporter::new(&mock_db, TransferFormat::Jsonl, true);
+        let chunk = exporter.next_chunk().await.unwrap().unwrap();
+        let record: UserRecord = serde_json::from_slice(&chunk).unwrap();
+
+        assert_eq!(record.email, "test@example.com");
+        assert_eq!(record.activated, Some(true));
+        assert!(valid_hash(&record.password_hash.unwrap()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_export_without_password_hashes() {
+        let mut mock_db = MockDatabaseOperations::new();
+        mock_db
+            .expect_read_all::<UserModel>()
+            .times(2)
+            .returning(|_, _| Ok(vec![UserModel::default()]));
+
+        // The column is kept but left empty
+        let mut exporter = UserExporter::new(&mock_db, TransferFormat::Csv, false);
+        let chunk = String::from_utf8(exporter.next_chunk().await.unwrap().unwrap()).unwrap();
+        assert!(chunk
+            .lines()
+            .nth(1)
+            .unwrap()
+            .starts_with("test@example.com,,"));
+
+        let mut exporter = UserExporter::new(&mock_db, TransferFormat::Jsonl, false);
+        let chunk = exporter.next_chunk().await.unwrap().unwrap();
+        let record: UserRecord = serde_json::from_slice(&chunk).unwrap();
+        assert_eq!(record.password_hash, None);
+    }
+}
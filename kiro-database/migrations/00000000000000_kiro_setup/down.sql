@@ -0,0 +1,6 @@
+DROP FUNCTION kiro_notify();
+DROP FUNCTION kiro_defaults();
+DROP FUNCTION kiro_thing(jsonb);
+DROP FUNCTION kiro_document(text, text, jsonb);
+DROP FUNCTION kiro_now();
+DROP FUNCTION kiro_record_id();
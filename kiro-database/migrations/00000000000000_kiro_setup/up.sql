@@ -0,0 +1,56 @@
+-- Records are stored as JSONB documents keyed by the id of their `DbId`, the
+-- same documents the models send to SurrealDB. Record links are stored as
+-- serialized `DbId`s and compared through `kiro_thing`.
+
+-- Random record key, like the ones generated by SurrealDB
+CREATE FUNCTION kiro_record_id() RETURNS text AS $$
+    SELECT string_agg(substr('abcdefghijklmnopqrstuvwxyz0123456789', (random() * 35)::int + 1, 1), '')
+    FROM generate_series(1, 20);
+$$ LANGUAGE sql VOLATILE;
+
+-- Current time, in the RFC 3339 format of `DbDateTime`
+CREATE FUNCTION kiro_now() RETURNS jsonb AS $$
+    SELECT to_jsonb(to_char(now() AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS.US"Z"'));
+$$ LANGUAGE sql STABLE;
+
+-- Record as read by the models, with its `DbId`
+CREATE FUNCTION kiro_document(tb text, id text, data jsonb) RETURNS jsonb AS $$
+    SELECT data || jsonb_build_object('id', jsonb_build_object('tb', tb, 'id', id));
+$$ LANGUAGE sql IMMUTABLE;
+
+-- `table:key` form of a record link, whatever its serialization
+CREATE FUNCTION kiro_thing(value jsonb) RETURNS text AS $$
+    SELECT CASE jsonb_typeof(value)
+        WHEN 'string' THEN value #>> '{}'
+        WHEN 'object' THEN (value ->> 'tb') || ':' || COALESCE(value -> 'id' ->> 'String', value ->> 'id')
+    END;
+$$ LANGUAGE sql IMMUTABLE;
+
+-- Fills the fields missing from a new record, the first argument holds the
+-- constant defaults and the next ones the fields defaulting to the current time
+CREATE FUNCTION kiro_defaults() RETURNS trigger AS $$
+DECLARE
+    field text;
+BEGIN
+    NEW.data := TG_ARGV[0]::jsonb || NEW.data;
+    FOR i IN 1 .. TG_NARGS - 1 LOOP
+        field := TG_ARGV[i];
+        IF NOT NEW.data ? field THEN
+            NEW.data := NEW.data || jsonb_build_object(field, kiro_now());
+        END IF;
+    END LOOP;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Notifies the live queries of the changes of a table
+CREATE FUNCTION kiro_notify() RETURNS trigger AS $$
+BEGIN
+    PERFORM pg_notify('kiro_changes', json_build_object(
+        'table', TG_TABLE_NAME,
+        'id', CASE TG_OP WHEN 'DELETE' THEN OLD.id ELSE NEW.id END,
+        'action', TG_OP
+    )::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,12 @@
+-- Account events table
+CREATE TABLE account_events (
+    id text PRIMARY KEY DEFAULT kiro_record_id(),
+    data jsonb NOT NULL CHECK (jsonb_typeof(data) = 'object')
+);
+
+CREATE TRIGGER account_events_defaults BEFORE INSERT ON account_events
+    FOR EACH ROW EXECUTE FUNCTION kiro_defaults('{}', 'created_at');
+CREATE TRIGGER account_events_notify AFTER INSERT OR UPDATE OR DELETE ON account_events
+    FOR EACH ROW EXECUTE FUNCTION kiro_notify();
+
+CREATE INDEX account_events_user ON account_events (kiro_thing(data -> 'user'));
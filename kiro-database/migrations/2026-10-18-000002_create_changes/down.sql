@@ -0,0 +1,3 @@
+DROP FUNCTION kiro_change_log();
+DROP TABLE change_versions;
+DROP TABLE changes;
@@ -0,0 +1,64 @@
+-- Change log table, written by the change_log triggers on users and groups
+CREATE TABLE changes (
+    id text PRIMARY KEY DEFAULT kiro_record_id(),
+    data jsonb NOT NULL CHECK (jsonb_typeof(data) = 'object')
+);
+
+CREATE TRIGGER changes_defaults BEFORE INSERT ON changes
+    FOR EACH ROW EXECUTE FUNCTION kiro_defaults('{"deleted": false}', 'created_at');
+CREATE TRIGGER changes_notify AFTER INSERT OR UPDATE OR DELETE ON changes
+    FOR EACH ROW EXECUTE FUNCTION kiro_notify();
+
+ALTER TABLE changes ADD CONSTRAINT changes_kind
+    CHECK (data ->> 'kind' IN ('User', 'Settings', 'Group'));
+CREATE UNIQUE INDEX changes_version ON changes (((data ->> 'version')::bigint));
+CREATE INDEX changes_user ON changes (kiro_thing(data -> 'user'));
+CREATE INDEX changes_record ON changes (kiro_thing(data -> 'record'));
+
+-- Last version written, and last version removed by the compaction
+CREATE TABLE change_versions (
+    id text PRIMARY KEY DEFAULT kiro_record_id(),
+    data jsonb NOT NULL CHECK (jsonb_typeof(data) = 'object')
+);
+
+CREATE TRIGGER change_versions_defaults BEFORE INSERT ON change_versions
+    FOR EACH ROW EXECUTE FUNCTION kiro_defaults('{"version": 0, "compacted": 0}');
+CREATE TRIGGER change_versions_notify AFTER INSERT OR UPDATE OR DELETE ON change_versions
+    FOR EACH ROW EXECUTE FUNCTION kiro_notify();
+
+-- Change log, the version is incremented in the transaction of the mutation so
+-- versions are strictly increasing in commit order
+CREATE FUNCTION kiro_change_log() RETURNS trigger AS $$
+DECLARE
+    version bigint;
+    record_id text;
+    kind text;
+BEGIN
+    record_id := CASE TG_OP WHEN 'DELETE' THEN OLD.id ELSE NEW.id END;
+    kind := TG_ARGV[0];
+
+    INSERT INTO change_versions (id, data) VALUES ('current', '{"version": 1}')
+    ON CONFLICT (id) DO UPDATE SET data = jsonb_set(
+        change_versions.data,
+        '{version}',
+        to_jsonb(COALESCE((change_versions.data ->> 'version')::bigint, 0) + 1)
+    )
+    RETURNING (data ->> 'version')::bigint INTO version;
+
+    IF TG_OP = 'UPDATE' AND kind = 'User'
+        AND OLD.data -> 'settings' IS DISTINCT FROM NEW.data -> 'settings' THEN
+        kind := 'Settings';
+    END IF;
+
+    INSERT INTO changes (data) VALUES (jsonb_strip_nulls(jsonb_build_object(
+        'version', version,
+        'kind', kind,
+        'record', jsonb_build_object('tb', TG_TABLE_NAME, 'id', record_id),
+        'user', CASE WHEN TG_TABLE_NAME = 'users'
+            THEN jsonb_build_object('tb', 'users', 'id', record_id) END,
+        'deleted', TG_OP = 'DELETE'
+    )));
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
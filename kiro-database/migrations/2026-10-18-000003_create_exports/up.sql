@@ -0,0 +1,12 @@
+-- Exports table
+CREATE TABLE exports (
+    id text PRIMARY KEY DEFAULT kiro_record_id(),
+    data jsonb NOT NULL CHECK (jsonb_typeof(data) = 'object')
+);
+
+CREATE TRIGGER exports_defaults BEFORE INSERT ON exports
+    FOR EACH ROW EXECUTE FUNCTION kiro_defaults('{}', 'created_at');
+CREATE TRIGGER exports_notify AFTER INSERT OR UPDATE OR DELETE ON exports
+    FOR EACH ROW EXECUTE FUNCTION kiro_notify();
+
+CREATE INDEX exports_user ON exports (kiro_thing(data -> 'user'));
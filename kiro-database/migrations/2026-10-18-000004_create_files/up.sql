@@ -0,0 +1,12 @@
+-- Files table
+CREATE TABLE files (
+    id text PRIMARY KEY DEFAULT kiro_record_id(),
+    data jsonb NOT NULL CHECK (jsonb_typeof(data) = 'object')
+);
+
+CREATE TRIGGER files_defaults BEFORE INSERT ON files
+    FOR EACH ROW EXECUTE FUNCTION kiro_defaults('{}', 'created_at');
+CREATE TRIGGER files_notify AFTER INSERT OR UPDATE OR DELETE ON files
+    FOR EACH ROW EXECUTE FUNCTION kiro_notify();
+
+CREATE INDEX files_user ON files (kiro_thing(data -> 'user'));
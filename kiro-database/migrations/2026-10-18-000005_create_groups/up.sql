@@ -0,0 +1,15 @@
+-- Groups table
+CREATE TABLE groups (
+    id text PRIMARY KEY DEFAULT kiro_record_id(),
+    data jsonb NOT NULL CHECK (jsonb_typeof(data) = 'object')
+);
+
+CREATE TRIGGER groups_defaults BEFORE INSERT ON groups
+    FOR EACH ROW EXECUTE FUNCTION kiro_defaults('{}', 'created_at', 'updated_at');
+CREATE TRIGGER groups_notify AFTER INSERT OR UPDATE OR DELETE ON groups
+    FOR EACH ROW EXECUTE FUNCTION kiro_notify();
+
+CREATE UNIQUE INDEX groups_name ON groups ((data ->> 'name'));
+
+CREATE TRIGGER groups_change_log AFTER INSERT OR UPDATE OR DELETE ON groups
+    FOR EACH ROW EXECUTE FUNCTION kiro_change_log('Group');
@@ -0,0 +1,12 @@
+-- Invitation codes of the invite-only registration
+CREATE TABLE invitations (
+    id text PRIMARY KEY DEFAULT kiro_record_id(),
+    data jsonb NOT NULL CHECK (jsonb_typeof(data) = 'object')
+);
+
+CREATE TRIGGER invitations_defaults BEFORE INSERT ON invitations
+    FOR EACH ROW EXECUTE FUNCTION kiro_defaults('{"is_admin": false}', 'created_at');
+CREATE TRIGGER invitations_notify AFTER INSERT OR UPDATE OR DELETE ON invitations
+    FOR EACH ROW EXECUTE FUNCTION kiro_notify();
+
+CREATE UNIQUE INDEX invitations_code ON invitations ((data ->> 'code'));
@@ -0,0 +1,12 @@
+-- Links table
+CREATE TABLE links (
+    id text PRIMARY KEY DEFAULT kiro_record_id(),
+    data jsonb NOT NULL CHECK (jsonb_typeof(data) = 'object')
+);
+
+CREATE TRIGGER links_defaults BEFORE INSERT ON links
+    FOR EACH ROW EXECUTE FUNCTION kiro_defaults('{}');
+CREATE TRIGGER links_notify AFTER INSERT OR UPDATE OR DELETE ON links
+    FOR EACH ROW EXECUTE FUNCTION kiro_notify();
+
+CREATE INDEX links_expiry ON links ((data ->> 'expiry'));
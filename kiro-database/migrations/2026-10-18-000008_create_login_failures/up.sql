@@ -0,0 +1,14 @@
+-- Failed logins, counted to require a CAPTCHA
+CREATE TABLE login_failures (
+    id text PRIMARY KEY DEFAULT kiro_record_id(),
+    data jsonb NOT NULL CHECK (jsonb_typeof(data) = 'object')
+);
+
+CREATE TRIGGER login_failures_defaults BEFORE INSERT ON login_failures
+    FOR EACH ROW EXECUTE FUNCTION kiro_defaults('{}', 'created_at');
+CREATE TRIGGER login_failures_notify AFTER INSERT OR UPDATE OR DELETE ON login_failures
+    FOR EACH ROW EXECUTE FUNCTION kiro_notify();
+
+CREATE INDEX login_failures_email ON login_failures ((data ->> 'email'));
+CREATE INDEX login_failures_ip_address ON login_failures ((data ->> 'ip_address'));
+CREATE INDEX login_failures_created_at ON login_failures ((data ->> 'created_at'));
@@ -0,0 +1,37 @@
+-- OAuth clients table
+CREATE TABLE oauth_clients (
+    id text PRIMARY KEY DEFAULT kiro_record_id(),
+    data jsonb NOT NULL CHECK (jsonb_typeof(data) = 'object')
+);
+
+CREATE TRIGGER oauth_clients_defaults BEFORE INSERT ON oauth_clients
+    FOR EACH ROW EXECUTE FUNCTION kiro_defaults('{}', 'created_at');
+CREATE TRIGGER oauth_clients_notify AFTER INSERT OR UPDATE OR DELETE ON oauth_clients
+    FOR EACH ROW EXECUTE FUNCTION kiro_notify();
+
+-- OAuth authorization codes table
+CREATE TABLE oauth_codes (
+    id text PRIMARY KEY DEFAULT kiro_record_id(),
+    data jsonb NOT NULL CHECK (jsonb_typeof(data) = 'object')
+);
+
+CREATE TRIGGER oauth_codes_defaults BEFORE INSERT ON oauth_codes
+    FOR EACH ROW EXECUTE FUNCTION kiro_defaults('{}');
+CREATE TRIGGER oauth_codes_notify AFTER INSERT OR UPDATE OR DELETE ON oauth_codes
+    FOR EACH ROW EXECUTE FUNCTION kiro_notify();
+
+CREATE UNIQUE INDEX oauth_codes_code ON oauth_codes ((data ->> 'code'));
+
+-- OAuth consents table
+CREATE TABLE oauth_consents (
+    id text PRIMARY KEY DEFAULT kiro_record_id(),
+    data jsonb NOT NULL CHECK (jsonb_typeof(data) = 'object')
+);
+
+CREATE TRIGGER oauth_consents_defaults BEFORE INSERT ON oauth_consents
+    FOR EACH ROW EXECUTE FUNCTION kiro_defaults('{}', 'created_at', 'updated_at');
+CREATE TRIGGER oauth_consents_notify AFTER INSERT OR UPDATE OR DELETE ON oauth_consents
+    FOR EACH ROW EXECUTE FUNCTION kiro_notify();
+
+CREATE UNIQUE INDEX oauth_consents_user_client
+    ON oauth_consents (kiro_thing(data -> 'user'), kiro_thing(data -> 'client'));
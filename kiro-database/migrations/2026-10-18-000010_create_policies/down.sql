@@ -0,0 +1,2 @@
+DROP TABLE policy_consents;
+DROP TABLE policy_documents;
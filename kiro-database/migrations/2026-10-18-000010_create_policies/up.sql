@@ -0,0 +1,30 @@
+-- Published versions of the terms of service and privacy policy
+CREATE TABLE policy_documents (
+    id text PRIMARY KEY DEFAULT kiro_record_id(),
+    data jsonb NOT NULL CHECK (jsonb_typeof(data) = 'object')
+);
+
+CREATE TRIGGER policy_documents_defaults BEFORE INSERT ON policy_documents
+    FOR EACH ROW EXECUTE FUNCTION kiro_defaults('{}', 'published_at', 'created_at');
+CREATE TRIGGER policy_documents_notify AFTER INSERT OR UPDATE OR DELETE ON policy_documents
+    FOR EACH ROW EXECUTE FUNCTION kiro_notify();
+
+ALTER TABLE policy_documents ADD CONSTRAINT policy_documents_kind
+    CHECK (data ->> 'kind' IN ('Terms', 'Privacy'));
+CREATE UNIQUE INDEX policy_documents_kind_version
+    ON policy_documents ((data ->> 'kind'), ((data ->> 'version')::bigint));
+
+-- Acceptances of a policy document by a user
+CREATE TABLE policy_consents (
+    id text PRIMARY KEY DEFAULT kiro_record_id(),
+    data jsonb NOT NULL CHECK (jsonb_typeof(data) = 'object')
+);
+
+CREATE TRIGGER policy_consents_defaults BEFORE INSERT ON policy_consents
+    FOR EACH ROW EXECUTE FUNCTION kiro_defaults('{}', 'accepted_at');
+CREATE TRIGGER policy_consents_notify AFTER INSERT OR UPDATE OR DELETE ON policy_consents
+    FOR EACH ROW EXECUTE FUNCTION kiro_notify();
+
+CREATE UNIQUE INDEX policy_consents_user_document
+    ON policy_consents (kiro_thing(data -> 'user'), kiro_thing(data -> 'document'));
+CREATE INDEX policy_consents_user ON policy_consents (kiro_thing(data -> 'user'));
@@ -0,0 +1,2 @@
+DROP TABLE sessions;
+DROP FUNCTION kiro_session_expiry();
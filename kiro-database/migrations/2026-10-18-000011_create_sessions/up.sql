@@ -0,0 +1,28 @@
+-- Session table
+CREATE TABLE sessions (
+    id text PRIMARY KEY DEFAULT kiro_record_id(),
+    data jsonb NOT NULL CHECK (jsonb_typeof(data) = 'object')
+);
+
+CREATE TRIGGER sessions_defaults BEFORE INSERT ON sessions
+    FOR EACH ROW EXECUTE FUNCTION kiro_defaults('{}', 'created_at');
+CREATE TRIGGER sessions_notify AFTER INSERT OR UPDATE OR DELETE ON sessions
+    FOR EACH ROW EXECUTE FUNCTION kiro_notify();
+
+CREATE INDEX sessions_expires_at ON sessions ((data ->> 'expires_at'));
+
+-- Sessions expire after 7 days unless stated otherwise
+CREATE FUNCTION kiro_session_expiry() RETURNS trigger AS $$
+BEGIN
+    IF NOT NEW.data ? 'expires_at' THEN
+        NEW.data := NEW.data || jsonb_build_object('expires_at', to_jsonb(to_char(
+            (now() + interval '7 days') AT TIME ZONE 'UTC',
+            'YYYY-MM-DD"T"HH24:MI:SS.US"Z"'
+        )));
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER sessions_expiry BEFORE INSERT ON sessions
+    FOR EACH ROW EXECUTE FUNCTION kiro_session_expiry();
@@ -0,0 +1,25 @@
+-- User table
+CREATE TABLE users (
+    id text PRIMARY KEY DEFAULT kiro_record_id(),
+    data jsonb NOT NULL CHECK (jsonb_typeof(data) = 'object')
+);
+
+CREATE TRIGGER users_defaults BEFORE INSERT ON users
+    FOR EACH ROW EXECUTE FUNCTION kiro_defaults('{"avatar_variants": [], "activated": true, "is_admin": false, "settings_version": 0}', 'created_at', 'updated_at');
+CREATE TRIGGER users_notify AFTER INSERT OR UPDATE OR DELETE ON users
+    FOR EACH ROW EXECUTE FUNCTION kiro_notify();
+
+ALTER TABLE users ADD CONSTRAINT users_email
+    CHECK (data ->> 'email' ~ '^[^@\s]+@[^@\s]+\.[^@\s]+$');
+ALTER TABLE users ADD CONSTRAINT users_pending_email
+    CHECK (data ->> 'pending_email' IS NULL OR data ->> 'pending_email' ~ '^[^@\s]+@[^@\s]+\.[^@\s]+$');
+ALTER TABLE users ADD CONSTRAINT users_handle
+    CHECK (data ->> 'handle' IS NULL OR data ->> 'handle' = lower(data ->> 'handle'));
+
+CREATE UNIQUE INDEX users_customer_id ON users ((data ->> 'customer_id'));
+CREATE UNIQUE INDEX users_email_key ON users ((data ->> 'email'));
+CREATE UNIQUE INDEX users_handle_key ON users ((data ->> 'handle'));
+
+-- Change log
+CREATE TRIGGER users_change_log AFTER INSERT OR UPDATE OR DELETE ON users
+    FOR EACH ROW EXECUTE FUNCTION kiro_change_log('User');
@@ -0,0 +1,14 @@
+-- Email addresses attached to an account, besides its primary one
+CREATE TABLE user_emails (
+    id text PRIMARY KEY DEFAULT kiro_record_id(),
+    data jsonb NOT NULL CHECK (jsonb_typeof(data) = 'object')
+);
+
+CREATE TRIGGER user_emails_defaults BEFORE INSERT ON user_emails
+    FOR EACH ROW EXECUTE FUNCTION kiro_defaults('{"verified": false, "is_primary": false}', 'created_at');
+CREATE TRIGGER user_emails_notify AFTER INSERT OR UPDATE OR DELETE ON user_emails
+    FOR EACH ROW EXECUTE FUNCTION kiro_notify();
+
+CREATE UNIQUE INDEX user_emails_user_email
+    ON user_emails (kiro_thing(data -> 'user'), (data ->> 'email'));
+CREATE INDEX user_emails_email ON user_emails ((data ->> 'email'));
@@ -0,0 +1 @@
+DROP INDEX users_directory_id_key;
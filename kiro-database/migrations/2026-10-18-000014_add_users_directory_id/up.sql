@@ -0,0 +1,3 @@
+-- Immutable identifier of the directory entry a user is bound to, set by LDAP
+-- provisioning or by the SCIM externalId
+CREATE UNIQUE INDEX users_directory_id_key ON users ((data ->> 'directory_id'));
@@ -0,0 +1,12 @@
+-- Bulk user exports, audit log of the admins and CLI runs reading every user
+CREATE TABLE user_exports (
+    id text PRIMARY KEY DEFAULT kiro_record_id(),
+    data jsonb NOT NULL CHECK (jsonb_typeof(data) = 'object')
+);
+
+CREATE TRIGGER user_exports_defaults BEFORE INSERT ON user_exports
+    FOR EACH ROW EXECUTE FUNCTION kiro_defaults('{}', 'created_at');
+CREATE TRIGGER user_exports_notify AFTER INSERT OR UPDATE OR DELETE ON user_exports
+    FOR EACH ROW EXECUTE FUNCTION kiro_notify();
+
+CREATE INDEX user_exports_created_at ON user_exports ((data ->> 'created_at'));
@@ -0,0 +1 @@
+DROP FUNCTION kiro_datetime(jsonb);
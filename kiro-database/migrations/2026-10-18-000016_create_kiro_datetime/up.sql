@@ -0,0 +1,8 @@
+-- Date of a document field, written as seconds since the epoch by the models
+-- and as an RFC 3339 string by `kiro_now`
+CREATE FUNCTION kiro_datetime(value jsonb) RETURNS timestamptz AS $$
+    SELECT CASE jsonb_typeof(value)
+        WHEN 'number' THEN to_timestamp((value #>> '{}')::double precision)
+        WHEN 'string' THEN (value #>> '{}')::timestamptz
+    END;
+$$ LANGUAGE sql STABLE;
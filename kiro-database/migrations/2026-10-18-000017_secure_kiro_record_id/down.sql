@@ -0,0 +1,4 @@
+CREATE OR REPLACE FUNCTION kiro_record_id() RETURNS text AS $$
+    SELECT string_agg(substr('abcdefghijklmnopqrstuvwxyz0123456789', (random() * 35)::int + 1, 1), '')
+    FROM generate_series(1, 20);
+$$ LANGUAGE sql VOLATILE;
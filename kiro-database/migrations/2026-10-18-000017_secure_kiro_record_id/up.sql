@@ -0,0 +1,9 @@
+-- Record keys are the secret tokens of the links, they are drawn from the
+-- cryptographic generator of pgcrypto. Each byte picks one of 32 characters,
+-- so every character is equally likely.
+CREATE EXTENSION IF NOT EXISTS pgcrypto;
+
+CREATE OR REPLACE FUNCTION kiro_record_id() RETURNS text AS $$
+    SELECT string_agg(substr('abcdefghijklmnopqrstuvwxyz234567', get_byte(bytes, i) % 32 + 1, 1), '' ORDER BY i)
+    FROM gen_random_bytes(26) AS bytes, generate_series(0, 25) AS i;
+$$ LANGUAGE sql VOLATILE;
@@ -0,0 +1 @@
+DROP INDEX user_emails_verified_email;
@@ -0,0 +1,3 @@
+-- An address can only be verified by one account
+CREATE UNIQUE INDEX user_emails_verified_email
+    ON user_emails ((data ->> 'email')) WHERE (data ->> 'verified')::boolean;
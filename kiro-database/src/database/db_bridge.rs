@@ -43,7 +43,12 @@ use surrealdb::{
 #[cfg(feature = "surrealdb")]
 use chrono::Utc;
 
+#[cfg(feature = "surrealdb")]
+use crate::database::db_query::{identifier, options_clause, SurrealQuery};
+use crate::database::db_query::{Condition, Update};
 use crate::database::db_types::DbId;
+#[cfg(feature = "postgres")]
+use crate::database::postgres_repo::{self, PostgresRepo};
 use crate::error::DatabaseError;
 
 pub type DatabaseStream<T> = Pin<Box<dyn Stream<Item = Result<T, DatabaseError>> + Send>>;
@@ -54,6 +59,8 @@ pub type QueryBindings = serde_json::Value;
 pub enum Database {
     #[cfg(feature = "surrealdb")]
     Surreal(Surreal<Any>),
+    #[cfg(feature = "postgres")]
+    Postgres(PostgresRepo),
     Mock(MockDatabaseOperations),
 }
 
@@ -89,6 +96,7 @@ pub struct QueryOptions {
     pub order_by: Option<String>,
     pub order_direction: Option<OrderDirection>,
     pub limit: Option<usize>,
+    pub start: Option<usize>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -112,6 +120,8 @@ impl DatabaseOperations for Database {
                 .content(data)
                 .await
                 .map_err(DatabaseError::from),
+            #[cfg(feature = "postgres")]
+            Self::Postgres(repo) => postgres_repo::create(&repo.pool, table, data).await,
             Self::Mock(db) => db.create(table, data).await,
         }
     }
@@ -131,6 +141,8 @@ impl DatabaseOperations for Database {
                     .take(0)
                     .map_err(DatabaseError::from)
             }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(repo) => postgres_repo::select(&repo.pool, thing).await,
             Self::Mock(db) => db.select(thing).await,
         }
     }
@@ -145,13 +157,14 @@ impl DatabaseOperations for Database {
             #[cfg(feature = "surrealdb")]
             Self::Surreal(db) => {
                 let query = db.query(sql);
-                let result: Vec<U> = if let Some(bindings) = bindings {
+                let response = if let Some(bindings) = bindings {
                     query.bind(bindings).await?
                 } else {
                     query.await?
-                }
-                .take(0)
-                .map_err(DatabaseError::from)?;
+                };
+
+                // Every statement must succeed, not only the one whose rows are returned
+                let result: Vec<U> = response.check()?.take(0).map_err(DatabaseError::from)?;
 
                 match result.len() {
                     0 => Ok(Vec::new()),
@@ -159,6 +172,8 @@ impl DatabaseOperations for Database {
                     _ => Ok(result),
                 }
             }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(repo) => postgres_repo::query(&repo.pool, sql, bindings).await,
             Self::Mock(db) => db.query(sql, bindings).await,
         }
     }
@@ -176,6 +191,8 @@ impl DatabaseOperations for Database {
                     .await
                     .map_err(DatabaseError::from)
             }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(repo) => postgres_repo::update(&repo.pool, thing, data).await,
             Self::Mock(db) => db.update(thing, data).await,
         }
     }
@@ -187,6 +204,8 @@ impl DatabaseOperations for Database {
                 let surreal_thing = Thing::from(thing);
                 db.delete(surreal_thing).await.map_err(DatabaseError::from)
             }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(repo) => postgres_repo::delete(&repo.pool, thing).await,
             Self::Mock(db) => db.delete(thing).await,
         }
     }
@@ -201,21 +220,7 @@ impl DatabaseOperations for Database {
             #[cfg(feature = "surrealdb")]
             Self::Surreal(db) => {
                 let mut sql = String::from("SELECT * FROM type::table($table)");
-
-                if let Some(opts) = options {
-                    if let Some(order_by) = opts.order_by {
-                        sql.push_str(&format!(" ORDER BY {}", order_by));
-                        if let Some(direction) = opts.order_direction {
-                            sql.push_str(match direction {
-                                OrderDirection::ASC => " ASC",
-                                OrderDirection::DESC => " DESC",
-                            });
-                        }
-                    }
-                    if let Some(limit) = opts.limit {
-                        sql.push_str(&format!(" LIMIT {}", limit));
-                    }
-                }
+                sql.push_str(&options_clause(options));
 
                 db.query(&sql)
                     .bind(("table", table))
@@ -223,6 +228,8 @@ impl DatabaseOperations for Database {
                     .take(0)
                     .map_err(DatabaseError::from)
             }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(repo) => postgres_repo::read_all(&repo.pool, table, options).await,
             Self::Mock(db) => db.read_all(table, options).await,
         }
     }
@@ -239,21 +246,7 @@ impl DatabaseOperations for Database {
                 let surreal_things: Vec<Thing> = ids.into_iter().map(Thing::from).collect();
                 let mut sql =
                     String::from("SELECT * FROM type::table($table) WHERE id INSIDE $ids");
-
-                if let Some(opts) = options {
-                    if let Some(order_by) = opts.order_by {
-                        sql.push_str(&format!(" ORDER BY {}", order_by));
-                        if let Some(direction) = opts.order_direction {
-                            sql.push_str(match direction {
-                                OrderDirection::ASC => " ASC",
-                                OrderDirection::DESC => " DESC",
-                            });
-                        }
-                    }
-                    if let Some(limit) = opts.limit {
-                        sql.push_str(&format!(" LIMIT {}", limit));
-                    }
-                }
+                sql.push_str(&options_clause(options));
 
                 db.query(&sql)
                     .bind(("table", table))
@@ -262,6 +255,10 @@ impl DatabaseOperations for Database {
                     .take(0)
                     .map_err(DatabaseError::from)
             }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(repo) => {
+                postgres_repo::read_by_ids(&repo.pool, table, ids, options).await
+            }
             Self::Mock(db) => db.read_by_ids(table, ids, options).await,
         }
     }
@@ -276,21 +273,7 @@ impl DatabaseOperations for Database {
             #[cfg(feature = "surrealdb")]
             Self::Surreal(db) => {
                 let mut sql = format!("SELECT * FROM type::table($table) WHERE {} = $value", field);
-
-                if let Some(opts) = options {
-                    if let Some(order_by) = opts.order_by {
-                        sql.push_str(&format!(" ORDER BY {}", order_by));
-                        if let Some(direction) = opts.order_direction {
-                            sql.push_str(match direction {
-                                OrderDirection::ASC => " ASC",
-                                OrderDirection::DESC => " DESC",
-                            });
-                        }
-                    }
-                    if let Some(limit) = opts.limit {
-                        sql.push_str(&format!(" LIMIT {}", limit));
-                    }
-                }
+                sql.push_str(&options_clause(options));
 
                 db.query(&sql)
                     .bind(("table", table))
@@ -299,6 +282,10 @@ impl DatabaseOperations for Database {
                     .take(0)
                     .map_err(DatabaseError::from)
             }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(repo) => {
+                postgres_repo::read_by_field(&repo.pool, table, field, value, options).await
+            }
             Self::Mock(db) => db.read_by_field(table, field, value, options).await,
         }
     }
@@ -317,21 +304,7 @@ impl DatabaseOperations for Database {
                     "SELECT * FROM type::table($table) WHERE {} = type::thing($value)",
                     field
                 );
-
-                if let Some(opts) = options {
-                    if let Some(order_by) = opts.order_by {
-                        sql.push_str(&format!(" ORDER BY {}", order_by));
-                        if let Some(direction) = opts.order_direction {
-                            sql.push_str(match direction {
-                                OrderDirection::ASC => " ASC",
-                                OrderDirection::DESC => " DESC",
-                            });
-                        }
-                    }
-                    if let Some(limit) = opts.limit {
-                        sql.push_str(&format!(" LIMIT {}", limit));
-                    }
-                }
+                sql.push_str(&options_clause(options));
 
                 db.query(&sql)
                     .bind(("table", table))
@@ -340,6 +313,10 @@ impl DatabaseOperations for Database {
                     .take(0)
                     .map_err(DatabaseError::from)
             }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(repo) => {
+                postgres_repo::read_by_field_thing(&repo.pool, table, field, value, options).await
+            }
             Self::Mock(db) => db.read_by_field_thing(table, field, value, options).await,
         }
     }
@@ -358,6 +335,10 @@ impl DatabaseOperations for Database {
                     .map(|_: Option<serde_json::Value>| ())
                     .map_err(DatabaseError::from)
             }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(repo) => {
+                postgres_repo::update_field(&repo.pool, thing, field, value).await
+            }
             Self::Mock(db) => db.update_field(thing, field, value).await,
         }
     }
@@ -376,6 +357,10 @@ impl DatabaseOperations for Database {
                     .map(|_: Option<serde_json::Value>| ())
                     .map_err(DatabaseError::from)
             }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(repo) => {
+                postgres_repo::add_to_field(&repo.pool, thing, field, value).await
+            }
             Self::Mock(db) => db.add_to_field(thing, field, value).await,
         }
     }
@@ -403,6 +388,10 @@ impl DatabaseOperations for Database {
 
                 Ok(())
             }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(repo) => {
+                postgres_repo::remove_from_field(&repo.pool, thing, field, value).await
+            }
             Self::Mock(db) => db.remove_from_field(thing, field, value).await,
         }
     }
@@ -447,6 +436,16 @@ impl DatabaseOperations for Database {
 
                 Ok(!result.is_empty())
             }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(repo) => {
+                postgres_repo::check_organization_access(
+                    &repo.pool,
+                    user_id,
+                    organization_id,
+                    required_role,
+                )
+                .await
+            }
             Self::Mock(db) => {
                 db.check_organization_access(user_id, organization_id, required_role)
                     .await
@@ -465,10 +464,134 @@ impl DatabaseOperations for Database {
                     .map(|_: Option<serde_json::Value>| ())
                     .map_err(DatabaseError::from)
             }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(repo) => postgres_repo::delete_soft(&repo.pool, thing).await,
             Self::Mock(db) => db.delete_soft(thing).await,
         }
     }
 
+    async fn read_where<T>(
+        &self, table: &str, condition: Condition, options: Option<QueryOptions>,
+    ) -> Result<Vec<T>, DatabaseError>
+    where
+        T: for<'de> SerdeDeserialize<'de> + Send + Sync + 'static,
+    {
+        match self {
+            #[cfg(feature = "surrealdb")]
+            Self::Surreal(db) => SurrealQuery::select(table, &condition, options)?
+                .prepare(db)
+                .await?
+                .check()?
+                .take(0)
+                .map_err(DatabaseError::from),
+            #[cfg(feature = "postgres")]
+            Self::Postgres(repo) => {
+                postgres_repo::read_where(&repo.pool, table, condition, options).await
+            }
+            Self::Mock(db) => db.read_where(table, condition, options).await,
+        }
+    }
+
+    async fn count_where(&self, table: &str, condition: Condition) -> Result<u64, DatabaseError> {
+        match self {
+            #[cfg(feature = "surrealdb")]
+            Self::Surreal(db) => {
+                let count: Option<Count> = SurrealQuery::count(table, &condition)?
+                    .prepare(db)
+                    .await?
+                    .check()?
+                    .take(0)?;
+
+                Ok(count.map_or(0, |count| count.count))
+            }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(repo) => postgres_repo::count_where(&repo.pool, table, condition).await,
+            Self::Mock(db) => db.count_where(table, condition).await,
+        }
+    }
+
+    async fn delete_where(
+        &self, table: &str, condition: Condition,
+    ) -> Result<usize, DatabaseError> {
+        match self {
+            #[cfg(feature = "surrealdb")]
+            Self::Surreal(db) => {
+                let deleted: Vec<serde_json::Value> = SurrealQuery::delete(table, &condition)?
+                    .prepare(db)
+                    .await?
+                    .check()?
+                    .take(0)?;
+
+                Ok(deleted.len())
+            }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(repo) => postgres_repo::delete_where(&repo.pool, table, condition).await,
+            Self::Mock(db) => db.delete_where(table, condition).await,
+        }
+    }
+
+    async fn update_where<T>(&self, update: Update) -> Result<Vec<T>, DatabaseError>
+    where
+        T: for<'de> SerdeDeserialize<'de> + Send + Sync + 'static,
+    {
+        match self {
+            #[cfg(feature = "surrealdb")]
+            Self::Surreal(db) => SurrealQuery::update(&update)?
+                .prepare(db)
+                .await?
+                .check()?
+                .take(0)
+                .map_err(DatabaseError::from),
+            #[cfg(feature = "postgres")]
+            Self::Postgres(repo) => postgres_repo::update_where(&repo.pool, update).await,
+            Self::Mock(db) => db.update_where(update).await,
+        }
+    }
+
+    async fn update_chain<T>(
+        &self, first: Update, then: Vec<Update>,
+    ) -> Result<Vec<T>, DatabaseError>
+    where
+        T: for<'de> SerdeDeserialize<'de> + Send + Sync + 'static,
+    {
+        match self {
+            #[cfg(feature = "surrealdb")]
+            Self::Surreal(db) => {
+                // A failing statement cancels the whole transaction
+                let mut response = SurrealQuery::update_chain(&first, &then)?
+                    .prepare(db)
+                    .await?
+                    .check()?;
+                let last = response.num_statements().saturating_sub(1);
+
+                response.take(last).map_err(DatabaseError::from)
+            }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(repo) => postgres_repo::update_chain(&repo.pool, first, then).await,
+            Self::Mock(db) => db.update_chain(first, then).await,
+        }
+    }
+
+    async fn insert<T, U>(&self, table: &str, data: Vec<T>) -> Result<Vec<U>, DatabaseError>
+    where
+        T: SerdeSerialize + Send + Sync + 'static,
+        U: for<'de> SerdeDeserialize<'de> + Send + Sync + 'static,
+    {
+        match self {
+            #[cfg(feature = "surrealdb")]
+            Self::Surreal(db) => db
+                .query(format!("INSERT INTO {} $data;", identifier(table)?))
+                .bind(("data", data))
+                .await?
+                .check()?
+                .take(0)
+                .map_err(DatabaseError::from),
+            #[cfg(feature = "postgres")]
+            Self::Postgres(repo) => postgres_repo::insert(&repo.pool, table, data).await,
+            Self::Mock(db) => db.insert(table, data).await,
+        }
+    }
+
     async fn live<T>(&self, table: &str) -> Result<DatabaseStream<Vec<T>>, DatabaseError>
     where
         T: for<'de> SerdeDeserialize<'de> + HasId + Send + Sync + 'static,
@@ -486,7 +609,17 @@ impl DatabaseOperations for Database {
                         .live()
                         .await
                         .expect("Failed to create live stream");
-                    while let Some(result) = stream.next().await {
+
+                    loop {
+                        let result = tokio::select! {
+                            // Dropping the stream kills the live query
+                            _ = tx.closed() => break,
+                            result = stream.next() => match result {
+                                Some(result) => result,
+                                None => break,
+                            },
+                        };
+
                         match result {
                             Ok(notification) => {
                                 let data = match notification.action {
@@ -514,42 +647,85 @@ impl DatabaseOperations for Database {
 
                 Ok(Box::pin(ReceiverStream::new(rx)))
             }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(repo) => postgres_repo::live(repo, table),
             Self::Mock(db) => db.live(table).await,
         }
     }
 
-    async fn handle_actions<D>(
-        &self, notification: DatabaseNotification<D>, tx: Sender<Option<Vec<D>>>, id: Option<D::Id>,
-    ) -> Result<(), DatabaseError>
+    async fn live_record<T>(&self, thing: DbId) -> Result<DatabaseStream<Option<T>>, DatabaseError>
     where
-        D: HasId + SerdeSerialize + Send + Sync + 'static,
+        T: for<'de> SerdeDeserialize<'de> + HasId + Send + Sync + 'static,
     {
         match self {
             #[cfg(feature = "surrealdb")]
-            Self::Surreal(_db) => {
-                let data = notification.data.clone();
+            Self::Surreal(db) => {
+                let db = Arc::new(db.clone());
+                let (tx, rx) = mpsc::channel(16);
+                let surreal_thing = Thing::from(thing);
 
-                match notification.action {
-                    DatabaseAction::Create | DatabaseAction::Update => {
-                        if let Some(ref id) = id {
-                            if id == data.id() {
-                                let _ = tx.send(Some(vec![data]));
-                            }
-                        } else {
-                            let _ = tx.send(Some(vec![data]));
+                tokio::spawn(async move {
+                    let mut stream = match db.select(surreal_thing).live().await {
+                        Ok(stream) => stream,
+                        Err(e) => {
+                            let _ = tx.send(Err(DatabaseError::from(e))).await;
+                            return;
                         }
-                    }
-                    DatabaseAction::Delete => {
-                        if let Some(ref id) = id {
-                            if id == data.id() {
-                                tx.send(None).unwrap();
-                            }
-                        } else {
-                            tx.send(None).unwrap();
+                    };
+
+                    loop {
+                        let result = tokio::select! {
+                            // Dropping the stream kills the live query
+                            _ = tx.closed() => break,
+                            result = stream.next() => match result {
+                                Some(result) => result,
+                                None => break,
+                            },
+                        };
+
+                        let data = match result {
+                            Ok(notification) => match notification.action {
+                                surrealdb::Action::Create | surrealdb::Action::Update => {
+                                    match serde_json::from_value::<T>(notification.data) {
+                                        Ok(value) => Ok(Some(value)),
+                                        Err(_) => continue,
+                                    }
+                                }
+                                surrealdb::Action::Delete => Ok(None),
+                                _ => continue,
+                            },
+                            Err(e) => Err(DatabaseError::from(e)),
+                        };
+
+                        if tx.send(data).await.is_err() {
+                            break;
                         }
                     }
-                }
+                });
+
+                Ok(Box::pin(ReceiverStream::new(rx)))
+            }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(repo) => postgres_repo::live_record(repo, thing),
+            Self::Mock(db) => db.live_record(thing).await,
+        }
+    }
 
+    async fn handle_actions<D>(
+        &self, notification: DatabaseNotification<D>, tx: Sender<Option<Vec<D>>>, id: Option<D::Id>,
+    ) -> Result<(), DatabaseError>
+    where
+        D: HasId + SerdeSerialize + Send + Sync + 'static,
+    {
+        match self {
+            #[cfg(feature = "surrealdb")]
+            Self::Surreal(_db) => {
+                notify_watchers(notification, tx, id);
+                Ok(())
+            }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(_repo) => {
+                notify_watchers(notification, tx, id);
                 Ok(())
             }
             Self::Mock(db) => db.handle_actions(notification, tx, id).await,
@@ -574,11 +750,51 @@ impl DatabaseOperations for Database {
 
                 Result::Ok(result[0].clone())
             }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(repo) => postgres_repo::renew_session(&repo.pool, session_key).await,
             Self::Mock(db) => db.renew_session(session_key).await,
         }
     }
 }
 
+/// Number of records of a `count()` query
+#[cfg(feature = "surrealdb")]
+#[derive(Deserialize)]
+struct Count {
+    count: u64,
+}
+
+/// Sends a notification to the watchers of a table, or of one of its records
+#[cfg(any(feature = "surrealdb", feature = "postgres"))]
+fn notify_watchers<D>(
+    notification: DatabaseNotification<D>, tx: Sender<Option<Vec<D>>>, id: Option<D::Id>,
+) where
+    D: HasId,
+{
+    let data = notification.data.clone();
+
+    match notification.action {
+        DatabaseAction::Create | DatabaseAction::Update => {
+            if let Some(ref id) = id {
+                if id == data.id() {
+                    let _ = tx.send(Some(vec![data]));
+                }
+            } else {
+                let _ = tx.send(Some(vec![data]));
+            }
+        }
+        DatabaseAction::Delete => {
+            if let Some(ref id) = id {
+                if id == data.id() {
+                    tx.send(None).unwrap();
+                }
+            } else {
+                tx.send(None).unwrap();
+            }
+        }
+    }
+}
+
 #[allow(dead_code)]
 #[automock(type DatabaseError = crate::error::DatabaseError;)]
 #[async_trait]
@@ -652,10 +868,42 @@ pub trait DatabaseOperations: Send + Sync + 'static {
 
     async fn delete_soft(&self, thing: DbId) -> Result<(), DatabaseError>;
 
+    async fn read_where<T>(
+        &self, table: &str, condition: Condition, options: Option<QueryOptions>,
+    ) -> Result<Vec<T>, DatabaseError>
+    where
+        T: for<'de> SerdeDeserialize<'de> + Send + Sync + 'static;
+
+    async fn count_where(&self, table: &str, condition: Condition) -> Result<u64, DatabaseError>;
+
+    async fn delete_where(&self, table: &str, condition: Condition)
+        -> Result<usize, DatabaseError>;
+
+    async fn update_where<T>(&self, update: Update) -> Result<Vec<T>, DatabaseError>
+    where
+        T: for<'de> SerdeDeserialize<'de> + Send + Sync + 'static;
+
+    /// Runs the updates in a transaction, the next ones only once the first one
+    /// updated a record, and returns the records of the first one
+    async fn update_chain<T>(
+        &self, first: Update, then: Vec<Update>,
+    ) -> Result<Vec<T>, DatabaseError>
+    where
+        T: for<'de> SerdeDeserialize<'de> + Send + Sync + 'static;
+
+    async fn insert<T, U>(&self, table: &str, data: Vec<T>) -> Result<Vec<U>, DatabaseError>
+    where
+        T: SerdeSerialize + Send + Sync + 'static,
+        U: for<'de> SerdeDeserialize<'de> + Send + Sync + 'static;
+
     async fn live<T>(&self, table: &str) -> Result<DatabaseStream<Vec<T>>, DatabaseError>
     where
         T: for<'de> SerdeDeserialize<'de> + HasId + Send + Sync + 'static;
 
+    async fn live_record<T>(&self, thing: DbId) -> Result<DatabaseStream<Option<T>>, DatabaseError>
+    where
+        T: for<'de> SerdeDeserialize<'de> + HasId + Send + Sync + 'static;
+
     async fn handle_actions<D>(
         &self, notification: DatabaseNotification<D>, tx: Sender<Option<Vec<D>>>, id: Option<D::Id>,
     ) -> Result<(), DatabaseError>
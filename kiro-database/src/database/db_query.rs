@@ -0,0 +1,754 @@
+// database/db_query.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+#[cfg(feature = "surrealdb")]
+use surrealdb::{
+    engine::any::Any,
+    method::Query,
+    sql::{Datetime, Thing},
+    Surreal,
+};
+
+#[cfg(feature = "surrealdb")]
+use crate::database::db_bridge::{OrderDirection, QueryOptions};
+use crate::{
+    database::{DbDateTime, DbId},
+    error::DatabaseError,
+};
+
+/// # Database Value
+///
+/// Value compared or written by a typed query. Each backend binds it with its
+/// own type: record links are compared as records, and dates as dates.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DbValue {
+    Bool(bool),
+    Int(i64),
+    Text(String),
+    Thing(DbId),
+    Datetime(DateTime<Utc>),
+    Json(serde_json::Value),
+}
+
+impl DbValue {
+    /// Serializes any value, e.g. a list or a nested object
+    ///
+    /// # Arguments
+    /// - `value`: Value to serialize
+    ///
+    /// # Returns
+    /// - `Result<Self, DatabaseError>`: JSON value or serialization error
+    pub fn json<T: Serialize>(value: &T) -> Result<Self, DatabaseError> {
+        Ok(Self::Json(serde_json::to_value(value)?))
+    }
+}
+
+impl From<bool> for DbValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i64> for DbValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<u64> for DbValue {
+    fn from(value: u64) -> Self {
+        Self::Int(i64::try_from(value).unwrap_or(i64::MAX))
+    }
+}
+
+impl From<usize> for DbValue {
+    fn from(value: usize) -> Self {
+        Self::Int(i64::try_from(value).unwrap_or(i64::MAX))
+    }
+}
+
+impl From<&str> for DbValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<String> for DbValue {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<DbId> for DbValue {
+    fn from(value: DbId) -> Self {
+        Self::Thing(value)
+    }
+}
+
+impl From<DateTime<Utc>> for DbValue {
+    fn from(value: DateTime<Utc>) -> Self {
+        Self::Datetime(value)
+    }
+}
+
+impl From<DbDateTime> for DbValue {
+    fn from(value: DbDateTime) -> Self {
+        Self::Datetime(value.into_inner())
+    }
+}
+
+/// Case insensitive text comparison, the compared value is lowercased
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TextMatch {
+    Equals,
+    Contains,
+    StartsWith,
+    EndsWith,
+}
+
+/// # Condition
+///
+/// Condition on the records of a table, compiled to SurrealQL or to SQL by
+/// each backend. Fields are names or dotted paths, e.g. `settings.theme`, and
+/// `id` is the record identifier.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use kiro_database::{db_query::Condition, DbId};
+///
+/// let condition = Condition::And(vec![
+///     Condition::eq("user", DbId::from(("users", "jane"))),
+///     Condition::eq("is_primary", true),
+/// ]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum Condition {
+    /// Every record
+    All,
+    Eq(String, DbValue),
+    Ne(String, DbValue),
+    Lt(String, DbValue),
+    Le(String, DbValue),
+    Gt(String, DbValue),
+    Ge(String, DbValue),
+    /// The array field holds the value
+    Contains(String, DbValue),
+    /// The field is one of the values
+    Inside(String, Vec<DbValue>),
+    /// The field holds a value, neither missing nor null
+    IsSet(String),
+    /// The field is missing or null
+    IsNone(String),
+    Text(String, TextMatch, String),
+    Not(Box<Condition>),
+    And(Vec<Condition>),
+    Or(Vec<Condition>),
+}
+
+impl Condition {
+    pub fn eq(field: &str, value: impl Into<DbValue>) -> Self {
+        Self::Eq(field.to_string(), value.into())
+    }
+
+    pub fn ne(field: &str, value: impl Into<DbValue>) -> Self {
+        Self::Ne(field.to_string(), value.into())
+    }
+
+    pub fn lt(field: &str, value: impl Into<DbValue>) -> Self {
+        Self::Lt(field.to_string(), value.into())
+    }
+
+    pub fn le(field: &str, value: impl Into<DbValue>) -> Self {
+        Self::Le(field.to_string(), value.into())
+    }
+
+    pub fn gt(field: &str, value: impl Into<DbValue>) -> Self {
+        Self::Gt(field.to_string(), value.into())
+    }
+
+    pub fn ge(field: &str, value: impl Into<DbValue>) -> Self {
+        Self::Ge(field.to_string(), value.into())
+    }
+
+    pub fn contains(field: &str, value: impl Into<DbValue>) -> Self {
+        Self::Contains(field.to_string(), value.into())
+    }
+
+    pub fn inside<V: Into<DbValue>>(field: &str, values: impl IntoIterator<Item = V>) -> Self {
+        Self::Inside(
+            field.to_string(),
+            values.into_iter().map(Into::into).collect(),
+        )
+    }
+
+    pub fn is_set(field: &str) -> Self {
+        Self::IsSet(field.to_string())
+    }
+
+    pub fn is_none(field: &str) -> Self {
+        Self::IsNone(field.to_string())
+    }
+
+    pub fn text(field: &str, matching: TextMatch, value: &str) -> Self {
+        Self::Text(field.to_string(), matching, value.to_lowercase())
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(condition: Condition) -> Self {
+        Self::Not(Box::new(condition))
+    }
+}
+
+/// Change of a field made by an [`Update`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum FieldUpdate {
+    Set(String, DbValue),
+    /// Removes the field
+    Unset(String),
+    /// Adds to a numeric field, missing fields count as zero
+    Increment(String, i64),
+    /// Sets the field to the current time of the database
+    Now(String),
+}
+
+impl FieldUpdate {
+    pub fn set(field: &str, value: impl Into<DbValue>) -> Self {
+        Self::Set(field.to_string(), value.into())
+    }
+
+    pub fn unset(field: &str) -> Self {
+        Self::Unset(field.to_string())
+    }
+
+    pub fn increment(field: &str, by: i64) -> Self {
+        Self::Increment(field.to_string(), by)
+    }
+
+    pub fn now(field: &str) -> Self {
+        Self::Now(field.to_string())
+    }
+}
+
+/// # Update
+///
+/// Changes applied to the records of a table matching a condition.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use kiro_database::{
+///     db_query::{Condition, FieldUpdate, Update},
+///     DbId,
+/// };
+///
+/// // Claims an invitation unless it was used meanwhile
+/// let update = Update::record(
+///     DbId::from(("invitations", "abc")),
+///     vec![FieldUpdate::now("used_at")],
+/// )
+/// .when(Condition::is_none("used_by"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Update {
+    pub table: String,
+    pub condition: Condition,
+    pub changes: Vec<FieldUpdate>,
+}
+
+impl Update {
+    pub fn new(table: &str, condition: Condition, changes: Vec<FieldUpdate>) -> Self {
+        Self {
+            table: table.to_string(),
+            condition,
+            changes,
+        }
+    }
+
+    /// Update of a single record
+    pub fn record(thing: DbId, changes: Vec<FieldUpdate>) -> Self {
+        Self {
+            table: thing.tb.clone(),
+            condition: Condition::eq("id", thing),
+            changes,
+        }
+    }
+
+    /// Restricts the update to the records also matching a condition
+    pub fn when(self, condition: Condition) -> Self {
+        Self {
+            condition: Condition::And(vec![self.condition, condition]),
+            ..self
+        }
+    }
+}
+
+/// Checks a table name, table names are inlined in the queries
+pub(crate) fn identifier(name: &str) -> Result<&str, DatabaseError> {
+    let valid = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    if valid {
+        Ok(name)
+    } else {
+        Err(DatabaseError::Internal(format!(
+            "Invalid table name: {}",
+            name
+        )))
+    }
+}
+
+/// Checks a field of a condition or an update, `settings.theme` or
+/// `/settings/theme`, and splits it in segments
+pub(crate) fn field_segments(field: &str) -> Result<Vec<String>, DatabaseError> {
+    let segments: Vec<String> = field
+        .trim_start_matches('/')
+        .split(['/', '.'])
+        .map(str::to_string)
+        .collect();
+
+    let valid = segments.iter().all(|segment| {
+        !segment.is_empty()
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    });
+
+    if valid {
+        Ok(segments)
+    } else {
+        Err(DatabaseError::Internal(format!("Invalid field: {}", field)))
+    }
+}
+
+/// Splits a condition on a single record of a table from the rest of the
+/// condition, so the query reads the record instead of scanning the table
+pub(crate) fn record_target(table: &str, condition: &Condition) -> (Option<DbId>, Condition) {
+    let is_record = |condition: &Condition| match condition {
+        Condition::Eq(field, DbValue::Thing(thing)) if field == "id" && thing.tb == table => {
+            Some(thing.clone())
+        }
+        _ => None,
+    };
+
+    match condition {
+        Condition::And(conditions) => match conditions.split_first() {
+            Some((first, rest)) => match is_record(first) {
+                Some(thing) => (Some(thing), Condition::And(rest.to_vec())),
+                None => (None, condition.clone()),
+            },
+            None => (None, Condition::All),
+        },
+        _ => match is_record(condition) {
+            Some(thing) => (Some(thing), Condition::All),
+            None => (None, condition.clone()),
+        },
+    }
+}
+
+/// # SurrealQL Query
+///
+/// SurrealQL statements compiled from typed queries, with their parameters
+/// named `$p0`, `$p1`...
+#[cfg(feature = "surrealdb")]
+#[derive(Debug, Default)]
+pub(crate) struct SurrealQuery {
+    pub sql: String,
+    pub params: Vec<(String, DbValue)>,
+}
+
+#[cfg(feature = "surrealdb")]
+impl SurrealQuery {
+    /// `SELECT` of the records matching a condition
+    pub(crate) fn select(
+        table: &str, condition: &Condition, options: Option<QueryOptions>,
+    ) -> Result<Self, DatabaseError> {
+        let mut query = Self::default();
+        let (target, clause) = query.target(table, condition)?;
+        query.sql = format!(
+            "SELECT * FROM {} WHERE {}{};",
+            target,
+            clause,
+            options_clause(options)
+        );
+
+        Ok(query)
+    }
+
+    /// Number of records matching a condition, as a `count` field
+    pub(crate) fn count(table: &str, condition: &Condition) -> Result<Self, DatabaseError> {
+        let mut query = Self::default();
+        let (target, clause) = query.target(table, condition)?;
+        query.sql = format!(
+            "SELECT count() AS count FROM {} WHERE {} GROUP ALL;",
+            target, clause
+        );
+
+        Ok(query)
+    }
+
+    /// `DELETE` of the records matching a condition, returning them
+    pub(crate) fn delete(table: &str, condition: &Condition) -> Result<Self, DatabaseError> {
+        let mut query = Self::default();
+        let (target, clause) = query.target(table, condition)?;
+        query.sql = format!("DELETE {} WHERE {} RETURN BEFORE;", target, clause);
+
+        Ok(query)
+    }
+
+    /// `UPDATE` of the records matching a condition, returning them
+    pub(crate) fn update(update: &Update) -> Result<Self, DatabaseError> {
+        let mut query = Self::default();
+        query.sql = format!("{} RETURN AFTER;", query.update_statement(update, None)?);
+
+        Ok(query)
+    }
+
+    /// Transaction running the first update, then the next ones only if the
+    /// first one updated a record; it returns the records of the first update
+    pub(crate) fn update_chain(first: &Update, then: &[Update]) -> Result<Self, DatabaseError> {
+        let mut query = Self::default();
+        let mut sql = format!(
+            "BEGIN TRANSACTION; LET $updated = ({} RETURN AFTER);",
+            query.update_statement(first, None)?
+        );
+
+        for update in then {
+            sql.push_str(&format!(
+                " {};",
+                query.update_statement(update, Some("array::len($updated) > 0"))?
+            ));
+        }
+
+        sql.push_str(" RETURN $updated; COMMIT TRANSACTION;");
+        query.sql = sql;
+
+        Ok(query)
+    }
+
+    /// Prepares the statements with their parameters
+    pub(crate) fn prepare(self, db: &Surreal<Any>) -> Query<'_, Any> {
+        let mut query = db.query(self.sql);
+
+        for (name, value) in self.params {
+            query = match value {
+                DbValue::Bool(value) => query.bind((name, value)),
+                DbValue::Int(value) => query.bind((name, value)),
+                DbValue::Text(value) => query.bind((name, value)),
+                DbValue::Thing(value) => query.bind((name, Thing::from(value))),
+                DbValue::Datetime(value) => query.bind((name, Datetime::from(value))),
+                DbValue::Json(value) => query.bind((name, value)),
+            };
+        }
+
+        query
+    }
+
+    fn param(&mut self, value: DbValue) -> String {
+        let name = format!("p{}", self.params.len());
+        self.params.push((name.clone(), value));
+
+        format!("${}", name)
+    }
+
+    /// Target of a statement, the record or the table, and its `WHERE` clause
+    fn target(
+        &mut self, table: &str, condition: &Condition,
+    ) -> Result<(String, String), DatabaseError> {
+        let table = identifier(table)?;
+
+        match record_target(table, condition) {
+            (Some(thing), condition) => {
+                let target = self.param(DbValue::Thing(thing));
+                Ok((target, self.condition(&condition)?))
+            }
+            (None, condition) => Ok((table.to_string(), self.condition(&condition)?)),
+        }
+    }
+
+    fn update_statement(
+        &mut self, update: &Update, guard: Option<&str>,
+    ) -> Result<String, DatabaseError> {
+        if update.changes.is_empty() {
+            return Err(DatabaseError::Internal(
+                "Update without changes".to_string(),
+            ));
+        }
+
+        let (target, clause) = self.target(&update.table, &update.condition)?;
+
+        let changes = update
+            .changes
+            .iter()
+            .map(|change| match change {
+                FieldUpdate::Set(field, value) => Ok(format!(
+                    "{} = {}",
+                    field_name(field)?,
+                    self.param(value.clone())
+                )),
+                FieldUpdate::Unset(field) => Ok(format!("{} = NONE", field_name(field)?)),
+                FieldUpdate::Increment(field, by) => Ok(format!(
+                    "{} += {}",
+                    field_name(field)?,
+                    self.param(DbValue::Int(*by))
+                )),
+                FieldUpdate::Now(field) => Ok(format!("{} = time::now()", field_name(field)?)),
+            })
+            .collect::<Result<Vec<_>, DatabaseError>>()?;
+
+        let clause = match guard {
+            Some(guard) => format!("{} AND ({})", guard, clause),
+            None => clause,
+        };
+
+        Ok(format!(
+            "UPDATE {} SET {} WHERE {}",
+            target,
+            changes.join(", "),
+            clause
+        ))
+    }
+
+    fn condition(&mut self, condition: &Condition) -> Result<String, DatabaseError> {
+        let compare = |query: &mut Self, field: &str, operator: &str, value: &DbValue| {
+            Ok::<_, DatabaseError>(format!(
+                "{} {} {}",
+                field_name(field)?,
+                operator,
+                query.param(value.clone())
+            ))
+        };
+
+        match condition {
+            Condition::All => Ok("true".to_string()),
+            Condition::Eq(field, value) => compare(self, field, "=", value),
+            Condition::Ne(field, value) => compare(self, field, "!=", value),
+            Condition::Lt(field, value) => compare(self, field, "<", value),
+            Condition::Le(field, value) => compare(self, field, "<=", value),
+            Condition::Gt(field, value) => compare(self, field, ">", value),
+            Condition::Ge(field, value) => compare(self, field, ">=", value),
+            Condition::Contains(field, value) => compare(self, field, "CONTAINS", value),
+            Condition::Inside(field, values) => {
+                if values.is_empty() {
+                    return Ok("false".to_string());
+                }
+
+                let values: Vec<String> = values
+                    .iter()
+                    .map(|value| self.param(value.clone()))
+                    .collect();
+                Ok(format!(
+                    "{} INSIDE [{}]",
+                    field_name(field)?,
+                    values.join(", ")
+                ))
+            }
+            Condition::IsSet(field) => Ok(format!("{} != NONE", field_name(field)?)),
+            Condition::IsNone(field) => Ok(format!("{} = NONE", field_name(field)?)),
+            Condition::Text(field, matching, value) => {
+                let field = format!("string::lowercase({})", field_name(field)?);
+                let value = self.param(DbValue::Text(value.to_lowercase()));
+
+                Ok(match matching {
+                    TextMatch::Equals => format!("{} = {}", field, value),
+                    TextMatch::Contains => format!("string::contains({}, {})", field, value),
+                    TextMatch::StartsWith => format!("string::starts_with({}, {})", field, value),
+                    TextMatch::EndsWith => format!("string::ends_with({}, {})", field, value),
+                })
+            }
+            Condition::Not(condition) => Ok(format!("!({})", self.condition(condition)?)),
+            Condition::And(conditions) => self.join(conditions, " AND ", "true"),
+            Condition::Or(conditions) => self.join(conditions, " OR ", "false"),
+        }
+    }
+
+    fn join(
+        &mut self, conditions: &[Condition], separator: &str, empty: &str,
+    ) -> Result<String, DatabaseError> {
+        match conditions {
+            [] => Ok(empty.to_string()),
+            [condition] => self.condition(condition),
+            _ => {
+                let clauses = conditions
+                    .iter()
+                    .map(|condition| self.condition(condition))
+                    .collect::<Result<Vec<_>, DatabaseError>>()?;
+                Ok(format!("({})", clauses.join(separator)))
+            }
+        }
+    }
+}
+
+/// Field of a SurrealQL statement
+#[cfg(feature = "surrealdb")]
+fn field_name(field: &str) -> Result<String, DatabaseError> {
+    Ok(field_segments(field)?.join("."))
+}
+
+/// Builds the ORDER BY, LIMIT and START clauses of the options
+#[cfg(feature = "surrealdb")]
+pub(crate) fn options_clause(options: Option<QueryOptions>) -> String {
+    let mut clause = String::new();
+
+    if let Some(opts) = options {
+        if let Some(order_by) = opts.order_by {
+            clause.push_str(&format!(" ORDER BY {}", order_by));
+            if let Some(direction) = opts.order_direction {
+                clause.push_str(match direction {
+                    OrderDirection::ASC => " ASC",
+                    OrderDirection::DESC => " DESC",
+                });
+            }
+        }
+        if let Some(limit) = opts.limit {
+            clause.push_str(&format!(" LIMIT {}", limit));
+        }
+        if let Some(start) = opts.start {
+            clause.push_str(&format!(" START {}", start));
+        }
+    }
+
+    clause
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identifier() {
+        assert!(identifier("user_emails").is_ok());
+        assert!(identifier("users; DROP TABLE users").is_err());
+        assert!(identifier("1users").is_err());
+        assert!(identifier("").is_err());
+    }
+
+    #[test]
+    fn test_field_segments() {
+        assert_eq!(
+            field_segments("settings.theme").unwrap(),
+            vec!["settings", "theme"]
+        );
+        assert_eq!(
+            field_segments("/pending_email").unwrap(),
+            vec!["pending_email"]
+        );
+        assert!(field_segments("email = 'a' OR true").is_err());
+        assert!(field_segments("settings..theme").is_err());
+    }
+
+    #[test]
+    fn test_record_target() {
+        let user = DbId::from(("users", "jane"));
+
+        let update = Update::record(user.clone(), vec![FieldUpdate::now("updated_at")])
+            .when(Condition::eq("settings_version", 3_i64));
+        assert_eq!(
+            record_target("users", &update.condition),
+            (
+                Some(user.clone()),
+                Condition::And(vec![Condition::eq("settings_version", 3_i64)])
+            )
+        );
+
+        // A record of another table isn't a target
+        let condition = Condition::eq("id", user);
+        assert_eq!(
+            record_target("sessions", &condition),
+            (None, condition.clone())
+        );
+    }
+
+    #[cfg(feature = "surrealdb")]
+    #[test]
+    fn test_surreal_select() {
+        let condition = Condition::And(vec![
+            Condition::eq("user", DbId::from(("users", "jane"))),
+            Condition::Or(vec![
+                Condition::is_none("expires_at"),
+                Condition::gt("expires_at", Utc::now()),
+            ]),
+            Condition::text("email", TextMatch::StartsWith, "Jane"),
+        ]);
+
+        let query = SurrealQuery::select(
+            "invitations",
+            &condition,
+            Some(QueryOptions {
+                order_by: Some("created_at".to_string()),
+                order_direction: Some(OrderDirection::ASC),
+                limit: Some(10),
+                start: Some(20),
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(
+            query.sql,
+            "SELECT * FROM invitations WHERE (user = $p0 AND (expires_at = NONE OR expires_at > $p1) \
+             AND string::starts_with(string::lowercase(email), $p2)) \
+             ORDER BY created_at ASC LIMIT 10 START 20;"
+        );
+        assert_eq!(query.params[2].1, DbValue::Text("jane".to_string()));
+    }
+
+    #[cfg(feature = "surrealdb")]
+    #[test]
+    fn test_surreal_update_chain() {
+        let user = DbId::from(("users", "jane"));
+        let first = Update::record(
+            user.clone(),
+            vec![
+                FieldUpdate::set("email", "jane@example.com"),
+                FieldUpdate::unset("pending_email"),
+                FieldUpdate::increment("settings_version", 1),
+                FieldUpdate::now("updated_at"),
+            ],
+        )
+        .when(Condition::eq("pending_email", "jane@example.com"));
+        let demote = Update::new(
+            "user_emails",
+            Condition::eq("user", user),
+            vec![FieldUpdate::set("is_primary", false)],
+        );
+
+        let query = SurrealQuery::update_chain(&first, &[demote]).unwrap();
+
+        assert_eq!(
+            query.sql,
+            "BEGIN TRANSACTION; LET $updated = (UPDATE $p0 SET email = $p2, pending_email = NONE, \
+             settings_version += $p3, updated_at = time::now() WHERE pending_email = $p1 RETURN AFTER); \
+             UPDATE user_emails SET is_primary = $p5 WHERE array::len($updated) > 0 AND (user = $p4); \
+             RETURN $updated; COMMIT TRANSACTION;"
+        );
+        assert_eq!(query.params.len(), 6);
+    }
+
+    #[cfg(feature = "surrealdb")]
+    #[test]
+    fn test_surreal_invalid_queries() {
+        assert!(SurrealQuery::count("users", &Condition::is_set("email; DELETE users")).is_err());
+        assert!(SurrealQuery::delete("users; DELETE users", &Condition::All).is_err());
+        assert!(SurrealQuery::update(&Update::new("users", Condition::All, vec![])).is_err());
+        assert_eq!(
+            SurrealQuery::count("users", &Condition::Inside("id".to_string(), vec![]))
+                .unwrap()
+                .sql,
+            "SELECT count() AS count FROM users WHERE false GROUP ALL;"
+        );
+    }
+}
@@ -429,10 +429,22 @@ impl<'de> Deserialize<'de> for DbDateTime {
 
         #[cfg(not(feature = "surrealdb"))]
         {
-            let timestamp = i64::deserialize(deserializer)?;
-            DateTime::from_timestamp(timestamp, 0)
-                .map(Self)
-                .ok_or_else(|| D::Error::custom("invalid timestamp"))
+            // Timestamps written by the database itself are RFC 3339 strings
+            #[derive(Deserialize)]
+            #[serde(untagged)]
+            enum Timestamp {
+                Seconds(i64),
+                Rfc3339(String),
+            }
+
+            match Timestamp::deserialize(deserializer)? {
+                Timestamp::Seconds(timestamp) => DateTime::from_timestamp(timestamp, 0)
+                    .map(Self)
+                    .ok_or_else(|| D::Error::custom("invalid timestamp")),
+                Timestamp::Rfc3339(value) => DateTime::parse_from_rfc3339(&value)
+                    .map(|dt| Self(dt.with_timezone(&Utc)))
+                    .map_err(D::Error::custom),
+            }
         }
     }
 }
@@ -22,17 +22,22 @@ mod db_types;
 #[cfg(feature = "surrealdb")]
 pub mod surrealdb_repo;
 
-// /// # Postgres Repository
-// ///
-// /// The Postgres Repository module is a module that provides utilities for the Postgres repository.
-// #[cfg(feature = "postgres")]
-// pub mod postgres_repo;
+/// # Postgres Repository
+///
+/// The Postgres Repository module is a module that provides utilities for the Postgres repository.
+#[cfg(feature = "postgres")]
+pub mod postgres_repo;
 
 /// # Database Bridge
 ///
 /// The database bridge module is a module that provides utilities for the database bridge.
 pub mod db_bridge;
 
+/// # Database Query
+///
+/// The database query module provides the typed conditions and updates run by each backend.
+pub mod db_query;
+
 /// Re-export database types and identifiers
 ///
 /// The `DbId` type is a database-agnostic identifier that can represent various types
@@ -14,6 +14,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use include_dir::{include_dir, Dir};
 use serde::{Deserialize, Serialize};
 use surrealdb::{
     engine::any::{self, Any},
@@ -24,6 +25,15 @@ use surrealdb_migrations::MigrationRunner;
 
 use crate::{database::db_types::DbId, error::DatabaseError, utils::env::get_env_or};
 
+/// SurrealQL schema applied to the in-memory databases
+static SCHEMA: Dir = include_dir!("$CARGO_MANIFEST_DIR/../migrations");
+
+/// Schema files only used by the migration runner bookkeeping
+const SCHEMA_SKIPPED: [&str; 1] = ["script_migration.surql"];
+
+/// Engines running inside the process, which have no users to sign in with
+const EMBEDDED_ENGINES: [&str; 4] = ["mem://", "memory", "rocksdb://", "file://"];
+
 /// # SurrealDBRepo
 ///
 /// Repository implementation for SurrealDB database connectivity and operations.
@@ -33,6 +43,7 @@ use crate::{database::db_types::DbId, error::DatabaseError, utils::env::get_env_
 /// - Handles database migrations
 /// - Provides transaction support
 /// - Support for multiple database namespaces
+/// - Embedded engines (`mem://`, `rocksdb://path`) for development and tests
 ///
 /// ## Examples
 /// ```rust,no_run
@@ -86,7 +97,8 @@ impl SurrealDBRepo {
     /// Initializes a new SurrealDB connection with authentication
     ///
     /// # Environment Variables
-    /// - `SURREAL_DB_HOST`: Database host URL (default: ws://127.0.0.1:8000)
+    /// - `SURREAL_DB_HOST`: Database host URL (default: ws://127.0.0.1:8000), or an
+    ///   embedded engine such as `mem://` or `rocksdb://path`
     /// - `SURREAL_USER`: Database username (default: root)
     /// - `SURREAL_PASS`: Database password (default: root)
     /// - `SURREAL_NAMESPACE`: Database namespace (default: test)
@@ -112,23 +124,28 @@ impl SurrealDBRepo {
             )
         })?;
 
+        // Embedded engines have no users, the process owns the whole datastore
+        let embedded = is_embedded(&db_location);
+
         let db_user = get_env_or("SURREAL_USER", "root");
         let db_pass = get_env_or("SURREAL_PASS", "root");
 
-        #[cfg(feature = "tracing")]
-        tracing::info!("🔐 Signing in...");
-
-        db.signin(Root {
-            username: db_user.as_str(),
-            password: db_pass.as_str(),
-        })
-        .await
-        .map_err(|e| {
-            std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("🔥 Failed to sign in: {}", e),
-            )
-        })?;
+        if !embedded {
+            #[cfg(feature = "tracing")]
+            tracing::info!("🔐 Signing in...");
+
+            db.signin(Root {
+                username: db_user.as_str(),
+                password: db_pass.as_str(),
+            })
+            .await
+            .map_err(|e| {
+                std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    format!("🔥 Failed to sign in: {}", e),
+                )
+            })?;
+        }
 
         let db_namespace = get_env_or("SURREAL_NAMESPACE", "test");
         let db_database = get_env_or("SURREAL_DATABASE", "test");
@@ -143,6 +160,10 @@ impl SurrealDBRepo {
                 )
             })?;
 
+        if embedded {
+            return Ok(Self { db });
+        }
+
         let session_db = any::connect(db_location).await.map_err(|e| {
             std::io::Error::new(
                 std::io::ErrorKind::Other,
@@ -208,6 +229,71 @@ impl SurrealDBRepo {
 
         Ok(())
     }
+
+    /// Creates a fresh in-memory database with the schema applied
+    ///
+    /// Every call starts its own embedded datastore, so tests using it are
+    /// isolated from each other and need no running server.
+    ///
+    /// # Returns
+    /// - `Result<Self, DatabaseError>`: Repository on the new database
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// let repo = SurrealDBRepo::in_memory().await?;
+    /// let db = Database::Surreal(repo.db);
+    /// ```
+    pub async fn in_memory() -> Result<Self, DatabaseError> {
+        let db = any::connect("mem://").await?;
+        db.use_ns("kiro").use_db("client").await?;
+
+        Self::apply_schema(&db).await?;
+
+        Ok(Self { db })
+    }
+
+    /// Applies the SurrealQL schema embedded in the binary
+    ///
+    /// Unlike [`SurrealDBRepo::migrate`], it doesn't read the migrations from
+    /// the working directory, nor record them.
+    ///
+    /// # Arguments
+    /// - `db`: Database connection instance
+    ///
+    /// # Returns
+    /// - `Result<(), DatabaseError>`: Success or error
+    pub async fn apply_schema(db: &Surreal<Any>) -> Result<(), DatabaseError> {
+        let mut files: Vec<_> = SCHEMA
+            .files()
+            .filter(|file| file.path().extension().is_some_and(|ext| ext == "surql"))
+            .filter(|file| {
+                !SCHEMA_SKIPPED
+                    .iter()
+                    .any(|skipped| file.path().ends_with(skipped))
+            })
+            .collect();
+        files.sort_by(|a, b| a.path().cmp(b.path()));
+
+        for file in files {
+            let sql = file.contents_utf8().ok_or_else(|| {
+                DatabaseError::Internal(format!("Invalid schema file: {}", file.path().display()))
+            })?;
+
+            db.query(sql).await?.check()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns whether a database location is an engine embedded in the process
+///
+/// # Arguments
+/// - `location`: Database location, e.g. `ws://127.0.0.1:8000` or `mem://`
+fn is_embedded(location: &str) -> bool {
+    EMBEDDED_ENGINES
+        .iter()
+        .any(|engine| location.starts_with(engine))
 }
 
 #[cfg(test)]
@@ -217,17 +303,66 @@ mod tests {
 
     #[tokio::test]
     async fn test_init_connection() {
+        std::env::set_var("SURREAL_DB_HOST", "mem://");
+
         let result = SurrealDBRepo::init().await;
         assert!(result.is_ok());
     }
 
     #[tokio::test]
     async fn test_migrations() {
+        std::env::set_var("SURREAL_DB_HOST", "mem://");
+
         let repo = SurrealDBRepo::init().await.unwrap();
         let result = SurrealDBRepo::migrate(repo.db).await;
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_embedded_engines() {
+        assert!(is_embedded("mem://"));
+        assert!(is_embedded("memory"));
+        assert!(is_embedded("rocksdb://data/kiro.db"));
+        assert!(!is_embedded("ws://127.0.0.1:8000"));
+        assert!(!is_embedded("https://db.example.com"));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_applies_schema() {
+        let repo = SurrealDBRepo::in_memory().await.unwrap();
+
+        let info: Option<serde_json::Value> =
+            repo.db.query("INFO FOR DB").await.unwrap().take(0).unwrap();
+        let tables = info.unwrap()["tables"].clone();
+
+        assert!(tables.get("users").is_some());
+        assert!(tables.get("sessions").is_some());
+        assert!(tables.get("script_migration").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_databases_are_isolated() {
+        let first = SurrealDBRepo::in_memory().await.unwrap();
+        let second = SurrealDBRepo::in_memory().await.unwrap();
+
+        first
+            .db
+            .query("CREATE scratch:one SET value = 1")
+            .await
+            .unwrap()
+            .check()
+            .unwrap();
+
+        let records: Vec<serde_json::Value> = second
+            .db
+            .query("SELECT * FROM scratch")
+            .await
+            .unwrap()
+            .take(0)
+            .unwrap();
+        assert!(records.is_empty());
+    }
+
     #[test]
     fn test_record_serialization() {
         #[derive(Debug, Serialize, Deserialize, PartialEq)]
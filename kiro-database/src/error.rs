@@ -14,7 +14,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+
 use tonic::Status;
+use tonic_types::{ErrorDetails, StatusExt};
+
+/// Domain of the error keys sent in the error details
+pub const ERROR_DOMAIN: &str = "kiro";
 
 #[derive(thiserror::Error, Debug)]
 #[allow(clippy::upper_case_acronyms)]
@@ -30,7 +36,15 @@ pub enum DatabaseError {
     #[error("SurrealDB error: {0}")]
     SurrealDB(#[from] surrealdb::Error),
 
-    #[cfg(feature = "surrealdb")]
+    #[cfg(feature = "postgres")]
+    #[error("PostgreSQL error: {0}")]
+    Postgres(#[from] diesel::result::Error),
+
+    #[cfg(feature = "postgres")]
+    #[error("PostgreSQL pool error: {0}")]
+    PostgresPool(#[from] diesel::r2d2::PoolError),
+
+    #[cfg(any(feature = "surrealdb", feature = "postgres"))]
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -41,6 +55,47 @@ pub enum DatabaseError {
     Internal(String),
 }
 
+impl DatabaseError {
+    /// Returns the stable key of the error, used to localize its message
+    pub fn key(&self) -> &'static str {
+        match self {
+            DatabaseError::DBOptionNone => "database.record_not_found",
+            DatabaseError::DBConnectionError => "database.connection_failed",
+            _ => "database.error",
+        }
+    }
+}
+
+/// Attaches the stable key of an error to a status
+///
+/// The key is sent as the reason of an `ErrorInfo` detail, so clients can
+/// localize the message themselves.
+///
+/// ## Arguments
+///
+/// - `status` - Status to attach the key to
+/// - `key` - Stable key of the error
+pub fn with_error_key(status: Status, key: &str) -> Status {
+    Status::with_error_details(
+        status.code(),
+        status.message(),
+        ErrorDetails::with_error_info(key, ERROR_DOMAIN, HashMap::new()),
+    )
+}
+
+/// Returns the stable key attached to a status, if any
+///
+/// ## Arguments
+///
+/// - `status` - Status to read the key from
+pub fn error_key(status: &Status) -> Option<String> {
+    status
+        .get_error_details()
+        .error_info()
+        .filter(|info| info.domain == ERROR_DOMAIN)
+        .map(|info| info.reason.clone())
+}
+
 /// Convert Error to tonic::Status
 ///
 /// ## Arguments
@@ -48,20 +103,28 @@ pub enum DatabaseError {
 /// - `error` - Error to convert
 impl From<DatabaseError> for Status {
     fn from(error: DatabaseError) -> Self {
-        match error {
+        let key = error.key();
+
+        let status = match error {
             // Database errors
             DatabaseError::DBOptionNone => {
                 Status::internal("Database Record that was just checked doesn't exist?")
             }
             #[cfg(feature = "surrealdb")]
             DatabaseError::SurrealDB(e) => Status::internal(e.to_string()),
+            #[cfg(feature = "postgres")]
+            DatabaseError::Postgres(e) => Status::internal(e.to_string()),
+            #[cfg(feature = "postgres")]
+            DatabaseError::PostgresPool(e) => Status::unavailable(e.to_string()),
             DatabaseError::DBConnectionError => Status::internal("Surreal initialization failed"),
             // Token errors
-            #[cfg(feature = "surrealdb")]
+            #[cfg(any(feature = "surrealdb", feature = "postgres"))]
             DatabaseError::Io(e) => Status::internal(e.to_string()),
             DatabaseError::SerdeJson(e) => Status::internal(e.to_string()),
             // DK errors
             DatabaseError::Internal(e) => Status::internal(e),
-        }
+        };
+
+        with_error_key(status, key)
     }
 }
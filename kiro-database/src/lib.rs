@@ -24,21 +24,26 @@ mod utils;
 #[cfg(feature = "surrealdb")]
 pub use database::surrealdb_repo::SurrealDBRepo;
 
-// /// # Postgres Module
-// ///
-// /// The postgres module provides postgres functionality.
-// #[cfg(feature = "postgres")]
-// pub use database::postgres_repo::PostgresRepo;
+/// # Postgres Module
+///
+/// The postgres module provides postgres functionality.
+#[cfg(feature = "postgres")]
+pub use database::postgres_repo::{PgPool, PostgresRepo};
 
 /// # Database Module
 ///
 /// The database module provides database functionality.
 pub use database::db_bridge;
 
+/// # Query Module
+///
+/// The query module provides the typed conditions and updates of the database.
+pub use database::db_query;
+
 /// # Error Module
 ///
 /// The error module provides error handling functionality.
-pub use error::DatabaseError;
+pub use error::{error_key, with_error_key, DatabaseError, ERROR_DOMAIN};
 
 /// Re-export database types and identifiers
 ///
@@ -14,7 +14,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use kiro_database::DatabaseError;
+use kiro_database::{with_error_key, DatabaseError};
 use tonic::Status;
 
 #[derive(thiserror::Error, Debug)]
@@ -65,9 +65,30 @@ impl From<MailerError> for DatabaseError {
     }
 }
 
+impl MailerError {
+    /// Returns the stable key of the error, used to localize its message
+    pub fn key(&self) -> &'static str {
+        match self {
+            MailerError::NotFound => "link.not_found",
+            MailerError::Expired => "link.expired",
+            MailerError::CreationFailed => "link.creation_failed",
+            MailerError::DeletionFailed => "link.deletion_failed",
+            MailerError::InvalidType => "link.invalid_type",
+            MailerError::InvalidFormat => "link.invalid_format",
+            MailerError::AlreadyExists => "link.already_exists",
+            MailerError::ValidationFailed => "link.validation_failed",
+            MailerError::InvalidAddress(_) => "email.invalid_address",
+            MailerError::Mailer(_) | MailerError::SMTP(_) => "email.send_failed",
+            MailerError::Database(e) => e.key(),
+        }
+    }
+}
+
 impl From<MailerError> for Status {
     fn from(error: MailerError) -> Self {
-        match error {
+        let key = error.key();
+
+        let status = match error {
             MailerError::NotFound => Status::not_found("Link not found"),
             MailerError::Expired => Status::failed_precondition("Link has expired"),
             MailerError::CreationFailed => Status::internal("Failed to create link"),
@@ -82,6 +103,8 @@ impl From<MailerError> for Status {
             MailerError::Mailer(e) => Status::internal(e.to_string()),
             MailerError::SMTP(e) => Status::internal(e.to_string()),
             MailerError::Database(e) => e.into(),
-        }
+        };
+
+        with_error_key(status, key)
     }
 }
@@ -22,6 +22,7 @@ use chrono::{DateTime, Utc};
 #[cfg(any(feature = "client", feature = "group"))]
 use kiro_database::{
     db_bridge::{DatabaseOperations, OrderDirection, QueryOptions},
+    db_query::{Condition, DbValue},
     get_env_or,
 };
 
@@ -43,6 +44,10 @@ use crate::error::MailerError;
 ///   PasswordChange,
 ///   PasswordReset,
 ///   VerifyAccount,
+///   AccountReactivation,
+///   EmailConfirmation,
+///   EmailChangeCancel,
+///   EmailVerification,
 ///   EmailGroupReset,
 ///   EmailGroupChange,
 /// }
@@ -58,6 +63,14 @@ pub enum LinkType {
     PasswordReset,
     #[cfg(feature = "client")]
     VerifyAccount,
+    #[cfg(feature = "client")]
+    AccountReactivation,
+    #[cfg(feature = "client")]
+    EmailConfirmation,
+    #[cfg(feature = "client")]
+    EmailChangeCancel,
+    #[cfg(feature = "client")]
+    EmailVerification,
     #[cfg(feature = "group")]
     EmailGroupReset,
     #[cfg(feature = "group")]
@@ -182,6 +195,7 @@ impl LinkModel {
                 order_by: Some("expiry:".to_string()),
                 order_direction: Some(OrderDirection::ASC),
                 limit: Some(10),
+                start: None,
             }),
         )
         .await
@@ -215,6 +229,7 @@ impl LinkModel {
                 order_by: Some("expiry:".to_string()),
                 order_direction: Some(OrderDirection::ASC),
                 limit: Some(10),
+                start: None,
             }),
         )
         .await
@@ -227,6 +242,36 @@ impl LinkModel {
         })
     }
 
+    /// # Get valid link
+    ///
+    /// The `get_valid_link` method gets a link by id, checking its type and expiry.
+    ///
+    /// ```rust
+    /// let link = Link::get_valid_link(db.clone(), link_id, link_type).await?;
+    ///
+    /// println!("🔗 Link: {:?}", link);
+    /// ```
+    #[cfg(any(feature = "client", feature = "group"))]
+    pub async fn get_valid_link<DB: DatabaseOperations + Send + Sync>(
+        db: &DB, link_id: &str, link_type: LinkType,
+    ) -> Result<Self, MailerError> {
+        let link = db
+            .select::<LinkModel>(DbId::from(("links", link_id)))
+            .await
+            .map_err(MailerError::Database)?
+            .ok_or(MailerError::NotFound)?;
+
+        if link.link_type != link_type {
+            return Err(MailerError::InvalidType);
+        }
+
+        if link.expiry <= Utc::now() {
+            return Err(MailerError::Expired);
+        }
+
+        Ok(link)
+    }
+
     /// # Delete link by user and type
     ///
     /// The `delete_link_by_user_and_type` method deletes a link by user and type.
@@ -240,18 +285,24 @@ impl LinkModel {
     pub async fn delete_link_by_user_and_type<DB: DatabaseOperations + Send + Sync>(
         db: &DB, user_id: DbId, link_type: LinkType,
     ) -> Result<(), MailerError> {
-        let bindings = serde_json::json!({
-            "user": user_id,
-            "link_type": link_type
-        });
-
-        db.query::<LinkModel>(
-            "DELETE links WHERE user = type::thing($user) AND link_type = $link_type;",
-            Some(bindings),
-        )
-        .await
-        .map_err(MailerError::Database)
-        .and_then(|res| res.first().map(|_| ()).ok_or(MailerError::NotFound))
+        let link_type = DbValue::json(&link_type).map_err(MailerError::Database)?;
+
+        let deleted = db
+            .delete_where(
+                "links",
+                Condition::And(vec![
+                    Condition::eq("user", user_id),
+                    Condition::eq("link_type", link_type),
+                ]),
+            )
+            .await
+            .map_err(MailerError::Database)?;
+
+        if deleted == 0 {
+            return Err(MailerError::NotFound);
+        }
+
+        Ok(())
     }
 
     /// # Construct link
@@ -275,6 +326,14 @@ impl LinkModel {
             LinkType::PasswordReset => "reset-password",
             #[cfg(feature = "client")]
             LinkType::VerifyAccount => "verify",
+            #[cfg(feature = "client")]
+            LinkType::AccountReactivation => "reactivate-account",
+            #[cfg(feature = "client")]
+            LinkType::EmailConfirmation => "confirm-email",
+            #[cfg(feature = "client")]
+            LinkType::EmailChangeCancel => "cancel-email-change",
+            #[cfg(feature = "client")]
+            LinkType::EmailVerification => "verify-email",
             #[cfg(feature = "group")]
             LinkType::EmailGroupChange => "change-group-email",
             #[cfg(feature = "group")]
@@ -453,6 +512,40 @@ mod tests {
         assert_eq!(link, test_link_clone2);
     }
 
+    #[cfg(any(feature = "client", feature = "group"))]
+    #[tokio::test]
+    async fn test_get_valid_link() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let test_link = create_test_link();
+        let expired_link = LinkModel {
+            expiry: DbDateTime::from(Utc::now() - Duration::hours(1)),
+            ..create_test_link()
+        };
+
+        mock_db
+            .expect_select::<LinkModel>()
+            .with(eq(DbId::from(("links", "123"))))
+            .times(2)
+            .returning(move |_| Ok(Some(test_link.clone())));
+
+        mock_db
+            .expect_select::<LinkModel>()
+            .with(eq(DbId::from(("links", "expired"))))
+            .times(1)
+            .returning(move |_| Ok(Some(expired_link.clone())));
+
+        let link = LinkModel::get_valid_link(&mock_db, "123", LinkType::EmailReset)
+            .await
+            .unwrap();
+        assert_eq!(link.id, DbId::from(("links", "123")));
+
+        let result = LinkModel::get_valid_link(&mock_db, "123", LinkType::Unimplemented).await;
+        assert!(matches!(result, Err(MailerError::InvalidType)));
+
+        let result = LinkModel::get_valid_link(&mock_db, "expired", LinkType::EmailReset).await;
+        assert!(matches!(result, Err(MailerError::Expired)));
+    }
+
     #[cfg(any(feature = "client", feature = "group"))]
     #[tokio::test]
     async fn test_delete_link_by_user_and_type_success() {
@@ -462,12 +555,10 @@ mod tests {
         let test_link_type = test_link.link_type.clone();
 
         mock_db
-            .expect_query::<LinkModel>()
-            .withf(move |query: &str, bindings: &Option<serde_json::Value>| {
-                query.contains("DELETE links WHERE") && bindings.is_some()
-            })
+            .expect_delete_where()
+            .withf(|table, _| table == "links")
             .times(1)
-            .returning(move |_, _| Ok(vec![test_link.clone()]));
+            .returning(|_, _| Ok(1));
 
         let result =
             LinkModel::delete_link_by_user_and_type(&mock_db, test_user_id, test_link_type).await;
@@ -14,7 +14,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use kiro_database::DatabaseError;
+use kiro_database::{with_error_key, DatabaseError};
 use std::io;
 use tonic::Status;
 
@@ -74,9 +74,25 @@ impl From<StorageError> for DatabaseError {
     }
 }
 
+impl StorageError {
+    /// Returns the stable key of the error, used to localize its message
+    pub fn key(&self) -> &'static str {
+        match self {
+            StorageError::NotFound => "storage.not_found",
+            StorageError::InvalidConfig => "storage.invalid_config",
+            StorageError::ClientInitError => "storage.client_init_failed",
+            StorageError::InvalidKeyFormat => "storage.invalid_key_format",
+            StorageError::Database(e) => e.key(),
+            _ => "storage.error",
+        }
+    }
+}
+
 impl From<StorageError> for Status {
     fn from(error: StorageError) -> Self {
-        match error {
+        let key = error.key();
+
+        let status = match error {
             StorageError::NotFound => Status::not_found("Object not found in bucket"),
             #[cfg(feature = "aws")]
             StorageError::S3GetError(e) => Status::internal(format!("S3 get error: {}", e)),
@@ -94,6 +110,8 @@ impl From<StorageError> for Status {
             StorageError::InvalidKeyFormat => Status::invalid_argument("Invalid object key format"),
             StorageError::Database(e) => e.into(),
             StorageError::Internal(msg) => Status::internal(msg),
-        }
+        };
+
+        with_error_key(status, key)
     }
 }
@@ -26,7 +26,7 @@ mod utils;
 ///
 /// The mailer utils module provides utilities for mailing.
 #[cfg(feature = "aws")]
-pub use utils::{BucketS3, BucketS3Trait};
+pub use utils::{BucketS3, BucketS3Trait, MultipartUpload};
 
 /// # Error
 ///
@@ -16,7 +16,11 @@
 
 use std::sync::Arc;
 
-use aws_sdk_s3::{primitives::ByteStream, Client};
+use aws_sdk_s3::{
+    primitives::ByteStream,
+    types::{CompletedMultipartUpload, CompletedPart},
+    Client,
+};
 
 use clap::Parser;
 use futures::future::join_all;
@@ -55,6 +59,15 @@ struct Opt {
     verbose: bool,
 }
 
+/// # Multipart upload
+///
+/// The MultipartUpload struct is a struct that represents an object uploaded in parts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultipartUpload {
+    pub key: String,
+    pub upload_id: String,
+}
+
 /// # Bucket
 ///
 /// The bucket module is a module that provides utilities for buckets.
@@ -70,6 +83,20 @@ pub trait BucketS3Trait: Send + Sync {
     async fn put_object(
         &self, object: ByteStream, source_id: DbId, request_path: &str, name: &str,
     ) -> Result<String, StorageError>;
+
+    async fn create_multipart_upload(
+        &self, source_id: DbId, request_path: &str, name: &str,
+    ) -> Result<MultipartUpload, StorageError>;
+
+    async fn upload_part(
+        &self, upload: &MultipartUpload, part_number: i32, part: ByteStream,
+    ) -> Result<String, StorageError>;
+
+    async fn complete_multipart_upload(
+        &self, upload: &MultipartUpload, e_tags: Vec<String>,
+    ) -> Result<String, StorageError>;
+
+    async fn abort_multipart_upload(&self, upload: &MultipartUpload) -> Result<(), StorageError>;
 }
 
 #[async_trait::async_trait]
@@ -79,6 +106,29 @@ impl BucketS3Trait for BucketS3 {
     ) -> Result<String, StorageError> {
         self.put_object(object, source_id, request_path, name).await
     }
+
+    async fn create_multipart_upload(
+        &self, source_id: DbId, request_path: &str, name: &str,
+    ) -> Result<MultipartUpload, StorageError> {
+        self.create_multipart_upload(source_id, request_path, name)
+            .await
+    }
+
+    async fn upload_part(
+        &self, upload: &MultipartUpload, part_number: i32, part: ByteStream,
+    ) -> Result<String, StorageError> {
+        self.upload_part(upload, part_number, part).await
+    }
+
+    async fn complete_multipart_upload(
+        &self, upload: &MultipartUpload, e_tags: Vec<String>,
+    ) -> Result<String, StorageError> {
+        self.complete_multipart_upload(upload, e_tags).await
+    }
+
+    async fn abort_multipart_upload(&self, upload: &MultipartUpload) -> Result<(), StorageError> {
+        self.abort_multipart_upload(upload).await
+    }
 }
 
 impl BucketS3 {
@@ -212,6 +262,137 @@ impl BucketS3 {
         }
     }
 
+    /// # Create multipart upload
+    ///
+    /// The `create_multipart_upload` method starts the upload of an object in parts.
+    /// Every part but the last one must be at least 5 MiB.
+    ///
+    /// ```rust,ignore
+    /// let upload = BucketS3::new().await.create_multipart_upload(source_id, request_path, name).await?;
+    ///
+    /// println!("📤 Upload: {:?}", upload);
+    /// ```
+    pub async fn create_multipart_upload(
+        &self, source_id: DbId, request_path: &str, name: &str,
+    ) -> Result<MultipartUpload, StorageError> {
+        let key = format!("{}/{}_{}", &source_id.id, &request_path, &name);
+
+        let output = self
+            .client
+            .create_multipart_upload()
+            .bucket(&self.bucket)
+            .key(&key)
+            .send()
+            .await
+            .map_err(|e| StorageError::S3Error(e.to_string()))?;
+
+        let upload_id = output
+            .upload_id()
+            .ok_or_else(|| StorageError::S3Error("Missing upload id".to_string()))?
+            .to_string();
+
+        Ok(MultipartUpload { key, upload_id })
+    }
+
+    /// # Upload part
+    ///
+    /// The `upload_part` method uploads a part of a multipart upload and returns its ETag.
+    /// Part numbers start at 1.
+    ///
+    /// ```rust,ignore
+    /// let e_tag = BucketS3::new().await.upload_part(&upload, 1, part).await?;
+    ///
+    /// println!("🏷️ ETag: {:?}", e_tag);
+    /// ```
+    pub async fn upload_part(
+        &self, upload: &MultipartUpload, part_number: i32, part: ByteStream,
+    ) -> Result<String, StorageError> {
+        let output = self
+            .client
+            .upload_part()
+            .bucket(&self.bucket)
+            .key(&upload.key)
+            .upload_id(&upload.upload_id)
+            .part_number(part_number)
+            .body(part)
+            .send()
+            .await
+            .map_err(|e| StorageError::S3Error(e.to_string()))?;
+
+        output
+            .e_tag()
+            .map(str::to_string)
+            .ok_or_else(|| StorageError::S3Error("Missing part ETag".to_string()))
+    }
+
+    /// # Complete multipart upload
+    ///
+    /// The `complete_multipart_upload` method assembles the uploaded parts, in order,
+    /// and returns the URL of the object.
+    ///
+    /// ```rust,ignore
+    /// let url = BucketS3::new().await.complete_multipart_upload(&upload, e_tags).await?;
+    ///
+    /// println!("🔗 URL: {:?}", url);
+    /// ```
+    pub async fn complete_multipart_upload(
+        &self, upload: &MultipartUpload, e_tags: Vec<String>,
+    ) -> Result<String, StorageError> {
+        let parts = e_tags
+            .into_iter()
+            .zip(1..)
+            .map(|(e_tag, part_number)| {
+                CompletedPart::builder()
+                    .e_tag(e_tag)
+                    .part_number(part_number)
+                    .build()
+            })
+            .collect();
+
+        self.client
+            .complete_multipart_upload()
+            .bucket(&self.bucket)
+            .key(&upload.key)
+            .upload_id(&upload.upload_id)
+            .multipart_upload(
+                CompletedMultipartUpload::builder()
+                    .set_parts(Some(parts))
+                    .build(),
+            )
+            .send()
+            .await
+            .map_err(|e| StorageError::S3Error(e.to_string()))?;
+
+        Ok(format!(
+            "https://{}.s3.{}.amazonaws.com/{}",
+            self.bucket, self.region, upload.key
+        ))
+    }
+
+    /// # Abort multipart upload
+    ///
+    /// The `abort_multipart_upload` method discards the parts of an unfinished upload.
+    ///
+    /// ```rust,ignore
+    /// BucketS3::new().await.abort_multipart_upload(&upload).await?;
+    ///
+    /// println!("🗑️ Upload aborted");
+    /// ```
+    pub async fn abort_multipart_upload(
+        &self, upload: &MultipartUpload,
+    ) -> Result<(), StorageError> {
+        self.client
+            .abort_multipart_upload()
+            .bucket(&self.bucket)
+            .key(&upload.key)
+            .upload_id(&upload.upload_id)
+            .send()
+            .await
+            .map_err(|e| StorageError::S3Error(e.to_string()))?;
+
+        Ok(())
+    }
+
     /// # Prepare upload
     ///
     /// The `prepare_upload` method prepares an upload to the S3 bucket.
@@ -280,6 +461,52 @@ impl BucketS3 {
             &self.bucket, &self.region, &source_id.id, &request_path
         ))
     }
+
+    /// # Delete objects
+    ///
+    /// The `delete_objects` method deletes every object stored for a source.
+    ///
+    /// ```rust,ignore
+    /// let deleted = BucketS3::new().await.delete_objects(source_id).await?;
+    ///
+    /// println!("🗑️ Deleted objects: {:?}", deleted);
+    /// ```
+    pub async fn delete_objects(&self, source_id: DbId) -> Result<usize, StorageError> {
+        let prefix = format!("{}/", &source_id.id);
+        let mut deleted = 0;
+        let mut continuation_token = None;
+
+        loop {
+            let page = self
+                .client
+                .list_objects_v2()
+                .bucket(&self.bucket)
+                .prefix(&prefix)
+                .set_continuation_token(continuation_token)
+                .send()
+                .await
+                .map_err(|e| StorageError::S3Error(e.to_string()))?;
+
+            for object in page.contents() {
+                if let Some(key) = object.key() {
+                    self.client
+                        .delete_object()
+                        .bucket(&self.bucket)
+                        .key(key)
+                        .send()
+                        .await
+                        .map_err(StorageError::S3DeleteError)?;
+
+                    deleted += 1;
+                }
+            }
+
+            match page.next_continuation_token() {
+                Some(token) => continuation_token = Some(token.to_string()),
+                None => return Ok(deleted),
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -339,6 +566,16 @@ mod tests {
         assert!(url.is_err());
     }
 
+    #[tokio::test]
+    async fn test_bucket_s3_delete_objects() {
+        let bucket = BucketS3::new().await;
+        let source_id = DbId::from(("users", "test"));
+
+        let deleted = bucket.delete_objects(source_id).await;
+
+        assert!(deleted.is_err());
+    }
+
     #[tokio::test]
     async fn test_bucket_s3_put_multiple_objects() {
         let bucket = BucketS3::new().await;
@@ -21,7 +21,7 @@ pub mod aws;
 ///
 /// The bucket module is a module that provides utilities for buckets.
 #[cfg(feature = "aws")]
-pub use aws::{BucketS3, BucketS3Trait};
+pub use aws::{BucketS3, BucketS3Trait, MultipartUpload};
 
 // Expose MockMailerTrait when testing or when mock feature is enabled
 #[cfg(all(feature = "aws", any(test, feature = "mock")))]
@@ -0,0 +1,219 @@
+// cli.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::{Path, PathBuf};
+
+use kiro_client::{import_batch_size, TransferFormat, UserExportModel, UserExporter, UserImporter};
+use kiro_database::db_bridge::Database;
+use tokio::{
+    fs::File,
+    io::{AsyncReadExt, AsyncWriteExt},
+};
+
+use crate::error::ServerError;
+
+/// Size of the chunks read from the import file
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+const USAGE: &str = "Usage: kiro import-users <file> [--format csv|jsonl]\n       kiro export-users <file> [--format csv|jsonl] [--password-hashes]";
+
+/// # Command
+///
+/// Maintenance commands run instead of the server.
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    /// Imports the users of a CSV or JSON Lines file
+    ImportUsers {
+        path: PathBuf,
+        format: TransferFormat,
+    },
+    /// Exports all users to a CSV or JSON Lines file, with their password
+    /// hashes if requested
+    ExportUsers {
+        path: PathBuf,
+        format: TransferFormat,
+        password_hashes: bool,
+    },
+}
+
+impl Command {
+    /// # From args
+    ///
+    /// Parses the command line arguments, without the program name. Returns
+    /// `None` when no command is given, to run the server. The format defaults
+    /// to the extension of the file, the password hashes are only exported
+    /// with `--password-hashes`.
+    pub fn from_args(mut args: impl Iterator<Item = String>) -> Result<Option<Self>, ServerError> {
+        let Some(command) = args.next() else {
+            return Ok(None);
+        };
+
+        let path = args.next().map(PathBuf::from).ok_or_else(usage)?;
+
+        let mut format = None;
+        let mut password_hashes = false;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--format" if format.is_none() => format = Some(args.next().ok_or_else(usage)?),
+                "--password-hashes" if command == "export-users" => password_hashes = true,
+                _ => return Err(usage()),
+            }
+        }
+
+        let format = match format {
+            Some(format) => format,
+            None => extension(&path).ok_or_else(usage)?,
+        };
+        let format = format
+            .parse::<TransferFormat>()
+            .map_err(|e| ServerError::AnyhowError(e.to_string()))?;
+
+        match command.as_str() {
+            "import-users" => Ok(Some(Self::ImportUsers { path, format })),
+            "export-users" => Ok(Some(Self::ExportUsers {
+                path,
+                format,
+                password_hashes,
+            })),
+            _ => Err(usage()),
+        }
+    }
+
+    /// # Run
+    ///
+    /// Runs the command against the database. The import report is printed
+    /// as JSON, the export is recorded in the audit log without an actor.
+    pub async fn run(self, db: &Database) -> Result<(), ServerError> {
+        match self {
+            Self::ImportUsers { path, format } => {
+                let mut file = File::open(&path).await?;
+                let mut importer = UserImporter::new(db, format, import_batch_size());
+                let mut chunk = vec![0; READ_CHUNK_SIZE];
+
+                loop {
+                    let read = file.read(&mut chunk).await?;
+                    if read == 0 {
+                        break;
+                    }
+                    importer.feed(&chunk[..read]).await.map_err(anyhow)?;
+                }
+
+                let report = importer.finish().await.map_err(anyhow)?;
+                println!("{}", serde_json::to_string_pretty(&report).map_err(anyhow)?);
+            }
+            Self::ExportUsers {
+                path,
+                format,
+                password_hashes,
+            } => {
+                UserExportModel::record(db, None, format, password_hashes)
+                    .await
+                    .map_err(anyhow)?;
+
+                let mut file = File::create(&path).await?;
+                let mut exporter = UserExporter::new(db, format, password_hashes);
+
+                while let Some(chunk) = exporter.next_chunk().await.map_err(anyhow)? {
+                    file.write_all(&chunk).await?;
+                }
+                file.flush().await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the extension of a file, as a format name
+fn extension(path: &Path) -> Option<String> {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_string)
+}
+
+/// Returns the usage of the commands, as an error
+fn usage() -> ServerError {
+    ServerError::AnyhowError(USAGE.to_string())
+}
+
+/// Maps the errors of the client library
+fn anyhow(error: impl std::fmt::Display) -> ServerError {
+    ServerError::AnyhowError(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn test_no_command() {
+        assert_eq!(Command::from_args(args(&[])).unwrap(), None);
+    }
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(
+            Command::from_args(args(&["import-users", "users.jsonl"])).unwrap(),
+            Some(Command::ImportUsers {
+                path: PathBuf::from("users.jsonl"),
+                format: TransferFormat::Jsonl,
+            })
+        );
+    }
+
+    #[test]
+    fn test_format_flag() {
+        assert_eq!(
+            Command::from_args(args(&["export-users", "users.txt", "--format", "csv"])).unwrap(),
+            Some(Command::ExportUsers {
+                path: PathBuf::from("users.txt"),
+                format: TransferFormat::Csv,
+                password_hashes: false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_password_hashes_flag() {
+        assert_eq!(
+            Command::from_args(args(&["export-users", "users.csv", "--password-hashes"])).unwrap(),
+            Some(Command::ExportUsers {
+                path: PathBuf::from("users.csv"),
+                format: TransferFormat::Csv,
+                password_hashes: true,
+            })
+        );
+
+        // Only the export writes password hashes
+        assert!(
+            Command::from_args(args(&["import-users", "users.csv", "--password-hashes"])).is_err()
+        );
+    }
+
+    #[test]
+    fn test_invalid_command() {
+        assert!(Command::from_args(args(&["drop-users", "users.csv"])).is_err());
+        assert!(Command::from_args(args(&["import-users"])).is_err());
+        assert!(Command::from_args(args(&["import-users", "users"])).is_err());
+    }
+}
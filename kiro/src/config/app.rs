@@ -35,6 +35,13 @@ pub enum Environment {
 
 impl AppConfig {
     pub(crate) fn init() -> Result<Self, ServerError> {
+        // Keys the hashes replacing the data of users opting out of data collection
+        if get_env_or("PRIVACY_HASH_KEY", "").is_empty() {
+            return Err(ServerError::ServerStartup(
+                "PRIVACY_HASH_KEY is not set".to_string(),
+            ));
+        }
+
         Ok(Self {
             frontend_url: get_env_or("FRONT_CONNECT_URL", "http://localhost:5173"),
             environment: Environment::from_env(),
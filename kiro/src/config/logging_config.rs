@@ -179,6 +179,8 @@ pub struct LoggingConfig {
     pub public_endpoints: Vec<String>,
     /// Admin-only endpoints
     pub admin_endpoints: Vec<String>,
+    /// Endpoints reachable before accepting the current policy documents
+    pub consent_endpoints: Vec<String>,
     /// Minimum severity threshold for email notifications
     #[cfg(feature = "mailer")]
     pub email_severity_threshold: ErrorSeverity,
@@ -196,6 +198,27 @@ impl Default for LoggingConfig {
                 // Auth Service public endpoints
                 "/v1.AuthService/Login".to_string(),
                 "/v1.AuthService/Register".to_string(),
+                "/v1.AuthService/RestoreAccount".to_string(),
+                "/v1.AuthService/RequestReactivation".to_string(),
+                "/v1.AuthService/ReactivateAccount".to_string(),
+                "/v1.AuthService/ConfirmEmailChange".to_string(),
+                "/v1.AuthService/CancelEmailChange".to_string(),
+                "/v1.AuthService/VerifyEmail".to_string(),
+                // Auth HTTP public endpoints
+                "/auth/login".to_string(),
+                "/auth/register".to_string(),
+                "/auth/restore_account".to_string(),
+                "/auth/request_reactivation".to_string(),
+                "/auth/reactivate_account".to_string(),
+                "/auth/confirm_email_change".to_string(),
+                "/auth/cancel_email_change".to_string(),
+                "/auth/verify_email".to_string(),
+                // OAuth2 / OpenID Connect public endpoints
+                "/.well-known/openid-configuration".to_string(),
+                "/oauth/jwks".to_string(),
+                "/oauth/authorize".to_string(),
+                "/oauth/token".to_string(),
+                "/oauth/userinfo".to_string(),
                 // Payment Service public endpoints
                 "/v1.PaymentService/WebhookHandler".to_string(),
             ],
@@ -208,6 +231,28 @@ impl Default for LoggingConfig {
                 "/v1.AdminService/SendResetPassword".to_string(),
                 "/v1.AdminService/UpdateActivation".to_string(),
                 "/v1.AdminService/UpdateIsAdmin".to_string(),
+                "/v1.ClientService/ReactivateUser".to_string(),
+                "/v1.ClientService/ImportUsers".to_string(),
+                "/v1.ClientService/ExportUsers".to_string(),
+                "/v1.ClientService/CreateInvitation".to_string(),
+                "/user/reactivate_user".to_string(),
+                "/user/create_invitation".to_string(),
+                // OAuth2 client registration
+                "/oauth/clients".to_string(),
+            ],
+            consent_endpoints: vec![
+                // Client Service consent endpoints
+                "/v1.ClientService/AcceptPolicy".to_string(),
+                "/v1.ClientService/ListConsents".to_string(),
+                "/v1.ClientService/ReadUser".to_string(),
+                "/v1.ClientService/DeleteUser".to_string(),
+                "/v1.AuthService/Logout".to_string(),
+                // HTTP consent endpoints
+                "/user/accept_policy".to_string(),
+                "/user/list_consents".to_string(),
+                "/user/read_user".to_string(),
+                "/user/delete_user".to_string(),
+                "/auth/logout".to_string(),
             ],
             #[cfg(feature = "mailer")]
             email_severity_threshold: ErrorSeverity::High,
@@ -260,6 +305,9 @@ mod tests {
         assert!(config
             .admin_endpoints
             .contains(&"/v1.AdminService/ReadUsersAdmin".to_string()));
+        assert!(config
+            .consent_endpoints
+            .contains(&"/v1.ClientService/AcceptPolicy".to_string()));
         #[cfg(feature = "mailer")]
         assert_eq!(config.email_severity_threshold, ErrorSeverity::High);
     }
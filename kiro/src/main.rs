@@ -22,12 +22,23 @@ use dotenv::dotenv;
 use kiro_database::db_bridge::Database;
 use rustls::crypto;
 
+// The server runs on a single database backend
+#[cfg(all(feature = "surrealdb", feature = "postgres"))]
+compile_error!(
+    "The surrealdb and postgres features are exclusive, build the Postgres backend with --no-default-features"
+);
+
 #[cfg(feature = "surrealdb")]
 use kiro_database::SurrealDBRepo;
 
+#[cfg(feature = "postgres")]
+use kiro_database::PostgresRepo;
+
 #[cfg(not(any(feature = "surrealdb", feature = "postgres")))]
 use kiro_database::db_bridge::MockDatabaseOperations;
 
+#[cfg(feature = "import")]
+mod cli;
 mod config;
 mod error;
 mod middleware;
@@ -44,6 +55,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Load configuration
     let config = Config::init()?;
 
+    // Maintenance commands run instead of the server
+    #[cfg(feature = "import")]
+    let command = cli::Command::from_args(std::env::args().skip(1))?;
+
     if crypto::CryptoProvider::install_default(crypto::aws_lc_rs::default_provider()).is_err() {
         tracing::warn!("Failed to install default crypto provider");
     }
@@ -53,6 +68,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         telemetry::init_tracer()?;
     }
 
+    // Abort before serving when the configured signing key cannot be loaded
+    #[cfg(feature = "oauth")]
+    kiro_client::init_signing_keys();
+
     let addr = SocketAddr::new(IpAddr::from(Ipv6Addr::UNSPECIFIED), config.ports.https());
     let server = Server::new(addr, config.clone()).await?;
 
@@ -65,9 +84,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Database::Surreal(surrealdb.db)
     };
 
+    #[cfg(feature = "postgres")]
+    let db = {
+        let postgres = PostgresRepo::init().await?;
+        tracing::info!("🔧 Migrating database");
+        PostgresRepo::migrate(postgres.pool.clone()).await?;
+        Database::Postgres(postgres)
+    };
+
     #[cfg(not(any(feature = "surrealdb", feature = "postgres")))]
     let db = Database::Mock(MockDatabaseOperations::new());
 
+    #[cfg(feature = "import")]
+    if let Some(command) = command {
+        command.run(&db).await?;
+        return Ok(());
+    }
+
     // Initialize services
     // await_initialization(&db).await?;
 
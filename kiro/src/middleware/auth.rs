@@ -16,9 +16,12 @@
 
 use futures::future::BoxFuture;
 use http::{Request, Response, StatusCode};
-use kiro_client::SessionModel;
-use kiro_database::db_bridge::DatabaseOperations;
-use tonic::{metadata::MetadataMap, Status};
+use kiro_client::{
+    check_consent, get_cookie, keyed_status, verify_csrf, PolicyCache, SessionModel, UserModel,
+    SESSION_COOKIE,
+};
+use kiro_database::{db_bridge::DatabaseOperations, error_key};
+use tonic::{metadata::MetadataMap, Code, Status};
 use tower_http::auth::{AsyncAuthorizeRequest, AsyncRequireAuthorizationLayer};
 
 use crate::{config::LoggingConfig, tonic_auth, utils::grpc_utils::get_token_from_md};
@@ -28,6 +31,7 @@ use crate::{config::LoggingConfig, tonic_auth, utils::grpc_utils::get_token_from
 pub struct AuthMiddleware<DB> {
     db: DB,
     config: LoggingConfig,
+    policies: PolicyCache,
 }
 
 impl<DB> AuthMiddleware<DB>
@@ -39,6 +43,7 @@ where
         Self {
             db,
             config: LoggingConfig::default(),
+            policies: PolicyCache::new(),
         }
     }
 
@@ -55,12 +60,16 @@ where
             .or_else(|| headers.get(http::header::AUTHORIZATION))
             .or_else(|| headers.get("Authorization"));
 
-        if let Some(auth) = auth {
-            if let Ok(auth_str) = auth.to_str() {
-                if let Ok(value) = tonic::metadata::MetadataValue::try_from(auth_str) {
-                    if let Ok(key) = tonic::metadata::MetadataKey::from_bytes(b"authorization") {
-                        metadata.insert(key, value);
-                    }
+        // Fall back to the browser session cookie
+        let auth = match auth.and_then(|auth| auth.to_str().ok()) {
+            Some(auth_str) => Some(auth_str.to_string()),
+            None => Self::session_cookie(headers).map(|token| format!("Bearer {}", token)),
+        };
+
+        if let Some(auth_str) = auth {
+            if let Ok(value) = tonic::metadata::MetadataValue::try_from(auth_str.as_str()) {
+                if let Ok(key) = tonic::metadata::MetadataKey::from_bytes(b"authorization") {
+                    metadata.insert(key, value);
                 }
             }
         }
@@ -68,6 +77,40 @@ where
         metadata
     }
 
+    /// Returns the session cookie if the request is authenticated by cookie only
+    fn session_cookie(headers: &http::HeaderMap) -> Option<String> {
+        if headers.contains_key(http::header::AUTHORIZATION) {
+            return None;
+        }
+
+        get_cookie(headers, SESSION_COOKIE)
+    }
+
+    /// Rejects sessions whose account was disabled or removed
+    async fn check_account(&self, session: SessionModel) -> Result<SessionModel, Status> {
+        match self.db.select::<UserModel>(session.user_id.clone()).await {
+            Ok(Some(user)) if !user.activated => {
+                Err(keyed_status(Code::PermissionDenied, "account.disabled"))
+            }
+            Ok(Some(_)) => Ok(session),
+            Ok(None) => Err(keyed_status(Code::Unauthenticated, "session.invalid_token")),
+            Err(e) => Err(Status::internal(e.to_string())),
+        }
+    }
+
+    /// Rejects sessions whose user must accept new versions of the policy documents
+    async fn check_policies(
+        &self, session: SessionModel, path: &str,
+    ) -> Result<SessionModel, Status> {
+        if self.config.consent_endpoints.contains(&path.to_string()) {
+            return Ok(session);
+        }
+
+        check_consent(&self.db, &self.policies, session.user_id.clone()).await?;
+
+        Ok(session)
+    }
+
     /// Validates the session for a request
     async fn validate_session(&self, request: &Request<()>) -> Result<SessionModel, Status> {
         let path = request.uri().path();
@@ -80,17 +123,25 @@ where
             return Err(Status::ok("Public endpoint"));
         }
 
+        // Cookie authenticated requests must pass the double-submit CSRF check
+        if Self::session_cookie(request.headers()).is_some()
+            && !verify_csrf(request.method(), request.headers())
+        {
+            return Err(keyed_status(Code::PermissionDenied, "session.invalid_csrf"));
+        }
+
         let metadata = Self::http_headers_to_grpc_metadata(request.headers());
         let token = tonic_auth!(get_token_from_md(&metadata), "Token authentication error");
 
         match SessionModel::get_session(&self.db, token.clone()).await {
             Ok(Some(session)) => {
                 if self.config.admin_endpoints.contains(&path.to_string()) && !session.is_admin {
-                    return Err(Status::permission_denied("Admin privileges required"));
+                    return Err(keyed_status(Code::PermissionDenied, "auth.admin_required"));
                 }
-                Ok(session)
+                let session = self.check_account(session).await?;
+                self.check_policies(session, path).await
             }
-            Ok(None) => Err(Status::unauthenticated("Unauthorized: Invalid token")),
+            Ok(None) => Err(keyed_status(Code::Unauthenticated, "session.invalid_token")),
             Err(e) => Err(Status::internal(e.to_string())),
         }
     }
@@ -144,6 +195,7 @@ where
                     let status_code = match status.code() {
                         tonic::Code::Unauthenticated => StatusCode::UNAUTHORIZED,
                         tonic::Code::PermissionDenied => StatusCode::FORBIDDEN,
+                        tonic::Code::FailedPrecondition => StatusCode::PRECONDITION_FAILED,
                         tonic::Code::Internal => StatusCode::INTERNAL_SERVER_ERROR,
                         _ => StatusCode::UNAUTHORIZED,
                     };
@@ -158,6 +210,14 @@ where
                                 .unwrap()
                         })?;
 
+                    // gRPC clients read the precondition failure and the error key
+                    // from the status headers
+                    if status_code == StatusCode::PRECONDITION_FAILED
+                        || error_key(&status).is_some()
+                    {
+                        let _ = status.add_header(response.headers_mut());
+                    }
+
                     if status_code == StatusCode::UNAUTHORIZED {
                         response.headers_mut().insert(
                             http::header::WWW_AUTHENTICATE,
@@ -184,6 +244,7 @@ where
 mod tests {
     use super::*;
     use http::header::HeaderValue;
+    use kiro_client::{PolicyConsentModel, PolicyDocumentModel};
     use kiro_database::db_bridge::MockDatabaseOperations;
 
     #[tokio::test]
@@ -199,6 +260,130 @@ mod tests {
         assert!(metadata.contains_key("authorization"));
     }
 
+    #[tokio::test]
+    async fn test_http_headers_to_grpc_metadata_from_cookie() {
+        let mut headers = http::HeaderMap::new();
+        headers.insert(
+            http::header::COOKIE,
+            HeaderValue::from_static("kiro_session=cookie-token; kiro_csrf=csrf"),
+        );
+
+        let metadata =
+            AuthMiddleware::<MockDatabaseOperations>::http_headers_to_grpc_metadata(&headers);
+        assert_eq!(
+            metadata.get("authorization").unwrap().to_str().unwrap(),
+            "Bearer cookie-token"
+        );
+
+        // The authorization header takes precedence over the cookie
+        headers.insert(
+            "authorization",
+            HeaderValue::from_static("Bearer header-token"),
+        );
+
+        let metadata =
+            AuthMiddleware::<MockDatabaseOperations>::http_headers_to_grpc_metadata(&headers);
+        assert_eq!(
+            metadata.get("authorization").unwrap().to_str().unwrap(),
+            "Bearer header-token"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_session_rejects_missing_csrf() {
+        let middleware = AuthMiddleware::new(MockDatabaseOperations::new());
+
+        let request = Request::builder()
+            .method(http::Method::POST)
+            .uri("/user/language")
+            .header(
+                http::header::COOKIE,
+                "kiro_session=cookie-token; kiro_csrf=csrf",
+            )
+            .body(())
+            .unwrap();
+
+        let status = middleware.validate_session(&request).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+        assert_eq!(status.message(), "Invalid CSRF token");
+    }
+
+    #[tokio::test]
+    async fn test_check_account_rejects_disabled_user() {
+        let mut mock_db = MockDatabaseOperations::new();
+        let session = SessionModel::default();
+        let user_id = session.user_id.clone();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .withf(move |id| *id == user_id)
+            .times(1)
+            .returning(|_| {
+                Ok(Some(UserModel {
+                    activated: false,
+                    ..Default::default()
+                }))
+            });
+
+        let middleware = AuthMiddleware::new(mock_db);
+
+        let status = middleware.check_account(session).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+        assert_eq!(status.message(), "Account disabled");
+        assert_eq!(error_key(&status).as_deref(), Some("account.disabled"));
+    }
+
+    #[tokio::test]
+    async fn test_check_account_accepts_active_user() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_select::<UserModel>()
+            .times(1)
+            .returning(|_| Ok(Some(UserModel::default())));
+
+        let middleware = AuthMiddleware::new(mock_db);
+
+        assert!(middleware
+            .check_account(SessionModel::default())
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_check_policies_requires_acceptance() {
+        let mut mock_db = MockDatabaseOperations::new();
+
+        mock_db
+            .expect_read_where::<PolicyDocumentModel>()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![PolicyDocumentModel::default()]));
+
+        mock_db
+            .expect_read_by_field_thing::<PolicyConsentModel>()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        let middleware = AuthMiddleware::new(mock_db);
+
+        let status = middleware
+            .check_policies(SessionModel::default(), "/v1.ClientService/UpdateTheme")
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+        assert_eq!(status.message(), "Policy acceptance required");
+    }
+
+    #[tokio::test]
+    async fn test_check_policies_skips_consent_endpoints() {
+        let middleware = AuthMiddleware::new(MockDatabaseOperations::new());
+
+        assert!(middleware
+            .check_policies(SessionModel::default(), "/v1.ClientService/AcceptPolicy")
+            .await
+            .is_ok());
+    }
+
     #[test]
     fn test_auth_middleware_new() {
         let db = MockDatabaseOperations::new();
@@ -0,0 +1,202 @@
+// middleware/locale.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+use http::header::{ACCEPT_LANGUAGE, CONTENT_LENGTH, CONTENT_TYPE};
+use kiro_client::{localize_message, localize_status, resolve_language, SessionModel};
+use kiro_database::db_bridge::Database;
+use tonic::{Code, Status};
+
+/// Largest error body rewritten, larger bodies are sent as is
+const MAX_ERROR_BODY: usize = 64 * 1024;
+
+/// Localizes the error messages of a response
+///
+/// The language is the one of the session user's settings, else the one of the
+/// `accept-language` header. Errors are localized by their stable key, never by
+/// their message: gRPC errors carry it in their details, JSON errors of the
+/// HTTP routes in a `code` field next to their `error` message.
+///
+/// Errors sent in the trailers of a stream are not localized, they still carry
+/// the key when built from an error.
+pub async fn localize(State(db): State<Database>, request: Request, next: Next) -> Response {
+    let session = request.extensions().get::<SessionModel>().cloned();
+    let accept_language = request
+        .headers()
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from);
+
+    let mut response = next.run(request).await;
+
+    // gRPC errors returned by a handler are sent in the headers
+    if let Some(status) = Status::from_header_map(response.headers()) {
+        if status.code() == Code::Ok {
+            return response;
+        }
+
+        let language = resolve_language(&db, session.as_ref(), accept_language.as_deref()).await;
+        let _ = localize_status(status, language).add_header(response.headers_mut());
+
+        return response;
+    }
+
+    let is_json_error = (response.status().is_client_error()
+        || response.status().is_server_error())
+        && response
+            .headers()
+            .get(CONTENT_TYPE)
+            .is_some_and(|value| value.as_bytes().starts_with(b"application/json"));
+
+    if !is_json_error {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+
+    let Ok(bytes) = to_bytes(body, MAX_ERROR_BODY).await else {
+        return Response::from_parts(parts, Body::empty());
+    };
+
+    let Ok(mut json) = serde_json::from_slice::<serde_json::Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    let Some(key) = json.get("code").and_then(|code| code.as_str()) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    let language = resolve_language(&db, session.as_ref(), accept_language.as_deref()).await;
+
+    let Some(message) = localize_message(key, language) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    json["error"] = serde_json::json!(message);
+
+    parts.headers.remove(CONTENT_LENGTH);
+
+    Response::from_parts(parts, Body::from(json.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use axum::{
+        http::StatusCode, middleware::from_fn_with_state, response::IntoResponse, routing::get,
+        Json, Router,
+    };
+    use kiro_client::keyed_status;
+    use kiro_database::db_bridge::MockDatabaseOperations;
+    use tower::ServiceExt;
+
+    fn router() -> Router {
+        let db = Database::Mock(MockDatabaseOperations::new());
+
+        Router::new()
+            .route(
+                "/json",
+                get(|| async {
+                    (
+                        StatusCode::NOT_FOUND,
+                        Json(serde_json::json!({
+                            "error": "User not found",
+                            "code": "user.not_found",
+                        })),
+                    )
+                }),
+            )
+            .route(
+                "/grpc",
+                get(|| async {
+                    keyed_status(Code::NotFound, "user.not_found").into_http::<Body>()
+                }),
+            )
+            .route(
+                "/unkeyed",
+                get(|| async {
+                    (
+                        StatusCode::NOT_FOUND,
+                        Json(serde_json::json!({ "error": "User not found" })),
+                    )
+                }),
+            )
+            .route(
+                "/ok",
+                get(|| async {
+                    Json(serde_json::json!({ "error": "User not found" })).into_response()
+                }),
+            )
+            .layer(from_fn_with_state(db, localize))
+    }
+
+    fn request(path: &str) -> Request {
+        Request::builder()
+            .uri(path)
+            .header(ACCEPT_LANGUAGE, "fr-FR, fr;q=0.9, en;q=0.8")
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_localize_json_error() {
+        let response = router().oneshot(request("/json")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["error"], "Utilisateur introuvable");
+        assert_eq!(body["code"], "user.not_found");
+    }
+
+    #[tokio::test]
+    async fn test_localize_grpc_error() {
+        let response = router().oneshot(request("/grpc")).await.unwrap();
+
+        let status = Status::from_header_map(response.headers()).unwrap();
+        assert_eq!(status.code(), Code::NotFound);
+        assert_eq!(status.message(), "Utilisateur introuvable");
+        assert_eq!(
+            kiro_database::error_key(&status).as_deref(),
+            Some("user.not_found")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unkeyed_error_untouched() {
+        let response = router().oneshot(request("/unkeyed")).await.unwrap();
+
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["error"], "User not found");
+        assert!(body.get("code").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_successful_response_untouched() {
+        let response = router().oneshot(request("/ok")).await.unwrap();
+
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["error"], "User not found");
+    }
+}
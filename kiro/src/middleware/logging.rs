@@ -17,6 +17,8 @@
 use std::{fmt::Debug, time::Duration};
 
 use http::{HeaderMap, Request as HttpRequest, Response as HttpResponse};
+#[cfg(feature = "client")]
+use kiro_client::PrivacyPolicy;
 use tokio::task;
 use tower_http::{
     classify::{GrpcErrorsAsFailures, GrpcFailureClass, SharedClassifier},
@@ -32,6 +34,16 @@ use crate::utils::error_mailer::ErrorMailer;
 
 const AUTH_HEADER: &str = "authorization";
 
+/// Returns the headers of a request or response as they may be logged
+///
+/// Credentials and client addresses are redacted by the privacy policy.
+fn loggable_headers(headers: &HeaderMap) -> HeaderMap {
+    #[cfg(feature = "client")]
+    return PrivacyPolicy::redact_headers(headers);
+    #[cfg(not(feature = "client"))]
+    headers.clone()
+}
+
 /// A type alias for the complete tracing layer configuration
 pub type GrpcTraceLayer = TraceLayer<
     SharedClassifier<GrpcErrorsAsFailures>,
@@ -206,7 +218,7 @@ impl<B> OnRequest<B> for LogOnRequest {
             path = %path,
             service_name = %service_name,
             method_name = %method_name,
-            headers = ?request.headers(),
+            headers = ?loggable_headers(request.headers()),
             environment = ?self.config.app.environment,
             "Incoming request to {}/{}",
             service_name,
@@ -236,7 +248,7 @@ impl<B> OnResponse<B> for LogOnResponse {
             "{:?} {} {:?} {:?}",
             response.version(),
             response.status(),
-            response.headers(),
+            loggable_headers(response.headers()),
             latency
         );
     }
@@ -385,6 +397,7 @@ mod tests {
     use http::HeaderValue;
 
     fn create_test_config() -> Config {
+        std::env::set_var("PRIVACY_HASH_KEY", "privacy_hash_key");
         Config::init().unwrap()
     }
 
@@ -420,4 +433,18 @@ mod tests {
         let span = tracing::info_span!("test");
         logger.log_auth_status(&headers, &span, "/test/path");
     }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_loggable_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTH_HEADER, HeaderValue::from_static("Bearer token123"));
+        headers.insert("x-real-ip", HeaderValue::from_static("203.0.113.195"));
+        headers.insert("te", HeaderValue::from_static("trailers"));
+
+        let logged = format!("{:?}", loggable_headers(&headers));
+        assert!(!logged.contains("token123"));
+        assert!(!logged.contains("203.0.113.195"));
+        assert!(logged.contains("trailers"));
+    }
 }
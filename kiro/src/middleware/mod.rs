@@ -20,6 +20,12 @@
 #[cfg(feature = "client")]
 pub mod auth;
 
+/// # Locale Middleware
+///
+/// The locale module localizes the error messages of responses.
+#[cfg(feature = "client")]
+pub mod locale;
+
 /// # Logging Middleware
 ///
 /// The logging module provides middleware for logging requests and responses.
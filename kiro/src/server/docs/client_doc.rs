@@ -23,30 +23,60 @@ use utoipa::OpenApi;
         kiro_client::login::login,
         kiro_client::logout::logout,
         kiro_client::register::register,
+        kiro_client::restore_account::restore_account,
         // # User
+        kiro_client::accept_policy::accept_policy,
+        kiro_client::create_invitation::create_invitation,
         kiro_client::delete_user::delete_user,
         kiro_client::disable_user::disable_user,
+        kiro_client::get_changes_since::get_changes_since,
+        kiro_client::list_consents::list_consents,
+        kiro_client::list_emails::list_emails,
+        kiro_client::reactivate_user::reactivate_user,
         kiro_client::read_user::read_user,
+        kiro_client::remove_email::remove_email,
+        kiro_client::set_primary_email::set_primary_email,
         kiro_client::update_email::update_email,
         kiro_client::update_language::update_language,
         kiro_client::update_notifications::update_notifications,
         kiro_client::update_password::update_password,
         kiro_client::update_privacy::update_privacy,
+        kiro_client::update_profile::update_profile,
         kiro_client::update_security::update_security,
+        kiro_client::update_settings::update_settings,
         kiro_client::update_theme::update_theme,
     ),
     components(
         schemas(
             // # Authentication
             kiro_api::auth::v1::AuthRequest,
+            kiro_api::auth::v1::AcceptedPolicy,
             kiro_api::auth::v1::Session,
             // # User
             kiro_api::client::v1::User,
+            kiro_api::client::v1::AcceptPolicyRequest,
+            kiro_api::client::v1::Consent,
+            kiro_api::client::v1::ListConsentsResponse,
+            kiro_api::client::v1::CreateInvitationRequest,
+            kiro_api::client::v1::Invitation,
+            kiro_api::client::v1::AddEmailRequest,
+            kiro_api::client::v1::UserEmail,
+            kiro_api::client::v1::ListEmailsResponse,
+            kiro_api::client::v1::RemoveEmailRequest,
+            kiro_api::client::v1::SetPrimaryEmailRequest,
+            kiro_api::client::v1::PolicyDocument,
+            kiro_api::client::v1::Change,
+            kiro_api::client::v1::Group,
+            kiro_api::client::v1::GetChangesSinceRequest,
+            kiro_api::client::v1::GetChangesSinceResponse,
+            kiro_api::client::v1::ReactivateUserRequest,
             kiro_api::client::v1::UpdateEmailRequest,
             kiro_api::client::v1::UpdateLanguageRequest,
             kiro_api::client::v1::UpdateNotificationsRequest,
             kiro_api::client::v1::UpdatePasswordRequest,
             kiro_api::client::v1::UpdatePrivacyRequest,
+            kiro_api::client::v1::UpdateProfileRequest,
+            kiro_api::client::v1::UpdateSettingsRequest,
             kiro_api::client::v1::UpdateThemeRequest,
         )
     ),
@@ -16,9 +16,25 @@
 
 #[cfg(feature = "client")]
 mod client_doc;
+#[cfg(feature = "oauth")]
+mod oauth_doc;
+#[cfg(feature = "scim")]
+mod scim_doc;
 
 /// # Client Swagger Documentation
 ///
 /// The client swagger documentation module provides the OpenAPI documentation for the client service.
 #[cfg(feature = "client")]
 pub use client_doc::ClientDoc;
+
+/// # OAuth Swagger Documentation
+///
+/// The oauth swagger documentation module provides the OpenAPI documentation for the identity provider.
+#[cfg(feature = "oauth")]
+pub use oauth_doc::OAuthDoc;
+
+/// # SCIM Swagger Documentation
+///
+/// The scim swagger documentation module provides the OpenAPI documentation for user provisioning.
+#[cfg(feature = "scim")]
+pub use scim_doc::ScimDoc;
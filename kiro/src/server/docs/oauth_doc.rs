@@ -0,0 +1,52 @@
+// server/docs/oauth_doc.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use utoipa::OpenApi;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        // # OpenID Connect
+        kiro_client::discovery::discovery,
+        kiro_client::jwks::jwks,
+        kiro_client::userinfo::userinfo,
+        // # OAuth2
+        kiro_client::authorize::authorize,
+        kiro_client::consent::grant_consent,
+        kiro_client::consent::revoke_consent,
+        kiro_client::token::token,
+        kiro_client::register_client::register_client,
+    ),
+    components(
+        schemas(
+            kiro_client::GrantType,
+            kiro_client::consent::ConsentRequest,
+            kiro_client::token::TokenRequest,
+            kiro_client::token::TokenResponse,
+            kiro_client::register_client::RegisterClientRequest,
+            kiro_client::register_client::RegisterClientResponse,
+        )
+    ),
+    tags(
+        (name = "oauth", description = "OAuth2 / OpenID Connect identity provider endpoints"),
+    ),
+    info(
+        title = "Kiro Identity Provider API",
+        version = "0.0.2",
+        description = "OAuth2 / OpenID Connect API for Kiro",
+    )
+)]
+pub struct OAuthDoc;
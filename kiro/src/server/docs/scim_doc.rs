@@ -0,0 +1,61 @@
+// server/docs/scim_doc.rs
+//
+// Copyright Charlie Cohen <linzellart@gmail.com>
+//
+// Licensed under the GNU General Public License, Version 3.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.gnu.org/licenses/gpl-3.0.html
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use utoipa::OpenApi;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        // # Service provider
+        kiro_client::scim::service_provider::service_provider_config,
+        // # Users
+        kiro_client::scim::users::list_users,
+        kiro_client::scim::users::get_user,
+        kiro_client::scim::users::create_user,
+        kiro_client::scim::users::replace_user,
+        kiro_client::scim::users::patch_user,
+        kiro_client::scim::users::delete_user,
+        // # Groups
+        kiro_client::scim::groups::list_groups,
+        kiro_client::scim::groups::get_group,
+        kiro_client::scim::groups::create_group,
+        kiro_client::scim::groups::replace_group,
+        kiro_client::scim::groups::patch_group,
+        kiro_client::scim::groups::delete_group,
+    ),
+    components(
+        schemas(
+            kiro_client::scim::resources::ScimMeta,
+            kiro_client::scim::resources::ScimEmail,
+            kiro_client::scim::resources::ScimMember,
+            kiro_client::scim::resources::ScimUser,
+            kiro_client::scim::resources::ScimUserRequest,
+            kiro_client::scim::resources::ScimGroup,
+            kiro_client::scim::resources::ScimGroupRequest,
+            kiro_client::scim::resources::PatchOperation,
+            kiro_client::scim::resources::PatchRequest,
+        )
+    ),
+    tags(
+        (name = "scim", description = "SCIM 2.0 user and group provisioning endpoints"),
+    ),
+    info(
+        title = "Kiro Provisioning API",
+        version = "0.0.2",
+        description = "SCIM 2.0 API for Kiro",
+    )
+)]
+pub struct ScimDoc;
@@ -52,6 +52,18 @@ impl Server {
     pub async fn run(
         self, db: Database, config: crate::config::Config,
     ) -> Result<(), crate::error::ServerError> {
+        // Spawn account purge job
+        #[cfg(feature = "client")]
+        tokio::spawn(kiro_client::run_purge_job(db.clone()));
+
+        // Spawn change log compaction job
+        #[cfg(feature = "client")]
+        tokio::spawn(kiro_client::run_change_compaction_job(db.clone()));
+
+        // Spawn data retention job
+        #[cfg(feature = "client")]
+        tokio::spawn(kiro_client::run_retention_job(db.clone()));
+
         let app = setup::create_app(db, config.clone()).await?;
 
         // Create shutdown future
@@ -36,17 +36,25 @@ use utoipa_swagger_ui::SwaggerUi;
 
 #[cfg(any(feature = "client", feature = "documentation"))]
 use crate::server::docs::ClientDoc;
+#[cfg(all(feature = "oauth", feature = "documentation"))]
+use crate::server::docs::OAuthDoc;
+#[cfg(all(feature = "scim", feature = "documentation"))]
+use crate::server::docs::ScimDoc;
 use crate::{config::CertificateConfig, server::certificate::CertificateManager};
 
+#[cfg(feature = "oauth")]
+use kiro_client::oauth_routes;
+#[cfg(feature = "scim")]
+use kiro_client::scim_routes;
 #[cfg(feature = "client")]
 use kiro_client::{
     auth_routes, user_routes, AuthService, AuthServiceServer, ClientService, ClientServiceServer,
 };
 
-#[cfg(feature = "client")]
-use crate::middleware::auth::auth_layer;
 #[cfg(feature = "tracing")]
 use crate::middleware::logging::trace_layer;
+#[cfg(feature = "client")]
+use crate::middleware::{auth::auth_layer, locale::localize};
 
 use super::{health, Database};
 
@@ -94,6 +102,19 @@ pub async fn create_app(
     let mut routes_builder = setup_routes(db.clone()).await?.routes().into_axum_router();
     let client_openapi = ClientDoc::openapi();
 
+    // HTTP routes are nested before the auth layer so cookie sessions are resolved
+    #[cfg(feature = "client")]
+    {
+        routes_builder = routes_builder
+            .nest("/auth", auth_routes(db.clone()))
+            .nest("/user", user_routes(db.clone()));
+    }
+
+    #[cfg(feature = "oauth")]
+    {
+        routes_builder = routes_builder.merge(oauth_routes(db.clone()));
+    }
+
     // Add governors
     #[cfg(feature = "governors")]
     {
@@ -110,9 +131,18 @@ pub async fn create_app(
         });
     }
 
+    // The locale layer runs after the auth layer, to read the language of the session user
     #[cfg(feature = "client")]
     {
-        routes_builder = routes_builder.layer(auth_layer(db.clone()));
+        routes_builder = routes_builder
+            .layer(axum::middleware::from_fn_with_state(db.clone(), localize))
+            .layer(auth_layer(db.clone()));
+    }
+
+    // SCIM routes are merged after the auth layer, they authenticate with the provisioning token
+    #[cfg(feature = "scim")]
+    {
+        routes_builder = routes_builder.merge(scim_routes(db.clone()));
     }
 
     #[cfg(feature = "tracing")]
@@ -122,8 +152,15 @@ pub async fn create_app(
 
     #[cfg(all(feature = "documentation", feature = "client"))]
     {
-        routes_builder = routes_builder
-            .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/client.json", client_openapi));
+        let swagger_ui = SwaggerUi::new("/swagger-ui").url("/api-docs/client.json", client_openapi);
+
+        #[cfg(feature = "oauth")]
+        let swagger_ui = swagger_ui.url("/api-docs/oauth.json", OAuthDoc::openapi());
+
+        #[cfg(feature = "scim")]
+        let swagger_ui = swagger_ui.url("/api-docs/scim.json", ScimDoc::openapi());
+
+        routes_builder = routes_builder.merge(swagger_ui);
     }
 
     routes_builder = routes_builder
@@ -131,13 +168,6 @@ pub async fn create_app(
         .route("/health", get(health::health_check))
         .route("/", get(|| async { "Hello, World!" }));
 
-    #[cfg(feature = "client")]
-    {
-        routes_builder = routes_builder
-            .nest("/auth", auth_routes(db.clone()))
-            .nest("/user", user_routes(db));
-    }
-
     Ok(routes_builder)
 }
 
@@ -149,6 +179,8 @@ fn setup_cors(config: crate::config::Config) -> Result<CorsLayer, crate::error::
             AUTHORIZATION,
             ACCEPT,
             CONTENT_TYPE,
+            HeaderName::from_static("x-csrf-token"),
+            HeaderName::from_static("x-captcha-token"),
             HeaderName::from_static("grpc-status"),
             HeaderName::from_static("grpc-message"),
             HeaderName::from_static("grpc-encoding"),
@@ -180,6 +212,7 @@ fn setup_cors(config: crate::config::Config) -> Result<CorsLayer, crate::error::
         .expose_headers(vec![
             HeaderName::from_static("grpc-status"),
             HeaderName::from_static("grpc-message"),
+            HeaderName::from_static("grpc-status-details-bin"),
             HeaderName::from_static("grpc-encoding"),
             HeaderName::from_static("grpc-accept-encoding"),
         ]))
@@ -76,6 +76,14 @@ pub fn init_tracer() -> Result<(), ServerError> {
         .with_metadata(metadata.clone())
         .with_channel(channel.clone());
 
+    let resource = opentelemetry_sdk::Resource::new(vec![
+        opentelemetry::KeyValue::new(
+            "service.name",
+            format!("kiro/api-client-{}", get_env_or("ENVIRONMENT", "DEV")),
+        ),
+        opentelemetry::KeyValue::new("service.version", version!("v")),
+    ]);
+
     let tracer_provider = opentelemetry_otlp::new_pipeline()
         .tracing()
         .with_exporter(exporter)
@@ -86,18 +94,26 @@ pub fn init_tracer() -> Result<(), ServerError> {
                 .with_max_events_per_span(64)
                 .with_max_attributes_per_span(16)
                 .with_max_events_per_span(16)
-                .with_resource(opentelemetry_sdk::Resource::new(vec![
-                    opentelemetry::KeyValue::new(
-                        "service.name",
-                        format!("kiro/api-client-{}", get_env_or("ENVIRONMENT", "DEV")),
-                    ),
-                    opentelemetry::KeyValue::new("service.version", version!("v")),
-                ])),
+                .with_resource(resource.clone()),
         )
         .install_batch(Tokio)?;
 
     global::set_tracer_provider(tracer_provider.clone());
 
+    // Events with `monotonic_counter.` fields, like the retention job reports, become metrics
+    let meter_provider = opentelemetry_otlp::new_pipeline()
+        .metrics(Tokio)
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_metadata(metadata)
+                .with_channel(channel),
+        )
+        .with_resource(resource)
+        .build()?;
+
+    global::set_meter_provider(meter_provider.clone());
+
     tracing::trace!(target: "relay", "✅ Successfully initialized trace provider on tokio runtime");
 
     let filter = filter::Targets::new()
@@ -107,6 +123,7 @@ pub fn init_tracer() -> Result<(), ServerError> {
     let dispatch: Dispatch = tracing_subscriber::registry()
         .with(filter)
         .with(tracing_opentelemetry::layer().with_tracer(tracer_provider.tracer("relay")))
+        .with(tracing_opentelemetry::MetricsLayer::new(meter_provider))
         .with(
             tracing_subscriber::fmt::layer()
                 .with_target(true)